@@ -0,0 +1,89 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use log_analyzer::cli::ErrorsSortBy;
+use log_analyzer::comparator::LogFilter;
+use log_analyzer::config::default_config;
+use log_analyzer::errors::{ErrorsOptions, analyze_errors_with_config, format_errors_json};
+use log_analyzer::{LogEntry, parse_log_entry};
+use std::hint::black_box;
+
+const ENTRIES: usize = 200_000;
+
+fn synthetic_logs() -> Vec<LogEntry> {
+    (0..ENTRIES)
+        .map(|i| {
+            let (level, message) = match i % 4 {
+                0 => (
+                    "ERROR",
+                    format!(
+                        "Render with id \"{i:08x}\" failed at https://example.test/r/{i} after {} ms",
+                        i % 900
+                    ),
+                ),
+                1 => (
+                    "WARN ",
+                    format!("Retrying request [0--{i:012x}] job_id={i:010} attempt {}", i % 3),
+                ),
+                2 => (
+                    "ERROR",
+                    format!(
+                        "Session 123e4567-e89b-12d3-a456-{i:012} timed out at 2026-01-01T00:00:{:02}.000Z",
+                        i % 60
+                    ),
+                ),
+                _ => ("INFO ", format!("Tick {i}")),
+            };
+            let line = format!(
+                "core (manager-{}/eyes-{}) | 2026-01-01T00:{:02}:{:02}.{:03}Z [{level}] {message}",
+                i % 8,
+                i % 97,
+                (i / 60_000) % 60,
+                (i / 1_000) % 60,
+                i % 1_000
+            );
+            parse_log_entry(&line, i + 1).expect("synthetic line parses")
+        })
+        .collect()
+}
+
+fn options(threads: usize) -> ErrorsOptions {
+    ErrorsOptions {
+        top_n: 10,
+        include_warn: true,
+        show_sessions: true,
+        sort_by: ErrorsSortBy::Count,
+        file_count: 1,
+        suppressed_patterns: Vec::new(),
+        raw_limit: None,
+        threads,
+        summary_only: false,
+    }
+}
+
+fn errors_clustering(c: &mut Criterion) {
+    let logs = synthetic_logs();
+    let filter = LogFilter::new();
+    let config = default_config();
+
+    let sequential = analyze_errors_with_config(&logs, &filter, config, &options(1));
+    let parallel = analyze_errors_with_config(&logs, &filter, config, &options(0));
+    assert_eq!(
+        format_errors_json(&sequential, &options(1)),
+        format_errors_json(&parallel, &options(0)),
+        "sharded clustering must not change the report"
+    );
+
+    let mut group = c.benchmark_group("errors_clustering");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(ENTRIES as u64));
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    for threads in [1, cores.max(2)] {
+        group.bench_with_input(BenchmarkId::new("threads", threads), &threads, |b, &n| {
+            let options = options(n);
+            b.iter(|| analyze_errors_with_config(black_box(&logs), &filter, config, &options));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, errors_clustering);
+criterion_main!(benches);
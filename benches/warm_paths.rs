@@ -0,0 +1,141 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use log_analyzer::config::default_config;
+use log_analyzer::errors::normalize_message_pattern;
+use log_analyzer::parser::extract_json;
+use log_analyzer::{compare_json, parse_log_entry};
+use serde_json::{Value, json};
+use std::hint::black_box;
+
+const LINES: [(&str, &str); 5] = [
+    (
+        "request_send",
+        r#"core-universal (manager-ufg-1/eyes-ufg-2) | 2026-01-01T00:00:00.000Z [INFO ] Request "openEyes" [0--4f1c2a7e-9b1d-4c3e-8f2a-1d2e3f4a5b6c] will be sent to the address "[POST]https://eyesapi.example.test/api/sessions/running" with body {"appName":"demo","testName":"login","viewportSize":{"width":1280,"height":800},"properties":[{"name":"branch","value":"main"}]}"#,
+    ),
+    (
+        "request_receive",
+        r#"core-universal (manager-ufg-1/eyes-ufg-2) | 2026-01-01T00:00:01.250Z [INFO ] Request "openEyes" [0--4f1c2a7e-9b1d-4c3e-8f2a-1d2e3f4a5b6c] finished successfully with body {"statusCode":201,"id":"00000251739","isNew":false}"#,
+    ),
+    (
+        "event",
+        r#"socket (manager-ufg-1) | 2026-01-01T00:00:02.000Z [INFO ] Emit event of type "Core.checkAndClose" with payload {"name":"Core.checkAndClose","key":"a1b2c3","payload":{"settings":{"fully":true,"matchLevel":"Strict"}}}"#,
+    ),
+    (
+        "command",
+        r#"core-universal (manager-ufg-1/eyes-ufg-2/check-ufg-3) | 2026-01-01T00:00:03.000Z [INFO ] Command "check" is called with settings {"name":"home","fully":true,"region":{"x":0,"y":0,"width":1280,"height":4000}}"#,
+    ),
+    (
+        "generic",
+        "core-universal (manager-ufg-1/eyes-ufg-2) | 2026-01-01T00:00:04.000Z [WARN ] Render 8f2a1d2e took longer than expected, retrying",
+    ),
+];
+
+/// Nested object `depth` levels deep with `width` keys per level; `seed` varies the leaves
+fn deep_structure(depth: usize, width: usize, seed: u64) -> Value {
+    if depth == 0 {
+        return json!({ "text": format!("node-{seed}"), "visible": seed.is_multiple_of(2) });
+    }
+    let children: serde_json::Map<String, Value> = (0..width)
+        .map(|i| {
+            (
+                format!("child{i}"),
+                deep_structure(depth - 1, width, seed + i as u64),
+            )
+        })
+        .collect();
+    json!({ "tag": "div", "attributes": { "class": "box" }, "children": children })
+}
+
+fn large_payload_line(elements: usize) -> String {
+    let renders: Vec<Value> = (0..elements)
+        .map(|i| {
+            let browser = ["chrome", "firefox", "safari"][i % 3];
+            json!({
+                "renderId": format!("r-{i:06}"),
+                "browser": browser,
+                "size": { "width": 800 + i % 7 * 64, "height": 600 },
+                "selectorRegions": [{ "x": i, "y": i * 2, "width": 10, "height": 10 }],
+            })
+        })
+        .collect();
+    format!(
+        "core-universal (manager-ufg-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"render\" [0--bench] will be sent with body {}",
+        json!({ "renders": renders })
+    )
+}
+
+fn parse_lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_log_entry");
+    for (name, line) in LINES {
+        parse_log_entry(line, 1).expect("bench line parses");
+        group.throughput(Throughput::Bytes(line.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), line, |b, line| {
+            b.iter(|| parse_log_entry(black_box(line), 1));
+        });
+    }
+    group.finish();
+}
+
+fn extract_payloads(c: &mut Criterion) {
+    let indicators = &default_config().parser.json_indicators;
+    let mut group = c.benchmark_group("extract_json");
+    group.sample_size(20);
+    for elements in [100, 1_000, 10_000] {
+        let line = large_payload_line(elements);
+        assert!(extract_json(&line, indicators).is_some());
+        group.throughput(Throughput::Bytes(line.len() as u64));
+        group.bench_with_input(BenchmarkId::new("renders", elements), &line, |b, line| {
+            b.iter(|| extract_json(black_box(line), indicators));
+        });
+    }
+    group.finish();
+}
+
+fn compare_deep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compare_json");
+    for depth in [4, 6, 8] {
+        let before = deep_structure(depth, 3, 0);
+        let after = deep_structure(depth, 3, 1);
+        assert!(!compare_json(&before, &after).is_empty());
+        group.bench_with_input(
+            BenchmarkId::new("depth", depth),
+            &(before, after),
+            |b, (before, after)| {
+                b.iter(|| compare_json(black_box(before), black_box(after)));
+            },
+        );
+    }
+    group.finish();
+}
+
+fn normalize_errors(c: &mut Criterion) {
+    let messages = [
+        (
+            "ids_and_urls",
+            "Render with id \"8f2a1d2e-9b1d-4c3e-8f2a-1d2e3f4a5b6c\" failed at https://ufg.example.test/render/123456789?x=1 after 1532 ms",
+        ),
+        (
+            "timestamps",
+            "Session [0--4f1c2a7e] timed out at 2026-01-01T00:00:59.123Z (started 10:15:30.500)",
+        ),
+        (
+            "plain",
+            "Failed to close the batch because the server is unavailable",
+        ),
+    ];
+    let mut group = c.benchmark_group("normalize_message_pattern");
+    for (name, message) in messages {
+        group.bench_with_input(BenchmarkId::from_parameter(name), message, |b, message| {
+            b.iter(|| normalize_message_pattern(black_box(message)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    parse_lines,
+    extract_payloads,
+    compare_deep,
+    normalize_errors
+);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+use crate::config::{AnalyzerConfig, SessionInsights, analyze_sessions};
+use crate::parser::LogEntry;
+use std::sync::OnceLock;
+
+/// Parsed entries plus the state derived from them that several analyses need.
+///
+/// Build one per invocation and pass it to every analysis run over the same entries,
+/// so `errors`, `perf --fairness` and `trace` resolve sessions from
+/// the same hierarchy instead of each re-deriving it.
+pub struct AnalysisContext<'a> {
+    logs: &'a [LogEntry],
+    config: &'a AnalyzerConfig,
+    sessions: OnceLock<SessionInsights>,
+}
+
+impl<'a> AnalysisContext<'a> {
+    pub fn new(logs: &'a [LogEntry], config: &'a AnalyzerConfig) -> Self {
+        Self {
+            logs,
+            config,
+            sessions: OnceLock::new(),
+        }
+    }
+
+    pub fn logs(&self) -> &'a [LogEntry] {
+        self.logs
+    }
+
+    pub fn config(&self) -> &'a AnalyzerConfig {
+        self.config
+    }
+
+    /// Session hierarchy over every parsed entry (filters do not apply), built on first use
+    pub fn sessions(&self) -> &SessionInsights {
+        self.sessions
+            .get_or_init(|| analyze_sessions(self.logs, self.config))
+    }
+
+    /// Whether `sessions()` has been computed yet
+    pub fn has_sessions(&self) -> bool {
+        self.sessions.get().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_are_built_once_and_shared() {
+        let logs: Vec<LogEntry> = [
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Command \"openEyes\" is called with settings {}",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [INFO ] Command \"close\" is called with settings {}",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, line)| crate::parser::parse_log_entry(line, i + 1).expect("line parses"))
+        .collect();
+        let context = AnalysisContext::new(&logs, crate::config::default_config());
+
+        assert!(!context.has_sessions());
+        let first = context.sessions() as *const SessionInsights;
+        assert!(context.has_sessions());
+        assert!(std::ptr::eq(first, context.sessions()));
+    }
+}
@@ -0,0 +1,288 @@
+use crate::comparator::LogFilter;
+use crate::config::AnalyzerConfig;
+use crate::parser::LogEntry;
+use crate::perf_analyzer::{PerfAnalysisResults, TimedOperation, analyze_performance_with_config};
+use chrono::{DateTime, Local};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Timestamp ordering of one entry stream, observed in source order
+#[derive(Debug, Clone, Default)]
+pub struct ChronologyStats {
+    pub entries: usize,
+    /// Entries stamped earlier than an entry that precedes them
+    pub out_of_order: usize,
+    pub max_backward_ms: i64,
+    pub max_backward_line: Option<usize>,
+    latest: Option<DateTime<Local>>,
+}
+
+impl ChronologyStats {
+    fn observe(&mut self, log: &LogEntry) {
+        self.entries += 1;
+        match self.latest {
+            Some(latest) if log.timestamp < latest => {
+                self.out_of_order += 1;
+                let backward_ms = (latest - log.timestamp).num_milliseconds();
+                if backward_ms > self.max_backward_ms || self.max_backward_line.is_none() {
+                    self.max_backward_ms = backward_ms;
+                    self.max_backward_line = Some(log.source_line_number);
+                }
+            }
+            _ => self.latest = Some(log.timestamp),
+        }
+    }
+
+    pub fn out_of_order_pct(&self) -> f64 {
+        if self.entries == 0 {
+            0.0
+        } else {
+            self.out_of_order as f64 / self.entries as f64 * 100.0
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "entries": self.entries,
+            "out_of_order": self.out_of_order,
+            "out_of_order_pct": self.out_of_order_pct(),
+            "max_backward_ms": self.max_backward_ms,
+            "max_backward_line": self.max_backward_line,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FileChronology {
+    pub file: String,
+    pub stats: ChronologyStats,
+    pub components: BTreeMap<String, ChronologyStats>,
+}
+
+impl FileChronology {
+    /// Components with reordered entries, most reordered first
+    pub fn reordered_components(&self) -> Vec<(&String, &ChronologyStats)> {
+        let mut components: Vec<_> = self
+            .components
+            .iter()
+            .filter(|(_, stats)| stats.out_of_order > 0)
+            .collect();
+        components.sort_by(|a, b| b.1.out_of_order.cmp(&a.1.out_of_order).then(a.0.cmp(b.0)));
+        components
+    }
+}
+
+/// Operation pairing in source order versus after the global timestamp re-sort
+#[derive(Debug, Clone, Default)]
+pub struct PairingCheck {
+    pub operations_source_order: usize,
+    pub operations_sorted: usize,
+    pub orphans_source_order: usize,
+    pub orphans_sorted: usize,
+    /// Paired operations present in only one of the two orderings
+    pub changed_pairs: usize,
+}
+
+impl PairingCheck {
+    pub fn changed(&self) -> bool {
+        self.changed_pairs > 0 || self.orphans_source_order != self.orphans_sorted
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChronologyReport {
+    pub files: Vec<FileChronology>,
+    pub pairing: PairingCheck,
+}
+
+impl ChronologyReport {
+    pub fn out_of_order(&self) -> usize {
+        self.files.iter().map(|file| file.stats.out_of_order).sum()
+    }
+
+    /// Whether durations computed from these logs can be taken at face value
+    pub fn trustworthy(&self) -> bool {
+        self.out_of_order() == 0 && !self.pairing.changed()
+    }
+}
+
+pub fn check_file(file: &Path, logs: &[LogEntry], filter: &LogFilter) -> FileChronology {
+    let mut chronology = FileChronology {
+        file: file.display().to_string(),
+        stats: ChronologyStats::default(),
+        components: BTreeMap::new(),
+    };
+    for log in logs.iter().filter(|log| filter.matches(log)) {
+        chronology.stats.observe(log);
+        chronology
+            .components
+            .entry(log.component.clone())
+            .or_default()
+            .observe(log);
+    }
+    chronology
+}
+
+type PairKey = (
+    String,
+    String,
+    Option<String>,
+    DateTime<Local>,
+    DateTime<Local>,
+);
+
+/// Paired operations, including the negative durations set aside from statistics
+fn paired_operations(results: &PerfAnalysisResults) -> impl Iterator<Item = &TimedOperation> {
+    results.operations.iter().chain(&results.suspect_operations)
+}
+
+fn pair_keys(results: &PerfAnalysisResults) -> BTreeSet<PairKey> {
+    paired_operations(results)
+        .map(|op| {
+            (
+                op.op_type.clone(),
+                op.name.clone(),
+                op.correlation_id.clone(),
+                op.start_time,
+                op.end_time,
+            )
+        })
+        .collect()
+}
+
+/// Pairs operations over `source_order` (files concatenated as read) and over the
+/// same entries stably sorted by timestamp, as multi-file commands do.
+pub fn check_pairing(
+    source_order: &[LogEntry],
+    filter: &LogFilter,
+    config: &AnalyzerConfig,
+) -> PairingCheck {
+    let mut sorted = source_order.to_vec();
+    sorted.sort_by_key(|log| log.timestamp);
+
+    let before = analyze_performance_with_config(source_order, filter, None, config);
+    let after = analyze_performance_with_config(&sorted, filter, None, config);
+    let before_pairs = pair_keys(&before);
+    let after_pairs = pair_keys(&after);
+
+    PairingCheck {
+        operations_source_order: paired_operations(&before).count(),
+        operations_sorted: paired_operations(&after).count(),
+        orphans_source_order: before.orphans.len(),
+        orphans_sorted: after.orphans.len(),
+        changed_pairs: before_pairs.symmetric_difference(&after_pairs).count(),
+    }
+}
+
+fn backward_label(stats: &ChronologyStats) -> String {
+    match stats.max_backward_line {
+        Some(line) => format!(
+            ", max backward jump {}ms at line {line}",
+            stats.max_backward_ms
+        ),
+        None => String::new(),
+    }
+}
+
+pub fn format_chronology_text(report: &ChronologyReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "CHRONOLOGY: {}",
+        if report.trustworthy() {
+            "timestamps are monotonic"
+        } else {
+            "timestamps are reordered; treat durations with care"
+        }
+    );
+
+    for file in &report.files {
+        let _ = writeln!(
+            out,
+            "\n{}: {} entries, {} out of order ({:.1}%){}",
+            file.file,
+            file.stats.entries,
+            file.stats.out_of_order,
+            file.stats.out_of_order_pct(),
+            backward_label(&file.stats)
+        );
+        for (component, stats) in file.reordered_components() {
+            let _ = writeln!(
+                out,
+                "  {component}: {} of {} out of order{}",
+                stats.out_of_order,
+                stats.entries,
+                backward_label(stats)
+            );
+        }
+    }
+
+    let pairing = &report.pairing;
+    let _ = writeln!(
+        out,
+        "\nPairing after global re-sort: {}",
+        if pairing.changed() {
+            "CHANGED"
+        } else {
+            "unchanged"
+        }
+    );
+    let _ = writeln!(
+        out,
+        "  source order: {} operations, {} orphans",
+        pairing.operations_source_order, pairing.orphans_source_order
+    );
+    let _ = writeln!(
+        out,
+        "  sorted:       {} operations, {} orphans",
+        pairing.operations_sorted, pairing.orphans_sorted
+    );
+    if pairing.changed_pairs > 0 {
+        let _ = writeln!(
+            out,
+            "  {} paired operations differ between orderings",
+            pairing.changed_pairs
+        );
+    }
+    out
+}
+
+pub fn format_chronology_json(report: &ChronologyReport) -> String {
+    let files: Vec<Value> = report
+        .files
+        .iter()
+        .map(|file| {
+            let mut value = file.stats.to_json();
+            value["file"] = json!(file.file);
+            value["components"] = file
+                .components
+                .iter()
+                .map(|(component, stats)| (component.clone(), stats.to_json()))
+                .collect::<serde_json::Map<_, _>>()
+                .into();
+            value
+        })
+        .collect();
+    let pairing = &report.pairing;
+
+    serde_json::to_string_pretty(&json!({
+        "chronology": {
+            "trustworthy": report.trustworthy(),
+            "out_of_order": report.out_of_order(),
+            "files": files,
+            "pairing": {
+                "changed": pairing.changed(),
+                "changed_pairs": pairing.changed_pairs,
+                "operations_source_order": pairing.operations_source_order,
+                "operations_sorted": pairing.operations_sorted,
+                "orphans_source_order": pairing.orphans_source_order,
+                "orphans_sorted": pairing.orphans_sorted,
+            },
+        }
+    }))
+    .unwrap_or_else(|_| {
+        "{\"chronology\":{\"error\":\"failed to serialize chronology report\"}}".into()
+    })
+}
@@ -1,7 +1,14 @@
 mod direction;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use crate::config::SessionAttribution;
+use crate::parser::ScopeRange;
+use crate::time_buckets::DEFAULT_MAX_BUCKETS;
+use chrono::{DateTime, Local};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 pub use direction::Direction;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -12,6 +19,19 @@ pub enum OutputFormat {
     Json,
 }
 
+/// Output of the `render` command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RenderFormat {
+    /// Human-readable text output
+    Text,
+    /// JSON output, as `compare -F json`
+    Json,
+    /// Markdown document for pull requests and wikis
+    Markdown,
+    /// Self-contained HTML page
+    Html,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum ColorMode {
     /// Auto-detect color support (default)
@@ -22,7 +42,17 @@ pub enum ColorMode {
     Never,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum RelativeTo {
+    /// The wall clock, or --now (default)
+    #[default]
+    Now,
+    /// The last entry of the logs
+    LogEnd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum SortOrder {
     /// Sort by timestamp (default)
     #[default]
@@ -69,6 +99,16 @@ pub enum ErrorsSortBy {
     Impact,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CountBy {
+    /// Group by component name
+    Component,
+    /// Group by log level
+    Level,
+    /// Group by structured log type (event/request/command/generic + subtype)
+    Type,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum SearchCountBy {
     /// Total number of matching entries (grep -c style)
@@ -81,6 +121,8 @@ pub enum SearchCountBy {
     Type,
     /// Group by parsed JSON payload/settings (or <none>)
     Payload,
+    /// Histogram of match timestamps in fixed-width buckets (see --bucket)
+    Time,
 }
 
 /// Analyze, search, compare, and diagnose structured logs
@@ -106,29 +148,37 @@ pub enum SearchCountBy {
     --filter \"actor_kind:switch\"          Structured field filter on tracing/json logs")]
 pub struct Cli {
     /// Output format (text or json)
-    #[arg(short = 'F', long, value_enum, default_value_t = OutputFormat::Text, global = true, group = "output_options", env = "LOG_ANALYZER_FORMAT")]
+    #[arg(short = 'F', long, value_enum, default_value_t = OutputFormat::Text, global = true, env = "LOG_ANALYZER_FORMAT")]
     pub format: OutputFormat,
 
     /// JSON output (LLM-friendly, implies --compact). Shorthand for -F json -c
-    #[arg(
-        short = 'j',
-        long,
-        global = true,
-        group = "output_options",
-        conflicts_with = "format",
-        env = "LOG_ANALYZER_JSON"
-    )]
+    #[arg(short = 'j', long, global = true, env = "LOG_ANALYZER_JSON")]
     pub json: bool,
 
     /// Use compact mode for output (shorter keys, optimized structure)
+    #[arg(short = 'c', long, global = true, env = "LOG_ANALYZER_COMPACT")]
+    pub compact: bool,
+
+    /// Embed a legend mapping abbreviated JSON keys to full names (compact comparison
+    /// JSON and process output)
+    #[arg(long, global = true, env = "LOG_ANALYZER_LEGEND")]
+    pub legend: bool,
+
+    /// Print only the header and summary blocks of compare, errors, perf, and info,
+    /// leaving out per-entry, per-cluster, and per-operation details
+    #[arg(long, global = true, env = "LOG_ANALYZER_SUMMARY_ONLY")]
+    pub summary_only: bool,
+
+    /// Show only the first and last elements of longer arrays in printed payloads
+    /// (search --payloads, compare/diff --full, trace --diff-commands; 0 = no limit)
     #[arg(
-        short = 'c',
         long,
+        value_name = "N",
+        default_value_t = 20,
         global = true,
-        group = "output_options",
-        env = "LOG_ANALYZER_COMPACT"
+        env = "LOG_ANALYZER_MAX_ARRAY_ELEMENTS"
     )]
-    pub compact: bool,
+    pub max_array_elements: usize,
 
     /// Filter expression (e.g., "c:core l:ERROR !t:timeout")
     #[arg(short = 'f', long, global = true, env = "LOG_ANALYZER_FILTER")]
@@ -143,12 +193,7 @@ pub struct Cli {
     pub config: Option<PathBuf>,
 
     /// Built-in preset/profile to use instead of --config (base, eyes, custom-start, service-api, event-pipeline)
-    #[arg(
-        long,
-        global = true,
-        env = "LOG_ANALYZER_PRESET",
-        conflicts_with = "config"
-    )]
+    #[arg(long, global = true, env = "LOG_ANALYZER_PRESET")]
     pub preset: Option<String>,
 
     /// Control color output (auto, always, never)
@@ -169,8 +214,79 @@ pub struct Cli {
     )]
     pub quiet: bool,
 
+    /// Do not load the .loganalyzerignore file discovered from the working directory
+    #[arg(long, global = true, env = "LOG_ANALYZER_NO_IGNORE_FILE")]
+    pub no_ignore_file: bool,
+
+    /// List every payload that failed to parse (info/compare/diff show the first few per file)
+    #[arg(long, global = true)]
+    pub payload_errors: bool,
+
+    /// Embed the raw log text of reported entries in search --diff-against, errors,
+    /// and compare/diff JSON output
+    #[arg(long, global = true, env = "LOG_ANALYZER_INCLUDE_RAW")]
+    pub include_raw: bool,
+
+    /// Compare payload objects/arrays nested deeper than this as opaque blobs and stop
+    /// JSON schema analysis there (overrides `[comparison] max_json_depth`)
+    #[arg(
+        long,
+        global = true,
+        value_name = "DEPTH",
+        env = "LOG_ANALYZER_MAX_JSON_DEPTH"
+    )]
+    pub max_json_depth: Option<usize>,
+
+    /// Maximum bytes of raw log text embedded per entry with --include-raw
+    #[arg(long, global = true, default_value_t = 4096, value_parser = parse_raw_limit)]
+    pub raw_limit: usize,
+
+    /// Memory budget for compare/diff/llm-diff (e.g. 512M, 2G). When the estimated
+    /// footprint exceeds it, grouped entries are spilled to temporary files
+    #[arg(long, global = true, value_parser = parse_byte_size, env = "LOG_ANALYZER_MAX_MEMORY")]
+    pub max_memory: Option<u64>,
+
+    /// Analyze only run N (1-based) of logs that contain several runs, split at
+    /// `[runs]` start markers and long silences (see info --split-runs)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u64).range(1..), env = "LOG_ANALYZER_RUN")]
+    pub run: Option<u64>,
+
+    /// Instant that relative since:/until: filter bounds count back from (RFC3339),
+    /// instead of the wall clock, for reproducible runs
+    #[arg(long, global = true, value_name = "TIMESTAMP", value_parser = parse_timestamp, env = "LOG_ANALYZER_NOW")]
+    pub now: Option<DateTime<Local>>,
+
+    /// Count relative since:/until: filter bounds back from the wall clock (or --now),
+    /// or from the last entry of the logs
+    #[arg(long, global = true, value_enum, default_value_t = RelativeTo::Now, env = "LOG_ANALYZER_RELATIVE_TO")]
+    pub relative_to: RelativeTo,
+
+    /// Read only these lines of each file (1-based, inclusive, e.g. 10000-20000 or 5000-)
+    /// to iterate on filters and configs against a slice of a large log
+    #[arg(long, global = true, value_name = "START-END", value_parser = parse_line_range)]
+    pub lines: Option<ScopeRange>,
+
+    /// Read only the lines of each file starting in this byte range (e.g. 0-50M, 1G-);
+    /// line numbers stay those of the whole file
+    #[arg(long, global = true, value_name = "START-END", value_parser = parse_byte_range)]
+    pub bytes: Option<ScopeRange>,
+
+    /// Keep the earlier JSON shapes of duration/size fields: fractional `*_ms` values,
+    /// info --against time span in seconds, and no `*_human` companions
+    #[arg(long, global = true, env = "LOG_ANALYZER_LEGACY_JSON_UNITS")]
+    pub legacy_json_units: bool,
+
+    /// Metadata pair embedded under "meta" in JSON output (repeatable, e.g. --meta run_id=123)
+    #[arg(long = "meta", value_name = "KEY=VALUE", global = true, value_parser = parse_meta_pair)]
+    pub meta: Vec<(String, String)>,
+
+    /// Print what a warning code (e.g. LA0001) means and how to address it, then exit
+    #[arg(long, value_name = "CODE")]
+    pub explain: Option<String>,
+
+    /// Required unless --explain is given
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -182,9 +298,19 @@ pub enum Commands {
         #[arg(required = true)]
         file1: PathBuf,
 
-        /// Second log file
-        #[arg(required = true)]
-        file2: PathBuf,
+        /// Second log file (omit with --split-at)
+        #[arg(required_unless_present = "split_at")]
+        file2: Option<PathBuf>,
+
+        /// Compare FILE1 with itself: entries before this timestamp (or before the first entry
+        /// matching this filter expression) against the entries from there on
+        #[arg(
+            long,
+            value_name = "TIME|FILTER",
+            value_parser = crate::comparator::SplitPoint::parse,
+            conflicts_with_all = ["file2", "learn_noise"]
+        )]
+        split_at: Option<crate::comparator::SplitPoint>,
 
         /// Show only differences, skip matching objects
         #[arg(short = 'D', long)]
@@ -197,6 +323,48 @@ pub enum Commands {
         /// Sort output by given field
         #[arg(short = 's', long, value_enum, default_value_t = SortOrder::Time, env = "LOG_ANALYZER_SORT_BY")]
         sort_by: SortOrder,
+
+        /// Only compare grouped keys matching this glob (repeatable, e.g. '*|Request|*openEyes*')
+        #[arg(long = "only-key", value_name = "GLOB")]
+        only_keys: Vec<String>,
+
+        /// Skip grouped keys matching this glob (repeatable)
+        #[arg(long = "skip-key", value_name = "GLOB")]
+        skip_keys: Vec<String>,
+
+        /// For keys with unequal occurrence counts, compare one representative per payload-shape cluster
+        #[arg(long)]
+        cluster_payloads: bool,
+
+        /// Flag components whose share of any log level shifted by at least this many percentage points (0 = off)
+        #[arg(long, value_name = "PP", default_value_t = 10.0)]
+        level_drift_threshold: f64,
+
+        /// Highlight this many of the rarest differences (paths that seldom differ) ahead of the full listing (0 = off)
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        interesting: usize,
+
+        /// Treat messages and string values that differ only in letter case as equal
+        #[arg(long)]
+        ignore_case: bool,
+
+        /// Treat messages and string values that differ only in whitespace as equal
+        #[arg(long)]
+        ignore_whitespace: bool,
+
+        /// Learn diff noise from N known-equivalent log pairs (FILE1 FILE2 plus N-1 --pair)
+        /// and print a suggested .loganalyzerignore profile instead of the comparison
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        learn_noise: Option<u64>,
+
+        /// Another known-equivalent log pair for --learn-noise (repeatable)
+        #[arg(long = "pair", value_names = ["FILE1", "FILE2"], num_args = 2, requires = "learn_noise")]
+        pairs: Vec<PathBuf>,
+
+        /// Also save the comparison results to this file, to re-render later with
+        /// `render` without the original logs
+        #[arg(long, value_name = "FILE", conflicts_with = "learn_noise")]
+        save: Option<PathBuf>,
     },
 
     /// Compare two log files showing only differences (shortcut for compare --diff-only)
@@ -216,6 +384,34 @@ pub enum Commands {
         /// Sort output by given field
         #[arg(short = 's', long, value_enum, default_value_t = SortOrder::Time, env = "LOG_ANALYZER_SORT_BY")]
         sort_by: SortOrder,
+
+        /// Only compare grouped keys matching this glob (repeatable, e.g. '*|Request|*openEyes*')
+        #[arg(long = "only-key", value_name = "GLOB")]
+        only_keys: Vec<String>,
+
+        /// Skip grouped keys matching this glob (repeatable)
+        #[arg(long = "skip-key", value_name = "GLOB")]
+        skip_keys: Vec<String>,
+
+        /// For keys with unequal occurrence counts, compare one representative per payload-shape cluster
+        #[arg(long)]
+        cluster_payloads: bool,
+
+        /// Flag components whose share of any log level shifted by at least this many percentage points (0 = off)
+        #[arg(long, value_name = "PP", default_value_t = 10.0)]
+        level_drift_threshold: f64,
+
+        /// Highlight this many of the rarest differences (paths that seldom differ) ahead of the full listing (0 = off)
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        interesting: usize,
+
+        /// Treat messages and string values that differ only in letter case as equal
+        #[arg(long)]
+        ignore_case: bool,
+
+        /// Treat messages and string values that differ only in whitespace as equal
+        #[arg(long)]
+        ignore_whitespace: bool,
     },
 
     /// List components, event types, log levels, and statistics in one or more log files
@@ -240,6 +436,39 @@ pub enum Commands {
         /// Show detailed timeline analysis with event distribution
         #[arg(short = 't', long)]
         timeline: bool,
+
+        /// Timeline bucket width, down to 100ms (e.g. 100ms, 5s, 1m; default: finest that fits --max-buckets)
+        #[arg(long, value_name = "DURATION", requires = "timeline", value_parser = parse_duration_ms)]
+        bucket: Option<u64>,
+
+        /// Maximum number of timeline buckets; a finer --bucket is widened to fit
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_BUCKETS, value_parser = parse_max_buckets)]
+        max_buckets: usize,
+
+        /// Compare summary statistics against another log file (delta view with arrows/percentages)
+        #[arg(long, value_name = "FILE")]
+        against: Option<PathBuf>,
+
+        /// Show which payload fields each request name carries (rows = fields, columns = requests)
+        #[arg(long, conflicts_with = "against")]
+        field_matrix: bool,
+
+        /// Print the field matrix as CSV
+        #[arg(long, requires = "field_matrix")]
+        csv: bool,
+
+        /// List the runs detected in the logs (start markers from `[runs]`, long silences)
+        #[arg(long, conflicts_with = "against")]
+        split_runs: bool,
+
+        /// Cache each file's JSON schema report in DIR and reuse it while the file is unchanged
+        #[arg(
+            long,
+            value_name = "DIR",
+            requires = "json_schema",
+            conflicts_with_all = ["against", "field_matrix", "split_runs", "samples", "payloads", "timeline"]
+        )]
+        schema_cache: Option<PathBuf>,
     },
 
     /// Search a log file and print matching entries (structured grep replacement)
@@ -259,6 +488,63 @@ pub enum Commands {
         /// Count matches grouped by a structured field instead of printing entries
         #[arg(long, value_enum)]
         count_by: Option<SearchCountBy>,
+
+        /// Bucket width for --count-by time, down to 100ms (e.g. 100ms, 5s, 1m; default: finest that fits --max-buckets)
+        #[arg(long, value_name = "DURATION", requires = "count_by", value_parser = parse_duration_ms)]
+        bucket: Option<u64>,
+
+        /// Maximum number of buckets for --count-by time; a finer --bucket is widened to fit
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_BUCKETS, value_parser = parse_max_buckets)]
+        max_buckets: usize,
+
+        /// Diff each matching entry's payload against a golden JSON file and print per-entry verdicts
+        #[arg(long, value_name = "FILE", conflicts_with = "count_by")]
+        diff_against: Option<PathBuf>,
+
+        /// Payload path to ignore when diffing against the golden file (repeatable; also ignores nested paths)
+        #[arg(long = "ignore-path", value_name = "PATH", requires = "diff_against")]
+        ignore_paths: Vec<String>,
+
+        /// Only match sent requests and commands missing a field required by `[contracts]`
+        #[arg(long)]
+        violations: bool,
+
+        /// Only match entries whose component_id/session path contains this substring (as `trace --session`)
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Only match entries carrying this correlation/request ID substring (as `trace --id`)
+        #[arg(long, value_name = "ID")]
+        request_id: Option<String>,
+
+        /// Absolute tolerance for numeric differences when diffing against the golden file
+        #[arg(long, default_value_t = 0.0, requires = "diff_against")]
+        tolerance: f64,
+    },
+
+    /// Count matching entries on a fast scan that skips JSON payload parsing
+    Count {
+        /// One or more log files to count (supports shell-expanded globs)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+
+        /// Group counts by a structured field instead of printing a single total
+        #[arg(long, value_enum)]
+        by: Option<CountBy>,
+    },
+
+    /// Check timestamp monotonicity and whether re-sorting changes operation pairing
+    Chronology {
+        /// One or more log files to check, in the order they were written
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+    },
+
+    /// Check request/command payloads against the `[contracts]` required fields
+    Contracts {
+        /// One or more log files to check (supports shell-expanded globs)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
     },
 
     /// Diagnose clustered errors/warnings and affected sessions across one or more logs
@@ -282,6 +568,102 @@ pub enum Commands {
         /// Sort clusters by field
         #[arg(short = 's', long, value_enum, default_value_t = ErrorsSortBy::Count)]
         sort_by: ErrorsSortBy,
+
+        /// How entries on " & "-joined parallel sessions are attributed (overrides profile)
+        #[arg(long, value_enum)]
+        session_attribution: Option<SessionAttribution>,
+
+        /// Evaluate the error rate over sliding windows against the `[errors.slo]` objective
+        #[arg(long)]
+        slo: bool,
+
+        /// Maximum error rate per window in percent (overrides profile)
+        #[arg(long, value_name = "PERCENT", requires = "slo")]
+        slo_max_rate: Option<f64>,
+
+        /// Window length such as 30s or 1m (overrides profile)
+        #[arg(long, value_name = "DURATION", requires = "slo", value_parser = parse_duration_ms)]
+        slo_window: Option<u64>,
+
+        /// Step between window starts such as 5s (overrides profile)
+        #[arg(long, value_name = "DURATION", requires = "slo", value_parser = parse_duration_ms)]
+        slo_step: Option<u64>,
+
+        /// Worker threads for error clustering (0 = all available cores)
+        #[arg(long, default_value_t = 0, env = "LOG_ANALYZER_THREADS")]
+        threads: usize,
+
+        /// Read the files as a stream and save the analysis state here, resuming from it if it exists
+        #[arg(long, value_name = "FILE", conflicts_with = "slo")]
+        checkpoint: Option<PathBuf>,
+
+        /// Entries between checkpoint saves (state is also saved at every day boundary)
+        #[arg(
+            long,
+            value_name = "ENTRIES",
+            default_value_t = 100_000,
+            requires = "checkpoint"
+        )]
+        checkpoint_every: usize,
+
+        /// Read the files as a stream and report each calendar day as it closes
+        #[arg(long, conflicts_with = "slo")]
+        daily: bool,
+
+        /// Render each displayed cluster as a ready-to-paste issue (template from `[errors] ticket_template`)
+        #[arg(long, conflicts_with = "slo")]
+        ticket: bool,
+    },
+
+    /// Find the entries most similar to one example entry (message template + payload shape)
+    Similar {
+        /// Log files to search; the example entry is taken from the first file
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+
+        /// Line number of the example entry in the first file
+        #[arg(long, value_name = "N")]
+        to_line: usize,
+
+        /// Number of most similar entries to show (0 = all)
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+
+    /// Group near-identical entries of two files (simhash of message template and payload
+    /// shape) and list the groups whose counts differ
+    Dupes {
+        /// First log file
+        #[arg(required = true)]
+        file1: PathBuf,
+
+        /// Second log file
+        #[arg(required = true)]
+        file2: PathBuf,
+
+        /// Largest fingerprint distance (differing bits out of 64) that still counts as near-identical
+        #[arg(long, value_name = "BITS", default_value_t = 12)]
+        max_distance: u32,
+
+        /// Number of groups to show, largest count difference first (0 = all)
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+
+    /// Diff exactly two entries (message and payload), optionally as a shareable HTML page
+    #[command(name = "show-diff")]
+    ShowDiff {
+        /// First entry as FILE:LINE
+        #[arg(value_name = "FILE:LINE", value_parser = parse_entry_ref)]
+        left: (PathBuf, usize),
+
+        /// Second entry as FILE:LINE
+        #[arg(value_name = "FILE:LINE", value_parser = parse_entry_ref)]
+        right: (PathBuf, usize),
+
+        /// Write a self-contained two-pane HTML diff to this file
+        #[arg(long, value_name = "PATH")]
+        html: Option<PathBuf>,
     },
 
     /// Extract and aggregate a JSON payload/settings field from matching log entries
@@ -293,6 +675,10 @@ pub enum Commands {
         /// Field name/path to extract from payload JSON (supports dot paths, e.g. "foo.bar")
         #[arg(long, required = true)]
         field: String,
+
+        /// Extract the same field from another log file and show both value counts side by side
+        #[arg(long, value_name = "FILE")]
+        against: Option<PathBuf>,
     },
 
     /// Generate LLM-friendly compact JSON output of differences (shortcut for compare --diff-only -F json -c)
@@ -309,11 +695,51 @@ pub enum Commands {
         #[arg(short = 's', long, value_enum, default_value_t = SortOrder::Time, env = "LOG_ANALYZER_SORT_BY")]
         sort_by: SortOrder,
 
+        /// Only compare grouped keys matching this glob (repeatable, e.g. '*|Request|*openEyes*')
+        #[arg(long = "only-key", value_name = "GLOB")]
+        only_keys: Vec<String>,
+
+        /// Skip grouped keys matching this glob (repeatable)
+        #[arg(long = "skip-key", value_name = "GLOB")]
+        skip_keys: Vec<String>,
+
+        /// For keys with unequal occurrence counts, compare one representative per payload-shape cluster
+        #[arg(long)]
+        cluster_payloads: bool,
+
+        /// Flag components whose share of any log level shifted by at least this many percentage points (0 = off)
+        #[arg(long, value_name = "PP", default_value_t = 10.0)]
+        level_drift_threshold: f64,
+
+        /// Highlight this many of the rarest differences (paths that seldom differ) ahead of the full listing (0 = off)
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        interesting: usize,
+
+        /// Treat messages and string values that differ only in letter case as equal
+        #[arg(long)]
+        ignore_case: bool,
+
+        /// Treat messages and string values that differ only in whitespace as equal
+        #[arg(long)]
+        ignore_whitespace: bool,
+
         /// Disable hiding of sensitive fields from JSON payloads (sanitization is enabled by default)
         #[arg(long)]
         no_sanitize: bool,
     },
 
+    /// Render comparison results saved with `compare --save` as text, JSON, Markdown
+    /// or HTML, without the original logs
+    Render {
+        /// Results file written by `compare --save`
+        #[arg(required = true)]
+        file: PathBuf,
+
+        /// Output format (defaults to the global --format)
+        #[arg(long, value_enum)]
+        to: Option<RenderFormat>,
+    },
+
     /// Generate LLM-friendly compact JSON output of a single log file with sanitized content
     #[command(visible_alias = "llm")]
     Process {
@@ -359,6 +785,30 @@ pub enum Commands {
         /// Sort results by field
         #[arg(short = 's', long, value_enum, default_value_t = PerfSortOrder::Duration)]
         sort_by: PerfSortOrder,
+
+        /// How operations on " & "-joined parallel sessions are attributed (overrides profile)
+        #[arg(long, value_enum)]
+        session_attribution: Option<SessionAttribution>,
+
+        /// Warn about requests still pending this many milliseconds after being sent
+        #[arg(long, value_name = "MS")]
+        hang_threshold: Option<u64>,
+
+        /// Report per-session waiting/starvation and a fairness index per time bucket
+        #[arg(long)]
+        fairness: bool,
+
+        /// Bucket width in milliseconds for the fairness index
+        #[arg(long, value_name = "MS", default_value_t = 1000, requires = "fairness")]
+        fairness_bucket_ms: u64,
+
+        /// Count negative durations (clock skew, reordered entries) in the statistics
+        #[arg(long)]
+        include_suspect_durations: bool,
+
+        /// Split each check session's time into the profile's [[perf.phases]]
+        #[arg(long)]
+        phases: bool,
     },
 
     /// Trace a single operation lifecycle by correlation/request ID or session path
@@ -368,12 +818,51 @@ pub enum Commands {
         files: Vec<PathBuf>,
 
         /// Correlation/request ID substring to trace (matches raw log lines)
-        #[arg(long, conflicts_with = "session", required_unless_present = "session")]
+        #[arg(
+            long,
+            conflicts_with = "session",
+            required_unless_present_any = ["session", "diff_commands"]
+        )]
         id: Option<String>,
 
         /// component_id/session path substring to trace (matches hierarchy)
-        #[arg(long, conflicts_with = "id", required_unless_present = "id")]
+        #[arg(
+            long,
+            conflicts_with = "id",
+            required_unless_present_any = ["id", "diff_commands"]
+        )]
         session: Option<String>,
+
+        /// Diff settings between successive invocations of this command within each session
+        #[arg(long, value_name = "COMMAND")]
+        diff_commands: Option<String>,
+    },
+
+    /// Capture a trimmed, redacted slice of a log as a test fixture with a manifest
+    CaptureFixture {
+        /// Log file to capture from
+        #[arg(required = true)]
+        file: PathBuf,
+
+        /// Fixture name (written as <name>.log and <name>.manifest.json)
+        #[arg(long, value_parser = parse_fixture_name)]
+        name: String,
+
+        /// Only capture entries at or after this RFC3339 timestamp
+        #[arg(long, value_parser = parse_timestamp)]
+        since: Option<DateTime<Local>>,
+
+        /// Only capture entries at or before this RFC3339 timestamp
+        #[arg(long, value_parser = parse_timestamp)]
+        until: Option<DateTime<Local>>,
+
+        /// component_id/session path substring to keep (repeatable; default keeps all)
+        #[arg(long = "session", value_name = "PATH")]
+        sessions: Vec<String>,
+
+        /// Directory the fixture is written to
+        #[arg(long, default_value = crate::fixtures::DEFAULT_FIXTURE_DIR)]
+        dir: PathBuf,
     },
 
     /// Analyze one or more log files and generate a TOML config profile
@@ -391,6 +880,110 @@ pub enum Commands {
         #[arg(long)]
         template: Option<PathBuf>,
     },
+
+    /// Per-session reports
+    Sessions {
+        #[command(subcommand)]
+        action: SessionsCommand,
+    },
+
+    /// Inspect and validate config profiles
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+
+    /// Combine JSON reports from several runs
+    Report {
+        #[command(subcommand)]
+        action: ReportCommand,
+    },
+
+    /// Table of runs × key metrics (errors, orphaned sessions, p95 latency, duration)
+    /// with sparkline trends, for a directory holding one subdirectory of logs per run
+    Matrix {
+        /// Directory with one subdirectory per run; runs are ordered by subdirectory name
+        #[arg(required = true)]
+        dir: PathBuf,
+
+        /// Request or operation name whose p95 latency gets a column (repeatable;
+        /// default: the names under [perf] latency_budgets_ms)
+        #[arg(long = "request", value_name = "NAME")]
+        requests: Vec<String>,
+
+        /// Keep only the most recent N runs (0 = all)
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        last: usize,
+
+        /// Print the matrix as CSV
+        #[arg(long)]
+        csv: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SessionsCommand {
+    /// Chronological narrative of one session: creation settings, key requests,
+    /// warnings/errors, and the completion result
+    Story {
+        /// Session id or path (e.g. eyes-3 or manager-1/eyes-3)
+        #[arg(required = true)]
+        session: String,
+
+        /// Log files to read (supports shell-expanded globs)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+    },
+
+    /// Failed sessions (pending operations, never completed, or aborted) with the
+    /// error clusters and slow operations inside them, most likely cause first
+    Impact {
+        /// Log files to read (supports shell-expanded globs)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+
+        /// Session level to judge (default: the deepest level with completion commands)
+        #[arg(long, value_name = "NAME")]
+        level: Option<String>,
+
+        /// Operations taking at least this many milliseconds count as slow
+        #[arg(long, default_value = "1000")]
+        threshold_ms: u64,
+
+        /// Suspects listed per failed session (0 = all)
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        top: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Parse a log under two configs and report differences in entry kinds,
+    /// operation pairing, and session detection
+    CompareEffect {
+        /// Current config (path to TOML or built-in name)
+        #[arg(long)]
+        config_a: PathBuf,
+
+        /// Candidate config (path to TOML or built-in name)
+        #[arg(long)]
+        config_b: PathBuf,
+
+        /// Log file to analyze
+        #[arg(required = true)]
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReportCommand {
+    /// Roll up errors, perf, or compare/diff JSON reports into aggregate statistics
+    /// (clusters summed, latency distributions merged via histograms)
+    Merge {
+        /// JSON reports of the same kind, as written by -F json or -j
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+    },
 }
 
 impl Cli {
@@ -407,8 +1000,178 @@ impl Cli {
     pub fn effective_compact(&self) -> bool {
         self.json || self.compact
     }
+
+    /// Resolve options that exclude each other so a command-line flag overrides a
+    /// `LOG_ANALYZER_*` default. Conflicts from the same source are still rejected.
+    pub fn apply_env_precedence(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        if self.json && !matches!(self.format, OutputFormat::Json) {
+            match exclusive_winner(matches, "json", "format")? {
+                Some("format") => self.json = false,
+                _ => self.format = OutputFormat::Json,
+            }
+        }
+        if self.config.is_some() && self.preset.is_some() {
+            match exclusive_winner(matches, "config", "preset")? {
+                Some("preset") => self.config = None,
+                _ => self.preset = None,
+            }
+        }
+        Ok(())
+    }
+
+    /// Byte limit for embedded raw log text, or `None` without --include-raw
+    pub fn raw_excerpt_limit(&self) -> Option<usize> {
+        self.include_raw.then_some(self.raw_limit)
+    }
+}
+
+/// Which of two mutually exclusive options applies: the one given on the command line
+/// beats one from the environment. `None` when `second` was not set explicitly.
+fn exclusive_winner(
+    matches: &ArgMatches,
+    first: &'static str,
+    second: &'static str,
+) -> Result<Option<&'static str>, clap::Error> {
+    let source = |id: &str| matches.value_source(id);
+    match (source(first), source(second)) {
+        (_, None | Some(ValueSource::DefaultValue)) => Ok(None),
+        (Some(ValueSource::CommandLine), Some(ValueSource::EnvVariable)) => Ok(Some(first)),
+        (Some(ValueSource::EnvVariable), Some(ValueSource::CommandLine)) => Ok(Some(second)),
+        (Some(ValueSource::EnvVariable), _) => Err(Cli::command().error(
+            ErrorKind::ArgumentConflict,
+            format!(
+                "the environment variables 'LOG_ANALYZER_{}' and 'LOG_ANALYZER_{}' cannot be used together",
+                first.to_ascii_uppercase(),
+                second.to_ascii_uppercase()
+            ),
+        )),
+        _ => Err(Cli::command().error(
+            ErrorKind::ArgumentConflict,
+            format!("the arguments '--{first}' and '--{second}' cannot be used together"),
+        )),
+    }
+}
+
+/// Parse a duration such as `500ms`, `5s`, `1.5m`, or `2h` into milliseconds
+pub fn parse_duration_ms(input: &str) -> Result<u64, String> {
+    crate::filter::parse_window_duration(input)
+        .filter(|ms| *ms > 0)
+        .map(|ms| ms as u64)
+        .ok_or_else(|| format!("invalid duration '{input}' (expected e.g. 500ms, 5s, 1m, 2h)"))
+}
+
+/// Parse a `--max-buckets` count (at least 1)
+fn parse_max_buckets(input: &str) -> Result<usize, String> {
+    match input.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!(
+            "invalid bucket count '{input}' (expected a positive integer)"
+        )),
+    }
+}
+
+/// Parse a `capture-fixture --name`, which must name a file inside the fixture directory
+fn parse_fixture_name(input: &str) -> Result<String, String> {
+    if input.is_empty()
+        || input
+            .chars()
+            .any(|c| c == '/' || std::path::is_separator(c))
+    {
+        return Err(format!(
+            "invalid fixture name '{input}' (expected a file name without path separators; use --dir for the directory)"
+        ));
+    }
+    Ok(input.to_string())
+}
+
+/// Parse a `--raw-limit` byte size (e.g. `512`, `4K`)
+fn parse_raw_limit(input: &str) -> Result<usize, String> {
+    let bytes = parse_byte_size(input)?;
+    usize::try_from(bytes).map_err(|_| format!("raw limit '{input}' is too large"))
+}
+
+/// Parse a human-readable byte size such as `512M`, `2G`, `1.5GiB`, or `1048576`
+pub fn parse_byte_size(input: &str) -> Result<u64, String> {
+    let trimmed = input.trim();
+    let split_at = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split_at);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid size '{input}' (expected e.g. 512M, 2G)"))?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        "T" | "TB" | "TIB" => 1 << 40,
+        other => return Err(format!("unknown size unit '{other}' in '{input}'")),
+    };
+
+    Ok((value * multiplier as f64) as u64)
+}
+
+/// Parse a `--lines` range of 1-based line numbers
+fn parse_line_range(input: &str) -> Result<ScopeRange, String> {
+    ScopeRange::parse(input, |bound| match bound.parse::<u64>() {
+        Ok(line) if line > 0 => Ok(line),
+        _ => Err(format!(
+            "invalid line number '{bound}' in '{input}' (expected a positive integer)"
+        )),
+    })
+}
+
+/// Parse a `--bytes` range whose bounds are byte sizes such as `50M`
+fn parse_byte_range(input: &str) -> Result<ScopeRange, String> {
+    ScopeRange::parse(input, parse_byte_size)
+}
+
+/// Parse a `key=value` metadata pair
+pub fn parse_entry_ref(input: &str) -> Result<(PathBuf, usize), String> {
+    match input.rsplit_once(':') {
+        Some((file, line)) if !file.is_empty() => match line.parse::<usize>() {
+            Ok(line) if line > 0 => Ok((PathBuf::from(file), line)),
+            _ => Err(format!(
+                "invalid line number in '{input}' (expected FILE:LINE)"
+            )),
+        },
+        _ => Err(format!(
+            "invalid entry reference '{input}' (expected FILE:LINE)"
+        )),
+    }
+}
+
+pub fn parse_meta_pair(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("invalid metadata '{input}' (expected KEY=VALUE)")),
+    }
+}
+
+/// Parse an RFC3339 timestamp such as `2026-01-01T12:00:00Z`
+pub fn parse_timestamp(input: &str) -> Result<DateTime<Local>, String> {
+    DateTime::parse_from_rfc3339(input.trim())
+        .map(|ts| ts.with_timezone(&Local))
+        .map_err(|e| format!("invalid timestamp '{input}' (expected RFC3339): {e}"))
 }
 
 pub fn cli_parse() -> Cli {
-    Cli::parse()
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let Err(e) = cli.apply_env_precedence(&matches) {
+        e.exit();
+    }
+    if cli.command.is_none() && cli.explain.is_none() {
+        Cli::command()
+            .error(
+                ErrorKind::MissingSubcommand,
+                "a subcommand is required unless --explain is given",
+            )
+            .exit();
+    }
+    cli
 }
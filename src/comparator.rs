@@ -5,10 +5,18 @@ mod file_cmp;
 mod format_cmp;
 mod helpers;
 mod json_cmp;
+mod level_drift;
+mod markup_cmp;
+mod noise;
+mod rarity;
+mod saved;
+mod spill;
+mod split;
 
 use crate::cli::SortOrder;
 pub use console_cmp::display_comparison_results;
-pub use console_summary::display_log_summary;
+pub(crate) use console_summary::payload_field_paths;
+pub use console_summary::{SchemaReport, display_log_summary, display_schema_report};
 pub use entities::*;
 pub use file_cmp::write_comparison_results;
 pub use format_cmp::{
@@ -17,10 +25,30 @@ pub use format_cmp::{
 };
 pub use helpers::*;
 pub use json_cmp::generate_json_output;
+pub use level_drift::{
+    LevelCounts, LevelDrift, LevelShare, MIN_DRIFT_ENTRIES, detect_level_drift,
+    level_counts_from_keys,
+};
+pub use markup_cmp::{format_comparison_html, format_comparison_markdown};
+pub use noise::{
+    NoiseProfile, NoisyPath, format_noise_profile_json, format_noise_profile_text, learn_noise,
+};
+pub use rarity::{ScoredDifference, most_interesting_differences, rarity_path};
+pub use saved::{
+    SAVED_COMPARISON_FORMAT, SAVED_COMPARISON_VERSION, SavedComparison, SavedSettings, SavedSplit,
+    load_comparison, save_comparison,
+};
+pub use spill::{
+    GroupSpiller, PARSED_BYTES_PER_FILE_BYTE, SpilledGroups, compare_spilled_groups,
+    exceeds_memory_budget, spill_chunk_bytes, spill_log_groups,
+};
+pub use split::{LogSplit, SplitPoint, split_logs};
 
-use crate::parser::LogEntry;
+use crate::parser::{LogEntry, raw_excerpt};
+use crate::similar::{jaccard, payload_shape_paths};
 use serde_json::{Value, json};
-use std::collections::HashMap;
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 fn split_key_parts(key: &str) -> (&str, &str, &str) {
     let mut parts = key.split('|');
@@ -60,13 +88,19 @@ pub fn compare_logs(
     options: &ComparisonOptions,
 ) -> Result<ComparisonResults, ComparisonError> {
     // Group logs by component and event type
-    let grouped_logs1 = group_logs_by_key(logs1, filter);
-    let grouped_logs2 = group_logs_by_key(logs2, filter);
+    let mut grouped_logs1 = group_logs_by_key(logs1, filter);
+    let mut grouped_logs2 = group_logs_by_key(logs2, filter);
+    let level_drift = detect_level_drift(
+        &level_counts_from_keys(grouped_logs1.iter().map(|(k, v)| (k.as_str(), v.len()))),
+        &level_counts_from_keys(grouped_logs2.iter().map(|(k, v)| (k.as_str(), v.len()))),
+        options.level_drift_threshold,
+    );
+    grouped_logs1.retain(|key, _| options.selects_key(key));
+    grouped_logs2.retain(|key, _| options.selects_key(key));
 
     // Find unique and shared keys
     let mut unique_to_log1 = Vec::new();
     let mut unique_to_log2 = Vec::new();
-    let mut shared_comparisons = Vec::new();
 
     // Find keys unique to logs1
     for key in grouped_logs1.keys() {
@@ -81,6 +115,22 @@ pub fn compare_logs(
             unique_to_log2.push(key.clone());
         }
     }
+    // Hash map order differs between runs; output indices must not
+    unique_to_log1.sort();
+    unique_to_log2.sort();
+    let unique_sources = |keys: &[String], grouped: &HashMap<String, Vec<&LogEntry>>| {
+        keys.iter()
+            .map(|key| {
+                let sources = grouped[key]
+                    .iter()
+                    .map(|log| GroupedEntry::from_log(log).source(options))
+                    .collect();
+                (key.clone(), sources)
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+    let unique_sources1 = unique_sources(&unique_to_log1, &grouped_logs1);
+    let unique_sources2 = unique_sources(&unique_to_log2, &grouped_logs2);
 
     // Process the shared keys
     let mut keys: Vec<String> = grouped_logs1
@@ -90,17 +140,98 @@ pub fn compare_logs(
         .collect();
 
     // Sort the keys based on the specified sort order
-    match options.sort_order {
+    sort_shared_keys(&mut keys, options.sort_order, |key| {
+        earliest_timestamp_for_key(key, &grouped_logs1, &grouped_logs2)
+    });
+
+    let mut results = ComparisonResults {
+        unique_to_log1,
+        unique_to_log2,
+        unique_sources1,
+        unique_sources2,
+        shared_comparisons: Vec::new(),
+        level_drift,
+        payload_errors: Vec::new(),
+        truncated_subtrees: 0,
+        identical_counts: BTreeMap::new(),
+        trivial_differences: 0,
+    };
+
+    for key in keys {
+        let entries1: Vec<GroupedEntry> = grouped_logs1[&key]
+            .iter()
+            .map(|log| GroupedEntry::from_log(log))
+            .collect();
+        let entries2: Vec<GroupedEntry> = grouped_logs2[&key]
+            .iter()
+            .map(|log| GroupedEntry::from_log(log))
+            .collect();
+
+        compare_key_group(&key, &entries1, &entries2, options, &mut results);
+    }
+
+    // Apply DiffCount sorting if selected
+    if options.sort_order == SortOrder::DiffCount {
+        results
+            .shared_comparisons
+            .sort_by(|a, b| b.json_differences.len().cmp(&a.json_differences.len()));
+    }
+
+    Ok(results)
+}
+
+/// Minimal view of a grouped log entry needed to pair and diff occurrences
+pub(crate) struct GroupedEntry<'a> {
+    pub line_number: usize,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub request_id: Option<&'a str>,
+    pub message: &'a str,
+    pub raw_logline: &'a str,
+    pub payload: Option<&'a Value>,
+}
+
+impl<'a> GroupedEntry<'a> {
+    fn from_log(log: &'a LogEntry) -> Self {
+        Self {
+            line_number: log.source_line_number,
+            timestamp: log.timestamp,
+            request_id: log.request_id(),
+            message: &log.message,
+            raw_logline: &log.raw_logline,
+            payload: log.payload(),
+        }
+    }
+
+    /// Reference to this entry for a unique instance it belongs to
+    pub(crate) fn source(&self, options: &ComparisonOptions) -> SourceEntry {
+        SourceEntry {
+            line_number: self.line_number,
+            timestamp: self.timestamp,
+            request_id: self.request_id.map(str::to_string),
+            raw: options
+                .raw_limit
+                .map(|limit| raw_excerpt(self.raw_logline, limit)),
+        }
+    }
+}
+
+/// Orders shared comparison keys according to the requested sort order, breaking ties by key
+pub(crate) fn sort_shared_keys(
+    keys: &mut [String],
+    sort_order: SortOrder,
+    first_timestamp: impl Fn(&str) -> Option<chrono::DateTime<chrono::Local>>,
+) {
+    match sort_order {
         SortOrder::Time => keys.sort_by(|a, b| {
-            let ts_a = earliest_timestamp_for_key(a, &grouped_logs1, &grouped_logs2);
-            let ts_b = earliest_timestamp_for_key(b, &grouped_logs1, &grouped_logs2);
+            let ts_a = first_timestamp(a);
+            let ts_b = first_timestamp(b);
             ts_a.cmp(&ts_b).then_with(|| a.cmp(b))
         }),
         SortOrder::Component => {
             keys.sort_by(|a, b| {
                 let (component_a, _, _) = split_key_parts(a);
                 let (component_b, _, _) = split_key_parts(b);
-                component_a.cmp(component_b)
+                component_a.cmp(component_b).then_with(|| a.cmp(b))
             });
         }
         SortOrder::Level => {
@@ -120,7 +251,10 @@ pub fn compare_logs(
             keys.sort_by(|a, b| {
                 let (_, level_a, _) = split_key_parts(a);
                 let (_, level_b, _) = split_key_parts(b);
-                level_priority(level_b).cmp(&level_priority(level_a)) // Higher priority first
+                // Higher priority first
+                level_priority(level_b)
+                    .cmp(&level_priority(level_a))
+                    .then_with(|| a.cmp(b))
             });
         }
         SortOrder::Type => {
@@ -128,100 +262,354 @@ pub fn compare_logs(
             keys.sort_by(|a, b| {
                 let (_, _, type_a) = split_key_parts(a);
                 let (_, _, type_b) = split_key_parts(b);
-                type_a.cmp(type_b)
+                type_a.cmp(type_b).then_with(|| a.cmp(b))
             });
         }
         SortOrder::DiffCount => {
-            // We'll need to calculate diff counts first, then sort
-            // This will be applied after computing all diffs
+            // Diff counts are applied after computing all diffs; the stable sort there
+            // keeps this key order for ties
+            keys.sort();
         }
     };
+}
 
-    for key in keys {
-        let entries1 = grouped_logs1.get(&key).unwrap();
-        let entries2 = grouped_logs2.get(&key).unwrap();
-
-        // Pair entries one-to-one by index to avoid N x M cross-product explosions.
-        let pair_count = entries1.len().min(entries2.len());
-        for idx in 0..pair_count {
-            let log1 = entries1[idx];
-            let log2 = entries2[idx];
-
-            if let (Some(payload1), Some(payload2)) = (log1.payload(), log2.payload()) {
-                let json_diffs = compare_json(payload1, payload2);
-
-                // Only process if there are differences or if we're not in diff_only mode
-                if !json_diffs.is_empty() || !options.diff_only {
-                    let (text1, text2) = if !json_diffs.is_empty() && log1.message != log2.message {
-                        (Some(log1.message.clone()), Some(log2.message.clone()))
-                    } else {
-                        (None, None)
-                    };
-
-                    shared_comparisons.push(LogComparison {
-                        key: key.clone(),
-                        log1_index: idx,
-                        log2_index: idx,
-                        json_differences: json_diffs
-                            .into_iter()
-                            .map(|(path, val1, val2)| {
-                                let change_type = determine_change_type(&val1, &val2);
-                                JsonDifference {
-                                    path,
-                                    value1: val1,
-                                    value2: val2,
-                                    change_type,
-                                }
-                            })
-                            .collect(),
-                        text1,
-                        text2,
-                        log1_line_number: log1.source_line_number,
-                        log2_line_number: log2.source_line_number,
-                        log1_payload: Some(payload1.to_owned()),
-                        log2_payload: Some(payload2.to_owned()),
-                    });
+/// Pairs the occurrences of one shared key and records differences and unpaired occurrences
+pub(crate) fn compare_key_group(
+    key: &str,
+    entries1: &[GroupedEntry],
+    entries2: &[GroupedEntry],
+    options: &ComparisonOptions,
+    results: &mut ComparisonResults,
+) {
+    let schemas = KeySchemas::new(entries1, entries2, options.max_json_depth);
+    if options.cluster_payloads && entries1.len() != entries2.len() {
+        compare_payload_clusters(key, entries1, entries2, &schemas, options, results);
+        return;
+    }
+
+    // Pair entries one-to-one by index to avoid N x M cross-product explosions.
+    let pair_count = entries1.len().min(entries2.len());
+    for idx in 0..pair_count {
+        compare_pair(
+            key,
+            (idx, &entries1[idx]),
+            (idx, &entries2[idx]),
+            None,
+            &schemas,
+            options,
+            results,
+        );
+    }
+
+    // Preserve unmatched occurrences so they are not silently dropped.
+    for (idx, log) in entries1.iter().enumerate().skip(pair_count) {
+        let instance = format!(
+            "{} [unpaired occurrence {} at line {}]",
+            key,
+            idx + 1,
+            log.line_number
+        );
+        results
+            .unique_sources1
+            .insert(instance.clone(), vec![log.source(options)]);
+        results.unique_to_log1.push(instance);
+    }
+
+    for (idx, log) in entries2.iter().enumerate().skip(pair_count) {
+        let instance = format!(
+            "{} [unpaired occurrence {} at line {}]",
+            key,
+            idx + 1,
+            log.line_number
+        );
+        results
+            .unique_sources2
+            .insert(instance.clone(), vec![log.source(options)]);
+        results.unique_to_log2.push(instance);
+    }
+}
+
+/// Payload field paths of one key across all of its occurrences in each file, used to tell
+/// fields one file never has apart from values that differ. Computed on first use.
+struct KeySchemas<'e, 'a> {
+    entries1: &'e [GroupedEntry<'a>],
+    entries2: &'e [GroupedEntry<'a>],
+    max_json_depth: Option<usize>,
+    paths: OnceCell<(BTreeSet<String>, BTreeSet<String>)>,
+}
+
+impl<'e, 'a> KeySchemas<'e, 'a> {
+    fn new(
+        entries1: &'e [GroupedEntry<'a>],
+        entries2: &'e [GroupedEntry<'a>],
+        max_json_depth: Option<usize>,
+    ) -> Self {
+        Self {
+            entries1,
+            entries2,
+            max_json_depth,
+            paths: OnceCell::new(),
+        }
+    }
+
+    /// Whether an added or removed value at `path` is a field only one file's schema has
+    fn change_at(&self, path: &str, change_type: ChangeType) -> Option<SchemaChange> {
+        if change_type == ChangeType::Modified {
+            return None;
+        }
+        let (paths1, paths2) = self.paths.get_or_init(|| {
+            let schema = |entries: &[GroupedEntry]| -> BTreeSet<String> {
+                entries
+                    .iter()
+                    .filter_map(|entry| entry.payload)
+                    .flat_map(|payload| payload_field_paths(payload, self.max_json_depth))
+                    .collect()
+            };
+            (schema(self.entries1), schema(self.entries2))
+        });
+        let path = fold_array_selectors(path);
+        match (paths1.contains(&path), paths2.contains(&path)) {
+            (false, true) => Some(SchemaChange::FieldAdded),
+            (true, false) => Some(SchemaChange::FieldRemoved),
+            _ => None,
+        }
+    }
+}
+
+/// Records the comparison of one occurrence pair when both carry a payload
+fn compare_pair(
+    key: &str,
+    (idx1, log1): (usize, &GroupedEntry),
+    (idx2, log2): (usize, &GroupedEntry),
+    cluster_sizes: Option<(usize, usize)>,
+    schemas: &KeySchemas,
+    options: &ComparisonOptions,
+    results: &mut ComparisonResults,
+) {
+    let (Some(payload1), Some(payload2)) = (log1.payload, log2.payload) else {
+        return;
+    };
+    let (mut json_diffs, truncated) = compare_json_limited(
+        payload1,
+        payload2,
+        &options.array_identity_keys,
+        options.max_json_depth,
+    );
+    results.truncated_subtrees += truncated;
+    json_diffs.retain(|(path, _, _)| !path_is_ignored(&options.ignore_paths, path));
+    let before_normalization = json_diffs.len();
+    json_diffs.retain(|(_, value1, value2)| match (value1, value2) {
+        (Value::String(text1), Value::String(text2)) => !options.texts_equivalent(text1, text2),
+        _ => true,
+    });
+    results.trivial_differences += before_normalization - json_diffs.len();
+
+    if json_diffs.is_empty() {
+        *results.identical_counts.entry(key.to_string()).or_default() += 1;
+        // Identical pairs are always counted; diff_only skips their details
+        if options.diff_only {
+            return;
+        }
+    }
+
+    let messages_differ = log1.message != log2.message;
+    let trivial_message = messages_differ && options.texts_equivalent(log1.message, log2.message);
+    if trivial_message && !json_diffs.is_empty() {
+        results.trivial_differences += 1;
+    }
+    let (text1, text2) = if !json_diffs.is_empty() && messages_differ && !trivial_message {
+        (
+            Some(log1.message.to_string()),
+            Some(log2.message.to_string()),
+        )
+    } else {
+        (None, None)
+    };
+
+    results.shared_comparisons.push(LogComparison {
+        key: key.to_string(),
+        log1_index: idx1,
+        log2_index: idx2,
+        json_differences: json_diffs
+            .into_iter()
+            .map(|(path, val1, val2)| {
+                let change_type = determine_change_type(&val1, &val2);
+                JsonDifference {
+                    schema_change: schemas.change_at(&path, change_type),
+                    path,
+                    value1: val1,
+                    value2: val2,
+                    change_type,
                 }
-            }
+            })
+            .collect(),
+        text1,
+        text2,
+        log1_line_number: log1.line_number,
+        log2_line_number: log2.line_number,
+        log1_timestamp: log1.timestamp,
+        log2_timestamp: log2.timestamp,
+        log1_request_id: log1.request_id.map(str::to_string),
+        log2_request_id: log2.request_id.map(str::to_string),
+        log1_payload: Some(payload1.to_owned()),
+        log2_payload: Some(payload2.to_owned()),
+        cluster_sizes,
+        log1_raw: options
+            .raw_limit
+            .map(|limit| raw_excerpt(log1.raw_logline, limit)),
+        log2_raw: options
+            .raw_limit
+            .map(|limit| raw_excerpt(log2.raw_logline, limit)),
+    });
+}
+
+/// Minimum Jaccard similarity of typed payload paths for two payloads to share a cluster
+const PAYLOAD_CLUSTER_SIMILARITY: f64 = 0.8;
+
+type PayloadShape = Option<BTreeSet<String>>;
+
+fn shape_similarity(a: &PayloadShape, b: &PayloadShape) -> f64 {
+    match (a, b) {
+        (Some(a), Some(b)) => jaccard(a, b),
+        (None, None) => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// Groups occurrence indexes by payload shape, in order of first appearance. An entry
+/// joins the first cluster whose representative's shape is similar enough, so an
+/// optional field does not split a request type into separate clusters
+fn cluster_by_payload_shape(
+    entries: &[GroupedEntry],
+    max_depth: Option<usize>,
+) -> Vec<(PayloadShape, Vec<usize>)> {
+    let mut clusters: Vec<(PayloadShape, Vec<usize>)> = Vec::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        let shape = entry
+            .payload
+            .map(|payload| payload_shape_paths(payload, max_depth));
+        match clusters
+            .iter_mut()
+            .find(|(existing, _)| shape_similarity(existing, &shape) >= PAYLOAD_CLUSTER_SIMILARITY)
+        {
+            Some((_, members)) => members.push(idx),
+            None => clusters.push((shape, vec![idx])),
         }
+    }
+    clusters
+}
 
-        // Preserve unmatched occurrences so they are not silently dropped.
-        if entries1.len() > pair_count {
-            for (idx, log) in entries1.iter().enumerate().skip(pair_count) {
-                unique_to_log1.push(format!(
-                    "{} [unpaired occurrence {} at line {}]",
+/// Compares one representative per payload-shape cluster instead of pairing by index,
+/// for keys whose occurrence counts differ between the files. Clusters pair with the
+/// most similar unpaired cluster of the other file
+fn compare_payload_clusters(
+    key: &str,
+    entries1: &[GroupedEntry],
+    entries2: &[GroupedEntry],
+    schemas: &KeySchemas,
+    options: &ComparisonOptions,
+    results: &mut ComparisonResults,
+) {
+    let clusters1 = cluster_by_payload_shape(entries1, options.max_json_depth);
+    let clusters2 = cluster_by_payload_shape(entries2, options.max_json_depth);
+    let mut matched2 = vec![false; clusters2.len()];
+
+    for (shape, members1) in &clusters1 {
+        let representative1 = members1[0];
+        // Pair with the most similar cluster of the other file that is still free
+        let counterpart = clusters2
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !matched2[*idx])
+            .map(|(idx, (other, _))| (idx, shape_similarity(shape, other)))
+            .filter(|(_, similarity)| *similarity >= PAYLOAD_CLUSTER_SIMILARITY)
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)));
+        match counterpart {
+            Some((idx, _)) => {
+                matched2[idx] = true;
+                let members2 = &clusters2[idx].1;
+                let representative2 = members2[0];
+                compare_pair(
                     key,
-                    idx + 1,
-                    log.source_line_number
-                ));
+                    (representative1, &entries1[representative1]),
+                    (representative2, &entries2[representative2]),
+                    Some((members1.len(), members2.len())),
+                    schemas,
+                    options,
+                    results,
+                );
             }
-        }
-
-        if entries2.len() > pair_count {
-            for (idx, log) in entries2.iter().enumerate().skip(pair_count) {
-                unique_to_log2.push(format!(
-                    "{} [unpaired occurrence {} at line {}]",
+            None => {
+                let instance = format!(
+                    "{} [payload cluster of {} at line {}]",
                     key,
-                    idx + 1,
-                    log.source_line_number
-                ));
+                    members1.len(),
+                    entries1[representative1].line_number
+                );
+                let sources = members1
+                    .iter()
+                    .map(|&idx| entries1[idx].source(options))
+                    .collect();
+                results.unique_sources1.insert(instance.clone(), sources);
+                results.unique_to_log1.push(instance);
             }
         }
     }
 
-    // Apply DiffCount sorting if selected
-    if options.sort_order == SortOrder::DiffCount {
-        shared_comparisons.sort_by(|a, b| b.json_differences.len().cmp(&a.json_differences.len()));
+    for ((_, members2), matched) in clusters2.iter().zip(matched2) {
+        if !matched {
+            let instance = format!(
+                "{} [payload cluster of {} at line {}]",
+                key,
+                members2.len(),
+                entries2[members2[0]].line_number
+            );
+            let sources = members2
+                .iter()
+                .map(|&idx| entries2[idx].source(options))
+                .collect();
+            results.unique_sources2.insert(instance.clone(), sources);
+            results.unique_to_log2.push(instance);
+        }
     }
+}
 
-    // Create and return results
-    let results = ComparisonResults {
-        unique_to_log1,
-        unique_to_log2,
-        shared_comparisons,
-    };
+/// Structural signature of a JSON value: object keys and value types, ignoring values
+pub fn payload_shape(value: &Value) -> String {
+    payload_shape_limited(value, None)
+}
 
-    Ok(results)
+/// [`payload_shape`] that stops descending `max_depth` levels below the root, where
+/// nested objects and arrays contribute only their type
+pub fn payload_shape_limited(value: &Value, max_depth: Option<usize>) -> String {
+    let nested_depth = match max_depth {
+        Some(0) if value.is_object() => return "object".to_string(),
+        Some(0) if value.is_array() => return "array".to_string(),
+        other => other.map(|max| max - 1),
+    };
+    match value {
+        Value::Object(map) => {
+            let mut fields: Vec<String> = map
+                .iter()
+                .map(|(key, value)| format!("{key}:{}", payload_shape_limited(value, nested_depth)))
+                .collect();
+            fields.sort();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let mut shapes: Vec<String> = items
+                .iter()
+                .map(|item| payload_shape_limited(item, nested_depth))
+                .collect();
+            shapes.sort();
+            shapes.dedup();
+            format!("[{}]", shapes.join("|"))
+        }
+        Value::String(_) => "string".to_string(),
+        Value::Number(_) => "number".to_string(),
+        Value::Bool(_) => "bool".to_string(),
+        Value::Null => "null".to_string(),
+    }
 }
 
 /// Compares two JSON values and returns a vector of differences.
@@ -229,137 +617,316 @@ pub fn compare_logs(
 /// Each difference is represented as a tuple with the JSON path and the differing values.
 /// This function compares values semantically, ignoring the order of object properties.
 pub fn compare_json(json1: &Value, json2: &Value) -> Vec<(String, Value, Value)> {
-    let mut differences = Vec::new();
-    compare_json_recursive(json1, json2, "".to_string(), &mut differences);
-    differences
+    compare_json_with_identity(json1, json2, &BTreeMap::new())
 }
 
-fn compare_json_recursive(
+/// Like [`compare_json`], but arrays of objects at the paths in `identity_keys`
+/// (index-free array path → key field) are paired by that field's value instead of
+/// best-effort similarity. Unpaired elements are reported as added or removed.
+pub fn compare_json_with_identity(
     json1: &Value,
     json2: &Value,
-    path: String,
-    differences: &mut Vec<(String, Value, Value)>,
-) {
-    match (json1, json2) {
-        (Value::Object(obj1), Value::Object(obj2)) => {
-            compare_objects(obj1, obj2, path, differences);
-        }
-        (Value::Array(arr1), Value::Array(arr2)) => {
-            compare_arrays(arr1, arr2, path, differences);
+    identity_keys: &BTreeMap<String, String>,
+) -> Vec<(String, Value, Value)> {
+    compare_json_limited(json1, json2, identity_keys, None).0
+}
+
+/// Like [`compare_json_with_identity`], but objects and arrays nested `max_depth` levels
+/// below the root are compared as opaque blobs: any change inside one is reported as a
+/// single difference at its path. Also returns how many subtrees were not descended.
+pub fn compare_json_limited(
+    json1: &Value,
+    json2: &Value,
+    identity_keys: &BTreeMap<String, String>,
+    max_depth: Option<usize>,
+) -> (Vec<(String, Value, Value)>, usize) {
+    let mut walk = DiffWalk::new(identity_keys, max_depth);
+    walk.compare(json1, json2, "".to_string(), 0);
+    (walk.differences, walk.truncated)
+}
+
+/// Compares two JSON values and drops differences suppressed by the given rules
+pub fn compare_json_with_rules(
+    json1: &Value,
+    json2: &Value,
+    rules: &JsonDiffRules,
+) -> Vec<(String, Value, Value)> {
+    compare_json_limited(json1, json2, &rules.array_identity_keys, rules.max_depth)
+        .0
+        .into_iter()
+        .filter(|(path, value1, value2)| !rules.ignores(path, value1, value2))
+        .collect()
+}
+
+/// State of one recursive payload comparison
+struct DiffWalk<'a> {
+    identity_keys: &'a BTreeMap<String, String>,
+    max_depth: Option<usize>,
+    differences: Vec<(String, Value, Value)>,
+    /// Container pairs compared as opaque blobs because of `max_depth`
+    truncated: usize,
+}
+
+impl<'a> DiffWalk<'a> {
+    fn new(identity_keys: &'a BTreeMap<String, String>, max_depth: Option<usize>) -> Self {
+        Self {
+            identity_keys,
+            max_depth,
+            differences: Vec::new(),
+            truncated: 0,
         }
-        (val1, val2) => {
-            if val1 != val2 {
-                differences.push((path, val1.clone(), val2.clone()));
+    }
+
+    fn compare(&mut self, json1: &Value, json2: &Value, path: String, depth: usize) {
+        let is_container = |value: &Value| value.is_object() || value.is_array();
+        if self.max_depth.is_some_and(|max| depth >= max)
+            && (is_container(json1) || is_container(json2))
+        {
+            self.truncated += 1;
+            if json1 != json2 {
+                self.differences.push((path, json1.clone(), json2.clone()));
             }
+            return;
         }
-    }
-}
 
-/// Compares two JSON objects
-fn compare_objects(
-    obj1: &serde_json::Map<String, Value>,
-    obj2: &serde_json::Map<String, Value>,
-    path: String,
-    differences: &mut Vec<(String, Value, Value)>,
-) {
-    // Check keys that exist in both objects.
-    for (key, val1) in obj1 {
-        let current_path = if path.is_empty() {
-            key.clone()
-        } else {
-            format!("{}.{}", path, key)
-        };
-
-        match obj2.get(key) {
-            Some(val2) => compare_json_recursive(val1, val2, current_path, differences),
-            None => differences.push((current_path, val1.clone(), json!(null))),
+        match (json1, json2) {
+            (Value::Object(obj1), Value::Object(obj2)) => {
+                self.compare_objects(obj1, obj2, path, depth + 1);
+            }
+            (Value::Array(arr1), Value::Array(arr2)) => {
+                self.compare_arrays(arr1, arr2, path, depth + 1);
+            }
+            (val1, val2) => {
+                if val1 != val2 {
+                    self.differences.push((path, val1.clone(), val2.clone()));
+                }
+            }
         }
     }
 
-    // Check keys that only exist in obj2.
-    for (key, val2) in obj2 {
-        if !obj1.contains_key(key) {
+    /// Compares two JSON objects whose members sit at `depth`
+    fn compare_objects(
+        &mut self,
+        obj1: &serde_json::Map<String, Value>,
+        obj2: &serde_json::Map<String, Value>,
+        path: String,
+        depth: usize,
+    ) {
+        // Check keys that exist in both objects.
+        for (key, val1) in obj1 {
             let current_path = if path.is_empty() {
                 key.clone()
             } else {
                 format!("{}.{}", path, key)
             };
-            differences.push((current_path, json!(null), val2.clone()));
+
+            match obj2.get(key) {
+                Some(val2) => self.compare(val1, val2, current_path, depth),
+                None => self
+                    .differences
+                    .push((current_path, val1.clone(), json!(null))),
+            }
+        }
+
+        // Check keys that only exist in obj2.
+        for (key, val2) in obj2 {
+            if !obj1.contains_key(key) {
+                let current_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                self.differences
+                    .push((current_path, json!(null), val2.clone()));
+            }
         }
     }
-}
 
-/// Compares two JSON arrays
-fn compare_arrays(
-    arr1: &[Value],
-    arr2: &[Value],
-    path: String,
-    differences: &mut Vec<(String, Value, Value)>,
-) {
-    // Special handling for arrays containing objects
-    if arr1.len() == arr2.len()
-        && arr1.iter().all(|v| v.is_object())
-        && arr2.iter().all(|v| v.is_object())
-    {
-        compare_object_arrays(arr1, arr2, path, differences);
-        return;
+    /// Compares two JSON arrays whose elements sit at `depth`
+    fn compare_arrays(&mut self, arr1: &[Value], arr2: &[Value], path: String, depth: usize) {
+        if !self.identity_keys.is_empty()
+            && let Some(key_field) = self.identity_keys.get(&strip_array_indices(&path))
+            && arr1.iter().chain(arr2).all(|v| v.is_object())
+        {
+            self.compare_identified_arrays(arr1, arr2, path, key_field, depth);
+            return;
+        }
+
+        // Special handling for arrays containing objects
+        if arr1.len() == arr2.len()
+            && arr1.iter().all(|v| v.is_object())
+            && arr2.iter().all(|v| v.is_object())
+        {
+            self.compare_object_arrays(arr1, arr2, path, depth);
+            return;
+        }
+
+        // Standard array comparison for non-object arrays or different length arrays
+        let max_len = arr1.len().max(arr2.len());
+        for i in 0..max_len {
+            let current_path = format!("{}[{}]", path, i);
+            if i < arr1.len() && i < arr2.len() {
+                self.compare(&arr1[i], &arr2[i], current_path, depth);
+            } else if i < arr1.len() {
+                self.differences
+                    .push((current_path.clone(), arr1[i].clone(), json!(null)));
+            } else {
+                self.differences
+                    .push((current_path.clone(), json!(null), arr2[i].clone()));
+            }
+        }
+    }
+
+    /// Compares arrays of objects by the value of their identity key field. Elements
+    /// missing the field are paired by position among themselves.
+    fn compare_identified_arrays(
+        &mut self,
+        arr1: &[Value],
+        arr2: &[Value],
+        path: String,
+        key_field: &str,
+        depth: usize,
+    ) {
+        let identity = |value: &Value| value.get(key_field).map(identity_label);
+        let mut unmatched2: Vec<usize> = (0..arr2.len()).collect();
+        let mut keyless1: Vec<usize> = Vec::new();
+
+        for (i, obj1) in arr1.iter().enumerate() {
+            let Some(id) = identity(obj1) else {
+                keyless1.push(i);
+                continue;
+            };
+            let current_path = format!("{path}[{key_field}={id}]");
+            match unmatched2
+                .iter()
+                .position(|&j| identity(&arr2[j]).as_deref() == Some(id.as_str()))
+            {
+                Some(pos) => {
+                    let j = unmatched2.remove(pos);
+                    self.compare(obj1, &arr2[j], current_path, depth);
+                }
+                None => self
+                    .differences
+                    .push((current_path, obj1.clone(), json!(null))),
+            }
+        }
+
+        let mut keyless2 = Vec::new();
+        for j in unmatched2 {
+            match identity(&arr2[j]) {
+                Some(id) => self.differences.push((
+                    format!("{path}[{key_field}={id}]"),
+                    json!(null),
+                    arr2[j].clone(),
+                )),
+                None => keyless2.push(j),
+            }
+        }
+
+        for pos in 0..keyless1.len().max(keyless2.len()) {
+            match (keyless1.get(pos), keyless2.get(pos)) {
+                (Some(&i), Some(&j)) => {
+                    self.compare(&arr1[i], &arr2[j], format!("{path}[{i}]"), depth)
+                }
+                (Some(&i), None) => {
+                    self.differences
+                        .push((format!("{path}[{i}]"), arr1[i].clone(), json!(null)))
+                }
+                (None, Some(&j)) => {
+                    self.differences
+                        .push((format!("{path}[{j}]"), json!(null), arr2[j].clone()))
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    /// Compares arrays of objects using best-match strategy
+    fn compare_object_arrays(
+        &mut self,
+        arr1: &[Value],
+        arr2: &[Value],
+        path: String,
+        depth: usize,
+    ) {
+        let mut matched_indices = vec![false; arr2.len()];
+
+        for (i, obj1) in arr1.iter().enumerate() {
+            let mut best_match_idx = None;
+            let mut fewest_differences = usize::MAX;
+
+            // Find the best matching object in arr2
+            for (j, obj2) in arr2.iter().enumerate() {
+                if !matched_indices[j] {
+                    let mut trial = DiffWalk::new(self.identity_keys, self.max_depth);
+                    trial.compare(obj1, obj2, "temp".to_string(), depth);
+
+                    if trial.differences.is_empty() {
+                        // Perfect match
+                        best_match_idx = Some(j);
+                        break;
+                    } else if trial.differences.len() < fewest_differences {
+                        fewest_differences = trial.differences.len();
+                        best_match_idx = Some(j);
+                    }
+                }
+            }
+
+            // Compare with best match
+            if let Some(j) = best_match_idx {
+                matched_indices[j] = true;
+                let current_path = format!("{}[{}]", path, i);
+                self.compare(&arr1[i], &arr2[j], current_path, depth);
+            }
+        }
     }
+}
 
-    // Standard array comparison for non-object arrays or different length arrays
-    let max_len = arr1.len().max(arr2.len());
-    for i in 0..max_len {
-        let current_path = format!("{}[{}]", path, i);
-        if i < arr1.len() && i < arr2.len() {
-            compare_json_recursive(&arr1[i], &arr2[i], current_path, differences);
-        } else if i < arr1.len() {
-            differences.push((current_path.clone(), arr1[i].clone(), json!(null)));
-        } else {
-            differences.push((current_path.clone(), json!(null), arr2[i].clone()));
+/// Array path with element indices and identity selectors removed (`a[0].b[id=x]` → `a.b`)
+fn strip_array_indices(path: &str) -> String {
+    let mut stripped = String::with_capacity(path.len());
+    let mut depth = 0usize;
+    for c in path.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => stripped.push(c),
+            _ => {}
         }
     }
+    stripped
 }
 
-/// Compares arrays of objects using best-match strategy
-fn compare_object_arrays(
-    arr1: &[Value],
-    arr2: &[Value],
-    path: String,
-    differences: &mut Vec<(String, Value, Value)>,
-) {
-    let mut matched_indices = vec![false; arr2.len()];
-
-    for (i, obj1) in arr1.iter().enumerate() {
-        let mut best_match_idx = None;
-        let mut fewest_differences = usize::MAX;
-
-        // Find the best matching object in arr2
-        for (j, obj2) in arr2.iter().enumerate() {
-            if !matched_indices[j] {
-                let mut temp_differences = Vec::new();
-                compare_json_recursive(obj1, obj2, "temp".to_string(), &mut temp_differences);
-
-                if temp_differences.is_empty() {
-                    // Perfect match
-                    best_match_idx = Some(j);
-                    break;
-                } else if temp_differences.len() < fewest_differences {
-                    fewest_differences = temp_differences.len();
-                    best_match_idx = Some(j);
+/// Path with every array index or identity selector folded to `[]` (`a[0].b[id=x]` →
+/// `a[].b[]`), the form the schema analysis records
+fn fold_array_selectors(path: &str) -> String {
+    let mut folded = String::with_capacity(path.len());
+    let mut depth = 0usize;
+    for c in path.chars() {
+        match c {
+            '[' => {
+                if depth == 0 {
+                    folded.push_str("[]");
                 }
+                depth += 1;
             }
+            ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => folded.push(c),
+            _ => {}
         }
+    }
+    folded
+}
 
-        // Compare with best match
-        if let Some(j) = best_match_idx {
-            matched_indices[j] = true;
-            let current_path = format!("{}[{}]", path, i);
-            compare_json_recursive(&arr1[i], &arr2[j], current_path, differences);
-        }
+fn identity_label(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
     }
 }
 
 /// Determines the type of change based on the two values
-fn determine_change_type(val1: &Value, val2: &Value) -> ChangeType {
+pub(crate) fn determine_change_type(val1: &Value, val2: &Value) -> ChangeType {
     match (val1.is_null(), val2.is_null()) {
         (true, false) => ChangeType::Added,
         (false, true) => ChangeType::Removed,
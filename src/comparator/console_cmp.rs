@@ -2,6 +2,7 @@ use crate::ComparisonOptions;
 use crate::comparator::ComparisonResults;
 use crate::comparator::format_cmp::OutputFormatter;
 use crate::comparator::format_cmp::format_comparison_results;
+use crate::render::RenderOptions;
 use colored::Colorize;
 use comfy_table::Table;
 use std::io;
@@ -18,16 +19,22 @@ pub fn should_print(options: &ComparisonOptions, required_level: u8) -> bool {
 }
 
 /// Console output formatter implementation with improved styling
-pub struct ConsoleFormatter;
+pub struct ConsoleFormatter {
+    pub render: RenderOptions,
+}
 
 impl OutputFormatter for ConsoleFormatter {
     fn write_header(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.bold().bright_white().on_bright_black());
+        println!(
+            "{}",
+            self.render
+                .paint(text.bold().bright_white().on_bright_black())
+        );
         Ok(())
     }
 
     fn write_divider(&mut self, char: &str, count: usize) -> io::Result<()> {
-        println!("{}", char.repeat(count).bright_white());
+        println!("{}", self.render.paint(char.repeat(count).bright_white()));
         Ok(())
     }
 
@@ -37,43 +44,43 @@ impl OutputFormatter for ConsoleFormatter {
     }
 
     fn write_source_file1(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.cyan());
+        println!("{}", self.render.paint(text.cyan()));
         Ok(())
     }
 
     fn write_source_file2(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.magenta());
+        println!("{}", self.render.paint(text.magenta()));
         Ok(())
     }
 
     fn write_highlight(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.yellow().bold());
+        println!("{}", self.render.paint(text.yellow().bold()));
         Ok(())
     }
 
     fn write_label(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.bold().bright_blue());
+        println!("{}", self.render.paint(text.bold().bright_blue()));
         Ok(())
     }
 
     // New methods with semantic coloring
     fn write_success(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.green().bold());
+        println!("{}", self.render.paint(text.green().bold()));
         Ok(())
     }
 
     fn write_warning(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.yellow().bold());
+        println!("{}", self.render.paint(text.yellow().bold()));
         Ok(())
     }
 
     fn write_error(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.red().bold());
+        println!("{}", self.render.paint(text.red().bold()));
         Ok(())
     }
 
     fn write_info(&mut self, text: &str) -> io::Result<()> {
-        println!("{}", text.bright_white().bold());
+        println!("{}", self.render.paint(text.bright_white().bold()));
         Ok(())
     }
 
@@ -85,7 +92,9 @@ impl OutputFormatter for ConsoleFormatter {
 
 /// Formats and displays the comparison results to the console
 pub fn display_comparison_results(results: &ComparisonResults, options: &ComparisonOptions) {
-    let mut formatter = ConsoleFormatter;
+    let mut formatter = ConsoleFormatter {
+        render: options.render,
+    };
     // Ignore the result since console output errors are rare and there's not much we can do about them
     let _ = format_comparison_results(&mut formatter, results, options);
 }
@@ -1,10 +1,19 @@
 use crate::comparator::create_styled_table;
+use crate::config::SchemaRules;
+use crate::render::RenderOptions;
+use crate::time_buckets::{TimeBucketOptions, build_time_histogram};
 use crate::{LogEntry, LogEntryKind};
 use chrono::{DateTime, Local};
 use colored::ColoredString;
 use colored::{Color, Colorize};
 use comfy_table::Cell;
-use std::collections::HashMap;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+static ARRAY_INDEX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[\d+\]").expect("valid array index regex"));
 
 /// Displays all statistics about the logs with improved formatting and organization
 ///
@@ -13,13 +22,22 @@ use std::collections::HashMap;
 /// * `show_samples` - Whether to show sample log messages for each component
 /// * `show_json_schema` - Whether to display JSON schema information for payloads
 /// * `show_payload_stats` - Whether to show payload statistics
-/// * `show_timeline` - Whether to show detailed timeline analysis
+/// * `timeline` - Bucketing for the detailed timeline analysis, when it should be shown
+/// * `schema_rules` - Example values and masking for the JSON schema analysis
+/// * `max_json_depth` - Nesting depth at which the JSON schema analysis stops descending
+/// * `scope` - The `--lines` / `--bytes` slice the logs were read from, shown in the header
+/// * `render` - Colouring of the printed report
+#[allow(clippy::too_many_arguments)]
 pub fn display_log_summary(
     logs: &[LogEntry],
     show_samples: bool,
     show_json_schema: bool,
     show_payload_stats: bool,
-    show_timeline: bool,
+    timeline: Option<TimeBucketOptions>,
+    schema_rules: &SchemaRules,
+    max_json_depth: Option<usize>,
+    scope: Option<&str>,
+    render: &RenderOptions,
 ) {
     // Count entries by type for better statistics
     let mut component_counts: HashMap<&str, usize> = HashMap::new();
@@ -37,9 +55,7 @@ pub fn display_log_summary(
     let mut request_payload_sizes: HashMap<&str, Vec<usize>> = HashMap::new();
 
     // For JSON schema analysis
-    let mut event_payload_keys: HashMap<&str, HashMap<String, usize>> = HashMap::new();
-    let mut command_payload_keys: HashMap<&str, HashMap<String, usize>> = HashMap::new();
-    let mut request_payload_keys: HashMap<&str, HashMap<String, usize>> = HashMap::new();
+    let mut schema = SchemaReport::default();
 
     // For timeline analysis
     let mut timestamps: Vec<DateTime<Local>> = Vec::new();
@@ -60,7 +76,7 @@ pub fn display_log_summary(
         }
 
         // Add timestamp for timeline analysis
-        if show_timeline {
+        if timeline.is_some() {
             timestamps.push(log.timestamp);
             component_timeline
                 .entry(&log.component)
@@ -68,6 +84,10 @@ pub fn display_log_summary(
                 .push(log.timestamp);
         }
 
+        if show_json_schema {
+            schema.record(log, schema_rules, max_json_depth);
+        }
+
         match &log.kind {
             LogEntryKind::Event {
                 event_type,
@@ -86,15 +106,6 @@ pub fn display_log_summary(
                         .or_default()
                         .push(payload_size);
                 }
-
-                // Collect JSON schema information
-                if show_json_schema && payload.is_some() {
-                    collect_json_keys(
-                        payload.as_ref().unwrap(),
-                        "",
-                        event_payload_keys.entry(event_type).or_default(),
-                    );
-                }
             }
             LogEntryKind::Command {
                 command, settings, ..
@@ -111,15 +122,6 @@ pub fn display_log_summary(
                         .or_default()
                         .push(payload_size);
                 }
-
-                // Collect JSON schema information
-                if show_json_schema && settings.is_some() {
-                    collect_json_keys(
-                        settings.as_ref().unwrap(),
-                        "",
-                        command_payload_keys.entry(command).or_default(),
-                    );
-                }
             }
             LogEntryKind::Request {
                 request, payload, ..
@@ -136,15 +138,6 @@ pub fn display_log_summary(
                         .or_default()
                         .push(payload_size);
                 }
-
-                // Collect JSON schema information
-                if show_json_schema && payload.is_some() {
-                    collect_json_keys(
-                        payload.as_ref().unwrap(),
-                        "",
-                        request_payload_keys.entry(request).or_default(),
-                    );
-                }
             }
             LogEntryKind::Generic { .. } => {}
         }
@@ -154,12 +147,18 @@ pub fn display_log_summary(
     let total_entries = logs.len();
 
     // Display header
-    println!("{}", "=".repeat(80).bright_white());
-    println!("{}", "LOG SUMMARY REPORT".bold().bright_white());
-    println!("{}", "=".repeat(80).bright_white());
+    println!("{}", render.paint("=".repeat(80).bright_white()));
+    println!(
+        "{}",
+        render.paint("LOG SUMMARY REPORT".bold().bright_white())
+    );
+    println!("{}", render.paint("=".repeat(80).bright_white()));
+    if let Some(scope) = scope {
+        println!("Scope: {scope} of each file");
+    }
     println!(
         "Total log entries: {}",
-        total_entries.to_string().green().bold()
+        render.paint(total_entries.to_string().green().bold())
     );
 
     // Helper function to print sorted counts with percentages using styled tables
@@ -174,8 +173,8 @@ pub fn display_log_summary(
             let mut items: Vec<(&str, usize)> = counts.into_iter().collect();
             items.sort_by(|a, b| b.1.cmp(&a.1));
 
-            println!("\n{}", title.bold());
-            println!("{}", "-".repeat(80).bright_black());
+            println!("\n{}", render.paint(title.bold()));
+            println!("{}", render.paint("-".repeat(80).bright_black()));
 
             let mut table = create_styled_table(&["Name", "Count", "Percent", "Distribution"]);
 
@@ -222,26 +221,28 @@ pub fn display_log_summary(
 
     // Display timeline summary if we have timestamps in the logs
     if let Some((earliest, latest)) = get_time_range(logs) {
-        println!("\n{}", "TIME RANGE".bold());
-        println!("{}", "-".repeat(80).bright_black());
-        println!("  Earliest: {}", earliest.to_string().cyan());
-        println!("  Latest:   {}", latest.to_string().cyan());
+        println!("\n{}", render.paint("TIME RANGE".bold()));
+        println!("{}", render.paint("-".repeat(80).bright_black()));
+        println!("  Earliest: {}", render.paint(earliest.to_string().cyan()));
+        println!("  Latest:   {}", render.paint(latest.to_string().cyan()));
 
         if let Ok(duration) = latest.signed_duration_since(earliest).to_std() {
             let duration_str = format_duration(duration);
-            println!("  Span:     {}", duration_str.green());
+            println!("  Span:     {}", render.paint(duration_str.green()));
         }
 
         // Enhanced timeline analysis if requested
-        if show_timeline && timestamps.len() > 5 {
-            display_timeline_analysis(&timestamps, &component_timeline);
+        if let Some(buckets) = &timeline
+            && timestamps.len() > 5
+        {
+            display_timeline_analysis(&timestamps, &component_timeline, buckets, render);
         }
     }
 
     // Display component samples if requested
     if show_samples && !component_samples.is_empty() {
-        println!("\n{}", "SAMPLE MESSAGES".bold());
-        println!("{}", "-".repeat(80).bright_black());
+        println!("\n{}", render.paint("SAMPLE MESSAGES".bold()));
+        println!("{}", render.paint("-".repeat(80).bright_black()));
 
         // Convert to vec and sort by count (using component_counts for ordering)
         let mut components: Vec<(&str, &Vec<&LogEntry>)> =
@@ -255,7 +256,7 @@ pub fn display_log_summary(
         for (component, samples) in components {
             println!(
                 "\n  {} ({} entries):",
-                component.cyan().bold(),
+                render.paint(component.cyan().bold()),
                 component_counts.get(component).unwrap_or(&0)
             );
 
@@ -268,8 +269,8 @@ pub fn display_log_summary(
 
                 println!(
                     "    {}. [{}] {}",
-                    (i + 1).to_string().bright_white(),
-                    sample.level.as_str().color(get_level_color(&sample.level)),
+                    render.paint((i + 1).to_string().bright_white()),
+                    render.paint(sample.level.as_str().color(get_level_color(&sample.level))),
                     short_msg
                 );
             }
@@ -282,8 +283,8 @@ pub fn display_log_summary(
             || !command_payload_sizes.is_empty()
             || !request_payload_sizes.is_empty())
     {
-        println!("\n{}", "PAYLOAD STATISTICS".bold());
-        println!("{}", "-".repeat(80).bright_black());
+        println!("\n{}", render.paint("PAYLOAD STATISTICS".bold()));
+        println!("{}", render.paint("-".repeat(80).bright_black()));
 
         // Helper function to display payload stats for a specific type
         let display_payload_stats = |title: &str, stats_map: &HashMap<&str, Vec<usize>>| {
@@ -291,7 +292,7 @@ pub fn display_log_summary(
                 return;
             }
 
-            println!("\n  {}:", title.bright_white().bold());
+            println!("\n  {}:", render.paint(title.bright_white().bold()));
 
             let mut table = create_styled_table(&["Name", "Count", "Avg (bytes)", "Min", "Max"]);
 
@@ -325,55 +326,81 @@ pub fn display_log_summary(
         display_payload_stats("REQUEST PAYLOADS", &request_payload_sizes);
     }
 
-    // Display JSON schema information if requested
-    if show_json_schema
-        && (!event_payload_keys.is_empty()
-            || !command_payload_keys.is_empty()
-            || !request_payload_keys.is_empty())
-    {
-        println!("\n{}", "JSON SCHEMA ANALYSIS".bold());
-        println!("{}", "-".repeat(80).bright_black());
-
-        // Helper function to display schema for a specific type
-        let display_schema = |title: &str,
-                              schema_map: &HashMap<&str, HashMap<String, usize>>,
-                              occurrence_counts: &HashMap<&str, usize>,
-                              name_color: fn(&str) -> ColoredString| {
+    if show_json_schema {
+        display_schema_report(&schema, schema_rules, max_json_depth, render);
+    }
+}
+
+/// Prints the JSON schema analysis of `info --json-schema`
+pub fn display_schema_report(
+    report: &SchemaReport,
+    schema_rules: &SchemaRules,
+    max_json_depth: Option<usize>,
+    render: &RenderOptions,
+) {
+    if report.is_empty() {
+        return;
+    }
+    println!("\n{}", render.paint("JSON SCHEMA ANALYSIS".bold()));
+    println!("{}", render.paint("-".repeat(80).bright_black()));
+
+    // Example values share one byte budget across the whole report
+    let mut example_budget = schema_rules.example_byte_budget;
+    let mut budget_exhausted = false;
+
+    // Helper function to display schema for a specific type
+    let mut display_schema =
+        |title: &str, section: &SchemaSection, name_color: fn(&str) -> ColoredString| {
+            let (schema_map, occurrence_counts) = (&section.schemas, &section.counts);
             if schema_map.is_empty() {
                 return;
             }
 
-            println!("\n  {}:", title.bright_white().bold());
+            println!("\n  {}:", render.paint(title.bright_white().bold()));
 
             // Convert to vec and sort by frequency
-            let mut items: Vec<(&str, &HashMap<String, usize>)> =
-                schema_map.iter().map(|(k, v)| (*k, v)).collect();
+            let mut items: Vec<(&str, &SchemaStats)> =
+                schema_map.iter().map(|(k, v)| (k.as_str(), v)).collect();
             items.sort_by(|a, b| {
                 let count_a = occurrence_counts.get(a.0).unwrap_or(&0);
                 let count_b = occurrence_counts.get(b.0).unwrap_or(&0);
-                count_b.cmp(count_a) // Sort by frequency (most frequent first)
+                // Sort by frequency (most frequent first), then by name
+                count_b.cmp(count_a).then_with(|| a.0.cmp(b.0))
             });
 
-            for (name, keys) in items {
+            for (name, stats) in items {
                 println!(
                     "\n    {} ({} occurrences):",
-                    name_color(name).bold(),
+                    render.paint(name_color(name).bold()),
                     occurrence_counts.get(name).unwrap_or(&0)
                 );
 
-                // Sort keys by occurrence count
-                let mut sorted_keys: Vec<(&String, &usize)> = keys.iter().collect();
-                sorted_keys.sort_by(|a, b| b.1.cmp(a.1));
+                // Sort keys by occurrence count, then by path so reruns print the same order
+                let mut sorted_keys: Vec<(&String, &usize)> = stats.keys.iter().collect();
+                sorted_keys.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
 
                 // Display top fields (max 10)
                 let display_count = sorted_keys.len().min(10);
                 for (i, (key, count)) in sorted_keys.iter().take(display_count).enumerate() {
+                    let examples = stats
+                        .examples_line(key, schema_rules.examples_per_field)
+                        .and_then(|line| {
+                            if line.len() > example_budget {
+                                budget_exhausted = true;
+                                return None;
+                            }
+                            example_budget -= line.len();
+                            Some(line)
+                        });
                     println!(
-                        "      {}. {} ({}/{})",
-                        (i + 1).to_string().bright_white(),
+                        "      {}. {} ({}/{}){}",
+                        render.paint((i + 1).to_string().bright_white()),
                         key,
-                        count.to_string().bright_white(),
-                        occurrence_counts.get(name).unwrap_or(&0)
+                        render.paint(count.to_string().bright_white()),
+                        occurrence_counts.get(name).unwrap_or(&0),
+                        examples
+                            .map(|line| format!("  e.g. {}", render.paint(line.bright_black())))
+                            .unwrap_or_default()
                     );
                 }
 
@@ -384,26 +411,24 @@ pub fn display_log_summary(
                         sorted_keys.len() - display_count
                     );
                 }
+                if stats.truncated > 0 {
+                    println!(
+                        "      ({} nested subtrees beyond depth {} not analyzed)",
+                        stats.truncated,
+                        max_json_depth.unwrap_or_default()
+                    );
+                }
             }
         };
 
-        display_schema(
-            "EVENT SCHEMAS",
-            &event_payload_keys,
-            &event_type_counts,
-            |s| s.yellow(),
-        );
-        display_schema(
-            "COMMAND SCHEMAS",
-            &command_payload_keys,
-            &command_counts,
-            |s| s.magenta(),
-        );
-        display_schema(
-            "REQUEST SCHEMAS",
-            &request_payload_keys,
-            &request_counts,
-            |s| s.bright_green(),
+    display_schema("EVENT SCHEMAS", &report.events, |s| s.yellow());
+    display_schema("COMMAND SCHEMAS", &report.commands, |s| s.magenta());
+    display_schema("REQUEST SCHEMAS", &report.requests, |s| s.bright_green());
+
+    if budget_exhausted {
+        println!(
+            "\n  (some examples omitted: {}-byte example budget reached)",
+            schema_rules.example_byte_budget
         );
     }
 }
@@ -412,97 +437,73 @@ pub fn display_log_summary(
 fn display_timeline_analysis(
     timestamps: &[DateTime<Local>],
     component_timeline: &HashMap<&str, Vec<DateTime<Local>>>,
+    buckets: &TimeBucketOptions,
+    render: &RenderOptions,
 ) {
-    // Only proceed if we have enough timestamps
-    if timestamps.len() < 5 {
+    let Some(histogram) = build_time_histogram(timestamps.iter().copied(), buckets) else {
         return;
-    }
-
-    // Find the earliest and latest timestamps
-    let earliest = timestamps.iter().min().unwrap();
-    let latest = timestamps.iter().max().unwrap();
-
-    // Calculate time span and decide on appropriate bucket size
-    let time_span = latest.signed_duration_since(*earliest).to_std().unwrap();
-    let total_seconds = time_span.as_secs();
-
-    // Determine appropriate bucket size based on time span
-    let (bucket_size, bucket_unit) = if total_seconds < 60 {
-        // Less than a minute, use 5-second buckets
-        (std::time::Duration::from_secs(5), "5 sec")
-    } else if total_seconds < 3600 {
-        // Less than an hour, use 1-minute buckets
-        (std::time::Duration::from_secs(60), "1 min")
-    } else if total_seconds < 86400 {
-        // Less than a day, use 10-minute buckets
-        (std::time::Duration::from_secs(600), "10 min")
-    } else {
-        // More than a day, use 1-hour buckets
-        (std::time::Duration::from_secs(3600), "1 hour")
     };
-
-    // Calculate number of buckets
-    let num_buckets = (time_span.as_secs() / bucket_size.as_secs()).max(1) as usize;
-
-    // Create buckets for overall timeline
-    let mut timeline_buckets = vec![0; num_buckets];
-
-    // Fill buckets
-    for timestamp in timestamps {
-        let bucket_idx = calculate_bucket_index(earliest, timestamp, bucket_size, num_buckets);
-        timeline_buckets[bucket_idx] += 1;
-    }
+    let time_format = histogram.time_format();
+    let bucket_index = |timestamp: &DateTime<Local>| {
+        histogram
+            .index_of(timestamp)
+            .min(histogram.counts.len() - 1)
+    };
 
     // Calculate max count for scaling
-    let max_count = *timeline_buckets.iter().max().unwrap_or(&1);
+    let max_count = *histogram.counts.iter().max().unwrap_or(&1);
 
     // Display timeline header
     println!(
         "\n  {}",
-        "EVENT DISTRIBUTION OVER TIME".bright_white().bold()
+        render.paint("EVENT DISTRIBUTION OVER TIME".bright_white().bold())
     );
-    println!("  (each bucket represents {})", bucket_unit.bright_black());
-    println!("  {}", "-".repeat(70).bright_black());
+    println!(
+        "  (each bucket represents {})",
+        render.paint(histogram.width_label().bright_black())
+    );
+    println!("  {}", render.paint("-".repeat(70).bright_black()));
 
     // Display overall timeline histogram
-    for (i, count) in timeline_buckets.iter().enumerate() {
+    for (i, count) in histogram.counts.iter().enumerate() {
         let bar_length = ((count * 40) / max_count).max(1);
         let bar = "█".repeat(bar_length);
-
-        // Calculate time for this bucket
-        let bucket_time =
-            *earliest + chrono::Duration::from_std(bucket_size.mul_f64(i as f64)).unwrap();
-        let time_str = bucket_time.format("%H:%M:%S").to_string();
+        let time_str = histogram.bucket_start(i).format(time_format).to_string();
 
         println!(
             "  {}: {:4} events |{}",
-            time_str.bright_blue(),
-            count.to_string().bright_white(),
-            bar.color(get_gradient_color(*count as f64 * 100.0 / max_count as f64))
+            render.paint(time_str.bright_blue()),
+            render.paint(count.to_string().bright_white()),
+            render.paint(bar.color(get_gradient_color(*count as f64 * 100.0 / max_count as f64)))
         );
     }
 
     // Display component distribution
     println!(
         "\n  {}",
-        "COMPONENT ACTIVITY DISTRIBUTION".bright_white().bold()
+        render.paint("COMPONENT ACTIVITY DISTRIBUTION".bright_white().bold())
     );
-    println!("  {}", "-".repeat(70).bright_black());
+    println!("  {}", render.paint("-".repeat(70).bright_black()));
 
     // Sort components by total count
     let mut components: Vec<(&str, &Vec<DateTime<Local>>)> =
         component_timeline.iter().map(|(k, v)| (*k, v)).collect();
     components.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
 
+    let num_buckets = histogram.counts.len();
+
     // Display top 5 components
     for (name, timestamps) in components.iter().take(5) {
-        println!("  {}: {} events", name.cyan(), timestamps.len());
+        println!(
+            "  {}: {} events",
+            render.paint(name.cyan()),
+            timestamps.len()
+        );
 
         // Calculate component buckets
         let mut comp_buckets = vec![0; num_buckets];
         for timestamp in *timestamps {
-            let bucket_idx = calculate_bucket_index(earliest, timestamp, bucket_size, num_buckets);
-            comp_buckets[bucket_idx] += 1;
+            comp_buckets[bucket_index(timestamp)] += 1;
         }
 
         // Find max count for this component
@@ -517,46 +518,30 @@ fn display_timeline_analysis(
         };
 
         for i in (0..num_buckets).step_by(step) {
-            if i < comp_buckets.len() {
-                let count = comp_buckets[i];
-                let bar_length = ((count * 20) / comp_max).max(if count > 0 { 1 } else { 0 });
-                let bar = if bar_length > 0 {
-                    "█".repeat(bar_length)
-                } else {
-                    "".to_string()
-                };
-
-                // Calculate time for this bucket
-                let bucket_time =
-                    *earliest + chrono::Duration::from_std(bucket_size.mul_f64(i as f64)).unwrap();
-                let time_str = bucket_time.format("%H:%M:%S").to_string();
+            let count = comp_buckets[i];
+            let bar_length = ((count * 20) / comp_max).max(if count > 0 { 1 } else { 0 });
+            let bar = if bar_length > 0 {
+                "█".repeat(bar_length)
+            } else {
+                "".to_string()
+            };
+            let time_str = histogram.bucket_start(i).format(time_format).to_string();
 
-                if count > 0 {
-                    println!(
-                        "    {}: {:3} |{}",
-                        time_str.bright_blue(),
-                        count.to_string().bright_white(),
+            if count > 0 {
+                println!(
+                    "    {}: {:3} |{}",
+                    render.paint(time_str.bright_blue()),
+                    render.paint(count.to_string().bright_white()),
+                    render.paint(
                         bar.color(get_gradient_color(count as f64 * 100.0 / comp_max as f64))
-                    );
-                }
+                    )
+                );
             }
         }
         println!();
     }
 }
 
-/// Calculate bucket index for a timestamp
-fn calculate_bucket_index(
-    earliest: &DateTime<Local>,
-    timestamp: &DateTime<Local>,
-    bucket_size: std::time::Duration,
-    num_buckets: usize,
-) -> usize {
-    let duration = timestamp.signed_duration_since(*earliest).to_std().unwrap();
-    let bucket_idx = (duration.as_secs() / bucket_size.as_secs()) as usize;
-    bucket_idx.min(num_buckets - 1) // Ensure we don't go out of bounds
-}
-
 /// Get color for a log level
 fn get_level_color(level: &str) -> Color {
     match level.to_lowercase().as_str() {
@@ -652,11 +637,189 @@ fn format_duration(duration: std::time::Duration) -> String {
 }
 
 /// Helper function to collect JSON keys from a Value, recursively traversing objects
+/// Distinct values tracked per leaf field; later new values are not counted
+const MAX_TRACKED_EXAMPLES: usize = 64;
+
+/// Field occurrence counts of one payload type, with the values seen at leaf fields
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct SchemaStats {
+    keys: HashMap<String, usize>,
+    /// Objects/arrays not descended because of `max_json_depth`
+    truncated: usize,
+    /// Leaf key (`path (type)`) → rendered value → occurrences
+    examples: HashMap<String, HashMap<String, usize>>,
+}
+
+impl SchemaStats {
+    fn record_example(
+        &mut self,
+        key: &str,
+        value: &serde_json::Value,
+        path: &str,
+        rules: &SchemaRules,
+    ) {
+        if rules.examples_per_field == 0 {
+            return;
+        }
+        let rendered = if rules.masks(path) {
+            "[MASKED]".to_string()
+        } else {
+            render_example(value, rules.example_max_chars)
+        };
+        let values = self.examples.entry(key.to_string()).or_default();
+        if let Some(count) = values.get_mut(&rendered) {
+            *count += 1;
+        } else if values.len() < MAX_TRACKED_EXAMPLES {
+            values.insert(rendered, 1);
+        }
+    }
+
+    /// The `limit` most frequent values of a leaf key, e.g. `"ok" ×4, "failed" ×1`
+    fn examples_line(&self, key: &str, limit: usize) -> Option<String> {
+        let values = self.examples.get(key)?;
+        let mut sorted: Vec<(&String, &usize)> = values.iter().collect();
+        sorted.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let parts: Vec<String> = sorted
+            .into_iter()
+            .take(limit)
+            .map(|(value, count)| format!("{value} ×{count}"))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// Adds the counts of `other`, as if its payloads had been collected into `self`.
+    /// New example values are taken most frequent first while the per-field cap allows.
+    fn merge(&mut self, other: SchemaStats) {
+        for (key, count) in other.keys {
+            *self.keys.entry(key).or_insert(0) += count;
+        }
+        self.truncated += other.truncated;
+        for (key, other_values) in other.examples {
+            let values = self.examples.entry(key).or_default();
+            let mut other_values: Vec<(String, usize)> = other_values.into_iter().collect();
+            other_values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            for (value, count) in other_values {
+                if let Some(existing) = values.get_mut(&value) {
+                    *existing += count;
+                } else if values.len() < MAX_TRACKED_EXAMPLES {
+                    values.insert(value, count);
+                }
+            }
+        }
+    }
+}
+
+/// Occurrences and payload schemas of the events, commands or requests of one report
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct SchemaSection {
+    /// Name → entries, with or without a payload
+    counts: HashMap<String, usize>,
+    /// Name → schema of its payloads
+    schemas: HashMap<String, SchemaStats>,
+}
+
+impl SchemaSection {
+    fn record(
+        &mut self,
+        name: &str,
+        payload: Option<&serde_json::Value>,
+        rules: &SchemaRules,
+        max_json_depth: Option<usize>,
+    ) {
+        *self.counts.entry(name.to_string()).or_insert(0) += 1;
+        if let Some(payload) = payload {
+            let stats = self.schemas.entry(name.to_string()).or_default();
+            collect_json_keys(payload, "", stats, rules, max_json_depth);
+        }
+    }
+
+    fn merge(&mut self, other: SchemaSection) {
+        for (name, count) in other.counts {
+            *self.counts.entry(name).or_insert(0) += count;
+        }
+        for (name, stats) in other.schemas {
+            self.schemas.entry(name).or_default().merge(stats);
+        }
+    }
+}
+
+/// Payload schemas of `info --json-schema`. Reports of separate files merge into the
+/// report of all of them, so each file's report can be cached on its own.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaReport {
+    events: SchemaSection,
+    commands: SchemaSection,
+    requests: SchemaSection,
+}
+
+impl SchemaReport {
+    pub fn collect(logs: &[LogEntry], rules: &SchemaRules, max_json_depth: Option<usize>) -> Self {
+        let mut report = Self::default();
+        for log in logs {
+            report.record(log, rules, max_json_depth);
+        }
+        report
+    }
+
+    pub fn record(&mut self, log: &LogEntry, rules: &SchemaRules, max_json_depth: Option<usize>) {
+        match &log.kind {
+            LogEntryKind::Event {
+                event_type,
+                payload,
+                ..
+            } => self
+                .events
+                .record(event_type, payload.as_ref(), rules, max_json_depth),
+            LogEntryKind::Command {
+                command, settings, ..
+            } => self
+                .commands
+                .record(command, settings.as_ref(), rules, max_json_depth),
+            LogEntryKind::Request {
+                request, payload, ..
+            } => self
+                .requests
+                .record(request, payload.as_ref(), rules, max_json_depth),
+            LogEntryKind::Generic { .. } => {}
+        }
+    }
+
+    pub fn merge(&mut self, other: SchemaReport) {
+        self.events.merge(other.events);
+        self.commands.merge(other.commands);
+        self.requests.merge(other.requests);
+    }
+
+    /// No payload of any event, command or request was seen
+    pub fn is_empty(&self) -> bool {
+        self.events.schemas.is_empty()
+            && self.commands.schemas.is_empty()
+            && self.requests.schemas.is_empty()
+    }
+}
+
+fn render_example(value: &serde_json::Value, max_chars: usize) -> String {
+    match value {
+        serde_json::Value::String(text) if text.chars().count() > max_chars => {
+            let truncated: String = text.chars().take(max_chars).collect();
+            serde_json::Value::String(format!("{truncated}…")).to_string()
+        }
+        _ => value.to_string(),
+    }
+}
+
 fn collect_json_keys(
     value: &serde_json::Value,
     prefix: &str,
-    keys_map: &mut HashMap<String, usize>,
+    stats: &mut SchemaStats,
+    rules: &SchemaRules,
+    depth_left: Option<usize>,
 ) {
+    if depth_left == Some(0) && (value.is_object() || value.is_array()) {
+        stats.truncated += 1;
+        return;
+    }
+    let depth_left = depth_left.map(|depth| depth - 1);
     match value {
         serde_json::Value::Object(obj) => {
             for (key, val) in obj {
@@ -667,22 +830,22 @@ fn collect_json_keys(
                 };
 
                 // Increment the count for this path
-                *keys_map.entry(path.clone()).or_insert(0) += 1;
+                *stats.keys.entry(path.clone()).or_insert(0) += 1;
 
                 // Recursively collect keys from nested objects
-                collect_json_keys(val, &path, keys_map);
+                collect_json_keys(val, &path, stats, rules, depth_left);
             }
         }
         serde_json::Value::Array(arr) => {
             // For arrays, we just note the existence of an array at this path
             // and recursively process each element
-            *keys_map.entry(format!("{}[]", prefix)).or_insert(0) += 1;
+            *stats.keys.entry(format!("{}[]", prefix)).or_insert(0) += 1;
 
             for (idx, val) in arr.iter().enumerate() {
                 // Only traverse deeper if not primitive types
                 if val.is_object() || val.is_array() {
                     let path = format!("{}[{}]", prefix, idx);
-                    collect_json_keys(val, &path, keys_map);
+                    collect_json_keys(val, &path, stats, rules, depth_left);
                 }
             }
         }
@@ -696,13 +859,33 @@ fn collect_json_keys(
                 _ => unreachable!(),
             };
 
-            *keys_map
-                .entry(format!("{} ({})", prefix, type_name))
-                .or_insert(0) += 1;
+            let key = format!("{} ({})", prefix, type_name);
+            *stats.keys.entry(key.clone()).or_insert(0) += 1;
+            stats.record_example(&key, value, prefix, rules);
         }
     }
 }
 
+/// Distinct field paths of one payload as collected by the schema analysis, with array
+/// indices folded (`renders[].browser`)
+pub(crate) fn payload_field_paths(
+    value: &serde_json::Value,
+    max_json_depth: Option<usize>,
+) -> BTreeSet<String> {
+    let rules = SchemaRules {
+        examples_per_field: 0,
+        ..SchemaRules::default()
+    };
+    let mut stats = SchemaStats::default();
+    collect_json_keys(value, "", &mut stats, &rules, max_json_depth);
+    stats
+        .keys
+        .into_keys()
+        .filter(|key| !key.ends_with(')') && !key.ends_with("[]"))
+        .map(|key| ARRAY_INDEX_RE.replace_all(&key, "[]").into_owned())
+        .collect()
+}
+
 /// Helper function to calculate statistics for a collection of values
 fn calculate_stats(values: &[usize]) -> (usize, usize, usize, f64) {
     if values.is_empty() {
@@ -1,7 +1,14 @@
 use crate::LogEntryKind;
 use crate::cli::Direction;
-use crate::parser::LogEntry;
-use serde_json::Value;
+use crate::config::RunRules;
+use crate::filter::TimeBound;
+use crate::parser::{LogEntry, PayloadParseErrors};
+use crate::render::RenderOptions;
+use crate::runs::detect_runs;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
 
 /// Error types for comparison operations
 #[derive(Debug)]
@@ -23,7 +30,8 @@ impl From<serde_json::Error> for ComparisonError {
 }
 
 /// Represents the type of change detected in a JSON comparison
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ChangeType {
     /// Key/value added in log2 (null → value)
     Added,
@@ -33,17 +41,85 @@ pub enum ChangeType {
     Modified,
 }
 
+/// A payload field that appears in only one file's schema for a key, across every instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaChange {
+    /// Field never present in log1 payloads of the key
+    FieldAdded,
+    /// Field never present in log2 payloads of the key
+    FieldRemoved,
+}
+
+impl SchemaChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SchemaChange::FieldAdded => "field_added",
+            SchemaChange::FieldRemoved => "field_removed",
+        }
+    }
+}
+
 /// Represents the difference between two JSON values
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonDifference {
     pub path: String,
     pub value1: Value,
     pub value2: Value,
     pub change_type: ChangeType,
+    /// Set when the difference is a structural change rather than a value change
+    pub schema_change: Option<SchemaChange>,
+}
+
+impl JsonDifference {
+    /// Absolute and percentage change when both values are numbers
+    pub fn numeric_delta(&self) -> Option<NumericDelta> {
+        let before = self.value1.as_f64()?;
+        let after = self.value2.as_f64()?;
+        Some(NumericDelta {
+            absolute: after - before,
+            percent: (before != 0.0).then(|| (after - before) / before.abs() * 100.0),
+        })
+    }
+}
+
+/// Change between two numeric payload values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericDelta {
+    pub absolute: f64,
+    /// `None` when the original value is zero
+    pub percent: Option<f64>,
+}
+
+impl NumericDelta {
+    /// Short label such as `+600 (+150%)`
+    pub fn label(&self) -> String {
+        let absolute = if self.absolute.fract() == 0.0 {
+            format!("{:+}", self.absolute as i64)
+        } else {
+            format!("{:+.3}", self.absolute)
+                .trim_end_matches('0')
+                .to_string()
+        };
+        match self.percent {
+            Some(percent) if percent.fract() == 0.0 => {
+                format!("{absolute} ({:+}%)", percent as i64)
+            }
+            Some(percent) => format!("{absolute} ({percent:+.1}%)"),
+            None => absolute,
+        }
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({
+            "absolute": self.absolute,
+            "percent": self.percent,
+        })
+    }
 }
 
 /// Represents a comparison between two log entries
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LogComparison {
     pub key: String,
     pub log1_index: usize,
@@ -53,8 +129,17 @@ pub struct LogComparison {
     pub text2: Option<String>,
     pub log1_line_number: usize,
     pub log2_line_number: usize,
+    pub log1_timestamp: DateTime<Local>,
+    pub log2_timestamp: DateTime<Local>,
+    pub log1_request_id: Option<String>,
+    pub log2_request_id: Option<String>,
     pub log1_payload: Option<Value>,
     pub log2_payload: Option<Value>,
+    /// Occurrences represented on each side when payload clustering paired cluster representatives
+    pub cluster_sizes: Option<(usize, usize)>,
+    /// Raw log text of each side (only with `ComparisonOptions::raw_limit`)
+    pub log1_raw: Option<String>,
+    pub log2_raw: Option<String>,
 }
 
 /// Represents filtering criteria for logs
@@ -68,8 +153,112 @@ pub struct LogFilter {
     exclude_text: Vec<String>,
     include_fields: Vec<(String, String)>,
     exclude_fields: Vec<(String, String)>,
+    missing_paths: Vec<String>,
+    present_paths: Vec<String>,
+    include_tags: Vec<String>,
+    exclude_tags: Vec<String>,
+    include_methods: Vec<String>,
+    exclude_methods: Vec<String>,
+    include_hosts: Vec<String>,
+    exclude_hosts: Vec<String>,
     include_directions: Vec<Direction>,
     exclude_directions: Vec<Direction>,
+    exclusion_windows: Vec<ExclusionWindow>,
+    run: Option<RunSelection>,
+    time_range: Option<TimeRange>,
+}
+
+/// What relative `since:`/`until:` bounds count back from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeReference {
+    /// A fixed instant: the wall clock, or `--now`
+    At(DateTime<Local>),
+    /// The last entry of the logs, found by [`LogFilter::resolve_windows`]
+    LogEnd,
+}
+
+/// Keeps entries at or after any `since` bound and at or before any `until` bound
+#[derive(Clone)]
+struct TimeRange {
+    since: Vec<TimeBound>,
+    until: Vec<TimeBound>,
+    reference: TimeReference,
+    /// The last entry's timestamp once resolved against [`TimeReference::LogEnd`]
+    log_end: Option<DateTime<Local>>,
+}
+
+impl TimeRange {
+    /// `None` while a relative bound waits for the log end
+    fn instant(&self, bound: TimeBound) -> Option<DateTime<Local>> {
+        match bound {
+            TimeBound::At(at) => Some(at.with_timezone(&Local)),
+            TimeBound::Ago(ms) => {
+                let reference = match self.reference {
+                    TimeReference::At(at) => Some(at),
+                    TimeReference::LogEnd => self.log_end,
+                };
+                reference.map(|reference| reference - chrono::Duration::milliseconds(ms))
+            }
+        }
+    }
+
+    fn covers(&self, timestamp: DateTime<Local>) -> bool {
+        let after = |bound: &TimeBound| self.instant(*bound).is_none_or(|at| timestamp >= at);
+        let before = |bound: &TimeBound| self.instant(*bound).is_none_or(|at| timestamp <= at);
+        (self.since.is_empty() || self.since.iter().any(after))
+            && (self.until.is_empty() || self.until.iter().any(before))
+    }
+}
+
+/// Excludes entries within `window_ms` of an entry matching `anchor`.
+/// Anchor timestamps are collected by [`LogFilter::resolve_windows`].
+#[derive(Clone)]
+struct ExclusionWindow {
+    window_ms: i64,
+    anchor: LogFilter,
+    anchor_times: Vec<DateTime<Local>>,
+}
+
+impl ExclusionWindow {
+    fn covers(&self, timestamp: DateTime<Local>) -> bool {
+        let window = chrono::Duration::milliseconds(self.window_ms);
+        let first = self
+            .anchor_times
+            .partition_point(|anchor| *anchor < timestamp - window);
+        self.anchor_times
+            .get(first)
+            .is_some_and(|anchor| *anchor <= timestamp + window)
+    }
+}
+
+/// Keeps the entries of one run. The run's time span is found by
+/// [`LogFilter::resolve_windows`]; until then every entry passes.
+#[derive(Clone)]
+struct RunSelection {
+    number: usize,
+    rules: RunRules,
+    span: RunSpan,
+}
+
+#[derive(Clone, Copy)]
+enum RunSpan {
+    Unresolved,
+    /// The stream has fewer runs than requested
+    Missing,
+    /// From the run's first entry up to the next run's first entry
+    Between(DateTime<Local>, Option<DateTime<Local>>),
+}
+
+impl RunSelection {
+    fn covers(&self, timestamp: DateTime<Local>) -> bool {
+        match self.span {
+            RunSpan::Unresolved => true,
+            RunSpan::Missing => false,
+            RunSpan::Between(start, end) => {
+                timestamp >= start && end.is_none_or(|end| timestamp < end)
+            }
+        }
+    }
 }
 
 impl LogFilter {
@@ -141,6 +330,65 @@ impl LogFilter {
         self
     }
 
+    /// Keep entries with a payload that lacks `path`
+    pub fn missing_field(mut self, path: Option<impl Into<String>>) -> Self {
+        if let Some(path) = path {
+            self.missing_paths.push(path.into());
+        }
+        self
+    }
+
+    /// Drop entries with a payload that lacks `path` (`!missing:`)
+    pub fn has_field(mut self, path: Option<impl Into<String>>) -> Self {
+        if let Some(path) = path {
+            self.present_paths.push(path.into());
+        }
+        self
+    }
+
+    pub fn with_tag(mut self, tag: Option<impl Into<String>>) -> Self {
+        if let Some(tag) = tag {
+            self.include_tags.push(tag.into());
+        }
+        self
+    }
+
+    pub fn exclude_tag(mut self, tag: Option<impl Into<String>>) -> Self {
+        if let Some(tag) = tag {
+            self.exclude_tags.push(tag.into());
+        }
+        self
+    }
+
+    pub fn with_method(mut self, method: Option<impl Into<String>>) -> Self {
+        if let Some(method) = method {
+            self.include_methods.push(method.into());
+        }
+        self
+    }
+
+    pub fn exclude_method(mut self, method: Option<impl Into<String>>) -> Self {
+        if let Some(method) = method {
+            self.exclude_methods.push(method.into());
+        }
+        self
+    }
+
+    /// Keep requests sent to a host matching `host` (substring, or glob with `*`/`?`)
+    pub fn with_host(mut self, host: Option<impl Into<String>>) -> Self {
+        if let Some(host) = host {
+            self.include_hosts.push(host.into());
+        }
+        self
+    }
+
+    pub fn exclude_host(mut self, host: Option<impl Into<String>>) -> Self {
+        if let Some(host) = host {
+            self.exclude_hosts.push(host.into());
+        }
+        self
+    }
+
     pub fn with_direction(mut self, direction: &Option<Direction>) -> Self {
         if let Some(direction) = direction.clone() {
             self.include_directions.push(direction);
@@ -155,6 +403,144 @@ impl LogFilter {
         self
     }
 
+    pub fn exclude_within(mut self, window_ms: i64, anchor: LogFilter) -> Self {
+        self.exclusion_windows.push(ExclusionWindow {
+            window_ms,
+            anchor,
+            anchor_times: Vec::new(),
+        });
+        self
+    }
+
+    /// Keep entries at or after `bound`; relative bounds count back from the wall clock
+    /// until [`LogFilter::relative_to`] says otherwise
+    pub fn since(mut self, bound: TimeBound) -> Self {
+        self.time_range_mut().since.push(bound);
+        self
+    }
+
+    /// Keep entries at or before `bound`
+    pub fn until(mut self, bound: TimeBound) -> Self {
+        self.time_range_mut().until.push(bound);
+        self
+    }
+
+    fn time_range_mut(&mut self) -> &mut TimeRange {
+        self.time_range.get_or_insert_with(|| TimeRange {
+            since: Vec::new(),
+            until: Vec::new(),
+            reference: TimeReference::At(Local::now()),
+            log_end: None,
+        })
+    }
+
+    /// Count relative `since:`/`until:` bounds back from `reference`
+    pub fn relative_to(mut self, reference: TimeReference) -> Self {
+        if let Some(range) = &mut self.time_range {
+            range.reference = reference;
+        }
+        self
+    }
+
+    /// The fixed instant relative bounds count back from, if the filter has any
+    pub fn relative_reference(&self) -> Option<DateTime<Local>> {
+        let range = self.time_range.as_ref()?;
+        let relative = range
+            .since
+            .iter()
+            .chain(&range.until)
+            .any(|bound| matches!(bound, TimeBound::Ago(_)));
+        match range.reference {
+            TimeReference::At(at) if relative => Some(at),
+            _ => None,
+        }
+    }
+
+    /// Keep only run `number` (1-based) of the stream, split according to `rules`
+    pub fn select_run(mut self, number: Option<usize>, rules: &RunRules) -> Self {
+        self.run = number.map(|number| RunSelection {
+            number,
+            rules: rules.clone(),
+            span: RunSpan::Unresolved,
+        });
+        self
+    }
+
+    pub fn has_exclusion_windows(&self) -> bool {
+        !self.exclusion_windows.is_empty()
+    }
+
+    /// Whether [`LogFilter::resolve_windows`] changes what this filter matches
+    pub fn needs_resolution(&self) -> bool {
+        self.has_exclusion_windows()
+            || self.run.is_some()
+            || self
+                .time_range
+                .as_ref()
+                .is_some_and(|range| range.reference == TimeReference::LogEnd)
+    }
+
+    /// Whether matching looks at payloads (`missing:` terms, also inside `!within:`
+    /// anchors), so entries must be parsed with their payloads
+    pub fn reads_payloads(&self) -> bool {
+        !self.missing_paths.is_empty()
+            || !self.present_paths.is_empty()
+            || self
+                .exclusion_windows
+                .iter()
+                .any(|window| window.anchor.reads_payloads())
+    }
+
+    /// First pass for `!within:` windows, `--run` and `--relative-to log-end`: returns a
+    /// copy of this filter with anchor timestamps, the selected run's span and the log end
+    /// taken from `logs`. Without it, windows exclude nothing, every run is kept and
+    /// bounds relative to the log end keep everything. A missing run or a filter that
+    /// matches nothing is reported to `warnings`.
+    pub fn resolve_windows(&self, logs: &[LogEntry], warnings: &WarningLog) -> LogFilter {
+        let mut resolved = self.clone();
+        if let Some(range) = &mut resolved.time_range {
+            range.log_end = logs.iter().map(|log| log.timestamp).max();
+        }
+        if let Some(run) = &mut resolved.run {
+            let runs = detect_runs(logs, &run.rules);
+            let selected = runs.get(run.number - 1);
+            if selected.is_none() && !logs.is_empty() {
+                warnings.warn(
+                    WarningCode::RunNotFound,
+                    format!(
+                        "--run {} requested but only {} run(s) detected; no entries selected",
+                        run.number,
+                        runs.len()
+                    ),
+                );
+            }
+            run.span = selected.map_or(RunSpan::Missing, |selected| {
+                RunSpan::Between(
+                    logs[selected.first_index].timestamp,
+                    runs.get(run.number)
+                        .map(|next| logs[next.first_index].timestamp),
+                )
+            });
+        }
+        for window in &mut resolved.exclusion_windows {
+            window.anchor_times = logs
+                .iter()
+                .filter(|log| window.anchor.matches(log))
+                .map(|log| log.timestamp)
+                .collect();
+            window.anchor_times.sort();
+        }
+        let run_missing =
+            matches!(&resolved.run, Some(run) if matches!(run.span, RunSpan::Missing));
+        if !logs.is_empty() && !run_missing && !logs.iter().any(|log| resolved.matches(log)) {
+            warnings.warn(
+                WarningCode::FilterMatchedNothing,
+                format!("the filter matched none of the {} entries", logs.len()),
+            );
+        }
+        resolved
+    }
+
     pub fn matches(&self, log: &LogEntry) -> bool {
         fn contains_ci(haystack: &str, needle: &str) -> bool {
             haystack.to_lowercase().contains(&needle.to_lowercase())
@@ -199,6 +585,39 @@ impl LogFilter {
                 .is_some_and(|field_value| contains_ci(field_value, value))
         });
 
+        let lacks = |path: &String| log.payload().is_some() && log.payload_value(path).is_none();
+        let missing_match = self.missing_paths.is_empty() || self.missing_paths.iter().any(lacks);
+        let present_match = !self.present_paths.iter().any(lacks);
+
+        // The request's endpoint, else a `method`/`host` field of tracing and JSON lines
+        let endpoint = log.endpoint();
+        let method = endpoint
+            .and_then(|endpoint| endpoint.method.as_deref())
+            .or_else(|| log.structured_field("method"));
+        let host = endpoint
+            .and_then(|endpoint| endpoint.host.as_deref())
+            .or_else(|| log.structured_field("host"));
+        let method_is =
+            |filter: &String| method.is_some_and(|method| method.eq_ignore_ascii_case(filter));
+        let host_is = |filter: &String| {
+            host.is_some_and(|host| {
+                if filter.contains(['*', '?']) {
+                    glob_matches(&filter.to_lowercase(), &host.to_lowercase())
+                } else {
+                    contains_ci(host, filter)
+                }
+            })
+        };
+        let method_match =
+            self.include_methods.is_empty() || self.include_methods.iter().any(method_is);
+        let exclude_method_match = !self.exclude_methods.iter().any(method_is);
+        let host_match = self.include_hosts.is_empty() || self.include_hosts.iter().any(host_is);
+        let exclude_host_match = !self.exclude_hosts.iter().any(host_is);
+
+        let tagged = |tag: &String| log.has_tag(tag);
+        let tag_match = self.include_tags.is_empty() || self.include_tags.iter().any(tagged);
+        let exclude_tag_match = !self.exclude_tags.iter().any(tagged);
+
         let log_direction = match &log.kind {
             LogEntryKind::Event { direction, .. } => Some(Direction::from(direction.clone())),
             LogEntryKind::Request { direction, .. } => Some(Direction::from(direction.clone())),
@@ -217,6 +636,19 @@ impl LogFilter {
             .iter()
             .all(|filter| log_direction.as_ref() != Some(filter));
 
+        let window_match = self
+            .exclusion_windows
+            .iter()
+            .all(|window| !window.covers(log.timestamp));
+        let run_match = self
+            .run
+            .as_ref()
+            .is_none_or(|run| run.covers(log.timestamp));
+        let time_match = self
+            .time_range
+            .as_ref()
+            .is_none_or(|range| range.covers(log.timestamp));
+
         component_match
             && include_direction_match
             && exclude_direction_match
@@ -227,10 +659,23 @@ impl LogFilter {
             && exclude_level_match
             && excludes_match
             && exclude_field_match
+            && missing_match
+            && present_match
+            && tag_match
+            && exclude_tag_match
+            && method_match
+            && exclude_method_match
+            && host_match
+            && exclude_host_match
+            && window_match
+            && run_match
+            && time_match
     }
 }
 
+use super::normalize_text;
 use crate::cli::SortOrder;
+use crate::diagnostics::{WarningCode, WarningLog};
 
 /// Options for controlling the comparison output
 #[derive(Default)]
@@ -242,6 +687,40 @@ pub struct ComparisonOptions {
     pub sort_order: SortOrder,
     pub verbosity: u8, // 0: quiet, 1: normal, 2+: verbose
     pub quiet: bool,
+    /// Glob patterns a grouped key must match to be compared (empty = all keys)
+    pub only_keys: Vec<String>,
+    /// Glob patterns excluding grouped keys from comparison
+    pub skip_keys: Vec<String>,
+    /// Compare payload-shape cluster representatives for keys with unequal occurrence counts
+    pub cluster_payloads: bool,
+    /// Percentage-point shift in a component's level share that flags level drift (0 = off)
+    pub level_drift_threshold: f64,
+    /// Payload paths whose differences are not reported (also ignores nested paths)
+    pub ignore_paths: Vec<String>,
+    /// Index-free array path → key field used to pair array-of-object elements
+    pub array_identity_keys: BTreeMap<String, String>,
+    /// List every unparseable payload instead of the first few per file
+    pub all_payload_errors: bool,
+    /// Embed each compared entry's raw log text in JSON output, cut to this many bytes
+    pub raw_limit: Option<usize>,
+    /// Nesting depth below which payload subtrees are compared as opaque blobs
+    pub max_json_depth: Option<usize>,
+    /// Rarest differences highlighted ahead of the full listing (0 = off)
+    pub interesting_limit: usize,
+    /// Treat messages and string values differing only in letter case as equal
+    pub ignore_case: bool,
+    /// Treat messages and string values differing only in whitespace as equal
+    pub ignore_whitespace: bool,
+    /// Embed a legend of abbreviated keys in compact JSON output
+    pub include_legend: bool,
+    /// Render only the summary counts, without unique entries, differences, or drift
+    pub summary_only: bool,
+    /// Longer arrays in `show_full_json` payloads keep only their first and last elements (0 = all)
+    pub max_array_elements: usize,
+    /// Colouring of console output
+    pub render: RenderOptions,
+    /// `--lines` / `--bytes` slice the logs were read from, noted in the text header
+    pub scope: Option<String>,
 }
 
 impl ComparisonOptions {
@@ -283,17 +762,259 @@ impl ComparisonOptions {
         self.quiet = value;
         self
     }
+
+    pub fn only_keys(mut self, patterns: Vec<String>) -> Self {
+        self.only_keys = patterns;
+        self
+    }
+
+    pub fn skip_keys(mut self, patterns: Vec<String>) -> Self {
+        self.skip_keys = patterns;
+        self
+    }
+
+    pub fn cluster_payloads(mut self, value: bool) -> Self {
+        self.cluster_payloads = value;
+        self
+    }
+
+    pub fn level_drift_threshold(mut self, threshold_pp: f64) -> Self {
+        self.level_drift_threshold = threshold_pp;
+        self
+    }
+
+    pub fn ignore_paths(mut self, paths: Vec<String>) -> Self {
+        self.ignore_paths = paths;
+        self
+    }
+
+    pub fn array_identity_keys(mut self, keys: BTreeMap<String, String>) -> Self {
+        self.array_identity_keys = keys;
+        self
+    }
+
+    pub fn all_payload_errors(mut self, value: bool) -> Self {
+        self.all_payload_errors = value;
+        self
+    }
+
+    pub fn include_legend(mut self, value: bool) -> Self {
+        self.include_legend = value;
+        self
+    }
+
+    pub fn summary_only(mut self, value: bool) -> Self {
+        self.summary_only = value;
+        self
+    }
+
+    pub fn max_array_elements(mut self, value: usize) -> Self {
+        self.max_array_elements = value;
+        self
+    }
+
+    pub fn render(mut self, render: RenderOptions) -> Self {
+        self.render = render;
+        self
+    }
+
+    pub fn scope(mut self, scope: Option<String>) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn raw_limit(mut self, limit: Option<usize>) -> Self {
+        self.raw_limit = limit;
+        self
+    }
+
+    pub fn max_json_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_json_depth = depth;
+        self
+    }
+
+    pub fn interesting_limit(mut self, limit: usize) -> Self {
+        self.interesting_limit = limit;
+        self
+    }
+
+    pub fn ignore_case(mut self, value: bool) -> Self {
+        self.ignore_case = value;
+        self
+    }
+
+    pub fn ignore_whitespace(mut self, value: bool) -> Self {
+        self.ignore_whitespace = value;
+        self
+    }
+
+    /// Whether `ignore_case`/`ignore_whitespace` make two differing texts equal
+    pub fn texts_equivalent(&self, text1: &str, text2: &str) -> bool {
+        (self.ignore_case || self.ignore_whitespace)
+            && normalize_text(text1, self.ignore_case, self.ignore_whitespace)
+                == normalize_text(text2, self.ignore_case, self.ignore_whitespace)
+    }
+
+    /// Whether a grouped key (`component|LEVEL|Type|details`) passes `only_keys`/`skip_keys`
+    pub fn selects_key(&self, key: &str) -> bool {
+        (self.only_keys.is_empty()
+            || self
+                .only_keys
+                .iter()
+                .any(|pattern| glob_matches(pattern, key)))
+            && !self
+                .skip_keys
+                .iter()
+                .any(|pattern| glob_matches(pattern, key))
+    }
+}
+
+/// Matches `text` against a glob where `*` is any sequence and `?` any single character
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Rules for suppressing expected noise when diffing JSON payloads
+#[derive(Debug, Clone, Default)]
+pub struct JsonDiffRules {
+    /// Paths to ignore (a path also ignores everything nested under it)
+    pub ignore_paths: Vec<String>,
+    /// Absolute tolerance for numeric differences
+    pub tolerance: f64,
+    /// Index-free array path → key field used to pair array-of-object elements
+    pub array_identity_keys: BTreeMap<String, String>,
+    /// Nesting depth below which subtrees are compared as opaque blobs
+    pub max_depth: Option<usize>,
+}
+
+impl JsonDiffRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ignore_paths(mut self, paths: Vec<String>) -> Self {
+        self.ignore_paths = paths;
+        self
+    }
+
+    pub fn tolerance(mut self, value: f64) -> Self {
+        self.tolerance = value;
+        self
+    }
+
+    pub fn array_identity_keys(mut self, keys: BTreeMap<String, String>) -> Self {
+        self.array_identity_keys = keys;
+        self
+    }
+
+    pub fn max_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Whether a difference at `path` is ignored by these rules
+    pub fn ignores(&self, path: &str, value1: &Value, value2: &Value) -> bool {
+        if path_is_ignored(&self.ignore_paths, path) {
+            return true;
+        }
+
+        match (value1.as_f64(), value2.as_f64()) {
+            (Some(a), Some(b)) if self.tolerance > 0.0 => (a - b).abs() <= self.tolerance,
+            _ => false,
+        }
+    }
+}
+
+/// Whether `path` equals or is nested under one of `ignore_paths`; `[]` in an ignored
+/// path matches any array index or identity selector
+pub fn path_is_ignored(ignore_paths: &[String], path: &str) -> bool {
+    let folded = super::rarity_path(path);
+    ignore_paths.iter().any(|ignored| {
+        let candidate = if ignored.contains("[]") {
+            folded.as_str()
+        } else {
+            path
+        };
+        candidate == ignored
+            || candidate
+                .strip_prefix(ignored.as_str())
+                .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+    })
+}
+
+/// Source entry behind a unique instance, so it can be traced back to its input file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceEntry {
+    pub line_number: usize,
+    pub timestamp: DateTime<Local>,
+    pub request_id: Option<String>,
+    pub raw: Option<String>,
 }
 
 /// Results of comparing two sets of logs
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ComparisonResults {
     pub unique_to_log1: Vec<String>,
     pub unique_to_log2: Vec<String>,
+    /// Source entries behind each `unique_to_log1` instance, keyed by the instance
+    #[serde(default)]
+    pub unique_sources1: BTreeMap<String, Vec<SourceEntry>>,
+    /// Source entries behind each `unique_to_log2` instance, keyed by the instance
+    #[serde(default)]
+    pub unique_sources2: BTreeMap<String, Vec<SourceEntry>>,
     pub shared_comparisons: Vec<LogComparison>,
+    /// Components whose level distribution shifted beyond the drift threshold
+    pub level_drift: Vec<super::LevelDrift>,
+    /// Payloads of each compared file that failed to parse (only files with failures)
+    pub payload_errors: Vec<PayloadParseErrors>,
+    /// Payload subtrees compared as opaque blobs because of `max_json_depth`
+    pub truncated_subtrees: usize,
+    /// Pairs per shared key whose payloads matched; counted even when `diff_only`
+    /// leaves them out of `shared_comparisons`
+    pub identical_counts: BTreeMap<String, usize>,
+    /// Message and string-value differences dropped by `ignore_case`/`ignore_whitespace`
+    pub trivial_differences: usize,
 }
 
 impl ComparisonResults {
+    pub fn identical_count(&self, key: &str) -> usize {
+        self.identical_counts.get(key).copied().unwrap_or(0)
+    }
+
+    pub fn total_identical(&self) -> usize {
+        self.identical_counts.values().sum()
+    }
+
+    /// Differences annotated as schema-level changes
+    pub fn schema_change_count(&self) -> usize {
+        self.shared_comparisons
+            .iter()
+            .flat_map(|comparison| &comparison.json_differences)
+            .filter(|diff| diff.schema_change.is_some())
+            .count()
+    }
+
     pub fn summary(&self) -> String {
         format!(
             "Unique to log file 1: {}\nUnique to log file 2: {}\nShared log types: {}",
@@ -2,22 +2,27 @@ use crate::ComparisonOptions;
 use crate::comparator::ComparisonResults;
 use crate::comparator::format_cmp::OutputFormatter;
 use crate::comparator::format_cmp::format_comparison_results;
+use crate::output::OutputFile;
 use comfy_table::Table;
-use std::fs::File;
 use std::io::{self, Write};
 use std::path::Path;
 
 /// File output formatter implementation with improved structure
 pub struct FileFormatter {
-    file: File,
+    file: OutputFile,
 }
 
 impl FileFormatter {
     /// Creates a new file formatter with the given path
     pub fn new(path: &Path) -> io::Result<Self> {
-        let file = File::create(path)?;
+        let file = OutputFile::create(path)?;
         Ok(Self { file })
     }
+
+    /// Moves the finished file into place
+    pub fn finish(self) -> io::Result<()> {
+        self.file.commit()
+    }
 }
 
 impl OutputFormatter for FileFormatter {
@@ -78,5 +83,6 @@ pub fn write_comparison_results(
     output_path: &Path,
 ) -> io::Result<()> {
     let mut formatter = FileFormatter::new(output_path)?;
-    format_comparison_results(&mut formatter, results, options)
+    format_comparison_results(&mut formatter, results, options)?;
+    formatter.finish()
 }
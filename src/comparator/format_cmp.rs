@@ -1,4 +1,8 @@
-use crate::comparator::{ComparisonOptions, ComparisonResults, JsonDifference, LogComparison};
+use crate::comparator::{
+    ComparisonOptions, ComparisonResults, JsonDifference, LevelDrift, LevelShare, LogComparison,
+    SchemaChange, ScoredDifference, most_interesting_differences,
+};
+use crate::payload_view::elide_long_arrays;
 use comfy_table::{Attribute, Cell, ContentArrangement, Table};
 use std::collections::HashMap;
 
@@ -35,6 +39,63 @@ pub fn create_styled_table(headers: &[&str]) -> Table {
     table
 }
 
+fn format_level_drift(drift: &LevelDrift) -> String {
+    let mut shifted: Vec<&LevelShare> = drift
+        .levels
+        .iter()
+        .filter(|share| share.shift().abs() >= 0.05)
+        .collect();
+    shifted.sort_by(|a, b| b.shift().abs().total_cmp(&a.shift().abs()));
+
+    let levels = shifted
+        .iter()
+        .map(|share| {
+            format!(
+                "{} {:.1}% → {:.1}% ({:+.1}pp)",
+                share.level,
+                share.share1,
+                share.share2,
+                share.shift()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{}: {} [{} → {} entries]",
+        drift.component, levels, drift.total1, drift.total2
+    )
+}
+
+fn short_json(value: &serde_json::Value, max_chars: usize) -> String {
+    let rendered = value.to_string();
+    if rendered.chars().count() > max_chars {
+        let truncated: String = rendered.chars().take(max_chars).collect();
+        format!("{truncated}...")
+    } else {
+        rendered
+    }
+}
+
+fn format_interesting_difference(scored: &ScoredDifference, total: usize) -> String {
+    let parts: Vec<&str> = scored.key.split('|').collect();
+    let key = if parts.len() >= 3 {
+        parts[2..].join("|").trim().to_string()
+    } else {
+        scored.key.clone()
+    };
+    format!(
+        "{}: {} ➔ {}  [{}/{} diffs at this path]  {} (L{} ↔ L{})",
+        scored.path,
+        short_json(&scored.value1, 40),
+        short_json(&scored.value2, 40),
+        scored.path_frequency,
+        total,
+        key,
+        scored.log1_line_number,
+        scored.log2_line_number
+    )
+}
+
 /// Formats comparison results using the provided formatter
 pub fn format_comparison_results<F: OutputFormatter>(
     formatter: &mut F,
@@ -48,6 +109,9 @@ pub fn format_comparison_results<F: OutputFormatter>(
         formatter.write_header("LOG COMPARISON SUMMARY")?;
         formatter.write_divider("=", 80)?;
     }
+    if let Some(scope) = &options.scope {
+        formatter.write_info(&format!("Scope: {scope} of each file"))?;
+    }
 
     // Improved summary statistics with colorization
     let unique_log1_count = results.unique_to_log1.len();
@@ -65,6 +129,27 @@ pub fn format_comparison_results<F: OutputFormatter>(
         unique_log1_count, unique_log2_count, shared_log_count
     ))?;
 
+    let total_identical = results.total_identical();
+    if total_identical > 0 && !options.quiet {
+        formatter.write_info(&format!(
+            "{} identical pair(s) across {} shared key(s){}",
+            total_identical,
+            results.identical_counts.len(),
+            if options.diff_only {
+                " (details hidden in diff-only mode)"
+            } else {
+                ""
+            }
+        ))?;
+    }
+
+    let schema_changes = results.schema_change_count();
+    if schema_changes > 0 && !options.quiet {
+        formatter.write_info(&format!(
+            "{schema_changes} difference(s) are schema changes: fields one file never logs for that key"
+        ))?;
+    }
+
     // Show more detailed info at higher verbosity levels
     if crate::comparator::console_cmp::should_print(options, 2) {
         formatter.write_info(&format!(
@@ -73,6 +158,68 @@ pub fn format_comparison_results<F: OutputFormatter>(
         ))?;
     }
 
+    if options.summary_only {
+        return Ok(());
+    }
+
+    // Level drift is an early-warning signal, so it shows at default verbosity
+    if !results.level_drift.is_empty() && !options.quiet {
+        formatter.write_divider("=", 80)?;
+        formatter.write_header("COMPONENT LEVEL DRIFT")?;
+        formatter.write_divider("=", 80)?;
+        for drift in &results.level_drift {
+            formatter.write_warning(&format_level_drift(drift))?;
+        }
+    }
+
+    // Unparseable payloads make the comparison look cleaner than it is
+    if !results.payload_errors.is_empty() && !options.quiet {
+        formatter.write_divider("=", 80)?;
+        formatter.write_header("PAYLOAD PARSE FAILURES")?;
+        formatter.write_divider("=", 80)?;
+        for file_errors in &results.payload_errors {
+            formatter.write_warning(&format!(
+                "{}: {} payload(s) failed to parse",
+                file_errors.file,
+                file_errors.failures.len()
+            ))?;
+            for failure in file_errors.samples(options.all_payload_errors) {
+                formatter.write_line(&format!("  line {}: {}", failure.line, failure.snippet))?;
+            }
+        }
+    }
+
+    if results.truncated_subtrees > 0 && !options.quiet {
+        formatter.write_warning(&format!(
+            "{} payload subtree(s) deeper than {} levels compared as opaque blobs",
+            results.truncated_subtrees,
+            options.max_json_depth.unwrap_or_default()
+        ))?;
+    }
+
+    if results.trivial_differences > 0 && !options.quiet {
+        formatter.write_info(&format!(
+            "{} trivial text difference(s) suppressed by {}",
+            results.trivial_differences,
+            match (options.ignore_case, options.ignore_whitespace) {
+                (true, true) => "--ignore-case/--ignore-whitespace",
+                (true, false) => "--ignore-case",
+                _ => "--ignore-whitespace",
+            }
+        ))?;
+    }
+
+    // Rare one-off changes get lost among systematic ones, so list them first
+    let interesting = most_interesting_differences(results, options.interesting_limit);
+    if !interesting.is_empty() && !options.quiet {
+        formatter.write_divider("=", 80)?;
+        formatter.write_header("MOST INTERESTING DIFFERENCES (rarest paths first)")?;
+        formatter.write_divider("=", 80)?;
+        for scored in &interesting {
+            formatter.write_line(&format_interesting_difference(scored, total_comparisons))?;
+        }
+    }
+
     // Display unique keys with better formatting - only in normal/verbose mode
     if crate::comparator::console_cmp::should_print(options, 1) {
         if !results.unique_to_log1.is_empty() {
@@ -179,6 +326,12 @@ pub fn format_comparison_results<F: OutputFormatter>(
             }
 
             let parts: Vec<&str> = key.split('|').collect();
+            let identical_note = match results.identical_count(key) {
+                identical if options.diff_only && identical > 0 => {
+                    format!(", {identical} identical")
+                }
+                _ => String::new(),
+            };
 
             // Print formatted key header with clearer structure
             formatter.write_line("")?;
@@ -196,21 +349,23 @@ pub fn format_comparison_results<F: OutputFormatter>(
                 };
 
                 formatter.write_highlight(&format!(
-                    "[K{}] {} {} {}{}{} ({} instances)",
+                    "[K{}] {} {} {}{}{} ({} instances{})",
                     key_idx + 1,
                     component,
                     level,
                     kind,
                     if details.is_empty() { "" } else { ": " },
                     details,
-                    comparisons.len()
+                    comparisons.len(),
+                    identical_note
                 ))?;
             } else {
                 formatter.write_highlight(&format!(
-                    "[K{}] {} ({} instances)",
+                    "[K{}] {} ({} instances{})",
                     key_idx + 1,
                     key,
-                    comparisons.len()
+                    comparisons.len(),
+                    identical_note
                 ))?;
             }
             formatter.write_divider("▲", 80)?;
@@ -225,18 +380,23 @@ pub fn format_comparison_results<F: OutputFormatter>(
                     continue;
                 }
 
+                let cluster_note = comparison
+                    .cluster_sizes
+                    .map(|(size1, size2)| format!(" [payload cluster {size1} ↔ {size2}]"))
+                    .unwrap_or_default();
                 formatter.write_line(&format!(
-                    "\n{}/{}. FILE1 #{} (line {}) ↔ FILE2 #{} (line {})",
+                    "\n{}/{}. FILE1 #{} (line {}) ↔ FILE2 #{} (line {}){}",
                     idx + 1,
                     comparisons.len(),
                     comparison.log1_index,
                     comparison.log1_line_number,
                     comparison.log2_index,
-                    comparison.log2_line_number
+                    comparison.log2_line_number,
+                    cluster_note
                 ))?;
 
                 if options.show_full_json {
-                    format_full_json_comparison(formatter, comparison)?;
+                    format_full_json_comparison(formatter, comparison, options.max_array_elements)?;
                 } else {
                     format_json_differences(formatter, comparison)?;
                 }
@@ -263,6 +423,24 @@ pub fn format_comparison_results<F: OutputFormatter>(
         formatter.write_divider("=", 80)?;
     }
 
+    // Diff-only output drops fully identical keys; list them so "identical" is
+    // distinguishable from "never compared"
+    if options.diff_only && !options.quiet {
+        let identical_keys: Vec<(&String, &usize)> = results
+            .identical_counts
+            .iter()
+            .filter(|(key, _)| !grouped_comparisons.contains_key(key))
+            .collect();
+        if !identical_keys.is_empty() {
+            formatter.write_divider("=", 80)?;
+            formatter.write_header("SHARED LOGS WITH ONLY IDENTICAL PAIRS")?;
+            formatter.write_divider("=", 80)?;
+            for (key, count) in identical_keys {
+                formatter.write_line(&format!("{count:>6} identical  {key}"))?;
+            }
+        }
+    }
+
     formatter.write_line("\nComparison completed successfully.")?;
     Ok(())
 }
@@ -350,19 +528,30 @@ pub fn format_json_differences<F: OutputFormatter>(
 
             // Improved formatting for differences
             formatter.write_line(&format!(
-                "    [D:{}] [{}] {} :",
+                "    [D:{}] [{}] {} :{}",
                 diff_idx + 1,
                 change_indicator,
-                path_display
+                path_display,
+                match diff.schema_change {
+                    Some(SchemaChange::FieldAdded) => " [schema: field new in file 2]",
+                    Some(SchemaChange::FieldRemoved) => " [schema: field gone from file 2]",
+                    None => "",
+                }
             ))?;
+            // Each value is prefixed with the `<file>:<line>` of the entry it came from
             formatter.write_source_file1(&format!(
-                "      {}{}",
+                "      1:{}  {}{}",
+                comparison.log1_line_number,
                 value1_display,
                 if value1_truncated { " (truncated)" } else { "" }
             ))?;
-            formatter.write_line("      ➔")?;
+            match diff.numeric_delta() {
+                Some(delta) => formatter.write_line(&format!("      ➔ {}", delta.label()))?,
+                None => formatter.write_line("      ➔")?,
+            }
             formatter.write_source_file2(&format!(
-                "      {}{}",
+                "      2:{}  {}{}",
+                comparison.log2_line_number,
                 value2_display,
                 if value2_truncated { " (truncated)" } else { "" }
             ))?;
@@ -372,10 +561,12 @@ pub fn format_json_differences<F: OutputFormatter>(
     Ok(())
 }
 
-/// Formats full JSON comparison with better indentation and structure
+/// Formats full JSON comparison with better indentation and structure; arrays longer than
+/// `max_array_elements` keep only their first and last elements (0 = all)
 pub fn format_full_json_comparison<F: OutputFormatter>(
     formatter: &mut F,
     comparison: &LogComparison,
+    max_array_elements: usize,
 ) -> std::io::Result<()> {
     if let (Some(log1_payload), Some(log2_payload)) =
         (&comparison.log1_payload, &comparison.log2_payload)
@@ -383,7 +574,7 @@ pub fn format_full_json_comparison<F: OutputFormatter>(
         formatter.write_label("  FULL JSON COMPARISON:")?;
 
         formatter.write_source_file1("  LOG FILE 1:")?;
-        match serde_json::to_string_pretty(log1_payload) {
+        match serde_json::to_string_pretty(&elide_long_arrays(log1_payload, max_array_elements)) {
             Ok(json) => {
                 // Indent each line for better readability
                 for line in json.lines() {
@@ -394,7 +585,7 @@ pub fn format_full_json_comparison<F: OutputFormatter>(
         };
 
         formatter.write_source_file2("\n  LOG FILE 2:")?;
-        match serde_json::to_string_pretty(log2_payload) {
+        match serde_json::to_string_pretty(&elide_long_arrays(log2_payload, max_array_elements)) {
             Ok(json) => {
                 // Indent each line for better readability
                 for line in json.lines() {
@@ -1,5 +1,6 @@
 use crate::LogEntry;
 use crate::comparator::entities::LogFilter;
+use crate::render::RenderOptions;
 use colored::Colorize;
 use similar::{ChangeTag, TextDiff};
 use std::collections::HashMap;
@@ -26,15 +27,30 @@ pub fn get_log_key(log: &LogEntry) -> String {
     format!("{}|{}|{}", log.component, log.level, log.log_key())
 }
 
+/// Text with letter case folded and/or whitespace removed, so reformatting that
+/// only changes those does not register as a difference
+pub fn normalize_text(text: &str, ignore_case: bool, ignore_whitespace: bool) -> String {
+    let text: String = if ignore_whitespace {
+        text.chars().filter(|c| !c.is_whitespace()).collect()
+    } else {
+        text.to_string()
+    };
+    if ignore_case {
+        text.to_lowercase()
+    } else {
+        text
+    }
+}
+
 /// Computes a colored text diff between two strings
-pub fn compute_text_diff(text1: &str, text2: &str) -> String {
+pub fn compute_text_diff(text1: &str, text2: &str, render: &RenderOptions) -> String {
     let diff = TextDiff::from_lines(text1, text2);
     let mut result = String::new();
 
     for change in diff.iter_all_changes() {
         match change.tag() {
-            ChangeTag::Delete => result.push_str(&format!("{}", change.to_string().red())),
-            ChangeTag::Insert => result.push_str(&format!("{}", change.to_string().green())),
+            ChangeTag::Delete => result.push_str(&render.paint(change.to_string().red())),
+            ChangeTag::Insert => result.push_str(&render.paint(change.to_string().green())),
             ChangeTag::Equal => continue,
         }
     }
@@ -2,8 +2,203 @@ use crate::ComparisonOptions;
 use crate::comparator::ComparisonResults;
 use crate::comparator::JsonDifference;
 use crate::comparator::LogComparison;
+use crate::comparator::SourceEntry;
+use crate::comparator::most_interesting_differences;
+use crate::legend::{COMPACT_SCHEMA_VERSION, compact_diff_legend};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
 use serde_json::{Value, json};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
+fn level_drift_values(results: &ComparisonResults) -> Value {
+    results
+        .level_drift
+        .iter()
+        .map(|drift| drift.to_json())
+        .collect()
+}
+
+fn payload_error_values(results: &ComparisonResults, options: &ComparisonOptions) -> Value {
+    results
+        .payload_errors
+        .iter()
+        .map(|file_errors| file_errors.to_json(options.all_payload_errors))
+        .collect()
+}
+
+fn interesting_values(
+    results: &ComparisonResults,
+    options: &ComparisonOptions,
+    compact: bool,
+) -> Option<Value> {
+    let interesting = most_interesting_differences(results, options.interesting_limit);
+    (!interesting.is_empty()).then(|| {
+        interesting
+            .iter()
+            .map(|scored| scored.to_json(compact))
+            .collect()
+    })
+}
+
+fn payload_error_counts(results: &ComparisonResults) -> Value {
+    results
+        .payload_errors
+        .iter()
+        .map(|file_errors| (file_errors.file.clone(), json!(file_errors.failures.len())))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// Differences of a key group by path, each with the position of the instance it came from
+fn path_groups<'c>(
+    comparisons: &[&'c LogComparison],
+) -> BTreeMap<&'c str, Vec<(usize, &'c LogComparison, &'c JsonDifference)>> {
+    let mut groups: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+    for (instance, comparison) in comparisons.iter().enumerate() {
+        for diff in &comparison.json_differences {
+            groups
+                .entry(diff.path.as_str())
+                .or_default()
+                .push((instance, *comparison, diff));
+        }
+    }
+    groups
+}
+
+/// One value pair of a path group together with where it came from: the position of
+/// its instance in the group's `instances` and the line and timestamp of each side
+fn value_provenance(
+    instance: usize,
+    comparison: &LogComparison,
+    diff: &JsonDifference,
+    compact: bool,
+) -> Value {
+    let timestamps = [comparison.log1_timestamp, comparison.log2_timestamp].map(|timestamp| {
+        timestamp
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Millis, true)
+    });
+    let mut value = if compact {
+        json!({
+            "n": instance,
+            "v1": diff.value1,
+            "v2": diff.value2,
+            "ln": [comparison.log1_line_number, comparison.log2_line_number],
+            "ts": timestamps,
+        })
+    } else {
+        json!({
+            "instance": instance,
+            "value1": diff.value1,
+            "value2": diff.value2,
+            "log1_line": comparison.log1_line_number,
+            "log2_line": comparison.log2_line_number,
+            "log1_timestamp": timestamps[0],
+            "log2_timestamp": timestamps[1],
+        })
+    };
+    if let Some(delta) = diff.numeric_delta() {
+        value[if compact { "d" } else { "delta" }] = delta.to_json();
+    }
+    value
+}
+
+/// Reference back to the exact source entry behind one side of a comparison.
+/// `id` is `<file_index>:<line>` and is unique across both input files.
+fn source_ref(
+    file_index: usize,
+    line: usize,
+    timestamp: &DateTime<Local>,
+    request_id: Option<&str>,
+    raw: Option<&str>,
+    compact: bool,
+) -> Value {
+    let id = format!("{file_index}:{line}");
+    let timestamp = timestamp
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true);
+    let mut value = if compact {
+        json!({ "id": id, "f": file_index, "ln": line, "ts": timestamp })
+    } else {
+        json!({ "id": id, "file_index": file_index, "line": line, "timestamp": timestamp })
+    };
+    if let Some(request_id) = request_id {
+        value[if compact { "rid" } else { "request_id" }] = json!(request_id);
+    }
+    if let Some(raw) = raw {
+        value["raw"] = json!(raw);
+    }
+    value
+}
+
+/// References to the source entries behind one unique instance
+fn unique_source_refs(
+    sources: &BTreeMap<String, Vec<SourceEntry>>,
+    file_index: usize,
+    instance: &str,
+    compact: bool,
+) -> Value {
+    let refs = sources.get(instance).into_iter().flatten().map(|source| {
+        source_ref(
+            file_index,
+            source.line_number,
+            &source.timestamp,
+            source.request_id.as_deref(),
+            source.raw.as_deref(),
+            compact,
+        )
+    });
+    Value::Array(refs.collect())
+}
+
+fn source_refs(comparison: &LogComparison, compact: bool) -> (Value, Value) {
+    (
+        source_ref(
+            1,
+            comparison.log1_line_number,
+            &comparison.log1_timestamp,
+            comparison.log1_request_id.as_deref(),
+            comparison.log1_raw.as_deref(),
+            compact,
+        ),
+        source_ref(
+            2,
+            comparison.log2_line_number,
+            &comparison.log2_timestamp,
+            comparison.log2_request_id.as_deref(),
+            comparison.log2_raw.as_deref(),
+            compact,
+        ),
+    )
+}
+
+/// Shared comparisons to render, one group per key in the order keys first appear (the
+/// `--sort-by` order), with each group's instances ordered by their log indices
+fn key_groups<'r>(
+    results: &'r ComparisonResults,
+    options: &ComparisonOptions,
+) -> Vec<(&'r str, Vec<&'r LogComparison>)> {
+    let mut groups: Vec<(&str, Vec<&LogComparison>)> = Vec::new();
+    let mut positions: BTreeMap<&str, usize> = BTreeMap::new();
+    for comparison in &results.shared_comparisons {
+        // Filter out comparisons without differences if diff_only is set
+        if options.diff_only && comparison.json_differences.is_empty() && comparison.text1.is_none()
+        {
+            continue;
+        }
+        let position = *positions.entry(&comparison.key).or_insert_with(|| {
+            groups.push((&comparison.key, Vec::new()));
+            groups.len() - 1
+        });
+        groups[position].1.push(comparison);
+    }
+    for (_, group) in &mut groups {
+        group.sort_by_key(|comparison| (comparison.log1_index, comparison.log2_index));
+    }
+    groups
+}
+
+/// Top-level keys kept by `--summary-only` in every JSON layout
+const SUMMARY_KEYS: &[&str] = &["s", "summary", "schema_version", "legend"];
 
 /// JSON output formatter for LLM consumption
 pub struct JsonFormatter {
@@ -50,16 +245,56 @@ impl JsonFormatter {
             "u2c": unique_log2_count,      // unique_to_log2_count
             "sc": shared_log_count,        // shared_count
             "dc": total_diff_count,        // differences_count
-            "hd": total_diff_count > 0     // has_differences
+            "hd": total_diff_count > 0,    // has_differences
+            "idc": results.total_identical(), // identical_count
+            "scc": results.schema_change_count() // schema_changes_count
         });
 
         self.output["s"] = summary;
+        self.output["schema_version"] = json!(COMPACT_SCHEMA_VERSION);
+        if options.include_legend {
+            self.output["legend"] = compact_diff_legend(); // scope -> short key -> full name
+        }
+
+        if !results.level_drift.is_empty() {
+            self.output["ld"] = results
+                .level_drift
+                .iter()
+                .map(|drift| {
+                    json!({
+                        "c": drift.component,                  // component
+                        "n": [drift.total1, drift.total2],     // entry totals
+                        "l": drift                             // level -> [count1, count2]
+                            .levels
+                            .iter()
+                            .map(|share| (share.level.clone(), json!([share.count1, share.count2])))
+                            .collect::<serde_json::Map<_, _>>()
+                    })
+                })
+                .collect();
+        }
+
+        if !results.payload_errors.is_empty() {
+            self.output["pe"] = payload_error_values(results, options); // payload parse failures
+        }
+        if results.truncated_subtrees > 0 {
+            self.output["td"] = json!(results.truncated_subtrees); // subtrees cut by max depth
+        }
+        if results.trivial_differences > 0 {
+            self.output["tds"] = json!(results.trivial_differences); // trivial diffs suppressed
+        }
+        if let Some(interesting) = interesting_values(results, options, true) {
+            self.output["mi"] = interesting; // most interesting (rarest) differences
+        }
+        if !results.identical_counts.is_empty() {
+            self.output["idk"] = json!(results.identical_counts); // identical pairs per key
+        }
 
         // Keep unique/unpaired entries in JSON output even for diff mode.
-        self.add_unique_logs_compact(&results.unique_to_log1, &results.unique_to_log2);
+        self.add_unique_logs_compact(results);
 
         // Add shared comparisons
-        self.add_comparisons_compact(&results.shared_comparisons, options);
+        self.add_comparisons_compact(results, options);
 
         self.output.clone()
     }
@@ -93,7 +328,9 @@ impl JsonFormatter {
             "unique_to_log2_count": unique_log2_count,
             "shared_count": shared_log_count,
             "differences_count": total_diff_count,
-            "has_differences": total_diff_count > 0
+            "has_differences": total_diff_count > 0,
+            "identical_count": results.total_identical(),
+            "schema_changes_count": results.schema_change_count()
         });
 
         readable_output["summary"] = summary;
@@ -104,7 +341,7 @@ impl JsonFormatter {
             .enumerate()
             .map(|(idx, key)| {
                 let parts: Vec<&str> = key.split('|').collect();
-                if parts.len() >= 3 {
+                let mut unique = if parts.len() >= 3 {
                     json!({
                         "index": idx,
                         "component": parts[0],
@@ -118,7 +355,9 @@ impl JsonFormatter {
                         "index": idx,
                         "raw_key": key
                     })
-                }
+                };
+                unique["sources"] = unique_source_refs(&results.unique_sources1, 1, key, false);
+                unique
             })
             .collect();
 
@@ -128,7 +367,7 @@ impl JsonFormatter {
             .enumerate()
             .map(|(idx, key)| {
                 let parts: Vec<&str> = key.split('|').collect();
-                if parts.len() >= 3 {
+                let mut unique = if parts.len() >= 3 {
                     json!({
                         "index": idx,
                         "component": parts[0],
@@ -142,52 +381,54 @@ impl JsonFormatter {
                         "index": idx,
                         "raw_key": key
                     })
-                }
+                };
+                unique["sources"] = unique_source_refs(&results.unique_sources2, 2, key, false);
+                unique
             })
             .collect();
 
         readable_output["unique_to_log1"] = Value::Array(unique1);
         readable_output["unique_to_log2"] = Value::Array(unique2);
 
-        // Group comparisons by key
-        let mut comparisons_array = Vec::new();
-        let mut current_key = String::new();
-        let mut current_group = Vec::new();
-
-        for comparison in &results.shared_comparisons {
-            // Filter out comparisons without differences if diff_only is set
-            if options.diff_only
-                && comparison.json_differences.is_empty()
-                && comparison.text1.is_none()
-            {
-                continue;
-            }
+        let comparisons_array: Vec<Value> = key_groups(results, options)
+            .into_iter()
+            .map(|(key, group)| {
+                self.format_key_group_readable(key, &group, results.identical_count(key))
+            })
+            .collect();
 
-            if comparison.key != current_key {
-                if !current_group.is_empty() {
-                    // Add the previous group
-                    let key_entry = self.format_key_group_readable(&current_key, &current_group);
-                    comparisons_array.push(key_entry);
-                    current_group = Vec::new();
-                }
-                current_key = comparison.key.clone();
-            }
-            current_group.push(comparison);
+        readable_output["comparisons"] = Value::Array(comparisons_array);
+        if !results.level_drift.is_empty() {
+            readable_output["level_drift"] = level_drift_values(results);
         }
-
-        // Add the last group if it exists
-        if !current_group.is_empty() {
-            let key_entry = self.format_key_group_readable(&current_key, &current_group);
-            comparisons_array.push(key_entry);
+        if results.truncated_subtrees > 0 {
+            readable_output["summary"]["truncated_subtrees"] = json!(results.truncated_subtrees);
+        }
+        if results.trivial_differences > 0 {
+            readable_output["summary"]["trivial_differences_suppressed"] =
+                json!(results.trivial_differences);
+        }
+        if let Some(interesting) = interesting_values(results, options, false) {
+            readable_output["most_interesting_differences"] = interesting;
+        }
+        if !results.identical_counts.is_empty() {
+            readable_output["identical_counts"] = json!(results.identical_counts);
+        }
+        if !results.payload_errors.is_empty() {
+            readable_output["summary"]["payload_parse_failures"] = payload_error_counts(results);
+            readable_output["payload_errors"] = payload_error_values(results, options);
         }
-
-        readable_output["comparisons"] = Value::Array(comparisons_array);
 
         readable_output
     }
 
     /// Formats a key group in readable format
-    fn format_key_group_readable(&self, key: &str, comparisons: &[&LogComparison]) -> Value {
+    fn format_key_group_readable(
+        &self,
+        key: &str,
+        comparisons: &[&LogComparison],
+        identical: usize,
+    ) -> Value {
         let parts: Vec<&str> = key.split('|').collect();
 
         let key_info = if parts.len() >= 3 {
@@ -204,58 +445,55 @@ impl JsonFormatter {
             })
         };
 
-        // Group differences by path
-        let mut path_groups: HashMap<String, Vec<(&JsonDifference, usize, usize)>> = HashMap::new();
-
-        // Collect all differences by path
-        for comparison in comparisons {
-            for diff in &comparison.json_differences {
-                let entry = path_groups.entry(diff.path.clone()).or_default();
-                entry.push((diff, comparison.log1_index, comparison.log2_index));
-            }
-        }
-
         // Create comparison instances with references to differences
         let comparison_values: Vec<Value> = comparisons
             .iter()
             .map(|comparison| {
-                json!({
+                let (source1, source2) = source_refs(comparison, false);
+                let mut instance = json!({
                     "log1_index": comparison.log1_index,
                     "log2_index": comparison.log2_index,
                     "text1": comparison.text1,
                     "text2": comparison.text2,
                     "log1_line": comparison.log1_line_number,
                     "log2_line": comparison.log2_line_number,
+                    "log1_source": source1,
+                    "log2_source": source2,
                     "diff_count": comparison.json_differences.len()
-                })
+                });
+                if let Some((size1, size2)) = comparison.cluster_sizes {
+                    instance["cluster_sizes"] = json!([size1, size2]);
+                }
+                instance
             })
             .collect();
 
         // Create path-grouped differences
         let mut differences = Vec::new();
-        for (path, diffs) in path_groups {
-            let mut values1 = Vec::new();
-            let mut values2 = Vec::new();
-            let mut indexes = Vec::new();
-
-            for (diff, log1_idx, log2_idx) in diffs {
-                values1.push(diff.value1.clone());
-                values2.push(diff.value2.clone());
-                indexes.push(json!([log1_idx, log2_idx]));
-            }
+        for (path, diffs) in path_groups(comparisons) {
+            let schema_change = diffs.iter().find_map(|(_, _, diff)| diff.schema_change);
+            let values: Vec<Value> = diffs
+                .iter()
+                .map(|(instance, comparison, diff)| {
+                    value_provenance(*instance, comparison, diff, false)
+                })
+                .collect();
 
-            differences.push(json!({
+            let mut difference = json!({
                 "path": path,
-                "value1": values1,
-                "value2": values2,
-                "indexes": indexes
-            }));
+                "values": values
+            });
+            if let Some(change) = schema_change {
+                difference["schema_change"] = json!(change.as_str());
+            }
+            differences.push(difference);
         }
 
         json!({
             "key": key_info,
             "instances": comparison_values,
             "instance_count": comparisons.len(),
+            "identical_count": identical,
             "differences": differences
         })
     }
@@ -289,7 +527,9 @@ impl JsonFormatter {
             "unique_to_log2_count": unique_log2_count,
             "shared_log_count": shared_log_count,
             "total_differences_count": total_diff_count,
-            "has_differences": total_diff_count > 0
+            "has_differences": total_diff_count > 0,
+            "identical_count": results.total_identical(),
+            "schema_changes_count": results.schema_change_count()
         });
 
         standard_output["summary"] = summary;
@@ -300,7 +540,7 @@ impl JsonFormatter {
             .enumerate()
             .map(|(idx, key)| {
                 let parts: Vec<&str> = key.split('|').collect();
-                if parts.len() >= 3 {
+                let mut unique = if parts.len() >= 3 {
                     json!({
                         "index": idx,
                         "component": parts[0],
@@ -314,7 +554,9 @@ impl JsonFormatter {
                         "index": idx,
                         "raw_key": key
                     })
-                }
+                };
+                unique["sources"] = unique_source_refs(&results.unique_sources1, 1, key, false);
+                unique
             })
             .collect();
 
@@ -324,7 +566,7 @@ impl JsonFormatter {
             .enumerate()
             .map(|(idx, key)| {
                 let parts: Vec<&str> = key.split('|').collect();
-                if parts.len() >= 3 {
+                let mut unique = if parts.len() >= 3 {
                     json!({
                         "index": idx,
                         "component": parts[0],
@@ -338,7 +580,9 @@ impl JsonFormatter {
                         "index": idx,
                         "raw_key": key
                     })
-                }
+                };
+                unique["sources"] = unique_source_refs(&results.unique_sources2, 2, key, false);
+                unique
             })
             .collect();
 
@@ -364,7 +608,9 @@ impl JsonFormatter {
             if comparison.key != current_key {
                 if !current_group.is_empty() {
                     // Add the previous group
-                    let key_entry = self.format_key_group_standard(&current_key, &current_group);
+                    let identical = results.identical_count(&current_key);
+                    let key_entry =
+                        self.format_key_group_standard(&current_key, &current_group, identical);
                     comparisons_array.push(key_entry);
                     current_group = Vec::new();
                 }
@@ -375,16 +621,42 @@ impl JsonFormatter {
 
         // Add the last group if it exists
         if !current_group.is_empty() {
-            let key_entry = self.format_key_group_standard(&current_key, &current_group);
+            let identical = results.identical_count(&current_key);
+            let key_entry = self.format_key_group_standard(&current_key, &current_group, identical);
             comparisons_array.push(key_entry);
         }
 
         standard_output["comparisons"] = Value::Array(comparisons_array);
+        if !results.level_drift.is_empty() {
+            standard_output["level_drift"] = level_drift_values(results);
+        }
+        if results.truncated_subtrees > 0 {
+            standard_output["summary"]["truncated_subtrees"] = json!(results.truncated_subtrees);
+        }
+        if results.trivial_differences > 0 {
+            standard_output["summary"]["trivial_differences_suppressed"] =
+                json!(results.trivial_differences);
+        }
+        if let Some(interesting) = interesting_values(results, options, false) {
+            standard_output["most_interesting_differences"] = interesting;
+        }
+        if !results.identical_counts.is_empty() {
+            standard_output["identical_counts"] = json!(results.identical_counts);
+        }
+        if !results.payload_errors.is_empty() {
+            standard_output["summary"]["payload_parse_failures"] = payload_error_counts(results);
+            standard_output["payload_errors"] = payload_error_values(results, options);
+        }
 
         standard_output
     }
 
-    fn format_key_group_standard(&self, key: &str, comparisons: &[&LogComparison]) -> Value {
+    fn format_key_group_standard(
+        &self,
+        key: &str,
+        comparisons: &[&LogComparison],
+        identical: usize,
+    ) -> Value {
         let parts: Vec<&str> = key.split('|').collect();
 
         let key_info = if parts.len() >= 3 {
@@ -405,7 +677,8 @@ impl JsonFormatter {
             .iter()
             .map(|comparison| {
                 let diffs = self.format_json_differences_standard(&comparison.json_differences);
-                json!({
+                let (source1, source2) = source_refs(comparison, false);
+                let mut instance = json!({
                     "log1_index": comparison.log1_index,
                     "log2_index": comparison.log2_index,
                     "json_differences": diffs,
@@ -413,15 +686,22 @@ impl JsonFormatter {
                     "text2": comparison.text2,
                     "log1_line": comparison.log1_line_number,
                     "log2_line": comparison.log2_line_number,
+                    "log1_source": source1,
+                    "log2_source": source2,
                     "diff_count": comparison.json_differences.len()
-                })
+                });
+                if let Some((size1, size2)) = comparison.cluster_sizes {
+                    instance["cluster_sizes"] = json!([size1, size2]);
+                }
+                instance
             })
             .collect();
 
         json!({
             "key": key_info,
             "instances": comparison_values,
-            "instance_count": comparisons.len()
+            "instance_count": comparisons.len(),
+            "identical_count": identical
         })
     }
 
@@ -434,25 +714,35 @@ impl JsonFormatter {
                     crate::comparator::ChangeType::Removed => "removed",
                     crate::comparator::ChangeType::Modified => "modified",
                 };
-                json!({
+                let mut value = json!({
                     "path": diff.path,
                     "value1": diff.value1,
                     "value2": diff.value2,
                     "change_type": change_type_str
-                })
+                });
+                if let Some(delta) = diff.numeric_delta() {
+                    value["delta"] = delta.to_json();
+                }
+                if let Some(change) = diff.schema_change {
+                    value["schema_change"] = json!(change.as_str());
+                }
+                value
             })
             .collect();
         Value::Array(diffs)
     }
 
     /// Adds unique logs in compact format
-    fn add_unique_logs_compact(&mut self, unique_to_log1: &[String], unique_to_log2: &[String]) {
-        let unique1: Vec<Value> = unique_to_log1
+    fn add_unique_logs_compact(&mut self, results: &ComparisonResults) {
+        let sources1 = &results.unique_sources1;
+        let sources2 = &results.unique_sources2;
+        let unique1: Vec<Value> = results
+            .unique_to_log1
             .iter()
             .enumerate()
             .map(|(idx, key)| {
                 let parts: Vec<&str> = key.split('|').collect();
-                if parts.len() >= 3 {
+                let mut unique = if parts.len() >= 3 {
                     json!({
                         "i": idx,               // index
                         "c": parts[0],          // component
@@ -466,16 +756,19 @@ impl JsonFormatter {
                         "i": idx,               // index
                         "r": key                // raw_key
                     })
-                }
+                };
+                unique["src"] = unique_source_refs(sources1, 1, key, true);
+                unique
             })
             .collect();
 
-        let unique2: Vec<Value> = unique_to_log2
+        let unique2: Vec<Value> = results
+            .unique_to_log2
             .iter()
             .enumerate()
             .map(|(idx, key)| {
                 let parts: Vec<&str> = key.split('|').collect();
-                if parts.len() >= 3 {
+                let mut unique = if parts.len() >= 3 {
                     json!({
                         "i": idx,               // index
                         "c": parts[0],          // component
@@ -489,7 +782,9 @@ impl JsonFormatter {
                         "i": idx,               // index
                         "r": key                // raw_key
                     })
-                }
+                };
+                unique["src"] = unique_source_refs(sources2, 2, key, true);
+                unique
             })
             .collect();
 
@@ -500,40 +795,21 @@ impl JsonFormatter {
     /// Adds comparisons in compact format
     fn add_comparisons_compact(
         &mut self,
-        comparisons: &[LogComparison],
+        results: &ComparisonResults,
         options: &ComparisonOptions,
     ) {
-        let mut current_key = String::new();
-        let mut current_group = Vec::new();
-
-        for comparison in comparisons {
-            // Filter out comparisons without differences if diff_only is set
-            if options.diff_only
-                && comparison.json_differences.is_empty()
-                && comparison.text1.is_none()
-            {
-                continue;
-            }
-
-            if comparison.key != current_key {
-                if !current_group.is_empty() {
-                    // Add the previous group
-                    self.add_key_group_compact(&current_key, &current_group);
-                    current_group = Vec::new();
-                }
-                current_key = comparison.key.clone();
-            }
-            current_group.push(comparison);
-        }
-
-        // Add the last group if it exists
-        if !current_group.is_empty() {
-            self.add_key_group_compact(&current_key, &current_group);
+        for (key, group) in key_groups(results, options) {
+            self.add_key_group_compact(key, &group, results.identical_count(key));
         }
     }
 
     /// Creates a JSON group for comparisons with the same key in compact format
-    fn add_key_group_compact(&mut self, key: &str, comparisons: &[&LogComparison]) {
+    fn add_key_group_compact(
+        &mut self,
+        key: &str,
+        comparisons: &[&LogComparison],
+        identical: usize,
+    ) {
         let parts: Vec<&str> = key.split('|').collect();
 
         let key_info = if parts.len() >= 3 {
@@ -550,58 +826,55 @@ impl JsonFormatter {
             })
         };
 
-        // Group differences by path
-        let mut path_groups: HashMap<String, Vec<(&JsonDifference, usize, usize)>> = HashMap::new();
-
-        // Collect all differences by path
-        for comparison in comparisons.iter() {
-            for diff in &comparison.json_differences {
-                let entry = path_groups.entry(diff.path.clone()).or_default();
-                entry.push((diff, comparison.log1_index, comparison.log2_index));
-            }
-        }
-
         // Create comparison instances with references to differences
         let comparison_values: Vec<Value> = comparisons
             .iter()
             .map(|comparison| {
-                json!({
+                let (source1, source2) = source_refs(comparison, true);
+                let mut instance = json!({
                     "l1": comparison.log1_index, // log1_index
                     "l2": comparison.log2_index, // log2_index
                     "t1": comparison.text1,  // text1
                     "t2": comparison.text2,  // text2
                     "ln1": comparison.log1_line_number, // log1_line_number
                     "ln2": comparison.log2_line_number, // log2_line_number
+                    "s1": source1, // log1_source
+                    "s2": source2, // log2_source
                     "dc": comparison.json_differences.len() // diff_count
-                })
+                });
+                if let Some((size1, size2)) = comparison.cluster_sizes {
+                    instance["cs"] = json!([size1, size2]); // cluster_sizes
+                }
+                instance
             })
             .collect();
 
         // Create path-grouped differences
         let mut differences = Vec::new();
-        for (path, diffs) in path_groups {
-            let mut values1 = Vec::new();
-            let mut values2 = Vec::new();
-            let mut indexes = Vec::new();
-
-            for (diff, log1_idx, log2_idx) in diffs {
-                values1.push(diff.value1.clone());
-                values2.push(diff.value2.clone());
-                indexes.push(json!([log1_idx, log2_idx]));
-            }
+        for (path, diffs) in path_groups(comparisons) {
+            let schema_change = diffs.iter().find_map(|(_, _, diff)| diff.schema_change);
+            let values: Vec<Value> = diffs
+                .iter()
+                .map(|(instance, comparison, diff)| {
+                    value_provenance(*instance, comparison, diff, true)
+                })
+                .collect();
 
-            differences.push(json!({
+            let mut difference = json!({
                 "p": path,           // path
-                "v1": values1,       // value1 array
-                "v2": values2,       // value2 array
-                "i": indexes         // indexes of comparisons
-            }));
+                "vs": values         // values with provenance
+            });
+            if let Some(change) = schema_change {
+                difference["sc"] = json!(change.as_str()); // schema_change
+            }
+            differences.push(difference);
         }
 
         let key_entry = json!({
             "k": key_info,           // key information
             "i": comparison_values,  // instances
             "ic": comparisons.len(), // instance_count
+            "idc": identical,        // identical_count
             "d": differences         // path-grouped differences
         });
 
@@ -615,7 +888,12 @@ impl JsonFormatter {
 /// Generates JSON representation of comparison results for LLM consumption
 pub fn generate_json_output(results: &ComparisonResults, options: &ComparisonOptions) -> String {
     let mut formatter = JsonFormatter::new();
-    let json_value = formatter.format_results(results, options);
+    let mut json_value = formatter.format_results(results, options);
+    if options.summary_only
+        && let Some(document) = json_value.as_object_mut()
+    {
+        document.retain(|key, _| SUMMARY_KEYS.contains(&key.as_str()));
+    }
 
     // Format JSON based on options
     if options.compact_mode {
@@ -0,0 +1,141 @@
+use super::split_key_parts;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Components need at least this many entries in both files before their level mix is judged
+pub const MIN_DRIFT_ENTRIES: usize = 5;
+
+/// Per-component counts by level, keyed `component -> level -> count`
+pub type LevelCounts = BTreeMap<String, BTreeMap<String, usize>>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelShare {
+    pub level: String,
+    pub count1: usize,
+    pub count2: usize,
+    /// Share of the component's entries in file 1 (0-100)
+    pub share1: f64,
+    /// Share of the component's entries in file 2 (0-100)
+    pub share2: f64,
+}
+
+impl LevelShare {
+    /// Change in share, in percentage points
+    pub fn shift(&self) -> f64 {
+        self.share2 - self.share1
+    }
+}
+
+/// A component whose level distribution moved by at least the drift threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelDrift {
+    pub component: String,
+    pub total1: usize,
+    pub total2: usize,
+    pub levels: Vec<LevelShare>,
+}
+
+impl LevelDrift {
+    /// Level with the largest absolute share shift
+    pub fn largest_shift(&self) -> Option<&LevelShare> {
+        self.levels
+            .iter()
+            .max_by(|a, b| a.shift().abs().total_cmp(&b.shift().abs()))
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "component": self.component,
+            "total1": self.total1,
+            "total2": self.total2,
+            "max_shift_pp": self.largest_shift().map(LevelShare::shift),
+            "levels": self
+                .levels
+                .iter()
+                .map(|share| json!({
+                    "level": share.level,
+                    "count1": share.count1,
+                    "count2": share.count2,
+                    "share1": share.share1,
+                    "share2": share.share2,
+                    "shift_pp": share.shift(),
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Accumulates per-component level counts from grouped comparison keys
+pub fn level_counts_from_keys<'a>(
+    groups: impl IntoIterator<Item = (&'a str, usize)>,
+) -> LevelCounts {
+    let mut counts = LevelCounts::new();
+    for (key, count) in groups {
+        let (component, level, _) = split_key_parts(key);
+        *counts
+            .entry(component.to_string())
+            .or_default()
+            .entry(level.to_string())
+            .or_insert(0) += count;
+    }
+    counts
+}
+
+/// Flags components present in both files whose share of any level moved by at least
+/// `threshold_pp` percentage points. A threshold of 0 disables the check.
+pub fn detect_level_drift(
+    counts1: &LevelCounts,
+    counts2: &LevelCounts,
+    threshold_pp: f64,
+) -> Vec<LevelDrift> {
+    if threshold_pp <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut drifts: Vec<LevelDrift> = counts1
+        .iter()
+        .filter_map(|(component, levels1)| {
+            let levels2 = counts2.get(component)?;
+            let total1: usize = levels1.values().sum();
+            let total2: usize = levels2.values().sum();
+            if total1 < MIN_DRIFT_ENTRIES || total2 < MIN_DRIFT_ENTRIES {
+                return None;
+            }
+
+            let names: BTreeSet<&String> = levels1.keys().chain(levels2.keys()).collect();
+            let levels: Vec<LevelShare> = names
+                .into_iter()
+                .map(|level| {
+                    let count1 = levels1.get(level).copied().unwrap_or(0);
+                    let count2 = levels2.get(level).copied().unwrap_or(0);
+                    LevelShare {
+                        level: level.clone(),
+                        count1,
+                        count2,
+                        share1: count1 as f64 / total1 as f64 * 100.0,
+                        share2: count2 as f64 / total2 as f64 * 100.0,
+                    }
+                })
+                .collect();
+
+            levels
+                .iter()
+                .any(|share| share.shift().abs() >= threshold_pp)
+                .then(|| LevelDrift {
+                    component: component.clone(),
+                    total1,
+                    total2,
+                    levels,
+                })
+        })
+        .collect();
+
+    drifts.sort_by(|a, b| {
+        let shift = |drift: &LevelDrift| drift.largest_shift().map_or(0.0, |s| s.shift().abs());
+        shift(b)
+            .total_cmp(&shift(a))
+            .then_with(|| a.component.cmp(&b.component))
+    });
+    drifts
+}
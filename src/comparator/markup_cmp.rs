@@ -0,0 +1,312 @@
+use crate::ComparisonOptions;
+use crate::comparator::ComparisonResults;
+use crate::comparator::format_cmp::{OutputFormatter, format_comparison_results};
+use crate::show_diff::{HTML_STYLE, escape_html};
+use comfy_table::{Row, Table};
+use std::fmt::Write;
+use std::io;
+
+fn cells(row: &Row) -> Vec<String> {
+    row.cell_iter().map(|cell| cell.content()).collect()
+}
+
+/// Markdown output: headings for sections, fenced blocks for runs of plain lines
+#[derive(Default)]
+struct MarkdownFormatter {
+    out: String,
+    /// Lines of the open fenced block, held back until the fence length is known
+    block: Option<String>,
+}
+
+/// Backtick fence longer than any backtick run in `block`, so payload lines cannot close it
+fn fence_for(block: &str) -> String {
+    let longest_run = block
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(longest_run.max(2) + 1)
+}
+
+impl MarkdownFormatter {
+    fn close_block(&mut self) {
+        if let Some(block) = self.block.take() {
+            let fence = fence_for(&block);
+            let _ = write!(self.out, "{fence}text\n{block}{fence}\n\n");
+        }
+    }
+
+    fn block_line(&mut self, text: &str) {
+        if self.block.is_none() && text.trim().is_empty() {
+            return;
+        }
+        let _ = writeln!(self.block.get_or_insert_default(), "{text}");
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.close_block();
+        let _ = write!(self.out, "{text}\n\n");
+    }
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+impl OutputFormatter for MarkdownFormatter {
+    fn write_header(&mut self, text: &str) -> io::Result<()> {
+        self.paragraph(&format!("## {}", text.trim()));
+        Ok(())
+    }
+
+    fn write_divider(&mut self, _char: &str, _count: usize) -> io::Result<()> {
+        self.close_block();
+        Ok(())
+    }
+
+    fn write_line(&mut self, text: &str) -> io::Result<()> {
+        self.block_line(text);
+        Ok(())
+    }
+
+    fn write_source_file1(&mut self, text: &str) -> io::Result<()> {
+        self.block_line(&format!("[FILE1] {text}"));
+        Ok(())
+    }
+
+    fn write_source_file2(&mut self, text: &str) -> io::Result<()> {
+        self.block_line(&format!("[FILE2] {text}"));
+        Ok(())
+    }
+
+    fn write_highlight(&mut self, text: &str) -> io::Result<()> {
+        self.paragraph(&format!("**{text}**"));
+        Ok(())
+    }
+
+    fn write_label(&mut self, text: &str) -> io::Result<()> {
+        self.paragraph(&format!("### {}", text.trim()));
+        Ok(())
+    }
+
+    fn write_success(&mut self, text: &str) -> io::Result<()> {
+        self.paragraph(&format!("✅ {text}"));
+        Ok(())
+    }
+
+    fn write_warning(&mut self, text: &str) -> io::Result<()> {
+        self.paragraph(&format!("> ⚠️ {text}"));
+        Ok(())
+    }
+
+    fn write_error(&mut self, text: &str) -> io::Result<()> {
+        self.paragraph(&format!("> ❌ {text}"));
+        Ok(())
+    }
+
+    fn write_info(&mut self, text: &str) -> io::Result<()> {
+        self.paragraph(text);
+        Ok(())
+    }
+
+    fn write_table(&mut self, table: &Table) -> io::Result<()> {
+        self.close_block();
+        let header = table.header().map(cells).unwrap_or_default();
+        let row = |values: &[String]| {
+            let values: Vec<String> = values.iter().map(|value| markdown_cell(value)).collect();
+            format!("| {} |\n", values.join(" | "))
+        };
+        self.out.push_str(&row(&header));
+        self.out
+            .push_str(&row(&vec!["---".to_string(); header.len()]));
+        for values in table.row_iter().map(cells) {
+            self.out.push_str(&row(&values));
+        }
+        self.out.push('\n');
+        Ok(())
+    }
+}
+
+/// HTML output: the same sections as text output, lines grouped in `<pre>` blocks
+#[derive(Default)]
+struct HtmlFormatter {
+    out: String,
+    in_block: bool,
+}
+
+impl HtmlFormatter {
+    fn close_block(&mut self) {
+        if self.in_block {
+            self.out.push_str("</pre>\n");
+            self.in_block = false;
+        }
+    }
+
+    fn block_line(&mut self, text: &str, class: Option<&str>) {
+        if !self.in_block {
+            if text.trim().is_empty() {
+                return;
+            }
+            self.out.push_str("<pre>");
+            self.in_block = true;
+        }
+        match class {
+            Some(class) => {
+                let _ = writeln!(
+                    self.out,
+                    "<span class=\"{class}\">{}</span>",
+                    escape_html(text)
+                );
+            }
+            None => {
+                let _ = writeln!(self.out, "{}", escape_html(text));
+            }
+        }
+    }
+
+    fn element(&mut self, tag: &str, class: Option<&str>, text: &str) {
+        self.close_block();
+        let class = class
+            .map(|class| format!(" class=\"{class}\""))
+            .unwrap_or_default();
+        let _ = writeln!(self.out, "<{tag}{class}>{}</{tag}>", escape_html(text));
+    }
+}
+
+impl OutputFormatter for HtmlFormatter {
+    fn write_header(&mut self, text: &str) -> io::Result<()> {
+        self.element("h2", None, text);
+        Ok(())
+    }
+
+    fn write_divider(&mut self, _char: &str, _count: usize) -> io::Result<()> {
+        self.close_block();
+        Ok(())
+    }
+
+    fn write_line(&mut self, text: &str) -> io::Result<()> {
+        self.block_line(text, None);
+        Ok(())
+    }
+
+    fn write_source_file1(&mut self, text: &str) -> io::Result<()> {
+        self.block_line(text, Some("removed"));
+        Ok(())
+    }
+
+    fn write_source_file2(&mut self, text: &str) -> io::Result<()> {
+        self.block_line(text, Some("added"));
+        Ok(())
+    }
+
+    fn write_highlight(&mut self, text: &str) -> io::Result<()> {
+        self.element("p", Some("changed"), text);
+        Ok(())
+    }
+
+    fn write_label(&mut self, text: &str) -> io::Result<()> {
+        self.element("h3", None, text.trim());
+        Ok(())
+    }
+
+    fn write_success(&mut self, text: &str) -> io::Result<()> {
+        self.element("p", Some("added"), text);
+        Ok(())
+    }
+
+    fn write_warning(&mut self, text: &str) -> io::Result<()> {
+        self.element("p", Some("changed"), text);
+        Ok(())
+    }
+
+    fn write_error(&mut self, text: &str) -> io::Result<()> {
+        self.element("p", Some("removed"), text);
+        Ok(())
+    }
+
+    fn write_info(&mut self, text: &str) -> io::Result<()> {
+        self.element("p", None, text);
+        Ok(())
+    }
+
+    fn write_table(&mut self, table: &Table) -> io::Result<()> {
+        self.close_block();
+        self.out.push_str("<table>\n");
+        let row = |tag: &str, values: Vec<String>| {
+            let values: String = values
+                .iter()
+                .map(|value| format!("<{tag}>{}</{tag}>", escape_html(value)))
+                .collect();
+            format!("<tr>{values}</tr>\n")
+        };
+        if let Some(header) = table.header() {
+            self.out.push_str(&row("th", cells(header)));
+        }
+        for values in table.row_iter().map(cells) {
+            self.out.push_str(&row("td", values));
+        }
+        self.out.push_str("</table>\n");
+        Ok(())
+    }
+}
+
+/// Comparison results as a Markdown document titled `title`
+pub fn format_comparison_markdown(
+    results: &ComparisonResults,
+    options: &ComparisonOptions,
+    title: &str,
+    notes: &[String],
+) -> String {
+    let mut formatter = MarkdownFormatter::default();
+    formatter.paragraph(&format!("# {title}"));
+    for note in notes {
+        formatter.paragraph(note);
+    }
+    // Writing to a String cannot fail
+    let _ = format_comparison_results(&mut formatter, results, options);
+    formatter.close_block();
+    formatter.out
+}
+
+/// Comparison results as a self-contained HTML page titled `title`
+pub fn format_comparison_html(
+    results: &ComparisonResults,
+    options: &ComparisonOptions,
+    title: &str,
+    notes: &[String],
+) -> String {
+    let mut formatter = HtmlFormatter::default();
+    formatter.element("h1", None, title);
+    for note in notes {
+        formatter.element("p", Some("meta"), note);
+    }
+    let _ = format_comparison_results(&mut formatter, results, options);
+    formatter.close_block();
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{HTML_STYLE}pre{{font-family:ui-monospace,Menlo,monospace;font-size:13px;white-space:pre-wrap}}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        formatter.out
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fences_outlast_backticks_in_payload_lines() {
+        let mut formatter = MarkdownFormatter::default();
+        formatter.block_line("{\"note\":\"```sh\"}");
+        formatter.block_line("plain");
+        formatter.close_block();
+        assert_eq!(
+            formatter.out,
+            "````text\n{\"note\":\"```sh\"}\nplain\n````\n\n"
+        );
+
+        formatter.out.clear();
+        formatter.block_line("plain");
+        formatter.close_block();
+        assert_eq!(formatter.out, "```text\nplain\n```\n\n");
+    }
+}
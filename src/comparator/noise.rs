@@ -0,0 +1,214 @@
+use super::{ComparisonResults, rarity_path};
+use crate::ignore::IGNORE_FILE_NAME;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+/// A payload path that differed between known-equivalent log pairs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoisyPath {
+    /// Diff path with array selectors folded to `[]`
+    pub path: String,
+    /// Pairs in which the path differed at least once
+    pub pairs_differing: usize,
+    /// Share of pairs in which the path differed; 1.0 means it always differs
+    pub confidence: f64,
+    /// Differences at the path across all pairs
+    pub occurrences: usize,
+    /// Grouped keys the differences were found under
+    pub keys: Vec<String>,
+}
+
+/// Suppression candidates learned from comparing equivalent runs
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NoiseProfile {
+    pub pairs: usize,
+    /// Most confident first, then most frequent
+    pub paths: Vec<NoisyPath>,
+}
+
+impl NoiseProfile {
+    /// Paths that differed in every pair
+    pub fn always_differing(&self) -> impl Iterator<Item = &NoisyPath> {
+        self.paths
+            .iter()
+            .filter(|path| path.pairs_differing == self.pairs)
+    }
+}
+
+/// Collects the payload paths that differ across `pairs`, each the comparison of two
+/// logs that should be equivalent (e.g. reruns of the same build)
+pub fn learn_noise(pairs: &[ComparisonResults]) -> NoiseProfile {
+    #[derive(Default)]
+    struct Seen {
+        pairs: usize,
+        occurrences: usize,
+        keys: BTreeSet<String>,
+    }
+
+    let mut seen: BTreeMap<String, Seen> = BTreeMap::new();
+    for results in pairs {
+        let mut in_pair: BTreeSet<String> = BTreeSet::new();
+        for comparison in &results.shared_comparisons {
+            for diff in &comparison.json_differences {
+                let path = rarity_path(&diff.path);
+                let entry = seen.entry(path.clone()).or_default();
+                entry.occurrences += 1;
+                entry.keys.insert(comparison.key.clone());
+                if in_pair.insert(path) {
+                    entry.pairs += 1;
+                }
+            }
+        }
+    }
+
+    let mut paths: Vec<NoisyPath> = seen
+        .into_iter()
+        .map(|(path, seen)| NoisyPath {
+            path,
+            pairs_differing: seen.pairs,
+            confidence: seen.pairs as f64 / pairs.len().max(1) as f64,
+            occurrences: seen.occurrences,
+            keys: seen.keys.into_iter().collect(),
+        })
+        .collect();
+    paths.sort_by(|a, b| {
+        b.pairs_differing
+            .cmp(&a.pairs_differing)
+            .then_with(|| b.occurrences.cmp(&a.occurrences))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    NoiseProfile {
+        pairs: pairs.len(),
+        paths,
+    }
+}
+
+/// The profile as `.loganalyzerignore` rules: paths that differed in every pair are active,
+/// the rest are commented out for review
+pub fn format_noise_profile_text(profile: &NoiseProfile) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Suggested {IGNORE_FILE_NAME} rules learned from {} equivalent log pair(s)",
+        profile.pairs
+    );
+    let _ = writeln!(
+        out,
+        "# {} of {} differing paths changed in every pair; review commented rules before enabling",
+        profile.always_differing().count(),
+        profile.paths.len()
+    );
+    if profile.paths.is_empty() {
+        let _ = writeln!(out, "# No payload differences found.");
+    }
+    for noisy in &profile.paths {
+        let prefix = if noisy.pairs_differing == profile.pairs {
+            ""
+        } else {
+            "# "
+        };
+        let _ = writeln!(
+            out,
+            "\n# confidence {:.2} ({}/{} pairs, {} differences)\n{prefix}path: {}",
+            noisy.confidence, noisy.pairs_differing, profile.pairs, noisy.occurrences, noisy.path
+        );
+    }
+    out
+}
+
+pub fn format_noise_profile_json(profile: &NoiseProfile) -> String {
+    serde_json::to_string_pretty(&json!({ "noise_profile": profile })).unwrap_or_else(|_| {
+        "{\"noise_profile\":{\"error\":\"failed to serialize noise profile\"}}".into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comparator::{ChangeType, JsonDifference, LogComparison};
+    use chrono::Local;
+
+    fn pair(paths: &[&str]) -> ComparisonResults {
+        let now = Local::now();
+        ComparisonResults {
+            unique_to_log1: Vec::new(),
+            unique_to_log2: Vec::new(),
+            unique_sources1: BTreeMap::new(),
+            unique_sources2: BTreeMap::new(),
+            shared_comparisons: vec![LogComparison {
+                key: "core|INFO|Request|check".to_string(),
+                log1_index: 0,
+                log2_index: 0,
+                json_differences: paths
+                    .iter()
+                    .map(|path| JsonDifference {
+                        path: path.to_string(),
+                        value1: json!(1),
+                        value2: json!(2),
+                        change_type: ChangeType::Modified,
+                        schema_change: None,
+                    })
+                    .collect(),
+                text1: None,
+                text2: None,
+                log1_line_number: 1,
+                log2_line_number: 1,
+                log1_timestamp: now,
+                log2_timestamp: now,
+                log1_request_id: None,
+                log2_request_id: None,
+                log1_payload: None,
+                log2_payload: None,
+                cluster_sizes: None,
+                log1_raw: None,
+                log2_raw: None,
+            }],
+            level_drift: Vec::new(),
+            payload_errors: Vec::new(),
+            truncated_subtrees: 0,
+            identical_counts: BTreeMap::new(),
+            trivial_differences: 0,
+        }
+    }
+
+    #[test]
+    fn scores_paths_by_share_of_pairs_they_differ_in() {
+        let profile = learn_noise(&[
+            pair(&["renderId", "renders[0].ts", "renders[1].ts"]),
+            pair(&["renderId", "renders[3].ts"]),
+            pair(&["renderId", "settings.name"]),
+        ]);
+
+        let scores: Vec<(&str, usize, usize)> = profile
+            .paths
+            .iter()
+            .map(|noisy| {
+                (
+                    noisy.path.as_str(),
+                    noisy.pairs_differing,
+                    noisy.occurrences,
+                )
+            })
+            .collect();
+        assert_eq!(
+            scores,
+            [
+                ("renderId", 3, 3),
+                ("renders[].ts", 2, 3),
+                ("settings.name", 1, 1)
+            ]
+        );
+        assert_eq!(profile.always_differing().count(), 1);
+
+        let text = format_noise_profile_text(&profile);
+        assert!(text.contains("# confidence 1.00 (3/3 pairs, 3 differences)\npath: renderId\n"));
+        assert!(
+            text.contains("# confidence 0.67 (2/3 pairs, 3 differences)\n# path: renders[].ts\n")
+        );
+        let rules =
+            crate::ignore::IgnoreRules::parse(&text).expect("profile is a valid ignore file");
+        assert_eq!(rules.paths, ["renderId"]);
+    }
+}
@@ -0,0 +1,112 @@
+use super::ComparisonResults;
+use regex::Regex;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static ARRAY_SELECTOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[[^\]]*\]").expect("valid array selector regex"));
+
+/// A payload difference ranked by how rarely its path differs across the comparison
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredDifference {
+    pub key: String,
+    pub path: String,
+    pub value1: Value,
+    pub value2: Value,
+    pub log1_line_number: usize,
+    pub log2_line_number: usize,
+    /// Differences across the whole comparison at the same path (array selectors folded)
+    pub path_frequency: usize,
+    /// `ln(total differences / path_frequency)`; higher is rarer
+    pub rarity: f64,
+}
+
+impl ScoredDifference {
+    pub fn to_json(&self, compact: bool) -> Value {
+        if compact {
+            json!({
+                "k": self.key,
+                "p": self.path,
+                "v1": self.value1,
+                "v2": self.value2,
+                "ln": [self.log1_line_number, self.log2_line_number],
+                "pf": self.path_frequency,
+                "r": self.rarity,
+            })
+        } else {
+            json!({
+                "key": self.key,
+                "path": self.path,
+                "value1": self.value1,
+                "value2": self.value2,
+                "log1_line": self.log1_line_number,
+                "log2_line": self.log2_line_number,
+                "path_frequency": self.path_frequency,
+                "rarity": self.rarity,
+            })
+        }
+    }
+}
+
+/// Path with array indices and identity selectors folded, so `renders[2].status` and
+/// `renders[renderId=r1].status` count as the same path
+pub fn rarity_path(path: &str) -> String {
+    ARRAY_SELECTOR_RE.replace_all(path, "[]").into_owned()
+}
+
+/// Up to `limit` differences whose paths differ least often across the comparison.
+/// Empty when every path differs equally often, since nothing then stands out.
+pub fn most_interesting_differences(
+    results: &ComparisonResults,
+    limit: usize,
+) -> Vec<ScoredDifference> {
+    if limit == 0 {
+        return Vec::new();
+    }
+
+    let mut frequency: HashMap<String, usize> = HashMap::new();
+    for comparison in &results.shared_comparisons {
+        for diff in &comparison.json_differences {
+            *frequency.entry(rarity_path(&diff.path)).or_default() += 1;
+        }
+    }
+    let total: usize = frequency.values().sum();
+    let (Some(&rarest), Some(&commonest)) = (frequency.values().min(), frequency.values().max())
+    else {
+        return Vec::new();
+    };
+    if rarest == commonest {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<ScoredDifference> = results
+        .shared_comparisons
+        .iter()
+        .flat_map(|comparison| {
+            comparison.json_differences.iter().map(|diff| {
+                let path_frequency = frequency[&rarity_path(&diff.path)];
+                ScoredDifference {
+                    key: comparison.key.clone(),
+                    path: diff.path.clone(),
+                    value1: diff.value1.clone(),
+                    value2: diff.value2.clone(),
+                    log1_line_number: comparison.log1_line_number,
+                    log2_line_number: comparison.log2_line_number,
+                    path_frequency,
+                    rarity: (total as f64 / path_frequency as f64).ln(),
+                }
+            })
+        })
+        .filter(|scored| scored.path_frequency < commonest)
+        .collect();
+    scored.sort_by(|a, b| {
+        a.path_frequency
+            .cmp(&b.path_frequency)
+            .then_with(|| a.key.cmp(&b.key))
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.log1_line_number.cmp(&b.log1_line_number))
+    });
+    scored.truncate(limit);
+    scored
+}
@@ -0,0 +1,146 @@
+//! Comparison results saved by `compare --save` and re-rendered by `render`, so a
+//! comparison can be reviewed where the original logs are not available
+
+use crate::cli::SortOrder;
+use crate::comparator::{ComparisonOptions, ComparisonResults, LogSplit};
+use crate::output::write_output;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Value of the `format` field that identifies a saved comparison
+pub const SAVED_COMPARISON_FORMAT: &str = "log-analyzer-comparison";
+/// Bumped whenever the saved layout changes in a way older readers cannot follow
+pub const SAVED_COMPARISON_VERSION: u64 = 1;
+
+/// A saved comparison: the results plus what is needed to render them as `compare` did
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedComparison {
+    pub format: String,
+    pub version: u64,
+    pub file1: String,
+    pub file2: String,
+    /// How FILE1 was cut when it was compared with itself (`--split-at`)
+    pub split: Option<SavedSplit>,
+    pub settings: SavedSettings,
+    pub results: ComparisonResults,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedSplit {
+    pub description: String,
+    pub details: Value,
+}
+
+/// The comparison options that shaped the results; options that only change how they
+/// are printed (colours, verbosity, compact JSON) are taken from the `render` call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSettings {
+    pub diff_only: bool,
+    pub show_full_json: bool,
+    pub sort_order: SortOrder,
+    pub interesting_limit: usize,
+    pub level_drift_threshold: f64,
+    pub max_json_depth: Option<usize>,
+    pub ignore_case: bool,
+    pub ignore_whitespace: bool,
+}
+
+impl SavedSettings {
+    fn of(options: &ComparisonOptions) -> Self {
+        Self {
+            diff_only: options.diff_only,
+            show_full_json: options.show_full_json,
+            sort_order: options.sort_order,
+            interesting_limit: options.interesting_limit,
+            level_drift_threshold: options.level_drift_threshold,
+            max_json_depth: options.max_json_depth,
+            ignore_case: options.ignore_case,
+            ignore_whitespace: options.ignore_whitespace,
+        }
+    }
+
+    /// `options` with the saved settings applied
+    pub fn apply(&self, options: ComparisonOptions) -> ComparisonOptions {
+        options
+            .diff_only(self.diff_only)
+            .show_full_json(self.show_full_json)
+            .sort_by(self.sort_order)
+            .interesting_limit(self.interesting_limit)
+            .level_drift_threshold(self.level_drift_threshold)
+            .max_json_depth(self.max_json_depth)
+            .ignore_case(self.ignore_case)
+            .ignore_whitespace(self.ignore_whitespace)
+    }
+}
+
+/// Writes `results` to `path` as versioned JSON
+pub fn save_comparison(
+    path: &Path,
+    files: [&Path; 2],
+    split: Option<&LogSplit>,
+    results: &ComparisonResults,
+    options: &ComparisonOptions,
+) -> Result<(), Box<dyn Error>> {
+    let saved = serde_json::json!({
+        "format": SAVED_COMPARISON_FORMAT,
+        "version": SAVED_COMPARISON_VERSION,
+        "file1": files[0].display().to_string(),
+        "file2": files[1].display().to_string(),
+        "split": split.map(|split| SavedSplit {
+            description: split.describe(),
+            details: split.to_json(),
+        }),
+        "settings": SavedSettings::of(options),
+        "results": results,
+    });
+    write_output(path, serde_json::to_string_pretty(&saved)?.as_bytes()).map_err(|e| {
+        format!(
+            "Failed to write saved comparison '{}': {}",
+            path.display(),
+            e
+        )
+    })?;
+    Ok(())
+}
+
+/// Reads a comparison written by [`save_comparison`], refusing other files and newer versions
+pub fn load_comparison(path: &Path) -> Result<SavedComparison, Box<dyn Error>> {
+    let bytes = fs::read(path).map_err(|e| {
+        format!(
+            "Failed to read saved comparison '{}': {}",
+            path.display(),
+            e
+        )
+    })?;
+    let document: Value = serde_json::from_slice(&bytes)
+        .map_err(|e| format!("'{}' is not a saved comparison: {}", path.display(), e))?;
+    if document.get("format").and_then(Value::as_str) != Some(SAVED_COMPARISON_FORMAT) {
+        return Err(format!(
+            "'{}' is not a saved comparison (write one with compare --save)",
+            path.display()
+        )
+        .into());
+    }
+    let version = document
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or_default();
+    if version > SAVED_COMPARISON_VERSION {
+        return Err(format!(
+            "'{}' was saved in format version {version}, newer than the supported version {SAVED_COMPARISON_VERSION}; upgrade log-analyzer to render it",
+            path.display()
+        )
+        .into());
+    }
+    serde_json::from_value(document).map_err(|e| {
+        format!(
+            "Failed to read saved comparison '{}': {}",
+            path.display(),
+            e
+        )
+        .into()
+    })
+}
@@ -0,0 +1,421 @@
+use super::{
+    ComparisonError, ComparisonOptions, ComparisonResults, GroupedEntry, LogFilter, SourceEntry,
+    compare_key_group, detect_level_drift, get_log_key, level_counts_from_keys, sort_shared_keys,
+};
+use crate::cli::SortOrder;
+use crate::parser::LogEntry;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Lines, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Rough in-memory size of parsed entries relative to the raw file size
+/// (owned strings, raw line copy, parsed JSON payload, map overhead).
+pub const PARSED_BYTES_PER_FILE_BYTE: u64 = 4;
+
+/// Smallest sorted run worth writing, so a tiny budget does not end up with one
+/// temporary file per entry
+const MIN_SPILL_CHUNK_BYTES: u64 = 1024 * 1024;
+
+/// Whether comparing the given files in memory is expected to exceed `max_memory` bytes
+pub fn exceeds_memory_budget(files: &[&Path], max_memory: u64) -> bool {
+    let total: u64 = files
+        .iter()
+        .filter_map(|file| fs::metadata(file).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    total.saturating_mul(PARSED_BYTES_PER_FILE_BYTE) > max_memory
+}
+
+/// Estimated bytes of parsed entries to buffer per sorted run when spilling under
+/// `max_memory`: half the budget, leaving the rest to merging and comparing
+pub fn spill_chunk_bytes(max_memory: u64) -> u64 {
+    (max_memory / 2).max(MIN_SPILL_CHUNK_BYTES)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SpilledRecord {
+    key: String,
+    line_number: usize,
+    timestamp: DateTime<Local>,
+    request_id: Option<String>,
+    message: String,
+    raw_logline: String,
+    payload: Option<Value>,
+}
+
+impl SpilledRecord {
+    fn from_log(log: LogEntry) -> Self {
+        Self {
+            key: get_log_key(&log),
+            line_number: log.source_line_number,
+            timestamp: log.timestamp,
+            request_id: log.request_id().map(str::to_string),
+            payload: log.payload().cloned(),
+            message: log.message,
+            raw_logline: log.raw_logline,
+        }
+    }
+
+    /// Estimated size of the parsed entry this record was made from
+    fn parsed_bytes(&self) -> u64 {
+        (self.key.len() + self.raw_logline.len()) as u64 * PARSED_BYTES_PER_FILE_BYTE
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KeyMeta {
+    count: usize,
+    first_timestamp: DateTime<Local>,
+}
+
+/// Grouped log entries written to a temporary JSON-lines file, sorted by comparison key.
+/// Only the per-key index stays in memory; the file is deleted once it is dropped.
+#[derive(Debug)]
+pub struct SpilledGroups {
+    file: File,
+    keys: BTreeMap<String, KeyMeta>,
+}
+
+impl SpilledGroups {
+    pub fn entry_count(&self) -> usize {
+        self.keys.values().map(|meta| meta.count).sum()
+    }
+
+    fn level_counts(&self) -> super::LevelCounts {
+        level_counts_from_keys(
+            self.keys
+                .iter()
+                .map(|(key, meta)| (key.as_str(), meta.count)),
+        )
+    }
+
+    fn reader(&self) -> Result<Lines<BufReader<File>>, ComparisonError> {
+        Ok(rewound(self.file.try_clone()?)?.lines())
+    }
+}
+
+/// `file` read from its start
+fn rewound(mut file: File) -> std::io::Result<BufReader<File>> {
+    file.seek(SeekFrom::Start(0))?;
+    Ok(BufReader::new(file))
+}
+
+/// Groups entries by comparison key on disk as they are pushed, as an external sort:
+/// entries are buffered up to `chunk_bytes`, each full buffer is sorted by key and
+/// written to a temporary run, and [`GroupSpiller::finish`] merges the runs. Entries
+/// of one key keep the order they were pushed in.
+pub struct GroupSpiller<'f> {
+    filter: &'f LogFilter,
+    chunk_bytes: u64,
+    buffer: Vec<SpilledRecord>,
+    buffered_bytes: u64,
+    runs: Vec<File>,
+}
+
+impl<'f> GroupSpiller<'f> {
+    pub fn new(filter: &'f LogFilter, chunk_bytes: u64) -> Self {
+        Self {
+            filter,
+            chunk_bytes,
+            buffer: Vec::new(),
+            buffered_bytes: 0,
+            runs: Vec::new(),
+        }
+    }
+
+    /// Adds `log` when the filter selects it, writing out a run once the buffer is full
+    pub fn push(&mut self, log: LogEntry) -> Result<(), ComparisonError> {
+        if !self.filter.matches(&log) {
+            return Ok(());
+        }
+        let record = SpilledRecord::from_log(log);
+        self.buffered_bytes += record.parsed_bytes();
+        self.buffer.push(record);
+        if self.buffered_bytes >= self.chunk_bytes {
+            self.write_run()?;
+        }
+        Ok(())
+    }
+
+    fn write_run(&mut self) -> Result<(), ComparisonError> {
+        // Stable, so entries of one key stay in file order
+        self.buffer.sort_by(|a, b| a.key.cmp(&b.key));
+        let mut writer = BufWriter::new(tempfile::tempfile()?);
+        for record in self.buffer.drain(..) {
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+        self.runs
+            .push(writer.into_inner().map_err(|err| err.into_error())?);
+        self.buffered_bytes = 0;
+        Ok(())
+    }
+
+    /// Merges the sorted runs into one file sorted by key, indexing the keys
+    pub fn finish(mut self) -> Result<SpilledGroups, ComparisonError> {
+        if !self.buffer.is_empty() {
+            self.write_run()?;
+        }
+
+        let mut runs = self
+            .runs
+            .into_iter()
+            .map(|run| Ok(rewound(run)?.lines()))
+            .collect::<Result<Vec<_>, ComparisonError>>()?;
+        let mut heads: Vec<Option<SpilledRecord>> = Vec::with_capacity(runs.len());
+        // Ties between runs go to the earlier run, which holds the earlier entries
+        let mut queue = BinaryHeap::new();
+        for (run, lines) in runs.iter_mut().enumerate() {
+            let head = next_record(lines)?;
+            if let Some(record) = &head {
+                queue.push(Reverse((record.key.clone(), run)));
+            }
+            heads.push(head);
+        }
+
+        let mut spilled = SpilledGroups {
+            file: tempfile::tempfile()?,
+            keys: BTreeMap::new(),
+        };
+        let mut writer = BufWriter::new(spilled.file.try_clone()?);
+        while let Some(Reverse((key, run))) = queue.pop() {
+            let record = heads[run].take().expect("queued runs have a head record");
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+            // The merge keeps file order within a key, so the first record is the key's
+            // first entry, matching the in-memory `--sort-by time` order
+            spilled
+                .keys
+                .entry(key)
+                .and_modify(|meta| meta.count += 1)
+                .or_insert(KeyMeta {
+                    count: 1,
+                    first_timestamp: record.timestamp,
+                });
+
+            heads[run] = next_record(&mut runs[run])?;
+            if let Some(next) = &heads[run] {
+                queue.push(Reverse((next.key.clone(), run)));
+            }
+        }
+        writer.flush()?;
+
+        Ok(spilled)
+    }
+}
+
+fn next_record(
+    lines: &mut Lines<BufReader<File>>,
+) -> Result<Option<SpilledRecord>, ComparisonError> {
+    match lines.next() {
+        Some(line) => Ok(Some(serde_json::from_str(&line?)?)),
+        None => Ok(None),
+    }
+}
+
+/// Groups `logs` by comparison key and spills them to a temporary sorted file, writing
+/// sorted runs of at most `chunk_bytes` of estimated parsed entries along the way
+pub fn spill_log_groups(
+    logs: impl IntoIterator<Item = LogEntry>,
+    filter: &LogFilter,
+    chunk_bytes: u64,
+) -> Result<SpilledGroups, ComparisonError> {
+    let mut spiller = GroupSpiller::new(filter, chunk_bytes);
+    for log in logs {
+        spiller.push(log)?;
+    }
+    spiller.finish()
+}
+
+fn read_group(
+    lines: &mut Lines<BufReader<File>>,
+    count: usize,
+) -> Result<Vec<SpilledRecord>, ComparisonError> {
+    let mut records = Vec::with_capacity(count);
+    for _ in 0..count {
+        let line = lines.next().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "spill file ended before all grouped entries were read",
+            )
+        })??;
+        records.push(serde_json::from_str(&line)?);
+    }
+    Ok(records)
+}
+
+fn grouped_view(records: &[SpilledRecord]) -> Vec<GroupedEntry<'_>> {
+    records
+        .iter()
+        .map(|record| GroupedEntry {
+            line_number: record.line_number,
+            timestamp: record.timestamp,
+            request_id: record.request_id.as_deref(),
+            message: &record.message,
+            raw_logline: &record.raw_logline,
+            payload: record.payload.as_ref(),
+        })
+        .collect()
+}
+
+/// Records the source entries of a key found in only one file
+fn note_unique_sources(
+    key: &str,
+    records: &[SpilledRecord],
+    options: &ComparisonOptions,
+    sources: &mut BTreeMap<String, Vec<SourceEntry>>,
+) {
+    if options.selects_key(key) {
+        let entries = grouped_view(records)
+            .iter()
+            .map(|entry| entry.source(options))
+            .collect();
+        sources.insert(key.to_string(), entries);
+    }
+}
+
+/// Merge-based comparison of two spilled group files. Both files are read
+/// sequentially in key order, so only one key group per side is held in memory.
+pub fn compare_spilled_groups(
+    groups1: &SpilledGroups,
+    groups2: &SpilledGroups,
+    options: &ComparisonOptions,
+) -> Result<ComparisonResults, ComparisonError> {
+    let mut results = ComparisonResults {
+        unique_to_log1: groups1
+            .keys
+            .keys()
+            .filter(|key| !groups2.keys.contains_key(*key) && options.selects_key(key))
+            .cloned()
+            .collect(),
+        unique_to_log2: groups2
+            .keys
+            .keys()
+            .filter(|key| !groups1.keys.contains_key(*key) && options.selects_key(key))
+            .cloned()
+            .collect(),
+        unique_sources1: BTreeMap::new(),
+        unique_sources2: BTreeMap::new(),
+        shared_comparisons: Vec::new(),
+        level_drift: detect_level_drift(
+            &groups1.level_counts(),
+            &groups2.level_counts(),
+            options.level_drift_threshold,
+        ),
+        payload_errors: Vec::new(),
+        truncated_subtrees: 0,
+        identical_counts: BTreeMap::new(),
+        trivial_differences: 0,
+    };
+
+    let mut reader1 = groups1.reader()?;
+    let mut reader2 = groups2.reader()?;
+    let mut iter1 = groups1.keys.iter().peekable();
+    let mut iter2 = groups2.keys.iter().peekable();
+    let mut per_key: BTreeMap<String, ComparisonResults> = BTreeMap::new();
+
+    // Both key indexes are sorted, matching the record order in the spill files.
+    while let (Some((key1, meta1)), Some((key2, meta2))) = (iter1.peek(), iter2.peek()) {
+        match key1.cmp(key2) {
+            std::cmp::Ordering::Less => {
+                let records = read_group(&mut reader1, meta1.count)?;
+                note_unique_sources(key1, &records, options, &mut results.unique_sources1);
+                iter1.next();
+            }
+            std::cmp::Ordering::Greater => {
+                let records = read_group(&mut reader2, meta2.count)?;
+                note_unique_sources(key2, &records, options, &mut results.unique_sources2);
+                iter2.next();
+            }
+            std::cmp::Ordering::Equal if !options.selects_key(key1) => {
+                read_group(&mut reader1, meta1.count)?;
+                read_group(&mut reader2, meta2.count)?;
+                iter1.next();
+                iter2.next();
+            }
+            std::cmp::Ordering::Equal => {
+                let records1 = read_group(&mut reader1, meta1.count)?;
+                let records2 = read_group(&mut reader2, meta2.count)?;
+                let mut key_results = ComparisonResults {
+                    unique_to_log1: Vec::new(),
+                    unique_to_log2: Vec::new(),
+                    unique_sources1: BTreeMap::new(),
+                    unique_sources2: BTreeMap::new(),
+                    shared_comparisons: Vec::new(),
+                    level_drift: Vec::new(),
+                    payload_errors: Vec::new(),
+                    truncated_subtrees: 0,
+                    identical_counts: BTreeMap::new(),
+                    trivial_differences: 0,
+                };
+                compare_key_group(
+                    key1,
+                    &grouped_view(&records1),
+                    &grouped_view(&records2),
+                    options,
+                    &mut key_results,
+                );
+                per_key.insert((*key1).clone(), key_results);
+                iter1.next();
+                iter2.next();
+            }
+        }
+    }
+    // Keys left on one side once the other runs out are unique as well
+    for (key, meta) in iter1 {
+        let records = read_group(&mut reader1, meta.count)?;
+        note_unique_sources(key, &records, options, &mut results.unique_sources1);
+    }
+    for (key, meta) in iter2 {
+        let records = read_group(&mut reader2, meta.count)?;
+        note_unique_sources(key, &records, options, &mut results.unique_sources2);
+    }
+
+    let mut keys: Vec<String> = per_key.keys().cloned().collect();
+    sort_shared_keys(&mut keys, options.sort_order, |key| {
+        let ts1 = groups1.keys.get(key).map(|meta| meta.first_timestamp);
+        let ts2 = groups2.keys.get(key).map(|meta| meta.first_timestamp);
+        match (ts1, ts2) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    });
+
+    for key in keys {
+        if let Some(mut key_results) = per_key.remove(&key) {
+            results.truncated_subtrees += key_results.truncated_subtrees;
+            results.trivial_differences += key_results.trivial_differences;
+            results
+                .identical_counts
+                .append(&mut key_results.identical_counts);
+            results
+                .shared_comparisons
+                .append(&mut key_results.shared_comparisons);
+            results
+                .unique_to_log1
+                .append(&mut key_results.unique_to_log1);
+            results
+                .unique_to_log2
+                .append(&mut key_results.unique_to_log2);
+            results
+                .unique_sources1
+                .append(&mut key_results.unique_sources1);
+            results
+                .unique_sources2
+                .append(&mut key_results.unique_sources2);
+        }
+    }
+
+    if options.sort_order == SortOrder::DiffCount {
+        results
+            .shared_comparisons
+            .sort_by_key(|comparison| std::cmp::Reverse(comparison.json_differences.len()));
+    }
+
+    Ok(results)
+}
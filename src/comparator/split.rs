@@ -0,0 +1,172 @@
+use crate::diagnostics::WarningLog;
+use crate::filter::{FilterExpression, to_log_filter};
+use crate::parser::LogEntry;
+use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat, TimeZone};
+use serde_json::{Value, json};
+
+/// Where `compare --split-at` cuts a single log in two
+#[derive(Debug, Clone, PartialEq)]
+pub enum SplitPoint {
+    /// Entries stamped at or after this time form the second half
+    At(DateTime<Local>),
+    /// The first entry matching this filter expression starts the second half
+    Marker(String),
+}
+
+impl SplitPoint {
+    /// Parses an RFC3339 timestamp, a local `YYYY-MM-DDTHH:MM:SS[.fff]` time, or otherwise
+    /// a filter expression such as `"t:config reloaded"`
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+            return Ok(SplitPoint::At(at.with_timezone(&Local)));
+        }
+        for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(input, format)
+                && let Some(at) = Local.from_local_datetime(&naive).earliest()
+            {
+                return Ok(SplitPoint::At(at));
+            }
+        }
+        FilterExpression::parse(input)
+            .map(|_| SplitPoint::Marker(input.to_string()))
+            .map_err(|e| {
+                format!(
+                    "invalid split point '{input}' (expected a timestamp or filter expression): {e}"
+                )
+            })
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            SplitPoint::At(at) => at.to_rfc3339_opts(SecondsFormat::Millis, true),
+            SplitPoint::Marker(expression) => format!("first entry matching \"{expression}\""),
+        }
+    }
+}
+
+/// Where a log was cut and how many entries fell on each side
+#[derive(Debug, Clone)]
+pub struct LogSplit {
+    pub point: SplitPoint,
+    /// Split time, or the timestamp of the marker entry
+    pub boundary: DateTime<Local>,
+    /// 1-based source line of the marker entry
+    pub marker_line: Option<usize>,
+    pub before_entries: usize,
+    pub after_entries: usize,
+}
+
+impl LogSplit {
+    /// One-line description for text output
+    pub fn describe(&self) -> String {
+        let marker = self
+            .marker_line
+            .map(|line| format!(", line {line}"))
+            .unwrap_or_default();
+        format!(
+            "Split at {} ({}{marker}): {} entries before, {} after",
+            self.point.describe(),
+            self.boundary.to_rfc3339_opts(SecondsFormat::Millis, true),
+            self.before_entries,
+            self.after_entries
+        )
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "at": self.point.describe(),
+            "boundary": self.boundary.to_rfc3339_opts(SecondsFormat::Millis, true),
+            "marker_line": self.marker_line,
+            "before_entries": self.before_entries,
+            "after_entries": self.after_entries,
+        })
+    }
+}
+
+/// Cuts `logs` (in stream order) at `point` into the entries before and from it on.
+/// Fails when the marker never matches or either half would be empty, since there
+/// would be nothing to compare.
+pub fn split_logs(
+    logs: Vec<LogEntry>,
+    point: &SplitPoint,
+) -> Result<(Vec<LogEntry>, Vec<LogEntry>, LogSplit), String> {
+    let (before, after, boundary, marker_line) = match point {
+        SplitPoint::At(at) => {
+            let (before, after): (Vec<_>, Vec<_>) =
+                logs.into_iter().partition(|entry| entry.timestamp < *at);
+            (before, after, *at, None)
+        }
+        SplitPoint::Marker(expression) => {
+            let expr = FilterExpression::parse(expression).map_err(|e| e.to_string())?;
+            let mut filter = to_log_filter(&expr);
+            if filter.needs_resolution() {
+                // A marker that matches nothing is an error below, not a warning
+                filter = filter.resolve_windows(&logs, &WarningLog::default());
+            }
+            let index = logs
+                .iter()
+                .position(|entry| filter.matches(entry))
+                .ok_or_else(|| format!("--split-at: no entry matches \"{expression}\""))?;
+            let boundary = logs[index].timestamp;
+            let marker_line = logs[index].source_line_number;
+            let mut before = logs;
+            let after = before.split_off(index);
+            (before, after, boundary, Some(marker_line))
+        }
+    };
+    if before.is_empty() || after.is_empty() {
+        return Err(format!(
+            "--split-at {} leaves no entries {} it",
+            point.describe(),
+            if before.is_empty() { "before" } else { "after" }
+        ));
+    }
+    let split = LogSplit {
+        point: point.clone(),
+        boundary,
+        marker_line,
+        before_entries: before.len(),
+        after_entries: after.len(),
+    };
+    Ok((before, after, split))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs() -> Vec<LogEntry> {
+        [
+            "core | 2026-01-01T00:00:00.000Z [INFO ] started",
+            "core | 2026-01-01T00:05:00.000Z [INFO ] config reloaded",
+            "core | 2026-01-01T00:06:00.000Z [INFO ] working",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, line)| crate::parser::parse_log_entry(line, i + 1).expect("line parses"))
+        .collect()
+    }
+
+    #[test]
+    fn splits_at_timestamps_and_markers() {
+        let at = SplitPoint::parse("2026-01-01T00:05:00Z").expect("timestamp parses");
+        let (before, after, _) = split_logs(logs(), &at).expect("both halves have entries");
+        assert_eq!((before.len(), after.len()), (1, 2));
+
+        let marker = SplitPoint::parse("t:reloaded").expect("filter parses");
+        assert_eq!(marker, SplitPoint::Marker("t:reloaded".to_string()));
+        let (_, _, split) = split_logs(logs(), &marker).expect("marker matches");
+        assert_eq!((split.before_entries, split.marker_line), (1, Some(2)));
+        assert!(split.describe().contains("2 after"));
+
+        let late = SplitPoint::parse("2026-02-01T00:00:00Z").unwrap();
+        assert!(
+            split_logs(logs(), &late)
+                .unwrap_err()
+                .contains("no entries after")
+        );
+        let missing = SplitPoint::Marker("t:never".to_string());
+        assert!(split_logs(logs(), &missing).is_err());
+    }
+}
@@ -1,10 +1,12 @@
-use crate::parser::{LogEntry, LogEntryKind};
+use crate::filter::{FilterExpression, FilterType};
+use crate::parser::{LogEntry, LogEntryKind, ReadScope, RequestDirection, value_at_path};
+use crate::session_path::{SessionPath, segment_base, session_level_index};
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 use thiserror::Error;
 
@@ -38,6 +40,18 @@ pub enum ConfigError {
     },
     #[error("Unknown built-in preset '{name}'. Available built-ins: {available}")]
     UnknownBuiltin { name: String, available: String },
+    #[error("Invalid tag '{tag}' in config file '{path}': {message}")]
+    InvalidTag {
+        path: String,
+        tag: String,
+        message: String,
+    },
+    #[error("Invalid heartbeat rule '{name}' in config file '{path}': {message}")]
+    InvalidHeartbeat {
+        path: String,
+        name: String,
+        message: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +64,24 @@ pub struct AnalyzerConfig {
     pub profile: ProfileRules,
     #[serde(skip_serializing_if = "SessionsRules::is_empty")]
     pub sessions: SessionsRules,
+    #[serde(skip_serializing_if = "FixtureRules::is_empty")]
+    pub fixtures: FixtureRules,
+    #[serde(skip_serializing_if = "ErrorsRules::is_empty")]
+    pub errors: ErrorsRules,
+    #[serde(skip_serializing_if = "ComparisonRules::is_empty")]
+    pub comparison: ComparisonRules,
+    #[serde(skip_serializing_if = "SchemaRules::is_default")]
+    pub schema: SchemaRules,
+    #[serde(skip_serializing_if = "ContractRules::is_empty")]
+    pub contracts: ContractRules,
+    #[serde(skip_serializing_if = "RunRules::is_default")]
+    pub runs: RunRules,
+    #[serde(skip_serializing_if = "TagRules::is_empty")]
+    pub tags: TagRules,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub heartbeats: Vec<HeartbeatRule>,
+    #[serde(skip_serializing_if = "SerializationRules::is_default")]
+    pub serialization: SerializationRules,
 }
 
 impl Default for AnalyzerConfig {
@@ -60,6 +92,15 @@ impl Default for AnalyzerConfig {
             perf: PerfRules::default(),
             profile: ProfileRules::default(),
             sessions: SessionsRules::default(),
+            fixtures: FixtureRules::default(),
+            errors: ErrorsRules::default(),
+            comparison: ComparisonRules::default(),
+            schema: SchemaRules::default(),
+            contracts: ContractRules::default(),
+            runs: RunRules::default(),
+            tags: TagRules::default(),
+            heartbeats: Vec::new(),
+            serialization: SerializationRules::default(),
         }
     }
 }
@@ -75,6 +116,13 @@ impl AnalyzerConfig {
     pub fn effective_session_levels(&self) -> Vec<SessionLevelConfig> {
         self.sessions.levels.clone()
     }
+
+    pub fn has_session_display_names(&self) -> bool {
+        self.sessions
+            .levels
+            .iter()
+            .any(|level| level.display_name.is_some())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -107,6 +155,12 @@ pub struct ParserRules {
     pub json_indicators: Vec<String>,
     pub module_depth: usize,
     pub module_strip_prefix: String,
+    /// Skip embedded JSON payload extraction (runtime-only fast scan used by `count`)
+    #[serde(skip)]
+    pub skip_payloads: bool,
+    /// Slice of each file to read (runtime-only, from `--lines` / `--bytes`)
+    #[serde(skip)]
+    pub scope: ReadScope,
 }
 
 impl Default for ParserRules {
@@ -127,6 +181,8 @@ impl Default for ParserRules {
             json_indicators: Vec::new(),
             module_depth: 2,
             module_strip_prefix: String::new(),
+            skip_payloads: false,
+            scope: ReadScope::default(),
         }
     }
 }
@@ -137,6 +193,23 @@ pub struct PerfRules {
     pub command_start_markers: Vec<String>,
     pub command_completion_markers: Vec<String>,
     pub event_correlation_keys: Vec<String>,
+    /// p95 latency budget in milliseconds per operation name, used by the health summary
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub latency_budgets_ms: BTreeMap<String, u64>,
+    /// Ordered phases a check session's time is split into by `perf --phases`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<PhaseRule>,
+    /// Prefix of the session path segment that identifies a check for `perf --phases`;
+    /// operations nested deeper (renders, requests) count toward that check
+    pub check_segment_prefix: String,
+}
+
+/// A named phase and the operations (request, command, or event names) whose time belongs to it
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PhaseRule {
+    pub name: String,
+    pub operations: Vec<String>,
 }
 
 impl Default for PerfRules {
@@ -152,6 +225,9 @@ impl Default for PerfRules {
                 "id".to_string(),
                 "key".to_string(),
             ],
+            latency_budgets_ms: BTreeMap::new(),
+            phases: Vec::new(),
+            check_segment_prefix: "check-".to_string(),
         }
     }
 }
@@ -168,6 +244,49 @@ pub struct ProfileRules {
 #[serde(default)]
 pub struct SessionsRules {
     pub levels: Vec<SessionLevelConfig>,
+    /// How operations whose `component_id` lists several `" & "`-joined session paths are counted
+    #[serde(skip_serializing_if = "SessionAttribution::is_default")]
+    pub parallel_attribution: SessionAttribution,
+}
+
+/// Attribution of a fan-out entry whose `component_id` is `"path-a & path-b"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionAttribution {
+    /// Count the entry once for every distinct session path (default)
+    #[default]
+    All,
+    /// Count the entry only for the first listed session path
+    First,
+    /// Split one count evenly across the distinct session paths
+    Proportional,
+}
+
+impl SessionAttribution {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Distinct session paths of `component_id` with the weight each one receives
+    pub fn attribute(self, component_id: &str) -> Vec<(&str, f64)> {
+        let paths = split_parallel_session_paths(component_id);
+        match self {
+            Self::All => paths.into_iter().map(|path| (path, 1.0)).collect(),
+            Self::First => paths.into_iter().take(1).map(|path| (path, 1.0)).collect(),
+            Self::Proportional => {
+                let weight = 1.0 / paths.len().max(1) as f64;
+                paths.into_iter().map(|path| (path, weight)).collect()
+            }
+        }
+    }
+}
+
+/// Splits a `component_id` listing parallel sessions (`"a/b & a/c"`) into distinct paths
+pub fn split_parallel_session_paths(component_id: &str) -> Vec<&str> {
+    SessionPath::parse_parallel(component_id)
+        .iter()
+        .map(SessionPath::as_str)
+        .collect()
 }
 
 impl SessionsRules {
@@ -176,6 +295,335 @@ impl SessionsRules {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FixtureRules {
+    /// JSON field names whose values are replaced with `"[REDACTED]"` in captured fixtures
+    pub redact_fields: Vec<String>,
+}
+
+impl FixtureRules {
+    fn is_empty(&self) -> bool {
+        self.redact_fields.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ErrorsRules {
+    /// Template file for `errors --ticket`, relative to the profile (`{{title}}`, `{{count}}`, `{{filter}}`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticket_template: Option<PathBuf>,
+    /// Sliding-window error-rate objective evaluated by `errors --slo`
+    #[serde(skip_serializing_if = "SloRules::is_default")]
+    pub slo: SloRules,
+}
+
+impl ErrorsRules {
+    fn is_empty(&self) -> bool {
+        self.ticket_template.is_none() && self.slo.is_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SloRules {
+    /// Highest acceptable share (percent) of ERROR entries within one window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_error_rate_pct: Option<f64>,
+    pub window_ms: u64,
+    pub step_ms: u64,
+    /// Windows with fewer entries are not evaluated
+    pub min_entries: usize,
+}
+
+impl Default for SloRules {
+    fn default() -> Self {
+        Self {
+            max_error_rate_pct: None,
+            window_ms: 60_000,
+            step_ms: 5_000,
+            min_entries: 1,
+        }
+    }
+}
+
+impl SloRules {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ComparisonRules {
+    /// Array path (without indices, e.g. `renders` or `checks.renders`) → key field whose
+    /// value identifies an element, so payload diffs pair elements by identity
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub array_identity_keys: BTreeMap<String, String>,
+    /// Nesting depth below which payload objects/arrays are compared as opaque blobs
+    /// and not descended by `info --json-schema` (unset = unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_json_depth: Option<usize>,
+}
+
+impl ComparisonRules {
+    fn is_empty(&self) -> bool {
+        self.array_identity_keys.is_empty() && self.max_json_depth.is_none()
+    }
+
+    /// Identity keys with an optional trailing `[]` stripped from each path
+    pub fn identity_keys(&self) -> BTreeMap<String, String> {
+        self.array_identity_keys
+            .iter()
+            .map(|(path, key)| (path.trim_end_matches("[]").to_string(), key.clone()))
+            .collect()
+    }
+}
+
+/// Which representation of an entry's timestamp serialized entries carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimestampRepr {
+    /// RFC3339 in UTC with milliseconds
+    Rfc3339,
+    /// Milliseconds since the Unix epoch
+    EpochMillis,
+    #[default]
+    Both,
+}
+
+impl TimestampRepr {
+    pub fn rfc3339(self) -> bool {
+        self != Self::EpochMillis
+    }
+
+    pub fn epoch_millis(self) -> bool {
+        self != Self::Rfc3339
+    }
+}
+
+/// Typed fields of entries serialized by `process` and `search --format json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SerializationRules {
+    pub timestamps: TimestampRepr,
+    /// Attach the duration of the paired operation an entry starts or ends; off by
+    /// default, since it runs the `perf` pairing over the whole file
+    pub durations: bool,
+}
+
+impl Default for SerializationRules {
+    fn default() -> Self {
+        Self {
+            timestamps: TimestampRepr::Both,
+            durations: false,
+        }
+    }
+}
+
+impl SerializationRules {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How one log stream is split into separate runs (see `--run`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunRules {
+    /// Message substrings that mark the first entry of a new run
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub start_markers: Vec<String>,
+    /// Silence between consecutive entries that starts a new run (0 = off)
+    pub gap_ms: u64,
+}
+
+impl Default for RunRules {
+    fn default() -> Self {
+        Self {
+            start_markers: Vec::new(),
+            gap_ms: 1_800_000,
+        }
+    }
+}
+
+impl RunRules {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Payload fields that must be present, keyed by request or command name
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ContractRules {
+    /// Request name → dotted paths required in the payload of each sent request
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub requests: BTreeMap<String, Vec<String>>,
+    /// Command name → dotted paths required in the command settings
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, Vec<String>>,
+}
+
+impl ContractRules {
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty() && self.commands.is_empty()
+    }
+
+    /// Required paths for `entry`: sent requests and commands with a declared contract
+    pub fn required_fields(&self, entry: &LogEntry) -> Option<&[String]> {
+        match &entry.kind {
+            LogEntryKind::Request {
+                request,
+                direction: RequestDirection::Send,
+                ..
+            } => self.requests.get(request),
+            LogEntryKind::Command { command, .. } => self.commands.get(command),
+            _ => None,
+        }
+        .map(Vec::as_slice)
+    }
+
+    /// Required paths absent from `entry`'s payload (all of them when it has no payload)
+    pub fn missing_fields<'a>(&'a self, entry: &LogEntry) -> Vec<&'a str> {
+        self.required_fields(entry)
+            .unwrap_or_default()
+            .iter()
+            .filter(|path| entry.payload_value(path).is_none())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Named filter expressions; entries matching one carry its name as a tag
+/// (e.g. `network = "c:core-requests"`), usable as `-f tag:network`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(transparent)]
+pub struct TagRules {
+    pub rules: BTreeMap<String, String>,
+}
+
+impl TagRules {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// First rule that is not a plain filter expression, as `(tag, reason)`. Rules
+    /// cannot use `within:` windows or refer to other tags.
+    pub fn invalid_rule(&self) -> Option<(&str, String)> {
+        self.rules.iter().find_map(|(tag, expression)| {
+            let reason = match FilterExpression::parse(expression) {
+                Err(err) => err.to_string(),
+                Ok(expr) => {
+                    let nested = expr.terms.iter().find(|term| {
+                        matches!(term.filter_type, FilterType::Within | FilterType::Tag)
+                    });
+                    nested?;
+                    "tag rules cannot use within: or tag: terms".to_string()
+                }
+            };
+            Some((tag.as_str(), reason))
+        })
+    }
+}
+
+/// Recurring keepalive entries matching `filter` are collapsed to the first one of each
+/// `window_ms`, per distinct `key`, before any analysis sees them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeartbeatRule {
+    pub name: String,
+    /// Filter expression selecting the heartbeat entries
+    pub filter: String,
+    pub window_ms: u64,
+    /// Parts of an entry that tell heartbeats apart: `component`, `component_id`, `level`,
+    /// `message` (the normalized message pattern, numbers masked) or a structured field name
+    pub key: Vec<String>,
+}
+
+impl Default for HeartbeatRule {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            filter: String::new(),
+            window_ms: 60_000,
+            key: vec!["component".to_string(), "message".to_string()],
+        }
+    }
+}
+
+impl HeartbeatRule {
+    /// Why the rule cannot be applied, if it cannot. Rules match one entry at a time,
+    /// so they cannot use `within:` windows.
+    pub fn invalid_reason(&self) -> Option<String> {
+        if self.name.trim().is_empty() {
+            return Some("heartbeat rules need a name".to_string());
+        }
+        if self.window_ms == 0 {
+            return Some("window_ms must be greater than 0".to_string());
+        }
+        match FilterExpression::parse(&self.filter) {
+            Err(err) => Some(err.to_string()),
+            Ok(expr) if expr.is_empty() => Some("filter must not be empty".to_string()),
+            Ok(expr) => expr
+                .terms
+                .iter()
+                .any(|term| term.filter_type == FilterType::Within)
+                .then(|| "heartbeat filters cannot use within: terms".to_string()),
+        }
+    }
+}
+
+/// Example values attached to fields in `info --json-schema`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SchemaRules {
+    /// Most frequent values shown per field (0 = no examples)
+    pub examples_per_field: usize,
+    /// Characters kept from each example value
+    pub example_max_chars: usize,
+    /// Total bytes of example values printed across the whole schema report
+    pub example_byte_budget: usize,
+    /// Field names (case-insensitive) whose values are shown as `[MASKED]`, in addition to
+    /// names containing one of the sensitive words that `process` redacts
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mask_fields: Vec<String>,
+}
+
+impl Default for SchemaRules {
+    fn default() -> Self {
+        Self {
+            examples_per_field: 3,
+            example_max_chars: 40,
+            example_byte_budget: 4096,
+            mask_fields: Vec::new(),
+        }
+    }
+}
+
+impl SchemaRules {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether values of the field at `path` (e.g. `user.email`, `items[0].token`) are masked
+    pub fn masks(&self, path: &str) -> bool {
+        let field = path
+            .rsplit('.')
+            .next()
+            .unwrap_or(path)
+            .split('[')
+            .next()
+            .unwrap_or_default();
+        crate::llm_processor::is_sensitive_field(field)
+            || self
+                .mask_fields
+                .iter()
+                .any(|masked| masked.eq_ignore_ascii_case(field))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionLevelConfig {
     pub name: String,
@@ -186,6 +634,71 @@ pub struct SessionLevelConfig {
     pub complete_commands: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub summary_fields: Vec<String>,
+    /// Display-name template such as `"{scenario} [{browser}]"`; placeholders are
+    /// create-time settings paths, plus `{id}` for the raw path segment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+}
+
+impl SessionLevelConfig {
+    /// Settings paths referenced by the display-name template (excluding `{id}`)
+    pub fn display_name_fields(&self) -> Vec<String> {
+        self.display_name
+            .as_deref()
+            .map(template_placeholders)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|field| field != "id")
+            .collect()
+    }
+
+    /// Render the display-name template for a session, if a template is configured.
+    /// Returns `None` when none of the template fields could be resolved.
+    pub fn render_display_name(&self, session: &SessionInfo) -> Option<String> {
+        let template = self.display_name.as_deref()?;
+        let mut rendered = String::with_capacity(template.len());
+        let mut resolved_any = false;
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}').map(|offset| start + offset) else {
+                break;
+            };
+            rendered.push_str(&rest[..start]);
+            let field = rest[start + 1..end].trim();
+            if field == "id" {
+                rendered.push_str(&session.id);
+            } else if let Some(value) = session.summary_fields.get(field) {
+                resolved_any = true;
+                match value {
+                    Value::String(text) => rendered.push_str(text),
+                    other => rendered.push_str(&other.to_string()),
+                }
+            } else {
+                rendered.push('?');
+            }
+            rest = &rest[end + 1..];
+        }
+        rendered.push_str(rest);
+
+        resolved_any.then_some(rendered)
+    }
+}
+
+fn template_placeholders(template: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|offset| start + offset) else {
+            break;
+        };
+        let field = rest[start + 1..end].trim();
+        if !field.is_empty() && !fields.iter().any(|f| f == field) {
+            fields.push(field.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+    fields
 }
 
 #[derive(Debug, Clone, Default)]
@@ -218,6 +731,38 @@ impl SessionInsights {
         self.levels.iter().all(|level| level.sessions.is_empty())
     }
 
+    /// Display name for a single session path segment, when its level has a template
+    pub fn display_name(&self, segment: &str) -> Option<String> {
+        self.levels.iter().find_map(|level| {
+            level
+                .sessions
+                .get(segment)
+                .and_then(|session| level.config.render_display_name(session))
+        })
+    }
+
+    /// Render a `component_id` path with templated display names substituted for
+    /// known session segments. Returns the raw path when no segment has a display name.
+    pub fn display_path(&self, component_id: &str) -> String {
+        let mut renamed = false;
+        let segments: Vec<String> = component_id
+            .split('/')
+            .map(|segment| match self.display_name(segment) {
+                Some(name) => {
+                    renamed = true;
+                    name
+                }
+                None => segment.to_string(),
+            })
+            .collect();
+
+        if renamed {
+            segments.join(" / ")
+        } else {
+            component_id.to_string()
+        }
+    }
+
     pub fn level_session_ids(&self, level_index: usize) -> BTreeSet<String> {
         self.levels
             .get(level_index)
@@ -305,7 +850,15 @@ pub fn load_config_from_path(path: &Path) -> Result<AnalyzerConfig, ConfigError>
         source,
     })?;
 
-    parse_config_toml(&raw, &path_display)
+    let mut config = parse_config_toml(&raw, &path_display)?;
+    // Paths inside a profile are relative to the profile, not the working directory
+    if let Some(template) = &mut config.errors.ticket_template
+        && template.is_relative()
+        && let Some(dir) = path.parent()
+    {
+        *template = dir.join(&*template);
+    }
+    Ok(config)
 }
 
 pub fn default_config() -> &'static AnalyzerConfig {
@@ -344,10 +897,29 @@ pub fn load_builtin_template(name: &str) -> Option<AnalyzerConfig> {
 }
 
 fn parse_config_toml(raw: &str, path_display: &str) -> Result<AnalyzerConfig, ConfigError> {
-    toml::from_str::<AnalyzerConfig>(raw).map_err(|source| ConfigError::Parse {
+    let config = toml::from_str::<AnalyzerConfig>(raw).map_err(|source| ConfigError::Parse {
         path: path_display.to_string(),
         source,
-    })
+    })?;
+    if let Some((tag, message)) = config.tags.invalid_rule() {
+        return Err(ConfigError::InvalidTag {
+            path: path_display.to_string(),
+            tag: tag.to_string(),
+            message,
+        });
+    }
+    if let Some((rule, message)) = config
+        .heartbeats
+        .iter()
+        .find_map(|rule| Some((rule, rule.invalid_reason()?)))
+    {
+        return Err(ConfigError::InvalidHeartbeat {
+            path: path_display.to_string(),
+            name: rule.name.clone(),
+            message,
+        });
+    }
+    Ok(config)
 }
 
 fn normalized_template_key(input: &str) -> Option<String> {
@@ -368,6 +940,15 @@ fn normalized_template_key(input: &str) -> Option<String> {
     Some(stem.to_ascii_lowercase())
 }
 
+/// Build only the session hierarchy insights (used to resolve session display names)
+pub fn analyze_sessions(logs: &[LogEntry], cfg: &AnalyzerConfig) -> SessionInsights {
+    let mut sessions = SessionInsights::from_configs(cfg.effective_session_levels());
+    for entry in logs {
+        analyze_session_path(entry, &mut sessions);
+    }
+    sessions
+}
+
 pub fn analyze_profile(logs: &[LogEntry], cfg: &AnalyzerConfig) -> ProfileInsights {
     let mut insights = ProfileInsights {
         sessions: SessionInsights::from_configs(cfg.effective_session_levels()),
@@ -402,18 +983,17 @@ pub fn analyze_profile(logs: &[LogEntry], cfg: &AnalyzerConfig) -> ProfileInsigh
 
         analyze_session_path(entry, &mut insights.sessions);
 
-        match &entry.kind {
-            LogEntryKind::Command { command, .. } => {
-                if !known_commands.is_empty() && !known_commands.contains(&command.to_lowercase()) {
-                    insights.unknown_commands.insert(command.clone());
-                }
-            }
-            LogEntryKind::Request { request, .. } => {
-                if !known_requests.is_empty() && !known_requests.contains(&request.to_lowercase()) {
-                    insights.unknown_requests.insert(request.clone());
-                }
-            }
-            _ => {}
+        if let Some(command) = entry.command_name()
+            && !known_commands.is_empty()
+            && !known_commands.contains(&command.to_lowercase())
+        {
+            insights.unknown_commands.insert(command.to_string());
+        }
+        if let Some(request) = entry.request_name()
+            && !known_requests.is_empty()
+            && !known_requests.contains(&request.to_lowercase())
+        {
+            insights.unknown_requests.insert(request.to_string());
         }
     }
 
@@ -432,18 +1012,25 @@ fn analyze_session_path(entry: &LogEntry, sessions: &mut SessionInsights) {
         return;
     }
 
-    let path_segments: Vec<&str> = entry
-        .component_id
-        .split('/')
-        .filter(|segment| !segment.is_empty())
-        .collect();
-    if path_segments.is_empty() {
-        return;
+    // Parallel paths usually share ancestors; count the entry once per session.
+    let mut counted: Vec<&str> = Vec::new();
+    for path in entry.session_paths() {
+        record_session_path(entry, &path, &mut counted, sessions);
     }
+}
 
+fn record_session_path<'a>(
+    entry: &LogEntry,
+    path: &SessionPath<'a>,
+    counted: &mut Vec<&'a str>,
+    sessions: &mut SessionInsights,
+) {
+    let path_segments = path.segments();
     let mut matched_segments = Vec::new();
     for (path_index, segment) in path_segments.iter().enumerate() {
-        let Some(level_index) = find_matching_session_level(segment, &sessions.levels) else {
+        let Some(level_index) =
+            session_level_index(segment, sessions.levels.iter().map(|level| &level.config))
+        else {
             continue;
         };
 
@@ -459,7 +1046,10 @@ fn analyze_session_path(entry: &LogEntry, sessions: &mut SessionInsights) {
         if entry.timestamp > session.last_seen {
             session.last_seen = entry.timestamp;
         }
-        session.entry_count += 1;
+        if !counted.contains(segment) {
+            counted.push(segment);
+            session.entry_count += 1;
+        }
 
         matched_segments.push(MatchedSessionSegment {
             path_index,
@@ -501,7 +1091,7 @@ fn analyze_session_path(entry: &LogEntry, sessions: &mut SessionInsights) {
             continue;
         };
 
-        let op_type = strip_instance_suffix(segment);
+        let op_type = segment_base(segment);
         if op_type.is_empty() {
             continue;
         }
@@ -544,10 +1134,14 @@ fn analyze_session_path(entry: &LogEntry, sessions: &mut SessionInsights) {
         }
 
         let create_summary_fields = if is_create {
-            sessions.levels[matched.level_index]
-                .config
-                .summary_fields
-                .clone()
+            let level_config = &sessions.levels[matched.level_index].config;
+            let mut fields = level_config.summary_fields.clone();
+            for field in level_config.display_name_fields() {
+                if !fields.contains(&field) {
+                    fields.push(field);
+                }
+            }
+            fields
         } else {
             Vec::new()
         };
@@ -567,31 +1161,6 @@ fn analyze_session_path(entry: &LogEntry, sessions: &mut SessionInsights) {
     }
 }
 
-fn find_matching_session_level(segment: &str, levels: &[SessionLevelInsights]) -> Option<usize> {
-    let mut best_match: Option<(usize, usize)> = None;
-    for (index, level) in levels.iter().enumerate() {
-        let prefix = level.config.segment_prefix.as_str();
-        if prefix.is_empty() || !segment.starts_with(prefix) {
-            continue;
-        }
-
-        let prefix_len = prefix.len();
-        match best_match {
-            Some((_, best_len)) if best_len >= prefix_len => {}
-            _ => best_match = Some((index, prefix_len)),
-        }
-    }
-
-    best_match.map(|(index, _)| index)
-}
-
-fn strip_instance_suffix(segment: &str) -> &str {
-    segment
-        .rsplit_once('-')
-        .map(|(base, _)| base)
-        .unwrap_or(segment)
-}
-
 fn extract_summary_fields(
     session: &mut SessionInfo,
     settings: Option<&Value>,
@@ -614,23 +1183,6 @@ fn extract_summary_fields(
     }
 }
 
-fn value_at_path<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
-    let mut current = root;
-    for segment in path.split('.') {
-        if segment.is_empty() {
-            return None;
-        }
-
-        current = match current {
-            Value::Object(map) => map.get(segment)?,
-            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
-            _ => return None,
-        };
-    }
-
-    Some(current)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -664,6 +1216,7 @@ mod tests {
                 settings,
             },
             source_line_number: 1,
+            tags: Vec::new(),
         }
     }
 
@@ -679,6 +1232,7 @@ mod tests {
             module_path: None,
             kind: LogEntryKind::Generic { payload: None },
             source_line_number: 1,
+            tags: Vec::new(),
         }
     }
 
@@ -693,6 +1247,7 @@ mod tests {
                         create_command: None,
                         complete_commands: Vec::new(),
                         summary_fields: Vec::new(),
+                        display_name: None,
                     },
                     SessionLevelConfig {
                         name: "level-2".to_string(),
@@ -700,8 +1255,10 @@ mod tests {
                         create_command: None,
                         complete_commands: Vec::new(),
                         summary_fields: Vec::new(),
+                        display_name: None,
                     },
                 ],
+                ..SessionsRules::default()
             },
             ..AnalyzerConfig::default()
         };
@@ -755,6 +1312,7 @@ summary_fields = ["concurrency", "batch.id"]
                         create_command: Some("makeManager".to_string()),
                         complete_commands: vec!["closeBatch".to_string()],
                         summary_fields: vec!["concurrency".to_string(), "batch.id".to_string()],
+                        display_name: None,
                     },
                     SessionLevelConfig {
                         name: "test".to_string(),
@@ -762,8 +1320,10 @@ summary_fields = ["concurrency", "batch.id"]
                         create_command: Some("openEyes".to_string()),
                         complete_commands: vec!["close".to_string(), "abort".to_string()],
                         summary_fields: Vec::new(),
+                        display_name: None,
                     },
                 ],
+                ..SessionsRules::default()
             },
             ..AnalyzerConfig::default()
         };
@@ -824,4 +1384,70 @@ summary_fields = ["concurrency", "batch.id"]
         assert_eq!(insights.sessions.level_session_ids(0).len(), 1);
         assert_eq!(insights.sessions.level_session_ids(1).len(), 1);
     }
+
+    #[test]
+    fn session_display_name_template_renders_create_settings() {
+        let cfg = AnalyzerConfig {
+            sessions: SessionsRules {
+                levels: vec![
+                    SessionLevelConfig {
+                        name: "runner".to_string(),
+                        segment_prefix: "manager-".to_string(),
+                        create_command: None,
+                        complete_commands: Vec::new(),
+                        summary_fields: Vec::new(),
+                        display_name: None,
+                    },
+                    SessionLevelConfig {
+                        name: "test".to_string(),
+                        segment_prefix: "eyes-".to_string(),
+                        create_command: Some("openEyes".to_string()),
+                        complete_commands: Vec::new(),
+                        summary_fields: Vec::new(),
+                        display_name: Some(
+                            "{ scenario } [{browser}] {viewport.width}x{viewport.height}"
+                                .to_string(),
+                        ),
+                    },
+                ],
+                ..SessionsRules::default()
+            },
+            ..AnalyzerConfig::default()
+        };
+        assert!(cfg.has_session_display_names());
+
+        let logs = vec![
+            command_entry(
+                "manager-1/eyes-1/openEyes-rw2",
+                "2026-01-01T00:00:01Z",
+                "openEyes",
+                Some(json!({
+                    "scenario": "Login",
+                    "browser": "chrome",
+                    "viewport": {"width": 800, "height": 600}
+                })),
+            ),
+            command_entry(
+                "manager-1/eyes-2/openEyes-rw3",
+                "2026-01-01T00:00:02Z",
+                "openEyes",
+                Some(json!({"browser": "firefox"})),
+            ),
+        ];
+
+        let sessions = analyze_sessions(&logs, &cfg);
+        assert_eq!(
+            sessions.display_name("eyes-1").as_deref(),
+            Some("Login [chrome] 800x600")
+        );
+        assert_eq!(
+            sessions.display_name("eyes-2").as_deref(),
+            Some("? [firefox] ?x?")
+        );
+        assert_eq!(
+            sessions.display_path("manager-1/eyes-1/check-ufg-jdx"),
+            "manager-1 / Login [chrome] 800x600 / check-ufg-jdx"
+        );
+        assert_eq!(sessions.display_path("manager-9/other"), "manager-9/other");
+    }
 }
@@ -0,0 +1,350 @@
+use crate::comparator::LogFilter;
+use crate::config::{AnalyzerConfig, analyze_sessions};
+use crate::parser::LogEntry;
+use crate::perf_analyzer::analyze_performance_with_config;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+const ENTRY_KINDS: [&str; 4] = ["Event", "Command", "Request", "Generic"];
+
+/// How one config classifies, pairs, and groups the entries of a file
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigEffect {
+    pub config: String,
+    pub profile_name: String,
+    pub total_entries: usize,
+    pub kinds: BTreeMap<String, usize>,
+    pub pairing: PairingEffect,
+    pub sessions: Vec<SessionLevelEffect>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PairingEffect {
+    pub completed: usize,
+    pub orphaned: usize,
+    /// Share of started operations that completed, `None` when nothing started
+    pub pairing_rate_pct: Option<f64>,
+    pub by_type: BTreeMap<String, OperationPairing>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OperationPairing {
+    pub completed: usize,
+    pub orphaned: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionLevelEffect {
+    pub level: String,
+    pub detected: usize,
+    pub completed: usize,
+    pub incomplete: usize,
+}
+
+/// Side-by-side effect of two configs on the same file
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigEffectReport {
+    pub file: String,
+    pub a: ConfigEffect,
+    pub b: ConfigEffect,
+}
+
+pub fn measure_config_effect(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    config: &AnalyzerConfig,
+    label: &str,
+) -> ConfigEffect {
+    let logs: Vec<LogEntry> = logs
+        .iter()
+        .filter(|entry| filter.matches(entry))
+        .cloned()
+        .collect();
+
+    let mut kinds: BTreeMap<String, usize> = ENTRY_KINDS
+        .iter()
+        .map(|kind| (kind.to_string(), 0))
+        .collect();
+    for entry in &logs {
+        *kinds.entry(entry.entry_type().to_string()).or_default() += 1;
+    }
+
+    let perf = analyze_performance_with_config(&logs, &LogFilter::new(), None, config);
+    let mut pairing = PairingEffect {
+        completed: perf.operations.len(),
+        orphaned: perf.orphans.len(),
+        ..PairingEffect::default()
+    };
+    for operation in &perf.operations {
+        pairing
+            .by_type
+            .entry(operation.op_type.clone())
+            .or_default()
+            .completed += 1;
+    }
+    for orphan in &perf.orphans {
+        pairing
+            .by_type
+            .entry(orphan.op_type.clone())
+            .or_default()
+            .orphaned += 1;
+    }
+    let started = pairing.completed + pairing.orphaned;
+    pairing.pairing_rate_pct =
+        (started > 0).then(|| pairing.completed as f64 * 100.0 / started as f64);
+
+    let sessions = analyze_sessions(&logs, config)
+        .levels
+        .iter()
+        .map(|level| SessionLevelEffect {
+            level: level.config.name.clone(),
+            detected: level.sessions.len(),
+            completed: level.completed_count(),
+            incomplete: level.incomplete_count(),
+        })
+        .collect();
+
+    ConfigEffect {
+        config: label.to_string(),
+        profile_name: config.profile_name.clone(),
+        total_entries: logs.len(),
+        kinds,
+        pairing,
+        sessions,
+    }
+}
+
+impl ConfigEffect {
+    fn session_level(&self, name: &str) -> Option<&SessionLevelEffect> {
+        self.sessions.iter().find(|level| level.level == name)
+    }
+}
+
+impl ConfigEffectReport {
+    fn kind_names(&self) -> BTreeSet<&str> {
+        self.a
+            .kinds
+            .keys()
+            .chain(self.b.kinds.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn operation_types(&self) -> BTreeSet<&str> {
+        self.a
+            .pairing
+            .by_type
+            .keys()
+            .chain(self.b.pairing.by_type.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Session level names in config A order, followed by levels only B defines
+    fn session_levels(&self) -> Vec<&str> {
+        let mut levels: Vec<&str> = self.a.sessions.iter().map(|l| l.level.as_str()).collect();
+        for level in &self.b.sessions {
+            if !levels.contains(&level.level.as_str()) {
+                levels.push(&level.level);
+            }
+        }
+        levels
+    }
+
+    pub fn has_differences(&self) -> bool {
+        let session_counts = |effect: &ConfigEffect| -> Vec<(String, usize, usize)> {
+            effect
+                .sessions
+                .iter()
+                .filter(|level| level.detected > 0)
+                .map(|level| (level.level.clone(), level.detected, level.completed))
+                .collect()
+        };
+        self.a.total_entries != self.b.total_entries
+            || self.a.kinds != self.b.kinds
+            || self.a.pairing.completed != self.b.pairing.completed
+            || self.a.pairing.orphaned != self.b.pairing.orphaned
+            || session_counts(&self.a) != session_counts(&self.b)
+    }
+}
+
+fn signed_delta(a: usize, b: usize) -> i64 {
+    b as i64 - a as i64
+}
+
+fn format_delta(a: usize, b: usize) -> String {
+    match signed_delta(a, b) {
+        0 => "=".to_string(),
+        delta => format!("{delta:+}"),
+    }
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    rate.map(|rate| format!("{rate:.1}%"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn format_rate_delta(a: Option<f64>, b: Option<f64>) -> String {
+    match (a, b) {
+        (Some(a), Some(b)) if (b - a).abs() < 0.05 => "=".to_string(),
+        (Some(a), Some(b)) => format!("{:+.1}pp", b - a),
+        _ => "-".to_string(),
+    }
+}
+
+fn row(out: &mut String, label: &str, a: &str, b: &str, delta: &str) {
+    let _ = writeln!(out, "  {label:<28} {a:>12} {b:>12} {delta:>10}");
+}
+
+pub fn format_config_effect_text(report: &ConfigEffectReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "CONFIG EFFECT: {}", report.file);
+    let _ = writeln!(
+        out,
+        "  A: {} (profile {})",
+        report.a.config, report.a.profile_name
+    );
+    let _ = writeln!(
+        out,
+        "  B: {} (profile {})",
+        report.b.config, report.b.profile_name
+    );
+
+    let _ = writeln!(out, "\nEntry kinds:");
+    row(&mut out, "", "A", "B", "Δ");
+    row(
+        &mut out,
+        "total",
+        &report.a.total_entries.to_string(),
+        &report.b.total_entries.to_string(),
+        &format_delta(report.a.total_entries, report.b.total_entries),
+    );
+    for kind in report.kind_names() {
+        let a = report.a.kinds.get(kind).copied().unwrap_or(0);
+        let b = report.b.kinds.get(kind).copied().unwrap_or(0);
+        row(
+            &mut out,
+            kind,
+            &a.to_string(),
+            &b.to_string(),
+            &format_delta(a, b),
+        );
+    }
+
+    let _ = writeln!(out, "\nPairing:");
+    let (pa, pb) = (&report.a.pairing, &report.b.pairing);
+    row(
+        &mut out,
+        "completed",
+        &pa.completed.to_string(),
+        &pb.completed.to_string(),
+        &format_delta(pa.completed, pb.completed),
+    );
+    row(
+        &mut out,
+        "orphaned",
+        &pa.orphaned.to_string(),
+        &pb.orphaned.to_string(),
+        &format_delta(pa.orphaned, pb.orphaned),
+    );
+    row(
+        &mut out,
+        "pairing rate",
+        &format_rate(pa.pairing_rate_pct),
+        &format_rate(pb.pairing_rate_pct),
+        &format_rate_delta(pa.pairing_rate_pct, pb.pairing_rate_pct),
+    );
+    for op_type in report.operation_types() {
+        let a = pa.by_type.get(op_type).cloned().unwrap_or_default();
+        let b = pb.by_type.get(op_type).cloned().unwrap_or_default();
+        row(
+            &mut out,
+            &format!("{op_type} completed/orphaned"),
+            &format!("{}/{}", a.completed, a.orphaned),
+            &format!("{}/{}", b.completed, b.orphaned),
+            &format_delta(a.completed, b.completed),
+        );
+    }
+
+    let levels = report.session_levels();
+    let _ = writeln!(out, "\nSessions detected:");
+    if levels.is_empty() {
+        let _ = writeln!(out, "  (no session levels configured)");
+    }
+    for level in levels {
+        let cell = |effect: &ConfigEffect| match effect.session_level(level) {
+            Some(level) => format!("{} ({} done)", level.detected, level.completed),
+            None => "-".to_string(),
+        };
+        let detected =
+            |effect: &ConfigEffect| effect.session_level(level).map_or(0, |l| l.detected);
+        row(
+            &mut out,
+            level,
+            &cell(&report.a),
+            &cell(&report.b),
+            &format_delta(detected(&report.a), detected(&report.b)),
+        );
+    }
+
+    if !report.has_differences() {
+        let _ = writeln!(
+            out,
+            "\nNo differences: both configs classify, pair, and group this file identically."
+        );
+    }
+    out
+}
+
+pub fn format_config_effect_json(report: &ConfigEffectReport) -> String {
+    let kinds: Map<String, Value> = report
+        .kind_names()
+        .into_iter()
+        .map(|kind| {
+            let a = report.a.kinds.get(kind).copied().unwrap_or(0);
+            let b = report.b.kinds.get(kind).copied().unwrap_or(0);
+            (kind.to_string(), json!(signed_delta(a, b)))
+        })
+        .collect();
+    let sessions: Map<String, Value> = report
+        .session_levels()
+        .into_iter()
+        .map(|level| {
+            let detected =
+                |effect: &ConfigEffect| effect.session_level(level).map_or(0, |l| l.detected);
+            (
+                level.to_string(),
+                json!(signed_delta(detected(&report.a), detected(&report.b))),
+            )
+        })
+        .collect();
+    let rate_delta = match (
+        report.a.pairing.pairing_rate_pct,
+        report.b.pairing.pairing_rate_pct,
+    ) {
+        (Some(a), Some(b)) => json!(b - a),
+        _ => Value::Null,
+    };
+
+    serde_json::to_string_pretty(&json!({
+        "config_effect": {
+            "file": report.file,
+            "a": report.a,
+            "b": report.b,
+            "delta": {
+                "total_entries": signed_delta(report.a.total_entries, report.b.total_entries),
+                "kinds": kinds,
+                "completed": signed_delta(report.a.pairing.completed, report.b.pairing.completed),
+                "orphaned": signed_delta(report.a.pairing.orphaned, report.b.pairing.orphaned),
+                "pairing_rate_pct": rate_delta,
+                "sessions_detected": sessions,
+            },
+            "has_differences": report.has_differences(),
+        }
+    }))
+    .unwrap_or_else(|_| {
+        "{\"config_effect\":{\"error\":\"failed to serialize config effect\"}}".into()
+    })
+}
@@ -1,5 +1,5 @@
 use crate::config::{AnalyzerConfig, LogFormat, SessionLevelConfig};
-use crate::parser::{LogEntry, LogEntryKind};
+use crate::parser::LogEntry;
 use std::collections::{BTreeMap, BTreeSet};
 
 pub struct GenerateConfigOptions {
@@ -50,18 +50,11 @@ pub fn generate_config(
             }
         }
 
-        match &entry.kind {
-            LogEntryKind::Command { command, .. } => {
-                if !command.is_empty() {
-                    commands.insert(command.clone());
-                }
-            }
-            LogEntryKind::Request { request, .. } => {
-                if !request.is_empty() {
-                    requests.insert(request.clone());
-                }
-            }
-            _ => {}
+        if let Some(command) = entry.command_name().filter(|name| !name.is_empty()) {
+            commands.insert(command.to_string());
+        }
+        if let Some(request) = entry.request_name().filter(|name| !name.is_empty()) {
+            requests.insert(request.to_string());
         }
     }
 
@@ -98,6 +91,7 @@ pub fn generate_config(
                 create_command: None,
                 complete_commands: Vec::new(),
                 summary_fields: Vec::new(),
+                display_name: None,
             })
             .collect();
     }
@@ -0,0 +1,213 @@
+use crate::comparator::LogFilter;
+use crate::config::ContractRules;
+use crate::parser::{LogEntry, LogEntryKind};
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Violations listed per contract in text output
+const TEXT_VIOLATION_LIMIT: usize = 5;
+
+/// Conformance of entries to `[contracts]` payload requirements
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContractReport {
+    /// Declared contracts, most violated first
+    pub contracts: Vec<ContractSummary>,
+    pub checked: usize,
+    pub violating: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractSummary {
+    /// `request` or `command`
+    pub kind: &'static str,
+    pub name: String,
+    pub required: Vec<String>,
+    /// Matching entries the contract applied to
+    pub checked: usize,
+    /// Entries missing at least one required field
+    pub violating: usize,
+    /// Required field → entries missing it
+    pub missing_by_field: BTreeMap<String, usize>,
+    pub violations: Vec<ContractViolation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractViolation {
+    pub line_number: usize,
+    pub timestamp: String,
+    pub component_id: String,
+    pub missing: Vec<String>,
+}
+
+/// Checks every entry matching `filter` against its request/command contract
+pub fn build_contract_report(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    rules: &ContractRules,
+) -> ContractReport {
+    let mut summaries: BTreeMap<(&'static str, &str), ContractSummary> = rules
+        .requests
+        .iter()
+        .map(|(name, required)| (("request", name.as_str()), required))
+        .chain(
+            rules
+                .commands
+                .iter()
+                .map(|(name, required)| (("command", name.as_str()), required)),
+        )
+        .map(|((kind, name), required)| {
+            let summary = ContractSummary {
+                kind,
+                name: name.to_string(),
+                required: required.clone(),
+                checked: 0,
+                violating: 0,
+                missing_by_field: required.iter().map(|path| (path.clone(), 0)).collect(),
+                violations: Vec::new(),
+            };
+            ((kind, name), summary)
+        })
+        .collect();
+
+    for entry in logs.iter().filter(|entry| filter.matches(entry)) {
+        if rules.required_fields(entry).is_none() {
+            continue;
+        }
+        let key = match &entry.kind {
+            LogEntryKind::Request { request, .. } => ("request", request.as_str()),
+            LogEntryKind::Command { command, .. } => ("command", command.as_str()),
+            _ => continue,
+        };
+        let Some(summary) = summaries.get_mut(&key) else {
+            continue;
+        };
+        summary.checked += 1;
+        let missing = rules.missing_fields(entry);
+        if missing.is_empty() {
+            continue;
+        }
+        summary.violating += 1;
+        for path in &missing {
+            *summary
+                .missing_by_field
+                .entry(path.to_string())
+                .or_default() += 1;
+        }
+        summary.violations.push(ContractViolation {
+            line_number: entry.source_line_number,
+            timestamp: entry
+                .timestamp
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            component_id: entry.component_id.clone(),
+            missing: missing.into_iter().map(str::to_string).collect(),
+        });
+    }
+
+    let mut contracts: Vec<ContractSummary> = summaries.into_values().collect();
+    contracts.sort_by(|a, b| {
+        b.violating
+            .cmp(&a.violating)
+            .then_with(|| a.kind.cmp(b.kind))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    ContractReport {
+        checked: contracts.iter().map(|contract| contract.checked).sum(),
+        violating: contracts.iter().map(|contract| contract.violating).sum(),
+        contracts,
+    }
+}
+
+pub fn format_contracts_text(report: &ContractReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "CONTRACTS: {} of {} checked entries missing required fields ({} contracts)",
+        report.violating,
+        report.checked,
+        report.contracts.len()
+    );
+    if report.contracts.is_empty() {
+        let _ = writeln!(
+            out,
+            "\nNo contracts declared. Add [contracts.requests] or [contracts.commands] to the profile."
+        );
+        return out;
+    }
+
+    for contract in &report.contracts {
+        let status = if contract.violating == 0 {
+            "✓"
+        } else {
+            "✗"
+        };
+        let _ = writeln!(
+            out,
+            "\n{status} {} {}: {} of {} entries violating",
+            contract.kind, contract.name, contract.violating, contract.checked
+        );
+        for (field, missing) in &contract.missing_by_field {
+            let _ = writeln!(out, "    {missing:>6} missing  {field}");
+        }
+        for violation in contract.violations.iter().take(TEXT_VIOLATION_LIMIT) {
+            let _ = writeln!(
+                out,
+                "    line {} ({}): missing {}",
+                violation.line_number,
+                violation.component_id,
+                violation.missing.join(", ")
+            );
+        }
+        if contract.violations.len() > TEXT_VIOLATION_LIMIT {
+            let _ = writeln!(
+                out,
+                "    ... {} more (use -F json for all)",
+                contract.violations.len() - TEXT_VIOLATION_LIMIT
+            );
+        }
+    }
+    out
+}
+
+pub fn format_contracts_json(report: &ContractReport) -> String {
+    serde_json::to_string_pretty(&json!({ "contracts": report }))
+        .unwrap_or_else(|_| "{\"contracts\":{\"error\":\"failed to serialize contracts\"}}".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_missing_fields_per_contract() {
+        let rules: ContractRules = toml::from_str(
+            r#"
+            [requests]
+            check = ["settings.name", "renderId"]
+            "#,
+        )
+        .expect("contract rules parse");
+        let config = crate::config::load_config(None, Some("eyes")).expect("eyes preset");
+        let logs: Vec<LogEntry> = [
+            r#"core | 2026-01-01T00:00:00.000Z [INFO ] Request "check" [0--a] will be sent with body {"settings":{"name":"x"},"renderId":"r1"}"#,
+            r#"core | 2026-01-01T00:00:01.000Z [INFO ] Request "check" [0--b] will be sent with body {"settings":{}}"#,
+            r#"core | 2026-01-01T00:00:02.000Z [INFO ] Request "check" [0--b] finished successfully with body {"statusCode":200}"#,
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            crate::parser::parse_log_entry_with_config(line, i + 1, &config).expect("line parses")
+        })
+        .collect();
+
+        let report = build_contract_report(&logs, &LogFilter::new(), &rules);
+        let contract = &report.contracts[0];
+        assert_eq!((contract.checked, contract.violating), (2, 1));
+        assert_eq!(contract.missing_by_field["settings.name"], 1);
+        assert_eq!(contract.missing_by_field["renderId"], 1);
+        assert_eq!(contract.violations[0].line_number, 2);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::cli::CountBy;
+use crate::comparator::LogFilter;
+use crate::parser::LogEntry;
+use serde_json::json;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountGroup {
+    pub key: String,
+    pub count: usize,
+}
+
+/// Matching entry totals produced by the `count` fast path, accumulated file by file
+#[derive(Debug, Clone, Default)]
+pub struct CountSummary {
+    pub total: usize,
+    grouped: BTreeMap<String, usize>,
+}
+
+impl CountSummary {
+    pub fn record(&mut self, logs: &[LogEntry], filter: &LogFilter, by: Option<CountBy>) {
+        for log in logs.iter().filter(|log| filter.matches(log)) {
+            self.total += 1;
+            let Some(by) = by else {
+                continue;
+            };
+            let key = match by {
+                CountBy::Component => log.component.clone(),
+                CountBy::Level => log.level.clone(),
+                CountBy::Type => log.log_key(),
+            };
+            *self.grouped.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    /// Groups ordered by descending count, then key
+    pub fn groups(&self) -> Vec<CountGroup> {
+        let mut groups: Vec<_> = self
+            .grouped
+            .iter()
+            .map(|(key, &count)| CountGroup {
+                key: key.clone(),
+                count,
+            })
+            .collect();
+        groups.sort_by_key(|group| (Reverse(group.count), group.key.clone()));
+        groups
+    }
+}
+
+fn count_by_label(by: CountBy) -> &'static str {
+    match by {
+        CountBy::Component => "component",
+        CountBy::Level => "level",
+        CountBy::Type => "type",
+    }
+}
+
+pub fn format_count_text(summary: &CountSummary, by: Option<CountBy>) -> String {
+    let Some(by) = by else {
+        return format!("{}\n", summary.total);
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "COUNT by {} ({} entr{})",
+        count_by_label(by),
+        summary.total,
+        if summary.total == 1 { "y" } else { "ies" }
+    );
+
+    let groups = summary.groups();
+    if groups.is_empty() {
+        return out;
+    }
+
+    out.push('\n');
+    for group in groups {
+        let _ = writeln!(out, "{:>6}  {}", group.count, group.key);
+    }
+
+    out
+}
+
+pub fn format_count_json(files: &[PathBuf], summary: &CountSummary, by: Option<CountBy>) -> String {
+    serde_json::to_string_pretty(&json!({
+        "count": {
+            "files": files
+                .iter()
+                .map(|file| file.display().to_string())
+                .collect::<Vec<_>>(),
+            "total": summary.total,
+            "by": by.map(count_by_label),
+            "groups": summary
+                .groups()
+                .iter()
+                .map(|group| json!({
+                    "key": group.key,
+                    "count": group.count,
+                }))
+                .collect::<Vec<_>>(),
+        }
+    }))
+    .unwrap_or_else(|_| "{\"count\":{\"error\":\"failed to serialize count output\"}}".into())
+}
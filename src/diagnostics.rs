@@ -0,0 +1,199 @@
+use serde::Serialize;
+use std::cell::RefCell;
+
+/// A condition the analyzer reports about its own input or output, with a stable code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum WarningCode {
+    #[serde(rename = "LA0001")]
+    FilterMatchedNothing,
+    #[serde(rename = "LA0002")]
+    UnknownFilterLevel,
+    #[serde(rename = "LA0003")]
+    PayloadParseFailures,
+    #[serde(rename = "LA0004")]
+    TruncatedComparison,
+    #[serde(rename = "LA0005")]
+    SuspectDurations,
+    #[serde(rename = "LA0006")]
+    RunNotFound,
+    #[serde(rename = "LA0007")]
+    CheckpointedFileShrank,
+    #[serde(rename = "LA0008")]
+    HeartbeatsSuppressed,
+}
+
+impl WarningCode {
+    pub const ALL: [WarningCode; 8] = [
+        WarningCode::FilterMatchedNothing,
+        WarningCode::UnknownFilterLevel,
+        WarningCode::PayloadParseFailures,
+        WarningCode::TruncatedComparison,
+        WarningCode::SuspectDurations,
+        WarningCode::RunNotFound,
+        WarningCode::CheckpointedFileShrank,
+        WarningCode::HeartbeatsSuppressed,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            WarningCode::FilterMatchedNothing => "LA0001",
+            WarningCode::UnknownFilterLevel => "LA0002",
+            WarningCode::PayloadParseFailures => "LA0003",
+            WarningCode::TruncatedComparison => "LA0004",
+            WarningCode::SuspectDurations => "LA0005",
+            WarningCode::RunNotFound => "LA0006",
+            WarningCode::CheckpointedFileShrank => "LA0007",
+            WarningCode::HeartbeatsSuppressed => "LA0008",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|warning| warning.code().eq_ignore_ascii_case(code.trim()))
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            WarningCode::FilterMatchedNothing => "filter matched no entries",
+            WarningCode::UnknownFilterLevel => "unknown log level in filter",
+            WarningCode::PayloadParseFailures => "payloads failed to parse",
+            WarningCode::TruncatedComparison => "payload comparison truncated at max depth",
+            WarningCode::SuspectDurations => "negative operation durations set aside",
+            WarningCode::RunNotFound => "requested run does not exist",
+            WarningCode::CheckpointedFileShrank => "checkpointed file is shorter than before",
+            WarningCode::HeartbeatsSuppressed => "repeated heartbeat entries suppressed",
+        }
+    }
+
+    /// What the condition means and what to do about it, for `--explain`
+    pub fn explanation(self) -> &'static str {
+        match self {
+            WarningCode::FilterMatchedNothing => {
+                "The --filter expression (together with .loganalyzerignore filters and --run) \
+                 excluded every entry, so the report is empty. Check the filter for typos, \
+                 try a looser term, or run `info` without a filter to see the components, \
+                 levels and message texts that are present."
+            }
+            WarningCode::UnknownFilterLevel => {
+                "A `l:` / `level:` term names a level that is not one of TRACE, DEBUG, INFO, \
+                 WARN, WARNING, ERROR or FATAL. Levels are matched case-insensitively, so an \
+                 unknown name usually means a typo and the term matches nothing."
+            }
+            WarningCode::PayloadParseFailures => {
+                "Some entries announced a JSON payload that was invalid json5 or cut off. Those \
+                 payloads are missing from comparisons and field analysis, so an \"identical\" \
+                 result may hide differences. Rerun with --payload-errors to list every failure."
+            }
+            WarningCode::TruncatedComparison => {
+                "Payload subtrees nested deeper than --max-json-depth (or [comparison] \
+                 max_json_depth) were compared as opaque blobs: a difference anywhere inside \
+                 is reported once, at the cut-off path. Raise the depth to see the exact paths."
+            }
+            WarningCode::SuspectDurations => {
+                "Some paired operations ended before they started, because of clock skew between \
+                 components or entries written out of order. They are excluded from the timing \
+                 statistics and listed separately; pass --include-suspect-durations to count them."
+            }
+            WarningCode::RunNotFound => {
+                "--run N asked for a run the log does not contain, so no entries were selected. \
+                 Use `info --split-runs` to list the runs detected with the [runs] start markers \
+                 and gap setting."
+            }
+            WarningCode::CheckpointedFileShrank => {
+                "A file is shorter than the read position saved in the errors --checkpoint, so \
+                 it was replaced rather than appended to. It is read again from the start, which \
+                 can count entries that were already analyzed twice. Delete the checkpoint to \
+                 start over."
+            }
+            WarningCode::HeartbeatsSuppressed => {
+                "Entries matching a [[heartbeats]] rule of the profile repeated within the rule's \
+                 window_ms, so only the first entry of each window was kept. Every command, \
+                 counts, timelines and error clusters included, leaves the others out. Narrow \
+                 the rule's filter, shorten its window or remove it to see every heartbeat."
+            }
+        }
+    }
+}
+
+/// One emitted warning, as listed in the `warnings` array of JSON output
+#[derive(Debug, Clone, Serialize)]
+pub struct Warning {
+    pub code: WarningCode,
+    pub message: String,
+}
+
+/// Warnings emitted during one run, in order
+#[derive(Debug, Default)]
+pub struct WarningLog {
+    warnings: RefCell<Vec<Warning>>,
+}
+
+impl WarningLog {
+    /// Prints a warning to stderr and records it for JSON output
+    pub fn warn(&self, code: WarningCode, message: impl Into<String>) {
+        let message = message.into();
+        eprintln!("Warning [{}]: {message}", code.code());
+        self.record(code, message);
+    }
+
+    /// Records a warning for JSON output only, for conditions the report already describes
+    pub fn record(&self, code: WarningCode, message: impl Into<String>) {
+        self.warnings.borrow_mut().push(Warning {
+            code,
+            message: message.into(),
+        });
+    }
+
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.warnings.borrow().clone()
+    }
+}
+
+/// Text for `--explain <CODE>`
+pub fn format_explanation(code: WarningCode) -> String {
+    format!(
+        "{}: {}\n\n{}\n",
+        code.code(),
+        code.title(),
+        code.explanation()
+    )
+}
+
+/// Every code with its title, for `--explain` with an unknown code
+pub fn format_catalog() -> String {
+    WarningCode::ALL
+        .iter()
+        .map(|code| format!("  {}  {}\n", code.code(), code.title()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique_and_round_trip() {
+        for code in WarningCode::ALL {
+            assert_eq!(WarningCode::from_code(code.code()), Some(code));
+            assert_eq!(
+                serde_json::to_value(code).unwrap(),
+                serde_json::json!(code.code())
+            );
+        }
+        assert_eq!(
+            WarningCode::from_code("la0003"),
+            Some(WarningCode::PayloadParseFailures)
+        );
+        assert_eq!(WarningCode::from_code("LA9999"), None);
+    }
+
+    #[test]
+    fn warning_logs_are_independent() {
+        let first = WarningLog::default();
+        let second = WarningLog::default();
+        first.record(WarningCode::RunNotFound, "no run 2");
+        assert_eq!(first.warnings().len(), 1);
+        assert!(second.warnings().is_empty());
+    }
+}
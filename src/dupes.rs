@@ -0,0 +1,367 @@
+//! `dupes`: near-identical entries of two files, grouped by a simhash fingerprint of the
+//! normalized message template and the payload shape
+
+use crate::errors::normalize_message_pattern;
+use crate::parser::LogEntry;
+use crate::similar::{TEMPLATE_WEIGHT, shape_paths, template_tokens};
+use serde_json::json;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::Path;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Stable 64-bit FNV-1a hash, here of one fingerprint feature
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Simhash over the entry's template tokens and payload shape paths, weighted like
+/// `similar` scores (0.7 template, 0.3 shape) so a long payload cannot drown the message
+pub fn fingerprint(entry: &LogEntry) -> u64 {
+    let tokens = template_tokens(&entry.message);
+    let shape = shape_paths(entry).unwrap_or_default();
+    let mut features: Vec<(String, f64)> = Vec::with_capacity(tokens.len() + shape.len());
+    let token_weight = TEMPLATE_WEIGHT / tokens.len().max(1) as f64;
+    features.extend(
+        tokens
+            .into_iter()
+            .map(|token| (format!("t:{token}"), token_weight)),
+    );
+    let shape_weight = (1.0 - TEMPLATE_WEIGHT) / shape.len().max(1) as f64;
+    features.extend(
+        shape
+            .into_iter()
+            .map(|path| (format!("p:{path}"), shape_weight)),
+    );
+
+    let mut bits = [0.0f64; 64];
+    for (feature, weight) in &features {
+        let hash = fnv1a(feature.as_bytes());
+        for (bit, sum) in bits.iter_mut().enumerate() {
+            if hash >> bit & 1 == 1 {
+                *sum += weight;
+            } else {
+                *sum -= weight;
+            }
+        }
+    }
+    bits.iter()
+        .enumerate()
+        .filter(|(_, sum)| **sum > 0.0)
+        .fold(0, |fingerprint, (bit, _)| fingerprint | 1 << bit)
+}
+
+/// One normalized message pattern inside a group, as seen in one file
+#[derive(Debug, Clone)]
+pub struct DupeVariant {
+    pub pattern: String,
+    pub level: String,
+    pub count: usize,
+    pub first_line: usize,
+}
+
+/// Near-identical entries of one component across both files
+#[derive(Debug, Clone)]
+pub struct DupeGroup {
+    pub component: String,
+    /// Fingerprint of the group's most frequent member
+    pub fingerprint: u64,
+    pub counts: [usize; 2],
+    /// Per file, most frequent pattern first
+    pub variants: [Vec<DupeVariant>; 2],
+}
+
+impl DupeGroup {
+    pub fn delta(&self) -> i64 {
+        self.counts[1] as i64 - self.counts[0] as i64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DupesReport {
+    pub max_distance: u32,
+    /// Groups with entries in both files
+    pub groups_in_both: usize,
+    /// Groups in both files whose counts differ, largest difference first
+    pub groups: Vec<DupeGroup>,
+}
+
+/// Entries of one exact `(component, fingerprint)`
+#[derive(Default)]
+struct Node {
+    counts: [usize; 2],
+    variants: [BTreeMap<String, DupeVariant>; 2],
+}
+
+fn find_root(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
+
+/// Groups the entries of both files by component and fingerprint, joining fingerprints
+/// at most `max_distance` bits apart (transitively)
+pub fn find_dupes(files: [&[&LogEntry]; 2], max_distance: u32) -> DupesReport {
+    let mut nodes: BTreeMap<(String, u64), Node> = BTreeMap::new();
+    for (side, entries) in files.iter().enumerate() {
+        for entry in entries.iter() {
+            let node = nodes
+                .entry((entry.component.clone(), fingerprint(entry)))
+                .or_default();
+            node.counts[side] += 1;
+            let pattern = normalize_message_pattern(&entry.message);
+            node.variants[side]
+                .entry(pattern.clone())
+                .or_insert_with(|| DupeVariant {
+                    pattern,
+                    level: entry.level.clone(),
+                    count: 0,
+                    first_line: entry.source_line_number,
+                })
+                .count += 1;
+        }
+    }
+
+    let keys: Vec<(String, u64)> = nodes.keys().cloned().collect();
+    let mut parents: Vec<usize> = (0..keys.len()).collect();
+    for a in 0..keys.len() {
+        for b in a + 1..keys.len() {
+            if keys[a].0 != keys[b].0 {
+                break;
+            }
+            if (keys[a].1 ^ keys[b].1).count_ones() <= max_distance {
+                let (root_a, root_b) = (find_root(&mut parents, a), find_root(&mut parents, b));
+                parents[root_b] = root_a;
+            }
+        }
+    }
+
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for index in 0..keys.len() {
+        members
+            .entry(find_root(&mut parents, index))
+            .or_default()
+            .push(index);
+    }
+
+    let mut nodes: Vec<Node> = nodes.into_values().collect();
+    let totals: Vec<usize> = nodes
+        .iter()
+        .map(|node| node.counts[0] + node.counts[1])
+        .collect();
+    let mut groups_in_both = 0;
+    let mut groups = Vec::new();
+    for indices in members.into_values() {
+        let mut counts = [0, 0];
+        let mut variants: [HashMap<String, DupeVariant>; 2] = Default::default();
+        for &index in &indices {
+            let node = std::mem::take(&mut nodes[index]);
+            for side in 0..2 {
+                counts[side] += node.counts[side];
+                for (pattern, variant) in &node.variants[side] {
+                    variants[side]
+                        .entry(pattern.clone())
+                        .and_modify(|merged| {
+                            merged.count += variant.count;
+                            merged.first_line = merged.first_line.min(variant.first_line);
+                        })
+                        .or_insert_with(|| variant.clone());
+                }
+            }
+        }
+        if counts[0] == 0 || counts[1] == 0 {
+            continue;
+        }
+        groups_in_both += 1;
+        if counts[0] == counts[1] {
+            continue;
+        }
+
+        let representative = indices
+            .iter()
+            .copied()
+            .max_by_key(|&index| (totals[index], Reverse(index)))
+            .unwrap_or(indices[0]);
+        let [before, after] = variants.map(|side| {
+            let mut side: Vec<DupeVariant> = side.into_values().collect();
+            side.sort_by(|a, b| {
+                b.count
+                    .cmp(&a.count)
+                    .then_with(|| a.pattern.cmp(&b.pattern))
+            });
+            side
+        });
+        groups.push(DupeGroup {
+            component: keys[representative].0.clone(),
+            fingerprint: keys[representative].1,
+            counts,
+            variants: [before, after],
+        });
+    }
+
+    groups.sort_by(|a, b| {
+        b.delta()
+            .abs()
+            .cmp(&a.delta().abs())
+            .then_with(|| a.component.cmp(&b.component))
+            .then_with(|| a.fingerprint.cmp(&b.fingerprint))
+    });
+    DupesReport {
+        max_distance,
+        groups_in_both,
+        groups,
+    }
+}
+
+fn file_label(file: &Path) -> String {
+    file.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| file.display().to_string())
+}
+
+fn format_variants(out: &mut String, label: &str, variants: &[DupeVariant]) {
+    let Some(top) = variants.first() else {
+        return;
+    };
+    let _ = write!(
+        out,
+        "     {label}:{}: [{}] {}",
+        top.first_line, top.level, top.pattern
+    );
+    if variants.len() > 1 {
+        let _ = write!(out, "  (+{} more variant(s))", variants.len() - 1);
+    }
+    out.push('\n');
+}
+
+pub fn format_dupes_text(files: [&Path; 2], report: &DupesReport, top: usize) -> String {
+    let labels = files.map(file_label);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "NEAR-DUPLICATES: {} ↔ {} (fingerprint distance ≤ {} bits)",
+        labels[0], labels[1], report.max_distance
+    );
+    let _ = writeln!(
+        out,
+        "{} group(s) occur in both files, {} with differing counts",
+        report.groups_in_both,
+        report.groups.len()
+    );
+
+    let shown = if top == 0 {
+        report.groups.len()
+    } else {
+        top.min(report.groups.len())
+    };
+    for (index, group) in report.groups.iter().take(shown).enumerate() {
+        let _ = writeln!(
+            out,
+            "\n #{:<2} {}  {} ×{} → {} ×{}  ({:+})",
+            index + 1,
+            group.component,
+            labels[0],
+            group.counts[0],
+            labels[1],
+            group.counts[1],
+            group.delta()
+        );
+        format_variants(&mut out, &labels[0], &group.variants[0]);
+        format_variants(&mut out, &labels[1], &group.variants[1]);
+    }
+    if shown < report.groups.len() {
+        let _ = writeln!(
+            out,
+            "\n... {} more group(s) (use --top 0 to show all)",
+            report.groups.len() - shown
+        );
+    }
+    out
+}
+
+pub fn format_dupes_json(files: [&Path; 2], report: &DupesReport, top: usize) -> String {
+    let variants_json = |variants: &[DupeVariant]| {
+        variants
+            .iter()
+            .map(|variant| {
+                json!({
+                    "pattern": variant.pattern,
+                    "level": variant.level,
+                    "count": variant.count,
+                    "first_line": variant.first_line,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    let shown = if top == 0 {
+        report.groups.len()
+    } else {
+        top.min(report.groups.len())
+    };
+
+    serde_json::to_string_pretty(&json!({
+        "dupes": {
+            "file1": files[0].display().to_string(),
+            "file2": files[1].display().to_string(),
+            "max_distance": report.max_distance,
+            "groups_in_both": report.groups_in_both,
+            "groups_with_differing_counts": report.groups.len(),
+            "groups": report.groups.iter().take(shown).map(|group| json!({
+                "component": group.component,
+                "fingerprint": format!("{:016x}", group.fingerprint),
+                "count1": group.counts[0],
+                "count2": group.counts[1],
+                "delta": group.delta(),
+                "variants1": variants_json(&group.variants[0]),
+                "variants2": variants_json(&group.variants[1]),
+            })).collect::<Vec<_>>(),
+        }
+    }))
+    .unwrap_or_else(|_| "{\"dupes\":{\"error\":\"failed to serialize dupes output\"}}".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::LogEntryKind;
+    use chrono::Local;
+
+    #[test]
+    fn fingerprint_distance_separates_reworded_from_unrelated_messages() {
+        let distance = |a: &str, b: &str| {
+            let entry = |message: &str| LogEntry {
+                timestamp: Local::now(),
+                component: "core".to_string(),
+                component_id: String::new(),
+                level: "INFO".to_string(),
+                message: message.to_string(),
+                raw_logline: String::new(),
+                structured_fields: Default::default(),
+                module_path: None,
+                kind: LogEntryKind::Generic { payload: None },
+                source_line_number: 1,
+                tags: Vec::new(),
+            };
+            (fingerprint(&entry(a)) ^ fingerprint(&entry(b))).count_ones()
+        };
+        assert!(
+            distance(
+                "Render with id \"abc\" failed due to an error - internal failure",
+                "Render with id \"abc\" has failed due to an error - internal failure",
+            ) <= 12
+        );
+        assert!(
+            distance(
+                "Render with id \"abc\" failed due to an error - internal failure",
+                "Uploading resources to the server finished",
+            ) > 12
+        );
+    }
+}
@@ -1,16 +1,31 @@
+use crate::analysis_context::AnalysisContext;
 use crate::cli::ErrorsSortBy;
-use crate::comparator::LogFilter;
-use crate::config::AnalyzerConfig;
+use crate::comparator::{LogFilter, glob_matches};
+use crate::config::{AnalyzerConfig, SessionAttribution, split_parallel_session_paths};
+use crate::health::{HealthSummary, LatencyBudgetCheck, format_health_text, score_health};
 use crate::parser::LogEntry;
-use crate::perf_analyzer::{OrphanOperation, analyze_performance_with_config};
+use crate::perf_analyzer::{
+    OperationPairing, TimedOperation, has_command_completion_patterns, session_keys,
+};
 use chrono::{DateTime, Local, SecondsFormat, Utc};
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::cmp::Reverse;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::Write;
 use std::sync::LazyLock;
 
+mod stream;
+
+pub use stream::{
+    CheckpointOptions, DayPattern, DayReport, StreamedErrors, analyze_errors_streaming,
+    format_day_text,
+};
+
+/// Entries per clustering shard; fixed so that merged results do not depend on the thread count
+const CLUSTER_SHARD_ENTRIES: usize = 16_384;
+
 static URL_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r#"https?://[^\s"')]+"#).expect("valid url regex"));
 static UUID_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -50,13 +65,21 @@ static LONG_NUMBER_RE: LazyLock<Regex> =
 static MULTISPACE_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\s+").expect("valid multispace regex"));
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ErrorsOptions {
     pub top_n: usize,
     pub include_warn: bool,
     pub show_sessions: bool,
     pub sort_by: ErrorsSortBy,
     pub file_count: usize,
+    /// Glob patterns of normalized messages left out of the analysis
+    pub suppressed_patterns: Vec<String>,
+    /// Embed each cluster's raw sample entry, cut to this many bytes
+    pub raw_limit: Option<usize>,
+    /// Worker threads for clustering (0 = available parallelism)
+    pub threads: usize,
+    /// Render only the health, header, and impact summary, without clusters or days
+    pub summary_only: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -68,9 +91,15 @@ pub struct ErrorAnalysisReport {
     pub warn_count: usize,
     pub unique_patterns: usize,
     pub affected_sessions_count: usize,
+    /// Entries left out because their pattern matched `suppressed_patterns`
+    pub suppressed_count: usize,
     pub clusters: Vec<ErrorClusterReport>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub longest_blocking: Option<LongestBlockingError>,
+    pub health: HealthSummary,
+    /// Per-day reports of a streaming analysis with `--daily`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<DayReport>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -82,17 +111,38 @@ pub struct ErrorClusterReport {
     pub first_timestamp: DateTime<Local>,
     pub last_timestamp: DateTime<Local>,
     pub sample_message: String,
+    /// Raw log text of the sample entry (only with `raw_limit`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_raw: Option<String>,
+    /// `-f` expression that selects the cluster's entries in `search`
+    pub suggested_filter: String,
     pub affected_sessions_count: usize,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub affected_sessions: Vec<ClusterSessionImpact>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blocking_ms: Option<i64>,
+    /// Operations in flight in the same session when the cluster's errors fired
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub during_operations: Vec<ClusterOperationLink>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterOperationLink {
+    pub op_type: String,
+    pub name: String,
+    /// Errors of the cluster logged while an operation of this type and name was in flight
+    pub count: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ClusterSessionImpact {
     pub session_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
     pub error_count: usize,
+    /// Sum of attribution weights when parallel sessions are split proportionally
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_share: Option<f64>,
     pub outcome: SessionOutcome,
     pub first_error_timestamp: DateTime<Local>,
     pub last_error_timestamp: DateTime<Local>,
@@ -112,10 +162,12 @@ pub struct LongestBlockingError {
     pub severity: String,
     pub pattern: String,
     pub session_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
     pub duration_ms: i64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ClusterAccum {
     severity: String,
     pattern: String,
@@ -124,48 +176,16 @@ struct ClusterAccum {
     first_timestamp: DateTime<Local>,
     last_timestamp: DateTime<Local>,
     sample_message: String,
+    sample_raw: Option<String>,
     session_counts: HashMap<String, usize>,
+    session_shares: HashMap<String, f64>,
     session_first_error: HashMap<String, DateTime<Local>>,
     session_last_error: HashMap<String, DateTime<Local>>,
 }
 
-#[derive(Debug, Clone)]
-struct SessionLifecycleState {
-    last_seen: DateTime<Local>,
-    orphaned: bool,
-}
-
-pub fn analyze_errors_with_config(
-    logs: &[LogEntry],
-    filter: &LogFilter,
-    config: &AnalyzerConfig,
-    options: &ErrorsOptions,
-) -> ErrorAnalysisReport {
-    let filtered_logs: Vec<&LogEntry> = logs.iter().filter(|entry| filter.matches(entry)).collect();
-    let perf_results = analyze_performance_with_config(logs, filter, None, config);
-    let session_states = build_session_lifecycle_states(&filtered_logs, &perf_results.orphans);
-    let level_filter = build_error_level_filter(options.include_warn);
-
-    let mut clusters: HashMap<(String, String), ClusterAccum> = HashMap::new();
-    let mut error_count = 0usize;
-    let mut warn_count = 0usize;
-    let mut affected_sessions: HashSet<String> = HashSet::new();
-
-    for entry in filtered_logs
-        .iter()
-        .copied()
-        .filter(|entry| level_filter.matches(entry))
-    {
-        let severity = normalized_severity(&entry.level);
-        match severity.as_str() {
-            "ERROR" => error_count += 1,
-            "WARN" => warn_count += 1,
-            _ => continue,
-        }
-
-        let pattern = normalize_message_pattern(&entry.message);
-        let key = (severity.clone(), pattern.clone());
-        let cluster = clusters.entry(key).or_insert_with(|| ClusterAccum {
+impl ClusterAccum {
+    fn new(severity: String, pattern: String, entry: &LogEntry, raw_limit: Option<usize>) -> Self {
+        Self {
             severity,
             pattern,
             count: 0,
@@ -173,70 +193,506 @@ pub fn analyze_errors_with_config(
             first_timestamp: entry.timestamp,
             last_timestamp: entry.timestamp,
             sample_message: entry.message.clone(),
+            sample_raw: raw_limit.map(|limit| entry.raw_excerpt(limit)),
             session_counts: HashMap::new(),
+            session_shares: HashMap::new(),
             session_first_error: HashMap::new(),
             session_last_error: HashMap::new(),
-        });
-
-        cluster.count += 1;
-        cluster.components.insert(entry.component.clone());
-        if entry.timestamp < cluster.first_timestamp {
-            cluster.first_timestamp = entry.timestamp;
-        }
-        if entry.timestamp > cluster.last_timestamp {
-            cluster.last_timestamp = entry.timestamp;
         }
+    }
 
-        if !entry.component_id.is_empty() {
-            affected_sessions.insert(entry.component_id.clone());
-            *cluster
+    fn record(&mut self, entry: &LogEntry, attribution: SessionAttribution) -> Vec<String> {
+        self.count += 1;
+        self.components.insert(entry.component.clone());
+        self.first_timestamp = self.first_timestamp.min(entry.timestamp);
+        self.last_timestamp = self.last_timestamp.max(entry.timestamp);
+
+        let mut sessions = Vec::new();
+        for (session_path, weight) in attribution.attribute(&entry.component_id) {
+            *self
                 .session_counts
-                .entry(entry.component_id.clone())
+                .entry(session_path.to_string())
                 .or_insert(0) += 1;
-            cluster
-                .session_first_error
-                .entry(entry.component_id.clone())
-                .and_modify(|ts| {
-                    if entry.timestamp < *ts {
-                        *ts = entry.timestamp;
-                    }
-                })
+            *self
+                .session_shares
+                .entry(session_path.to_string())
+                .or_insert(0.0) += weight;
+            self.session_first_error
+                .entry(session_path.to_string())
+                .and_modify(|ts| *ts = (*ts).min(entry.timestamp))
                 .or_insert(entry.timestamp);
-            cluster
-                .session_last_error
-                .entry(entry.component_id.clone())
-                .and_modify(|ts| {
-                    if entry.timestamp > *ts {
-                        *ts = entry.timestamp;
-                    }
+            self.session_last_error
+                .entry(session_path.to_string())
+                .and_modify(|ts| *ts = (*ts).max(entry.timestamp))
+                .or_insert(entry.timestamp);
+            sessions.push(session_path.to_string());
+        }
+        sessions
+    }
+
+    /// Fold in the accumulator of a later shard; the sample stays with the earliest entry
+    fn merge(&mut self, later: ClusterAccum) {
+        self.count += later.count;
+        self.components.extend(later.components);
+        self.first_timestamp = self.first_timestamp.min(later.first_timestamp);
+        self.last_timestamp = self.last_timestamp.max(later.last_timestamp);
+        for (session, count) in later.session_counts {
+            *self.session_counts.entry(session).or_insert(0) += count;
+        }
+        for (session, share) in later.session_shares {
+            *self.session_shares.entry(session).or_insert(0.0) += share;
+        }
+        for (session, ts) in later.session_first_error {
+            self.session_first_error
+                .entry(session)
+                .and_modify(|current| *current = (*current).min(ts))
+                .or_insert(ts);
+        }
+        for (session, ts) in later.session_last_error {
+            self.session_last_error
+                .entry(session)
+                .and_modify(|current| *current = (*current).max(ts))
+                .or_insert(ts);
+        }
+    }
+}
+
+/// Clusters and counters of one contiguous run of entries
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct ShardAccum {
+    #[serde(with = "stream::keyed_by_pattern")]
+    clusters: HashMap<(String, String), ClusterAccum>,
+    error_count: usize,
+    warn_count: usize,
+    suppressed_count: usize,
+    affected_sessions: HashSet<String>,
+}
+
+impl ShardAccum {
+    /// Clusters one entry that already passed the caller's filter; returns the cluster key
+    /// when the entry was counted
+    fn observe(
+        &mut self,
+        entry: &LogEntry,
+        level_filter: &LogFilter,
+        attribution: SessionAttribution,
+        options: &ErrorsOptions,
+    ) -> Option<(String, String)> {
+        if !level_filter.matches(entry) {
+            return None;
+        }
+        let severity = normalized_severity(&entry.level);
+        if severity != "ERROR" && severity != "WARN" {
+            return None;
+        }
+
+        let pattern = normalize_message_pattern(&entry.message);
+        if options
+            .suppressed_patterns
+            .iter()
+            .any(|suppressed| glob_matches(suppressed, &pattern))
+        {
+            self.suppressed_count += 1;
+            return None;
+        }
+        if severity == "ERROR" {
+            self.error_count += 1;
+        } else {
+            self.warn_count += 1;
+        }
+        let key = (severity, pattern);
+        let cluster = self.clusters.entry(key.clone()).or_insert_with(|| {
+            ClusterAccum::new(key.0.clone(), key.1.clone(), entry, options.raw_limit)
+        });
+        self.affected_sessions
+            .extend(cluster.record(entry, attribution));
+        Some(key)
+    }
+
+    fn merge(&mut self, later: ShardAccum) {
+        self.error_count += later.error_count;
+        self.warn_count += later.warn_count;
+        self.suppressed_count += later.suppressed_count;
+        self.affected_sessions.extend(later.affected_sessions);
+        for (key, cluster) in later.clusters {
+            match self.clusters.get_mut(&key) {
+                Some(existing) => existing.merge(cluster),
+                None => {
+                    self.clusters.insert(key, cluster);
+                }
+            }
+        }
+    }
+}
+
+fn accumulate_shard(
+    entries: &[&LogEntry],
+    level_filter: &LogFilter,
+    attribution: SessionAttribution,
+    options: &ErrorsOptions,
+) -> ShardAccum {
+    let mut shard = ShardAccum::default();
+    for entry in entries {
+        shard.observe(entry, level_filter, attribution, options);
+    }
+    shard
+}
+
+/// Cluster entries in fixed-size shards spread over worker threads, then merge the
+/// shards in input order so the result does not depend on the thread count
+fn accumulate_clusters(
+    entries: &[&LogEntry],
+    level_filter: &LogFilter,
+    attribution: SessionAttribution,
+    options: &ErrorsOptions,
+) -> ShardAccum {
+    let shards: Vec<&[&LogEntry]> = entries.chunks(CLUSTER_SHARD_ENTRIES).collect();
+    let threads = match options.threads {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .min(shards.len());
+
+    let accums: Vec<ShardAccum> = if threads <= 1 {
+        shards
+            .iter()
+            .map(|shard| accumulate_shard(shard, level_filter, attribution, options))
+            .collect()
+    } else {
+        let per_worker = shards.len().div_ceil(threads);
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = shards
+                .chunks(per_worker)
+                .map(|group| {
+                    scope.spawn(move || {
+                        group
+                            .iter()
+                            .map(|shard| {
+                                accumulate_shard(shard, level_filter, attribution, options)
+                            })
+                            .collect::<Vec<_>>()
+                    })
                 })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("errors clustering worker panicked"))
+                .collect()
+        })
+    };
+
+    let mut merged = ShardAccum::default();
+    for shard in accums {
+        merged.merge(shard);
+    }
+    merged
+}
+
+#[derive(Debug, Clone)]
+struct SessionLifecycleState {
+    last_seen: DateTime<Local>,
+    orphaned: bool,
+}
+
+/// `(severity, pattern)` of a cluster
+type ClusterKey = (String, String);
+
+/// A clustered error that an operation still pending in its session may turn out to
+/// have been logged during
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InFlightError {
+    timestamp: DateTime<Local>,
+    sessions: Vec<String>,
+    cluster: ClusterKey,
+    /// `(op_type, name)` of the operations already credited with this error
+    credited: Vec<(String, String)>,
+}
+
+/// Sessions, health inputs, and operation pairing of an errors analysis, fed one entry
+/// at a time. The batch and streaming analyses both report from it, so they agree on
+/// session outcomes, health, and `during_operations`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RunAccum {
+    /// Entries that passed the filter
+    entries: usize,
+    /// ERROR entries among them
+    error_entries: usize,
+    /// Time of the latest entry per session path
+    sessions: HashMap<String, DateTime<Local>>,
+    pairing: OperationPairing,
+    /// Durations of completed operations with a latency budget, per `(op_type, name)`
+    #[serde(with = "stream::keyed_by_pattern")]
+    budget_durations: HashMap<(String, String), Vec<i64>>,
+    in_flight_errors: Vec<InFlightError>,
+    #[serde(with = "stream::keyed_by_pattern")]
+    during_operations: HashMap<ClusterKey, Vec<ClusterOperationLink>>,
+}
+
+impl RunAccum {
+    fn new(commands_complete: bool) -> Self {
+        Self {
+            pairing: OperationPairing::new(commands_complete),
+            ..Self::default()
+        }
+    }
+
+    /// Notes command completion markers of a log read as a stream; call for every
+    /// entry, filtered out or not
+    fn watch_command_completion(&mut self, entry: &LogEntry, config: &AnalyzerConfig) {
+        self.pairing.watch_command_completion(entry, &config.perf);
+    }
+
+    /// Counts one entry that passed the filter. `cluster` gives the cluster key of an
+    /// error entry and is only asked for while an operation is pending in its session.
+    fn observe(
+        &mut self,
+        entry: &LogEntry,
+        cluster: impl FnOnce() -> Option<ClusterKey>,
+        config: &AnalyzerConfig,
+    ) {
+        self.entries += 1;
+        if entry.level.eq_ignore_ascii_case("ERROR") {
+            self.error_entries += 1;
+        }
+        for session_path in split_parallel_session_paths(&entry.component_id) {
+            self.sessions
+                .entry(session_path.to_string())
+                .and_modify(|last_seen| *last_seen = (*last_seen).max(entry.timestamp))
                 .or_insert(entry.timestamp);
         }
+
+        let sessions = session_keys(&entry.component_id);
+        if self.pairing.pending().any(|(_, side)| {
+            session_keys(side.component_id().unwrap_or(""))
+                .iter()
+                .any(|key| sessions.contains(key))
+        }) && let Some(cluster) = cluster()
+        {
+            self.in_flight_errors.push(InFlightError {
+                timestamp: entry.timestamp,
+                sessions: sessions.iter().map(ToString::to_string).collect(),
+                cluster,
+                credited: Vec::new(),
+            });
+        }
+
+        // Negative durations are set aside by perf analysis, so they neither count
+        // toward budgets nor link errors
+        if let Some(op) = self.pairing.observe(entry, None, &config.perf)
+            && op.suspect.is_none()
+        {
+            if config.perf.latency_budgets_ms.contains_key(&op.name) {
+                self.budget_durations
+                    .entry((op.op_type.clone(), op.name.clone()))
+                    .or_default()
+                    .push(op.duration_ms);
+            }
+            self.link_errors(&op);
+        }
+    }
+
+    /// Credits `op` with the in-flight errors of its session logged within its span, then
+    /// drops the errors no pending operation can claim any more
+    fn link_errors(&mut self, op: &TimedOperation) {
+        if self.in_flight_errors.is_empty() {
+            return;
+        }
+        let op_sessions = session_keys(op.component_id.as_deref().unwrap_or(""));
+        let credit = (op.op_type.clone(), op.name.clone());
+        for error in &mut self.in_flight_errors {
+            if error.timestamp < op.start_time
+                || error.timestamp > op.end_time
+                || !error
+                    .sessions
+                    .iter()
+                    .any(|session| op_sessions.contains(&session.as_str()))
+                || error.credited.contains(&credit)
+            {
+                continue;
+            }
+            error.credited.push(credit.clone());
+            let links = self
+                .during_operations
+                .entry(error.cluster.clone())
+                .or_default();
+            match links
+                .iter_mut()
+                .find(|link| link.op_type == op.op_type && link.name == op.name)
+            {
+                Some(link) => link.count += 1,
+                None => links.push(ClusterOperationLink {
+                    op_type: op.op_type.clone(),
+                    name: op.name.clone(),
+                    count: 1,
+                }),
+            }
+        }
+
+        let pending: Vec<(DateTime<Local>, Vec<&str>)> = self
+            .pairing
+            .pending()
+            .map(|(_, side)| {
+                let sessions = session_keys(side.component_id().unwrap_or(""));
+                (side.timestamp(), sessions)
+            })
+            .collect();
+        self.in_flight_errors.retain(|error| {
+            pending.iter().any(|(start, sessions)| {
+                *start <= error.timestamp
+                    && error
+                        .sessions
+                        .iter()
+                        .any(|session| sessions.contains(&session.as_str()))
+            })
+        });
     }
 
-    let mut longest_blocking: Option<LongestBlockingError> = None;
-    let mut finalized_clusters: Vec<ErrorClusterReport> = clusters
-        .into_values()
-        .map(|accum| finalize_cluster(accum, &session_states, &mut longest_blocking))
-        .collect();
+    fn into_report(
+        self,
+        shard: ShardAccum,
+        config: &AnalyzerConfig,
+        options: &ErrorsOptions,
+    ) -> ErrorAnalysisReport {
+        let orphans = self.pairing.into_orphans();
+        let orphaned: HashSet<&str> = orphans
+            .iter()
+            .filter_map(|orphan| orphan.component_id.as_deref())
+            .flat_map(split_parallel_session_paths)
+            .filter(|path| self.sessions.contains_key(*path))
+            .collect();
+        let session_states: HashMap<String, SessionLifecycleState> = self
+            .sessions
+            .iter()
+            .map(|(path, &last_seen)| {
+                let state = SessionLifecycleState {
+                    last_seen,
+                    orphaned: orphaned.contains(path.as_str()),
+                };
+                (path.clone(), state)
+            })
+            .collect();
 
-    sort_clusters(&mut finalized_clusters, options.sort_by);
+        let attribution = config.sessions.parallel_attribution;
+        let mut during_operations = self.during_operations;
+        let mut longest_blocking: Option<LongestBlockingError> = None;
+        let mut clusters: Vec<ErrorClusterReport> = shard
+            .clusters
+            .into_iter()
+            .map(|(key, accum)| {
+                let mut cluster =
+                    finalize_cluster(accum, &session_states, attribution, &mut longest_blocking);
+                cluster.during_operations = during_operations.remove(&key).unwrap_or_default();
+                cluster.during_operations.sort_by(|a, b| {
+                    b.count
+                        .cmp(&a.count)
+                        .then_with(|| a.op_type.cmp(&b.op_type))
+                        .then_with(|| a.name.cmp(&b.name))
+                });
+                cluster
+            })
+            .collect();
+        sort_clusters(&mut clusters, options.sort_by);
 
-    ErrorAnalysisReport {
-        file_count: options.file_count,
-        include_warn: options.include_warn,
-        total_entries: error_count + warn_count,
-        error_count,
-        warn_count,
-        unique_patterns: finalized_clusters.len(),
-        affected_sessions_count: affected_sessions.len(),
-        clusters: finalized_clusters,
-        longest_blocking,
+        let latency_budgets = config
+            .perf
+            .latency_budgets_ms
+            .iter()
+            .map(|(name, &budget_ms)| {
+                let p95_ms = self
+                    .budget_durations
+                    .iter()
+                    .filter(|((_, op_name), _)| op_name == name)
+                    .map(|(_, durations)| {
+                        let mut durations = durations.clone();
+                        durations.sort_unstable();
+                        durations[durations.len() * 95 / 100]
+                    })
+                    .max();
+                LatencyBudgetCheck::new(name, budget_ms, p95_ms)
+            })
+            .collect();
+        let health = score_health(
+            self.entries,
+            self.error_entries,
+            self.sessions.len(),
+            orphaned.len(),
+            latency_budgets,
+        );
+
+        ErrorAnalysisReport {
+            file_count: options.file_count,
+            include_warn: options.include_warn,
+            total_entries: shard.error_count + shard.warn_count,
+            error_count: shard.error_count,
+            warn_count: shard.warn_count,
+            unique_patterns: clusters.len(),
+            affected_sessions_count: shard.affected_sessions.len(),
+            suppressed_count: shard.suppressed_count,
+            clusters,
+            longest_blocking,
+            health,
+            days: Vec::new(),
+        }
+    }
+}
+
+pub fn analyze_errors_with_config(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    config: &AnalyzerConfig,
+    options: &ErrorsOptions,
+) -> ErrorAnalysisReport {
+    analyze_errors_in_context(&AnalysisContext::new(logs, config), filter, options)
+}
+
+/// Like [`analyze_errors_with_config`], resolving session display names from the
+/// context's shared session hierarchy
+pub fn analyze_errors_in_context(
+    context: &AnalysisContext,
+    filter: &LogFilter,
+    options: &ErrorsOptions,
+) -> ErrorAnalysisReport {
+    let (logs, config) = (context.logs(), context.config());
+    let filtered_logs: Vec<&LogEntry> = logs.iter().filter(|entry| filter.matches(entry)).collect();
+    let level_filter = build_error_level_filter(options.include_warn);
+    let attribution = config.sessions.parallel_attribution;
+
+    let shard = accumulate_clusters(&filtered_logs, &level_filter, attribution, options);
+    let mut run = RunAccum::new(has_command_completion_patterns(logs, &config.perf));
+    for entry in &filtered_logs {
+        let cluster = || {
+            let key = (
+                normalized_severity(&entry.level),
+                normalize_message_pattern(&entry.message),
+            );
+            (level_filter.matches(entry) && shard.clusters.contains_key(&key)).then_some(key)
+        };
+        run.observe(entry, cluster, config);
     }
+    let mut report = run.into_report(shard, config, options);
+
+    if config.has_session_display_names() {
+        let session_insights = context.sessions();
+        let display_name = |path: &str| {
+            let rendered = session_insights.display_path(path);
+            (rendered != path).then_some(rendered)
+        };
+        for cluster in &mut report.clusters {
+            for session in &mut cluster.affected_sessions {
+                session.display_name = display_name(&session.session_path);
+            }
+        }
+        if let Some(longest) = report.longest_blocking.as_mut() {
+            longest.display_name = display_name(&longest.session_path);
+        }
+    }
+
+    report
 }
 
 pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions) -> String {
-    let mut out = String::new();
+    let mut out = format_health_text(&report.health);
+    out.push('\n');
     let header_label = if report.warn_count > 0 {
         "ERRORS/WARNS"
     } else {
@@ -261,7 +717,11 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
         return out;
     }
 
-    let display_limit = displayed_cluster_count(report, options);
+    let display_limit = if options.summary_only {
+        0
+    } else {
+        displayed_cluster_count(report, options)
+    };
     let show_date = spans_multiple_dates(report);
     out.push('\n');
 
@@ -288,6 +748,7 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
             format_timestamp(cluster.last_timestamp, show_date)
         );
         let _ = writeln!(out, "     Sample: {}", cluster.sample_message);
+        let _ = writeln!(out, "     Filter: -f '{}'", cluster.suggested_filter);
         if let Some(blocking_ms) = cluster.blocking_ms
             && blocking_ms > 0
         {
@@ -297,6 +758,24 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
                 format_duration_approx(blocking_ms)
             );
         }
+        if !cluster.during_operations.is_empty() {
+            let links: Vec<String> = cluster
+                .during_operations
+                .iter()
+                .take(5)
+                .map(|link| format!("{} {} ×{}", link.op_type, link.name, link.count))
+                .collect();
+            let _ = writeln!(
+                out,
+                "     During: {}{}",
+                links.join(", "),
+                if cluster.during_operations.len() > 5 {
+                    format!(" (+{} more)", cluster.during_operations.len() - 5)
+                } else {
+                    String::new()
+                }
+            );
+        }
 
         if options.show_sessions {
             if cluster.affected_sessions.is_empty() {
@@ -313,9 +792,16 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
                 for session in &cluster.affected_sessions {
                     let _ = writeln!(
                         out,
-                        "       - {}  ×{}  {}{}",
-                        session.session_path,
+                        "       - {}  ×{}{}  {}{}",
+                        session
+                            .display_name
+                            .as_deref()
+                            .unwrap_or(&session.session_path),
                         session.error_count,
+                        session
+                            .error_share
+                            .map(|share| format!(" (share {share:.2})"))
+                            .unwrap_or_default(),
                         session.outcome.as_label(),
                         session
                             .blocking_ms
@@ -332,7 +818,7 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
         }
     }
 
-    if display_limit < report.clusters.len() {
+    if !options.summary_only && display_limit < report.clusters.len() {
         let _ = writeln!(
             out,
             "\n... {} more cluster{} hidden by --top-n {}",
@@ -346,7 +832,9 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
         );
     }
 
-    out.push('\n');
+    if !options.summary_only {
+        out.push('\n');
+    }
     let _ = writeln!(out, "Impact summary");
     let _ = writeln!(out, "  Total errors: {}", report.error_count);
     if report.include_warn {
@@ -358,6 +846,13 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
         "  Affected sessions: {}",
         report.affected_sessions_count
     );
+    if report.suppressed_count > 0 {
+        let _ = writeln!(
+            out,
+            "  Suppressed by ignore rules: {}",
+            report.suppressed_count
+        );
+    }
     if let Some(longest) = &report.longest_blocking {
         let _ = writeln!(
             out,
@@ -366,7 +861,14 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
             longest.severity,
             longest.pattern
         );
-        let _ = writeln!(out, "  Session: {}", longest.session_path);
+        let _ = writeln!(
+            out,
+            "  Session: {}",
+            longest
+                .display_name
+                .as_deref()
+                .unwrap_or(&longest.session_path)
+        );
     } else {
         let _ = writeln!(out, "  Longest blocking error: n/a");
     }
@@ -376,8 +878,9 @@ pub fn format_errors_text(report: &ErrorAnalysisReport, options: &ErrorsOptions)
 
 pub fn format_errors_json(report: &ErrorAnalysisReport, options: &ErrorsOptions) -> String {
     let display_limit = displayed_cluster_count(report, options);
-    serde_json::to_string_pretty(&json!({
+    let mut document = json!({
         "errors": {
+            "health": report.health,
             "summary": {
                 "file_count": report.file_count,
                 "include_warn": report.include_warn,
@@ -386,24 +889,36 @@ pub fn format_errors_json(report: &ErrorAnalysisReport, options: &ErrorsOptions)
                 "warn_count": report.warn_count,
                 "unique_patterns": report.unique_patterns,
                 "affected_sessions_count": report.affected_sessions_count,
+                "suppressed_count": report.suppressed_count,
                 "longest_blocking": report.longest_blocking,
             },
             "options": {
                 "top_n": options.top_n,
                 "show_sessions": options.show_sessions,
                 "sort_by": format!("{:?}", options.sort_by).to_ascii_lowercase(),
+                "summary_only": options.summary_only,
             },
             "clusters_total": report.clusters.len(),
             "clusters_displayed": display_limit,
             "clusters": report.clusters.iter().take(display_limit).collect::<Vec<_>>(),
         }
-    }))
-    .unwrap_or_else(|_| "{\"errors\":{\"error\":\"failed to serialize errors output\"}}".into())
+    });
+    if options.summary_only {
+        if let Some(errors) = document["errors"].as_object_mut() {
+            errors.remove("clusters");
+            errors.insert("clusters_displayed".into(), json!(0));
+        }
+    } else if !report.days.is_empty() {
+        document["errors"]["days"] = json!(report.days);
+    }
+    serde_json::to_string_pretty(&document)
+        .unwrap_or_else(|_| "{\"errors\":{\"error\":\"failed to serialize errors output\"}}".into())
 }
 
 fn finalize_cluster(
     accum: ClusterAccum,
     session_states: &HashMap<String, SessionLifecycleState>,
+    attribution: SessionAttribution,
     longest_blocking: &mut Option<LongestBlockingError>,
 ) -> ErrorClusterReport {
     let mut affected_sessions = Vec::with_capacity(accum.session_counts.len());
@@ -443,22 +958,36 @@ fn finalize_cluster(
 
         if let Some(ms) = session_blocking_ms {
             blocking_ms = Some(blocking_ms.map_or(ms, |current: i64| current.max(ms)));
-            if longest_blocking
-                .as_ref()
-                .is_none_or(|current| ms > current.duration_ms)
-            {
+            // Ties go to the lexicographically first cluster/session so the pick is stable
+            let candidate = (Reverse(ms), &accum.severity, &accum.pattern, &session_path);
+            if longest_blocking.as_ref().is_none_or(|current| {
+                candidate
+                    < (
+                        Reverse(current.duration_ms),
+                        &current.severity,
+                        &current.pattern,
+                        &current.session_path,
+                    )
+            }) {
                 *longest_blocking = Some(LongestBlockingError {
                     severity: accum.severity.clone(),
                     pattern: accum.pattern.clone(),
                     session_path: session_path.clone(),
+                    display_name: None,
                     duration_ms: ms,
                 });
             }
         }
 
+        let error_share = (attribution == SessionAttribution::Proportional)
+            .then(|| accum.session_shares.get(&session_path).copied())
+            .flatten();
+
         affected_sessions.push(ClusterSessionImpact {
             session_path,
+            display_name: None,
             error_count,
+            error_share,
             outcome,
             first_error_timestamp,
             last_error_timestamp,
@@ -473,7 +1002,7 @@ fn finalize_cluster(
             .then_with(|| a.session_path.cmp(&b.session_path))
     });
 
-    ErrorClusterReport {
+    let mut cluster = ErrorClusterReport {
         severity: accum.severity,
         pattern: accum.pattern,
         count: accum.count,
@@ -481,47 +1010,43 @@ fn finalize_cluster(
         first_timestamp: accum.first_timestamp,
         last_timestamp: accum.last_timestamp,
         sample_message: accum.sample_message,
+        sample_raw: accum.sample_raw,
+        suggested_filter: String::new(),
         affected_sessions_count: affected_sessions.len(),
         affected_sessions,
         blocking_ms,
-    }
+        during_operations: Vec::new(),
+    };
+    cluster.suggested_filter = suggested_filter(&cluster);
+    cluster
 }
 
-fn build_session_lifecycle_states(
-    logs: &[&LogEntry],
-    orphans: &[OrphanOperation],
-) -> HashMap<String, SessionLifecycleState> {
-    let mut states: HashMap<String, SessionLifecycleState> = HashMap::new();
-
-    for entry in logs.iter().copied() {
-        if !entry.component_id.is_empty() {
-            states
-                .entry(entry.component_id.clone())
-                .and_modify(|state| {
-                    if entry.timestamp > state.last_seen {
-                        state.last_seen = entry.timestamp;
-                    }
-                })
-                .or_insert(SessionLifecycleState {
-                    last_seen: entry.timestamp,
-                    orphaned: false,
-                });
-        }
+/// Filter expression that narrows logs to a cluster: its level, its component when
+/// unique, and the longest literal token of the normalized pattern. Terms containing
+/// quotes are left out so the expression can be pasted inside `-f '...'`
+fn suggested_filter(cluster: &ErrorClusterReport) -> String {
+    let mut terms = vec![format!("level:{}", cluster.severity)];
+    if let [component] = cluster.components.as_slice()
+        && !component.contains(char::is_whitespace)
+        && !component.contains(['"', '\''])
+    {
+        terms.push(format!("component:{component}"));
     }
 
-    for orphan in orphans {
-        let Some(component_id) = orphan.component_id.as_ref() else {
-            continue;
-        };
-        states
-            .entry(component_id.clone())
-            .and_modify(|state| state.orphaned = true);
+    let token = cluster
+        .pattern
+        .split_whitespace()
+        .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|token| !token.contains("...") && !token.contains(['"', '\'', ':']))
+        .max_by_key(|token| token.len());
+    if let Some(token) = token.filter(|token| token.len() >= 3) {
+        terms.push(format!("text:{token}"));
     }
 
-    states
+    terms.join(" ")
 }
 
-fn build_error_level_filter(include_warn: bool) -> LogFilter {
+pub(crate) fn build_error_level_filter(include_warn: bool) -> LogFilter {
     let filter = LogFilter::new().with_level(Some("ERROR"));
     if include_warn {
         filter.with_level(Some("WARN"))
@@ -530,7 +1055,7 @@ fn build_error_level_filter(include_warn: bool) -> LogFilter {
     }
 }
 
-fn normalized_severity(level: &str) -> String {
+pub(crate) fn normalized_severity(level: &str) -> String {
     let upper = level.trim().to_ascii_uppercase();
     if upper.starts_with("WARN") {
         "WARN".to_string()
@@ -539,7 +1064,8 @@ fn normalized_severity(level: &str) -> String {
     }
 }
 
-fn normalize_message_pattern(message: &str) -> String {
+/// Message with volatile parts (ids, URLs, timestamps, numbers) masked for clustering
+pub fn normalize_message_pattern(message: &str) -> String {
     let mut normalized = message.replace('\n', " ");
     normalized = URL_RE.replace_all(&normalized, "...").into_owned();
     normalized = UUID_RE.replace_all(&normalized, "...").into_owned();
@@ -570,12 +1096,14 @@ fn sort_clusters(clusters: &mut [ErrorClusterReport], sort_by: ErrorsSortBy) {
             .cmp(&a.count)
             .then_with(|| b.affected_sessions_count.cmp(&a.affected_sessions_count))
             .then_with(|| b.last_timestamp.cmp(&a.last_timestamp))
-            .then_with(|| a.pattern.cmp(&b.pattern)),
+            .then_with(|| a.pattern.cmp(&b.pattern))
+            .then_with(|| a.severity.cmp(&b.severity)),
         ErrorsSortBy::Time => b
             .last_timestamp
             .cmp(&a.last_timestamp)
             .then_with(|| b.count.cmp(&a.count))
-            .then_with(|| a.pattern.cmp(&b.pattern)),
+            .then_with(|| a.pattern.cmp(&b.pattern))
+            .then_with(|| a.severity.cmp(&b.severity)),
         ErrorsSortBy::Impact => b
             .affected_sessions_count
             .cmp(&a.affected_sessions_count)
@@ -586,11 +1114,15 @@ fn sort_clusters(clusters: &mut [ErrorClusterReport], sort_by: ErrorsSortBy) {
             })
             .then_with(|| b.count.cmp(&a.count))
             .then_with(|| b.last_timestamp.cmp(&a.last_timestamp))
-            .then_with(|| a.pattern.cmp(&b.pattern)),
+            .then_with(|| a.pattern.cmp(&b.pattern))
+            .then_with(|| a.severity.cmp(&b.severity)),
     });
 }
 
-fn displayed_cluster_count(report: &ErrorAnalysisReport, options: &ErrorsOptions) -> usize {
+pub(crate) fn displayed_cluster_count(
+    report: &ErrorAnalysisReport,
+    options: &ErrorsOptions,
+) -> usize {
     if options.top_n == 0 {
         report.clusters.len()
     } else {
@@ -653,7 +1185,7 @@ fn format_duration_approx(duration_ms: i64) -> String {
 }
 
 impl SessionOutcome {
-    fn as_label(self) -> &'static str {
+    pub(crate) fn as_label(self) -> &'static str {
         match self {
             SessionOutcome::Completed => "completed",
             SessionOutcome::Orphaned => "orphaned",
@@ -681,4 +1213,98 @@ mod tests {
         let normalized = normalize_message_pattern(input);
         assert_eq!(normalized, "Request \"check\" [...] failed");
     }
+
+    #[test]
+    fn sharded_clustering_matches_sequential() {
+        let logs: Vec<LogEntry> = (0..CLUSTER_SHARD_ENTRIES * 2 + 17)
+            .map(|i| {
+                let line = format!(
+                    "core (manager-1/eyes-{}) | 2026-01-01T00:00:{:02}.{:03}Z [{}] Render with id \"{i}\" failed in step {}",
+                    i % 7,
+                    (i / 1000) % 60,
+                    i % 1000,
+                    if i % 3 == 0 { "WARN " } else { "ERROR" },
+                    i % 5
+                );
+                crate::parser::parse_log_entry(&line, i + 1).expect("line parses")
+            })
+            .collect();
+        let options = |threads| ErrorsOptions {
+            top_n: 0,
+            include_warn: true,
+            show_sessions: true,
+            sort_by: ErrorsSortBy::Count,
+            file_count: 1,
+            suppressed_patterns: Vec::new(),
+            raw_limit: None,
+            threads,
+            summary_only: false,
+        };
+        let config = crate::config::default_config();
+        let filter = LogFilter::new();
+
+        let sequential = analyze_errors_with_config(&logs, &filter, config, &options(1));
+        let sharded = analyze_errors_with_config(&logs, &filter, config, &options(4));
+        assert_eq!(sequential.clusters.len(), 10);
+        assert_eq!(
+            format_errors_json(&sequential, &options(1)),
+            format_errors_json(&sharded, &options(4))
+        );
+    }
+
+    #[test]
+    fn streaming_matches_batch() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("run.log");
+        std::fs::write(
+            &path,
+            concat!(
+                "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--a] will be sent with body {}\n",
+                "core (manager-1/eyes-1) | 2026-01-01T00:00:00.500Z [ERROR] Render failed\n",
+                "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--a] finished successfully with body {\"statusCode\":200}\n",
+                "core (manager-1/eyes-2) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--b] will be sent with body {}\n",
+                "core (manager-1/eyes-2) | 2026-01-01T00:00:03.000Z [ERROR] Render failed\n",
+                "core (manager-1/eyes-3) | 2026-01-01T00:00:04.000Z [WARN ] Slow upload\n",
+            ),
+        )
+        .expect("write log");
+        let mut config = crate::config::load_builtin_template("eyes").expect("eyes preset");
+        config.perf.latency_budgets_ms = [("check".to_string(), 500)].into_iter().collect();
+        let options = ErrorsOptions {
+            top_n: 0,
+            include_warn: true,
+            show_sessions: true,
+            sort_by: ErrorsSortBy::Count,
+            file_count: 1,
+            suppressed_patterns: Vec::new(),
+            raw_limit: None,
+            threads: 1,
+            summary_only: false,
+        };
+        let filter = LogFilter::new();
+
+        let logs = crate::parser::parse_log_file_with_config(&path, &config).expect("log parses");
+        let batch = analyze_errors_with_config(&logs, &filter, &config, &options);
+        let mut streamed = analyze_errors_streaming(
+            &[path],
+            &config,
+            &filter,
+            &options,
+            None,
+            &crate::diagnostics::WarningLog::default(),
+            |_| {},
+        )
+        .expect("log streams")
+        .report;
+        streamed.days.clear();
+
+        assert_eq!(batch.health.orphaned_sessions, 1);
+        assert_eq!(batch.health.latency_budgets[0].p95_ms, Some(1000));
+        assert_eq!(batch.clusters[0].during_operations.len(), 1);
+        assert_eq!(batch.clusters[0].during_operations[0].count, 1);
+        assert_eq!(
+            format_errors_json(&batch, &options),
+            format_errors_json(&streamed, &options)
+        );
+    }
 }
@@ -0,0 +1,407 @@
+use super::{ErrorAnalysisReport, ErrorsOptions, RunAccum, ShardAccum, build_error_level_filter};
+use crate::comparator::LogFilter;
+use crate::config::AnalyzerConfig;
+use crate::diagnostics::{WarningCode, WarningLog};
+use crate::output::{Compression, OutputFile};
+use crate::parser::{LogEntry, LogEntryReader, ReadPosition};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write as _};
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the checkpoint layout changes
+const CHECKPOINT_VERSION: u32 = 2;
+/// Characters of a file's first line kept to recognize it after rotation renames it
+const FILE_HEAD_CHARS: usize = 200;
+/// Patterns listed per day report
+const DAY_TOP_PATTERNS: usize = 3;
+
+/// Where and how often a streaming analysis saves its state
+#[derive(Debug, Clone)]
+pub struct CheckpointOptions {
+    pub path: PathBuf,
+    /// Entries read between saves; the state is also saved at every day boundary
+    pub every_entries: usize,
+    /// Filter expression the analysis runs with; a checkpoint only resumes under the same one
+    pub filter_expression: Option<String>,
+}
+
+/// Errors and warnings of one calendar day (local time)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayReport {
+    pub date: NaiveDate,
+    /// `false` for the last day read so far, which later entries may extend
+    pub complete: bool,
+    pub error_count: usize,
+    pub warn_count: usize,
+    pub unique_patterns: usize,
+    /// Patterns not seen on any earlier day
+    pub new_patterns: usize,
+    /// Most frequent patterns of the day
+    pub top_patterns: Vec<DayPattern>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayPattern {
+    pub severity: String,
+    pub pattern: String,
+    pub count: usize,
+}
+
+/// Result of [`analyze_errors_streaming`]
+#[derive(Debug, Clone)]
+pub struct StreamedErrors {
+    pub report: ErrorAnalysisReport,
+    /// Entries restored from the checkpoint instead of being read again
+    pub resumed_entries: usize,
+}
+
+/// Serializes maps keyed by `(severity, pattern)` as `[severity, pattern, value]` lists,
+/// since JSON object keys must be strings
+pub(super) mod keyed_by_pattern {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<S: Serializer, V: Serialize>(
+        map: &HashMap<(String, String), V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            map.iter()
+                .map(|((severity, pattern), value)| (severity, pattern, value)),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, V: Deserialize<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<(String, String), V>, D::Error> {
+        let list: Vec<(String, String, V)> = Vec::deserialize(deserializer)?;
+        Ok(list
+            .into_iter()
+            .map(|(severity, pattern, value)| ((severity, pattern), value))
+            .collect())
+    }
+}
+
+/// Read position in one input file, recognized by its first line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FileCursor {
+    /// Path the file was last read from
+    path: String,
+    head: String,
+    position: ReadPosition,
+}
+
+/// Counters of the day currently being read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DayAccum {
+    date: NaiveDate,
+    error_count: usize,
+    warn_count: usize,
+    new_patterns: usize,
+    #[serde(with = "keyed_by_pattern")]
+    patterns: HashMap<(String, String), usize>,
+}
+
+impl DayAccum {
+    fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            error_count: 0,
+            warn_count: 0,
+            new_patterns: 0,
+            patterns: HashMap::new(),
+        }
+    }
+
+    fn report(&self, complete: bool) -> DayReport {
+        let mut top_patterns: Vec<DayPattern> = self
+            .patterns
+            .iter()
+            .map(|((severity, pattern), &count)| DayPattern {
+                severity: severity.clone(),
+                pattern: pattern.clone(),
+                count,
+            })
+            .collect();
+        top_patterns.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.severity.cmp(&b.severity))
+                .then_with(|| a.pattern.cmp(&b.pattern))
+        });
+        top_patterns.truncate(DAY_TOP_PATTERNS);
+        DayReport {
+            date: self.date,
+            complete,
+            error_count: self.error_count,
+            warn_count: self.warn_count,
+            unique_patterns: self.patterns.len(),
+            new_patterns: self.new_patterns,
+            top_patterns,
+        }
+    }
+}
+
+/// Everything a streaming analysis knows after the entries read so far; saved as the checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StreamState {
+    version: u32,
+    /// Options that change what gets counted; resuming under other options is refused
+    settings: String,
+    files: Vec<FileCursor>,
+    clusters: ShardAccum,
+    run: RunAccum,
+    day: Option<DayAccum>,
+    days: Vec<DayReport>,
+}
+
+impl StreamState {
+    fn new(settings: String) -> Self {
+        Self {
+            version: CHECKPOINT_VERSION,
+            settings,
+            files: Vec::new(),
+            clusters: ShardAccum::default(),
+            // Command completion markers are noted as the log is read
+            run: RunAccum::new(false),
+            day: None,
+            days: Vec::new(),
+        }
+    }
+
+    fn load(path: &Path, settings: &str) -> Result<Option<Self>, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read checkpoint '{}': {}", path.display(), e))?;
+        let state: StreamState = serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid checkpoint '{}': {}", path.display(), e))?;
+        if state.version != CHECKPOINT_VERSION {
+            return Err(format!(
+                "Checkpoint '{}' has version {} (expected {}); delete it to start over",
+                path.display(),
+                state.version,
+                CHECKPOINT_VERSION
+            )
+            .into());
+        }
+        if state.settings != settings {
+            return Err(format!(
+                "Checkpoint '{}' was written with other options ({}); rerun with the same --warn, --filter and --session-attribution, or delete it to start over",
+                path.display(),
+                state.settings
+            )
+            .into());
+        }
+        Ok(Some(state))
+    }
+
+    /// Writes the state atomically, so an interrupted save leaves the previous checkpoint intact
+    fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let write = || -> std::io::Result<()> {
+            let mut file = OutputFile::create_with(path, Compression::None)?;
+            file.write_all(&serde_json::to_vec(self)?)?;
+            file.commit()
+        };
+        write().map_err(|e| format!("Failed to write checkpoint '{}': {}", path.display(), e))?;
+        Ok(())
+    }
+
+    /// Counts one entry that passed the filter; returns the report of the day it closed
+    fn observe(
+        &mut self,
+        entry: &LogEntry,
+        level_filter: &LogFilter,
+        config: &AnalyzerConfig,
+        options: &ErrorsOptions,
+    ) -> Option<DayReport> {
+        // Entries stamped before the current day (clock jitter around midnight) count
+        // toward the current day rather than reopening a closed one
+        let date = entry.timestamp.date_naive();
+        let mut closed = None;
+        match &self.day {
+            Some(day) if date > day.date => {
+                closed = Some(day.report(true));
+                self.day = Some(DayAccum::new(date));
+            }
+            Some(_) => {}
+            None => self.day = Some(DayAccum::new(date)),
+        }
+        if let Some(report) = &closed {
+            self.days.push(report.clone());
+        }
+
+        let attribution = config.sessions.parallel_attribution;
+        let cluster = self
+            .clusters
+            .observe(entry, level_filter, attribution, options);
+        self.run.observe(entry, || cluster.clone(), config);
+        if let Some(key) = cluster {
+            let first_seen = self.clusters.clusters[&key].count == 1;
+            let day = self.day.as_mut().expect("a day was opened above");
+            if key.0 == "ERROR" {
+                day.error_count += 1;
+            } else {
+                day.warn_count += 1;
+            }
+            if first_seen {
+                day.new_patterns += 1;
+            }
+            *day.patterns.entry(key).or_insert(0) += 1;
+        }
+        closed
+    }
+
+    fn report(&self, config: &AnalyzerConfig, options: &ErrorsOptions) -> ErrorAnalysisReport {
+        let mut report = self
+            .run
+            .clone()
+            .into_report(self.clusters.clone(), config, options);
+        report.days = self.days.clone();
+        report
+            .days
+            .extend(self.day.as_ref().map(|day| day.report(false)));
+        report
+    }
+}
+
+/// First line of `path`, cut to [`FILE_HEAD_CHARS`]
+fn file_head(path: &Path) -> Result<String, Box<dyn Error>> {
+    let mut line = String::new();
+    BufReader::new(File::open(path)?).read_line(&mut line)?;
+    Ok(line.trim_end().chars().take(FILE_HEAD_CHARS).collect())
+}
+
+/// Clusters errors while reading `files` in the order given, one entry at a time, so
+/// memory does not grow with the log. With `checkpoint`, the state is saved periodically
+/// and at every day boundary, and an existing checkpoint is resumed: files are recognized
+/// by their first line (so rotated files are not read twice) and continue where they
+/// stopped. `on_day` receives each day as it closes, then the day still open at the end.
+///
+/// Reports the same clusters, session outcomes, health, and `during_operations` as
+/// [`super::analyze_errors_with_config`] over the same entries.
+pub fn analyze_errors_streaming(
+    files: &[PathBuf],
+    config: &AnalyzerConfig,
+    filter: &LogFilter,
+    options: &ErrorsOptions,
+    checkpoint: Option<&CheckpointOptions>,
+    warnings: &WarningLog,
+    mut on_day: impl FnMut(&DayReport),
+) -> Result<StreamedErrors, Box<dyn Error>> {
+    let settings = format!(
+        "warn={} attribution={:?} filter={:?} suppressed={:?}",
+        options.include_warn,
+        config.sessions.parallel_attribution,
+        checkpoint.and_then(|checkpoint| checkpoint.filter_expression.as_deref()),
+        options.suppressed_patterns
+    );
+    let mut state = match checkpoint {
+        Some(checkpoint) => StreamState::load(&checkpoint.path, &settings)?,
+        None => None,
+    }
+    .unwrap_or_else(|| StreamState::new(settings));
+    let resumed_entries = state.run.entries;
+    for day in &state.days {
+        on_day(day);
+    }
+
+    let level_filter = build_error_level_filter(options.include_warn);
+    let mut unsaved = 0usize;
+    for file in files {
+        let head = file_head(file)
+            .map_err(|e| format!("Failed to read log file '{}': {}", file.display(), e))?;
+        let length = std::fs::metadata(file)?.len();
+        let cursor = match state
+            .files
+            .iter()
+            .position(|cursor| !head.is_empty() && cursor.head == head)
+        {
+            Some(index) => index,
+            None => {
+                state.files.push(FileCursor {
+                    path: file.display().to_string(),
+                    head: head.clone(),
+                    position: ReadPosition::default(),
+                });
+                state.files.len() - 1
+            }
+        };
+        state.files[cursor].path = file.display().to_string();
+        if state.files[cursor].position.offset > length {
+            warnings.warn(
+                WarningCode::CheckpointedFileShrank,
+                format!(
+                    "'{}' is shorter than when it was checkpointed; reading it from the start",
+                    file.display()
+                ),
+            );
+            state.files[cursor].position = ReadPosition::default();
+        }
+
+        let mut reader = LogEntryReader::open_at(file, config, state.files[cursor].position)
+            .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?
+            .reporting_to(warnings);
+        while let Some(entry) = reader.next() {
+            let entry = entry
+                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?;
+            state.run.watch_command_completion(&entry, config);
+            if !filter.matches(&entry) {
+                continue;
+            }
+            let closed = state.observe(&entry, &level_filter, config, options);
+            unsaved += 1;
+            if let Some(day) = &closed {
+                on_day(day);
+            }
+            if let Some(checkpoint) = checkpoint
+                && (closed.is_some() || unsaved >= checkpoint.every_entries.max(1))
+            {
+                state.files[cursor].position = reader.position();
+                state.save(&checkpoint.path)?;
+                unsaved = 0;
+            }
+        }
+        state.files[cursor].position = reader.position();
+    }
+    if let Some(checkpoint) = checkpoint {
+        state.save(&checkpoint.path)?;
+    }
+
+    if let Some(day) = &state.day {
+        on_day(&day.report(false));
+    }
+    Ok(StreamedErrors {
+        report: state.report(config, options),
+        resumed_entries,
+    })
+}
+
+/// One day of a streaming analysis, printed as soon as the day closes
+pub fn format_day_text(day: &DayReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "DAY {}{}: {} errors, {} warnings, {} patterns ({} new)",
+        day.date,
+        if day.complete { "" } else { " (so far)" },
+        day.error_count,
+        day.warn_count,
+        day.unique_patterns,
+        day.new_patterns
+    );
+    for pattern in &day.top_patterns {
+        let _ = writeln!(
+            out,
+            "  {:>6}x [{}] {}",
+            pattern.count, pattern.severity, pattern.pattern
+        );
+    }
+    out
+}
@@ -1,6 +1,8 @@
+use crate::comparator::create_styled_table;
 use crate::parser::LogEntry;
+use comfy_table::{Cell, CellAlignment};
 use serde_json::{Value, json};
-use std::cmp::Reverse;
+use std::cmp::{Ordering, Reverse};
 use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::path::Path;
@@ -75,6 +77,201 @@ pub fn format_extract_json(
     .unwrap_or_else(|_| "{\"extract\":{\"error\":\"failed to serialize extract output\"}}".into())
 }
 
+/// Entries of one file selected for `extract --against`
+#[derive(Debug, Clone, Copy)]
+pub struct ExtractSide<'a> {
+    pub file: &'a Path,
+    pub logs: &'a [LogEntry],
+    pub match_indices: &'a [usize],
+}
+
+impl ExtractSide<'_> {
+    fn summary(&self, field_path: &str) -> ExtractSummary {
+        build_extract_summary(self.logs, self.match_indices, field_path)
+    }
+
+    fn label(&self) -> String {
+        self.file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.file.display().to_string())
+    }
+}
+
+/// One extracted value with its occurrence count in each file
+#[derive(Debug, Clone)]
+struct ValueDelta {
+    value: Value,
+    before: usize,
+    after: usize,
+}
+
+impl ValueDelta {
+    fn delta(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+
+    fn change_label(&self) -> String {
+        match (self.before, self.after) {
+            (before, after) if before == after => String::new(),
+            (0, _) => "(new)".to_string(),
+            (_, 0) => "(gone)".to_string(),
+            _ => format!("{:+}", self.delta()),
+        }
+    }
+}
+
+/// Union of both files' values: numbers ascending, then other values in serialized order
+fn value_deltas(before: &ExtractSummary, after: &ExtractSummary) -> Vec<ValueDelta> {
+    let mut rows: BTreeMap<&str, ValueDelta> = BTreeMap::new();
+    for group in &before.groups {
+        rows.insert(
+            &group.value_key,
+            ValueDelta {
+                value: group.value.clone(),
+                before: group.count,
+                after: 0,
+            },
+        );
+    }
+    for group in &after.groups {
+        rows.entry(&group.value_key)
+            .or_insert_with(|| ValueDelta {
+                value: group.value.clone(),
+                before: 0,
+                after: 0,
+            })
+            .after = group.count;
+    }
+
+    let mut rows: Vec<(&str, ValueDelta)> = rows.into_iter().collect();
+    rows.sort_by(
+        |(a_key, a), (b_key, b)| match (a.value.as_f64(), b.value.as_f64()) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a_key.cmp(b_key),
+        },
+    );
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
+/// Mean of the extracted values when every one of them is a number
+fn numeric_mean(summary: &ExtractSummary) -> Option<f64> {
+    let mut total = 0.0;
+    for group in &summary.groups {
+        total += group.value.as_f64()? * group.count as f64;
+    }
+    (summary.extracted > 0).then(|| total / summary.extracted as f64)
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+/// Side-by-side occurrence counts of each value of `field_path` in two files
+pub fn format_extract_delta_text(
+    before: &ExtractSide,
+    after: &ExtractSide,
+    field_path: &str,
+) -> String {
+    let (before_label, after_label) = (before.label(), after.label());
+    let (before, after) = (before.summary(field_path), after.summary(field_path));
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Extract delta for '{field_path}': {before_label} → {after_label}"
+    );
+    let rows = value_deltas(&before, &after);
+    if rows.is_empty() {
+        let _ = writeln!(
+            out,
+            "No values found in {} + {} matching entries.",
+            before.matches, after.matches
+        );
+        return out;
+    }
+
+    let mut table = create_styled_table(&["Value", &before_label, &after_label, "Δ"]);
+    let right = |text: String| Cell::new(text).set_alignment(CellAlignment::Right);
+    for row in &rows {
+        table.add_row(vec![
+            Cell::new(
+                serde_json::to_string(&row.value)
+                    .unwrap_or_else(|_| "\"<failed to serialize value>\"".to_string()),
+            ),
+            right(row.before.to_string()),
+            right(row.after.to_string()),
+            right(row.change_label()),
+        ]);
+    }
+    table.add_row(vec![
+        Cell::new("extracted"),
+        right(before.extracted.to_string()),
+        right(after.extracted.to_string()),
+        right(format!(
+            "{:+}",
+            after.extracted as i64 - before.extracted as i64
+        )),
+    ]);
+    let _ = writeln!(out, "{table}");
+
+    if let (Some(before_mean), Some(after_mean)) = (numeric_mean(&before), numeric_mean(&after)) {
+        let _ = writeln!(
+            out,
+            "Mean: {} → {} ({}{})",
+            format_number(before_mean),
+            format_number(after_mean),
+            if after_mean < before_mean { "-" } else { "+" },
+            format_number((after_mean - before_mean).abs())
+        );
+    }
+    if rows.iter().all(|row| row.before == row.after) {
+        let _ = writeln!(out, "Values unchanged.");
+    }
+    out
+}
+
+pub fn format_extract_delta_json(
+    before: &ExtractSide,
+    after: &ExtractSide,
+    field_path: &str,
+) -> String {
+    let side_json = |side: &ExtractSide, summary: &ExtractSummary| {
+        json!({
+            "file": side.file.display().to_string(),
+            "matches": summary.matches,
+            "extracted": summary.extracted,
+            "missing_payload": summary.missing_payload,
+            "missing_field": summary.missing_field,
+            "mean": numeric_mean(summary),
+        })
+    };
+    let (before_summary, after_summary) = (before.summary(field_path), after.summary(field_path));
+
+    serde_json::to_string_pretty(&json!({
+        "extract_delta": {
+            "field": field_path,
+            "before": side_json(before, &before_summary),
+            "after": side_json(after, &after_summary),
+            "values": value_deltas(&before_summary, &after_summary).iter().map(|row| json!({
+                "value": row.value,
+                "before": row.before,
+                "after": row.after,
+                "delta": row.delta(),
+            })).collect::<Vec<_>>(),
+        }
+    }))
+    .unwrap_or_else(|_| {
+        "{\"extract_delta\":{\"error\":\"failed to serialize extract delta\"}}".into()
+    })
+}
+
 fn build_extract_summary(
     logs: &[LogEntry],
     match_indices: &[usize],
@@ -125,39 +322,18 @@ fn build_extract_summary(
     }
 }
 
-fn extract_field_value<'a>(value: &'a Value, field_path: &str) -> Option<&'a Value> {
-    let mut current = value;
-    for segment in field_path.split('.') {
-        if segment.is_empty() {
-            return None;
-        }
-
-        current = match current {
-            Value::Object(map) => map.get(segment)?,
-            Value::Array(items) => {
-                let index = segment.parse::<usize>().ok()?;
-                items.get(index)?
-            }
-            _ => return None,
-        };
-    }
-
-    Some(current)
-}
-
 fn extract_entry_field_value(entry: &LogEntry, field_path: &str) -> Option<Value> {
     if let Some(value) = entry.structured_fields.get(field_path) {
         return Some(Value::String(value.clone()));
     }
 
-    let payload = entry.payload()?;
-    extract_field_value(payload, field_path).cloned()
+    entry.payload_value(field_path).cloned()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{extract_entry_field_value, extract_field_value};
-    use crate::parser::{LogEntry, LogEntryKind};
+    use super::extract_entry_field_value;
+    use crate::parser::{LogEntry, LogEntryKind, value_at_path};
     use chrono::Local;
     use serde_json::{Value, json};
     use std::collections::HashMap;
@@ -174,10 +350,10 @@ mod tests {
         });
 
         assert_eq!(
-            extract_field_value(&payload, "settings.retries.1.timeout"),
+            value_at_path(&payload, "settings.retries.1.timeout"),
             Some(&json!(2000))
         );
-        assert_eq!(extract_field_value(&payload, "settings.missing"), None);
+        assert_eq!(value_at_path(&payload, "settings.missing"), None);
     }
 
     #[test]
@@ -197,6 +373,7 @@ mod tests {
                 payload: Some(json!({"trace_id": "payload-value"})),
             },
             source_line_number: 1,
+            tags: Vec::new(),
         };
 
         assert_eq!(
@@ -0,0 +1,205 @@
+use crate::comparator::{create_styled_table, payload_field_paths};
+use crate::parser::{LogEntry, LogEntryKind};
+use comfy_table::{Cell, CellAlignment};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Share of each request's payloads that carry each field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldMatrix {
+    /// Request names (columns), most frequent first
+    pub requests: Vec<MatrixRequest>,
+    /// Fields (rows), most widely present first
+    pub fields: Vec<MatrixField>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixRequest {
+    pub name: String,
+    /// Entries of this request that carried a payload
+    pub payloads: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixField {
+    pub field: String,
+    /// Payloads carrying the field, aligned with `FieldMatrix::requests`
+    pub counts: Vec<usize>,
+}
+
+impl FieldMatrix {
+    pub fn presence_pct(&self, field: &MatrixField, column: usize) -> f64 {
+        let payloads = self.requests[column].payloads;
+        if payloads == 0 {
+            0.0
+        } else {
+            field.counts[column] as f64 * 100.0 / payloads as f64
+        }
+    }
+}
+
+pub fn build_field_matrix(logs: &[LogEntry], max_json_depth: Option<usize>) -> FieldMatrix {
+    let mut payloads: HashMap<&str, usize> = HashMap::new();
+    let mut presence: HashMap<String, HashMap<&str, usize>> = HashMap::new();
+    for entry in logs {
+        let LogEntryKind::Request {
+            request,
+            payload: Some(payload),
+            ..
+        } = &entry.kind
+        else {
+            continue;
+        };
+        *payloads.entry(request).or_default() += 1;
+        for field in payload_field_paths(payload, max_json_depth) {
+            *presence
+                .entry(field)
+                .or_default()
+                .entry(request)
+                .or_default() += 1;
+        }
+    }
+
+    let mut requests: Vec<MatrixRequest> = payloads
+        .into_iter()
+        .map(|(name, payloads)| MatrixRequest {
+            name: name.to_string(),
+            payloads,
+        })
+        .collect();
+    requests.sort_by(|a, b| {
+        b.payloads
+            .cmp(&a.payloads)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut fields: Vec<MatrixField> = presence
+        .into_iter()
+        .map(|(field, by_request)| MatrixField {
+            counts: requests
+                .iter()
+                .map(|request| by_request.get(request.name.as_str()).copied().unwrap_or(0))
+                .collect(),
+            field,
+        })
+        .collect();
+    let carriers = |field: &MatrixField| field.counts.iter().filter(|&&count| count > 0).count();
+    fields.sort_by(|a, b| {
+        carriers(b)
+            .cmp(&carriers(a))
+            .then_with(|| b.counts.iter().sum::<usize>().cmp(&a.counts.iter().sum()))
+            .then_with(|| a.field.cmp(&b.field))
+    });
+
+    FieldMatrix { requests, fields }
+}
+
+fn format_pct(pct: f64) -> String {
+    if pct == 0.0 {
+        "-".to_string()
+    } else {
+        format!("{pct:.0}%")
+    }
+}
+
+pub fn format_field_matrix_text(matrix: &FieldMatrix) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "FIELD PRESENCE: {} fields across {} request types",
+        matrix.fields.len(),
+        matrix.requests.len()
+    );
+    if matrix.requests.is_empty() {
+        let _ = writeln!(out, "\nNo request payloads found.");
+        return out;
+    }
+
+    let headers: Vec<String> = std::iter::once("Field".to_string())
+        .chain(
+            matrix
+                .requests
+                .iter()
+                .map(|request| format!("{} ({})", request.name, request.payloads)),
+        )
+        .collect();
+    let header_refs: Vec<&str> = headers.iter().map(String::as_str).collect();
+    let mut table = create_styled_table(&header_refs);
+    for field in &matrix.fields {
+        let mut row = vec![Cell::new(&field.field)];
+        row.extend((0..matrix.requests.len()).map(|column| {
+            Cell::new(format_pct(matrix.presence_pct(field, column)))
+                .set_alignment(CellAlignment::Right)
+        }));
+        table.add_row(row);
+    }
+    let _ = writeln!(out, "\n{table}");
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One row per field, one column per request name, presence in percent
+pub fn format_field_matrix_csv(matrix: &FieldMatrix) -> String {
+    let mut out = String::from("field");
+    for request in &matrix.requests {
+        out.push(',');
+        out.push_str(&csv_field(&request.name));
+    }
+    out.push('\n');
+    for field in &matrix.fields {
+        out.push_str(&csv_field(&field.field));
+        for column in 0..matrix.requests.len() {
+            let _ = write!(out, ",{:.1}", matrix.presence_pct(field, column));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub fn format_field_matrix_json(matrix: &FieldMatrix) -> String {
+    let fields: Vec<Value> = matrix
+        .fields
+        .iter()
+        .map(|field| {
+            let presence: Map<String, Value> = matrix
+                .requests
+                .iter()
+                .enumerate()
+                .map(|(column, request)| {
+                    (
+                        request.name.clone(),
+                        json!({
+                            "count": field.counts[column],
+                            "pct": matrix.presence_pct(field, column),
+                        }),
+                    )
+                })
+                .collect();
+            json!({ "field": field.field, "presence": presence })
+        })
+        .collect();
+    let requests: BTreeMap<&str, usize> = matrix
+        .requests
+        .iter()
+        .map(|request| (request.name.as_str(), request.payloads))
+        .collect();
+
+    serde_json::to_string_pretty(&json!({
+        "field_matrix": {
+            "requests": requests,
+            "fields": fields,
+        }
+    }))
+    .unwrap_or_else(|_| {
+        "{\"field_matrix\":{\"error\":\"failed to serialize field matrix\"}}".into()
+    })
+}
@@ -0,0 +1,117 @@
+//! Per-file status of multi-file runs, so one malformed or empty input among many stands
+//! out instead of silently diluting the aggregate results
+
+use crate::comparator::create_styled_table;
+use crate::parser::LogEntry;
+use crate::render::RenderOptions;
+use crate::units;
+use colored::Colorize;
+use comfy_table::{Cell, CellAlignment};
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// How parsing one input file went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStatus {
+    pub file: String,
+    pub entries: usize,
+    /// Non-blank lines that belong to no entry
+    pub skipped_lines: usize,
+    pub parse_ms: i64,
+    /// Entries at ERROR level
+    pub errors: usize,
+}
+
+impl FileStatus {
+    pub fn new(file: &Path, entries: &[LogEntry], skipped_lines: usize, elapsed: Duration) -> Self {
+        Self {
+            file: file.display().to_string(),
+            entries: entries.len(),
+            skipped_lines,
+            parse_ms: elapsed.as_millis() as i64,
+            errors: entries
+                .iter()
+                .filter(|entry| entry.level.eq_ignore_ascii_case("ERROR"))
+                .count(),
+        }
+    }
+
+    /// What is wrong with the file, if anything
+    pub fn problem(&self) -> Option<String> {
+        if self.entries == 0 {
+            Some(if self.skipped_lines == 0 {
+                "empty".to_string()
+            } else {
+                format!("no entries, {} line(s) skipped", self.skipped_lines)
+            })
+        } else if self.skipped_lines > 0 {
+            Some(format!("{} line(s) skipped", self.skipped_lines))
+        } else {
+            None
+        }
+    }
+}
+
+/// Statuses of the files parsed during one run, in parse order
+#[derive(Debug, Default)]
+pub struct FileStatusLog {
+    statuses: RefCell<Vec<FileStatus>>,
+}
+
+impl FileStatusLog {
+    pub fn record(&self, status: FileStatus) {
+        self.statuses.borrow_mut().push(status);
+    }
+
+    pub fn statuses(&self) -> Vec<FileStatus> {
+        self.statuses.borrow().clone()
+    }
+}
+
+/// Table of one row per file, followed by a line per file that looks wrong
+pub fn format_file_status_text(statuses: &[FileStatus], render: &RenderOptions) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "\n{}",
+        render.paint(format!("FILES ({})", statuses.len()).bold())
+    );
+
+    let mut table = create_styled_table(&[
+        "File",
+        "Entries",
+        "Skipped lines",
+        "Parse time",
+        "Errors",
+        "Status",
+    ]);
+    let right = |text: String| Cell::new(text).set_alignment(CellAlignment::Right);
+    for status in statuses {
+        table.add_row(vec![
+            Cell::new(&status.file),
+            right(status.entries.to_string()),
+            right(status.skipped_lines.to_string()),
+            right(units::format_duration_ms(status.parse_ms)),
+            right(status.errors.to_string()),
+            Cell::new(if status.problem().is_some() {
+                "check"
+            } else {
+                "ok"
+            }),
+        ]);
+    }
+    let _ = writeln!(out, "{table}");
+
+    for status in statuses {
+        if let Some(problem) = status.problem() {
+            let _ = writeln!(
+                out,
+                "{}",
+                render.paint(format!("  ! {}: {problem}", status.file).yellow())
+            );
+        }
+    }
+    out
+}
@@ -14,6 +14,16 @@ pub enum FilterParseError {
     #[error("Invalid direction value: '{0}'. Valid values are: incoming, outgoing")]
     InvalidDirection(String),
 
+    #[error(
+        "Invalid within window: '{0}'. Expected '<duration>-of:type:value', e.g. '!within:5s-of:text:retry'"
+    )]
+    InvalidWindow(String),
+
+    #[error(
+        "Invalid time bound: '{0}'. Expected an RFC3339 timestamp, 'now', 'now-<duration>' or '<duration>', e.g. 'since:30m'"
+    )]
+    InvalidTimeBound(String),
+
     #[error("Invalid filter expression: {0}")]
     InvalidExpression(String),
 }
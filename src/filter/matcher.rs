@@ -1,6 +1,7 @@
 use super::parser::{FilterExpression, FilterType};
 use crate::cli::Direction;
 use crate::comparator::LogFilter;
+use crate::diagnostics::{WarningCode, WarningLog};
 
 /// Convert a FilterExpression to a LogFilter
 ///
@@ -53,6 +54,48 @@ pub fn to_log_filter(expr: &FilterExpression) -> LogFilter {
         filter = filter.exclude_field(Some(field), Some(value));
     }
 
+    for path in expr.include_filters(&FilterType::Missing) {
+        filter = filter.missing_field(Some(path));
+    }
+    for path in expr.exclude_filters(&FilterType::Missing) {
+        filter = filter.has_field(Some(path));
+    }
+
+    for tag in expr.include_filters(&FilterType::Tag) {
+        filter = filter.with_tag(Some(tag));
+    }
+    for tag in expr.exclude_filters(&FilterType::Tag) {
+        filter = filter.exclude_tag(Some(tag));
+    }
+
+    for method in expr.include_filters(&FilterType::Method) {
+        filter = filter.with_method(Some(method));
+    }
+    for method in expr.exclude_filters(&FilterType::Method) {
+        filter = filter.exclude_method(Some(method));
+    }
+
+    for host in expr.include_filters(&FilterType::Host) {
+        filter = filter.with_host(Some(host));
+    }
+    for host in expr.exclude_filters(&FilterType::Host) {
+        filter = filter.exclude_host(Some(host));
+    }
+
+    for bound in expr.time_bounds(&FilterType::Since) {
+        filter = filter.since(bound);
+    }
+    for bound in expr.time_bounds(&FilterType::Until) {
+        filter = filter.until(bound);
+    }
+
+    for window in expr.exclusion_windows() {
+        let anchor = FilterExpression {
+            terms: vec![(*window.anchor).clone()],
+        };
+        filter = filter.exclude_within(window.millis, to_log_filter(&anchor));
+    }
+
     filter
 }
 
@@ -68,24 +111,24 @@ fn parse_direction(s: &str) -> Option<Direction> {
 /// Print warnings for any unknown filter values
 ///
 /// This helps users identify typos or unsupported values in their filters.
-pub fn print_filter_warnings(expr: &FilterExpression) {
+pub fn print_filter_warnings(expr: &FilterExpression, warnings: &WarningLog) {
     // Check level values
     let known_levels = [
         "TRACE", "DEBUG", "INFO", "WARN", "WARNING", "ERROR", "FATAL",
     ];
     for level in expr.include_filters(&FilterType::Level) {
         if !known_levels.iter().any(|k| k.eq_ignore_ascii_case(level)) {
-            eprintln!(
-                "Warning: Unknown log level '{}'. Common levels are: {:?}",
-                level, known_levels
+            warnings.warn(
+                WarningCode::UnknownFilterLevel,
+                format!("Unknown log level '{level}'. Common levels are: {known_levels:?}"),
             );
         }
     }
     for level in expr.exclude_filters(&FilterType::Level) {
         if !known_levels.iter().any(|k| k.eq_ignore_ascii_case(level)) {
-            eprintln!(
-                "Warning: Unknown log level '{}'. Common levels are: {:?}",
-                level, known_levels
+            warnings.warn(
+                WarningCode::UnknownFilterLevel,
+                format!("Unknown log level '{level}'. Common levels are: {known_levels:?}"),
             );
         }
     }
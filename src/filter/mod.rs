@@ -18,6 +18,14 @@
 //! - `level:` / `lvl:` / `l:` - Filter by log level
 //! - `text:` / `t:` - Filter by text in message
 //! - `direction:` / `dir:` / `d:` - Filter by direction (incoming/outgoing)
+//! - `missing:` - Filter to entries whose payload lacks a dotted field path
+//! - `tag:` - Filter by a tag assigned by the profile's `[tags]` rules
+//! - `method:` / `host:` - Filter requests by the method or host of their endpoint
+//!   (`host:` accepts `*`/`?` wildcards)
+//! - `!within:<duration>-of:type:value` - Exclude entries within a time window of
+//!   entries matching another term (resolved in a first pass over the logs)
+//! - `since:` / `until:` - Keep entries in a time range: an RFC3339 timestamp, `now`,
+//!   `now-<duration>` or a bare `<duration>` back from now (`--now`, `--relative-to`)
 //!
 //! # Examples
 //!
@@ -28,6 +36,11 @@
 //! component:core level:ERROR              # Core errors
 //! comp:core !text:timeout                 # Core logs without timeout
 //! dir:incoming                            # Incoming requests/events
+//! missing:settings.viewport               # Payloads without settings.viewport
+//! tag:network !tag:heartbeat              # Tagged network traffic, minus heartbeats
+//! method:POST host:eyesapi.*              # POST requests to eyesapi hosts
+//! !within:5s-of:text:retry                # Hide entries within 5s of a retry
+//! since:30m until:now-5m                  # From 30 to 5 minutes ago
 //! ```
 
 pub mod error;
@@ -36,4 +49,6 @@ pub mod parser;
 
 pub use error::FilterParseError;
 pub use matcher::{print_filter_warnings, to_log_filter};
-pub use parser::{FilterExpression, FilterTerm, FilterType};
+pub use parser::{
+    FilterExpression, FilterTerm, FilterType, TimeBound, TimeWindow, parse_window_duration,
+};
@@ -1,4 +1,5 @@
 use super::error::FilterParseError;
+use chrono::{DateTime, FixedOffset};
 use std::str::FromStr;
 
 /// Types of filters that can be applied
@@ -12,6 +13,20 @@ pub enum FilterType {
     Text,
     /// Filter by direction (incoming/outgoing)
     Direction,
+    /// Exclude entries within a time window of entries matching another term
+    Within,
+    /// Entries whose payload lacks a dotted field path
+    Missing,
+    /// Entries carrying a tag assigned by the profile's `[tags]` rules
+    Tag,
+    /// Requests sent with an HTTP method (e.g., "POST")
+    Method,
+    /// Requests sent to a host, `*` and `?` as wildcards (e.g., "eyesapi.*")
+    Host,
+    /// Entries at or after a time, absolute or relative (e.g., "30m", "now-1h")
+    Since,
+    /// Entries at or before a time, absolute or relative (e.g., "now-5m")
+    Until,
     /// Filter by any structured field key=value extracted from the log entry
     StructuredField,
 }
@@ -25,6 +40,13 @@ impl FromStr for FilterType {
             "level" | "lvl" | "l" => Ok(FilterType::Level),
             "text" | "t" => Ok(FilterType::Text),
             "direction" | "dir" | "d" => Ok(FilterType::Direction),
+            "within" => Ok(FilterType::Within),
+            "missing" => Ok(FilterType::Missing),
+            "tag" => Ok(FilterType::Tag),
+            "method" => Ok(FilterType::Method),
+            "host" => Ok(FilterType::Host),
+            "since" => Ok(FilterType::Since),
+            "until" => Ok(FilterType::Until),
             _ => Ok(FilterType::StructuredField),
         }
     }
@@ -38,6 +60,13 @@ impl FilterType {
             FilterType::Level => "level",
             FilterType::Text => "text",
             FilterType::Direction => "direction",
+            FilterType::Within => "within",
+            FilterType::Missing => "missing",
+            FilterType::Tag => "tag",
+            FilterType::Method => "method",
+            FilterType::Host => "host",
+            FilterType::Since => "since",
+            FilterType::Until => "until",
             FilterType::StructuredField => "field",
         }
     }
@@ -54,6 +83,83 @@ pub struct FilterTerm {
     pub value: String,
     /// Whether this is an exclusion filter (prefixed with !)
     pub exclude: bool,
+    /// Time window and anchor term for `within:` filters
+    pub window: Option<TimeWindow>,
+    /// Bound of `since:`/`until:` filters
+    pub bound: Option<TimeBound>,
+}
+
+/// Window of `millis` on either side of entries matching `anchor`
+#[derive(Debug, Clone)]
+pub struct TimeWindow {
+    pub millis: i64,
+    pub anchor: Box<FilterTerm>,
+}
+
+impl TimeWindow {
+    /// Parse a window value like `5s-of:text:retry`
+    fn parse(value: &str) -> Result<Self, FilterParseError> {
+        let invalid = || FilterParseError::InvalidWindow(value.to_string());
+        let (duration, anchor) = value.split_once("-of:").ok_or_else(invalid)?;
+        let millis = parse_window_duration(duration).ok_or_else(invalid)?;
+        let anchor = FilterTerm::parse(anchor)?;
+        if anchor.exclude || anchor.filter_type == FilterType::Within {
+            return Err(invalid());
+        }
+
+        Ok(TimeWindow {
+            millis,
+            anchor: Box::new(anchor),
+        })
+    }
+}
+
+/// A `since:`/`until:` bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBound {
+    /// An RFC3339 timestamp
+    At(DateTime<FixedOffset>),
+    /// Milliseconds before the reference instant: the wall clock (or `--now`), or the
+    /// last entry of the logs with `--relative-to log-end`
+    Ago(i64),
+}
+
+impl TimeBound {
+    /// Parse `2024-05-01T10:00:00Z`, `now`, `now-5m` or a bare duration like `30m`
+    /// (short for `now-30m`)
+    fn parse(value: &str) -> Result<Self, FilterParseError> {
+        let invalid = || FilterParseError::InvalidTimeBound(value.to_string());
+        if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+            return Ok(TimeBound::At(at));
+        }
+        let ago = match value.strip_prefix("now") {
+            Some("") => Some(0),
+            Some(rest) => rest.strip_prefix('-').and_then(parse_window_duration),
+            None => parse_window_duration(value),
+        };
+        ago.filter(|ms| *ms >= 0)
+            .map(TimeBound::Ago)
+            .ok_or_else(invalid)
+    }
+}
+
+/// Parse durations like `500ms`, `5s`, `1.5m`, `2h`, `1d` into milliseconds
+pub fn parse_window_duration(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let scale = match unit {
+        "ms" => 1.0,
+        "" | "s" => 1_000.0,
+        "m" => 60_000.0,
+        "h" => 3_600_000.0,
+        "d" => 86_400_000.0,
+        _ => return None,
+    };
+    Some((number * scale).round() as i64)
 }
 
 impl FilterTerm {
@@ -91,11 +197,38 @@ impl FilterTerm {
             }
         }
 
+        let window = if filter_type == FilterType::Within {
+            if !exclude {
+                return Err(FilterParseError::InvalidExpression(format!(
+                    "within: windows only support exclusion, use '!{}'",
+                    s
+                )));
+            }
+            Some(TimeWindow::parse(&value)?)
+        } else {
+            None
+        };
+
+        let bound = if matches!(filter_type, FilterType::Since | FilterType::Until) {
+            if exclude {
+                return Err(FilterParseError::InvalidExpression(format!(
+                    "{} bounds cannot be negated, use the opposite bound instead of '{}'",
+                    filter_type.canonical_name(),
+                    s
+                )));
+            }
+            Some(TimeBound::parse(&value)?)
+        } else {
+            None
+        };
+
         Ok(FilterTerm {
             filter_type,
             field_key,
             value,
             exclude,
+            window,
+            bound,
         })
     }
 }
@@ -164,6 +297,23 @@ impl FilterExpression {
             .collect()
     }
 
+    /// Get all bounds of `since:` or `until:` terms
+    pub fn time_bounds(&self, filter_type: &FilterType) -> Vec<TimeBound> {
+        self.terms
+            .iter()
+            .filter(|t| &t.filter_type == filter_type)
+            .filter_map(|t| t.bound)
+            .collect()
+    }
+
+    /// Get all `!within:` exclusion windows
+    pub fn exclusion_windows(&self) -> Vec<&TimeWindow> {
+        self.terms
+            .iter()
+            .filter_map(|t| t.window.as_ref())
+            .collect()
+    }
+
     pub fn exclude_structured_filters(&self) -> Vec<(&str, &str)> {
         self.terms
             .iter()
@@ -262,6 +412,68 @@ mod tests {
         assert_eq!(expr.include_structured_filters(), vec![("trace_id", "abc")]);
     }
 
+    #[test]
+    fn test_parse_within_window() {
+        let term = FilterTerm::parse("!within:5s-of:text:retry").unwrap();
+        assert_eq!(term.filter_type, FilterType::Within);
+        let window = term.window.expect("window");
+        assert_eq!(window.millis, 5_000);
+        assert_eq!(window.anchor.filter_type, FilterType::Text);
+        assert_eq!(window.anchor.value, "retry");
+
+        let term = FilterTerm::parse("!within:250ms-of:level:ERROR").unwrap();
+        assert_eq!(term.window.expect("window").millis, 250);
+
+        assert!(FilterTerm::parse("within:5s-of:text:retry").is_err());
+        assert!(FilterTerm::parse("!within:5x-of:text:retry").is_err());
+        assert!(FilterTerm::parse("!within:5s").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_bounds() {
+        let bound = |s: &str| FilterTerm::parse(s).unwrap().bound.expect("bound");
+        assert_eq!(bound("since:30m"), TimeBound::Ago(1_800_000));
+        assert_eq!(bound("until:now-5m"), TimeBound::Ago(300_000));
+        assert_eq!(bound("until:now"), TimeBound::Ago(0));
+        assert_eq!(bound("since:1d"), TimeBound::Ago(86_400_000));
+        assert!(matches!(
+            bound("since:2024-05-01T10:00:00Z"),
+            TimeBound::At(at) if at.to_rfc3339() == "2024-05-01T10:00:00+00:00"
+        ));
+
+        assert!(FilterTerm::parse("!since:30m").is_err());
+        assert!(FilterTerm::parse("until:now+5m").is_err());
+        assert!(FilterTerm::parse("since:yesterday").is_err());
+    }
+
+    #[test]
+    fn test_parse_missing_filter() {
+        let term = FilterTerm::parse("!missing:settings.viewport").unwrap();
+        assert_eq!(term.filter_type, FilterType::Missing);
+        assert_eq!(term.value, "settings.viewport");
+        assert!(term.exclude);
+        assert!(term.field_key.is_none());
+    }
+
+    #[test]
+    fn test_parse_tag_filter() {
+        let term = FilterTerm::parse("tag:network").unwrap();
+        assert_eq!(term.filter_type, FilterType::Tag);
+        assert_eq!(term.value, "network");
+        assert!(!term.exclude);
+    }
+
+    #[test]
+    fn test_parse_endpoint_filters() {
+        let method = FilterTerm::parse("method:POST").unwrap();
+        assert_eq!(method.filter_type, FilterType::Method);
+        assert!(method.field_key.is_none());
+        let host = FilterTerm::parse("!host:eyesapi.*").unwrap();
+        assert_eq!(host.filter_type, FilterType::Host);
+        assert_eq!(host.value, "eyesapi.*");
+        assert!(host.exclude);
+    }
+
     #[test]
     fn test_invalid_direction() {
         let result = FilterTerm::parse("direction:invalid");
@@ -0,0 +1,221 @@
+use crate::comparator::LogFilter;
+use crate::config::AnalyzerConfig;
+use crate::parser::{LogEntry, json_span, parse_log_file_with_config};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default directory captured fixtures are written to and loaded from
+pub const DEFAULT_FIXTURE_DIR: &str = "tests/fixtures";
+
+/// Which slice of a log to capture as a fixture
+#[derive(Debug, Clone, Default)]
+pub struct FixtureSpec {
+    pub name: String,
+    pub since: Option<DateTime<Local>>,
+    pub until: Option<DateTime<Local>>,
+    /// `component_id` substrings of the sessions of interest (empty = all entries)
+    pub sessions: Vec<String>,
+    pub redact_fields: Vec<String>,
+}
+
+impl FixtureSpec {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp <= until)
+            && (self.sessions.is_empty()
+                || self
+                    .sessions
+                    .iter()
+                    .any(|session| entry.component_id.contains(session.as_str())))
+    }
+}
+
+/// Metadata stored next to a captured fixture as `<name>.manifest.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixtureManifest {
+    pub name: String,
+    pub source_file: String,
+    pub log_file: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redacted_fields: Vec<String>,
+    pub entry_count: usize,
+    /// First and last source line numbers of the captured entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_lines: Option<(usize, usize)>,
+}
+
+pub fn fixture_log_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.log"))
+}
+
+pub fn fixture_manifest_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.manifest.json"))
+}
+
+/// Replaces the values of the given JSON fields in a raw log line with `"[REDACTED]"`,
+/// whole objects and arrays included
+pub fn redact_line(line: &str, fields: &[String]) -> String {
+    if fields.is_empty() {
+        return line.to_string();
+    }
+
+    let names = fields
+        .iter()
+        .map(|field| regex::escape(field))
+        .collect::<Vec<_>>()
+        .join("|");
+    // Only the key is matched; objects and arrays nest, so their extent is measured
+    let key_pattern = Regex::new(&format!(r#""(?:{names})"\s*:\s*"#))
+        .expect("escaped field names form a valid regex");
+    let scalar = Regex::new(r#"^(?:"(?:[^"\\]|\\.)*"|-?\d[\d.eE+-]*|true|false)"#)
+        .expect("scalar pattern is valid");
+
+    let mut redacted = String::with_capacity(line.len());
+    let mut copied = 0;
+    for key in key_pattern.find_iter(line) {
+        // Keys nested in a value that is already redacted
+        if key.start() < copied {
+            continue;
+        }
+        let value_start = key.end();
+        let value_len = match json_span(line, value_start) {
+            // An unclosed value runs to the end of the line
+            Some(Ok(span) | Err(span)) => Some(span.len()),
+            None => scalar.find(&line[value_start..]).map(|value| value.len()),
+        };
+        if let Some(value_len) = value_len {
+            redacted.push_str(&line[copied..value_start]);
+            redacted.push_str(r#""[REDACTED]""#);
+            copied = value_start + value_len;
+        }
+    }
+    redacted.push_str(&line[copied..]);
+    redacted
+}
+
+/// Selects the entries matching `filter` and `spec`, writes their redacted raw lines to
+/// `<dir>/<name>.log` and the manifest to `<dir>/<name>.manifest.json`
+pub fn capture_fixture(
+    logs: &[LogEntry],
+    source_file: &Path,
+    filter: &LogFilter,
+    spec: &FixtureSpec,
+    dir: &Path,
+) -> Result<FixtureManifest, Box<dyn Error>> {
+    let entries: Vec<&LogEntry> = logs
+        .iter()
+        .filter(|entry| filter.matches(entry) && spec.matches(entry))
+        .collect();
+
+    let mut content = String::new();
+    for entry in &entries {
+        content.push_str(&redact_line(&entry.raw_logline, &spec.redact_fields));
+        content.push('\n');
+    }
+
+    let log_path = fixture_log_path(dir, &spec.name);
+    let manifest = FixtureManifest {
+        name: spec.name.clone(),
+        source_file: source_file.display().to_string(),
+        log_file: format!("{}.log", spec.name),
+        since: spec.since.map(|ts| {
+            ts.with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true)
+        }),
+        until: spec.until.map(|ts| {
+            ts.with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true)
+        }),
+        sessions: spec.sessions.clone(),
+        redacted_fields: spec.redact_fields.clone(),
+        entry_count: entries.len(),
+        source_lines: entries
+            .first()
+            .zip(entries.last())
+            .map(|(first, last)| (first.source_line_number, last.source_line_number)),
+    };
+
+    fs::create_dir_all(dir)?;
+    fs::write(&log_path, content)?;
+    fs::write(
+        fixture_manifest_path(dir, &spec.name),
+        format!("{}\n", serde_json::to_string_pretty(&manifest)?),
+    )?;
+
+    Ok(manifest)
+}
+
+/// Loads a captured fixture for integration tests, checking the parsed entry count
+/// against the manifest
+pub fn load_fixture(
+    dir: &Path,
+    name: &str,
+    config: &AnalyzerConfig,
+) -> Result<(FixtureManifest, Vec<LogEntry>), Box<dyn Error>> {
+    let manifest_path = fixture_manifest_path(dir, name);
+    let manifest: FixtureManifest = serde_json::from_str(
+        &fs::read_to_string(&manifest_path)
+            .map_err(|e| format!("Failed to read '{}': {}", manifest_path.display(), e))?,
+    )?;
+
+    let log_path = dir.join(&manifest.log_file);
+    let logs = parse_log_file_with_config(&log_path, config)
+        .map_err(|e| format!("Failed to parse fixture '{}': {:?}", log_path.display(), e))?;
+    if logs.len() != manifest.entry_count {
+        return Err(format!(
+            "Fixture '{}' parsed {} entries, manifest expects {}",
+            name,
+            logs.len(),
+            manifest.entry_count
+        )
+        .into());
+    }
+
+    Ok((manifest, logs))
+}
+
+pub fn format_capture_text(dir: &Path, manifest: &FixtureManifest) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Captured {} entr{} from {} into {}",
+        manifest.entry_count,
+        if manifest.entry_count == 1 {
+            "y"
+        } else {
+            "ies"
+        },
+        manifest.source_file,
+        dir.join(&manifest.log_file).display()
+    );
+    if let Some((first, last)) = manifest.source_lines {
+        let _ = writeln!(out, "  Source lines: {first}-{last}");
+    }
+    if !manifest.redacted_fields.is_empty() {
+        let _ = writeln!(out, "  Redacted: {}", manifest.redacted_fields.join(", "));
+    }
+    let _ = writeln!(
+        out,
+        "  Manifest: {}",
+        fixture_manifest_path(dir, &manifest.name).display()
+    );
+    out
+}
+
+pub fn format_capture_json(manifest: &FixtureManifest) -> String {
+    serde_json::to_string_pretty(&json!({ "capture_fixture": manifest })).unwrap_or_else(|_| {
+        "{\"capture_fixture\":{\"error\":\"failed to serialize fixture manifest\"}}".into()
+    })
+}
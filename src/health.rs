@@ -0,0 +1,243 @@
+use crate::comparator::LogFilter;
+use crate::config::{AnalyzerConfig, split_parallel_session_paths};
+use crate::parser::LogEntry;
+use crate::perf_analyzer::PerfAnalysisResults;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// Maximum score deduction for each health dimension
+const ERROR_RATE_WEIGHT: f64 = 40.0;
+const ORPHANED_SESSIONS_WEIGHT: f64 = 30.0;
+const LATENCY_WEIGHT: f64 = 30.0;
+
+/// Error rate (as a fraction) that costs the full error weight
+const ERROR_RATE_CEILING: f64 = 0.10;
+/// Orphaned-session ratio that costs the full orphan weight
+const ORPHANED_SESSIONS_CEILING: f64 = 0.50;
+
+/// One-paragraph verdict shown before detailed `errors`/`info` output
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthSummary {
+    /// 0-100, higher is healthier
+    pub score: u8,
+    pub verdict: &'static str,
+    pub total_entries: usize,
+    pub error_entries: usize,
+    pub error_rate: f64,
+    pub sessions: usize,
+    pub orphaned_sessions: usize,
+    pub orphaned_session_ratio: f64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub latency_budgets: Vec<LatencyBudgetCheck>,
+    /// Up to three largest score deductions, biggest first
+    pub top_issues: Vec<HealthIssue>,
+}
+
+/// p95 latency of one operation name against its configured budget
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyBudgetCheck {
+    pub name: String,
+    pub budget_ms: u64,
+    /// `None` when no completed operation with this name was found
+    pub p95_ms: Option<i64>,
+    pub over_budget: bool,
+}
+
+impl LatencyBudgetCheck {
+    pub(crate) fn new(name: &str, budget_ms: u64, p95_ms: Option<i64>) -> Self {
+        Self {
+            name: name.to_string(),
+            budget_ms,
+            p95_ms,
+            over_budget: p95_ms.is_some_and(|p95| p95 > budget_ms as i64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthIssue {
+    pub description: String,
+    /// Points deducted from the score
+    pub penalty: f64,
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Scores error rate, orphaned sessions, and p95 latency against `[perf] latency_budgets_ms`
+pub fn compute_health(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    perf: &PerfAnalysisResults,
+    config: &AnalyzerConfig,
+) -> HealthSummary {
+    let mut total_entries = 0;
+    let mut error_entries = 0;
+    let mut sessions: BTreeSet<&str> = BTreeSet::new();
+    for entry in logs.iter().filter(|entry| filter.matches(entry)) {
+        total_entries += 1;
+        if entry.level.eq_ignore_ascii_case("ERROR") {
+            error_entries += 1;
+        }
+        sessions.extend(split_parallel_session_paths(&entry.component_id));
+    }
+
+    let orphaned: BTreeSet<&str> = perf
+        .orphans
+        .iter()
+        .filter_map(|orphan| orphan.component_id.as_deref())
+        .flat_map(split_parallel_session_paths)
+        .filter(|path| sessions.contains(path))
+        .collect();
+
+    let latency_budgets: Vec<LatencyBudgetCheck> = config
+        .perf
+        .latency_budgets_ms
+        .iter()
+        .map(|(name, &budget_ms)| {
+            let p95_ms = perf
+                .stats
+                .iter()
+                .filter(|stats| &stats.name == name)
+                .map(|stats| stats.p95_duration_ms)
+                .max();
+            LatencyBudgetCheck::new(name, budget_ms, p95_ms)
+        })
+        .collect();
+
+    score_health(
+        total_entries,
+        error_entries,
+        sessions.len(),
+        orphaned.len(),
+        latency_budgets,
+    )
+}
+
+/// Scores already-counted health inputs; shared by [`compute_health`] and streaming analysis
+pub(crate) fn score_health(
+    total_entries: usize,
+    error_entries: usize,
+    sessions: usize,
+    orphaned_sessions: usize,
+    latency_budgets: Vec<LatencyBudgetCheck>,
+) -> HealthSummary {
+    let error_rate = ratio(error_entries, total_entries);
+    let orphaned_session_ratio = ratio(orphaned_sessions, sessions);
+
+    let mut issues = vec![
+        HealthIssue {
+            description: format!(
+                "error rate {:.1}% ({} of {} entries)",
+                error_rate * 100.0,
+                error_entries,
+                total_entries
+            ),
+            penalty: ERROR_RATE_WEIGHT * (error_rate / ERROR_RATE_CEILING).min(1.0),
+        },
+        HealthIssue {
+            description: format!(
+                "{} of {} sessions orphaned ({:.1}%)",
+                orphaned_sessions,
+                sessions,
+                orphaned_session_ratio * 100.0
+            ),
+            penalty: ORPHANED_SESSIONS_WEIGHT
+                * (orphaned_session_ratio / ORPHANED_SESSIONS_CEILING).min(1.0),
+        },
+    ];
+
+    // Each budget gets an equal share of the latency weight, scaled by how far p95 overshoots
+    // (100% over budget costs the full share).
+    let budget_share = LATENCY_WEIGHT / latency_budgets.len().max(1) as f64;
+    for check in latency_budgets.iter().filter(|check| check.over_budget) {
+        let p95 = check.p95_ms.unwrap_or_default();
+        let overshoot = p95 as f64 / check.budget_ms.max(1) as f64 - 1.0;
+        issues.push(HealthIssue {
+            description: format!(
+                "{} p95 {}ms over {}ms budget",
+                check.name, p95, check.budget_ms
+            ),
+            penalty: budget_share * overshoot.min(1.0),
+        });
+    }
+
+    let penalty: f64 = issues.iter().map(|issue| issue.penalty).sum();
+    let score = (100.0 - penalty).round().clamp(0.0, 100.0) as u8;
+    issues.retain(|issue| issue.penalty > 0.0);
+    issues.sort_by(|a, b| b.penalty.total_cmp(&a.penalty));
+    issues.truncate(3);
+
+    HealthSummary {
+        score,
+        verdict: match score {
+            90.. => "healthy",
+            70..=89 => "degraded",
+            _ => "unhealthy",
+        },
+        total_entries,
+        error_entries,
+        error_rate,
+        sessions,
+        orphaned_sessions,
+        orphaned_session_ratio,
+        latency_budgets,
+        top_issues: issues,
+    }
+}
+
+pub fn format_health_text(health: &HealthSummary) -> String {
+    let mut out = String::new();
+    let over_budget = health
+        .latency_budgets
+        .iter()
+        .filter(|check| check.over_budget)
+        .count();
+
+    let _ = write!(
+        out,
+        "HEALTH: {}/100 ({}) - error rate {:.1}% ({} of {} entries)",
+        health.score,
+        health.verdict,
+        health.error_rate * 100.0,
+        health.error_entries,
+        health.total_entries
+    );
+    if health.sessions > 0 {
+        let _ = write!(
+            out,
+            ", {} of {} sessions orphaned",
+            health.orphaned_sessions, health.sessions
+        );
+    }
+    if !health.latency_budgets.is_empty() {
+        let _ = write!(
+            out,
+            ", {} of {} latency budgets exceeded",
+            over_budget,
+            health.latency_budgets.len()
+        );
+    }
+    let _ = writeln!(out, ".");
+
+    if !health.top_issues.is_empty() {
+        let _ = writeln!(out, "Top issues:");
+        for (idx, issue) in health.top_issues.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "  {}. {} (-{:.0})",
+                idx + 1,
+                issue.description,
+                issue.penalty
+            );
+        }
+    }
+
+    out
+}
@@ -0,0 +1,111 @@
+//! `[[heartbeats]]`: recurring keepalive entries collapsed to one per window while the
+//! log is read, so they stop dominating counts, timelines and error clusters
+
+use crate::comparator::LogFilter;
+use crate::config::HeartbeatRule;
+use crate::errors::normalize_message_pattern;
+use crate::filter::{FilterExpression, to_log_filter};
+use crate::parser::LogEntry;
+use chrono::{DateTime, Duration, Local};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+/// `[[heartbeats]]` rules compiled to filters, deciding one entry at a time which
+/// heartbeats repeat within their rule's window
+#[derive(Clone, Default)]
+pub struct HeartbeatSuppressor {
+    compiled: Vec<(HeartbeatRule, LogFilter)>,
+    /// Start of the current window, per rule index and key
+    windows: HashMap<(usize, Vec<String>), DateTime<Local>>,
+    /// Entries suppressed so far, per rule index
+    suppressed: Vec<usize>,
+}
+
+impl HeartbeatSuppressor {
+    /// Rules that do not parse are skipped (profiles loaded from TOML have already been validated)
+    pub fn new(rules: &[HeartbeatRule]) -> Self {
+        let compiled: Vec<_> = rules
+            .iter()
+            .filter_map(|rule| {
+                let expr = FilterExpression::parse(&rule.filter).ok()?;
+                Some((rule.clone(), to_log_filter(&expr)))
+            })
+            .collect();
+        Self {
+            suppressed: vec![0; compiled.len()],
+            compiled,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Whether `entry` repeats a heartbeat kept less than its rule's window earlier.
+    /// An entry belongs to the first rule it matches; the first entry of each window is kept.
+    pub fn suppresses(&mut self, entry: &LogEntry) -> bool {
+        let Some(index) = self
+            .compiled
+            .iter()
+            .position(|(_, filter)| filter.matches(entry))
+        else {
+            return false;
+        };
+        let rule = &self.compiled[index].0;
+        let window = Duration::milliseconds(rule.window_ms as i64);
+        match self.windows.entry((index, key_of(rule, entry))) {
+            Entry::Occupied(start)
+                if entry.timestamp >= *start.get() && entry.timestamp < *start.get() + window =>
+            {
+                self.suppressed[index] += 1;
+                true
+            }
+            Entry::Occupied(mut start) => {
+                start.insert(entry.timestamp);
+                false
+            }
+            Entry::Vacant(start) => {
+                start.insert(entry.timestamp);
+                false
+            }
+        }
+    }
+
+    /// `(rule name, suppressed entries)` of the rules that suppressed anything, in rule order
+    pub fn suppressed(&self) -> Vec<(&str, usize)> {
+        self.compiled
+            .iter()
+            .zip(&self.suppressed)
+            .filter(|(_, count)| **count > 0)
+            .map(|((rule, _), count)| (rule.name.as_str(), *count))
+            .collect()
+    }
+}
+
+/// `pattern` with every run of digits replaced by `#`, since heartbeats often carry a
+/// sequence number or a latency too short for the message pattern to mask
+fn mask_numbers(pattern: &str) -> String {
+    let mut masked = String::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        if !c.is_ascii_digit() {
+            masked.push(c);
+        } else if !masked.ends_with('#') {
+            masked.push('#');
+        }
+    }
+    masked
+}
+
+/// The values of the rule's key parts for `entry`
+fn key_of(rule: &HeartbeatRule, entry: &LogEntry) -> Vec<String> {
+    rule.key
+        .iter()
+        .map(|part| match part.as_str() {
+            "component" => entry.component.clone(),
+            "component_id" => entry.component_id.clone(),
+            "level" => entry.level.to_uppercase(),
+            "message" => mask_numbers(&normalize_message_pattern(&entry.message)),
+            field => entry
+                .structured_field(field)
+                .unwrap_or_default()
+                .to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,99 @@
+use crate::filter::FilterExpression;
+use std::path::{Path, PathBuf};
+
+/// Project-local ignore file, discovered from the working directory upwards
+pub const IGNORE_FILE_NAME: &str = ".loganalyzerignore";
+
+/// Triage decisions shared by every command: entry filters, payload diff paths,
+/// and error patterns to suppress
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    /// File the rules were loaded from
+    pub source: Option<PathBuf>,
+    /// Filter expressions appended to the global `--filter`
+    pub filters: Vec<String>,
+    /// Payload paths ignored when diffing (also ignores nested paths)
+    pub paths: Vec<String>,
+    /// Glob patterns matched against normalized error messages
+    pub error_patterns: Vec<String>,
+}
+
+impl IgnoreRules {
+    /// Parses `kind: value` lines; `#` starts a comment line
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut rules = Self::default();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((kind, value)) = line.split_once(':') else {
+                return Err(format!(
+                    "line {}: expected '<filter|path|error>: <value>'",
+                    idx + 1
+                ));
+            };
+            let value = value.trim();
+            if value.is_empty() {
+                return Err(format!("line {}: empty {} rule", idx + 1, kind.trim()));
+            }
+
+            match kind.trim() {
+                "filter" => {
+                    FilterExpression::parse(value)
+                        .map_err(|e| format!("line {}: invalid filter: {}", idx + 1, e))?;
+                    rules.filters.push(value.to_string());
+                }
+                "path" => rules.paths.push(value.to_string()),
+                "error" => rules.error_patterns.push(value.to_string()),
+                other => {
+                    return Err(format!(
+                        "line {}: unknown rule kind '{}' (expected filter, path, or error)",
+                        idx + 1,
+                        other
+                    ));
+                }
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty() && self.paths.is_empty() && self.error_patterns.is_empty()
+    }
+
+    /// The `--filter` expression with the ignore file's filters appended
+    pub fn filter_expression(&self, cli_filter: Option<&str>) -> Option<String> {
+        let parts: Vec<&str> = cli_filter
+            .into_iter()
+            .chain(self.filters.iter().map(String::as_str))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+/// Closest ignore file in `start` or one of its ancestors
+pub fn discover_ignore_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(IGNORE_FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Loads the ignore file discovered from the current directory (empty rules when none)
+pub fn load_ignore_rules() -> Result<IgnoreRules, Box<dyn std::error::Error>> {
+    let Some(path) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| discover_ignore_file(&cwd))
+    else {
+        return Ok(IgnoreRules::default());
+    };
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read ignore file '{}': {}", path.display(), e))?;
+    let mut rules = IgnoreRules::parse(&content)
+        .map_err(|e| format!("Invalid ignore file '{}': {}", path.display(), e))?;
+    rules.source = Some(path);
+    Ok(rules)
+}
@@ -0,0 +1,398 @@
+//! `sessions impact`: for every session that failed, the error clusters and slow
+//! operations seen inside it, ranked by how likely each is to explain the failure
+
+use crate::analysis_context::AnalysisContext;
+use crate::comparator::LogFilter;
+use crate::config::SessionLevelInsights;
+use crate::errors::normalize_message_pattern;
+use crate::parser::LogEntry;
+use crate::perf_analyzer::analyze_performance_with_config;
+use crate::session_path::SessionPath;
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// Sessions of one level, the ones among them that failed and what each failure saw
+#[derive(Debug, Clone, Serialize)]
+pub struct ImpactReport {
+    pub level: String,
+    /// Sessions of the level with at least one matching entry
+    pub sessions: usize,
+    pub threshold_ms: u64,
+    pub failed: Vec<FailedSession>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedSession {
+    pub session: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// UTC, millisecond precision
+    pub first_seen: String,
+    pub last_seen: String,
+    pub duration_ms: i64,
+    /// Why the session counts as failed
+    pub reasons: Vec<String>,
+    /// Most likely cause first
+    pub suspects: Vec<ImpactSuspect>,
+    /// Suspects left out by `--top`
+    pub omitted_suspects: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuspectKind {
+    Error,
+    SlowOperation,
+}
+
+/// An error pattern or a slow operation name seen inside a failed session
+#[derive(Debug, Clone, Serialize)]
+pub struct ImpactSuspect {
+    pub kind: SuspectKind,
+    /// Normalized error pattern, or `<op type> <name>` of the slow operation
+    pub name: String,
+    /// Occurrences inside this session
+    pub count: usize,
+    pub first_line: usize,
+    /// Time between the last occurrence and the end of the session
+    pub before_end_ms: i64,
+    /// Sessions of the level in which the suspect occurs, and how many of them failed
+    pub sessions_with: usize,
+    pub failed_sessions_with: usize,
+    /// 1 at the end of the session, 0 at its start
+    pub proximity: f64,
+    /// Share of the sessions with the suspect that failed
+    pub exclusivity: f64,
+    /// Mean of proximity and exclusivity
+    pub score: f64,
+}
+
+/// One suspect's occurrences inside one session
+struct Occurrence {
+    kind: SuspectKind,
+    count: usize,
+    first_line: usize,
+    last_seen: DateTime<Local>,
+}
+
+/// Level whose sessions are judged: `level` when given, else the deepest level that
+/// names completion commands (the test level of most profiles), else the deepest level
+fn select_level<'a>(
+    levels: &'a [SessionLevelInsights],
+    level: Option<&str>,
+) -> Result<&'a SessionLevelInsights, String> {
+    match level {
+        Some(name) => levels
+            .iter()
+            .find(|candidate| candidate.config.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let names: Vec<&str> = levels.iter().map(|l| l.config.name.as_str()).collect();
+                format!(
+                    "Unknown session level '{name}' (profile levels: {})",
+                    names.join(", ")
+                )
+            }),
+        None => levels
+            .iter()
+            .rev()
+            .find(|candidate| !candidate.config.complete_commands.is_empty())
+            .or_else(|| levels.last())
+            .ok_or_else(|| "The profile defines no session levels".to_string()),
+    }
+}
+
+/// Whether `component_id` runs inside session `id`
+fn in_session(component_id: &str, id: &str) -> bool {
+    SessionPath::parse_parallel(component_id)
+        .iter()
+        .any(|path| path.segments().contains(&id))
+}
+
+/// Builds the impact report over the sessions of `level` (see [`select_level`]).
+///
+/// A session failed when an operation inside it never finished, when its level expects
+/// a completion command and none was called, or when it completed via an abort command.
+/// Suspects are ERROR patterns and operations of at least `threshold_ms` inside the
+/// session; each is scored by how close to the session's end it last occurred and by
+/// the share of the level's sessions containing it that failed. `top == 0` keeps all.
+pub fn build_impact_report(
+    context: &AnalysisContext,
+    filter: &LogFilter,
+    level: Option<&str>,
+    threshold_ms: u64,
+    top: usize,
+) -> Result<ImpactReport, String> {
+    let (logs, config) = (context.logs(), context.config());
+    let insights = context.sessions();
+    let level = select_level(&insights.levels, level)?;
+
+    let mut entries: BTreeMap<&str, Vec<&LogEntry>> = BTreeMap::new();
+    for entry in logs.iter().filter(|entry| filter.matches(entry)) {
+        let mut seen: BTreeSet<&str> = BTreeSet::new();
+        for path in entry.session_paths() {
+            for &segment in path.segments() {
+                if level.sessions.contains_key(segment) && seen.insert(segment) {
+                    entries.entry(segment).or_default().push(entry);
+                }
+            }
+        }
+    }
+
+    let perf = analyze_performance_with_config(logs, filter, None, config);
+    let mut occurrences: BTreeMap<&str, BTreeMap<String, Occurrence>> = BTreeMap::new();
+    for (&id, session_entries) in &entries {
+        let found = occurrences.entry(id).or_default();
+        for entry in session_entries
+            .iter()
+            .filter(|entry| entry.level.eq_ignore_ascii_case("ERROR"))
+        {
+            found
+                .entry(normalize_message_pattern(&entry.message))
+                .and_modify(|occurrence| {
+                    occurrence.count += 1;
+                    occurrence.last_seen = occurrence.last_seen.max(entry.timestamp);
+                })
+                .or_insert(Occurrence {
+                    kind: SuspectKind::Error,
+                    count: 1,
+                    first_line: entry.source_line_number,
+                    last_seen: entry.timestamp,
+                });
+        }
+        for op in perf.operations.iter().filter(|op| {
+            op.duration_ms >= threshold_ms as i64
+                && op
+                    .component_id
+                    .as_deref()
+                    .is_some_and(|component_id| in_session(component_id, id))
+        }) {
+            found
+                .entry(format!("{} {}", op.op_type, op.name))
+                .and_modify(|occurrence| {
+                    occurrence.count += 1;
+                    occurrence.first_line = occurrence.first_line.min(op.lines.0);
+                    occurrence.last_seen = occurrence.last_seen.max(op.end_time);
+                })
+                .or_insert(Occurrence {
+                    kind: SuspectKind::SlowOperation,
+                    count: 1,
+                    first_line: op.lines.0,
+                    last_seen: op.end_time,
+                });
+        }
+    }
+
+    let mut reasons: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for &id in entries.keys() {
+        let info = &level.sessions[id];
+        let mut failure = Vec::new();
+        let pending: BTreeSet<&str> = perf
+            .orphans
+            .iter()
+            .filter(|orphan| {
+                orphan
+                    .component_id
+                    .as_deref()
+                    .is_some_and(|component_id| in_session(component_id, id))
+            })
+            .map(|orphan| orphan.name.as_str())
+            .collect();
+        if !pending.is_empty() {
+            failure.push(format!(
+                "no response to {}",
+                pending.into_iter().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        match info.completed_via.as_deref() {
+            Some(command) if command.to_ascii_lowercase().contains("abort") => {
+                failure.push(format!("aborted via {command}"));
+            }
+            None if !level.config.complete_commands.is_empty() => {
+                failure.push(format!(
+                    "never completed (expected {})",
+                    level.config.complete_commands.join(" or ")
+                ));
+            }
+            _ => {}
+        }
+        if !failure.is_empty() {
+            reasons.insert(id, failure);
+        }
+    }
+
+    // (sessions with the suspect, failed sessions with it)
+    let mut spread: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (&id, found) in &occurrences {
+        for name in found.keys() {
+            let counts = spread.entry(name.as_str()).or_default();
+            counts.0 += 1;
+            if reasons.contains_key(id) {
+                counts.1 += 1;
+            }
+        }
+    }
+
+    let mut failed: Vec<FailedSession> = reasons
+        .iter()
+        .map(|(&id, failure)| {
+            let session_entries = &entries[id];
+            let first_seen = session_entries.iter().map(|e| e.timestamp).min();
+            let last_seen = session_entries.iter().map(|e| e.timestamp).max();
+            let (first_seen, last_seen) = (
+                first_seen.unwrap_or_default(),
+                last_seen.unwrap_or_default(),
+            );
+            let duration_ms = last_seen
+                .signed_duration_since(first_seen)
+                .num_milliseconds();
+
+            let mut suspects: Vec<ImpactSuspect> = occurrences[id]
+                .iter()
+                .map(|(name, occurrence)| {
+                    let before_end_ms = last_seen
+                        .signed_duration_since(occurrence.last_seen)
+                        .num_milliseconds()
+                        .max(0);
+                    let proximity = if duration_ms > 0 {
+                        (1.0 - before_end_ms as f64 / duration_ms as f64).clamp(0.0, 1.0)
+                    } else {
+                        1.0
+                    };
+                    let (sessions_with, failed_sessions_with) = spread[name.as_str()];
+                    let exclusivity = failed_sessions_with as f64 / sessions_with as f64;
+                    ImpactSuspect {
+                        kind: occurrence.kind,
+                        name: name.clone(),
+                        count: occurrence.count,
+                        first_line: occurrence.first_line,
+                        before_end_ms,
+                        sessions_with,
+                        failed_sessions_with,
+                        proximity,
+                        exclusivity,
+                        score: (proximity + exclusivity) / 2.0,
+                    }
+                })
+                .collect();
+            suspects.sort_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then_with(|| a.before_end_ms.cmp(&b.before_end_ms))
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+            let omitted_suspects = if top == 0 {
+                0
+            } else {
+                suspects.len().saturating_sub(top)
+            };
+            suspects.truncate(suspects.len() - omitted_suspects);
+
+            FailedSession {
+                session: id.to_string(),
+                display_name: level.config.render_display_name(&level.sessions[id]),
+                first_seen: first_seen
+                    .with_timezone(&Utc)
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+                last_seen: last_seen
+                    .with_timezone(&Utc)
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+                duration_ms,
+                reasons: failure.clone(),
+                suspects,
+                omitted_suspects,
+            }
+        })
+        .collect();
+    // Fixed-width UTC timestamps sort chronologically
+    failed.sort_by(|a, b| {
+        a.first_seen
+            .cmp(&b.first_seen)
+            .then_with(|| a.session.cmp(&b.session))
+    });
+
+    Ok(ImpactReport {
+        level: level.config.name.clone(),
+        sessions: entries.len(),
+        threshold_ms,
+        failed,
+    })
+}
+
+pub fn format_impact_text(report: &ImpactReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "FAILED SESSION IMPACT: {} of {} {} session(s) failed",
+        report.failed.len(),
+        report.sessions,
+        report.level
+    );
+    if report.failed.is_empty() {
+        return out;
+    }
+    let _ = writeln!(
+        out,
+        "Suspects: ERROR patterns and operations ≥ {} ms, scored by closeness to the session end and share of sessions with them that failed",
+        report.threshold_ms
+    );
+
+    for session in &report.failed {
+        let _ = write!(out, "\n{}", session.session);
+        if let Some(name) = &session.display_name {
+            let _ = write!(out, " — {name}");
+        }
+        let _ = writeln!(
+            out,
+            "  {} → {} ({} ms)",
+            session.first_seen, session.last_seen, session.duration_ms
+        );
+        let _ = writeln!(out, "  failed: {}", session.reasons.join("; "));
+        if session.suspects.is_empty() {
+            let _ = writeln!(out, "  no errors or slow operations inside the session");
+            continue;
+        }
+        for (index, suspect) in session.suspects.iter().enumerate() {
+            let kind = match suspect.kind {
+                SuspectKind::Error => "error",
+                SuspectKind::SlowOperation => "slow ",
+            };
+            let repeat = if suspect.count > 1 {
+                format!(" ×{}", suspect.count)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                out,
+                "  #{:<2} {:.2}  {kind}  {}{repeat}",
+                index + 1,
+                suspect.score,
+                suspect.name
+            );
+            let _ = writeln!(
+                out,
+                "            line {}, {} ms before end; {} of {} session(s) with it failed",
+                suspect.first_line,
+                suspect.before_end_ms,
+                suspect.failed_sessions_with,
+                suspect.sessions_with
+            );
+        }
+        if session.omitted_suspects > 0 {
+            let _ = writeln!(
+                out,
+                "  ... {} more suspect(s) (use --top 0 to show all)",
+                session.omitted_suspects
+            );
+        }
+    }
+    out
+}
+
+pub fn format_impact_json(report: &ImpactReport) -> String {
+    serde_json::to_string_pretty(&json!({ "session_impact": report })).unwrap_or_else(|_| {
+        "{\"session_impact\":{\"error\":\"failed to serialize session impact\"}}".into()
+    })
+}
@@ -0,0 +1,406 @@
+use crate::health::HealthSummary;
+use crate::parser::LogEntry;
+use crate::units::{self, JsonUnits};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+/// High-level statistics used by `info --against` to show drift between two log sets
+#[derive(Debug, Clone, Default)]
+pub struct InfoSummary {
+    pub total_entries: usize,
+    pub components: BTreeMap<String, usize>,
+    pub levels: BTreeMap<String, usize>,
+    pub requests: BTreeMap<String, usize>,
+    pub payload_count: usize,
+    pub payload_total_bytes: usize,
+    pub payload_min_bytes: Option<usize>,
+    pub payload_max_bytes: Option<usize>,
+    pub time_span_ms: i64,
+}
+
+impl InfoSummary {
+    pub fn payload_avg_bytes(&self) -> f64 {
+        if self.payload_count == 0 {
+            0.0
+        } else {
+            self.payload_total_bytes as f64 / self.payload_count as f64
+        }
+    }
+}
+
+/// Unit of a delta row's values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowUnit {
+    Count,
+    Millis,
+    Bytes,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DeltaRow {
+    name: String,
+    before: f64,
+    after: f64,
+    unit: RowUnit,
+}
+
+impl DeltaRow {
+    fn new(name: impl Into<String>, before: f64, after: f64) -> Self {
+        Self {
+            name: name.into(),
+            before,
+            after,
+            unit: RowUnit::Count,
+        }
+    }
+
+    fn unit(mut self, unit: RowUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Row as shown in text output and legacy JSON: durations in seconds, named with the unit
+    fn in_seconds(&self) -> DeltaRow {
+        match self.unit {
+            RowUnit::Millis => DeltaRow::new(
+                format!("{} (s)", self.name),
+                self.before / 1000.0,
+                self.after / 1000.0,
+            ),
+            _ => self.clone(),
+        }
+    }
+
+    fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+
+    fn percent(&self) -> Option<f64> {
+        if self.before == 0.0 {
+            None
+        } else {
+            Some(self.delta() / self.before * 100.0)
+        }
+    }
+
+    fn arrow(&self) -> &'static str {
+        if self.after > self.before {
+            "↑"
+        } else if self.after < self.before {
+            "↓"
+        } else {
+            "="
+        }
+    }
+
+    fn to_json(&self, units: JsonUnits) -> Value {
+        let (unit, human): (&str, fn(i64) -> String) = match (units, self.unit) {
+            (JsonUnits::Standard, RowUnit::Millis) => ("ms", units::format_duration_ms),
+            (JsonUnits::Standard, RowUnit::Bytes) => {
+                ("bytes", |bytes| units::format_bytes(bytes.max(0) as u64))
+            }
+            _ => {
+                let row = self.in_seconds();
+                return json!({
+                    "name": row.name,
+                    "before": row.before,
+                    "after": row.after,
+                    "delta": row.delta(),
+                    "percent": row.percent(),
+                });
+            }
+        };
+        let (before, after) = (self.before.round() as i64, self.after.round() as i64);
+        json!({
+            "name": self.name,
+            "unit": unit,
+            "before": before,
+            "after": after,
+            "delta": after - before,
+            "percent": self.percent(),
+            "before_human": human(before),
+            "after_human": human(after),
+        })
+    }
+}
+
+pub fn build_info_summary(logs: &[LogEntry]) -> InfoSummary {
+    let mut summary = InfoSummary {
+        total_entries: logs.len(),
+        ..InfoSummary::default()
+    };
+
+    for log in logs {
+        *summary.components.entry(log.component.clone()).or_insert(0) += 1;
+        *summary.levels.entry(log.level.clone()).or_insert(0) += 1;
+
+        if let Some(request) = log.request_name() {
+            *summary.requests.entry(request.to_string()).or_insert(0) += 1;
+        }
+
+        if let Some(payload) = log.payload() {
+            let size = serde_json::to_string(payload).map(|s| s.len()).unwrap_or(0);
+            summary.payload_count += 1;
+            summary.payload_total_bytes += size;
+            summary.payload_min_bytes =
+                Some(summary.payload_min_bytes.map_or(size, |m| m.min(size)));
+            summary.payload_max_bytes =
+                Some(summary.payload_max_bytes.map_or(size, |m| m.max(size)));
+        }
+    }
+
+    if let (Some(first), Some(last)) = (
+        logs.iter().map(|log| log.timestamp).min(),
+        logs.iter().map(|log| log.timestamp).max(),
+    ) {
+        summary.time_span_ms = (last - first).num_milliseconds();
+    }
+
+    summary
+}
+
+fn count_rows(before: &BTreeMap<String, usize>, after: &BTreeMap<String, usize>) -> Vec<DeltaRow> {
+    let names: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    names
+        .into_iter()
+        .map(|name| {
+            DeltaRow::new(
+                name.clone(),
+                before.get(name).copied().unwrap_or(0) as f64,
+                after.get(name).copied().unwrap_or(0) as f64,
+            )
+        })
+        .collect()
+}
+
+fn total_rows(before: &InfoSummary, after: &InfoSummary) -> Vec<DeltaRow> {
+    vec![
+        DeltaRow::new(
+            "entries",
+            before.total_entries as f64,
+            after.total_entries as f64,
+        ),
+        DeltaRow::new(
+            "components",
+            before.components.len() as f64,
+            after.components.len() as f64,
+        ),
+        DeltaRow::new(
+            "time span",
+            before.time_span_ms as f64,
+            after.time_span_ms as f64,
+        )
+        .unit(RowUnit::Millis),
+    ]
+}
+
+fn payload_rows(before: &InfoSummary, after: &InfoSummary) -> Vec<DeltaRow> {
+    vec![
+        DeltaRow::new(
+            "count",
+            before.payload_count as f64,
+            after.payload_count as f64,
+        ),
+        DeltaRow::new(
+            "avg bytes",
+            before.payload_avg_bytes(),
+            after.payload_avg_bytes(),
+        )
+        .unit(RowUnit::Bytes),
+        DeltaRow::new(
+            "min bytes",
+            before.payload_min_bytes.unwrap_or(0) as f64,
+            after.payload_min_bytes.unwrap_or(0) as f64,
+        )
+        .unit(RowUnit::Bytes),
+        DeltaRow::new(
+            "max bytes",
+            before.payload_max_bytes.unwrap_or(0) as f64,
+            after.payload_max_bytes.unwrap_or(0) as f64,
+        )
+        .unit(RowUnit::Bytes),
+    ]
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+fn change_label(row: &DeltaRow) -> String {
+    if row.before == row.after {
+        return String::new();
+    }
+    if row.after == 0.0 {
+        return "(gone)".to_string();
+    }
+    match row.percent() {
+        Some(percent) => {
+            let sign = if row.delta() < 0.0 { "-" } else { "+" };
+            format!(
+                "{sign}{} ({percent:+.1}%)",
+                format_number(row.delta().abs())
+            )
+        }
+        None if row.after > 0.0 => "(new)".to_string(),
+        None => String::new(),
+    }
+}
+
+fn format_row(out: &mut String, row: &DeltaRow) {
+    let row = &row.in_seconds();
+    let _ = writeln!(
+        out,
+        "  {:<32} {:>10} → {:<10} {} {}",
+        row.name,
+        format_number(row.before),
+        format_number(row.after),
+        row.arrow(),
+        change_label(row)
+    );
+}
+
+fn format_section(out: &mut String, title: &str, rows: &[DeltaRow], skip_unchanged: bool) {
+    let changed: Vec<_> = rows
+        .iter()
+        .filter(|row| !skip_unchanged || row.before != row.after)
+        .collect();
+    let unchanged = rows.len() - changed.len();
+
+    let _ = writeln!(out, "\n{title}:");
+    if changed.is_empty() {
+        let _ = writeln!(out, "  (no changes)");
+        return;
+    }
+    for row in changed {
+        format_row(out, row);
+    }
+    if unchanged > 0 {
+        let _ = writeln!(out, "  ... {unchanged} unchanged");
+    }
+}
+
+/// Text for `info --summary-only`: entry, component, and level totals plus the time span
+pub fn format_info_summary_text(summary: &InfoSummary) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Total log entries: {} across {} component{}, spanning {}",
+        summary.total_entries,
+        summary.components.len(),
+        if summary.components.len() == 1 {
+            ""
+        } else {
+            "s"
+        },
+        units::format_duration_ms(summary.time_span_ms)
+    );
+    if !summary.levels.is_empty() {
+        let levels: Vec<String> = summary
+            .levels
+            .iter()
+            .map(|(level, count)| format!("{level} {count}"))
+            .collect();
+        let _ = writeln!(out, "Levels: {}", levels.join(", "));
+    }
+    out
+}
+
+/// JSON for `info --summary-only`, with the health verdict alongside the totals
+pub fn format_info_summary_json(summary: &InfoSummary, health: &HealthSummary) -> String {
+    serde_json::to_string_pretty(&json!({
+        "info": {
+            "health": health,
+            "summary": {
+                "total_entries": summary.total_entries,
+                "components_count": summary.components.len(),
+                "levels": summary.levels,
+                "time_span_ms": summary.time_span_ms,
+            },
+        }
+    }))
+    .unwrap_or_else(|_| "{\"info\":{\"error\":\"failed to serialize info summary\"}}".into())
+}
+
+/// With `summary_only`, only the totals section is rendered
+pub fn format_info_delta_text(
+    before_label: &str,
+    before: &InfoSummary,
+    after_label: &str,
+    after: &InfoSummary,
+    summary_only: bool,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Info delta: {before_label} → {after_label}");
+
+    format_section(&mut out, "Totals", &total_rows(before, after), false);
+    if summary_only {
+        return out;
+    }
+    format_section(
+        &mut out,
+        "Levels",
+        &count_rows(&before.levels, &after.levels),
+        true,
+    );
+    format_section(
+        &mut out,
+        "Components",
+        &count_rows(&before.components, &after.components),
+        true,
+    );
+    format_section(
+        &mut out,
+        "Requests",
+        &count_rows(&before.requests, &after.requests),
+        true,
+    );
+    format_section(&mut out, "Payloads", &payload_rows(before, after), false);
+
+    out
+}
+
+pub fn format_info_delta_json(
+    before_label: &str,
+    before: &InfoSummary,
+    after_label: &str,
+    after: &InfoSummary,
+    units: JsonUnits,
+    summary_only: bool,
+) -> String {
+    let rows_json = |rows: Vec<DeltaRow>| {
+        rows.iter()
+            .map(|row| row.to_json(units))
+            .collect::<Vec<Value>>()
+    };
+
+    if summary_only {
+        return serde_json::to_string_pretty(&json!({
+            "info_delta": {
+                "before": before_label,
+                "after": after_label,
+                "totals": rows_json(total_rows(before, after)),
+            }
+        }))
+        .unwrap_or_else(|_| {
+            "{\"info_delta\":{\"error\":\"failed to serialize info delta\"}}".into()
+        });
+    }
+
+    serde_json::to_string_pretty(&json!({
+        "info_delta": {
+            "before": before_label,
+            "after": after_label,
+            "totals": rows_json(total_rows(before, after)),
+            "levels": rows_json(count_rows(&before.levels, &after.levels)),
+            "components": rows_json(count_rows(&before.components, &after.components)),
+            "requests": rows_json(count_rows(&before.requests, &after.requests)),
+            "payloads": rows_json(payload_rows(before, after)),
+        }
+    }))
+    .unwrap_or_else(|_| "{\"info_delta\":{\"error\":\"failed to serialize info delta\"}}".into())
+}
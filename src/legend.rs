@@ -0,0 +1,184 @@
+use serde_json::{Map, Value};
+
+/// Version of the abbreviated-key layouts (compact comparison JSON and `process` output).
+/// Bumped whenever a short key is renamed, removed, or changes meaning.
+pub const COMPACT_SCHEMA_VERSION: u64 = 2;
+
+/// Short keys of compact comparison JSON, grouped by where they appear.
+/// Scopes are paths from the document root with `[]` for array elements; `""` is the root.
+const COMPACT_DIFF_KEYS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "",
+        &[
+            ("schema_version", "schema_version"),
+            ("legend", "legend"),
+            ("s", "summary"),
+            ("u1", "unique_to_log1"),
+            ("u2", "unique_to_log2"),
+            ("c", "comparisons"),
+            ("ld", "level_drift"),
+            ("pe", "payload_errors"),
+            ("td", "truncated_subtrees"),
+            ("tds", "trivial_differences_suppressed"),
+            ("mi", "most_interesting_differences"),
+            ("idk", "identical_counts"),
+        ],
+    ),
+    (
+        "s",
+        &[
+            ("u1c", "unique_to_log1_count"),
+            ("u2c", "unique_to_log2_count"),
+            ("sc", "shared_count"),
+            ("dc", "differences_count"),
+            ("hd", "has_differences"),
+            ("idc", "identical_count"),
+            ("scc", "schema_changes_count"),
+        ],
+    ),
+    (
+        "u1[], u2[], c[].k",
+        &[
+            ("i", "index"),
+            ("c", "component"),
+            ("l", "level"),
+            ("k", "kind"),
+            ("d", "details"),
+            ("r", "raw_key"),
+        ],
+    ),
+    ("u1[], u2[]", &[("src", "sources")]),
+    (
+        "c[]",
+        &[
+            ("k", "key"),
+            ("i", "instances"),
+            ("ic", "instance_count"),
+            ("idc", "identical_count"),
+            ("d", "differences"),
+        ],
+    ),
+    (
+        "c[].i[]",
+        &[
+            ("l1", "log1_index"),
+            ("l2", "log2_index"),
+            ("t1", "text1"),
+            ("t2", "text2"),
+            ("ln1", "log1_line"),
+            ("ln2", "log2_line"),
+            ("s1", "log1_source"),
+            ("s2", "log2_source"),
+            ("dc", "diff_count"),
+            ("cs", "cluster_sizes"),
+        ],
+    ),
+    (
+        "c[].i[].s1, c[].i[].s2, u1[].src[], u2[].src[]",
+        &[
+            ("id", "<file_index>:<line>, unique across both files"),
+            ("f", "file_index"),
+            ("ln", "line"),
+            ("ts", "timestamp"),
+            ("rid", "request_id"),
+            ("raw", "raw log text"),
+        ],
+    ),
+    (
+        "c[].d[]",
+        &[
+            ("p", "path"),
+            (
+                "vs",
+                "values, one per instance with a difference at the path",
+            ),
+            ("sc", "schema_change (field_added or field_removed)"),
+        ],
+    ),
+    (
+        "c[].d[].vs[]",
+        &[
+            ("n", "instance (position in c[].i)"),
+            ("v1", "value1"),
+            ("v2", "value2"),
+            ("ln", "[log1_line, log2_line]"),
+            ("ts", "[log1_timestamp, log2_timestamp]"),
+            ("d", "numeric delta"),
+        ],
+    ),
+    (
+        "ld[]",
+        &[
+            ("c", "component"),
+            ("n", "[total1, total2] entries"),
+            ("l", "level -> [count1, count2]"),
+        ],
+    ),
+    (
+        "mi[]",
+        &[
+            ("k", "key"),
+            ("p", "path"),
+            ("v1", "value1"),
+            ("v2", "value2"),
+            ("ln", "[log1_line, log2_line]"),
+            ("pf", "path_frequency"),
+            ("r", "rarity"),
+        ],
+    ),
+];
+
+/// Short keys and codes of `process` (`llm`) output
+const LLM_LOG_KEYS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "logs[]",
+        &[
+            ("idx", "index (1-based)"),
+            ("ts", "timestamp (time of day)"),
+            ("at", "timestamp (RFC3339, UTC)"),
+            ("ms", "timestamp (epoch milliseconds)"),
+            (
+                "dur",
+                "duration of the paired operation the entry starts or ends (ms)",
+            ),
+            ("comp", "component"),
+            ("lvl", "level"),
+            ("typ", "entry type"),
+            ("msg", "message"),
+            ("data", "payload (sanitized, nested depth capped)"),
+        ],
+    ),
+    (
+        "logs[].typ",
+        &[
+            ("E:<direction>:<type>", "event"),
+            ("C:<command>", "command"),
+            ("R:<direction>:<request>", "request"),
+            ("G", "generic entry"),
+        ],
+    ),
+];
+
+fn to_legend(scopes: &[(&str, &[(&str, &str)])]) -> Value {
+    scopes
+        .iter()
+        .map(|(scope, keys)| {
+            let names: Map<String, Value> = keys
+                .iter()
+                .map(|(short, full)| (short.to_string(), Value::from(*full)))
+                .collect();
+            (scope.to_string(), Value::Object(names))
+        })
+        .collect::<Map<_, _>>()
+        .into()
+}
+
+/// Legend for compact comparison JSON: scope -> short key -> full name
+pub fn compact_diff_legend() -> Value {
+    to_legend(COMPACT_DIFF_KEYS)
+}
+
+/// Legend for `process` output: scope -> short key -> full name
+pub fn llm_log_legend() -> Value {
+    to_legend(LLM_LOG_KEYS)
+}
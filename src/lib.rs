@@ -1,42 +1,96 @@
+pub mod analysis_context;
+pub mod chronology;
 pub mod cli;
 pub mod comparator;
 pub mod config;
+pub mod config_effect;
 pub mod config_generator;
+pub mod contracts;
+pub mod count;
+pub mod diagnostics;
+pub mod dupes;
 pub mod errors;
 pub mod extract;
+pub mod field_matrix;
+pub mod file_status;
 pub mod filter;
+pub mod fixtures;
+pub mod health;
+pub mod heartbeats;
+pub mod ignore;
+pub mod impact;
+pub mod info_diff;
+pub mod legend;
 pub mod llm_processor;
+pub mod matrix;
+pub mod output;
 pub mod parser;
+pub mod payload_view;
 pub mod perf_analyzer;
+pub mod render;
+pub mod report;
+pub mod runs;
+pub mod schema_cache;
 pub mod search;
+pub mod session_path;
+pub mod show_diff;
+pub mod similar;
+pub mod slo;
+pub mod story;
+pub mod tags;
+pub mod tickets;
+pub mod time_buckets;
 pub mod trace;
+pub mod typed_fields;
+pub mod units;
 
+use analysis_context::AnalysisContext;
 pub use cli::{
-    ColorMode, Commands, ErrorsSortBy, OutputFormat, SearchCountBy, SortOrder, cli_parse,
+    Cli, ColorMode, Commands, ErrorsSortBy, OutputFormat, RelativeTo, RenderFormat, SearchCountBy,
+    SortOrder, cli_parse,
 };
 pub use comparator::{
     ComparisonOptions, compare_json, compare_logs, display_comparison_results, generate_json_output,
 };
-use comparator::{LogFilter, display_log_summary};
-use errors::{ErrorsOptions, analyze_errors_with_config, format_errors_json, format_errors_text};
-use extract::{format_extract_json, format_extract_text};
+use comparator::{LogFilter, TimeReference, display_log_summary, display_schema_report};
+use diagnostics::{WarningCode, WarningLog};
+use errors::{ErrorsOptions, analyze_errors_in_context, format_errors_json, format_errors_text};
+use extract::{
+    ExtractSide, format_extract_delta_json, format_extract_delta_text, format_extract_json,
+    format_extract_text,
+};
+use file_status::{FileStatus, FileStatusLog, format_file_status_text};
 use filter::{FilterExpression, print_filter_warnings, to_log_filter};
+use info_diff::{
+    build_info_summary, format_info_delta_json, format_info_delta_text, format_info_summary_json,
+    format_info_summary_text,
+};
 pub use parser::{
     LogEntry, LogEntryKind, ParseError, detect_log_format, parse_log_entry,
-    parse_log_entry_with_config, parse_log_file, parse_log_file_with_config,
+    parse_log_entry_with_config, parse_log_file, parse_log_file_reporting,
+    parse_log_file_with_config,
 };
+use parser::{LogEntryReader, PayloadParseErrors, ReadScope, find_payload_parse_failures};
+use render::RenderOptions;
 use search::{
-    collect_match_indices, format_search_count_json, format_search_count_text, format_search_json,
-    format_search_text,
+    collect_match_indices, format_search_count_json, format_search_count_text,
+    format_search_diff_json, format_search_diff_text, format_search_json, format_search_text,
 };
+use std::io::IsTerminal;
+use std::time::Instant;
+use time_buckets::TimeBucketOptions;
 use trace::{TraceSelector, collect_trace_entries, format_trace_json, format_trace_text};
+use typed_fields::TypedFields;
 
 /// Build a LogFilter from the --filter expression
-fn build_filter(filter_expr: &Option<String>) -> Result<LogFilter, Box<dyn std::error::Error>> {
+fn build_filter(
+    filter_expr: &Option<String>,
+    warnings: &WarningLog,
+) -> Result<LogFilter, Box<dyn std::error::Error>> {
     if let Some(expr_str) = filter_expr {
         let expr = FilterExpression::parse(expr_str)
             .map_err(|e| format!("Invalid filter expression: {}", e))?;
-        print_filter_warnings(&expr);
+        print_filter_warnings(&expr, warnings);
         Ok(to_log_filter(&expr))
     } else {
         Ok(LogFilter::new())
@@ -156,55 +210,503 @@ fn write_output_file(
     path: &std::path::Path,
     content: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    std::fs::write(path, content)
+    output::write_output(path, content.as_bytes())
         .map_err(|e| format!("Failed to write output file '{}': {}", path.display(), e).into())
 }
 
+/// Load a config from a TOML path, falling back to a built-in profile name
+fn load_named_config(
+    path: &std::path::Path,
+) -> Result<config::AnalyzerConfig, Box<dyn std::error::Error>> {
+    if path.exists() {
+        return config::load_config_from_path(path)
+            .map_err(|e| format!("Failed to load config '{}': {}", path.display(), e).into());
+    }
+    config::load_builtin_template(&path.to_string_lossy()).ok_or_else(|| {
+        format!(
+            "Config '{}' not found as file path or built-in profile. Built-ins: {}",
+            path.display(),
+            config::builtin_template_names().join(", ")
+        )
+        .into()
+    })
+}
+
+/// Rewrites `*_ms` / `*_bytes` fields of a JSON report to whole numbers with `*_human`
+/// companions, unless `--legacy-json-units` asked for the old shapes
+fn standardize_units(
+    rendered: String,
+    format: OutputFormat,
+    json_units: units::JsonUnits,
+) -> String {
+    if !json_units.is_standard() || !matches!(format, OutputFormat::Json) {
+        return rendered;
+    }
+    let Ok(mut document) = serde_json::from_str::<serde_json::Value>(&rendered) else {
+        return rendered;
+    };
+    units::standardize_json_units(&mut document);
+    reserialize_like(rendered, &document)
+}
+
+/// Serializes `document` in the layout (pretty or compact, trailing newline) of `rendered`,
+/// falling back to `rendered` itself
+fn reserialize_like(rendered: String, document: &serde_json::Value) -> String {
+    let serialized = if rendered.trim_end().contains('\n') {
+        serde_json::to_string_pretty(document)
+    } else {
+        serde_json::to_string(document)
+    };
+    match serialized {
+        Ok(json) if rendered.ends_with('\n') => format!("{json}\n"),
+        Ok(json) => json,
+        Err(_) => rendered,
+    }
+}
+
+/// `meta` key noting the `--lines` / `--bytes` slice a report was built from
+const SCOPE_META_KEY: &str = "scope";
+
+/// Embeds `--meta` pairs as a top-level "meta" object, and the warnings emitted so far as
+/// a "warnings" array, in a rendered JSON document, keeping its compact or pretty layout.
+/// Text output only gets a leading line naming the read scope, if any.
+fn attach_meta(
+    rendered: String,
+    format: OutputFormat,
+    meta: &[(String, String)],
+    warnings: &WarningLog,
+) -> String {
+    use serde_json::{Map, Value};
+
+    if !matches!(format, OutputFormat::Json) {
+        return match meta.iter().find(|(key, _)| key == SCOPE_META_KEY) {
+            Some((_, scope)) => format!("Scope: {scope} of each file\n{rendered}"),
+            None => rendered,
+        };
+    }
+    let warnings = warnings.warnings();
+    if meta.is_empty() && warnings.is_empty() {
+        return rendered;
+    }
+    let Ok(Value::Object(mut document)) = serde_json::from_str::<Value>(&rendered) else {
+        return rendered;
+    };
+
+    if !meta.is_empty() {
+        let meta: Map<String, Value> = meta
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect();
+        document.insert("meta".to_string(), Value::Object(meta));
+    }
+    if !warnings.is_empty() {
+        document.insert(
+            "warnings".to_string(),
+            serde_json::to_value(&warnings).unwrap_or_default(),
+        );
+    }
+
+    reserialize_like(rendered, &Value::Object(document))
+}
+
 fn parse_and_merge_log_files_with_config(
     files: &[std::path::PathBuf],
     analyzer_config: &config::AnalyzerConfig,
+    file_statuses: &FileStatusLog,
+    warnings: &WarningLog,
 ) -> Result<Vec<LogEntry>, Box<dyn std::error::Error>> {
+    parse_and_merge_tracking_payload_errors(files, analyzer_config, file_statuses, warnings)
+        .map(|(logs, _)| logs)
+}
+
+/// Like [`parse_and_merge_log_files_with_config`], also returning the payloads of
+/// each file that failed to parse. Each file's status goes to `file_statuses`.
+fn parse_and_merge_tracking_payload_errors(
+    files: &[std::path::PathBuf],
+    analyzer_config: &config::AnalyzerConfig,
+    file_statuses: &FileStatusLog,
+    warnings: &WarningLog,
+) -> Result<(Vec<LogEntry>, Vec<PayloadParseErrors>), Box<dyn std::error::Error>> {
     let mut logs = Vec::new();
+    let mut payload_errors = Vec::new();
 
     for file in files {
-        let mut parsed = parse_log_file_with_config(file, analyzer_config)
+        let started = Instant::now();
+        let (mut parsed, skipped_lines) = LogEntryReader::open(file, analyzer_config)
+            .map(|reader| reader.reporting_to(warnings))
+            .and_then(|mut reader| {
+                let parsed = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+                Ok((parsed, reader.skipped_lines()))
+            })
             .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?;
+        file_statuses.record(FileStatus::new(
+            file,
+            &parsed,
+            skipped_lines,
+            started.elapsed(),
+        ));
+        let failures = find_payload_parse_failures(&parsed, &analyzer_config.parser);
+        if !failures.is_empty() {
+            record_payload_failures(file, failures.len(), warnings);
+            payload_errors.push(PayloadParseErrors {
+                file: file.display().to_string(),
+                failures,
+            });
+        }
         logs.append(&mut parsed);
     }
 
     logs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    Ok((logs, payload_errors))
+}
+
+fn record_payload_failures(file: &std::path::Path, count: usize, warnings: &WarningLog) {
+    warnings.record(
+        WarningCode::PayloadParseFailures,
+        format!("{count} payload(s) in '{}' failed to parse", file.display()),
+    );
+}
+
+fn print_payload_errors(payload_errors: &[PayloadParseErrors], all: bool) {
+    if payload_errors.is_empty() {
+        return;
+    }
+    println!("\nPayload parse failures:");
+    for file_errors in payload_errors {
+        println!(
+            "  {}: {} payload(s) failed to parse",
+            file_errors.file,
+            file_errors.failures.len()
+        );
+        for failure in file_errors.samples(all) {
+            println!("    line {}: {}", failure.line, failure.snippet);
+        }
+        let hidden = file_errors.failures.len() - file_errors.samples(all).len();
+        if hidden > 0 {
+            println!("    ... +{hidden} more (use --payload-errors to list all)");
+        }
+    }
+}
+
+/// Parses one side of a comparison, recording its payload parse failures
+fn parse_for_compare(
+    file: &std::path::Path,
+    analyzer_config: &config::AnalyzerConfig,
+    prepare: &impl Fn(&mut LogEntry),
+    payload_errors: &mut Vec<PayloadParseErrors>,
+    warnings: &WarningLog,
+) -> Result<Vec<LogEntry>, Box<dyn std::error::Error>> {
+    let mut logs = parse_log_file_reporting(file, analyzer_config, warnings)
+        .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?;
+    logs.iter_mut().for_each(prepare);
+    let failures = find_payload_parse_failures(&logs, &analyzer_config.parser);
+    record_compare_failures(file, failures, payload_errors, warnings);
     Ok(logs)
 }
 
+fn record_compare_failures(
+    file: &std::path::Path,
+    failures: Vec<parser::PayloadParseFailure>,
+    payload_errors: &mut Vec<PayloadParseErrors>,
+    warnings: &WarningLog,
+) {
+    if !failures.is_empty() {
+        record_payload_failures(file, failures.len(), warnings);
+        payload_errors.push(PayloadParseErrors {
+            file: file.display().to_string(),
+            failures,
+        });
+    }
+}
+
+/// Reads one side of a comparison straight into a [`comparator::GroupSpiller`], so at
+/// most one sorted run of parsed entries is in memory, recording its payload parse failures
+#[allow(clippy::too_many_arguments)]
+fn spill_for_compare(
+    file: &std::path::Path,
+    analyzer_config: &config::AnalyzerConfig,
+    prepare: &impl Fn(&mut LogEntry),
+    filter: &LogFilter,
+    chunk_bytes: u64,
+    payload_errors: &mut Vec<PayloadParseErrors>,
+    warnings: &WarningLog,
+) -> Result<comparator::SpilledGroups, Box<dyn std::error::Error>> {
+    let parse_error = |e| format!("Failed to parse log file '{}': {:?}", file.display(), e);
+    let mut spiller = comparator::GroupSpiller::new(filter, chunk_bytes);
+    let mut failures = Vec::new();
+    for log in LogEntryReader::open(file, analyzer_config)
+        .map_err(parse_error)?
+        .reporting_to(warnings)
+    {
+        let mut log = log.map_err(parse_error)?;
+        prepare(&mut log);
+        failures.extend(find_payload_parse_failures(
+            std::slice::from_ref(&log),
+            &analyzer_config.parser,
+        ));
+        spiller
+            .push(log)
+            .map_err(|e| format!("Failed to spill grouped entries: {:?}", e))?;
+    }
+    record_compare_failures(file, failures, payload_errors, warnings);
+    Ok(spiller
+        .finish()
+        .map_err(|e| format!("Failed to spill grouped entries: {:?}", e))?)
+}
+
+/// One side of a comparison: parsed entries, or entries grouped and spilled to disk
+enum CompareSide {
+    Parsed(Vec<LogEntry>),
+    Spilled(comparator::SpilledGroups),
+}
+
+/// Compares the sides returned by `side(0)` and `side(1)`, in memory when both are parsed
+/// and as a merge over the spilled files otherwise
+fn compare_entries(
+    mut side: impl FnMut(usize) -> Result<CompareSide, Box<dyn std::error::Error>>,
+    filter: &LogFilter,
+    options: &ComparisonOptions,
+    warnings: &WarningLog,
+) -> Result<comparator::ComparisonResults, Box<dyn std::error::Error>> {
+    let spill = |side: CompareSide| match side {
+        CompareSide::Spilled(groups) => Ok(groups),
+        // Already in memory, so grouped as a single run
+        CompareSide::Parsed(logs) => comparator::spill_log_groups(logs, filter, u64::MAX)
+            .map_err(|e| format!("Failed to spill grouped entries: {:?}", e)),
+    };
+    let results = match (side(0)?, side(1)?) {
+        (CompareSide::Parsed(logs1), CompareSide::Parsed(logs2)) => {
+            compare_logs(&logs1, &logs2, filter, options)
+                .map_err(|e| format!("Comparison failed: {:?}", e))?
+        }
+        (side1, side2) => {
+            let groups1 = spill(side1)?;
+            let groups2 = spill(side2)?;
+            comparator::compare_spilled_groups(&groups1, &groups2, options)
+                .map_err(|e| format!("Comparison failed: {:?}", e))?
+        }
+    };
+    if results.truncated_subtrees > 0 {
+        warnings.record(
+            WarningCode::TruncatedComparison,
+            format!(
+                "{} payload subtree(s) were compared as opaque blobs below the max JSON depth",
+                results.truncated_subtrees
+            ),
+        );
+    }
+    Ok(results)
+}
+
+fn note_spilling(options: &ComparisonOptions) {
+    if options.verbosity > 0 && !options.quiet {
+        eprintln!("Estimated memory exceeds --max-memory; spilling grouped entries to disk");
+    }
+}
+
+/// Parse and compare two log files. With a memory budget that the estimated
+/// footprint exceeds, each side is grouped and spilled to disk while it is read and
+/// the comparison runs as a merge over the spilled files.
+#[allow(clippy::too_many_arguments)]
+fn compare_log_files(
+    file1: &std::path::Path,
+    file2: &std::path::Path,
+    analyzer_config: &config::AnalyzerConfig,
+    filter: &LogFilter,
+    options: &ComparisonOptions,
+    max_memory: Option<u64>,
+    prepare: impl Fn(&mut LogEntry),
+    warnings: &WarningLog,
+) -> Result<comparator::ComparisonResults, Box<dyn std::error::Error>> {
+    let mut payload_errors = Vec::new();
+    let spill_budget =
+        max_memory.filter(|&budget| comparator::exceeds_memory_budget(&[file1, file2], budget));
+    if spill_budget.is_some() {
+        note_spilling(options);
+    }
+    let mut parse = |file: &std::path::Path| -> Result<CompareSide, Box<dyn std::error::Error>> {
+        // `!within:` windows, `--run` and the log end resolve against the whole file,
+        // so only a filter without them lets the side be spilled as it is read
+        if let Some(budget) = spill_budget
+            && !filter.needs_resolution()
+        {
+            return Ok(CompareSide::Spilled(spill_for_compare(
+                file,
+                analyzer_config,
+                &prepare,
+                filter,
+                comparator::spill_chunk_bytes(budget),
+                &mut payload_errors,
+                warnings,
+            )?));
+        }
+        let mut logs = parse_for_compare(
+            file,
+            analyzer_config,
+            &prepare,
+            &mut payload_errors,
+            warnings,
+        )?;
+        if filter.needs_resolution() {
+            // Resolve `!within:` windows, `--run` and the log end against this file's own entries
+            let resolved = filter.resolve_windows(&logs, warnings);
+            logs.retain(|log| resolved.matches(log));
+        }
+        Ok(match spill_budget {
+            Some(budget) => CompareSide::Spilled(
+                comparator::spill_log_groups(logs, filter, comparator::spill_chunk_bytes(budget))
+                    .map_err(|e| format!("Failed to spill grouped entries: {:?}", e))?,
+            ),
+            None => CompareSide::Parsed(logs),
+        })
+    };
+
+    let mut results = compare_entries(
+        |side| parse([file1, file2][side]),
+        filter,
+        options,
+        warnings,
+    )?;
+    results.payload_errors = payload_errors;
+    Ok(results)
+}
+
+/// Parse one log file and compare its entries before `point` with those from `point` on
+fn compare_log_halves(
+    file: &std::path::Path,
+    point: &comparator::SplitPoint,
+    analyzer_config: &config::AnalyzerConfig,
+    filter: &LogFilter,
+    options: &ComparisonOptions,
+    max_memory: Option<u64>,
+    warnings: &WarningLog,
+) -> Result<(comparator::ComparisonResults, comparator::LogSplit), Box<dyn std::error::Error>> {
+    let mut payload_errors = Vec::new();
+    let logs = parse_for_compare(
+        file,
+        analyzer_config,
+        &|_| {},
+        &mut payload_errors,
+        warnings,
+    )?;
+    // Windows and `--run` resolve against the whole file, before it is cut
+    let resolved = filter
+        .needs_resolution()
+        .then(|| filter.resolve_windows(&logs, warnings));
+    let (before, after, split) = comparator::split_logs(logs, point)?;
+    let mut halves = [before, after];
+    if let Some(resolved) = &resolved {
+        for half in &mut halves {
+            half.retain(|log| resolved.matches(log));
+        }
+    }
+    let spill_budget =
+        max_memory.filter(|&budget| comparator::exceeds_memory_budget(&[file], budget));
+    if spill_budget.is_some() {
+        note_spilling(options);
+    }
+    // The halves are cut from one parsed file, so the first is spilled before the
+    // second is compared against it
+    let mut results = compare_entries(
+        |side| {
+            let half = std::mem::take(&mut halves[side]);
+            Ok(match spill_budget {
+                Some(budget) => CompareSide::Spilled(
+                    comparator::spill_log_groups(
+                        half,
+                        filter,
+                        comparator::spill_chunk_bytes(budget),
+                    )
+                    .map_err(|e| format!("Failed to spill grouped entries: {:?}", e))?,
+                ),
+                None => CompareSide::Parsed(half),
+            })
+        },
+        filter,
+        options,
+        warnings,
+    )?;
+    results.payload_errors = payload_errors;
+    Ok((results, split))
+}
+
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = cli_parse();
-    let analyzer_config = config::load_config(cli.config.as_deref(), cli.preset.as_deref())
-        .map_err(|e| format!("Failed to load config: {}", e))?;
+    run_cli(&cli_parse(), None)
+}
+
+/// Runs one parsed command line. `profile` replaces the profile that `--config`/`--preset`
+/// would load, so callers running several analyses in one process can give each its own.
+pub fn run_cli(
+    cli: &Cli,
+    profile: Option<config::AnalyzerConfig>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let file_statuses = FileStatusLog::default();
+    let warnings = WarningLog::default();
+    let result = run_command(cli, profile, &file_statuses, &warnings);
+
+    // Per-file status of multi-file runs goes to stderr, on a terminal or with --verbose
+    let statuses = file_statuses.statuses();
+    if statuses.len() > 1 && !cli.quiet && (cli.verbose > 0 || std::io::stderr().is_terminal()) {
+        let render = RenderOptions::new(cli.color.into());
+        eprint!("{}", format_file_status_text(&statuses, &render));
+    }
+    result
+}
+
+fn run_command(
+    cli: &Cli,
+    profile: Option<config::AnalyzerConfig>,
+    file_statuses: &FileStatusLog,
+    warnings: &WarningLog,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(code) = &cli.explain {
+        let Some(code) = WarningCode::from_code(code) else {
+            return Err(format!(
+                "Unknown warning code '{code}'. Known codes:\n{}",
+                diagnostics::format_catalog()
+            )
+            .into());
+        };
+        print!("{}", diagnostics::format_explanation(code));
+        return Ok(());
+    }
+    let command = cli
+        .command
+        .as_ref()
+        .ok_or("a subcommand is required unless --explain is given")?;
+    let mut analyzer_config = match profile {
+        Some(profile) => profile,
+        None => config::load_config(cli.config.as_deref(), cli.preset.as_deref())
+            .map_err(|e| format!("Failed to load config: {}", e))?,
+    };
+    if let Some(depth) = cli.max_json_depth {
+        analyzer_config.comparison.max_json_depth = Some(depth);
+    }
+    analyzer_config.parser.scope = ReadScope {
+        lines: cli.lines,
+        bytes: cli.bytes,
+    };
     let format = cli.effective_format();
     let compact = cli.effective_compact();
     let output = &cli.output;
     let color_mode = cli.color;
     let verbose = cli.verbose;
     let quiet = cli.quiet;
-
-    // Set up color handling based on user preference
-    match color_mode {
-        ColorMode::Always => {
-            // Force colors on
-            unsafe {
-                std::env::set_var("CLICOLOR_FORCE", "1");
-            }
-        }
-        ColorMode::Never => {
-            // Disable colors
-            unsafe {
-                std::env::set_var("NO_COLOR", "1");
-            }
-        }
-        ColorMode::Auto => {
-            // Default behavior - let the terminal decide
-        }
+    let max_memory = cli.max_memory;
+    let mut meta = cli.meta.clone();
+    if let Some(scope) = analyzer_config.parser.scope.describe() {
+        meta.push((SCOPE_META_KEY.to_string(), scope));
     }
+    let meta = &meta;
+    let json_units = units::JsonUnits::from_legacy_flag(cli.legacy_json_units);
+    let ignore_rules = if cli.no_ignore_file {
+        ignore::IgnoreRules::default()
+    } else {
+        ignore::load_ignore_rules()?
+    };
+
+    // Colour is decided per run, so concurrent library callers do not affect each other
+    let render = RenderOptions::new(color_mode.into());
 
     // If in verbose mode, display some diagnostic information
     if verbose > 0 && !quiet {
@@ -217,49 +719,160 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             eprintln!("Filter: {}", filter_expr);
         }
         eprintln!("Config profile: {}", analyzer_config.profile_name);
+        if let Some(scope) = analyzer_config.parser.scope.describe() {
+            eprintln!("Read scope: {scope} of each file");
+        }
         if let Some(config_path) = &cli.config {
             eprintln!("Config file: {}", config_path.display());
         }
         if let Some(preset) = &cli.preset {
             eprintln!("Config preset: {}", preset);
         }
+        if let Some(ignore_path) = &ignore_rules.source {
+            eprintln!("Ignore file: {}", ignore_path.display());
+        }
     }
 
-    // Build the filter from the global --filter expression
-    let filter = build_filter(&cli.filter)?;
+    // Build the filter from the global --filter expression plus ignore-file filters
+    let filter = build_filter(
+        &ignore_rules.filter_expression(cli.filter.as_deref()),
+        warnings,
+    )?
+    .select_run(cli.run.map(|run| run as usize), &analyzer_config.runs)
+    .relative_to(match cli.relative_to {
+        RelativeTo::Now => TimeReference::At(cli.now.unwrap_or_else(chrono::Local::now)),
+        RelativeTo::LogEnd => TimeReference::LogEnd,
+    });
 
-    match &cli.command {
+    match command {
         Commands::Compare {
             file1,
             file2,
+            split_at,
             diff_only,
             full,
             sort_by,
+            only_keys,
+            skip_keys,
+            cluster_payloads,
+            level_drift_threshold,
+            interesting,
+            ignore_case,
+            ignore_whitespace,
+            learn_noise,
+            pairs,
+            save,
         } => {
-            // Parse log files with proper error handling
-            let logs1 = parse_log_file_with_config(file1, &analyzer_config)
-                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file1.display(), e))?;
-
-            let logs2 = parse_log_file_with_config(file2, &analyzer_config)
-                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file2.display(), e))?;
-
             // Create options
             let options = ComparisonOptions::new()
+                .render(render)
+                .scope(analyzer_config.parser.scope.describe())
                 .diff_only(*diff_only)
                 .show_full_json(*full)
                 .compact_mode(compact)
                 .readable_mode(true)
                 .sort_by(*sort_by)
                 .verbosity(verbose)
-                .quiet_mode(quiet);
+                .quiet_mode(quiet)
+                .only_keys(only_keys.clone())
+                .skip_keys(skip_keys.clone())
+                .cluster_payloads(*cluster_payloads)
+                .level_drift_threshold(*level_drift_threshold)
+                .interesting_limit(*interesting)
+                .ignore_case(*ignore_case)
+                .ignore_whitespace(*ignore_whitespace)
+                .ignore_paths(ignore_rules.paths.clone())
+                .array_identity_keys(analyzer_config.comparison.identity_keys())
+                .max_json_depth(analyzer_config.comparison.max_json_depth)
+                .all_payload_errors(cli.payload_errors)
+                .include_legend(cli.legend)
+                .summary_only(cli.summary_only)
+                .max_array_elements(cli.max_array_elements)
+                .raw_limit(cli.raw_excerpt_limit());
+
+            if let Some(expected) = learn_noise {
+                let given = pairs.len() / 2 + 1;
+                if given as u64 != *expected {
+                    return Err(format!(
+                        "--learn-noise {expected} expects {expected} log pairs, got {given} (FILE1 FILE2 plus {} --pair)",
+                        given - 1
+                    )
+                    .into());
+                }
+                let file2 = file2.as_ref().ok_or("--learn-noise requires FILE2")?;
+                let mut learned = Vec::with_capacity(given);
+                for (base, rerun) in std::iter::once((file1, file2))
+                    .chain(pairs.chunks_exact(2).map(|pair| (&pair[0], &pair[1])))
+                {
+                    learned.push(compare_log_files(
+                        base,
+                        rerun,
+                        &analyzer_config,
+                        &filter,
+                        &options,
+                        max_memory,
+                        |_| {},
+                        warnings,
+                    )?);
+                }
+                let profile = comparator::learn_noise(&learned);
+                let rendered = match format {
+                    OutputFormat::Text => comparator::format_noise_profile_text(&profile),
+                    OutputFormat::Json => comparator::format_noise_profile_json(&profile),
+                };
+                let rendered = attach_meta(rendered, format, meta, warnings);
+                print!("{rendered}");
+                if let Some(path) = output {
+                    write_output_file(path, &rendered)?;
+                }
+                return Ok(());
+            }
 
-            // Compare logs with proper error handling
-            let results = compare_logs(&logs1, &logs2, &filter, &options)
-                .map_err(|e| format!("Comparison failed: {:?}", e))?;
+            let (results, split) = match (split_at, file2) {
+                (Some(point), _) => {
+                    let (results, split) = compare_log_halves(
+                        file1,
+                        point,
+                        &analyzer_config,
+                        &filter,
+                        &options,
+                        max_memory,
+                        warnings,
+                    )?;
+                    (results, Some(split))
+                }
+                (None, Some(file2)) => {
+                    let results = compare_log_files(
+                        file1,
+                        file2,
+                        &analyzer_config,
+                        &filter,
+                        &options,
+                        max_memory,
+                        |_| {},
+                        warnings,
+                    )?;
+                    (results, None)
+                }
+                (None, None) => return Err("compare requires FILE2 or --split-at".into()),
+            };
+            if let Some(path) = save {
+                let file2 = file2.as_deref().unwrap_or(file1);
+                comparator::save_comparison(
+                    path,
+                    [file1, file2],
+                    split.as_ref(),
+                    &results,
+                    &options,
+                )?;
+            }
 
             // Display results in the selected format
             match format {
                 OutputFormat::Text => {
+                    if let Some(split) = &split {
+                        println!("{}", split.describe());
+                    }
                     display_comparison_results(&results, &options);
                     if let Some(path) = output {
                         comparator::write_comparison_results(&results, &options, path).map_err(
@@ -268,7 +881,17 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
                 OutputFormat::Json => {
-                    let json_output = generate_json_output(&results, &options);
+                    let mut json_output = generate_json_output(&results, &options);
+                    if let Some(split) = &split
+                        && let Ok(serde_json::Value::Object(mut document)) =
+                            serde_json::from_str::<serde_json::Value>(&json_output)
+                    {
+                        document.insert("split".to_string(), split.to_json());
+                        json_output =
+                            reserialize_like(json_output, &serde_json::Value::Object(document));
+                    }
+                    let json_output = standardize_units(json_output, format, json_units);
+                    let json_output = attach_meta(json_output, format, meta, warnings);
                     println!("{}", json_output);
                     if let Some(path) = output {
                         write_output_file(path, &json_output)?;
@@ -281,27 +904,50 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             file2,
             full,
             sort_by,
+            only_keys,
+            skip_keys,
+            cluster_payloads,
+            level_drift_threshold,
+            interesting,
+            ignore_case,
+            ignore_whitespace,
         } => {
-            // Parse log files with proper error handling
-            let logs1 = parse_log_file_with_config(file1, &analyzer_config)
-                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file1.display(), e))?;
-
-            let logs2 = parse_log_file_with_config(file2, &analyzer_config)
-                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file2.display(), e))?;
-
             // Create options with diff_only=true
             let options = ComparisonOptions::new()
+                .render(render)
+                .scope(analyzer_config.parser.scope.describe())
                 .diff_only(true)
                 .show_full_json(*full)
                 .compact_mode(compact)
                 .readable_mode(true)
                 .sort_by(*sort_by)
                 .verbosity(verbose)
-                .quiet_mode(quiet);
+                .quiet_mode(quiet)
+                .only_keys(only_keys.clone())
+                .skip_keys(skip_keys.clone())
+                .cluster_payloads(*cluster_payloads)
+                .level_drift_threshold(*level_drift_threshold)
+                .interesting_limit(*interesting)
+                .ignore_case(*ignore_case)
+                .ignore_whitespace(*ignore_whitespace)
+                .ignore_paths(ignore_rules.paths.clone())
+                .array_identity_keys(analyzer_config.comparison.identity_keys())
+                .max_json_depth(analyzer_config.comparison.max_json_depth)
+                .all_payload_errors(cli.payload_errors)
+                .include_legend(cli.legend)
+                .max_array_elements(cli.max_array_elements)
+                .raw_limit(cli.raw_excerpt_limit());
 
-            // Compare logs with proper error handling
-            let results = compare_logs(&logs1, &logs2, &filter, &options)
-                .map_err(|e| format!("Comparison failed: {:?}", e))?;
+            let results = compare_log_files(
+                file1,
+                file2,
+                &analyzer_config,
+                &filter,
+                &options,
+                max_memory,
+                |_| {},
+                warnings,
+            )?;
 
             // Display results in the selected format
             match format {
@@ -314,33 +960,106 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
                 OutputFormat::Json => {
-                    let json_output = generate_json_output(&results, &options);
+                    let json_output = standardize_units(
+                        generate_json_output(&results, &options),
+                        format,
+                        json_units,
+                    );
+                    let json_output = attach_meta(json_output, format, meta, warnings);
+                    println!("{}", json_output);
+                    if let Some(path) = output {
+                        write_output_file(path, &json_output)?;
+                    }
+                }
+            }
+        }
+        Commands::Render { file, to } => {
+            let saved = comparator::load_comparison(file)?;
+            let options = saved.settings.apply(
+                ComparisonOptions::new()
+                    .render(render)
+                    .compact_mode(compact)
+                    .readable_mode(true)
+                    .verbosity(verbose)
+                    .quiet_mode(quiet)
+                    .all_payload_errors(cli.payload_errors)
+                    .include_legend(cli.legend)
+                    .summary_only(cli.summary_only)
+                    .max_array_elements(cli.max_array_elements),
+            );
+            let title = format!("Log comparison: {} ↔ {}", saved.file1, saved.file2);
+            let notes: Vec<String> = saved
+                .split
+                .iter()
+                .map(|split| split.description.clone())
+                .collect();
+
+            let to = to.unwrap_or(match format {
+                OutputFormat::Json => RenderFormat::Json,
+                OutputFormat::Text => RenderFormat::Text,
+            });
+            match to {
+                RenderFormat::Text => {
+                    for note in &notes {
+                        println!("{note}");
+                    }
+                    display_comparison_results(&saved.results, &options);
+                    if let Some(path) = output {
+                        comparator::write_comparison_results(&saved.results, &options, path)
+                            .map_err(|e| {
+                                format!("Failed to write output file '{}': {}", path.display(), e)
+                            })?;
+                    }
+                }
+                RenderFormat::Json => {
+                    let mut json_output = generate_json_output(&saved.results, &options);
+                    if let Some(split) = &saved.split
+                        && let Ok(serde_json::Value::Object(mut document)) =
+                            serde_json::from_str::<serde_json::Value>(&json_output)
+                    {
+                        document.insert("split".to_string(), split.details.clone());
+                        json_output =
+                            reserialize_like(json_output, &serde_json::Value::Object(document));
+                    }
+                    let json_output =
+                        standardize_units(json_output, OutputFormat::Json, json_units);
+                    let json_output = attach_meta(json_output, OutputFormat::Json, meta, warnings);
                     println!("{}", json_output);
                     if let Some(path) = output {
                         write_output_file(path, &json_output)?;
                     }
                 }
+                RenderFormat::Markdown | RenderFormat::Html => {
+                    let rendered = if to == RenderFormat::Html {
+                        comparator::format_comparison_html(&saved.results, &options, &title, &notes)
+                    } else {
+                        comparator::format_comparison_markdown(
+                            &saved.results,
+                            &options,
+                            &title,
+                            &notes,
+                        )
+                    };
+                    print!("{rendered}");
+                    if let Some(path) = output {
+                        write_output_file(path, &rendered)?;
+                    }
+                }
             }
         }
         Commands::LlmDiff {
             file1,
             file2,
             sort_by,
+            only_keys,
+            skip_keys,
+            cluster_payloads,
+            level_drift_threshold,
+            interesting,
+            ignore_case,
+            ignore_whitespace,
             no_sanitize,
         } => {
-            // Parse log files with proper error handling
-            let mut logs1 = parse_log_file_with_config(file1, &analyzer_config)
-                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file1.display(), e))?;
-
-            let mut logs2 = parse_log_file_with_config(file2, &analyzer_config)
-                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file2.display(), e))?;
-
-            // Apply sanitization if enabled (default behavior unless --no-sanitize is used)
-            if !no_sanitize {
-                llm_processor::sanitize_logs(&mut logs1);
-                llm_processor::sanitize_logs(&mut logs2);
-            }
-
             // Create options for LlmDiff with fixed parameters
             let options = ComparisonOptions::new()
                 .diff_only(true)
@@ -349,14 +1068,55 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 .readable_mode(true)
                 .sort_by(*sort_by)
                 .verbosity(verbose)
-                .quiet_mode(quiet);
+                .quiet_mode(quiet)
+                .only_keys(only_keys.clone())
+                .skip_keys(skip_keys.clone())
+                .cluster_payloads(*cluster_payloads)
+                .level_drift_threshold(*level_drift_threshold)
+                .interesting_limit(*interesting)
+                .ignore_case(*ignore_case)
+                .ignore_whitespace(*ignore_whitespace)
+                .ignore_paths(ignore_rules.paths.clone())
+                .array_identity_keys(analyzer_config.comparison.identity_keys())
+                .max_json_depth(analyzer_config.comparison.max_json_depth)
+                .all_payload_errors(cli.payload_errors)
+                .include_legend(cli.legend)
+                // Raw lines bypass payload sanitization
+                .raw_limit(cli.raw_excerpt_limit().filter(|_| *no_sanitize));
 
-            // Compare logs with proper error handling
-            let results = compare_logs(&logs1, &logs2, &filter, &options)
-                .map_err(|e| format!("Comparison failed: {:?}", e))?;
+            // Apply sanitization if enabled (default behavior unless --no-sanitize is used)
+            let mut results = compare_log_files(
+                file1,
+                file2,
+                &analyzer_config,
+                &filter,
+                &options,
+                max_memory,
+                |log| {
+                    if !no_sanitize {
+                        llm_processor::sanitize_log_entry(log);
+                    }
+                },
+                warnings,
+            )?;
+            if !no_sanitize {
+                // Unparseable payloads cannot be sanitized field by field
+                for failure in results
+                    .payload_errors
+                    .iter_mut()
+                    .flat_map(|file_errors| file_errors.failures.iter_mut())
+                {
+                    failure.snippet = "[REDACTED]".to_string();
+                }
+            }
 
             // Output as JSON (fixed format for LlmDiff)
-            let json_output = generate_json_output(&results, &options);
+            let json_output = attach_meta(
+                generate_json_output(&results, &options),
+                OutputFormat::Json,
+                meta,
+                warnings,
+            );
             println!("{}", json_output);
             if let Some(path) = output {
                 write_output_file(path, &json_output)?;
@@ -368,23 +1128,196 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             json_schema,
             payloads,
             timeline,
+            bucket,
+            max_buckets,
+            against,
+            field_matrix,
+            csv,
+            split_runs,
+            schema_cache,
         } => {
+            if let Some(cache_dir) = schema_cache {
+                if filter.needs_resolution() {
+                    return Err("--schema-cache cannot be combined with --run, !within: windows or --relative-to log-end, which span all files".into());
+                }
+                let filter_expr = ignore_rules.filter_expression(cli.filter.as_deref());
+                let (report, usage) = schema_cache::load_schema_report(
+                    files,
+                    &analyzer_config,
+                    &filter,
+                    filter_expr.as_deref(),
+                    cache_dir,
+                    warnings,
+                )?;
+                display_schema_report(
+                    &report,
+                    &analyzer_config.schema,
+                    analyzer_config.comparison.max_json_depth,
+                    &render,
+                );
+                println!(
+                    "\nSchema cache: {} file(s) reused, {} parsed ({})",
+                    usage.reused,
+                    usage.parsed,
+                    cache_dir.display()
+                );
+                return Ok(());
+            }
+
             // Parse and merge log files, then sort by timestamp for session-wide analysis
-            let logs = parse_and_merge_log_files_with_config(files, &analyzer_config)?;
+            let (logs, payload_errors) = parse_and_merge_tracking_payload_errors(
+                files,
+                &analyzer_config,
+                file_statuses,
+                warnings,
+            )?;
+            let filter = filter.resolve_windows(&logs, warnings);
 
-            // Filter logs if filter is provided
-            let filtered_logs: Vec<_> = if cli.filter.is_some() {
-                logs.iter()
-                    .filter(|log| filter.matches(log))
-                    .cloned()
-                    .collect()
-            } else {
-                logs
-            };
+            if let Some(against) = against {
+                let against_logs = parse_log_file_reporting(against, &analyzer_config, warnings)
+                    .map_err(|e| {
+                        format!("Failed to parse log file '{}': {:?}", against.display(), e)
+                    })?;
+                let before: Vec<_> = logs.into_iter().filter(|log| filter.matches(log)).collect();
+                let against_filter = filter.resolve_windows(&against_logs, warnings);
+                let after: Vec<_> = against_logs
+                    .into_iter()
+                    .filter(|log| against_filter.matches(log))
+                    .collect();
+
+                let before_label = files
+                    .iter()
+                    .map(|file| file.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let after_label = against.display().to_string();
+                let before_summary = build_info_summary(&before);
+                let after_summary = build_info_summary(&after);
+
+                let rendered = match format {
+                    OutputFormat::Text => format_info_delta_text(
+                        &before_label,
+                        &before_summary,
+                        &after_label,
+                        &after_summary,
+                        cli.summary_only,
+                    ),
+                    OutputFormat::Json => format_info_delta_json(
+                        &before_label,
+                        &before_summary,
+                        &after_label,
+                        &after_summary,
+                        json_units,
+                        cli.summary_only,
+                    ),
+                };
+
+                let rendered = standardize_units(rendered, format, json_units);
+                let rendered = attach_meta(rendered, format, meta, warnings);
+                print!("{rendered}");
+                if let Some(path) = output {
+                    write_output_file(path, &rendered)?;
+                }
+                return Ok(());
+            }
+
+            let detected_runs = runs::detect_runs(&logs, &analyzer_config.runs);
+
+            // Apply --filter, --run and the ignore file's filters
+            let filtered_logs: Vec<_> =
+                logs.into_iter().filter(|log| filter.matches(log)).collect();
+
+            if *field_matrix {
+                let matrix = field_matrix::build_field_matrix(
+                    &filtered_logs,
+                    analyzer_config.comparison.max_json_depth,
+                );
+                // CSV is left as is, so it stays loadable as a table
+                let rendered = if *csv {
+                    field_matrix::format_field_matrix_csv(&matrix)
+                } else {
+                    let rendered = match format {
+                        OutputFormat::Text => field_matrix::format_field_matrix_text(&matrix),
+                        OutputFormat::Json => field_matrix::format_field_matrix_json(&matrix),
+                    };
+                    attach_meta(rendered, format, meta, warnings)
+                };
+                print!("{rendered}");
+                if let Some(path) = output {
+                    write_output_file(path, &rendered)?;
+                }
+                return Ok(());
+            }
+
+            let unfiltered = LogFilter::new();
+            let perf_results = perf_analyzer::analyze_performance_with_config(
+                &filtered_logs,
+                &unfiltered,
+                None,
+                &analyzer_config,
+            );
+            let health = health::compute_health(
+                &filtered_logs,
+                &unfiltered,
+                &perf_results,
+                &analyzer_config,
+            );
+            if cli.summary_only {
+                let summary = build_info_summary(&filtered_logs);
+                let rendered = match format {
+                    OutputFormat::Text => format!(
+                        "{}\n{}",
+                        health::format_health_text(&health),
+                        format_info_summary_text(&summary)
+                    ),
+                    OutputFormat::Json => format_info_summary_json(&summary, &health),
+                };
+                let rendered = standardize_units(rendered, format, json_units);
+                let rendered = attach_meta(rendered, format, meta, warnings);
+                print!("{rendered}");
+                if let Some(path) = output {
+                    write_output_file(path, &rendered)?;
+                }
+                return Ok(());
+            }
+            print!("{}", health::format_health_text(&health));
+            if *split_runs {
+                print!("{}", runs::format_runs_text(&detected_runs));
+            } else if detected_runs.len() > 1 && cli.run.is_none() {
+                println!(
+                    "\nNote: {} runs detected; statistics below mix them. Use --split-runs to list them or --run <N> to analyze one.",
+                    detected_runs.len()
+                );
+            }
+
+            if !analyzer_config.tags.is_empty() {
+                print!(
+                    "{}",
+                    tags::format_tag_rollups_text(&tags::build_tag_rollups(
+                        &filtered_logs,
+                        &analyzer_config.tags
+                    ))
+                );
+            }
 
             // Display log summary with enhanced options
-            display_log_summary(&filtered_logs, *samples, *json_schema, *payloads, *timeline);
+            display_log_summary(
+                &filtered_logs,
+                *samples,
+                *json_schema,
+                *payloads,
+                timeline.then(|| {
+                    TimeBucketOptions::new()
+                        .bucket_ms(*bucket)
+                        .max_buckets(*max_buckets)
+                }),
+                &analyzer_config.schema,
+                analyzer_config.comparison.max_json_depth,
+                analyzer_config.parser.scope.describe().as_deref(),
+                &render,
+            );
             print_profile_insights(&filtered_logs, &analyzer_config);
+            print_payload_errors(&payload_errors, cli.payload_errors);
 
             // Show filtering information if applied
             if let Some(ref filter_expr) = cli.filter {
@@ -408,8 +1341,9 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             no_sanitize,
         } => {
             // Parse log file with proper error handling
-            let logs = parse_log_file_with_config(file, &analyzer_config)
+            let logs = parse_log_file_reporting(file, &analyzer_config, warnings)
                 .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?;
+            let filter = filter.resolve_windows(&logs, warnings);
 
             // Filter logs
             let filtered_logs: Vec<_> = logs
@@ -419,12 +1353,20 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 .collect();
 
             // Process logs for LLM consumption (sanitize by default, unless --no-sanitize is used)
-            let llm_output =
-                llm_processor::process_logs_for_llm(&filtered_logs, *limit, !no_sanitize);
+            let mut llm_output = llm_processor::process_logs_for_llm_with_config(
+                &filtered_logs,
+                *limit,
+                !no_sanitize,
+                &analyzer_config,
+            );
+            if cli.legend {
+                llm_output.legend = Some(legend::llm_log_legend());
+            }
 
             // Output as JSON
             match serde_json::to_string_pretty(&llm_output) {
                 Ok(json) => {
+                    let json = attach_meta(json, OutputFormat::Json, meta, warnings);
                     println!("{}", json);
                     if let Some(path) = output {
                         write_output_file(path, &json)?;
@@ -438,31 +1380,191 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             context,
             payloads,
             count_by,
+            bucket,
+            max_buckets,
+            diff_against,
+            ignore_paths,
+            violations,
+            tolerance,
+            session,
+            request_id,
         } => {
-            let logs = parse_log_file_with_config(file, &analyzer_config)
+            let logs = parse_log_file_reporting(file, &analyzer_config, warnings)
                 .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?;
-            let match_indices = collect_match_indices(&logs, &filter);
+            let filter = filter.resolve_windows(&logs, warnings);
+            let mut match_indices = collect_match_indices(&logs, &filter);
+            let selectors: Vec<TraceSelector> = session
+                .iter()
+                .map(|session| TraceSelector::Session(session.clone()))
+                .chain(request_id.iter().map(|id| TraceSelector::Id(id.clone())))
+                .collect();
+            match_indices.retain(|&idx| {
+                selectors
+                    .iter()
+                    .all(|selector| selector.matches(&logs[idx]))
+            });
+            if *violations {
+                if analyzer_config.contracts.is_empty() {
+                    return Err("--violations requires [contracts.requests] or [contracts.commands] in the profile".into());
+                }
+                match_indices.retain(|&idx| {
+                    !analyzer_config
+                        .contracts
+                        .missing_fields(&logs[idx])
+                        .is_empty()
+                });
+            }
+
+            let rendered = if let Some(golden_file) = diff_against {
+                let golden_text = std::fs::read_to_string(golden_file).map_err(|e| {
+                    format!(
+                        "Failed to read golden file '{}': {}",
+                        golden_file.display(),
+                        e
+                    )
+                })?;
+                let golden: serde_json::Value =
+                    serde_json::from_str(&golden_text).map_err(|e| {
+                        format!(
+                            "Failed to parse golden file '{}': {}",
+                            golden_file.display(),
+                            e
+                        )
+                    })?;
+                let rules = comparator::JsonDiffRules::new()
+                    .ignore_paths(
+                        ignore_paths
+                            .iter()
+                            .chain(&ignore_rules.paths)
+                            .cloned()
+                            .collect(),
+                    )
+                    .array_identity_keys(analyzer_config.comparison.identity_keys())
+                    .max_depth(analyzer_config.comparison.max_json_depth)
+                    .tolerance(*tolerance);
 
-            let rendered = if let Some(count_by) = count_by {
                 match format {
                     OutputFormat::Text => {
-                        format_search_count_text(&logs, &match_indices, *count_by)
-                    }
-                    OutputFormat::Json => {
-                        format_search_count_json(file, &logs, &match_indices, *count_by)
+                        format_search_diff_text(golden_file, &golden, &logs, &match_indices, &rules)
                     }
+                    OutputFormat::Json => format_search_diff_json(
+                        file,
+                        golden_file,
+                        &golden,
+                        &logs,
+                        &match_indices,
+                        &rules,
+                        cli.raw_excerpt_limit(),
+                    ),
                 }
-            } else {
+            } else if let Some(count_by) = count_by {
+                let buckets = TimeBucketOptions::new()
+                    .bucket_ms(*bucket)
+                    .max_buckets(*max_buckets);
                 match format {
                     OutputFormat::Text => {
-                        format_search_text(&logs, &match_indices, *context, *payloads)
+                        format_search_count_text(&logs, &match_indices, *count_by, &buckets)
                     }
                     OutputFormat::Json => {
-                        format_search_json(file, &logs, &match_indices, *context, *payloads)
+                        format_search_count_json(file, &logs, &match_indices, *count_by, &buckets)
                     }
                 }
+            } else {
+                match format {
+                    OutputFormat::Text => format_search_text(
+                        &logs,
+                        &match_indices,
+                        *context,
+                        *payloads,
+                        cli.max_array_elements,
+                    ),
+                    OutputFormat::Json => format_search_json(
+                        file,
+                        &logs,
+                        &match_indices,
+                        *context,
+                        *payloads,
+                        &TypedFields::new(&logs, &analyzer_config),
+                    ),
+                }
+            };
+
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
+        Commands::Count { files, by } => {
+            let mut count_config = analyzer_config.clone();
+            // `missing:` terms inspect payloads, so only skip them when the filter does not
+            count_config.parser.skip_payloads = !filter.reads_payloads();
+
+            let mut summary = count::CountSummary::default();
+            for file in files {
+                let logs =
+                    parse_log_file_reporting(file, &count_config, warnings).map_err(|e| {
+                        format!("Failed to parse log file '{}': {:?}", file.display(), e)
+                    })?;
+                summary.record(&logs, &filter.resolve_windows(&logs, warnings), *by);
+            }
+
+            let rendered = match format {
+                OutputFormat::Text => count::format_count_text(&summary, *by),
+                OutputFormat::Json => count::format_count_json(files, &summary, *by),
+            };
+
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
+        Commands::Chronology { files } => {
+            let mut report = chronology::ChronologyReport::default();
+            let mut source_order = Vec::new();
+            for file in files {
+                let logs =
+                    parse_log_file_reporting(file, &analyzer_config, warnings).map_err(|e| {
+                        format!("Failed to parse log file '{}': {:?}", file.display(), e)
+                    })?;
+                let filter = filter.resolve_windows(&logs, warnings);
+                report
+                    .files
+                    .push(chronology::check_file(file, &logs, &filter));
+                source_order.extend(logs.into_iter().filter(|log| filter.matches(log)));
+            }
+            report.pairing =
+                chronology::check_pairing(&source_order, &LogFilter::new(), &analyzer_config);
+
+            let rendered = match format {
+                OutputFormat::Text => chronology::format_chronology_text(&report),
+                OutputFormat::Json => chronology::format_chronology_json(&report),
             };
 
+            let rendered = standardize_units(rendered, format, json_units);
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
+        Commands::Contracts { files } => {
+            let logs = parse_and_merge_log_files_with_config(
+                files,
+                &analyzer_config,
+                file_statuses,
+                warnings,
+            )?;
+            let filter = filter.resolve_windows(&logs, warnings);
+            let report =
+                contracts::build_contract_report(&logs, &filter, &analyzer_config.contracts);
+
+            let rendered = match format {
+                OutputFormat::Text => contracts::format_contracts_text(&report),
+                OutputFormat::Json => contracts::format_contracts_json(&report),
+            };
+            let rendered = attach_meta(rendered, format, meta, warnings);
             print!("{rendered}");
             if let Some(path) = output {
                 write_output_file(path, &rendered)?;
@@ -474,38 +1576,306 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             warn,
             sessions,
             sort_by,
+            session_attribution,
+            slo,
+            slo_max_rate,
+            slo_window,
+            slo_step,
+            threads,
+            checkpoint,
+            checkpoint_every,
+            daily,
+            ticket,
         } => {
-            let logs = parse_and_merge_log_files_with_config(files, &analyzer_config)?;
+            if *ticket && matches!(format, OutputFormat::Json) {
+                return Err(
+                    "--ticket renders text issues and cannot be combined with -F json".into(),
+                );
+            }
+            if let Some(attribution) = session_attribution {
+                analyzer_config.sessions.parallel_attribution = *attribution;
+            }
             let error_options = ErrorsOptions {
                 top_n: *top_n,
                 include_warn: *warn,
                 show_sessions: *sessions,
                 sort_by: *sort_by,
                 file_count: files.len(),
+                suppressed_patterns: ignore_rules.error_patterns.clone(),
+                raw_limit: cli.raw_excerpt_limit(),
+                threads: *threads,
+                summary_only: cli.summary_only,
             };
 
-            let report =
-                analyze_errors_with_config(&logs, &filter, &analyzer_config, &error_options);
+            let (report, day_text) = if checkpoint.is_some() || *daily {
+                if filter.needs_resolution() {
+                    return Err(
+                        "--checkpoint and --daily read entries one at a time and cannot resolve within: windows, --run or --relative-to log-end"
+                            .into(),
+                    );
+                }
+                let checkpoint = checkpoint.as_ref().map(|path| errors::CheckpointOptions {
+                    path: path.clone(),
+                    every_entries: *checkpoint_every,
+                    filter_expression: ignore_rules.filter_expression(cli.filter.as_deref()),
+                });
+                let stream_days =
+                    *daily && !cli.summary_only && !matches!(format, OutputFormat::Json);
+                let mut day_text = String::new();
+                let streamed = errors::analyze_errors_streaming(
+                    files,
+                    &analyzer_config,
+                    &filter,
+                    &error_options,
+                    checkpoint.as_ref(),
+                    warnings,
+                    |day| {
+                        if stream_days {
+                            let block = errors::format_day_text(day);
+                            print!("{block}");
+                            day_text.push_str(&block);
+                        }
+                    },
+                )?;
+                if streamed.resumed_entries > 0 {
+                    eprintln!(
+                        "Resumed from checkpoint: {} entries already analyzed",
+                        streamed.resumed_entries
+                    );
+                }
+                let mut report = streamed.report;
+                if *daily {
+                    if stream_days {
+                        day_text.push('\n');
+                        println!();
+                    }
+                } else {
+                    report.days.clear();
+                }
+
+                (report, day_text)
+            } else {
+                let logs = parse_and_merge_log_files_with_config(
+                    files,
+                    &analyzer_config,
+                    file_statuses,
+                    warnings,
+                )?;
+                let filter = filter.resolve_windows(&logs, warnings);
+
+                if *slo {
+                    let rules = &analyzer_config.errors.slo;
+                    let max_error_rate_pct = slo_max_rate.or(rules.max_error_rate_pct).ok_or(
+                        "--slo needs a maximum error rate: pass --slo-max-rate or set max_error_rate_pct under [errors.slo]",
+                    )?;
+                    let slo_options = slo::SloOptions {
+                        max_error_rate_pct,
+                        window_ms: slo_window.unwrap_or(rules.window_ms),
+                        step_ms: slo_step.unwrap_or(rules.step_ms),
+                        min_entries: rules.min_entries,
+                        include_warn: *warn,
+                        suppressed_patterns: ignore_rules.error_patterns.clone(),
+                    };
+                    let report = slo::evaluate_slo(&logs, &filter, &slo_options);
+                    let rendered = match format {
+                        OutputFormat::Text => slo::format_slo_text(&report),
+                        OutputFormat::Json => slo::format_slo_json(&report),
+                    };
+
+                    let rendered = standardize_units(rendered, format, json_units);
+                    let rendered = attach_meta(rendered, format, meta, warnings);
+                    print!("{rendered}");
+                    if let Some(path) = output {
+                        write_output_file(path, &rendered)?;
+                    }
+                    return Ok(());
+                }
+                let context = AnalysisContext::new(&logs, &analyzer_config);
+                let report = analyze_errors_in_context(&context, &filter, &error_options);
+                (report, String::new())
+            };
             let rendered = match format {
+                OutputFormat::Text if *ticket => tickets::format_errors_tickets(
+                    &report,
+                    &error_options,
+                    &tickets::load_ticket_template(&analyzer_config)?,
+                ),
                 OutputFormat::Text => format_errors_text(&report, &error_options),
                 OutputFormat::Json => format_errors_json(&report, &error_options),
             };
 
+            let rendered = standardize_units(rendered, format, json_units);
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &format!("{day_text}{rendered}"))?;
+            }
+        }
+        Commands::Dupes {
+            file1,
+            file2,
+            max_distance,
+            top,
+        } => {
+            let mut parsed = Vec::with_capacity(2);
+            for file in [file1, file2] {
+                let logs =
+                    parse_log_file_reporting(file, &analyzer_config, warnings).map_err(|e| {
+                        format!("Failed to parse log file '{}': {:?}", file.display(), e)
+                    })?;
+                parsed.push(logs);
+            }
+            let selected: Vec<Vec<&LogEntry>> = parsed
+                .iter()
+                .map(|logs| {
+                    let filter = filter.resolve_windows(logs, warnings);
+                    logs.iter().filter(|entry| filter.matches(entry)).collect()
+                })
+                .collect();
+            let report = dupes::find_dupes([&selected[0], &selected[1]], *max_distance);
+
+            let files = [file1.as_path(), file2.as_path()];
+            let rendered = match format {
+                OutputFormat::Text => dupes::format_dupes_text(files, &report, *top),
+                OutputFormat::Json => dupes::format_dupes_json(files, &report, *top),
+            };
+
+            let rendered = attach_meta(rendered, format, meta, warnings);
             print!("{rendered}");
             if let Some(path) = output {
                 write_output_file(path, &rendered)?;
             }
         }
-        Commands::Extract { file, field } => {
-            let logs = parse_log_file_with_config(file, &analyzer_config)
-                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?;
-            let match_indices = collect_match_indices(&logs, &filter);
+        Commands::Similar {
+            files,
+            to_line,
+            top,
+        } => {
+            let mut parsed = Vec::with_capacity(files.len());
+            for file in files {
+                let logs =
+                    parse_log_file_reporting(file, &analyzer_config, warnings).map_err(|e| {
+                        format!("Failed to parse log file '{}': {:?}", file.display(), e)
+                    })?;
+                parsed.push((file, logs));
+            }
+
+            let (reference_file, reference_logs) = &parsed[0];
+            let reference = similar::entry_at_line(reference_logs, *to_line).ok_or_else(|| {
+                format!(
+                    "No log entry at or before line {} in '{}'",
+                    to_line,
+                    reference_file.display()
+                )
+            })?;
+            let candidates: Vec<similar::SourcedEntry> = parsed
+                .iter()
+                .flat_map(|(file, logs)| {
+                    let filter = filter.resolve_windows(logs, warnings);
+                    logs.iter()
+                        .filter(move |entry| filter.matches(entry))
+                        .map(move |entry| similar::SourcedEntry { file, entry })
+                })
+                .collect();
+            let matches = similar::find_similar(reference, &candidates, *top);
+
+            let rendered = match format {
+                OutputFormat::Text => {
+                    similar::format_similar_text(reference_file, reference, &matches)
+                }
+                OutputFormat::Json => {
+                    similar::format_similar_json(reference_file, reference, &matches)
+                }
+            };
+
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
+        Commands::ShowDiff { left, right, html } => {
+            let load_entry = |(file, line): &(std::path::PathBuf, usize)| -> Result<LogEntry, Box<dyn std::error::Error>> {
+                let logs = parse_log_file_reporting(file, &analyzer_config, warnings).map_err(|e| {
+                    format!("Failed to parse log file '{}': {:?}", file.display(), e)
+                })?;
+                similar::entry_at_line(&logs, *line).cloned().ok_or_else(|| {
+                    format!("No log entry at or before line {} in '{}'", line, file.display()).into()
+                })
+            };
+            let left_entry = load_entry(left)?;
+            let right_entry = load_entry(right)?;
+            let diff = show_diff::diff_entries(
+                show_diff::DiffSide {
+                    file: &left.0,
+                    entry: &left_entry,
+                },
+                show_diff::DiffSide {
+                    file: &right.0,
+                    entry: &right_entry,
+                },
+                &comparator::JsonDiffRules::new()
+                    .ignore_paths(ignore_rules.paths.clone())
+                    .array_identity_keys(analyzer_config.comparison.identity_keys())
+                    .max_depth(analyzer_config.comparison.max_json_depth),
+            );
+
+            if let Some(path) = html {
+                write_output_file(path, &show_diff::format_show_diff_html(&diff))?;
+            }
 
             let rendered = match format {
-                OutputFormat::Text => format_extract_text(&logs, &match_indices, field),
-                OutputFormat::Json => format_extract_json(file, &logs, &match_indices, field),
+                OutputFormat::Text => show_diff::format_show_diff_text(&diff),
+                OutputFormat::Json => show_diff::format_show_diff_json(&diff),
             };
 
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
+        Commands::Extract {
+            file,
+            field,
+            against,
+        } => {
+            let logs = parse_log_file_reporting(file, &analyzer_config, warnings)
+                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?;
+            let match_indices =
+                collect_match_indices(&logs, &filter.resolve_windows(&logs, warnings));
+
+            let rendered = if let Some(against) = against {
+                let against_logs = parse_log_file_reporting(against, &analyzer_config, warnings)
+                    .map_err(|e| {
+                        format!("Failed to parse log file '{}': {:?}", against.display(), e)
+                    })?;
+                let against_indices = collect_match_indices(
+                    &against_logs,
+                    &filter.resolve_windows(&against_logs, warnings),
+                );
+                let before = ExtractSide {
+                    file,
+                    logs: &logs,
+                    match_indices: &match_indices,
+                };
+                let after = ExtractSide {
+                    file: against,
+                    logs: &against_logs,
+                    match_indices: &against_indices,
+                };
+                match format {
+                    OutputFormat::Text => format_extract_delta_text(&before, &after, field),
+                    OutputFormat::Json => format_extract_delta_json(&before, &after, field),
+                }
+            } else {
+                match format {
+                    OutputFormat::Text => format_extract_text(&logs, &match_indices, field),
+                    OutputFormat::Json => format_extract_json(file, &logs, &match_indices, field),
+                }
+            };
+
+            let rendered = attach_meta(rendered, format, meta, warnings);
             print!("{rendered}");
             if let Some(path) = output {
                 write_output_file(path, &rendered)?;
@@ -518,9 +1888,27 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             orphans_only,
             op_type,
             sort_by,
+            session_attribution,
+            hang_threshold,
+            fairness,
+            fairness_bucket_ms,
+            include_suspect_durations,
+            phases,
         } => {
+            if *phases && analyzer_config.perf.phases.is_empty() {
+                return Err("--phases requires [[perf.phases]] in the profile".into());
+            }
+            if let Some(attribution) = session_attribution {
+                analyzer_config.sessions.parallel_attribution = *attribution;
+            }
             // Parse and merge log files, then sort by timestamp for cross-file pairing
-            let logs = parse_and_merge_log_files_with_config(files, &analyzer_config)?;
+            let logs = parse_and_merge_log_files_with_config(
+                files,
+                &analyzer_config,
+                file_statuses,
+                warnings,
+            )?;
+            let filter = filter.resolve_windows(&logs, warnings);
 
             // Convert op_type filter to string
             let op_type_filter = op_type.map(|t| match t {
@@ -530,30 +1918,75 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             });
 
             // Analyze performance
-            let results = perf_analyzer::analyze_performance_with_config(
+            let mut results = perf_analyzer::analyze_performance_with_config(
                 &logs,
                 &filter,
                 op_type_filter,
                 &analyzer_config,
             );
+            if *include_suspect_durations {
+                results.include_suspect_durations(analyzer_config.sessions.parallel_attribution);
+            } else if results.suspect_durations.negative() > 0 {
+                warnings.record(
+                    WarningCode::SuspectDurations,
+                    format!(
+                        "{} operation(s) with negative durations were excluded from statistics",
+                        results.suspect_durations.negative()
+                    ),
+                );
+            }
+            perf_analyzer::link_operation_errors(&logs, &filter, &mut results.operations);
+            results.calculate_tag_stats();
+            if let Some(threshold_ms) = hang_threshold {
+                results.hang_warnings =
+                    perf_analyzer::detect_hanging_requests(&logs, &filter, *threshold_ms);
+            }
+            if *fairness {
+                let context = AnalysisContext::new(&logs, &analyzer_config);
+                results.fairness = Some(perf_analyzer::analyze_fairness(
+                    &logs,
+                    &filter,
+                    &results.operations,
+                    context.sessions(),
+                    *fairness_bucket_ms as i64,
+                ));
+            }
+            if *phases {
+                results.phases = Some(perf_analyzer::analyze_phases(
+                    &results.operations,
+                    &analyzer_config.perf.phases,
+                    &analyzer_config.perf.check_segment_prefix,
+                ));
+            }
 
             // Display results based on format
             match format {
                 OutputFormat::Text => {
-                    let text = perf_analyzer::format_perf_results_text(
-                        &results,
-                        *threshold_ms,
-                        *top_n,
-                        *orphans_only,
-                        *sort_by,
-                    );
+                    let text = if cli.summary_only {
+                        perf_analyzer::format_perf_summary_text(&results)
+                    } else {
+                        perf_analyzer::format_perf_results_text(
+                            &results,
+                            *threshold_ms,
+                            *top_n,
+                            *orphans_only,
+                            *sort_by,
+                        )
+                    };
+                    let text = attach_meta(text, format, meta, warnings);
                     print!("{text}");
                     if let Some(path) = output {
                         write_output_file(path, &text)?;
                     }
                 }
                 OutputFormat::Json => {
-                    let json = perf_analyzer::format_perf_results_json(&results);
+                    let json = if cli.summary_only {
+                        perf_analyzer::format_perf_summary_json(&results)
+                    } else {
+                        perf_analyzer::format_perf_results_json(&results)
+                    };
+                    let json = standardize_units(json, format, json_units);
+                    let json = attach_meta(json, format, meta, warnings);
                     println!("{}", json);
                     if let Some(path) = output {
                         write_output_file(path, &json)?;
@@ -561,29 +1994,80 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
-        Commands::Trace { files, id, session } => {
-            let logs = parse_and_merge_log_files_with_config(files, &analyzer_config)?;
+        Commands::Trace {
+            files,
+            id,
+            session,
+            diff_commands,
+        } => {
+            let logs = parse_and_merge_log_files_with_config(
+                files,
+                &analyzer_config,
+                file_statuses,
+                warnings,
+            )?;
+            let filter = filter.resolve_windows(&logs, warnings);
+            let context = AnalysisContext::new(&logs, &analyzer_config);
 
             let selector = if let Some(id) = id {
-                TraceSelector::Id(id.clone())
-            } else if let Some(session) = session {
-                TraceSelector::Session(session.clone())
+                Some(TraceSelector::Id(id.clone()))
             } else {
+                session
+                    .as_ref()
+                    .map(|session| TraceSelector::Session(session.clone()))
+            };
+
+            if let Some(command) = diff_commands {
+                let mut report =
+                    trace::diff_command_settings(&logs, &filter, selector.as_ref(), command);
+                for pair in &mut report.pairs {
+                    pair.differences.retain(|(path, _, _)| {
+                        !comparator::path_is_ignored(&ignore_rules.paths, path)
+                    });
+                }
+                let sessions = context.sessions();
+                let rendered = match format {
+                    OutputFormat::Text => {
+                        trace::format_command_diff_text(&report, sessions, cli.max_array_elements)
+                    }
+                    OutputFormat::Json => trace::format_command_diff_json(&report, sessions),
+                };
+
+                let rendered = attach_meta(rendered, format, meta, warnings);
+                print!("{rendered}");
+                if let Some(path) = output {
+                    write_output_file(path, &rendered)?;
+                }
+                return Ok(());
+            }
+
+            let Some(selector) = selector else {
                 return Err("Trace requires either --id or --session".into());
             };
 
             let entries = collect_trace_entries(&logs, &filter, &selector);
+            let no_sessions = config::SessionInsights::default();
+            let sessions = if analyzer_config.has_session_display_names() {
+                context.sessions()
+            } else {
+                &no_sessions
+            };
 
             match format {
                 OutputFormat::Text => {
-                    let text = format_trace_text(&entries, &selector);
+                    let text = format_trace_text(&entries, &selector, sessions);
                     print!("{text}");
                     if let Some(path) = output {
                         write_output_file(path, &text)?;
                     }
                 }
                 OutputFormat::Json => {
-                    let json = format_trace_json(&entries, &selector);
+                    let json = standardize_units(
+                        format_trace_json(&entries, &selector, sessions),
+                        format,
+                        json_units,
+                    );
+                    let json = attach_meta(json, format, meta, warnings);
                     println!("{}", json);
                     if let Some(path) = output {
                         write_output_file(path, &json)?;
@@ -591,6 +2075,37 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
+        Commands::CaptureFixture {
+            file,
+            name,
+            since,
+            until,
+            sessions,
+            dir,
+        } => {
+            let logs = parse_log_file_reporting(file, &analyzer_config, warnings)
+                .map_err(|e| format!("Failed to parse log file '{}': {:?}", file.display(), e))?;
+            let filter = filter.resolve_windows(&logs, warnings);
+            let spec = fixtures::FixtureSpec {
+                name: name.clone(),
+                since: *since,
+                until: *until,
+                sessions: sessions.clone(),
+                redact_fields: analyzer_config.fixtures.redact_fields.clone(),
+            };
+            let manifest = fixtures::capture_fixture(&logs, file, &filter, &spec, dir)?;
+
+            let rendered = match format {
+                OutputFormat::Text => fixtures::format_capture_text(dir, &manifest),
+                OutputFormat::Json => fixtures::format_capture_json(&manifest),
+            };
+
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
         Commands::GenerateConfig {
             files,
             profile_name,
@@ -623,7 +2138,12 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 .iter()
                 .filter_map(|file| detect_log_format(file, &base_config).ok())
                 .collect();
-            let logs = parse_and_merge_log_files_with_config(files, &base_config)?;
+            let logs = parse_and_merge_log_files_with_config(
+                files,
+                &base_config,
+                file_statuses,
+                warnings,
+            )?;
 
             let profile_name = profile_name.clone().unwrap_or_else(|| {
                 if files.len() == 1 {
@@ -673,6 +2193,185 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 write_output_file(path, &output_text)?;
             }
         }
+
+        Commands::Sessions {
+            action: cli::SessionsCommand::Story { session, files },
+        } => {
+            let logs = parse_and_merge_log_files_with_config(
+                files,
+                &analyzer_config,
+                file_statuses,
+                warnings,
+            )?;
+            let filter = filter.resolve_windows(&logs, warnings);
+            let context = AnalysisContext::new(&logs, &analyzer_config);
+            let story = story::build_session_story_in_context(&context, &filter, session)
+                .ok_or_else(|| format!("No log entries found for session '{session}'"))?;
+
+            let rendered = match format {
+                OutputFormat::Text => story::format_story_text(&story),
+                OutputFormat::Json => story::format_story_json(&story),
+            };
+            let rendered = standardize_units(rendered, format, json_units);
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
+
+        Commands::Sessions {
+            action:
+                cli::SessionsCommand::Impact {
+                    files,
+                    level,
+                    threshold_ms,
+                    top,
+                },
+        } => {
+            let logs = parse_and_merge_log_files_with_config(
+                files,
+                &analyzer_config,
+                file_statuses,
+                warnings,
+            )?;
+            let filter = filter.resolve_windows(&logs, warnings);
+            let context = AnalysisContext::new(&logs, &analyzer_config);
+            let report = impact::build_impact_report(
+                &context,
+                &filter,
+                level.as_deref(),
+                *threshold_ms,
+                *top,
+            )?;
+
+            let rendered = match format {
+                OutputFormat::Text => impact::format_impact_text(&report),
+                OutputFormat::Json => impact::format_impact_json(&report),
+            };
+            let rendered = standardize_units(rendered, format, json_units);
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
+
+        Commands::Config {
+            action:
+                cli::ConfigCommand::CompareEffect {
+                    config_a,
+                    config_b,
+                    file,
+                },
+        } => {
+            let measure = |config_path: &std::path::Path| {
+                let config = load_named_config(config_path)?;
+                let logs = parse_log_file_reporting(file, &config, warnings).map_err(|e| {
+                    format!("Failed to parse log file '{}': {:?}", file.display(), e)
+                })?;
+                let filter = filter.resolve_windows(&logs, warnings);
+                Ok::<_, Box<dyn std::error::Error>>(config_effect::measure_config_effect(
+                    &logs,
+                    &filter,
+                    &config,
+                    &config_path.display().to_string(),
+                ))
+            };
+            let report = config_effect::ConfigEffectReport {
+                file: file.display().to_string(),
+                a: measure(config_a)?,
+                b: measure(config_b)?,
+            };
+
+            let rendered = match format {
+                OutputFormat::Text => config_effect::format_config_effect_text(&report),
+                OutputFormat::Json => config_effect::format_config_effect_json(&report),
+            };
+            let rendered = attach_meta(rendered, format, meta, warnings);
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
+
+        Commands::Report {
+            action: cli::ReportCommand::Merge { files },
+        } => {
+            let merged = report::merge_report_files(files)?;
+            let json = standardize_units(
+                report::format_report_merge_json(&merged),
+                OutputFormat::Json,
+                json_units,
+            );
+            let json = attach_meta(json, OutputFormat::Json, meta, warnings);
+            match format {
+                OutputFormat::Text => {
+                    print!("{}", report::format_report_merge_text(&merged))
+                }
+                OutputFormat::Json => print!("{json}"),
+            }
+            // The roll-up file is always JSON so dashboards and scripts can consume it
+            if let Some(path) = output {
+                write_output_file(path, &json)?;
+            }
+        }
+        Commands::Matrix {
+            dir,
+            requests,
+            last,
+            csv,
+        } => {
+            let requests = if requests.is_empty() {
+                analyzer_config
+                    .perf
+                    .latency_budgets_ms
+                    .keys()
+                    .cloned()
+                    .collect()
+            } else {
+                requests.clone()
+            };
+            let mut runs = Vec::new();
+            for run in matrix::discover_runs(dir, *last)? {
+                let logs = parse_and_merge_log_files_with_config(
+                    &run.files,
+                    &analyzer_config,
+                    file_statuses,
+                    warnings,
+                )?;
+                let filter = filter.resolve_windows(&logs, warnings);
+                let perf = perf_analyzer::analyze_performance_with_config(
+                    &logs,
+                    &filter,
+                    None,
+                    &analyzer_config,
+                );
+                let health = health::compute_health(&logs, &filter, &perf, &analyzer_config);
+                runs.push(matrix::RunMetrics::new(&run, &perf, &health, &requests));
+            }
+            let run_matrix = matrix::RunMatrix {
+                dir: dir.clone(),
+                requests,
+                runs,
+            };
+
+            // CSV is left as is, so it stays loadable as a table
+            let rendered = if *csv {
+                matrix::format_matrix_csv(&run_matrix)
+            } else {
+                let rendered = match format {
+                    OutputFormat::Text => matrix::format_matrix_text(&run_matrix),
+                    OutputFormat::Json => matrix::format_matrix_json(&run_matrix),
+                };
+                let rendered = standardize_units(rendered, format, json_units);
+                attach_meta(rendered, format, meta, warnings)
+            };
+            print!("{rendered}");
+            if let Some(path) = output {
+                write_output_file(path, &rendered)?;
+            }
+        }
     }
 
     Ok(())
@@ -1,14 +1,28 @@
+use crate::config::{AnalyzerConfig, default_config};
+use crate::legend::COMPACT_SCHEMA_VERSION;
 use crate::parser::{LogEntry, LogEntryKind};
+use crate::typed_fields::TypedFields;
+use chrono::{SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value, json};
 use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize)]
 pub struct LlmLogOutput {
+    /// Version of the abbreviated entry layout
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u64,
+    /// Short key -> full name, embedded with `--legend`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legend: Option<Value>,
     pub metadata: LlmMetadata,
     pub logs: Vec<LlmLogEntry>,
 }
 
+fn default_schema_version() -> u64 {
+    1
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct LlmMetadata {
     pub total_entries: usize,
@@ -29,6 +43,15 @@ pub struct TimeRange {
 pub struct LlmLogEntry {
     pub idx: usize,
     pub ts: String,
+    /// Timestamp as RFC3339 (UTC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at: Option<String>,
+    /// Timestamp as epoch milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ms: Option<i64>,
+    /// Duration of the paired operation this entry starts or ends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dur: Option<i64>,
     pub comp: String,
     pub lvl: String,
     pub typ: String,
@@ -76,17 +99,20 @@ const SENSITIVE_FIELDS: &[&str] = &[
     "cert",
 ];
 
+/// Whether a field name contains one of [`SENSITIVE_FIELDS`] (case-insensitive)
+pub(crate) fn is_sensitive_field(key: &str) -> bool {
+    let key_lower = key.to_lowercase();
+    SENSITIVE_FIELDS
+        .iter()
+        .any(|&sensitive| key_lower.contains(sensitive))
+}
+
 pub fn sanitize_json_value(value: &Value) -> Value {
     match value {
         Value::Object(map) => {
             let mut sanitized_map = Map::new();
             for (key, val) in map {
-                let key_lower = key.to_lowercase();
-                let is_sensitive_field = SENSITIVE_FIELDS
-                    .iter()
-                    .any(|&sensitive| key_lower.contains(sensitive));
-
-                if is_sensitive_field {
+                if is_sensitive_field(key) {
                     // Only redact if the value could contain sensitive data
                     match val {
                         Value::String(s) if !s.is_empty() => {
@@ -178,6 +204,16 @@ pub fn compact_json_value(value: &Value, max_depth: usize, current_depth: usize)
 }
 
 pub fn process_logs_for_llm(logs: &[LogEntry], limit: usize, sanitize: bool) -> LlmLogOutput {
+    process_logs_for_llm_with_config(logs, limit, sanitize, default_config())
+}
+
+/// Like [`process_logs_for_llm`], with typed fields chosen by the profile's `[serialization]`
+pub fn process_logs_for_llm_with_config(
+    logs: &[LogEntry],
+    limit: usize,
+    sanitize: bool,
+    config: &AnalyzerConfig,
+) -> LlmLogOutput {
     let total_entries = logs.len();
     let filtered_entries = if limit > 0 && limit < logs.len() {
         limit
@@ -231,23 +267,17 @@ pub fn process_logs_for_llm(logs: &[LogEntry], limit: usize, sanitize: bool) ->
         *entry_types.entry(entry_type).or_insert(0) += 1;
     }
 
-    let time_range = if !logs_to_process.is_empty() {
-        Some(TimeRange {
-            start: logs_to_process
-                .first()
-                .unwrap()
-                .timestamp
-                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-                .to_string(),
-            end: logs_to_process
-                .last()
-                .unwrap()
-                .timestamp
-                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-                .to_string(),
-        })
-    } else {
-        None
+    let rfc3339 = |log: &LogEntry| {
+        log.timestamp
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Millis, true)
+    };
+    let time_range = match (logs_to_process.first(), logs_to_process.last()) {
+        (Some(first), Some(last)) => Some(TimeRange {
+            start: rfc3339(first),
+            end: rfc3339(last),
+        }),
+        _ => None,
     };
 
     let metadata = LlmMetadata {
@@ -259,6 +289,8 @@ pub fn process_logs_for_llm(logs: &[LogEntry], limit: usize, sanitize: bool) ->
         time_range,
     };
 
+    let typed = TypedFields::new(logs, config);
+
     // Process each log entry
     let processed_logs: Vec<LlmLogEntry> = logs_to_process
         .iter()
@@ -305,6 +337,9 @@ pub fn process_logs_for_llm(logs: &[LogEntry], limit: usize, sanitize: bool) ->
             LlmLogEntry {
                 idx: idx + 1,
                 ts: log.timestamp.format("%H:%M:%S%.3f").to_string(),
+                at: typed.rfc3339(log),
+                ms: typed.epoch_millis(log),
+                dur: typed.duration_ms(log),
                 comp: log.component.clone(),
                 lvl: log.level.clone(),
                 typ: entry_type,
@@ -315,6 +350,8 @@ pub fn process_logs_for_llm(logs: &[LogEntry], limit: usize, sanitize: bool) ->
         .collect();
 
     LlmLogOutput {
+        schema_version: COMPACT_SCHEMA_VERSION,
+        legend: None,
         metadata,
         logs: processed_logs,
     }
@@ -12,6 +12,7 @@ fn display_error(err: Box<dyn std::error::Error>) -> String {
 }
 
 fn main() {
+    log_analyzer::output::restore_default_sigpipe();
     if let Err(err) = log_analyzer::run() {
         eprintln!("Error: {}", display_error(err));
         process::exit(1);
@@ -0,0 +1,299 @@
+//! `matrix`: one row of key metrics per run directory, with a trend line per metric
+
+use crate::comparator::create_styled_table;
+use crate::health::HealthSummary;
+use crate::perf_analyzer::PerfAnalysisResults;
+use crate::units;
+use comfy_table::{Cell, CellAlignment};
+use serde::Serialize;
+use serde_json::json;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One run: a subdirectory and the log files directly inside it
+#[derive(Debug, Clone)]
+pub struct RunDir {
+    pub name: String,
+    pub files: Vec<PathBuf>,
+}
+
+/// Subdirectories of `dir` that contain files, ordered by name, keeping the last `last`
+/// (0 = all). Hidden entries are skipped.
+pub fn discover_runs(dir: &Path, last: usize) -> Result<Vec<RunDir>, Box<dyn Error>> {
+    let hidden = |path: &Path| {
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+    };
+    let read = |dir: &Path| {
+        fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read directory '{}': {}", dir.display(), e))
+    };
+
+    let mut runs = Vec::new();
+    for entry in read(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() || hidden(&path) {
+            continue;
+        }
+        let mut files: Vec<PathBuf> = read(&path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| file.is_file() && !hidden(file))
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        files.sort();
+        runs.push(RunDir {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            files,
+        });
+    }
+    if runs.is_empty() {
+        return Err(format!(
+            "'{}' has no run subdirectories with log files",
+            dir.display()
+        )
+        .into());
+    }
+    runs.sort_by(|a, b| a.name.cmp(&b.name));
+    if last > 0 && runs.len() > last {
+        runs.drain(..runs.len() - last);
+    }
+    Ok(runs)
+}
+
+/// p95 latency of one selected request in one run
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestLatency {
+    pub request: String,
+    pub p95_ms: Option<i64>,
+}
+
+/// Key metrics of one run
+#[derive(Debug, Clone, Serialize)]
+pub struct RunMetrics {
+    pub run: String,
+    pub files: usize,
+    pub entries: usize,
+    pub errors: usize,
+    pub sessions: usize,
+    pub orphaned_sessions: usize,
+    /// One per selected request, in selection order
+    pub latencies: Vec<RequestLatency>,
+    /// First to last entry
+    pub duration_ms: i64,
+}
+
+impl RunMetrics {
+    /// Collects the metrics of a run from its perf analysis and health summary
+    pub fn new(
+        run: &RunDir,
+        perf: &PerfAnalysisResults,
+        health: &HealthSummary,
+        requests: &[String],
+    ) -> Self {
+        let latencies = requests
+            .iter()
+            .map(|request| RequestLatency {
+                request: request.clone(),
+                p95_ms: perf
+                    .stats
+                    .iter()
+                    .filter(|stats| &stats.name == request)
+                    .map(|stats| stats.p95_duration_ms)
+                    .max(),
+            })
+            .collect();
+        Self {
+            run: run.name.clone(),
+            files: run.files.len(),
+            entries: health.total_entries,
+            errors: health.error_entries,
+            sessions: health.sessions,
+            orphaned_sessions: health.orphaned_sessions,
+            latencies,
+            duration_ms: perf
+                .time_range
+                .map(|(start, end)| (end - start).num_milliseconds())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Runs (oldest first) × metrics
+#[derive(Debug, Clone)]
+pub struct RunMatrix {
+    pub dir: PathBuf,
+    pub requests: Vec<String>,
+    pub runs: Vec<RunMetrics>,
+}
+
+impl RunMatrix {
+    /// `(metric name, value per run)` for every column that gets a trend line
+    fn series(&self) -> Vec<(String, Vec<Option<f64>>)> {
+        let column =
+            |value: fn(&RunMetrics) -> f64| self.runs.iter().map(|run| Some(value(run))).collect();
+        let mut series = vec![
+            ("errors".to_string(), column(|run| run.errors as f64)),
+            (
+                "orphaned_sessions".to_string(),
+                column(|run| run.orphaned_sessions as f64),
+            ),
+        ];
+        for (index, request) in self.requests.iter().enumerate() {
+            series.push((
+                format!("p95 {request}"),
+                self.runs
+                    .iter()
+                    .map(|run| run.latencies[index].p95_ms.map(|ms| ms as f64))
+                    .collect(),
+            ));
+        }
+        series.push(("duration".to_string(), column(|run| run.duration_ms as f64)));
+        series
+    }
+}
+
+/// One block character per value scaled between the smallest and largest value;
+/// missing values are blank
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    let present = values.iter().flatten();
+    let min = present.clone().copied().fold(f64::INFINITY, f64::min);
+    let max = present.copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| match value {
+            None => ' ',
+            Some(_) if max <= min => SPARK_LEVELS[0],
+            Some(value) => {
+                let level = (value - min) / (max - min) * (SPARK_LEVELS.len() - 1) as f64;
+                SPARK_LEVELS[level.round() as usize]
+            }
+        })
+        .collect()
+}
+
+fn format_p95(p95_ms: Option<i64>) -> String {
+    p95_ms.map_or_else(|| "-".to_string(), units::format_duration_ms)
+}
+
+pub fn format_matrix_text(matrix: &RunMatrix) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "RUN MATRIX: {} run(s) in {} (oldest first)",
+        matrix.runs.len(),
+        matrix.dir.display()
+    );
+
+    let mut headers = vec!["Run", "Entries", "Errors", "Orphaned"];
+    let p95_headers: Vec<String> = matrix
+        .requests
+        .iter()
+        .map(|request| format!("p95 {request}"))
+        .collect();
+    headers.extend(p95_headers.iter().map(String::as_str));
+    headers.push("Duration");
+    let mut table = create_styled_table(&headers);
+    let right = |text: String| Cell::new(text).set_alignment(CellAlignment::Right);
+    for run in &matrix.runs {
+        let mut row = vec![
+            Cell::new(&run.run),
+            right(run.entries.to_string()),
+            right(run.errors.to_string()),
+            right(format!("{}/{}", run.orphaned_sessions, run.sessions)),
+        ];
+        row.extend(
+            run.latencies
+                .iter()
+                .map(|latency| right(format_p95(latency.p95_ms))),
+        );
+        row.push(right(units::format_duration_ms(run.duration_ms)));
+        table.add_row(row);
+    }
+    let _ = writeln!(out, "\n{table}");
+
+    let series = matrix.series();
+    let width = series.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let _ = writeln!(out, "\nTrends (oldest → newest):");
+    for (name, values) in &series {
+        let _ = writeln!(out, "  {name:<width$}  {}", sparkline(values));
+    }
+    out
+}
+
+pub fn format_matrix_json(matrix: &RunMatrix) -> String {
+    let trends: serde_json::Map<String, serde_json::Value> = matrix
+        .series()
+        .into_iter()
+        .map(|(name, values)| (name, json!(sparkline(&values))))
+        .collect();
+    serde_json::to_string_pretty(&json!({
+        "matrix": {
+            "dir": matrix.dir.display().to_string(),
+            "requests": matrix.requests,
+            "runs": matrix.runs,
+            "trends": trends,
+        }
+    }))
+    .unwrap_or_else(|_| "{\"matrix\":{\"error\":\"failed to serialize run matrix\"}}".into())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One row per run; p95 columns are empty when a run has no completed operation of that name
+pub fn format_matrix_csv(matrix: &RunMatrix) -> String {
+    let mut out = String::from("run,files,entries,errors,sessions,orphaned_sessions");
+    for request in &matrix.requests {
+        let _ = write!(out, ",{}", csv_field(&format!("p95_ms {request}")));
+    }
+    out.push_str(",duration_ms\n");
+    for run in &matrix.runs {
+        let _ = write!(
+            out,
+            "{},{},{},{},{},{}",
+            csv_field(&run.run),
+            run.files,
+            run.entries,
+            run.errors,
+            run.sessions,
+            run.orphaned_sessions
+        );
+        for latency in &run.latencies {
+            out.push(',');
+            if let Some(p95_ms) = latency.p95_ms {
+                let _ = write!(out, "{p95_ms}");
+            }
+        }
+        let _ = writeln!(out, ",{}", run.duration_ms);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_between_extremes_and_blanks_missing_values() {
+        assert_eq!(
+            sparkline(&[Some(0.0), Some(7.0), None, Some(3.5), Some(14.0)]),
+            "▁▅ ▃█"
+        );
+        assert_eq!(sparkline(&[Some(5.0), Some(5.0)]), "▁▁");
+        assert_eq!(sparkline(&[None]), " ");
+    }
+}
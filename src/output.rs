@@ -0,0 +1,190 @@
+//! Report files written with `-o` and friends
+//!
+//! Files are written to a hidden temporary file next to the target and renamed into
+//! place once complete, so an interrupted run never leaves a partial report behind.
+//! Targets ending in `.gz` or `.zst` are compressed on the way out.
+
+use flate2::Compression as GzipLevel;
+use flate2::write::GzEncoder;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// zstd level used for `.zst` output; favors speed, reports compress well anyway
+const ZSTD_LEVEL: i32 = 3;
+
+/// Compression applied to an output file, chosen from its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("gz") => Compression::Gzip,
+            Some(ext) if ext.eq_ignore_ascii_case("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+}
+
+enum Sink {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+/// An output file that only appears at its path once [`OutputFile::commit`] succeeds.
+/// Dropping it uncommitted removes the temporary file and leaves the target untouched.
+pub struct OutputFile {
+    sink: Option<Sink>,
+    staging: PathBuf,
+    target: PathBuf,
+}
+
+impl OutputFile {
+    /// Compresses according to the extension of `path`
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Self::create_with(path, Compression::from_path(path))
+    }
+
+    pub fn create_with(path: &Path, compression: Compression) -> io::Result<Self> {
+        let staging = staging_path(path);
+        let file = BufWriter::new(File::create(&staging)?);
+        let sink = match compression {
+            Compression::None => Sink::Plain(file),
+            Compression::Gzip => Sink::Gzip(GzEncoder::new(file, GzipLevel::default())),
+            Compression::Zstd => match zstd::Encoder::new(file, ZSTD_LEVEL) {
+                Ok(encoder) => Sink::Zstd(encoder),
+                Err(e) => {
+                    let _ = std::fs::remove_file(&staging);
+                    return Err(e);
+                }
+            },
+        };
+        Ok(Self {
+            sink: Some(sink),
+            staging,
+            target: path.to_path_buf(),
+        })
+    }
+
+    /// Finishes compression, flushes to disk, and renames the file into place
+    pub fn commit(mut self) -> io::Result<()> {
+        let sink = self.sink.take().expect("sink is present until commit");
+        let finished = (|| {
+            let file = match sink {
+                Sink::Plain(file) => file,
+                Sink::Gzip(encoder) => encoder.finish()?,
+                Sink::Zstd(encoder) => encoder.finish()?,
+            };
+            let file = file.into_inner().map_err(|e| e.into_error())?;
+            file.sync_all()?;
+            drop(file);
+            std::fs::rename(&self.staging, &self.target)
+        })();
+        if finished.is_err() {
+            let _ = std::fs::remove_file(&self.staging);
+        }
+        finished
+    }
+
+    fn sink(&mut self) -> &mut dyn Write {
+        match self.sink.as_mut().expect("sink is present until commit") {
+            Sink::Plain(file) => file,
+            Sink::Gzip(encoder) => encoder,
+            Sink::Zstd(encoder) => encoder,
+        }
+    }
+}
+
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sink().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.sink().flush()
+    }
+}
+
+impl Drop for OutputFile {
+    fn drop(&mut self) {
+        if self.sink.take().is_some() {
+            let _ = std::fs::remove_file(&self.staging);
+        }
+    }
+}
+
+/// `.<name>.<pid>.tmp` in the target's directory, so the final rename stays on one filesystem
+fn staging_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    path.with_file_name(format!(".{name}.{}.tmp", std::process::id()))
+}
+
+/// Writes `content` to `path` atomically, compressing it for `.gz` / `.zst` targets
+pub fn write_output(path: &Path, content: &[u8]) -> io::Result<()> {
+    let mut file = OutputFile::create(path)?;
+    file.write_all(content)?;
+    file.commit()
+}
+
+/// Makes a closed stdout (e.g. `log-analyzer errors big.log | head`) end the process
+/// quietly, like other command-line tools, instead of panicking in the middle of
+/// printing a report. The Rust runtime ignores SIGPIPE; this puts the default action
+/// back. Call it before any other thread is started.
+#[cfg(unix)]
+pub fn restore_default_sigpipe() {
+    // SAFETY: only changes the process signal disposition, before other threads exist
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn restore_default_sigpipe() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn writes_atomically_and_compresses_by_extension() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let report = "{\"errors\": []}\n".repeat(50);
+
+        for name in ["report.json", "report.json.gz", "report.json.zst"] {
+            let path = dir.path().join(name);
+            write_output(&path, report.as_bytes()).expect("report is written");
+            let bytes = std::fs::read(&path).expect("report exists");
+            let text = match Compression::from_path(&path) {
+                Compression::None => String::from_utf8(bytes).unwrap(),
+                Compression::Gzip => {
+                    let mut text = String::new();
+                    flate2::read::GzDecoder::new(bytes.as_slice())
+                        .read_to_string(&mut text)
+                        .unwrap();
+                    text
+                }
+                Compression::Zstd => {
+                    String::from_utf8(zstd::decode_all(bytes.as_slice()).unwrap()).unwrap()
+                }
+            };
+            assert_eq!(text, report, "{name} round-trips");
+        }
+
+        let abandoned = dir.path().join("abandoned.json");
+        let mut file = OutputFile::create(&abandoned).expect("staging file is created");
+        file.write_all(b"partial").unwrap();
+        drop(file);
+        assert!(!abandoned.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+}
@@ -3,18 +3,22 @@ use regex::Regex;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
 use std::path::Path;
 use std::sync::LazyLock;
 
 use crate::config::{AnalyzerConfig, LogFormat, ParserRules, contains_any_marker, default_config};
+use crate::diagnostics::{WarningCode, WarningLog};
+use crate::heartbeats::HeartbeatSuppressor;
+use crate::tags::Tagger;
+use serde::{Deserialize, Serialize};
 
 mod entities;
 
 pub use entities::{
-    CommandLogParams, EventDirection, EventLogParams, LogEntry, LogEntryBase, LogEntryKind,
-    RequestDirection, RequestLogParams, create_command_log, create_event_log, create_generic_log,
-    create_request_log,
+    CommandLogParams, Endpoint, EventDirection, EventLogParams, LogEntry, LogEntryBase,
+    LogEntryKind, RequestDirection, RequestLogParams, create_command_log, create_event_log,
+    create_generic_log, create_request_log, raw_excerpt, value_at_path,
 };
 
 static CLASSIC_ENTRY_START: LazyLock<Regex> = LazyLock::new(|| {
@@ -67,6 +71,49 @@ impl From<std::io::Error> for ParseError {
     }
 }
 
+/// Maximum characters kept from an offending payload
+const PAYLOAD_SNIPPET_CHARS: usize = 120;
+/// Offending payloads listed per file in summaries (`--payload-errors` lists all)
+pub const PAYLOAD_ERROR_SAMPLES: usize = 3;
+
+/// A payload that was announced by a JSON indicator but could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayloadParseFailure {
+    pub line: usize,
+    pub snippet: String,
+}
+
+/// Payload parse failures of a single log file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PayloadParseErrors {
+    pub file: String,
+    pub failures: Vec<PayloadParseFailure>,
+}
+
+impl PayloadParseErrors {
+    /// The first [`PAYLOAD_ERROR_SAMPLES`] failures, or all of them
+    pub fn samples(&self, all: bool) -> &[PayloadParseFailure] {
+        let limit = if all {
+            self.failures.len()
+        } else {
+            PAYLOAD_ERROR_SAMPLES.min(self.failures.len())
+        };
+        &self.failures[..limit]
+    }
+
+    pub fn to_json(&self, all: bool) -> Value {
+        serde_json::json!({
+            "file": self.file,
+            "count": self.failures.len(),
+            "samples": self
+                .samples(all)
+                .iter()
+                .map(|failure| serde_json::json!({ "line": failure.line, "snippet": failure.snippet }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
 /// Parses a log file into a vector of LogEntry structs
 pub fn parse_log_file(path: impl AsRef<Path>) -> Result<Vec<LogEntry>, ParseError> {
     parse_log_file_with_config(path, default_config())
@@ -107,60 +154,342 @@ pub fn parse_log_file_with_config(
     path: impl AsRef<Path>,
     config: &AnalyzerConfig,
 ) -> Result<Vec<LogEntry>, ParseError> {
-    let path = path.as_ref();
-    let format = detect_log_format(path, config)?;
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    let mut logs = Vec::new();
+    LogEntryReader::open(path, config)?.collect()
+}
 
-    if format == LogFormat::JsonLines {
-        for (index, line) in reader.lines().enumerate() {
-            let line = line?;
-            if line.trim().is_empty() {
-                continue;
-            }
+/// Like [`parse_log_file_with_config`], recording the reader's warnings in `warnings`
+pub fn parse_log_file_reporting(
+    path: impl AsRef<Path>,
+    config: &AnalyzerConfig,
+    warnings: &WarningLog,
+) -> Result<Vec<LogEntry>, ParseError> {
+    LogEntryReader::open(path, config)?
+        .reporting_to(warnings)
+        .collect()
+}
 
-            match parse_log_entry_in_format(&line, index + 1, config, format) {
-                Ok(entry) => logs.push(entry),
-                Err(ParseError::InvalidLogFormat(_)) => {}
-                Err(err) => return Err(err),
-            }
+/// Where reading a log file can continue after the entries returned so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadPosition {
+    /// Byte offset of the first line not yet turned into an entry
+    pub offset: u64,
+    /// Lines before `offset`
+    pub lines: usize,
+}
+
+/// Inclusive range of 1-based line numbers or of byte offsets; `end: None` runs to the
+/// end of the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScopeRange {
+    pub start: u64,
+    pub end: Option<u64>,
+}
+
+impl ScopeRange {
+    /// Parses `START-END`, `START-` or `-END`, each bound read by `parse_bound`
+    pub fn parse(
+        input: &str,
+        parse_bound: impl Fn(&str) -> Result<u64, String>,
+    ) -> Result<Self, String> {
+        let (start, end) = input
+            .split_once('-')
+            .ok_or_else(|| format!("invalid range '{input}' (expected START-END)"))?;
+        let start = match start.trim() {
+            "" => 0,
+            bound => parse_bound(bound)?,
+        };
+        let end = match end.trim() {
+            "" => None,
+            bound => Some(parse_bound(bound)?),
+        };
+        if end.is_some_and(|end| end < start) {
+            return Err(format!("invalid range '{input}' (end is before start)"));
         }
+        Ok(Self { start, end })
+    }
 
-        return Ok(logs);
+    fn is_past(&self, value: u64) -> bool {
+        self.end.is_some_and(|end| value > end)
     }
+}
 
-    let mut current_log: Option<String> = None;
-    let mut current_line_number = 0usize;
+/// Slice of each input file to read (`--lines`, `--bytes`). An entry is read when both
+/// the number and the byte offset of its first line are in range, along with all its
+/// continuation lines; line numbers stay those of the whole file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReadScope {
+    pub lines: Option<ScopeRange>,
+    pub bytes: Option<ScopeRange>,
+}
 
-    for (index, line) in reader.lines().enumerate() {
-        let line_number = index + 1;
-        let line = line?;
+impl ReadScope {
+    pub fn is_whole_file(&self) -> bool {
+        self.lines.is_none() && self.bytes.is_none()
+    }
 
-        if line_starts_entry(&line, format) {
-            if let Some(log_text) = current_log.take() {
-                match parse_log_entry_in_format(&log_text, current_line_number, config, format) {
-                    Ok(entry) => logs.push(entry),
-                    Err(ParseError::InvalidLogFormat(_)) => {}
-                    Err(err) => return Err(err),
-                }
+    /// Whether the line starting at `position` comes before the scope
+    fn is_before(&self, position: ReadPosition) -> bool {
+        self.lines
+            .is_some_and(|lines| (position.lines as u64 + 1) < lines.start)
+            || self
+                .bytes
+                .is_some_and(|bytes| position.offset < bytes.start)
+    }
+
+    /// Whether the line starting at `position` and every later line are past the scope
+    fn is_past(&self, position: ReadPosition) -> bool {
+        self.lines
+            .is_some_and(|lines| lines.is_past(position.lines as u64 + 1))
+            || self
+                .bytes
+                .is_some_and(|bytes| bytes.is_past(position.offset))
+    }
+
+    /// Label for report headers, e.g. `lines 10000-20000`; `None` for the whole file
+    pub fn describe(&self) -> Option<String> {
+        let range = |range: &ScopeRange, bound: fn(u64) -> String| match range.end {
+            Some(end) => format!("{}-{}", bound(range.start), bound(end)),
+            None => format!("{}-", bound(range.start)),
+        };
+        let parts: Vec<String> = self
+            .lines
+            .iter()
+            .map(|lines| format!("lines {}", range(lines, |line| line.to_string())))
+            .chain(
+                self.bytes
+                    .iter()
+                    .map(|bytes| format!("bytes {}", range(bytes, crate::units::format_bytes))),
+            )
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// Reads a log file one entry at a time, joining continuation lines to the entry they
+/// follow. Entries that fail to parse are skipped, except for I/O and payload errors.
+pub struct LogEntryReader<'a> {
+    reader: BufReader<File>,
+    config: &'a AnalyzerConfig,
+    format: LogFormat,
+    tagger: Tagger,
+    heartbeats: HeartbeatSuppressor,
+    /// The file, for the suppressed heartbeats warning
+    source: String,
+    warnings: Option<&'a WarningLog>,
+    /// Text and line number of the entry still collecting continuation lines
+    pending: Option<(String, usize)>,
+    pending_offset: u64,
+    position: ReadPosition,
+    line: String,
+    finished: bool,
+    /// Non-blank lines that did not become part of an entry
+    skipped_lines: usize,
+}
+
+impl<'a> LogEntryReader<'a> {
+    pub fn open(path: impl AsRef<Path>, config: &'a AnalyzerConfig) -> Result<Self, ParseError> {
+        Self::open_at(path, config, ReadPosition::default())
+    }
+
+    /// Opens `path` to continue at a position returned by [`LogEntryReader::position`]
+    pub fn open_at(
+        path: impl AsRef<Path>,
+        config: &'a AnalyzerConfig,
+        position: ReadPosition,
+    ) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let format = detect_log_format(path, config)?;
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(position.offset))?;
+        let mut reader = Self {
+            reader: BufReader::new(file),
+            config,
+            format,
+            tagger: Tagger::new(&config.tags),
+            heartbeats: HeartbeatSuppressor::new(&config.heartbeats),
+            source: path.display().to_string(),
+            warnings: None,
+            pending: None,
+            pending_offset: position.offset,
+            position,
+            line: String::new(),
+            finished: false,
+            skipped_lines: 0,
+        };
+        reader.skip_to_scope()?;
+        Ok(reader)
+    }
+
+    /// Records warnings about the file, such as suppressed heartbeats, in `warnings`.
+    /// Without it the reader emits none.
+    pub fn reporting_to(mut self, warnings: &'a WarningLog) -> Self {
+        self.warnings = Some(warnings);
+        self
+    }
+
+    /// Reads past the lines before `[parser] scope`, counting them so line numbers stay
+    /// those of the whole file. A line straddling the scope's first byte is left out.
+    fn skip_to_scope(&mut self) -> Result<(), ParseError> {
+        let scope = self.config.parser.scope;
+        let mut line = Vec::new();
+        while scope.is_before(self.position) {
+            line.clear();
+            let read = self.reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
             }
+            self.position.offset += read as u64;
+            self.position.lines += 1;
+        }
+        self.pending_offset = self.position.offset;
+        Ok(())
+    }
 
-            current_log = Some(line);
-            current_line_number = line_number;
-        } else if let Some(ref mut log_text) = current_log {
-            log_text.push('\n');
-            log_text.push_str(&line);
+    /// Position to reopen at so that reading continues with the next entry
+    pub fn position(&self) -> ReadPosition {
+        match &self.pending {
+            Some((_, line_number)) => ReadPosition {
+                offset: self.pending_offset,
+                lines: line_number - 1,
+            },
+            None => self.position,
         }
     }
 
-    if let Some(log_text) = current_log
-        && let Ok(entry) = parse_log_entry_in_format(&log_text, current_line_number, config, format)
-    {
-        logs.push(entry);
+    /// Non-blank lines read so far that belong to no returned entry: text before the
+    /// first entry and entries that do not match the log format
+    pub fn skipped_lines(&self) -> usize {
+        self.skipped_lines
+    }
+
+    fn skip(&mut self, log_text: &str) {
+        self.skipped_lines += log_text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .count();
+    }
+
+    /// Reads the next line without its line ending; `false` at end of file or scope.
+    /// Past the scope, only continuation lines of the entry in progress are read.
+    fn read_line(&mut self) -> Result<bool, ParseError> {
+        self.line.clear();
+        let past_scope = self.config.parser.scope.is_past(self.position);
+        if past_scope && self.pending.is_none() {
+            return Ok(false);
+        }
+        let read = self.reader.read_line(&mut self.line)?;
+        if read == 0
+            || past_scope
+                && (self.format == LogFormat::JsonLines
+                    || line_starts_entry(&self.line, self.format))
+        {
+            return Ok(false);
+        }
+        self.position.offset += read as u64;
+        self.position.lines += 1;
+        if self.line.ends_with('\n') {
+            self.line.pop();
+            if self.line.ends_with('\r') {
+                self.line.pop();
+            }
+        }
+        Ok(true)
+    }
+
+    fn parse(&self, log_text: &str, line_number: usize) -> Result<LogEntry, ParseError> {
+        let mut entry = parse_log_entry_in_format(log_text, line_number, self.config, self.format)?;
+        self.tagger.tag(&mut entry);
+        Ok(entry)
+    }
+
+    /// `entry`, unless a `[[heartbeats]]` rule suppresses it
+    fn keep(&mut self, entry: LogEntry) -> Option<LogEntry> {
+        (!self.heartbeats.suppresses(&entry)).then_some(entry)
     }
 
-    Ok(logs)
+    /// Warns once the file is read if heartbeat rules suppressed entries
+    fn report_suppressed(&self) {
+        let suppressed = self.heartbeats.suppressed();
+        let Some(warnings) = self.warnings.filter(|_| !suppressed.is_empty()) else {
+            return;
+        };
+        let total: usize = suppressed.iter().map(|(_, count)| count).sum();
+        let rules: Vec<String> = suppressed
+            .iter()
+            .map(|(name, count)| format!("{name} ×{count}"))
+            .collect();
+        warnings.warn(
+            WarningCode::HeartbeatsSuppressed,
+            format!(
+                "suppressed {total} repeated heartbeat entries in '{}' ({})",
+                self.source,
+                rules.join(", ")
+            ),
+        );
+    }
+}
+
+impl Iterator for LogEntryReader<'_> {
+    type Item = Result<LogEntry, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let line_offset = self.position.offset;
+            match self.read_line() {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.finished = true;
+                    let entry = self.pending.take().and_then(|(log_text, line_number)| {
+                        let entry = self.parse(&log_text, line_number).ok();
+                        if entry.is_none() {
+                            self.skip(&log_text);
+                        }
+                        self.keep(entry?)
+                    });
+                    self.report_suppressed();
+                    return entry.map(Ok);
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            }
+            let line_number = self.position.lines;
+
+            let complete = if self.format == LogFormat::JsonLines {
+                if self.line.trim().is_empty() {
+                    continue;
+                }
+                Some((std::mem::take(&mut self.line), line_number))
+            } else if line_starts_entry(&self.line, self.format) {
+                self.pending_offset = line_offset;
+                self.pending
+                    .replace((std::mem::take(&mut self.line), line_number))
+            } else {
+                if let Some((log_text, _)) = &mut self.pending {
+                    log_text.push('\n');
+                    log_text.push_str(&self.line);
+                } else {
+                    let line = std::mem::take(&mut self.line);
+                    self.skip(&line);
+                }
+                None
+            };
+
+            if let Some((log_text, line_number)) = complete {
+                match self.parse(&log_text, line_number) {
+                    Ok(entry) => {
+                        if let Some(entry) = self.keep(entry) {
+                            return Some(Ok(entry));
+                        }
+                    }
+                    Err(ParseError::InvalidLogFormat(_)) => self.skip(&log_text),
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+        }
+        None
+    }
 }
 
 /// Parses a single log entry string into a LogEntry struct
@@ -175,7 +504,9 @@ pub fn parse_log_entry_with_config(
     config: &AnalyzerConfig,
 ) -> Result<LogEntry, ParseError> {
     let format = detect_format_from_lines(log_text.lines(), config.parser.format);
-    parse_log_entry_in_format(log_text, source_line_number, config, format)
+    let mut entry = parse_log_entry_in_format(log_text, source_line_number, config, format)?;
+    crate::tags::tag_entries(std::slice::from_mut(&mut entry), &config.tags);
+    Ok(entry)
 }
 
 fn detect_format_from_lines<'a>(
@@ -830,7 +1161,7 @@ fn determine_log_entry_kind(
             })?;
 
             let payload_str = event_parts[1].trim();
-            let payload = extract_json(payload_str, &parser_rules.json_indicators);
+            let payload = extract_payload(payload_str, parser_rules);
 
             message_text = format!(
                 "{} {} [JSON removed]",
@@ -864,7 +1195,7 @@ fn determine_log_entry_kind(
             })?;
 
             let payload_str = event_parts[1].trim();
-            let payload = extract_json(payload_str, &parser_rules.json_indicators);
+            let payload = extract_payload(payload_str, parser_rules);
 
             message_text = format!(
                 "{} {} [JSON removed]",
@@ -910,7 +1241,7 @@ fn determine_log_entry_kind(
                     if let Some(start_idx) = message.find(indicator.as_str()) {
                         let settings_start = start_idx + indicator.len() - 1;
                         let settings_str = &message[settings_start..];
-                        settings = extract_json(settings_str, &parser_rules.json_indicators);
+                        settings = extract_payload(settings_str, parser_rules);
 
                         cleaned_message = message[..start_idx].to_string();
                         cleaned_message.push_str(indicator);
@@ -977,20 +1308,25 @@ fn determine_log_entry_kind(
         }
     }
 
-    let payload = extract_json(message, &parser_rules.json_indicators);
+    let payload = extract_payload(message, parser_rules);
 
-    if payload.is_some() {
-        let mut cleaned_message = String::new();
-        for (index, ch) in message.char_indices() {
-            if (ch == '{' || ch == '[') && extract_json_from_position(message, index).is_some() {
-                cleaned_message = message[..index].to_string();
-                cleaned_message.push_str("[JSON removed]");
-                break;
+    // Skipped payloads are still cut from the message, so fast paths such as
+    // `count` see the same message (and log key) as a full parse
+    if payload.is_some() || parser_rules.skip_payloads {
+        let json_start = message.char_indices().find_map(|(index, ch)| {
+            if ch != '{' && ch != '[' {
+                return None;
             }
-        }
+            let found = if parser_rules.skip_payloads {
+                matches!(json_span(message, index), Some(Ok(span)) if looks_like_json(span))
+            } else {
+                extract_json_from_position(message, index).is_some()
+            };
+            found.then_some(index)
+        });
 
-        if !cleaned_message.is_empty() {
-            message_text = cleaned_message;
+        if let Some(index) = json_start {
+            message_text = format!("{}[JSON removed]", &message[..index]);
         }
     }
 
@@ -1012,7 +1348,7 @@ fn extract_request_info(
 ) -> (
     Option<String>,
     Option<String>,
-    Option<String>,
+    Option<Endpoint>,
     RequestDirection,
     Option<Value>,
 ) {
@@ -1046,9 +1382,17 @@ fn extract_request_info(
     if !parser_rules.request_endpoint_marker.is_empty()
         && let Some(addr_start) = message.find(&parser_rules.request_endpoint_marker)
     {
-        let addr_content_start = addr_start + parser_rules.request_endpoint_marker.len();
-        if let Some(addr_end) = message[addr_content_start..].find(']') {
-            endpoint = Some(message[addr_content_start..addr_content_start + addr_end].to_string());
+        // The marker usually ends inside the address (`address "[`): the method is read up
+        // to `]` and the URL that follows up to the closing quote
+        let address = &message[addr_start + parser_rules.request_endpoint_marker.len()..];
+        if let Some(method_end) = address.find(']') {
+            let url = &address[method_end + 1..];
+            let url_end = url.find(['"', ' ']).unwrap_or(url.len());
+            endpoint = Some(Endpoint::parse(&format!(
+                "[{}]{}",
+                &address[..method_end],
+                &url[..url_end]
+            )));
         }
     }
 
@@ -1061,7 +1405,7 @@ fn extract_request_info(
     for indicator in &parser_rules.request_payload_markers {
         if let Some(start_idx) = message.find(indicator.as_str()) {
             let body_content = &message[start_idx + indicator.len()..];
-            payload = extract_json(body_content, &parser_rules.json_indicators);
+            payload = extract_payload(body_content, parser_rules);
             break;
         }
     }
@@ -1090,32 +1434,74 @@ fn extract_event_type(event_part: &str) -> Option<String> {
     None
 }
 
-fn extract_json(input: &str, json_indicators: &[String]) -> Option<Value> {
-    for indicator in json_indicators {
-        if indicator.is_empty() {
-            continue;
-        }
-        if let Some(marker_pos) = input.find(indicator.as_str()) {
-            let start_pos = marker_pos + indicator.len();
-
-            let json_start = if indicator == "with body" || indicator == "with body " {
-                let mut index = None;
-                for (offset, ch) in input[start_pos..].char_indices() {
-                    if ch == '[' || ch == '{' {
-                        index = Some(start_pos + offset);
-                        break;
-                    }
-                }
-                index
-            } else {
-                Some(start_pos.saturating_sub(1))
-            };
+fn extract_payload(input: &str, parser_rules: &ParserRules) -> Option<Value> {
+    if parser_rules.skip_payloads {
+        return None;
+    }
+    extract_json(input, &parser_rules.json_indicators)
+}
 
-            if let Some(start_idx) = json_start
-                && let Some(json_value) = extract_json_from_position(input, start_idx)
-            {
-                return Some(json_value);
-            }
+/// Entries whose payload was announced by a JSON indicator but failed to parse
+/// (invalid json5 or a truncated object/array)
+pub fn find_payload_parse_failures(
+    logs: &[LogEntry],
+    parser_rules: &ParserRules,
+) -> Vec<PayloadParseFailure> {
+    if parser_rules.skip_payloads {
+        return Vec::new();
+    }
+
+    logs.iter()
+        .filter(|entry| entry.payload().is_none())
+        .filter_map(|entry| {
+            let span = parser_rules
+                .json_indicators
+                .iter()
+                .filter_map(|indicator| indicator_json_start(&entry.raw_logline, indicator))
+                .find_map(|start| match json_span(&entry.raw_logline, start)? {
+                    Ok(span) if parse_json_span(span).is_some() => None,
+                    Ok(span) | Err(span) => Some(span),
+                })?;
+            Some(PayloadParseFailure {
+                line: entry.source_line_number,
+                snippet: payload_snippet(span),
+            })
+        })
+        .collect()
+}
+
+fn payload_snippet(span: &str) -> String {
+    let flattened = span.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flattened.chars().count() <= PAYLOAD_SNIPPET_CHARS {
+        return flattened;
+    }
+    let truncated: String = flattened.chars().take(PAYLOAD_SNIPPET_CHARS).collect();
+    format!("{truncated}…")
+}
+
+fn indicator_json_start(input: &str, indicator: &str) -> Option<usize> {
+    if indicator.is_empty() {
+        return None;
+    }
+    let start_pos = input.find(indicator)? + indicator.len();
+
+    if indicator == "with body" || indicator == "with body " {
+        input[start_pos..]
+            .char_indices()
+            .find(|(_, ch)| *ch == '[' || *ch == '{')
+            .map(|(offset, _)| start_pos + offset)
+    } else {
+        Some(start_pos.saturating_sub(1))
+    }
+}
+
+/// First JSON payload in `input`, preferring the position announced by an indicator
+pub fn extract_json(input: &str, json_indicators: &[String]) -> Option<Value> {
+    for indicator in json_indicators {
+        if let Some(start_idx) = indicator_json_start(input, indicator)
+            && let Some(json_value) = extract_json_from_position(input, start_idx)
+        {
+            return Some(json_value);
         }
     }
 
@@ -1131,7 +1517,31 @@ fn extract_json(input: &str, json_indicators: &[String]) -> Option<Value> {
 }
 
 fn extract_json_from_position(input: &str, start_pos: usize) -> Option<Value> {
-    if start_pos >= input.len() {
+    parse_json_span(json_span(input, start_pos)?.ok()?)
+}
+
+/// Cheap stand-in for parsing a balanced span: any object, or an array whose
+/// array of nested values, strings or plain numbers (so `[0--id1]` style tags are kept)
+fn looks_like_json(span: &str) -> bool {
+    if span.starts_with('{') {
+        return true;
+    }
+    let inner = span[1..span.len() - 1].trim();
+    inner.is_empty()
+        || inner.starts_with(['{', '[', '"', '\''])
+        || inner
+            .split(',')
+            .all(|item| item.trim().parse::<f64>().is_ok())
+}
+
+fn parse_json_span(span: &str) -> Option<Value> {
+    json5::from_str::<Value>(&span.replace("undefined", "null")).ok()
+}
+
+/// The balanced object/array starting at `start_pos`, or `Err` with the rest of
+/// the input when it is never closed
+pub(crate) fn json_span(input: &str, start_pos: usize) -> Option<Result<&str, &str>> {
+    if start_pos >= input.len() || !input.is_char_boundary(start_pos) {
         return None;
     }
 
@@ -1167,23 +1577,19 @@ fn extract_json_from_position(input: &str, start_pos: usize) -> Option<Value> {
             '}' => {
                 brace_count -= 1;
                 if brace_count == 0 && first_char == '{' && bracket_count == 0 {
-                    let json_str =
-                        input[start_pos..=start_pos + index].replace("undefined", "null");
-                    return json5::from_str::<Value>(&json_str).ok();
+                    return Some(Ok(&input[start_pos..=start_pos + index]));
                 }
             }
             '[' => bracket_count += 1,
             ']' => {
                 bracket_count -= 1;
                 if bracket_count == 0 && first_char == '[' && brace_count == 0 {
-                    let json_str =
-                        input[start_pos..=start_pos + index].replace("undefined", "null");
-                    return json5::from_str::<Value>(&json_str).ok();
+                    return Some(Ok(&input[start_pos..=start_pos + index]));
                 }
             }
             _ => {}
         }
     }
 
-    None
+    Some(Err(&input[start_pos..]))
 }
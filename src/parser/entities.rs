@@ -1,4 +1,6 @@
+use crate::session_path::SessionPath;
 use chrono::{DateTime, Local};
+use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt;
@@ -35,6 +37,99 @@ impl fmt::Display for RequestDirection {
     }
 }
 
+/// Target of a request, split from the logged address (e.g. `[POST]https://host/api/x`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Endpoint {
+    /// HTTP method, upper-cased (e.g. "POST")
+    pub method: Option<String>,
+    /// URL scheme (e.g. "https")
+    pub scheme: Option<String>,
+    /// Host with its port, if any (e.g. "eyesapi.applitools.com")
+    pub host: Option<String>,
+    /// Path with its query string (e.g. "/api/sessions/running")
+    pub path: Option<String>,
+}
+
+impl Endpoint {
+    /// Splits an address such as `POST https://host/path`, `[POST]https://host/path`,
+    /// `https://host/path`, `/path` or a bare method
+    pub fn parse(address: &str) -> Self {
+        let mut endpoint = Endpoint::default();
+        let mut rest = address.trim();
+        if let Some(bracketed) = rest.strip_prefix('[')
+            && let Some((inside, after)) = bracketed.split_once(']')
+        {
+            if is_http_method(inside.trim()) {
+                endpoint.method = Some(inside.trim().to_uppercase());
+                rest = after.trim();
+            } else {
+                rest = inside.trim();
+            }
+        } else if let Some((first, after)) = rest.split_once(char::is_whitespace)
+            && is_http_method(first)
+        {
+            endpoint.method = Some(first.to_uppercase());
+            rest = after.trim();
+        } else if is_http_method(rest) {
+            endpoint.method = Some(rest.to_uppercase());
+            rest = "";
+        }
+
+        let rest = rest.split_whitespace().next().unwrap_or("");
+        let (scheme, location) = match rest.split_once("://") {
+            Some((scheme, location)) => (Some(scheme), location),
+            None => (None, rest),
+        };
+        let (host, path) = if scheme.is_none() && location.starts_with('/') {
+            ("", location)
+        } else {
+            match location.find(['/', '?']) {
+                Some(index) => location.split_at(index),
+                None => (location, ""),
+            }
+        };
+        let non_empty = |text: &str| (!text.is_empty()).then(|| text.to_string());
+        endpoint.scheme = scheme.and_then(non_empty);
+        endpoint.host = non_empty(host);
+        endpoint.path = non_empty(path);
+        endpoint
+    }
+}
+
+fn is_http_method(word: &str) -> bool {
+    const METHODS: [&str; 9] = [
+        "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "CONNECT", "TRACE",
+    ];
+    METHODS
+        .iter()
+        .any(|method| method.eq_ignore_ascii_case(word))
+}
+
+/// `POST https://host/path`, leaving out the parts that were not logged
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(method) = &self.method {
+            parts.push(method.clone());
+        }
+        let mut url = String::new();
+        if let Some(host) = &self.host {
+            if let Some(scheme) = &self.scheme {
+                url.push_str(scheme);
+                url.push_str("://");
+            }
+            url.push_str(host);
+        }
+        if let Some(path) = &self.path {
+            url.push_str(path);
+        }
+        if !url.is_empty() {
+            parts.push(url);
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
 /// Different types of log entries based on their purpose
 #[derive(Debug, Clone)]
 pub enum LogEntryKind {
@@ -60,8 +155,8 @@ pub enum LogEntryKind {
         request: String,
         /// Optional request ID
         request_id: Option<String>,
-        /// Optional endpoint information
-        endpoint: Option<String>,
+        /// Method, host and path the request was sent to, when logged
+        endpoint: Option<Endpoint>,
         /// Whether the request is being sent or received
         direction: RequestDirection,
         /// Optional JSON payload
@@ -97,6 +192,8 @@ pub struct LogEntry {
     pub kind: LogEntryKind,
     /// Source line number in the original file (1-indexed)
     pub source_line_number: usize,
+    /// Names of the profile's `[tags]` rules this entry matches
+    pub tags: Vec<String>,
 }
 
 impl LogEntry {
@@ -110,10 +207,98 @@ impl LogEntry {
         }
     }
 
+    /// Get the request ID when this entry is a request that carries one
+    pub fn request_id(&self) -> Option<&str> {
+        match &self.kind {
+            LogEntryKind::Request { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Get the endpoint when this entry is a request that logged one
+    pub fn endpoint(&self) -> Option<&Endpoint> {
+        match &self.kind {
+            LogEntryKind::Request { endpoint, .. } => endpoint.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether a `[tags]` rule named `tag` (case-insensitive) matched this entry
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|own| own.eq_ignore_ascii_case(tag))
+    }
+
     pub fn structured_field(&self, key: &str) -> Option<&str> {
         self.structured_fields.get(key).map(String::as_str)
     }
 
+    /// Get a payload value by dotted path (e.g. "startInfo.batchInfo.id", "items.0.name")
+    pub fn payload_value(&self, path: &str) -> Option<&Value> {
+        value_at_path(self.payload()?, path)
+    }
+
+    /// Get a payload string by dotted path
+    pub fn payload_str(&self, path: &str) -> Option<&str> {
+        self.payload_value(path)?.as_str()
+    }
+
+    /// Get a payload integer by dotted path
+    pub fn payload_i64(&self, path: &str) -> Option<i64> {
+        self.payload_value(path)?.as_i64()
+    }
+
+    /// Get a payload number by dotted path
+    pub fn payload_f64(&self, path: &str) -> Option<f64> {
+        self.payload_value(path)?.as_f64()
+    }
+
+    /// Get a payload boolean by dotted path
+    pub fn payload_bool(&self, path: &str) -> Option<bool> {
+        self.payload_value(path)?.as_bool()
+    }
+
+    /// Get the request name when this entry is a request
+    pub fn request_name(&self) -> Option<&str> {
+        match &self.kind {
+            LogEntryKind::Request { request, .. } => Some(request),
+            _ => None,
+        }
+    }
+
+    /// Get the command name when this entry is a command
+    pub fn command_name(&self) -> Option<&str> {
+        match &self.kind {
+            LogEntryKind::Command { command, .. } => Some(command),
+            _ => None,
+        }
+    }
+
+    /// Get the event type when this entry is an event
+    pub fn event_type(&self) -> Option<&str> {
+        match &self.kind {
+            LogEntryKind::Event { event_type, .. } => Some(event_type),
+            _ => None,
+        }
+    }
+
+    /// Session paths of the component ID (several when `" & "`-joined)
+    pub fn session_paths(&self) -> Vec<SessionPath<'_>> {
+        SessionPath::parse_parallel(&self.component_id)
+    }
+
+    /// Segments of the first session path in the component ID
+    pub fn session_segments(&self) -> Vec<&str> {
+        self.session_paths()
+            .first()
+            .map(|path| path.segments().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Raw log text (including continuation lines) cut to at most `limit` bytes
+    pub fn raw_excerpt(&self, limit: usize) -> String {
+        raw_excerpt(&self.raw_logline, limit)
+    }
+
     /// Check if this log entry is an event with the given type
     pub fn is_event(&self, event_type: &str) -> bool {
         match &self.kind {
@@ -167,6 +352,36 @@ impl LogEntry {
     }
 }
 
+/// `raw` cut to at most `limit` bytes on a character boundary, marking the cut
+pub fn raw_excerpt(raw: &str, limit: usize) -> String {
+    if raw.len() <= limit {
+        return raw.to_string();
+    }
+    let mut end = limit;
+    while !raw.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}… [truncated, {} bytes total]", &raw[..end], raw.len())
+}
+
+/// Look up a value by dotted path; numeric segments index into arrays
+pub fn value_at_path<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = root;
+    for segment in path.split('.') {
+        if segment.is_empty() {
+            return None;
+        }
+
+        current = match current {
+            Value::Object(map) => map.get(segment)?,
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+
+    Some(current)
+}
+
 // Parameter structs for creating log entries
 pub struct LogEntryBase {
     pub component: String,
@@ -195,7 +410,7 @@ pub struct RequestLogParams {
     pub base: LogEntryBase,
     pub request: String,
     pub request_id: Option<String>,
-    pub endpoint: Option<String>,
+    pub endpoint: Option<Endpoint>,
     pub direction: RequestDirection,
     pub payload: Option<Value>,
 }
@@ -217,6 +432,7 @@ pub fn create_event_log(params: EventLogParams) -> LogEntry {
             payload: params.payload,
         },
         source_line_number: params.base.source_line_number,
+        tags: Vec::new(),
     }
 }
 
@@ -235,6 +451,7 @@ pub fn create_command_log(params: CommandLogParams) -> LogEntry {
             settings: params.settings,
         },
         source_line_number: params.base.source_line_number,
+        tags: Vec::new(),
     }
 }
 
@@ -256,6 +473,7 @@ pub fn create_request_log(params: RequestLogParams) -> LogEntry {
             payload: params.payload,
         },
         source_line_number: params.base.source_line_number,
+        tags: Vec::new(),
     }
 }
 
@@ -281,5 +499,6 @@ pub fn create_generic_log(
         module_path: None,
         kind: LogEntryKind::Generic { payload },
         source_line_number,
+        tags: Vec::new(),
     }
 }
@@ -0,0 +1,61 @@
+//! Display-only shortening of payloads whose arrays hold hundreds of elements
+
+use serde_json::Value;
+
+/// Copy of `value` in which every array longer than `max_elements` keeps its first and
+/// last elements (`max_elements` in total) around a marker string naming the omitted
+/// count. `max_elements == 0` keeps everything.
+pub fn elide_long_arrays(value: &Value, max_elements: usize) -> Value {
+    match value {
+        Value::Array(items) if max_elements > 0 && items.len() > max_elements => {
+            let head = max_elements.div_ceil(2);
+            let tail = max_elements - head;
+            let omitted = items.len() - max_elements;
+            let mut shown: Vec<Value> = items[..head]
+                .iter()
+                .map(|item| elide_long_arrays(item, max_elements))
+                .collect();
+            shown.push(Value::String(format!(
+                "… {omitted} of {} elements omitted …",
+                items.len()
+            )));
+            shown.extend(
+                items[items.len() - tail..]
+                    .iter()
+                    .map(|item| elide_long_arrays(item, max_elements)),
+            );
+            Value::Array(shown)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| elide_long_arrays(item, max_elements))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, item)| (key.clone(), elide_long_arrays(item, max_elements)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn keeps_first_and_last_elements_of_long_nested_arrays() {
+        let payload = json!({"resources": (0..100).collect::<Vec<_>>(), "ids": [1, 2]});
+        assert_eq!(
+            elide_long_arrays(&payload, 5),
+            json!({
+                "resources": [0, 1, 2, "… 95 of 100 elements omitted …", 98, 99],
+                "ids": [1, 2],
+            })
+        );
+        assert_eq!(elide_long_arrays(&payload, 0), payload);
+    }
+}
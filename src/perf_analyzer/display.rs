@@ -1,4 +1,7 @@
-use super::entities::{PerfAnalysisResults, TimedOperation};
+use super::entities::{
+    FairnessReport, HangWarning, PerfAnalysisResults, PhaseBreakdown, PhaseTimes, SuspectDuration,
+    TimedOperation,
+};
 use crate::cli::PerfSortOrder;
 use crate::comparator::create_styled_table;
 use comfy_table::Cell;
@@ -28,46 +31,12 @@ pub fn format_perf_results_text(
 
     if orphans_only {
         write_orphans_only(&mut out, results);
+        write_hang_warnings(&mut out, &results.hang_warnings);
         return out;
     }
 
     // 1. Summary section
-    let _ = writeln!(
-        out,
-        "╔════════════════════════════════════════════════════════════╗"
-    );
-    let _ = writeln!(
-        out,
-        "║           PERFORMANCE ANALYSIS SUMMARY                    ║"
-    );
-    let _ = writeln!(
-        out,
-        "╚════════════════════════════════════════════════════════════╝"
-    );
-    let _ = writeln!(out);
-    let _ = writeln!(out, "Total log entries analyzed: {}", results.total_entries);
-    let _ = writeln!(
-        out,
-        "Completed operations:       {}",
-        results.operations.len()
-    );
-    let _ = writeln!(out, "Orphaned operations:        {}", results.orphans.len());
-
-    if let Some((start, end)) = results.time_range {
-        let duration = end.signed_duration_since(start);
-        let _ = writeln!(
-            out,
-            "Time range:                 {} to {}",
-            start.format("%H:%M:%S%.3f"),
-            end.format("%H:%M:%S%.3f")
-        );
-        let _ = writeln!(
-            out,
-            "Total duration:             {:.3}s",
-            duration.num_milliseconds() as f64 / 1000.0
-        );
-    }
-    let _ = writeln!(out);
+    write_summary(&mut out, results);
 
     // 2. Statistics table
     if !results.stats.is_empty() {
@@ -95,6 +64,9 @@ pub fn format_perf_results_text(
             "P50(ms)",
             "P95(ms)",
             "P99(ms)",
+            "Ops/s",
+            "Peak",
+            "Util%",
         ]);
 
         let mut stats = results.stats.clone();
@@ -121,6 +93,93 @@ pub fn format_perf_results_text(
                 Cell::new(stat.p50_duration_ms),
                 Cell::new(stat.p95_duration_ms),
                 Cell::new(stat.p99_duration_ms),
+                Cell::new(format!("{:.2}", stat.throughput.ops_per_sec)),
+                Cell::new(stat.throughput.peak_concurrency),
+                Cell::new(format!("{:.1}", stat.throughput.utilization_pct)),
+            ]);
+        }
+
+        let _ = writeln!(out, "{table}");
+        let _ = writeln!(out);
+    }
+
+    // Session rollup (only when entries carry component_id)
+    if !results.sessions.is_empty() {
+        let _ = writeln!(
+            out,
+            "╔════════════════════════════════════════════════════════════╗"
+        );
+        let _ = writeln!(
+            out,
+            "║           SESSION ROLLUP                                   ║"
+        );
+        let _ = writeln!(
+            out,
+            "╚════════════════════════════════════════════════════════════╝"
+        );
+        let _ = writeln!(out);
+
+        let mut table = create_styled_table(&["Session", "Ops", "Total(ms)", "Avg(ms)", "Orphans"]);
+        for session in results.sessions.iter().take(top_n) {
+            let avg = if session.operation_count > 0.0 {
+                session.total_duration_ms / session.operation_count
+            } else {
+                0.0
+            };
+            table.add_row(vec![
+                Cell::new(truncate_string(&session.session_path, 50)),
+                Cell::new(format_count(session.operation_count)),
+                Cell::new(format!("{:.0}", session.total_duration_ms)),
+                Cell::new(format!("{avg:.2}")),
+                Cell::new(format_count(session.orphan_count)),
+            ]);
+        }
+
+        let _ = writeln!(out, "{table}");
+        if results.sessions.len() > top_n {
+            let _ = writeln!(
+                out,
+                "... and {} more sessions",
+                results.sessions.len() - top_n
+            );
+        }
+        let _ = writeln!(out);
+    }
+
+    // Tag rollup (only when the profile declares [tags])
+    if !results.tags.is_empty() {
+        let _ = writeln!(
+            out,
+            "╔════════════════════════════════════════════════════════════╗"
+        );
+        let _ = writeln!(
+            out,
+            "║           TAG ROLLUP                                       ║"
+        );
+        let _ = writeln!(
+            out,
+            "╚════════════════════════════════════════════════════════════╝"
+        );
+        let _ = writeln!(out);
+
+        let mut table = create_styled_table(&[
+            "Tag",
+            "Ops",
+            "Total(ms)",
+            "Avg(ms)",
+            "P95(ms)",
+            "Max(ms)",
+            "Errors",
+        ]);
+        for tag in &results.tags {
+            table.add_row(vec![
+                Cell::new(&tag.tag),
+                Cell::new(tag.count),
+                Cell::new(tag.total_duration_ms),
+                Cell::new(format!("{:.2}", tag.avg_duration_ms)),
+                Cell::new(tag.p95_duration_ms),
+                Cell::new(tag.max_duration_ms),
+                Cell::new(tag.errors),
             ]);
         }
 
@@ -128,6 +187,53 @@ pub fn format_perf_results_text(
         let _ = writeln!(out);
     }
 
+    // Host rollup (only when request endpoints name a host)
+    if !results.hosts.is_empty() {
+        let _ = writeln!(
+            out,
+            "╔════════════════════════════════════════════════════════════╗"
+        );
+        let _ = writeln!(
+            out,
+            "║           HOST ROLLUP                                      ║"
+        );
+        let _ = writeln!(
+            out,
+            "╚════════════════════════════════════════════════════════════╝"
+        );
+        let _ = writeln!(out);
+
+        let mut table = create_styled_table(&[
+            "Host",
+            "Requests",
+            "Avg(ms)",
+            "P95(ms)",
+            "Max(ms)",
+            "Status>=400",
+        ]);
+        for host in &results.hosts {
+            table.add_row(vec![
+                Cell::new(&host.host),
+                Cell::new(host.count),
+                Cell::new(format!("{:.2}", host.avg_duration_ms)),
+                Cell::new(host.p95_duration_ms),
+                Cell::new(host.max_duration_ms),
+                Cell::new(host.error_responses),
+            ]);
+        }
+
+        let _ = writeln!(out, "{table}");
+        let _ = writeln!(out);
+    }
+
+    if let Some(fairness) = &results.fairness {
+        write_fairness(&mut out, fairness, top_n);
+    }
+
+    if let Some(phases) = &results.phases {
+        write_phases(&mut out, phases, top_n);
+    }
+
     // 3. Top N slowest operations
     if !results.operations.is_empty() {
         let _ = writeln!(
@@ -240,9 +346,68 @@ pub fn format_perf_results_text(
         let _ = writeln!(out);
     }
 
+    // 6. Stale pending requests (--hang-threshold)
+    write_hang_warnings(&mut out, &results.hang_warnings);
+
     out
 }
 
+/// Display requests that stayed pending past the hang threshold
+fn write_hang_warnings(out: &mut String, warnings: &[HangWarning]) {
+    if warnings.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(
+        out,
+        "╔════════════════════════════════════════════════════════════╗"
+    );
+    let _ = writeln!(
+        out,
+        "║           STALE PENDING REQUESTS                           ║"
+    );
+    let _ = writeln!(
+        out,
+        "╚════════════════════════════════════════════════════════════╝"
+    );
+    let _ = writeln!(out);
+
+    for (i, warning) in warnings.iter().enumerate() {
+        let resolution = match warning.resolved_after_ms {
+            Some(ms) => format!("resolved after {ms}ms"),
+            None => "never resolved".to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "{}. [{}] {} pending {}ms at {} ({})",
+            i + 1,
+            warning.op_type,
+            warning.name,
+            warning.pending_ms,
+            warning.detected_at.format("%H:%M:%S%.3f"),
+            resolution
+        );
+        let _ = writeln!(
+            out,
+            "   Sent: {}",
+            warning.start_time.format("%H:%M:%S%.3f")
+        );
+        if let Some(ref session) = warning.component_id {
+            let _ = writeln!(out, "   Session: {}", session);
+        }
+        if let Some(ref corr_id) = warning.correlation_id {
+            let _ = writeln!(out, "   Correlation ID: {}", truncate_string(corr_id, 50));
+        }
+        if !warning.recent_entries.is_empty() {
+            let _ = writeln!(out, "   Recent session entries:");
+            for line in &warning.recent_entries {
+                let _ = writeln!(out, "     {}", truncate_string(line, 120));
+            }
+        }
+        let _ = writeln!(out);
+    }
+}
+
 /// Display only orphaned operations
 fn write_orphans_only(out: &mut String, results: &PerfAnalysisResults) {
     let _ = writeln!(
@@ -316,7 +481,238 @@ fn write_timed_operation(out: &mut String, index: usize, op: &TimedOperation) {
         let _ = writeln!(out, "   Correlation ID: {}", truncate_string(corr_id, 50));
     }
 
+    if !op.errors_during.is_empty() {
+        let _ = writeln!(out, "   Errors during operation: {}", op.error_count());
+        for cluster in op.errors_during.iter().take(3) {
+            let _ = writeln!(
+                out,
+                "     ×{} [{}] {} (first at line {})",
+                cluster.count,
+                cluster.severity,
+                truncate_string(&cluster.pattern, 80),
+                cluster.first_line
+            );
+        }
+        if op.errors_during.len() > 3 {
+            let _ = writeln!(
+                out,
+                "     ... and {} more patterns",
+                op.errors_during.len() - 3
+            );
+        }
+    }
+
+    let _ = writeln!(out);
+}
+
+fn write_fairness(out: &mut String, fairness: &FairnessReport, top_n: usize) {
+    let _ = writeln!(
+        out,
+        "╔════════════════════════════════════════════════════════════╗"
+    );
+    let _ = writeln!(
+        out,
+        "║           SESSION FAIRNESS                                 ║"
+    );
+    let _ = writeln!(
+        out,
+        "╚════════════════════════════════════════════════════════════╝"
+    );
+    let _ = writeln!(out);
+
+    if fairness.sessions.is_empty() {
+        let _ = writeln!(
+            out,
+            "No operations with a session path (component_id) found."
+        );
+        let _ = writeln!(out);
+        return;
+    }
+
+    let format_index = |index: Option<f64>| index.map_or("-".to_string(), |i| format!("{i:.2}"));
+    let starved = fairness.sessions.iter().filter(|s| s.starved).count();
+    let _ = writeln!(
+        out,
+        "Fairness index ({}ms buckets): mean {}, min {}",
+        fairness.bucket_ms,
+        format_index(fairness.mean_fairness_index),
+        format_index(fairness.min_fairness_index)
+    );
+    let _ = writeln!(
+        out,
+        "Starved sessions:           {} of {}",
+        starved,
+        fairness.sessions.len()
+    );
+    let _ = writeln!(out);
+
+    let mut table = create_styled_table(&[
+        "Session",
+        "Concurrency",
+        "First op wait(ms)",
+        "Busy(ms)",
+        "Waiting(ms)",
+        "Busy %",
+        "Starved",
+    ]);
+    for session in fairness.sessions.iter().take(top_n) {
+        table.add_row(vec![
+            Cell::new(truncate_string(&session.session_path, 50)),
+            Cell::new(
+                session
+                    .concurrency
+                    .as_ref()
+                    .map_or("-".to_string(), |value| value.to_string()),
+            ),
+            Cell::new(session.wait_to_first_op_ms),
+            Cell::new(session.busy_ms),
+            Cell::new(session.waiting_ms),
+            Cell::new(format!("{:.1}", session.busy_pct)),
+            Cell::new(if session.starved { "yes" } else { "" }),
+        ]);
+    }
+    let _ = writeln!(out, "{table}");
+    if fairness.sessions.len() > top_n {
+        let _ = writeln!(
+            out,
+            "... and {} more sessions",
+            fairness.sessions.len() - top_n
+        );
+    }
+
+    let mut worst: Vec<_> = fairness
+        .buckets
+        .iter()
+        .filter(|bucket| bucket.fairness_index.is_some() && bucket.active_sessions > 1)
+        .collect();
+    worst.sort_by(|a, b| a.fairness_index.partial_cmp(&b.fairness_index).unwrap());
+    if !worst.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Least fair buckets:");
+        for bucket in worst.iter().take(5) {
+            let _ = writeln!(
+                out,
+                "  {}  index {}  ({} of {} active sessions running)",
+                bucket.start.format("%H:%M:%S%.3f"),
+                format_index(bucket.fairness_index),
+                bucket.running_sessions,
+                bucket.active_sessions
+            );
+        }
+    }
+    let _ = writeln!(out);
+}
+
+/// Fill characters of the stacked phase bars, one per phase (cycled), then `other`
+const PHASE_FILLS: [char; 4] = ['█', '▓', '▒', '░'];
+const OTHER_FILL: char = '·';
+const PHASE_BAR_WIDTH: usize = 30;
+
+/// Bar of `PHASE_BAR_WIDTH` characters with one segment per phase plus `other`, each
+/// proportional to its share of the summed times
+fn phase_bar(times: &PhaseTimes) -> String {
+    let parts: Vec<(char, i64)> = times
+        .phases
+        .iter()
+        .enumerate()
+        .map(|(index, phase)| (PHASE_FILLS[index % PHASE_FILLS.len()], phase.duration_ms))
+        .chain([(OTHER_FILL, times.other_ms)])
+        .collect();
+    let sum: i64 = parts.iter().map(|(_, ms)| ms.max(&0)).sum();
+    if sum == 0 {
+        return String::new();
+    }
+    let mut bar = String::new();
+    let mut cumulative = 0;
+    let mut drawn = 0;
+    for (fill, ms) in parts {
+        cumulative += ms.max(0);
+        let end = (cumulative as f64 / sum as f64 * PHASE_BAR_WIDTH as f64).round() as usize;
+        bar.extend(std::iter::repeat_n(fill, end.saturating_sub(drawn)));
+        drawn = drawn.max(end);
+    }
+    bar
+}
+
+fn write_phases(out: &mut String, breakdown: &PhaseBreakdown, top_n: usize) {
+    let _ = writeln!(
+        out,
+        "╔════════════════════════════════════════════════════════════╗"
+    );
+    let _ = writeln!(
+        out,
+        "║           CHECK LATENCY BREAKDOWN                          ║"
+    );
+    let _ = writeln!(
+        out,
+        "╚════════════════════════════════════════════════════════════╝"
+    );
+    let _ = writeln!(out);
+
+    if breakdown.checks.is_empty() {
+        let _ = writeln!(
+            out,
+            "No session ran an operation of the profile's [[perf.phases]]."
+        );
+        let _ = writeln!(out);
+        return;
+    }
+
+    let legend: Vec<String> = breakdown
+        .phases
+        .iter()
+        .enumerate()
+        .map(|(index, phase)| format!("{} {phase}", PHASE_FILLS[index % PHASE_FILLS.len()]))
+        .chain([format!("{OTHER_FILL} other")])
+        .collect();
+    let _ = writeln!(out, "{}", legend.join("  "));
     let _ = writeln!(out);
+
+    let phase_headers: Vec<String> = breakdown
+        .phases
+        .iter()
+        .map(|phase| format!("{phase}(ms)"))
+        .collect();
+    let mut headers = vec!["Check", "Total(ms)"];
+    headers.extend(phase_headers.iter().map(String::as_str));
+    headers.extend(["Other(ms)", "Breakdown"]);
+    let mut table = create_styled_table(&headers);
+    let row = |label: &str, times: &PhaseTimes| {
+        let mut row = vec![Cell::new(label), Cell::new(times.total_ms)];
+        row.extend(
+            times
+                .phases
+                .iter()
+                .map(|phase| Cell::new(phase.duration_ms)),
+        );
+        row.extend([Cell::new(times.other_ms), Cell::new(phase_bar(times))]);
+        row
+    };
+    table.add_row(row(
+        &format!("median of {}", breakdown.checks.len()),
+        &breakdown.median,
+    ));
+    for check in breakdown.checks.iter().take(top_n) {
+        table.add_row(row(&truncate_string(&check.session_path, 50), &check.times));
+    }
+    let _ = writeln!(out, "{table}");
+    if breakdown.checks.len() > top_n {
+        let _ = writeln!(
+            out,
+            "... and {} more checks",
+            breakdown.checks.len() - top_n
+        );
+    }
+    let _ = writeln!(out);
+}
+
+/// Format an attributed count, keeping fractions from proportional attribution visible
+fn format_count(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
 }
 
 /// Truncate a string to a maximum length with ellipsis
@@ -328,7 +724,126 @@ pub fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
+fn write_suspect_durations(out: &mut String, results: &PerfAnalysisResults) {
+    let counts = &results.suspect_durations;
+    if counts.negative() > 0 {
+        let mut causes = Vec::new();
+        for (count, reason) in [
+            (counts.clock_skew, SuspectDuration::ClockSkew),
+            (counts.reordered, SuspectDuration::Reordered),
+        ] {
+            if count > 0 {
+                causes.push(format!("{count} {}", reason.describe()));
+            }
+        }
+        let handling = if counts.included {
+            "included in statistics"
+        } else {
+            "excluded from statistics; --include-suspect-durations counts them"
+        };
+        let _ = writeln!(
+            out,
+            "Negative durations:         {} ({}), {handling}",
+            counts.negative(),
+            causes.join(", ")
+        );
+    }
+    if counts.zero_duration > 0 {
+        let _ = writeln!(out, "Zero-duration operations:   {}", counts.zero_duration);
+    }
+}
+
+/// Only the summary block of the text report, for `--summary-only`
+pub fn format_perf_summary_text(results: &PerfAnalysisResults) -> String {
+    let mut out = String::new();
+    write_summary(&mut out, results);
+    out
+}
+
+fn write_summary(out: &mut String, results: &PerfAnalysisResults) {
+    let _ = writeln!(
+        out,
+        "╔════════════════════════════════════════════════════════════╗"
+    );
+    let _ = writeln!(
+        out,
+        "║           PERFORMANCE ANALYSIS SUMMARY                    ║"
+    );
+    let _ = writeln!(
+        out,
+        "╚════════════════════════════════════════════════════════════╝"
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Total log entries analyzed: {}", results.total_entries);
+    let _ = writeln!(
+        out,
+        "Completed operations:       {}",
+        results.operations.len()
+    );
+    let _ = writeln!(out, "Orphaned operations:        {}", results.orphans.len());
+    write_suspect_durations(out, results);
+
+    if let Some((start, end)) = results.time_range {
+        let duration = end.signed_duration_since(start);
+        let _ = writeln!(
+            out,
+            "Time range:                 {} to {}",
+            start.format("%H:%M:%S%.3f"),
+            end.format("%H:%M:%S%.3f")
+        );
+        let _ = writeln!(
+            out,
+            "Total duration:             {:.3}s",
+            duration.num_milliseconds() as f64 / 1000.0
+        );
+    }
+    if !results.operations.is_empty() {
+        let throughput = &results.throughput;
+        let _ = writeln!(
+            out,
+            "Throughput:                 {:.2} ops/s ({:.2} ops/s per slot, peak concurrency {})",
+            throughput.ops_per_sec,
+            throughput.concurrency_adjusted_ops_per_sec,
+            throughput.peak_concurrency
+        );
+        let _ = writeln!(
+            out,
+            "Utilization:                {:.1}% busy (avg concurrency {:.2})",
+            throughput.utilization_pct, throughput.avg_concurrency
+        );
+        let with_errors = results
+            .operations
+            .iter()
+            .filter(|op| !op.errors_during.is_empty())
+            .count();
+        if with_errors > 0 {
+            let _ = writeln!(
+                out,
+                "Operations with errors:     {} (errors logged in the same session while in flight)",
+                with_errors
+            );
+        }
+    }
+    let _ = writeln!(out);
+}
+
 /// Format performance analysis results as JSON
 pub fn format_perf_results_json(results: &PerfAnalysisResults) -> String {
     serde_json::to_string_pretty(results).unwrap_or_else(|_| "{}".to_string())
 }
+
+/// Totals, throughput, and time range without per-operation details, for `--summary-only`
+pub fn format_perf_summary_json(results: &PerfAnalysisResults) -> String {
+    let summary = serde_json::json!({
+        "total_entries": results.total_entries,
+        "operations_count": results.operations.len(),
+        "orphans_count": results.orphans.len(),
+        "operation_types_count": results.stats.len(),
+        "sessions_count": results.sessions.len(),
+        "hang_warnings_count": results.hang_warnings.len(),
+        "throughput": results.throughput,
+        "suspect_durations": results.suspect_durations,
+        "time_range": results.time_range,
+    });
+    serde_json::to_string_pretty(&summary).unwrap_or_else(|_| "{}".to_string())
+}
@@ -1,5 +1,8 @@
+use crate::config::SessionAttribution;
+use crate::parser::Endpoint;
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// Represents a completed timed operation (paired start/end)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +27,90 @@ pub struct TimedOperation {
     pub endpoint: Option<String>,
     /// HTTP status or result status
     pub status: Option<String>,
+    /// Session/component path (`component_id`) of the starting log entry when present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_id: Option<String>,
+    /// Error clusters logged in the same session while the operation was in flight
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors_during: Vec<OperationErrors>,
+    /// `[tags]` of the starting log entry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Why the duration cannot be trusted, when it is negative
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspect: Option<SuspectDuration>,
+    /// Source line numbers of the starting and ending entries
+    #[serde(skip)]
+    pub lines: (usize, usize),
+}
+
+/// Cause of a negative operation duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuspectDuration {
+    /// The end entry carries an earlier timestamp than its start although it was logged
+    /// after it, or by another component (clocks that jumped or disagree)
+    ClockSkew,
+    /// The end entry was written before its start in the same component's log
+    Reordered,
+}
+
+impl SuspectDuration {
+    /// Classifies the pairing of a start and an end, each given as (component, line);
+    /// `None` when the duration is not negative
+    pub fn classify(duration_ms: i64, start: (&str, usize), end: (&str, usize)) -> Option<Self> {
+        if duration_ms >= 0 {
+            None
+        } else if start.0 == end.0 && end.1 < start.1 {
+            Some(SuspectDuration::Reordered)
+        } else {
+            Some(SuspectDuration::ClockSkew)
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            SuspectDuration::ClockSkew => "clock skew",
+            SuspectDuration::Reordered => "reordered entries",
+        }
+    }
+}
+
+/// Operations whose paired timestamps look wrong
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuspectDurationCounts {
+    /// Negative durations caused by clock skew
+    pub clock_skew: usize,
+    /// Negative durations caused by reordered entries
+    pub reordered: usize,
+    /// Operations that ended in the millisecond they started (kept in statistics)
+    pub zero_duration: usize,
+    /// Whether negative durations count toward the statistics (`--include-suspect-durations`)
+    pub included: bool,
+}
+
+impl SuspectDurationCounts {
+    pub fn negative(&self) -> usize {
+        self.clock_skew + self.reordered
+    }
+}
+
+impl TimedOperation {
+    /// Number of error entries logged while the operation was in flight
+    pub fn error_count(&self) -> usize {
+        self.errors_during.iter().map(|cluster| cluster.count).sum()
+    }
+}
+
+/// Error entries of one normalized pattern that fired during an operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationErrors {
+    pub severity: String,
+    pub pattern: String,
+    pub count: usize,
+    pub first_timestamp: DateTime<Local>,
+    /// Source line of the first matching entry
+    pub first_line: usize,
 }
 
 /// Represents an operation that was started but never completed
@@ -66,6 +153,253 @@ pub struct OperationStats {
     pub p95_duration_ms: i64,
     /// 99th percentile duration in milliseconds
     pub p99_duration_ms: i64,
+    /// Rate and utilization of this operation over the analyzed time range
+    pub throughput: ThroughputStats,
+}
+
+/// Rate, concurrency, and utilization metrics over the analyzed wall-clock window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThroughputStats {
+    /// Wall-clock window used as the rate denominator, in milliseconds
+    pub window_ms: i64,
+    /// Completed operations per second over the window
+    pub ops_per_sec: f64,
+    /// Maximum number of operations in flight at the same time
+    pub peak_concurrency: usize,
+    /// Average number of operations in flight (total operation time / window)
+    pub avg_concurrency: f64,
+    /// Operations per second per concurrent slot (ops_per_sec / peak_concurrency)
+    pub concurrency_adjusted_ops_per_sec: f64,
+    /// Wall-clock time with at least one operation in flight, in milliseconds
+    pub busy_ms: i64,
+    /// Share of the window with at least one operation in flight
+    pub utilization_pct: f64,
+}
+
+impl ThroughputStats {
+    /// Compute throughput metrics for a set of operations over a wall-clock window
+    pub fn from_operations(ops: &[&TimedOperation], window_ms: i64) -> Self {
+        if ops.is_empty() {
+            return Self {
+                window_ms,
+                ..Self::default()
+            };
+        }
+
+        let mut intervals: Vec<(i64, i64)> = ops
+            .iter()
+            .map(|op| {
+                let start = op.start_time.timestamp_millis();
+                (start, start + op.duration_ms.max(0))
+            })
+            .collect();
+        intervals.sort();
+
+        let span_ms = intervals.last().map_or(0, |(_, end)| *end)
+            - intervals.first().map_or(0, |(start, _)| *start);
+        let window_ms = if window_ms > 0 { window_ms } else { span_ms };
+
+        // Sweep line: process ends before starts at the same instant so back-to-back
+        // operations are not counted as overlapping.
+        let mut edges: Vec<(i64, i32)> = intervals
+            .iter()
+            .flat_map(|&(start, end)| [(start, 1), (end, -1)])
+            .collect();
+        edges.sort();
+        let mut in_flight = 0i32;
+        let mut peak_concurrency = 0usize;
+        for (_, delta) in edges {
+            in_flight += delta;
+            peak_concurrency = peak_concurrency.max(in_flight.max(0) as usize);
+        }
+
+        let mut busy_ms = 0i64;
+        let mut current: Option<(i64, i64)> = None;
+        for (start, end) in intervals {
+            current = match current {
+                Some((cur_start, cur_end)) if start <= cur_end => {
+                    Some((cur_start, cur_end.max(end)))
+                }
+                Some((cur_start, cur_end)) => {
+                    busy_ms += cur_end - cur_start;
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+        if let Some((cur_start, cur_end)) = current {
+            busy_ms += cur_end - cur_start;
+        }
+
+        if window_ms <= 0 {
+            return Self {
+                window_ms: 0,
+                peak_concurrency,
+                busy_ms,
+                ..Self::default()
+            };
+        }
+
+        let window_secs = window_ms as f64 / 1000.0;
+        let total_op_ms: i64 = ops.iter().map(|op| op.duration_ms.max(0)).sum();
+        let ops_per_sec = ops.len() as f64 / window_secs;
+
+        Self {
+            window_ms,
+            ops_per_sec,
+            peak_concurrency,
+            avg_concurrency: total_op_ms as f64 / window_ms as f64,
+            concurrency_adjusted_ops_per_sec: ops_per_sec / peak_concurrency.max(1) as f64,
+            busy_ms,
+            utilization_pct: (busy_ms as f64 / window_ms as f64 * 100.0).min(100.0),
+        }
+    }
+}
+
+/// A request that stayed pending longer than the hang threshold, reported at the
+/// first log entry where the stall became observable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HangWarning {
+    /// Type of operation: "Request"
+    pub op_type: String,
+    /// Name of the operation
+    pub name: String,
+    /// Correlation ID used to match start/end
+    pub correlation_id: Option<String>,
+    /// Session/component path (`component_id`) of the starting log entry when present
+    pub component_id: Option<String>,
+    /// Start time of the operation
+    pub start_time: DateTime<Local>,
+    /// Timestamp of the log entry at which the operation was first seen as stale
+    pub detected_at: DateTime<Local>,
+    /// How long the operation had been pending when detected, in milliseconds
+    pub pending_ms: i64,
+    /// Total duration if a response eventually arrived
+    pub resolved_after_ms: Option<i64>,
+    /// Last raw log lines from the same session before detection
+    pub recent_entries: Vec<String>,
+}
+
+/// Operations attributed to a single session path. Counts are fractional under
+/// proportional attribution of `" & "`-joined parallel sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPerfStats {
+    /// Session/component path
+    pub session_path: String,
+    /// Completed operations attributed to this session
+    pub operation_count: f64,
+    /// Total attributed duration of completed operations in milliseconds
+    pub total_duration_ms: f64,
+    /// Orphaned operations attributed to this session
+    pub orphan_count: f64,
+}
+
+/// Completed operations whose starting entry carries one tag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagPerfStats {
+    pub tag: String,
+    pub count: usize,
+    pub total_duration_ms: i64,
+    pub avg_duration_ms: f64,
+    pub p95_duration_ms: i64,
+    pub max_duration_ms: i64,
+    /// Error entries logged while the tagged operations were in flight
+    pub errors: usize,
+}
+
+/// Completed requests sent to one host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostPerfStats {
+    pub host: String,
+    pub count: usize,
+    pub avg_duration_ms: f64,
+    pub p95_duration_ms: i64,
+    pub max_duration_ms: i64,
+    /// Responses with a status code of 400 or above
+    pub error_responses: usize,
+}
+
+/// How evenly sessions shared execution time while they were waiting to run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FairnessReport {
+    /// Width of each fairness bucket in milliseconds
+    pub bucket_ms: i64,
+    /// Sessions ordered by time spent waiting while other sessions ran
+    pub sessions: Vec<SessionFairness>,
+    pub buckets: Vec<FairnessBucket>,
+    /// Mean Jain index over buckets with running sessions
+    pub mean_fairness_index: Option<f64>,
+    pub min_fairness_index: Option<f64>,
+}
+
+/// Execution and waiting time of one session path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionFairness {
+    pub session_path: String,
+    /// Concurrency setting captured from the session's (or an ancestor's) create command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<serde_json::Value>,
+    /// First log entry of the session
+    pub registered_at: DateTime<Local>,
+    /// Time from registration to the first operation start
+    pub wait_to_first_op_ms: i64,
+    pub operation_count: usize,
+    /// Wall-clock time with at least one of the session's operations in flight
+    pub busy_ms: i64,
+    /// Time inside the session's active span where it had nothing in flight but other sessions did
+    pub waiting_ms: i64,
+    /// `busy_ms` over the span from registration to the last operation end
+    pub busy_pct: f64,
+    /// Waited longer than it ran, for at least one bucket
+    pub starved: bool,
+}
+
+/// Jain fairness index of per-session busy time within one time bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FairnessBucket {
+    pub start: DateTime<Local>,
+    /// Sessions registered and not yet finished during the bucket
+    pub active_sessions: usize,
+    /// Active sessions with an operation in flight during the bucket
+    pub running_sessions: usize,
+    /// `(Σx)² / (n·Σx²)` over active sessions' busy time; 1.0 = perfectly even
+    pub fairness_index: Option<f64>,
+}
+
+/// Check sessions' time split into the profile's `[[perf.phases]]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseBreakdown {
+    /// Phase names in profile order
+    pub phases: Vec<String>,
+    /// Checks ordered by total time, slowest first
+    pub checks: Vec<CheckPhases>,
+    /// Medians across checks, in the same layout as one check
+    pub median: PhaseTimes,
+}
+
+/// Phase times of one check session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckPhases {
+    pub session_path: String,
+    #[serde(flatten)]
+    pub times: PhaseTimes,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PhaseTimes {
+    /// First operation start to last operation end
+    pub total_ms: i64,
+    /// One per phase, in profile order
+    pub phases: Vec<PhaseDuration>,
+    /// Time of `total_ms` covered by no phase
+    pub other_ms: i64,
+}
+
+/// Wall-clock time with at least one operation of the phase in flight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseDuration {
+    pub phase: String,
+    pub duration_ms: i64,
 }
 
 /// Results of performance analysis
@@ -73,10 +407,36 @@ pub struct OperationStats {
 pub struct PerfAnalysisResults {
     /// All completed timed operations
     pub operations: Vec<TimedOperation>,
-    /// Operations that never completed
+    /// Operations that never completed, and responses whose request was never logged
     pub orphans: Vec<OrphanOperation>,
     /// Aggregated statistics per operation type
     pub stats: Vec<OperationStats>,
+    /// Overall throughput across all completed operations
+    pub throughput: ThroughputStats,
+    /// Per-session rollup, sorted by total attributed duration (slowest first)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<SessionPerfStats>,
+    /// Negative-duration operations left out of `operations` and all statistics
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suspect_operations: Vec<TimedOperation>,
+    /// Negative and zero durations found while pairing
+    #[serde(default)]
+    pub suspect_durations: SuspectDurationCounts,
+    /// Per-tag rollup of completed operations, when the profile declares `[tags]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TagPerfStats>,
+    /// Per-host rollup of completed requests whose endpoint names a host, busiest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<HostPerfStats>,
+    /// Requests that stayed pending past `--hang-threshold`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hang_warnings: Vec<HangWarning>,
+    /// Per-session starvation and per-bucket fairness, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fairness: Option<FairnessReport>,
+    /// Per-check phase decomposition, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phases: Option<PhaseBreakdown>,
     /// Time range of the analyzed logs
     pub time_range: Option<(DateTime<Local>, DateTime<Local>)>,
     /// Total number of log entries analyzed
@@ -90,6 +450,15 @@ impl PerfAnalysisResults {
             operations: Vec::new(),
             orphans: Vec::new(),
             stats: Vec::new(),
+            throughput: ThroughputStats::default(),
+            sessions: Vec::new(),
+            suspect_operations: Vec::new(),
+            suspect_durations: SuspectDurationCounts::default(),
+            tags: Vec::new(),
+            hosts: Vec::new(),
+            hang_warnings: Vec::new(),
+            fairness: None,
+            phases: None,
             time_range: None,
             total_entries: 0,
         }
@@ -110,9 +479,18 @@ impl PerfAnalysisResults {
         ops.into_iter().take(n).collect()
     }
 
+    /// Wall-clock window of the analyzed logs in milliseconds
+    pub fn window_ms(&self) -> i64 {
+        self.time_range
+            .map(|(start, end)| end.signed_duration_since(start).num_milliseconds())
+            .unwrap_or(0)
+    }
+
     /// Calculate statistics for all operations
     pub fn calculate_stats(&mut self) {
-        use std::collections::HashMap;
+        let window_ms = self.window_ms();
+        let all_ops: Vec<&TimedOperation> = self.operations.iter().collect();
+        self.throughput = ThroughputStats::from_operations(&all_ops, window_ms);
 
         // Group operations by (op_type, name)
         let mut grouped: HashMap<(String, String), Vec<&TimedOperation>> = HashMap::new();
@@ -138,6 +516,7 @@ impl PerfAnalysisResults {
                 let p50 = durations[count * 50 / 100];
                 let p95 = durations[count * 95 / 100];
                 let p99 = durations[count * 99 / 100];
+                let throughput = ThroughputStats::from_operations(&ops, window_ms);
 
                 OperationStats {
                     op_type,
@@ -149,6 +528,7 @@ impl PerfAnalysisResults {
                     p50_duration_ms: p50,
                     p95_duration_ms: p95,
                     p99_duration_ms: p99,
+                    throughput,
                 }
             })
             .collect();
@@ -156,6 +536,167 @@ impl PerfAnalysisResults {
         // Sort stats by average duration descending
         self.stats
             .sort_by(|a, b| b.avg_duration_ms.partial_cmp(&a.avg_duration_ms).unwrap());
+
+        self.calculate_host_stats();
+    }
+
+    /// Roll completed requests up by the host of their endpoint
+    fn calculate_host_stats(&mut self) {
+        let mut by_host: BTreeMap<String, Vec<&TimedOperation>> = BTreeMap::new();
+        for op in &self.operations {
+            if let Some(host) = op
+                .endpoint
+                .as_deref()
+                .and_then(|endpoint| Endpoint::parse(endpoint).host)
+            {
+                by_host.entry(host).or_default().push(op);
+            }
+        }
+
+        self.hosts = by_host
+            .into_iter()
+            .map(|(host, ops)| {
+                let mut durations: Vec<i64> = ops.iter().map(|op| op.duration_ms).collect();
+                durations.sort();
+                let count = durations.len();
+                let total: i64 = durations.iter().sum();
+                HostPerfStats {
+                    host,
+                    count,
+                    avg_duration_ms: total as f64 / count as f64,
+                    p95_duration_ms: durations[count * 95 / 100],
+                    max_duration_ms: durations[count - 1],
+                    error_responses: ops
+                        .iter()
+                        .filter(|op| {
+                            op.status
+                                .as_deref()
+                                .and_then(|status| status.parse::<u16>().ok())
+                                .is_some_and(|status| status >= 400)
+                        })
+                        .count(),
+                }
+            })
+            .collect();
+        self.hosts
+            .sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.host.cmp(&b.host)));
+    }
+}
+
+impl PerfAnalysisResults {
+    /// Roll completed and orphaned operations up by session path, splitting
+    /// `" & "`-joined parallel sessions according to `attribution`
+    pub fn calculate_session_stats(&mut self, attribution: SessionAttribution) {
+        let mut sessions: HashMap<String, SessionPerfStats> = HashMap::new();
+        let mut attribute = |component_id: &str, duration_ms: Option<i64>| {
+            for (path, weight) in attribution.attribute(component_id) {
+                let stats = sessions
+                    .entry(path.to_string())
+                    .or_insert_with(|| SessionPerfStats {
+                        session_path: path.to_string(),
+                        operation_count: 0.0,
+                        total_duration_ms: 0.0,
+                        orphan_count: 0.0,
+                    });
+                match duration_ms {
+                    Some(ms) => {
+                        stats.operation_count += weight;
+                        stats.total_duration_ms += ms as f64 * weight;
+                    }
+                    None => stats.orphan_count += weight,
+                }
+            }
+        };
+
+        for op in &self.operations {
+            if let Some(component_id) = &op.component_id {
+                attribute(component_id, Some(op.duration_ms));
+            }
+        }
+        for orphan in &self.orphans {
+            if let Some(component_id) = &orphan.component_id {
+                attribute(component_id, None);
+            }
+        }
+
+        self.sessions = sessions.into_values().collect();
+        self.sessions.sort_by(|a, b| {
+            b.total_duration_ms
+                .total_cmp(&a.total_duration_ms)
+                .then_with(|| a.session_path.cmp(&b.session_path))
+        });
+    }
+}
+
+impl PerfAnalysisResults {
+    /// Counts zero and negative durations and moves the negative ones to
+    /// `suspect_operations`, so one skewed clock cannot poison averages and percentiles
+    pub fn set_aside_suspect_durations(&mut self) {
+        let (suspect, operations): (Vec<TimedOperation>, Vec<TimedOperation>) = self
+            .operations
+            .drain(..)
+            .partition(|op| op.suspect.is_some());
+        self.operations = operations;
+        self.suspect_operations = suspect;
+        self.suspect_durations = SuspectDurationCounts {
+            clock_skew: self.count_suspect(SuspectDuration::ClockSkew),
+            reordered: self.count_suspect(SuspectDuration::Reordered),
+            zero_duration: self
+                .operations
+                .iter()
+                .filter(|op| op.duration_ms == 0)
+                .count(),
+            included: false,
+        };
+    }
+
+    fn count_suspect(&self, reason: SuspectDuration) -> usize {
+        self.suspect_operations
+            .iter()
+            .filter(|op| op.suspect == Some(reason))
+            .count()
+    }
+
+    /// Puts set-aside negative durations back into `operations` and recomputes the
+    /// statistics and rollups
+    pub fn include_suspect_durations(&mut self, attribution: SessionAttribution) {
+        self.operations.append(&mut self.suspect_operations);
+        self.suspect_durations.included = true;
+        self.calculate_stats();
+        self.calculate_session_stats(attribution);
+        self.calculate_tag_stats();
+    }
+}
+
+impl PerfAnalysisResults {
+    /// Roll completed operations up by the tags of their starting entry. Call again
+    /// after linking operation errors to count them.
+    pub fn calculate_tag_stats(&mut self) {
+        let mut by_tag: BTreeMap<&str, Vec<&TimedOperation>> = BTreeMap::new();
+        for op in &self.operations {
+            for tag in &op.tags {
+                by_tag.entry(tag).or_default().push(op);
+            }
+        }
+
+        self.tags = by_tag
+            .into_iter()
+            .map(|(tag, ops)| {
+                let mut durations: Vec<i64> = ops.iter().map(|op| op.duration_ms).collect();
+                durations.sort();
+                let count = durations.len();
+                let total: i64 = durations.iter().sum();
+                TagPerfStats {
+                    tag: tag.to_string(),
+                    count,
+                    total_duration_ms: total,
+                    avg_duration_ms: total as f64 / count as f64,
+                    p95_duration_ms: durations[count * 95 / 100],
+                    max_duration_ms: durations[count - 1],
+                    errors: ops.iter().map(|op| op.error_count()).sum(),
+                }
+            })
+            .collect();
     }
 }
 
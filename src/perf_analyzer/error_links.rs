@@ -0,0 +1,88 @@
+use super::entities::{OperationErrors, TimedOperation};
+use crate::comparator::LogFilter;
+use crate::config::split_parallel_session_paths;
+use crate::errors::{build_error_level_filter, normalize_message_pattern, normalized_severity};
+use crate::parser::LogEntry;
+use std::collections::{BTreeMap, HashMap};
+
+/// Session paths of a `component_id`; entries without one share the empty path
+pub(crate) fn session_keys(component_id: &str) -> Vec<&str> {
+    let paths = split_parallel_session_paths(component_id);
+    if paths.is_empty() { vec![""] } else { paths }
+}
+
+/// For each operation, indices into `errors` of the entries logged in the same session
+/// within its start..=end span
+fn entries_during_operations(
+    operations: &[TimedOperation],
+    errors: &[&LogEntry],
+) -> Vec<Vec<usize>> {
+    let mut by_session: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, entry) in errors.iter().enumerate() {
+        for key in session_keys(&entry.component_id) {
+            by_session.entry(key).or_default().push(idx);
+        }
+    }
+    for indices in by_session.values_mut() {
+        indices.sort_by_key(|&idx| (errors[idx].timestamp, idx));
+    }
+
+    operations
+        .iter()
+        .map(|op| {
+            let mut hits: Vec<usize> = Vec::new();
+            for key in session_keys(op.component_id.as_deref().unwrap_or("")) {
+                let Some(indices) = by_session.get(key) else {
+                    continue;
+                };
+                let from = indices.partition_point(|&idx| errors[idx].timestamp < op.start_time);
+                let to = indices.partition_point(|&idx| errors[idx].timestamp <= op.end_time);
+                hits.extend_from_slice(&indices[from..to]);
+            }
+            hits.sort_unstable();
+            hits.dedup();
+            hits
+        })
+        .collect()
+}
+
+/// Attach the ERROR entries that fired during each operation, grouped by normalized pattern
+pub fn link_operation_errors(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    operations: &mut [TimedOperation],
+) {
+    let level_filter = build_error_level_filter(false);
+    let errors: Vec<&LogEntry> = logs
+        .iter()
+        .filter(|entry| filter.matches(entry) && level_filter.matches(entry))
+        .collect();
+    if errors.is_empty() {
+        return;
+    }
+
+    let linked = entries_during_operations(operations, &errors);
+    for (op, entries) in operations.iter_mut().zip(linked) {
+        let mut clusters: BTreeMap<(String, String), OperationErrors> = BTreeMap::new();
+        for entry in entries.into_iter().map(|idx| errors[idx]) {
+            let severity = normalized_severity(&entry.level);
+            let pattern = normalize_message_pattern(&entry.message);
+            clusters
+                .entry((severity.clone(), pattern.clone()))
+                .or_insert_with(|| OperationErrors {
+                    severity,
+                    pattern,
+                    count: 0,
+                    first_timestamp: entry.timestamp,
+                    first_line: entry.source_line_number,
+                })
+                .count += 1;
+        }
+        op.errors_during = clusters.into_values().collect();
+        op.errors_during.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.first_timestamp.cmp(&b.first_timestamp))
+        });
+    }
+}
@@ -0,0 +1,263 @@
+use super::entities::{FairnessBucket, FairnessReport, SessionFairness, TimedOperation};
+use crate::comparator::LogFilter;
+use crate::config::{SessionInsights, split_parallel_session_paths};
+use crate::parser::LogEntry;
+use crate::time_buckets::{TimeBucketOptions, choose_bucket_ms};
+use chrono::{DateTime, Local, TimeZone};
+use std::collections::BTreeMap;
+
+pub(super) type Interval = (i64, i64);
+
+/// Upper bound on fairness buckets; a finer `bucket_ms` is widened to respect it
+const MAX_FAIRNESS_BUCKETS: usize = 1_000;
+
+struct SessionTimeline {
+    registered_ms: i64,
+    busy: Vec<Interval>,
+    operation_count: usize,
+}
+
+impl SessionTimeline {
+    fn active_span(&self) -> Interval {
+        let end = self.busy.last().map_or(self.registered_ms, |(_, end)| *end);
+        (self.registered_ms, end.max(self.registered_ms))
+    }
+}
+
+/// Sorts and merges overlapping intervals
+pub(super) fn merge_intervals(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort();
+    let mut merged: Vec<Interval> = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn intersect(a: &[Interval], b: &[Interval]) -> Vec<Interval> {
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < a.len() && j < b.len() {
+        let start = a[i].0.max(b[j].0);
+        let end = a[i].1.min(b[j].1);
+        if start < end {
+            out.push((start, end));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    out
+}
+
+pub(super) fn measure(intervals: &[Interval]) -> i64 {
+    intervals.iter().map(|(start, end)| end - start).sum()
+}
+
+fn local_from_millis(ms: i64) -> DateTime<Local> {
+    Local
+        .timestamp_millis_opt(ms)
+        .single()
+        .unwrap_or_else(|| DateTime::<Local>::from(DateTime::UNIX_EPOCH))
+}
+
+/// Concurrency summary field of the session or its closest ancestor segment
+fn session_concurrency(sessions: &SessionInsights, path: &str) -> Option<serde_json::Value> {
+    path.split('/').rev().find_map(|segment| {
+        sessions.levels.iter().find_map(|level| {
+            level.sessions.get(segment).and_then(|session| {
+                session
+                    .summary_fields
+                    .iter()
+                    .find(|(field, _)| {
+                        field
+                            .rsplit('.')
+                            .next()
+                            .is_some_and(|name| name.to_ascii_lowercase().contains("concurrency"))
+                    })
+                    .map(|(_, value)| value.clone())
+            })
+        })
+    })
+}
+
+/// Jain fairness index; `None` when nobody ran
+fn jain_index(values: &[f64]) -> Option<f64> {
+    let sum: f64 = values.iter().sum();
+    let sum_sq: f64 = values.iter().map(|value| value * value).sum();
+    (sum > 0.0).then(|| sum * sum / (values.len() as f64 * sum_sq))
+}
+
+/// Compares how long each session waited while other sessions had operations in flight,
+/// and computes a Jain fairness index of per-session busy time for every `bucket_ms` bucket.
+/// Long logs get wider buckets so there are at most `MAX_FAIRNESS_BUCKETS` of them
+pub fn analyze_fairness(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    operations: &[TimedOperation],
+    sessions: &SessionInsights,
+    bucket_ms: i64,
+) -> FairnessReport {
+    let bucket_ms = bucket_ms.max(1);
+
+    let mut registered: BTreeMap<&str, i64> = BTreeMap::new();
+    for entry in logs.iter().filter(|entry| filter.matches(entry)) {
+        let ts = entry.timestamp.timestamp_millis();
+        for path in split_parallel_session_paths(&entry.component_id) {
+            registered
+                .entry(path)
+                .and_modify(|first| *first = (*first).min(ts))
+                .or_insert(ts);
+        }
+    }
+
+    let mut intervals: BTreeMap<&str, Vec<Interval>> = BTreeMap::new();
+    for op in operations {
+        let Some(component_id) = op.component_id.as_deref() else {
+            continue;
+        };
+        let start = op.start_time.timestamp_millis();
+        for path in split_parallel_session_paths(component_id) {
+            intervals
+                .entry(path)
+                .or_default()
+                .push((start, start + op.duration_ms.max(0)));
+        }
+    }
+
+    let timelines: Vec<(&str, SessionTimeline)> = intervals
+        .into_iter()
+        .map(|(path, ops)| {
+            let operation_count = ops.len();
+            let busy = merge_intervals(ops);
+            let first_start = busy.first().map_or(0, |(start, _)| *start);
+            let registered_ms = registered
+                .get(path)
+                .copied()
+                .unwrap_or(first_start)
+                .min(first_start);
+            (
+                path,
+                SessionTimeline {
+                    registered_ms,
+                    busy,
+                    operation_count,
+                },
+            )
+        })
+        .collect();
+
+    let mut session_reports: Vec<SessionFairness> = timelines
+        .iter()
+        .enumerate()
+        .map(|(idx, (path, timeline))| {
+            let others = merge_intervals(
+                timelines
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_idx, _)| *other_idx != idx)
+                    .flat_map(|(_, (_, other))| other.busy.iter().copied())
+                    .collect(),
+            );
+            let span = timeline.active_span();
+            let others_in_span = intersect(&others, &[span]);
+            let waiting_ms =
+                measure(&others_in_span) - measure(&intersect(&others_in_span, &timeline.busy));
+            let busy_ms = measure(&timeline.busy);
+            let span_ms = span.1 - span.0;
+
+            SessionFairness {
+                session_path: path.to_string(),
+                concurrency: session_concurrency(sessions, path),
+                registered_at: local_from_millis(timeline.registered_ms),
+                wait_to_first_op_ms: timeline
+                    .busy
+                    .first()
+                    .map_or(0, |(start, _)| start - timeline.registered_ms),
+                operation_count: timeline.operation_count,
+                busy_ms,
+                waiting_ms,
+                busy_pct: if span_ms > 0 {
+                    busy_ms as f64 / span_ms as f64 * 100.0
+                } else {
+                    100.0
+                },
+                starved: waiting_ms > busy_ms && waiting_ms >= bucket_ms,
+            }
+        })
+        .collect();
+    session_reports.sort_by(|a, b| {
+        b.waiting_ms
+            .cmp(&a.waiting_ms)
+            .then_with(|| a.session_path.cmp(&b.session_path))
+    });
+
+    let mut buckets = Vec::new();
+    let mut bucket_width = bucket_ms;
+    let first_ms = timelines.iter().map(|(_, t)| t.registered_ms).min();
+    let last_ms = timelines.iter().map(|(_, t)| t.active_span().1).max();
+    if let (Some(first_ms), Some(last_ms)) = (first_ms, last_ms) {
+        let end_ms = last_ms.max(first_ms + 1);
+        let needed = ((end_ms - first_ms) as u64).div_ceil(bucket_ms as u64);
+        if needed > MAX_FAIRNESS_BUCKETS as u64 {
+            let options = TimeBucketOptions::new()
+                .bucket_ms(Some(bucket_ms as u64))
+                .max_buckets(MAX_FAIRNESS_BUCKETS);
+            bucket_width = choose_bucket_ms(first_ms, end_ms, &options) as i64;
+        }
+
+        // Buckets advance monotonically, so each session's busy intervals are walked once
+        let mut cursors = vec![0usize; timelines.len()];
+        let mut bucket_start = first_ms;
+        while bucket_start < end_ms {
+            let bucket_end = bucket_start + bucket_width;
+            let mut busy: Vec<f64> = Vec::new();
+            for ((_, timeline), cursor) in timelines.iter().zip(cursors.iter_mut()) {
+                let span = timeline.active_span();
+                if span.0.max(bucket_start) >= span.1.min(bucket_end) {
+                    continue;
+                }
+                while timeline
+                    .busy
+                    .get(*cursor)
+                    .is_some_and(|(_, end)| *end <= bucket_start)
+                {
+                    *cursor += 1;
+                }
+                let overlap: i64 = timeline.busy[*cursor..]
+                    .iter()
+                    .take_while(|(start, _)| *start < bucket_end)
+                    .map(|(start, end)| (*end).min(bucket_end) - (*start).max(bucket_start))
+                    .filter(|overlap| *overlap > 0)
+                    .sum();
+                busy.push(overlap as f64);
+            }
+            buckets.push(FairnessBucket {
+                start: local_from_millis(bucket_start),
+                active_sessions: busy.len(),
+                running_sessions: busy.iter().filter(|value| **value > 0.0).count(),
+                fairness_index: jain_index(&busy),
+            });
+            bucket_start = bucket_end;
+        }
+    }
+
+    let indexes: Vec<f64> = buckets
+        .iter()
+        .filter_map(|bucket| bucket.fairness_index)
+        .collect();
+
+    FairnessReport {
+        bucket_ms: bucket_width,
+        sessions: session_reports,
+        mean_fairness_index: (!indexes.is_empty())
+            .then(|| indexes.iter().sum::<f64>() / indexes.len() as f64),
+        min_fairness_index: indexes.iter().copied().reduce(f64::min),
+        buckets,
+    }
+}
@@ -1,15 +1,34 @@
 mod display;
 mod entities;
+mod error_links;
+mod fairness;
+mod pairing;
+mod phases;
 
 pub use display::{
-    display_perf_results, format_perf_results_json, format_perf_results_text, truncate_string,
+    display_perf_results, format_perf_results_json, format_perf_results_text,
+    format_perf_summary_json, format_perf_summary_text, truncate_string,
 };
-pub use entities::{OperationStats, OrphanOperation, PerfAnalysisResults, TimedOperation};
+pub use entities::{
+    CheckPhases, FairnessBucket, FairnessReport, HangWarning, HostPerfStats, OperationErrors,
+    OperationStats, OrphanOperation, PerfAnalysisResults, PhaseBreakdown, PhaseDuration,
+    PhaseTimes, SessionFairness, SessionPerfStats, SuspectDuration, SuspectDurationCounts,
+    TagPerfStats, ThroughputStats, TimedOperation,
+};
+pub use error_links::link_operation_errors;
+pub(crate) use error_links::session_keys;
+pub use fairness::analyze_fairness;
+pub(crate) use pairing::OperationPairing;
+pub use phases::analyze_phases;
 
 use crate::comparator::LogFilter;
 use crate::config::{AnalyzerConfig, PerfRules, contains_any_marker, default_config};
-use crate::parser::{EventDirection, LogEntry, LogEntryKind, RequestDirection};
-use std::collections::HashMap;
+use crate::parser::{LogEntry, LogEntryKind, RequestDirection};
+use chrono::{DateTime, Local};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// Number of preceding same-session log lines attached to a hang warning
+const HANG_CONTEXT_ENTRIES: usize = 3;
 
 /// Extracts the request ID from a log message containing [request_id] pattern
 /// The pattern is: Request "name" [id] where id contains "--" (e.g., "0--uuid" or "0--uuid#2")
@@ -62,9 +81,13 @@ fn extract_command_key(entry: &LogEntry) -> Option<String> {
     None
 }
 
+fn non_empty_component_id(entry: &LogEntry) -> Option<String> {
+    (!entry.component_id.is_empty()).then(|| entry.component_id.clone())
+}
+
 /// Checks if the logs contain any Command completion patterns
 /// If not, Command tracking should be skipped since they would all appear as orphans
-fn has_command_completion_patterns(logs: &[LogEntry], rules: &PerfRules) -> bool {
+pub(crate) fn has_command_completion_patterns(logs: &[LogEntry], rules: &PerfRules) -> bool {
     logs.iter().any(|entry| {
         if let LogEntryKind::Command { .. } = &entry.kind {
             // Check for "finished" pattern which indicates command completion
@@ -93,13 +116,8 @@ pub fn analyze_performance_with_config(
 ) -> PerfAnalysisResults {
     let mut results = PerfAnalysisResults::new();
 
-    // Track pending operations by correlation key
-    let mut pending_requests: HashMap<String, &LogEntry> = HashMap::new();
-    let mut pending_events: HashMap<String, &LogEntry> = HashMap::new();
-    let mut pending_commands: HashMap<String, &LogEntry> = HashMap::new();
-
-    // Check if we should track commands (only if completion patterns exist)
-    let track_commands = has_command_completion_patterns(logs, &config.perf);
+    // Commands are only reported as orphans when completion patterns exist
+    let mut pairing = OperationPairing::new(has_command_completion_patterns(logs, &config.perf));
 
     // Filter logs first
     let filtered_logs: Vec<&LogEntry> = logs.iter().filter(|log| filter.matches(log)).collect();
@@ -115,205 +133,171 @@ pub fn analyze_performance_with_config(
 
     // Process logs to find paired operations
     for entry in filtered_logs {
-        match &entry.kind {
-            LogEntryKind::Request {
-                request,
-                request_id,
-                endpoint,
-                direction,
-                payload,
-            } => {
-                if op_type_filter.is_some() && op_type_filter != Some("Request") {
-                    continue;
-                }
-
-                // Try to find correlation key
-                let correlation_key = if let Some(req_id) = request_id {
-                    Some(req_id.clone())
-                } else {
-                    extract_request_id(&entry.message)
-                };
+        results
+            .operations
+            .extend(pairing.observe(entry, op_type_filter, &config.perf));
+    }
 
-                match direction {
-                    RequestDirection::Send => {
-                        // This is a request start - store it
-                        if let Some(key) = correlation_key {
-                            pending_requests.insert(key, entry);
-                        }
-                    }
-                    RequestDirection::Receive => {
-                        // This is a request end - try to match with start
-                        if let Some(key) = correlation_key
-                            && let Some(start_entry) = pending_requests.remove(&key)
-                        {
-                            // Calculate duration
-                            let duration = entry
-                                .timestamp
-                                .signed_duration_since(start_entry.timestamp)
-                                .num_milliseconds();
+    // Convert remaining pending operations to orphans
+    results.orphans = pairing.into_orphans();
 
-                            // Extract status from payload
-                            let status = payload
-                                .as_ref()
-                                .and_then(|p| p.get("statusCode"))
-                                .and_then(|s| s.as_i64())
-                                .map(|s| s.to_string());
+    // Calculate statistics
+    results.set_aside_suspect_durations();
+    results.calculate_stats();
+    results.calculate_session_stats(config.sessions.parallel_attribution);
+    results.calculate_tag_stats();
 
-                            results.operations.push(TimedOperation {
-                                op_type: "Request".to_string(),
-                                name: request.clone(),
-                                correlation_id: Some(key),
-                                start_time: start_entry.timestamp,
-                                end_time: entry.timestamp,
-                                duration_ms: duration,
-                                start_component: start_entry.component.clone(),
-                                end_component: entry.component.clone(),
-                                endpoint: endpoint.clone(),
-                                status,
-                            });
-                        }
-                    }
-                }
-            }
-            LogEntryKind::Event {
-                event_type,
-                direction,
-                payload,
-            } => {
-                if op_type_filter.is_some() && op_type_filter != Some("Event") {
-                    continue;
-                }
+    results
+}
 
-                // Try to get correlation key from payload
-                let correlation_key = payload
-                    .as_ref()
-                    .and_then(|p| extract_event_key_with_rules(p, &config.perf));
+/// Replays request Send/Receive pairing in log order and reports every request that
+/// stayed pending longer than `threshold_ms`, as soon as a later entry shows the stall.
+pub fn detect_hanging_requests(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    threshold_ms: u64,
+) -> Vec<HangWarning> {
+    let threshold_ms = threshold_ms as i64;
+    let mut warnings: Vec<HangWarning> = Vec::new();
+    let mut pending = PendingRequests::default();
+    let mut recent_by_session: HashMap<&str, VecDeque<&LogEntry>> = HashMap::new();
 
-                match direction {
-                    EventDirection::Receive => {
-                        // Event received - this is the start
-                        if let Some(key) = correlation_key {
-                            pending_events.insert(key, entry);
-                        }
-                    }
-                    EventDirection::Emit => {
-                        // Event emitted - this is the end
-                        if let Some(key) = correlation_key
-                            && let Some(start_entry) = pending_events.remove(&key)
-                        {
-                            let duration = entry
-                                .timestamp
-                                .signed_duration_since(start_entry.timestamp)
-                                .num_milliseconds();
+    for entry in logs.iter().filter(|log| filter.matches(log)) {
+        report_stale_requests(
+            entry,
+            threshold_ms,
+            &mut pending,
+            &recent_by_session,
+            &mut warnings,
+        );
 
-                            results.operations.push(TimedOperation {
-                                op_type: "Event".to_string(),
-                                name: event_type.clone(),
-                                correlation_id: Some(key),
-                                start_time: start_entry.timestamp,
-                                end_time: entry.timestamp,
-                                duration_ms: duration,
-                                start_component: start_entry.component.clone(),
-                                end_component: entry.component.clone(),
-                                endpoint: None,
-                                status: None,
-                            });
-                        }
-                    }
-                }
+        if !entry.component_id.is_empty() {
+            let recent = recent_by_session
+                .entry(entry.component_id.as_str())
+                .or_default();
+            if recent.len() == HANG_CONTEXT_ENTRIES {
+                recent.pop_front();
             }
-            LogEntryKind::Command { command, .. } => {
-                if op_type_filter.is_some() && op_type_filter != Some("Command") {
-                    continue;
-                }
-
-                // Skip Command tracking if no completion patterns exist in the logs
-                // This prevents showing all Commands as orphans when the SDK doesn't log completion
-                if !track_commands {
-                    continue;
-                }
-
-                // Check if this is a start or finish
-                let is_start =
-                    contains_any_marker(&entry.message, &config.perf.command_start_markers);
-                let is_finish =
-                    contains_any_marker(&entry.message, &config.perf.command_completion_markers);
+            recent.push_back(entry);
+        }
 
-                if let Some(key) = extract_command_key(entry) {
-                    if is_start {
-                        pending_commands.insert(key, entry);
-                    } else if is_finish && let Some(start_entry) = pending_commands.remove(&key) {
-                        let duration = entry
+        let LogEntryKind::Request {
+            request_id,
+            direction,
+            ..
+        } = &entry.kind
+        else {
+            continue;
+        };
+        let Some(key) = request_id
+            .clone()
+            .or_else(|| extract_request_id(&entry.message))
+        else {
+            continue;
+        };
+        match direction {
+            RequestDirection::Send => pending.insert(key, entry),
+            RequestDirection::Receive => {
+                if let Some((start, Some(index))) = pending.remove(&key) {
+                    warnings[index].resolved_after_ms = Some(
+                        entry
                             .timestamp
-                            .signed_duration_since(start_entry.timestamp)
-                            .num_milliseconds();
-
-                        results.operations.push(TimedOperation {
-                            op_type: "Command".to_string(),
-                            name: command.clone(),
-                            correlation_id: Some(key),
-                            start_time: start_entry.timestamp,
-                            end_time: entry.timestamp,
-                            duration_ms: duration,
-                            start_component: start_entry.component.clone(),
-                            end_component: entry.component.clone(),
-                            endpoint: None,
-                            status: None,
-                        });
-                    }
+                            .signed_duration_since(start.timestamp)
+                            .num_milliseconds(),
+                    );
                 }
             }
-            LogEntryKind::Generic { .. } => {
-                // Skip generic log entries for performance analysis
-            }
         }
     }
 
-    // Convert remaining pending operations to orphans
-    for (key, entry) in pending_requests {
-        if let LogEntryKind::Request { request, .. } = &entry.kind {
-            results.orphans.push(OrphanOperation {
-                op_type: "Request".to_string(),
-                name: request.clone(),
-                correlation_id: Some(key),
-                start_time: entry.timestamp,
-                component: entry.component.clone(),
-                component_id: (!entry.component_id.is_empty()).then(|| entry.component_id.clone()),
-                context: entry.message.clone(),
-            });
+    warnings
+}
+
+/// Requests sent and not yet answered
+#[derive(Default)]
+struct PendingRequests<'a> {
+    /// correlation key -> (Send entry, index into the warnings list once reported)
+    by_key: HashMap<String, (&'a LogEntry, Option<usize>)>,
+    /// Requests not reported yet, by Send timestamp, line and key, so each entry only
+    /// visits the requests that just went stale
+    unreported: BTreeSet<(DateTime<Local>, usize, String)>,
+}
+
+impl<'a> PendingRequests<'a> {
+    fn insert(&mut self, key: String, send: &'a LogEntry) {
+        if let Some((previous, None)) = self.by_key.insert(key.clone(), (send, None)) {
+            self.unreported
+                .remove(&(previous.timestamp, previous.source_line_number, key.clone()));
         }
+        self.unreported
+            .insert((send.timestamp, send.source_line_number, key));
     }
 
-    for (key, entry) in pending_events {
-        if let LogEntryKind::Event { event_type, .. } = &entry.kind {
-            results.orphans.push(OrphanOperation {
-                op_type: "Event".to_string(),
-                name: event_type.clone(),
-                correlation_id: Some(key),
-                start_time: entry.timestamp,
-                component: entry.component.clone(),
-                component_id: (!entry.component_id.is_empty()).then(|| entry.component_id.clone()),
-                context: entry.message.clone(),
-            });
+    fn remove(&mut self, key: &str) -> Option<(&'a LogEntry, Option<usize>)> {
+        let (send, reported) = self.by_key.remove(key)?;
+        if reported.is_none() {
+            self.unreported
+                .remove(&(send.timestamp, send.source_line_number, key.to_string()));
         }
+        Some((send, reported))
     }
 
-    for (key, entry) in pending_commands {
-        if let LogEntryKind::Command { command, .. } = &entry.kind {
-            results.orphans.push(OrphanOperation {
-                op_type: "Command".to_string(),
-                name: command.clone(),
-                correlation_id: Some(key),
-                start_time: entry.timestamp,
-                component: entry.component.clone(),
-                component_id: (!entry.component_id.is_empty()).then(|| entry.component_id.clone()),
-                context: entry.message.clone(),
-            });
+    /// Takes the unreported requests pending longer than `threshold_ms` at `now`,
+    /// oldest first
+    fn take_stale(
+        &mut self,
+        now: DateTime<Local>,
+        threshold_ms: i64,
+    ) -> Vec<(String, &'a LogEntry)> {
+        let mut stale = Vec::new();
+        while let Some((started, _, _)) = self.unreported.first()
+            && now.signed_duration_since(*started).num_milliseconds() > threshold_ms
+        {
+            let (_, _, key) = self.unreported.pop_first().expect("first entry exists");
+            if let Some((send, _)) = self.by_key.get(&key) {
+                stale.push((key, *send));
+            }
         }
+        stale
     }
+}
 
-    // Calculate statistics
-    results.calculate_stats();
-
-    results
+fn report_stale_requests(
+    now: &LogEntry,
+    threshold_ms: i64,
+    pending: &mut PendingRequests,
+    recent_by_session: &HashMap<&str, VecDeque<&LogEntry>>,
+    warnings: &mut Vec<HangWarning>,
+) {
+    for (key, start) in pending.take_stale(now.timestamp, threshold_ms) {
+        let LogEntryKind::Request { request, .. } = &start.kind else {
+            continue;
+        };
+        let recent_entries = recent_by_session
+            .get(start.component_id.as_str())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|entry| entry.raw_logline.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some((_, slot)) = pending.by_key.get_mut(&key) {
+            *slot = Some(warnings.len());
+        }
+        warnings.push(HangWarning {
+            op_type: "Request".to_string(),
+            name: request.clone(),
+            correlation_id: Some(key),
+            component_id: non_empty_component_id(start),
+            start_time: start.timestamp,
+            detected_at: now.timestamp,
+            pending_ms: now
+                .timestamp
+                .signed_duration_since(start.timestamp)
+                .num_milliseconds(),
+            resolved_after_ms: None,
+            recent_entries,
+        });
+    }
 }
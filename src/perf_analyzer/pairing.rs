@@ -0,0 +1,276 @@
+use super::entities::{OrphanOperation, SuspectDuration, TimedOperation};
+use super::{extract_command_key, extract_event_key_with_rules, extract_request_id};
+use crate::config::{PerfRules, contains_any_marker};
+use crate::parser::{EventDirection, LogEntry, LogEntryKind, RequestDirection};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The part of a start (or early end) entry an operation keeps until its other side
+/// is logged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct OperationSide {
+    name: String,
+    timestamp: DateTime<Local>,
+    component: String,
+    component_id: Option<String>,
+    tags: Vec<String>,
+    line: usize,
+    endpoint: Option<String>,
+    status: Option<String>,
+    message: String,
+}
+
+impl OperationSide {
+    fn of(entry: &LogEntry, name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            timestamp: entry.timestamp,
+            component: entry.component.clone(),
+            component_id: (!entry.component_id.is_empty()).then(|| entry.component_id.clone()),
+            tags: entry.tags.clone(),
+            line: entry.source_line_number,
+            endpoint: entry.endpoint().map(ToString::to_string),
+            // Status of a response, from its payload
+            status: entry
+                .payload()
+                .and_then(|p| p.get("statusCode"))
+                .and_then(|s| s.as_i64())
+                .map(|s| s.to_string()),
+            message: entry.message.clone(),
+        }
+    }
+
+    pub(crate) fn timestamp(&self) -> DateTime<Local> {
+        self.timestamp
+    }
+
+    pub(crate) fn component_id(&self) -> Option<&str> {
+        self.component_id.as_deref()
+    }
+
+    fn into_orphan(self, op_type: &str, correlation_id: String) -> OrphanOperation {
+        OrphanOperation {
+            op_type: op_type.to_string(),
+            name: self.name,
+            correlation_id: Some(correlation_id),
+            start_time: self.timestamp,
+            component: self.component,
+            component_id: self.component_id,
+            context: self.message,
+        }
+    }
+}
+
+/// A completed operation spanning `start` to `end`, flagged when the end carries an
+/// earlier timestamp than the start
+fn paired_operation(
+    op_type: &str,
+    name: &str,
+    correlation_id: String,
+    start: &OperationSide,
+    end: &OperationSide,
+) -> TimedOperation {
+    let duration_ms = end
+        .timestamp
+        .signed_duration_since(start.timestamp)
+        .num_milliseconds();
+    TimedOperation {
+        op_type: op_type.to_string(),
+        name: name.to_string(),
+        correlation_id: Some(correlation_id),
+        start_time: start.timestamp,
+        end_time: end.timestamp,
+        duration_ms,
+        start_component: start.component.clone(),
+        end_component: end.component.clone(),
+        endpoint: None,
+        status: None,
+        component_id: start.component_id.clone(),
+        errors_during: Vec::new(),
+        tags: start.tags.clone(),
+        suspect: SuspectDuration::classify(
+            duration_ms,
+            (&start.component, start.line),
+            (&end.component, end.line),
+        ),
+        lines: (start.line, end.line),
+    }
+}
+
+/// Start/end pairing of requests, events, and commands, fed one entry at a time. It owns
+/// what it keeps of pending entries, so it can run over a stream and be checkpointed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct OperationPairing {
+    pending_requests: HashMap<String, OperationSide>,
+    pending_events: HashMap<String, OperationSide>,
+    pending_commands: HashMap<String, OperationSide>,
+    /// Responses seen before their request
+    early_responses: HashMap<String, OperationSide>,
+    /// Whether the log has any command completion marker; without one every command
+    /// would end up an orphan, so pending commands are not reported
+    commands_complete: bool,
+}
+
+impl OperationPairing {
+    pub(crate) fn new(commands_complete: bool) -> Self {
+        Self {
+            commands_complete,
+            ..Self::default()
+        }
+    }
+
+    /// Notes a command completion marker on any entry, filtered out or not, for a log
+    /// that is read as a stream and cannot be scanned up front
+    pub(crate) fn watch_command_completion(&mut self, entry: &LogEntry, rules: &PerfRules) {
+        if !self.commands_complete
+            && matches!(entry.kind, LogEntryKind::Command { .. })
+            && contains_any_marker(&entry.message, &rules.command_completion_markers)
+        {
+            self.commands_complete = true;
+        }
+    }
+
+    /// Operations started but not ended yet, with their op type; pending commands are
+    /// listed even before a completion marker is seen
+    pub(crate) fn pending(&self) -> impl Iterator<Item = (&'static str, &OperationSide)> {
+        let requests = self.pending_requests.values().map(|side| ("Request", side));
+        let events = self.pending_events.values().map(|side| ("Event", side));
+        let commands = self.pending_commands.values().map(|side| ("Command", side));
+        requests.chain(events).chain(commands)
+    }
+
+    /// Pairs `entry` with the pending side it ends, returning the completed operation
+    pub(crate) fn observe(
+        &mut self,
+        entry: &LogEntry,
+        op_type_filter: Option<&str>,
+        rules: &PerfRules,
+    ) -> Option<TimedOperation> {
+        match &entry.kind {
+            LogEntryKind::Request {
+                request,
+                request_id,
+                endpoint,
+                direction,
+                ..
+            } => {
+                if op_type_filter.is_some() && op_type_filter != Some("Request") {
+                    return None;
+                }
+
+                // Try to find correlation key
+                let key = request_id
+                    .clone()
+                    .or_else(|| extract_request_id(&entry.message))?;
+
+                match direction {
+                    RequestDirection::Send => {
+                        // This is a request start - store it, unless its response was
+                        // already seen (the response carries an earlier timestamp)
+                        let start = OperationSide::of(entry, request);
+                        let Some(end) = self.early_responses.remove(&key) else {
+                            self.pending_requests.insert(key, start);
+                            return None;
+                        };
+                        let mut op = paired_operation("Request", request, key, &start, &end);
+                        op.endpoint = endpoint.as_ref().map(ToString::to_string);
+                        op.status = end.status;
+                        Some(op)
+                    }
+                    RequestDirection::Receive => {
+                        // This is a request end - try to match with start
+                        let end = OperationSide::of(entry, request);
+                        let Some(start) = self.pending_requests.remove(&key) else {
+                            // Request IDs are unique, so a request logged later
+                            // still belongs to this response
+                            self.early_responses.insert(key, end);
+                            return None;
+                        };
+                        let mut op = paired_operation("Request", request, key, &start, &end);
+                        op.endpoint = start.endpoint.or(end.endpoint);
+                        op.status = end.status;
+                        Some(op)
+                    }
+                }
+            }
+            LogEntryKind::Event {
+                event_type,
+                direction,
+                payload,
+            } => {
+                if op_type_filter.is_some() && op_type_filter != Some("Event") {
+                    return None;
+                }
+
+                // Try to get correlation key from payload
+                let key = payload
+                    .as_ref()
+                    .and_then(|p| extract_event_key_with_rules(p, rules))?;
+
+                match direction {
+                    EventDirection::Receive => {
+                        // Event received - this is the start
+                        self.pending_events
+                            .insert(key, OperationSide::of(entry, event_type));
+                        None
+                    }
+                    EventDirection::Emit => {
+                        // Event emitted - this is the end
+                        let start = self.pending_events.remove(&key)?;
+                        let end = OperationSide::of(entry, event_type);
+                        Some(paired_operation("Event", event_type, key, &start, &end))
+                    }
+                }
+            }
+            LogEntryKind::Command { command, .. } => {
+                if op_type_filter.is_some() && op_type_filter != Some("Command") {
+                    return None;
+                }
+
+                // Check if this is a start or finish
+                let is_start = contains_any_marker(&entry.message, &rules.command_start_markers);
+                let is_finish =
+                    contains_any_marker(&entry.message, &rules.command_completion_markers);
+
+                let key = extract_command_key(entry)?;
+                if is_start {
+                    self.pending_commands
+                        .insert(key, OperationSide::of(entry, command));
+                    None
+                } else if is_finish {
+                    let start = self.pending_commands.remove(&key)?;
+                    let end = OperationSide::of(entry, command);
+                    Some(paired_operation("Command", command, key, &start, &end))
+                } else {
+                    None
+                }
+            }
+            LogEntryKind::Generic { .. } => {
+                // Skip generic log entries for performance analysis
+                None
+            }
+        }
+    }
+
+    /// Operations that were started but never completed, and responses whose request was
+    /// never logged. Commands are left out when the log has no completion marker, since
+    /// the SDK then never logs their end.
+    pub(crate) fn into_orphans(self) -> Vec<OrphanOperation> {
+        let requests = self
+            .pending_requests
+            .into_iter()
+            .chain(self.early_responses)
+            .map(|(key, side)| side.into_orphan("Request", key));
+        let events = self
+            .pending_events
+            .into_iter()
+            .map(|(key, side)| side.into_orphan("Event", key));
+        let commands = self
+            .pending_commands
+            .into_iter()
+            .filter(|_| self.commands_complete)
+            .map(|(key, side)| side.into_orphan("Command", key));
+        requests.chain(events).chain(commands).collect()
+    }
+}
@@ -0,0 +1,125 @@
+use super::entities::{CheckPhases, PhaseBreakdown, PhaseDuration, PhaseTimes, TimedOperation};
+use super::fairness::{Interval, measure, merge_intervals};
+use crate::config::{PhaseRule, split_parallel_session_paths};
+use crate::session_path::SessionPath;
+use std::collections::BTreeMap;
+
+struct CheckTimeline {
+    /// First operation start to last operation end
+    span: Interval,
+    /// Operation intervals per phase, in profile order
+    phases: Vec<Vec<Interval>>,
+}
+
+fn median(mut values: Vec<i64>) -> i64 {
+    values.sort_unstable();
+    match values.len() {
+        0 => 0,
+        n if n % 2 == 1 => values[n / 2],
+        n => (values[n / 2 - 1] + values[n / 2]) / 2,
+    }
+}
+
+/// The check an operation at `path` belongs to: the path up to its innermost segment
+/// starting with `check_prefix`, so renders and requests nested under a check count for it
+fn check_of(path: &str, check_prefix: &str) -> Option<String> {
+    let path = SessionPath::parse(path);
+    let segments = path.segments();
+    let depth = segments
+        .iter()
+        .rposition(|segment| segment.starts_with(check_prefix))?;
+    Some(segments[..=depth].join("/"))
+}
+
+/// Splits the time of every check with at least one phase operation into the profile's
+/// phases. A check is a session path segment starting with `check_prefix`, with all the
+/// operations nested under it. Overlapping operations of one phase count once; time no
+/// phase covers is `other_ms`.
+pub fn analyze_phases(
+    operations: &[TimedOperation],
+    phases: &[PhaseRule],
+    check_prefix: &str,
+) -> PhaseBreakdown {
+    let mut timelines: BTreeMap<String, CheckTimeline> = BTreeMap::new();
+    for op in operations {
+        let Some(component_id) = op.component_id.as_deref() else {
+            continue;
+        };
+        let start = op.start_time.timestamp_millis();
+        let interval = (start, start + op.duration_ms.max(0));
+        let phase = phases
+            .iter()
+            .position(|rule| rule.operations.contains(&op.name));
+        let mut checks: Vec<String> = split_parallel_session_paths(component_id)
+            .into_iter()
+            .filter_map(|path| check_of(path, check_prefix))
+            .collect();
+        checks.sort();
+        checks.dedup();
+        for check in checks {
+            let timeline = timelines.entry(check).or_insert_with(|| CheckTimeline {
+                span: interval,
+                phases: vec![Vec::new(); phases.len()],
+            });
+            timeline.span = (
+                timeline.span.0.min(interval.0),
+                timeline.span.1.max(interval.1),
+            );
+            if let Some(index) = phase {
+                timeline.phases[index].push(interval);
+            }
+        }
+    }
+
+    let mut checks: Vec<CheckPhases> = timelines
+        .into_iter()
+        .filter(|(_, timeline)| timeline.phases.iter().any(|phase| !phase.is_empty()))
+        .map(|(path, timeline)| {
+            let total_ms = timeline.span.1 - timeline.span.0;
+            let covered = measure(&merge_intervals(timeline.phases.concat()));
+            CheckPhases {
+                session_path: path,
+                times: PhaseTimes {
+                    total_ms,
+                    phases: phases
+                        .iter()
+                        .zip(timeline.phases)
+                        .map(|(rule, intervals)| PhaseDuration {
+                            phase: rule.name.clone(),
+                            duration_ms: measure(&merge_intervals(intervals)),
+                        })
+                        .collect(),
+                    other_ms: total_ms - covered,
+                },
+            }
+        })
+        .collect();
+    checks.sort_by(|a, b| {
+        b.times
+            .total_ms
+            .cmp(&a.times.total_ms)
+            .then_with(|| a.session_path.cmp(&b.session_path))
+    });
+
+    let column = |value: &dyn Fn(&PhaseTimes) -> i64| {
+        median(checks.iter().map(|check| value(&check.times)).collect())
+    };
+    let median = PhaseTimes {
+        total_ms: column(&|times| times.total_ms),
+        phases: phases
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| PhaseDuration {
+                phase: rule.name.clone(),
+                duration_ms: column(&|times| times.phases[index].duration_ms),
+            })
+            .collect(),
+        other_ms: column(&|times| times.other_ms),
+    };
+
+    PhaseBreakdown {
+        phases: phases.iter().map(|rule| rule.name.clone()).collect(),
+        checks,
+        median,
+    }
+}
@@ -0,0 +1,101 @@
+//! Per-call rendering options, so analyses running concurrently in one process can
+//! colour their output independently
+
+use crate::cli::ColorMode;
+use colored::control::ShouldColorize;
+use colored::{ColoredString, Styles};
+use std::borrow::Cow;
+
+/// ANSI SGR code of each text style, in the order `colored` writes them. `colored` keeps
+/// its own table private and only applies it through `Display`, which follows the
+/// process-wide colour decision.
+const STYLE_CODES: [(Styles, &str); 8] = [
+    (Styles::Bold, "1"),
+    (Styles::Dimmed, "2"),
+    (Styles::Italic, "3"),
+    (Styles::Underline, "4"),
+    (Styles::Blink, "5"),
+    (Styles::Reversed, "7"),
+    (Styles::Hidden, "8"),
+    (Styles::Strikethrough, "9"),
+];
+
+/// Whether console output is coloured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Colour when stdout is a terminal, honouring `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE`
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl From<ColorMode> for ColorChoice {
+    fn from(mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Auto => Self::Auto,
+            ColorMode::Always => Self::Always,
+            ColorMode::Never => Self::Never,
+        }
+    }
+}
+
+/// How formatters style their output. The colour decision is made once, when the options
+/// are built, and never consults or changes process-wide state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    colors: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::new(ColorChoice::Auto)
+    }
+}
+
+impl RenderOptions {
+    pub fn new(color: ColorChoice) -> Self {
+        let colors = match color {
+            ColorChoice::Auto => ShouldColorize::from_env().should_colorize(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        };
+        Self { colors }
+    }
+
+    pub fn colors(&self) -> bool {
+        self.colors
+    }
+
+    /// `styled` with its ANSI escapes when colours are on, its plain text otherwise.
+    /// Unlike `ColoredString`'s `Display`, this ignores `colored`'s global override.
+    pub fn paint(&self, styled: ColoredString) -> String {
+        if !self.colors || styled.is_plain() {
+            return styled.input;
+        }
+        let mut codes: Vec<Cow<'static, str>> = STYLE_CODES
+            .iter()
+            .filter(|(flag, _)| styled.style.contains(*flag))
+            .map(|(_, code)| Cow::Borrowed(*code))
+            .collect();
+        codes.extend(styled.bgcolor.map(|color| color.to_bg_str()));
+        codes.extend(styled.fgcolor.map(|color| color.to_fg_str()));
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), styled.input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use colored::Colorize;
+
+    #[test]
+    fn paints_per_call() {
+        let always = RenderOptions::new(ColorChoice::Always);
+        let never = RenderOptions::new(ColorChoice::Never);
+        assert_eq!(always.paint("ok".green().bold()), "\x1b[1;32mok\x1b[0m");
+        assert_eq!(always.paint("hi".on_blue()), "\x1b[44mhi\x1b[0m");
+        assert_eq!(always.paint("plain".normal()), "plain");
+        assert_eq!(never.paint("ok".green().bold()), "ok");
+    }
+}
@@ -0,0 +1,578 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Version of the `report_merge` document written by `report merge`
+pub const ROLLUP_SCHEMA_VERSION: u64 = 1;
+/// Newest input `schema_version` understood; inputs without one are treated as version 1
+const SUPPORTED_SCHEMA_VERSION: u64 = 2;
+/// Histogram sub-buckets per power of two (~9% relative error on merged percentiles)
+const HISTOGRAM_SUB_BUCKETS: f64 = 8.0;
+
+/// JSON report kinds that can be merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportKind {
+    Errors,
+    Perf,
+    Compare,
+}
+
+impl ReportKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ReportKind::Errors => "errors",
+            ReportKind::Perf => "perf",
+            ReportKind::Compare => "compare",
+        }
+    }
+}
+
+/// Recognizes `errors`, `perf`, and `compare`/`diff`/`llm-diff` JSON output by shape
+pub fn detect_report_kind(document: &Value) -> Option<ReportKind> {
+    if document.get("errors").is_some_and(Value::is_object) {
+        Some(ReportKind::Errors)
+    } else if document.get("stats").is_some() && document.get("operations").is_some() {
+        Some(ReportKind::Perf)
+    } else if document.get("comparisons").is_some() || document.get("c").is_some() {
+        Some(ReportKind::Compare)
+    } else {
+        None
+    }
+}
+
+/// Log-scaled latency histogram; merging two histograms and reading percentiles from
+/// the result approximates the percentiles of the combined samples
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencyHistogram {
+    buckets: BTreeMap<u32, u64>,
+    count: u64,
+    sum_ms: f64,
+    min_ms: i64,
+    max_ms: i64,
+}
+
+impl LatencyHistogram {
+    fn bucket_index(duration_ms: i64) -> u32 {
+        if duration_ms <= 1 {
+            0
+        } else {
+            ((duration_ms as f64).log2() * HISTOGRAM_SUB_BUCKETS).ceil() as u32
+        }
+    }
+
+    /// Inclusive upper bound of a bucket in milliseconds
+    fn bucket_upper_ms(index: u32) -> i64 {
+        (index as f64 / HISTOGRAM_SUB_BUCKETS).exp2().round() as i64
+    }
+
+    pub fn record(&mut self, duration_ms: i64) {
+        let duration_ms = duration_ms.max(0);
+        if self.count == 0 {
+            self.min_ms = duration_ms;
+            self.max_ms = duration_ms;
+        } else {
+            self.min_ms = self.min_ms.min(duration_ms);
+            self.max_ms = self.max_ms.max(duration_ms);
+        }
+        *self
+            .buckets
+            .entry(Self::bucket_index(duration_ms))
+            .or_default() += 1;
+        self.count += 1;
+        self.sum_ms += duration_ms as f64;
+    }
+
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.min_ms = other.min_ms;
+            self.max_ms = other.max_ms;
+        } else {
+            self.min_ms = self.min_ms.min(other.min_ms);
+            self.max_ms = self.max_ms.max(other.max_ms);
+        }
+        for (&index, &count) in &other.buckets {
+            *self.buckets.entry(index).or_default() += count;
+        }
+        self.count += other.count;
+        self.sum_ms += other.sum_ms;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean_ms(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum_ms / self.count as f64
+        }
+    }
+
+    /// Upper bound of the bucket holding the `pct` percentile, clamped to the observed range
+    pub fn percentile_ms(&self, pct: f64) -> i64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let rank = ((pct / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (&index, &count) in &self.buckets {
+            seen += count;
+            if seen >= rank {
+                return Self::bucket_upper_ms(index).clamp(self.min_ms, self.max_ms);
+            }
+        }
+        self.max_ms
+    }
+
+    fn to_json(&self) -> Value {
+        Value::Array(
+            self.buckets
+                .iter()
+                .map(|(&index, &count)| json!({ "le_ms": Self::bucket_upper_ms(index), "count": count }))
+                .collect(),
+        )
+    }
+}
+
+/// Aggregate of several reports of the same kind
+#[derive(Debug, Clone, Serialize)]
+pub struct MergedReport {
+    pub schema_version: u64,
+    pub kind: ReportKind,
+    /// Source of each merged report, in input order
+    pub inputs: Vec<String>,
+    /// Caveats such as inputs that listed only their top clusters
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    pub summary: Value,
+    /// Kind-specific rows: clusters, operations, or compared keys
+    pub items: Vec<Value>,
+}
+
+/// Reads and merges JSON reports written by `errors`, `perf`, or `compare`/`diff`
+pub fn merge_report_files(paths: &[impl AsRef<Path>]) -> Result<MergedReport, Box<dyn Error>> {
+    let mut reports = Vec::with_capacity(paths.len());
+    for path in paths {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read report '{}': {}", path.display(), e))?;
+        let document: Value = serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse report '{}': {}", path.display(), e))?;
+        reports.push((path.display().to_string(), document));
+    }
+    merge_reports(&reports)
+}
+
+/// Merges `(source, document)` pairs; every document must be the same report kind
+pub fn merge_reports(reports: &[(String, Value)]) -> Result<MergedReport, Box<dyn Error>> {
+    let Some((first_source, first)) = reports.first() else {
+        return Err("No reports to merge".into());
+    };
+    let kind = detect_report_kind(first).ok_or_else(|| {
+        format!("'{first_source}' is not an errors, perf, or compare JSON report")
+    })?;
+
+    for (source, document) in reports {
+        if let Some(version) = document.get("schema_version").and_then(Value::as_u64)
+            && version > SUPPORTED_SCHEMA_VERSION
+        {
+            return Err(format!(
+                "'{source}' uses schema_version {version}; this build understands up to {SUPPORTED_SCHEMA_VERSION}"
+            )
+            .into());
+        }
+        match detect_report_kind(document) {
+            Some(other) if other == kind => {}
+            Some(other) => {
+                return Err(format!(
+                    "Cannot merge {} report '{}' with {} report '{}'",
+                    other.label(),
+                    source,
+                    kind.label(),
+                    first_source
+                )
+                .into());
+            }
+            None => {
+                return Err(
+                    format!("'{source}' is not an errors, perf, or compare JSON report").into(),
+                );
+            }
+        }
+    }
+
+    let documents: Vec<(&str, &Value)> = reports
+        .iter()
+        .map(|(source, document)| (source.as_str(), document))
+        .collect();
+    let (summary, items, warnings) = match kind {
+        ReportKind::Errors => merge_errors(&documents),
+        ReportKind::Perf => merge_perf(&documents),
+        ReportKind::Compare => merge_compare(&documents),
+    };
+
+    Ok(MergedReport {
+        schema_version: ROLLUP_SCHEMA_VERSION,
+        kind,
+        inputs: reports.iter().map(|(source, _)| source.clone()).collect(),
+        warnings,
+        summary,
+        items,
+    })
+}
+
+fn u64_at(value: &Value, pointer: &str) -> u64 {
+    value.pointer(pointer).and_then(Value::as_u64).unwrap_or(0)
+}
+
+fn timestamp(value: &Value) -> Option<DateTime<FixedOffset>> {
+    value
+        .as_str()
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+}
+
+#[derive(Default)]
+struct ClusterRollup {
+    severity: String,
+    pattern: String,
+    count: u64,
+    runs: usize,
+    affected_sessions_count: u64,
+    components: BTreeSet<String>,
+    first_timestamp: Option<DateTime<FixedOffset>>,
+    last_timestamp: Option<DateTime<FixedOffset>>,
+    sample_message: String,
+}
+
+fn merge_errors(documents: &[(&str, &Value)]) -> (Value, Vec<Value>, Vec<String>) {
+    const SUMMED: [&str; 7] = [
+        "file_count",
+        "total_entries",
+        "error_count",
+        "warn_count",
+        "unique_patterns",
+        "affected_sessions_count",
+        "suppressed_count",
+    ];
+    let mut totals: BTreeMap<&str, u64> = SUMMED.iter().map(|key| (*key, 0)).collect();
+    let mut clusters: BTreeMap<(String, String), ClusterRollup> = BTreeMap::new();
+    let mut warnings = Vec::new();
+
+    for (source, document) in documents {
+        let errors = &document["errors"];
+        for key in SUMMED {
+            *totals.entry(key).or_default() += u64_at(errors, &format!("/summary/{key}"));
+        }
+        let listed = errors["clusters"].as_array().map_or(0, Vec::len) as u64;
+        let total = u64_at(errors, "/clusters_total");
+        if total > listed {
+            warnings.push(format!(
+                "{source} lists {listed} of {total} clusters (rerun errors with --top-n 0 for exact totals)"
+            ));
+        }
+
+        for cluster in errors["clusters"].as_array().into_iter().flatten() {
+            let severity = cluster["severity"].as_str().unwrap_or_default().to_string();
+            let pattern = cluster["pattern"].as_str().unwrap_or_default().to_string();
+            let rollup = clusters
+                .entry((severity.clone(), pattern.clone()))
+                .or_insert_with(|| ClusterRollup {
+                    severity,
+                    pattern,
+                    sample_message: cluster["sample_message"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    ..ClusterRollup::default()
+                });
+            rollup.count += u64_at(cluster, "/count");
+            rollup.runs += 1;
+            rollup.affected_sessions_count += u64_at(cluster, "/affected_sessions_count");
+            rollup.components.extend(
+                cluster["components"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string),
+            );
+            if let Some(first) = timestamp(&cluster["first_timestamp"]) {
+                rollup.first_timestamp =
+                    Some(rollup.first_timestamp.map_or(first, |ts| ts.min(first)));
+            }
+            if let Some(last) = timestamp(&cluster["last_timestamp"]) {
+                rollup.last_timestamp = Some(rollup.last_timestamp.map_or(last, |ts| ts.max(last)));
+            }
+        }
+    }
+
+    let mut rollups: Vec<ClusterRollup> = clusters.into_values().collect();
+    rollups.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| b.runs.cmp(&a.runs))
+            .then_with(|| a.pattern.cmp(&b.pattern))
+    });
+    totals.insert("unique_patterns", rollups.len() as u64);
+
+    let items = rollups
+        .into_iter()
+        .map(|rollup| {
+            json!({
+                "severity": rollup.severity,
+                "pattern": rollup.pattern,
+                "count": rollup.count,
+                "runs": rollup.runs,
+                "affected_sessions_count": rollup.affected_sessions_count,
+                "components": rollup.components,
+                "first_timestamp": rollup.first_timestamp.map(|ts| ts.to_rfc3339()),
+                "last_timestamp": rollup.last_timestamp.map(|ts| ts.to_rfc3339()),
+                "sample_message": rollup.sample_message,
+            })
+        })
+        .collect();
+    (json!(totals), items, warnings)
+}
+
+#[derive(Default)]
+struct OperationRollup {
+    histogram: LatencyHistogram,
+    runs: BTreeSet<usize>,
+}
+
+fn merge_perf(documents: &[(&str, &Value)]) -> (Value, Vec<Value>, Vec<String>) {
+    let mut operations: BTreeMap<(String, String), OperationRollup> = BTreeMap::new();
+    let (mut total_entries, mut orphans) = (0, 0);
+
+    for (run, (_, document)) in documents.iter().enumerate() {
+        total_entries += u64_at(document, "/total_entries");
+        orphans += document["orphans"].as_array().map_or(0, Vec::len);
+        for op in document["operations"].as_array().into_iter().flatten() {
+            let key = (
+                op["op_type"].as_str().unwrap_or_default().to_string(),
+                op["name"].as_str().unwrap_or_default().to_string(),
+            );
+            let rollup = operations.entry(key).or_default();
+            rollup
+                .histogram
+                .record(op["duration_ms"].as_i64().unwrap_or(0));
+            rollup.runs.insert(run);
+        }
+    }
+
+    let mut rows: Vec<((String, String), OperationRollup)> = operations.into_iter().collect();
+    rows.sort_by(|a, b| {
+        b.1.histogram
+            .count()
+            .cmp(&a.1.histogram.count())
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    let operation_count: u64 = rows
+        .iter()
+        .map(|(_, rollup)| rollup.histogram.count())
+        .sum();
+
+    let items = rows
+        .into_iter()
+        .map(|((op_type, name), OperationRollup { histogram, runs })| {
+            json!({
+                "op_type": op_type,
+                "name": name,
+                "count": histogram.count(),
+                "runs": runs.len(),
+                "avg_duration_ms": histogram.mean_ms(),
+                "min_duration_ms": histogram.min_ms,
+                "max_duration_ms": histogram.max_ms,
+                "p50_duration_ms": histogram.percentile_ms(50.0),
+                "p95_duration_ms": histogram.percentile_ms(95.0),
+                "p99_duration_ms": histogram.percentile_ms(99.0),
+                "histogram": histogram.to_json(),
+            })
+        })
+        .collect();
+    let summary = json!({
+        "total_entries": total_entries,
+        "operations": operation_count,
+        "orphans": orphans,
+    });
+    (summary, items, Vec::new())
+}
+
+#[derive(Default)]
+struct KeyRollup {
+    runs: usize,
+    instances: u64,
+    differences: u64,
+    identical: u64,
+}
+
+fn merge_compare(documents: &[(&str, &Value)]) -> (Value, Vec<Value>, Vec<String>) {
+    let mut totals: BTreeMap<&str, u64> = BTreeMap::new();
+    let mut keys: BTreeMap<String, KeyRollup> = BTreeMap::new();
+
+    for (_, document) in documents {
+        // Compact output (`-c`/`-j`) abbreviates keys
+        let compact = document.get("c").is_some();
+        let pick = |full: &'static str, short: &'static str| if compact { short } else { full };
+        let summary = &document[pick("summary", "s")];
+        let count = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| summary.get(*key).and_then(Value::as_u64))
+                .unwrap_or(0)
+        };
+        *totals.entry("unique_to_log1_count").or_default() +=
+            count(&["unique_to_log1_count", "u1c"]);
+        *totals.entry("unique_to_log2_count").or_default() +=
+            count(&["unique_to_log2_count", "u2c"]);
+        *totals.entry("shared_count").or_default() +=
+            count(&["shared_count", "shared_log_count", "sc"]);
+        *totals.entry("differences_count").or_default() +=
+            count(&["differences_count", "total_differences_count", "dc"]);
+        *totals.entry("identical_count").or_default() += count(&["identical_count", "idc"]);
+
+        let mut seen: BTreeSet<&str> = BTreeSet::new();
+        for group in document[pick("comparisons", "c")]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            let Some(raw_key) = group[pick("key", "k")][pick("raw_key", "r")].as_str() else {
+                continue;
+            };
+            let rollup = keys.entry(raw_key.to_string()).or_default();
+            if seen.insert(raw_key) {
+                rollup.runs += 1;
+            }
+            rollup.instances += u64_at(group, &format!("/{}", pick("instance_count", "ic")));
+            rollup.identical += u64_at(group, &format!("/{}", pick("identical_count", "idc")));
+            rollup.differences += group[pick("instances", "i")]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|instance| u64_at(instance, &format!("/{}", pick("diff_count", "dc"))))
+                .sum::<u64>();
+        }
+        // Keys whose pairs were all identical only appear in the identical-count map
+        let identical_only = document[pick("identical_counts", "idk")].as_object();
+        for (raw_key, identical) in identical_only.into_iter().flatten() {
+            if seen.contains(raw_key.as_str()) {
+                continue;
+            }
+            let rollup = keys.entry(raw_key.clone()).or_default();
+            rollup.runs += 1;
+            rollup.identical += identical.as_u64().unwrap_or(0);
+        }
+    }
+
+    let mut rows: Vec<(String, KeyRollup)> = keys.into_iter().collect();
+    rows.sort_by(|a, b| {
+        b.1.differences
+            .cmp(&a.1.differences)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    let items = rows
+        .into_iter()
+        .map(|(key, rollup)| {
+            json!({
+                "key": key,
+                "runs": rollup.runs,
+                "instances": rollup.instances,
+                "differences": rollup.differences,
+                "identical": rollup.identical,
+            })
+        })
+        .collect();
+    (json!(totals), items, Vec::new())
+}
+
+pub fn format_report_merge_json(report: &MergedReport) -> String {
+    serde_json::to_string_pretty(&json!({ "report_merge": report })).unwrap_or_else(|_| {
+        "{\"report_merge\":{\"error\":\"failed to serialize merged report\"}}".into()
+    })
+}
+
+pub fn format_report_merge_text(report: &MergedReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "REPORT MERGE: {} {} report(s)",
+        report.inputs.len(),
+        report.kind.label()
+    );
+    for warning in &report.warnings {
+        let _ = writeln!(out, "  ⚠ {warning}");
+    }
+    if let Some(summary) = report.summary.as_object() {
+        let _ = writeln!(out);
+        for (key, value) in summary {
+            let _ = writeln!(out, "  {key}: {value}");
+        }
+    }
+    let _ = writeln!(out);
+    for item in &report.items {
+        let line = match report.kind {
+            ReportKind::Errors => format!(
+                "{:>7}  [{}] {} ({} run(s))",
+                item["count"],
+                item["severity"].as_str().unwrap_or_default(),
+                item["pattern"].as_str().unwrap_or_default(),
+                item["runs"]
+            ),
+            ReportKind::Perf => format!(
+                "{:>7}  {} {}  p50 {}ms  p95 {}ms  p99 {}ms ({} run(s))",
+                item["count"],
+                item["op_type"].as_str().unwrap_or_default(),
+                item["name"].as_str().unwrap_or_default(),
+                item["p50_duration_ms"],
+                item["p95_duration_ms"],
+                item["p99_duration_ms"],
+                item["runs"]
+            ),
+            ReportKind::Compare => format!(
+                "{:>7}  {}  ({} instance(s), {} identical, {} run(s))",
+                item["differences"],
+                item["key"].as_str().unwrap_or_default(),
+                item["instances"],
+                item["identical"],
+                item["runs"]
+            ),
+        };
+        let _ = writeln!(out, "{line}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merged_histogram_percentiles_match_combined_samples() {
+        let (mut a, mut b) = (LatencyHistogram::default(), LatencyHistogram::default());
+        (1..=50).for_each(|ms| a.record(ms * 10));
+        (51..=100).for_each(|ms| b.record(ms * 10));
+        a.merge(&b);
+
+        assert_eq!(a.count(), 100);
+        assert_eq!(a.min_ms, 10);
+        assert_eq!(a.max_ms, 1000);
+        assert_eq!(a.mean_ms(), 505.0);
+        for (pct, exact) in [(50.0, 500.0), (95.0, 950.0), (99.0, 990.0)] {
+            let estimate = a.percentile_ms(pct) as f64;
+            assert!(
+                (estimate - exact).abs() / exact <= 0.1,
+                "p{pct}: {estimate} vs {exact}"
+            );
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::comparator::create_styled_table;
+use crate::config::RunRules;
+use crate::parser::LogEntry;
+use chrono::{DateTime, Local};
+use comfy_table::{Cell, CellAlignment};
+use std::fmt::Write;
+
+/// Why a run starts where it does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunBoundary {
+    /// First entry of the stream
+    Start,
+    /// Entry whose message contains this configured start marker
+    Marker(String),
+    /// Entry after this many milliseconds of silence
+    Gap(i64),
+}
+
+impl RunBoundary {
+    pub fn describe(&self) -> String {
+        match self {
+            RunBoundary::Start => "start of log".to_string(),
+            RunBoundary::Marker(marker) => format!("marker \"{marker}\""),
+            RunBoundary::Gap(ms) => format!("{} gap", format_gap(*ms)),
+        }
+    }
+}
+
+fn format_gap(ms: i64) -> String {
+    let minutes = ms / 60_000;
+    if minutes >= 60 {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    } else if minutes > 0 {
+        format!("{minutes}m")
+    } else {
+        format!("{}s", ms / 1_000)
+    }
+}
+
+/// A contiguous slice of a log stream that belongs to one run
+#[derive(Debug, Clone)]
+pub struct LogRun {
+    /// 1-based run number, as accepted by `--run`
+    pub number: usize,
+    pub boundary: RunBoundary,
+    pub first_index: usize,
+    pub entries: usize,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub error_count: usize,
+}
+
+impl LogRun {
+    pub fn duration_ms(&self) -> i64 {
+        (self.end - self.start).num_milliseconds()
+    }
+}
+
+/// Splits `logs` (in stream order) into runs at configured start markers and at
+/// silences longer than `gap_ms`. Always returns at least one run for non-empty logs.
+pub fn detect_runs(logs: &[LogEntry], rules: &RunRules) -> Vec<LogRun> {
+    let mut runs: Vec<LogRun> = Vec::new();
+    let mut previous: Option<DateTime<Local>> = None;
+
+    for (index, entry) in logs.iter().enumerate() {
+        let boundary = match (runs.last(), previous) {
+            (None, _) => Some(RunBoundary::Start),
+            (Some(_), previous) => rules
+                .start_markers
+                .iter()
+                .find(|marker| !marker.is_empty() && entry.message.contains(marker.as_str()))
+                .map(|marker| RunBoundary::Marker(marker.clone()))
+                .or_else(|| {
+                    let gap = (entry.timestamp - previous?).num_milliseconds();
+                    let long = rules.gap_ms > 0 && gap > rules.gap_ms as i64;
+                    long.then_some(RunBoundary::Gap(gap))
+                }),
+        };
+        if let Some(boundary) = boundary {
+            runs.push(LogRun {
+                number: runs.len() + 1,
+                boundary,
+                first_index: index,
+                entries: 0,
+                start: entry.timestamp,
+                end: entry.timestamp,
+                error_count: 0,
+            });
+        }
+
+        let run = runs
+            .last_mut()
+            .expect("a run was started for the first entry");
+        run.entries += 1;
+        run.start = run.start.min(entry.timestamp);
+        run.end = run.end.max(entry.timestamp);
+        if entry.level.eq_ignore_ascii_case("ERROR") {
+            run.error_count += 1;
+        }
+        previous = Some(entry.timestamp);
+    }
+    runs
+}
+
+/// Run table for `info --split-runs`
+pub fn format_runs_text(runs: &[LogRun]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "\nRUNS: {} detected", runs.len());
+    let mut table = create_styled_table(&[
+        "Run", "Start", "End", "Duration", "Entries", "Errors", "Boundary",
+    ]);
+    for run in runs {
+        table.add_row(vec![
+            Cell::new(run.number).set_alignment(CellAlignment::Right),
+            Cell::new(run.start.format("%Y-%m-%d %H:%M:%S")),
+            Cell::new(run.end.format("%Y-%m-%d %H:%M:%S")),
+            Cell::new(format_gap(run.duration_ms())),
+            Cell::new(run.entries).set_alignment(CellAlignment::Right),
+            Cell::new(run.error_count).set_alignment(CellAlignment::Right),
+            Cell::new(run.boundary.describe()),
+        ]);
+    }
+    let _ = writeln!(out, "{table}");
+    let _ = writeln!(out, "Use --run <N> to analyze a single run.");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ms: i64, message: &str) -> LogEntry {
+        let line = format!(
+            "core | {} [INFO ] {message}",
+            chrono::DateTime::from_timestamp_millis(1_767_225_600_000 + ms)
+                .unwrap()
+                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        );
+        crate::parser::parse_log_entry(&line, 1).expect("line parses")
+    }
+
+    #[test]
+    fn splits_at_markers_and_long_gaps() {
+        let logs = vec![
+            entry(0, "Core universal initialization"),
+            entry(1_000, "working"),
+            entry(2_000, "Core universal initialization"),
+            entry(3_000, "working"),
+            entry(3_000 + 3_600_000, "working after lunch"),
+        ];
+        let rules = RunRules {
+            start_markers: vec!["Core universal initialization".to_string()],
+            ..RunRules::default()
+        };
+
+        let runs = detect_runs(&logs, &rules);
+        let shape: Vec<(usize, usize)> = runs
+            .iter()
+            .map(|run| (run.first_index, run.entries))
+            .collect();
+        assert_eq!(shape, [(0, 2), (2, 2), (4, 1)]);
+        assert_eq!(runs[0].boundary, RunBoundary::Start);
+        assert!(matches!(runs[1].boundary, RunBoundary::Marker(_)));
+        assert_eq!(runs[2].boundary, RunBoundary::Gap(3_600_000));
+
+        let no_gaps = RunRules { gap_ms: 0, ..rules };
+        assert_eq!(detect_runs(&logs, &no_gaps).len(), 2);
+    }
+}
@@ -0,0 +1,132 @@
+//! Per-file cache of `info --json-schema` reports, so re-running the analysis after
+//! adding a file only parses the new file
+
+use crate::comparator::{LogFilter, SchemaReport};
+use crate::config::AnalyzerConfig;
+use crate::diagnostics::WarningLog;
+use crate::dupes::fnv1a;
+use crate::output::write_output;
+use crate::parser::parse_log_file_reporting;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the cached report layout or its collection changes
+const CACHE_VERSION: u32 = 1;
+
+/// The cached report of one input file
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    version: u32,
+    source: String,
+    /// Hash of the file's bytes; any edit invalidates the entry
+    content_hash: String,
+    /// Hash of everything besides the content that shapes the report, read scope and the
+    /// instant relative time bounds count back from included
+    settings_hash: String,
+    report: SchemaReport,
+}
+
+/// How many input files came from the cache and how many were parsed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    pub reused: usize,
+    pub parsed: usize,
+}
+
+fn settings_hash(
+    config: &AnalyzerConfig,
+    filter: &LogFilter,
+    filter_expr: Option<&str>,
+) -> Result<String, Box<dyn Error>> {
+    let settings = serde_json::to_string(&(
+        CACHE_VERSION,
+        config,
+        config.parser.scope,
+        filter_expr,
+        filter.relative_reference(),
+    ))?;
+    Ok(format!("{:016x}", fnv1a(settings.as_bytes())))
+}
+
+/// `<dir>/<hash of the canonical path>.json`
+fn entry_path(cache_dir: &Path, file: &Path) -> PathBuf {
+    let source = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+    let key = fnv1a(source.to_string_lossy().as_bytes());
+    cache_dir.join(format!("{key:016x}.json"))
+}
+
+/// The merged schema report of `files`, reusing each file's cached report while its
+/// content, the profile and the filter are unchanged. Parsed files get their entry
+/// (re)written. `filter` must not need resolution: windows and runs span all files.
+pub fn load_schema_report(
+    files: &[PathBuf],
+    config: &AnalyzerConfig,
+    filter: &LogFilter,
+    filter_expr: Option<&str>,
+    cache_dir: &Path,
+    warnings: &WarningLog,
+) -> Result<(SchemaReport, CacheUsage), Box<dyn Error>> {
+    fs::create_dir_all(cache_dir).map_err(|e| {
+        format!(
+            "Failed to create schema cache directory '{}': {}",
+            cache_dir.display(),
+            e
+        )
+    })?;
+    let settings_hash = settings_hash(config, filter, filter_expr)?;
+
+    let mut merged = SchemaReport::default();
+    let mut usage = CacheUsage::default();
+    for file in files {
+        let content = fs::read(file)
+            .map_err(|e| format!("Failed to read log file '{}': {}", file.display(), e))?;
+        let content_hash = format!("{:016x}", fnv1a(&content));
+        let path = entry_path(cache_dir, file);
+
+        let cached = fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CacheEntry>(&bytes).ok())
+            .filter(|entry| {
+                entry.version == CACHE_VERSION
+                    && entry.content_hash == content_hash
+                    && entry.settings_hash == settings_hash
+            });
+        let report = match cached {
+            Some(entry) => {
+                usage.reused += 1;
+                entry.report
+            }
+            None => {
+                let logs = parse_log_file_reporting(file, config, warnings).map_err(|e| {
+                    format!("Failed to parse log file '{}': {:?}", file.display(), e)
+                })?;
+                let matching: Vec<_> = logs.into_iter().filter(|log| filter.matches(log)).collect();
+                let report = SchemaReport::collect(
+                    &matching,
+                    &config.schema,
+                    config.comparison.max_json_depth,
+                );
+                let entry = CacheEntry {
+                    version: CACHE_VERSION,
+                    source: file.display().to_string(),
+                    content_hash,
+                    settings_hash: settings_hash.clone(),
+                    report,
+                };
+                write_output(&path, serde_json::to_string(&entry)?.as_bytes()).map_err(|e| {
+                    format!(
+                        "Failed to write schema cache entry '{}': {}",
+                        path.display(),
+                        e
+                    )
+                })?;
+                usage.parsed += 1;
+                entry.report
+            }
+        };
+        merged.merge(report);
+    }
+    Ok((merged, usage))
+}
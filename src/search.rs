@@ -1,8 +1,11 @@
 use crate::cli::SearchCountBy;
-use crate::comparator::LogFilter;
+use crate::comparator::{JsonDiffRules, LogFilter, compare_json_with_rules};
 use crate::parser::LogEntry;
+use crate::payload_view::elide_long_arrays;
+use crate::time_buckets::{TimeBucketOptions, TimeHistogram, build_time_histogram};
+use crate::typed_fields::TypedFields;
 use chrono::{SecondsFormat, Utc};
-use serde_json::json;
+use serde_json::{Value, json};
 use std::cmp::Reverse;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Write;
@@ -21,6 +24,23 @@ struct CountGroup {
     count: usize,
 }
 
+#[derive(Debug, Clone)]
+struct GoldenVerdict {
+    idx: usize,
+    /// `None` when the entry has no payload to compare
+    differences: Option<Vec<(String, Value, Value)>>,
+}
+
+impl GoldenVerdict {
+    fn label(&self) -> &'static str {
+        match &self.differences {
+            None => "NO_PAYLOAD",
+            Some(diffs) if diffs.is_empty() => "MATCH",
+            Some(_) => "DIFF",
+        }
+    }
+}
+
 pub fn collect_match_indices(logs: &[LogEntry], filter: &LogFilter) -> Vec<usize> {
     logs.iter()
         .enumerate()
@@ -33,6 +53,7 @@ pub fn format_search_text(
     match_indices: &[usize],
     context: usize,
     show_payloads: bool,
+    max_array_elements: usize,
 ) -> String {
     let mut out = String::new();
     let _ = writeln!(
@@ -80,8 +101,9 @@ pub fn format_search_text(
         );
 
         if show_payloads && let Some(payload) = entry.payload() {
-            let payload_text = serde_json::to_string(payload)
-                .unwrap_or_else(|_| "\"<failed to serialize payload>\"".to_string());
+            let payload_text =
+                serde_json::to_string(&elide_long_arrays(payload, max_array_elements))
+                    .unwrap_or_else(|_| "\"<failed to serialize payload>\"".to_string());
             let _ = writeln!(out, "       payload: {payload_text}");
         }
         if show_payloads && !entry.structured_fields.is_empty() {
@@ -100,13 +122,14 @@ pub fn format_search_json(
     match_indices: &[usize],
     context: usize,
     show_payloads: bool,
+    typed: &TypedFields,
 ) -> String {
     let rows = build_display_rows(logs, match_indices, context);
     let entries: Vec<_> = rows
         .iter()
         .map(|row| {
             let entry = &logs[row.idx];
-            json!({
+            let mut value = json!({
                 "is_match": row.is_match,
                 "source_line_number": entry.source_line_number,
                 "timestamp": entry
@@ -122,8 +145,21 @@ pub fn format_search_json(
                 "raw_logline": entry.raw_logline,
                 "module_path": entry.module_path,
                 "structured_fields": entry.structured_fields,
+                "tags": entry.tags,
                 "payload": if show_payloads { entry.payload().cloned() } else { None },
-            })
+            });
+            if let Some(object) = value.as_object_mut() {
+                if let Some(timestamp_ms) = typed.epoch_millis(entry) {
+                    object.insert("timestamp_ms".to_string(), json!(timestamp_ms));
+                }
+                if let Some(duration_ms) = typed.duration_ms(entry) {
+                    object.insert("duration_ms".to_string(), json!(duration_ms));
+                }
+                if let Some(endpoint) = entry.endpoint() {
+                    object.insert("endpoint".to_string(), json!(endpoint));
+                }
+            }
+            value
         })
         .collect();
 
@@ -143,10 +179,14 @@ pub fn format_search_count_text(
     logs: &[LogEntry],
     match_indices: &[usize],
     count_by: SearchCountBy,
+    buckets: &TimeBucketOptions,
 ) -> String {
     if count_by == SearchCountBy::Matches {
         return format!("{}\n", match_indices.len());
     }
+    if count_by == SearchCountBy::Time {
+        return format_search_histogram_text(logs, match_indices, buckets);
+    }
 
     let groups = build_count_groups(logs, match_indices, count_by);
     let mut out = String::new();
@@ -175,27 +215,177 @@ pub fn format_search_count_json(
     logs: &[LogEntry],
     match_indices: &[usize],
     count_by: SearchCountBy,
+    buckets: &TimeBucketOptions,
 ) -> String {
-    let groups = build_count_groups(logs, match_indices, count_by);
+    let histogram = (count_by == SearchCountBy::Time)
+        .then(|| match_histogram(logs, match_indices, buckets))
+        .flatten();
+    let groups = match &histogram {
+        Some(histogram) => histogram_groups(histogram),
+        None => build_count_groups(logs, match_indices, count_by),
+    };
+    let mut search = json!({
+        "file": file.display().to_string(),
+        "matches": match_indices.len(),
+        "count_by": count_by_label(count_by),
+        "groups": groups
+            .iter()
+            .map(|group| json!({
+                "key": group.key,
+                "count": group.count,
+            }))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(histogram) = &histogram {
+        search["bucket_ms"] = json!(histogram.bucket_ms);
+        search["bucket"] = json!(histogram.width_label());
+    }
+    serde_json::to_string_pretty(&json!({ "search": search })).unwrap_or_else(|_| {
+        "{\"search\":{\"error\":\"failed to serialize search count output\"}}".into()
+    })
+}
+
+pub fn format_search_diff_text(
+    golden_file: &Path,
+    golden: &Value,
+    logs: &[LogEntry],
+    match_indices: &[usize],
+    rules: &JsonDiffRules,
+) -> String {
+    let verdicts = build_golden_verdicts(golden, logs, match_indices, rules);
+    let (matching, differing, missing) = count_verdicts(&verdicts);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "SEARCH diff against {}: {} entr{} ({} match, {} differ, {} without payload)",
+        golden_file.display(),
+        verdicts.len(),
+        if verdicts.len() == 1 { "y" } else { "ies" },
+        matching,
+        differing,
+        missing
+    );
+
+    if verdicts.is_empty() {
+        let _ = writeln!(out, "No matching log entries found.");
+        return out;
+    }
+    out.push('\n');
+
+    for verdict in &verdicts {
+        let entry = &logs[verdict.idx];
+        let _ = writeln!(
+            out,
+            "{:>6}: {:<10} [{}] {} | {}",
+            entry.source_line_number,
+            verdict.label(),
+            entry.level,
+            entry.component,
+            entry.message.replace('\n', "\\n")
+        );
+        for (path, expected, actual) in verdict.differences.iter().flatten() {
+            let _ = writeln!(
+                out,
+                "       {}: {} → {}",
+                if path.is_empty() { "<root>" } else { path },
+                json_inline(expected),
+                json_inline(actual)
+            );
+        }
+    }
+
+    out
+}
+
+pub fn format_search_diff_json(
+    file: &Path,
+    golden_file: &Path,
+    golden: &Value,
+    logs: &[LogEntry],
+    match_indices: &[usize],
+    rules: &JsonDiffRules,
+    raw_limit: Option<usize>,
+) -> String {
+    let verdicts = build_golden_verdicts(golden, logs, match_indices, rules);
+    let (matching, differing, missing) = count_verdicts(&verdicts);
+
+    let entries: Vec<_> = verdicts
+        .iter()
+        .map(|verdict| {
+            let entry = &logs[verdict.idx];
+            let mut value = json!({
+                "source_line_number": entry.source_line_number,
+                "timestamp": entry
+                    .timestamp
+                    .with_timezone(&Utc)
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+                "component": entry.component,
+                "log_key": entry.log_key(),
+                "verdict": verdict.label(),
+                "differences": verdict.differences.iter().flatten().map(|(path, expected, actual)| json!({
+                    "path": path,
+                    "expected": expected,
+                    "actual": actual,
+                })).collect::<Vec<_>>(),
+            });
+            if let Some(limit) = raw_limit {
+                value["raw"] = json!(entry.raw_excerpt(limit));
+            }
+            value
+        })
+        .collect();
+
     serde_json::to_string_pretty(&json!({
-        "search": {
+        "search_diff": {
             "file": file.display().to_string(),
-            "matches": match_indices.len(),
-            "count_by": count_by_label(count_by),
-            "groups": groups
-                .iter()
-                .map(|group| json!({
-                    "key": group.key,
-                    "count": group.count,
-                }))
-                .collect::<Vec<_>>(),
+            "golden": golden_file.display().to_string(),
+            "ignore_paths": rules.ignore_paths,
+            "tolerance": rules.tolerance,
+            "matches": verdicts.len(),
+            "identical": matching,
+            "different": differing,
+            "missing_payload": missing,
+            "entries": entries,
         }
     }))
     .unwrap_or_else(|_| {
-        "{\"search\":{\"error\":\"failed to serialize search count output\"}}".into()
+        "{\"search_diff\":{\"error\":\"failed to serialize search diff output\"}}".into()
     })
 }
 
+fn build_golden_verdicts(
+    golden: &Value,
+    logs: &[LogEntry],
+    match_indices: &[usize],
+    rules: &JsonDiffRules,
+) -> Vec<GoldenVerdict> {
+    match_indices
+        .iter()
+        .map(|&idx| GoldenVerdict {
+            idx,
+            differences: logs[idx]
+                .payload()
+                .map(|payload| compare_json_with_rules(golden, payload, rules)),
+        })
+        .collect()
+}
+
+fn count_verdicts(verdicts: &[GoldenVerdict]) -> (usize, usize, usize) {
+    verdicts.iter().fold(
+        (0, 0, 0),
+        |(matching, differing, missing), verdict| match &verdict.differences {
+            None => (matching, differing, missing + 1),
+            Some(diffs) if diffs.is_empty() => (matching + 1, differing, missing),
+            Some(_) => (matching, differing + 1, missing),
+        },
+    )
+}
+
+fn json_inline(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "<invalid-json>".to_string())
+}
+
 fn build_display_rows(
     logs: &[LogEntry],
     match_indices: &[usize],
@@ -233,6 +423,67 @@ fn build_display_rows(
     rows
 }
 
+fn match_histogram(
+    logs: &[LogEntry],
+    match_indices: &[usize],
+    buckets: &TimeBucketOptions,
+) -> Option<TimeHistogram> {
+    build_time_histogram(
+        match_indices.iter().map(|&idx| logs[idx].timestamp),
+        buckets,
+    )
+}
+
+/// One group per bucket in chronological order, keyed by the bucket start (UTC)
+fn histogram_groups(histogram: &TimeHistogram) -> Vec<CountGroup> {
+    histogram
+        .counts
+        .iter()
+        .enumerate()
+        .map(|(idx, &count)| CountGroup {
+            key: histogram
+                .bucket_start(idx)
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            count,
+        })
+        .collect()
+}
+
+fn format_search_histogram_text(
+    logs: &[LogEntry],
+    match_indices: &[usize],
+    buckets: &TimeBucketOptions,
+) -> String {
+    let histogram = match_histogram(logs, match_indices, buckets);
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "SEARCH count by time ({} entr{}",
+        match_indices.len(),
+        if match_indices.len() == 1 { "y" } else { "ies" }
+    );
+    let Some(histogram) = histogram else {
+        let _ = writeln!(out, ")");
+        return out;
+    };
+    let _ = writeln!(out, ", {} buckets)\n", histogram.width_label());
+
+    let max_count = histogram.counts.iter().copied().max().unwrap_or(1).max(1);
+    for (idx, &count) in histogram.counts.iter().enumerate() {
+        let start = histogram.bucket_start(idx).with_timezone(&Utc);
+        let bar = "█".repeat((count * 40).div_ceil(max_count));
+        let line = format!(
+            "{}  {:>6}  {}",
+            start.format(histogram.time_format()),
+            count,
+            bar
+        );
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    out
+}
+
 fn build_count_groups(
     logs: &[LogEntry],
     match_indices: &[usize],
@@ -246,6 +497,10 @@ fn build_count_groups(
             SearchCountBy::Component => logs[idx].component.clone(),
             SearchCountBy::Level => logs[idx].level.clone(),
             SearchCountBy::Type => logs[idx].log_key(),
+            SearchCountBy::Time => logs[idx]
+                .timestamp
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
             SearchCountBy::Payload => logs[idx]
                 .payload()
                 .and_then(|payload| serde_json::to_string(payload).ok())
@@ -269,5 +524,6 @@ fn count_by_label(count_by: SearchCountBy) -> &'static str {
         SearchCountBy::Level => "level",
         SearchCountBy::Type => "type",
         SearchCountBy::Payload => "payload",
+        SearchCountBy::Time => "time",
     }
 }
@@ -0,0 +1,165 @@
+use crate::config::SessionLevelConfig;
+
+/// A parsed `component_id` session path such as `manager-1/eyes-2/check-3`.
+/// Empty segments are dropped; parallel paths (`"a/b & a/c"`) parse into one path each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionPath<'a> {
+    raw: &'a str,
+    segments: Vec<&'a str>,
+}
+
+impl<'a> SessionPath<'a> {
+    pub fn parse(path: &'a str) -> Self {
+        let raw = path.trim().trim_matches('/');
+        Self {
+            raw,
+            segments: raw
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Distinct non-empty session paths of a `component_id`, in listed order
+    pub fn parse_parallel(component_id: &'a str) -> Vec<Self> {
+        let mut paths: Vec<Self> = Vec::new();
+        for path in component_id.split(" & ").map(Self::parse) {
+            if !path.is_empty() && !paths.iter().any(|seen| seen.raw == path.raw) {
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.raw
+    }
+
+    pub fn segments(&self) -> &[&'a str] {
+        &self.segments
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Innermost segment (the session or operation the entry belongs to)
+    pub fn leaf(&self) -> Option<&'a str> {
+        self.segments.last().copied()
+    }
+
+    /// Path without its innermost segment
+    pub fn parent(&self) -> Option<SessionPath<'a>> {
+        let (head, _) = self.raw.rsplit_once('/')?;
+        let parent = Self::parse(head);
+        (!parent.is_empty()).then_some(parent)
+    }
+
+    /// Whether `other` is nested (at any depth) under this path
+    pub fn is_ancestor_of(&self, other: &SessionPath) -> bool {
+        self.segments.len() < other.segments.len() && other.segments.starts_with(&self.segments)
+    }
+
+    /// Whether the segments of `selector` (e.g. `eyes-2` or `manager-1/eyes-2`) appear
+    /// as a contiguous run of whole segments in this path
+    pub fn contains(&self, selector: &SessionPath) -> bool {
+        !selector.is_empty()
+            && self
+                .segments
+                .windows(selector.segments.len())
+                .any(|window| window == selector.segments.as_slice())
+    }
+
+    /// Index of the configured session level of each segment (`None` for operation segments)
+    pub fn segment_kinds(&self, levels: &[SessionLevelConfig]) -> Vec<Option<usize>> {
+        self.segments
+            .iter()
+            .map(|segment| session_level_index(segment, levels.iter()))
+            .collect()
+    }
+}
+
+/// Session level whose `segment_prefix` is the longest prefix of `segment`
+pub fn session_level_index<'c>(
+    segment: &str,
+    levels: impl IntoIterator<Item = &'c SessionLevelConfig>,
+) -> Option<usize> {
+    let mut best_match: Option<(usize, usize)> = None;
+    for (index, level) in levels.into_iter().enumerate() {
+        let prefix = level.segment_prefix.as_str();
+        if prefix.is_empty() || !segment.starts_with(prefix) {
+            continue;
+        }
+
+        let prefix_len = prefix.len();
+        match best_match {
+            Some((_, best_len)) if best_len >= prefix_len => {}
+            _ => best_match = Some((index, prefix_len)),
+        }
+    }
+
+    best_match.map(|(index, _)| index)
+}
+
+/// Instance suffix of a segment (`"zy9"` in `"close-zy9"`)
+pub fn instance_suffix(segment: &str) -> Option<&str> {
+    segment.rsplit_once('-').map(|(_, suffix)| suffix)
+}
+
+/// Segment without its instance suffix (`"close"` in `"close-zy9"`)
+pub fn segment_base(segment: &str) -> &str {
+    segment
+        .rsplit_once('-')
+        .map(|(base, _)| base)
+        .unwrap_or(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(name: &str, prefix: &str) -> SessionLevelConfig {
+        SessionLevelConfig {
+            name: name.to_string(),
+            segment_prefix: prefix.to_string(),
+            create_command: None,
+            complete_commands: Vec::new(),
+            summary_fields: Vec::new(),
+            display_name: None,
+        }
+    }
+
+    #[test]
+    fn parses_segments_kinds_and_hierarchy() {
+        let path = SessionPath::parse("manager-ufg-3nl/eyes-ufg-zn8//close-zy9/");
+        assert_eq!(
+            path.segments(),
+            ["manager-ufg-3nl", "eyes-ufg-zn8", "close-zy9"]
+        );
+        assert_eq!(path.leaf(), Some("close-zy9"));
+        assert_eq!(
+            path.parent().map(|parent| parent.as_str()),
+            Some("manager-ufg-3nl/eyes-ufg-zn8")
+        );
+        assert_eq!(instance_suffix("close-zy9"), Some("zy9"));
+        assert_eq!(segment_base("close-zy9"), "close");
+
+        let levels = [level("manager", "manager-"), level("eyes", "eyes-")];
+        assert_eq!(path.segment_kinds(&levels), [Some(0), Some(1), None]);
+
+        let parent = SessionPath::parse("manager-ufg-3nl");
+        assert!(parent.is_ancestor_of(&path));
+        assert!(!path.is_ancestor_of(&parent));
+    }
+
+    #[test]
+    fn selector_matches_whole_segments_across_parallel_paths() {
+        let paths = SessionPath::parse_parallel("m-1/eyes-10 & m-1/eyes-2 & m-1/eyes-2");
+        assert_eq!(paths.len(), 2);
+
+        let selector = SessionPath::parse("eyes-1");
+        assert!(!paths.iter().any(|path| path.contains(&selector)));
+        let selector = SessionPath::parse("m-1/eyes-2");
+        assert!(paths.iter().any(|path| path.contains(&selector)));
+    }
+}
@@ -0,0 +1,350 @@
+use crate::comparator::{
+    ChangeType, JsonDiffRules, JsonDifference, compare_json_with_rules, determine_change_type,
+};
+use crate::parser::LogEntry;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// One side of a `show-diff`: the entry and where it came from
+pub struct DiffSide<'a> {
+    pub file: &'a Path,
+    pub entry: &'a LogEntry,
+}
+
+impl DiffSide<'_> {
+    fn label(&self) -> String {
+        format!("{}:{}", self.file.display(), self.entry.source_line_number)
+    }
+
+    fn payload(&self) -> Value {
+        self.entry.payload().cloned().unwrap_or(Value::Null)
+    }
+}
+
+/// Side-by-side difference of exactly two entries
+pub struct EntryDiff<'a> {
+    pub left: DiffSide<'a>,
+    pub right: DiffSide<'a>,
+    pub differences: Vec<JsonDifference>,
+}
+
+impl EntryDiff<'_> {
+    pub fn message_changed(&self) -> bool {
+        self.left.entry.message != self.right.entry.message
+    }
+
+    fn changes_by_path(&self) -> BTreeMap<&str, ChangeType> {
+        self.differences
+            .iter()
+            .map(|diff| (diff.path.as_str(), diff.change_type))
+            .collect()
+    }
+}
+
+pub fn diff_entries<'a>(
+    left: DiffSide<'a>,
+    right: DiffSide<'a>,
+    rules: &JsonDiffRules,
+) -> EntryDiff<'a> {
+    let differences = compare_json_with_rules(&left.payload(), &right.payload(), rules)
+        .into_iter()
+        .map(|(path, value1, value2)| JsonDifference {
+            change_type: determine_change_type(&value1, &value2),
+            path,
+            value1,
+            value2,
+            schema_change: None,
+        })
+        .collect();
+    EntryDiff {
+        left,
+        right,
+        differences,
+    }
+}
+
+fn change_marker(change_type: ChangeType) -> &'static str {
+    match change_type {
+        ChangeType::Added => "+",
+        ChangeType::Removed => "-",
+        ChangeType::Modified => "~",
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() { "(payload)" } else { path }
+}
+
+pub fn format_show_diff_text(diff: &EntryDiff) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "SHOW-DIFF: {} ↔ {}",
+        diff.left.label(),
+        diff.right.label()
+    );
+
+    let _ = writeln!(out, "\nMessage:");
+    if diff.message_changed() {
+        let _ = writeln!(out, "  - {}", diff.left.entry.message);
+        let _ = writeln!(out, "  + {}", diff.right.entry.message);
+    } else {
+        let _ = writeln!(out, "  = {}", diff.left.entry.message);
+    }
+
+    let _ = writeln!(out, "\nPayload differences: {}", diff.differences.len());
+    for difference in &diff.differences {
+        let _ = writeln!(
+            out,
+            "  {} {}: {} → {}",
+            change_marker(difference.change_type),
+            display_path(&difference.path),
+            difference.value1,
+            difference.value2
+        );
+    }
+    out
+}
+
+fn side_json(side: &DiffSide) -> Value {
+    json!({
+        "file": side.file.display().to_string(),
+        "line": side.entry.source_line_number,
+        "timestamp": side.entry.timestamp.to_rfc3339(),
+        "component": side.entry.component,
+        "level": side.entry.level,
+        "message": side.entry.message,
+        "payload": side.entry.payload(),
+    })
+}
+
+pub fn format_show_diff_json(diff: &EntryDiff) -> String {
+    let differences: Vec<Value> = diff
+        .differences
+        .iter()
+        .map(|difference| {
+            json!({
+                "path": difference.path,
+                "change": format!("{:?}", difference.change_type).to_lowercase(),
+                "before": difference.value1,
+                "after": difference.value2,
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&json!({
+        "show_diff": {
+            "left": side_json(&diff.left),
+            "right": side_json(&diff.right),
+            "message_changed": diff.message_changed(),
+            "differences": differences,
+        }
+    }))
+    .unwrap_or_else(|_| "{\"show_diff\":{\"error\":\"failed to serialize entry diff\"}}".into())
+}
+
+pub(crate) const HTML_STYLE: &str = "\
+body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:24px;color:#1f2328}
+h1{font-size:18px}
+.panes{display:grid;grid-template-columns:1fr 1fr;gap:16px}
+.pane{border:1px solid #d0d7de;border-radius:6px;padding:12px;overflow:auto}
+.meta{color:#57606a;font-size:12px;margin-bottom:8px}
+.message{font-family:ui-monospace,Menlo,monospace;white-space:pre-wrap;padding:6px;border-radius:4px;background:#f6f8fa}
+.tree,.tree ul{list-style:none;padding-left:16px;margin:0;font-family:ui-monospace,Menlo,monospace;font-size:13px}
+summary{cursor:pointer}
+.key{color:#0550ae}
+.has-diff>summary{font-weight:600}
+.changed{background:#fff8c5}
+.removed{background:#ffebe9}
+.added{background:#dafbe1}
+table{border-collapse:collapse;margin:12px 0;font-family:ui-monospace,Menlo,monospace;font-size:13px}
+td,th{border:1px solid #d0d7de;padding:4px 8px;text-align:left;vertical-align:top}
+";
+
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[derive(Clone, Copy)]
+enum Pane {
+    Left,
+    Right,
+}
+
+struct TreeMarks<'a> {
+    changes: BTreeMap<&'a str, ChangeType>,
+    ancestors: BTreeSet<String>,
+    pane: Pane,
+}
+
+impl<'a> TreeMarks<'a> {
+    fn new(diff: &'a EntryDiff, pane: Pane) -> Self {
+        let changes = diff.changes_by_path();
+        let mut ancestors = BTreeSet::new();
+        for path in changes.keys() {
+            ancestors.insert(String::new());
+            for (idx, c) in path.char_indices() {
+                if c == '.' || c == '[' {
+                    ancestors.insert(path[..idx].to_string());
+                }
+            }
+        }
+        Self {
+            changes,
+            ancestors,
+            pane,
+        }
+    }
+
+    fn leaf_class(&self, path: &str) -> &'static str {
+        match (self.changes.get(path), self.pane) {
+            (Some(ChangeType::Modified), _) => " class=\"changed\"",
+            (Some(ChangeType::Removed), Pane::Left) => " class=\"removed\"",
+            (Some(ChangeType::Added), Pane::Right) => " class=\"added\"",
+            _ => "",
+        }
+    }
+}
+
+fn child_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+fn render_node(out: &mut String, label: &str, value: &Value, path: &str, marks: &TreeMarks) {
+    let children: Vec<(String, String, &Value)> = match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, child)| (key.clone(), child_path(path, key), child))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(idx, child)| (format!("[{idx}]"), format!("{path}[{idx}]"), child))
+            .collect(),
+        _ => {
+            let _ = write!(
+                out,
+                "<li{}><span class=\"key\">{}</span>: {}</li>",
+                marks.leaf_class(path),
+                escape_html(label),
+                escape_html(&value.to_string())
+            );
+            return;
+        }
+    };
+
+    let summary = match value {
+        Value::Object(_) => format!("{{…}} {} keys", children.len()),
+        _ => format!("[…] {} items", children.len()),
+    };
+    let has_diff = marks.ancestors.contains(path);
+    let _ = write!(
+        out,
+        "<li{}><details{}><summary><span class=\"key\">{}</span> {}</summary><ul>",
+        marks.leaf_class(path),
+        if has_diff {
+            " class=\"has-diff\" open"
+        } else {
+            ""
+        },
+        escape_html(label),
+        summary
+    );
+    for (child_label, child_path, child) in children {
+        render_node(out, &child_label, child, &child_path, marks);
+    }
+    out.push_str("</ul></details></li>");
+}
+
+fn render_pane(out: &mut String, diff: &EntryDiff, pane: Pane) {
+    let (side, message_class) = match pane {
+        Pane::Left => (&diff.left, "removed"),
+        Pane::Right => (&diff.right, "added"),
+    };
+    let entry = side.entry;
+    let _ = write!(
+        out,
+        "<div class=\"pane\"><h2>{}</h2><div class=\"meta\">{} · {} · {} · {}</div>",
+        escape_html(&side.label()),
+        entry.timestamp.to_rfc3339(),
+        escape_html(&entry.component),
+        escape_html(&entry.component_id),
+        escape_html(&entry.level)
+    );
+    let _ = write!(
+        out,
+        "<div class=\"message{}\">{}</div>",
+        if diff.message_changed() {
+            format!(" {message_class}")
+        } else {
+            String::new()
+        },
+        escape_html(&entry.message)
+    );
+    out.push_str("<ul class=\"tree\">");
+    match entry.payload() {
+        Some(payload) => render_node(out, "payload", payload, "", &TreeMarks::new(diff, pane)),
+        None => out.push_str("<li>(no payload)</li>"),
+    }
+    out.push_str("</ul></div>");
+}
+
+/// Standalone HTML page (inline styles, no scripts) with both entries side by side
+pub fn format_show_diff_html(diff: &EntryDiff) -> String {
+    let mut out = String::new();
+    let title = format!("{} ↔ {}", diff.left.label(), diff.right.label());
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\"><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>",
+        escape_html(&title),
+        HTML_STYLE
+    );
+    let _ = write!(
+        out,
+        "<h1>{}</h1><p>{} payload difference(s){}</p>",
+        escape_html(&title),
+        diff.differences.len(),
+        if diff.message_changed() {
+            ", message differs"
+        } else {
+            ""
+        }
+    );
+
+    if !diff.differences.is_empty() {
+        out.push_str("<table><tr><th></th><th>path</th><th>before</th><th>after</th></tr>");
+        for difference in &diff.differences {
+            let _ = write!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                change_marker(difference.change_type),
+                escape_html(display_path(&difference.path)),
+                escape_html(&difference.value1.to_string()),
+                escape_html(&difference.value2.to_string())
+            );
+        }
+        out.push_str("</table>");
+    }
+
+    out.push_str("<div class=\"panes\">");
+    render_pane(&mut out, diff, Pane::Left);
+    render_pane(&mut out, diff, Pane::Right);
+    out.push_str("</div></body></html>\n");
+    out
+}
@@ -0,0 +1,220 @@
+use crate::errors::normalize_message_pattern;
+use crate::parser::LogEntry;
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Weight of message-template similarity in the combined score (payload shape gets the rest)
+pub(crate) const TEMPLATE_WEIGHT: f64 = 0.7;
+
+/// A parsed entry with the input file it came from
+pub struct SourcedEntry<'a> {
+    pub file: &'a PathBuf,
+    pub entry: &'a LogEntry,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimilarMatch {
+    pub file: String,
+    pub line: usize,
+    pub component: String,
+    pub level: String,
+    pub message: String,
+    pub score: f64,
+    pub template_score: f64,
+    pub shape_score: f64,
+}
+
+/// Entry covering `line`: the last entry starting at or before it
+pub fn entry_at_line(logs: &[LogEntry], line: usize) -> Option<&LogEntry> {
+    logs.iter()
+        .filter(|entry| entry.source_line_number <= line)
+        .max_by_key(|entry| entry.source_line_number)
+}
+
+pub(crate) fn template_tokens(message: &str) -> BTreeSet<String> {
+    normalize_message_pattern(message)
+        .split_whitespace()
+        .map(|token| {
+            token
+                .chars()
+                .map(|c| if c.is_ascii_digit() { '#' } else { c })
+                .collect()
+        })
+        .collect()
+}
+
+fn collect_shape_paths(
+    value: &Value,
+    path: &str,
+    max_depth: Option<usize>,
+    out: &mut BTreeSet<String>,
+) {
+    let descend = max_depth != Some(0);
+    let nested_depth = max_depth.map(|max| max.saturating_sub(1));
+    let kind = match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter().filter(|_| descend) {
+                collect_shape_paths(child, &format!("{path}.{key}"), nested_depth, out);
+            }
+            "object"
+        }
+        Value::Array(items) => {
+            for item in items.iter().filter(|_| descend) {
+                collect_shape_paths(item, &format!("{path}[]"), nested_depth, out);
+            }
+            "array"
+        }
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "bool",
+        Value::Null => "null",
+    };
+    out.insert(format!("{path}:{kind}"));
+}
+
+pub(crate) fn shape_paths(entry: &LogEntry) -> Option<BTreeSet<String>> {
+    entry
+        .payload()
+        .map(|payload| payload_shape_paths(payload, None))
+}
+
+/// Typed paths (`.a.b:string`) of a payload, descending at most `max_depth` levels
+pub(crate) fn payload_shape_paths(value: &Value, max_depth: Option<usize>) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    collect_shape_paths(value, "", max_depth, &mut paths);
+    paths
+}
+
+pub(crate) fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        1.0
+    } else {
+        a.intersection(b).count() as f64 / union as f64
+    }
+}
+
+/// Ranks `candidates` by similarity to `reference`: Jaccard similarity of normalized
+/// message-template tokens, blended with Jaccard similarity of payload shape paths
+pub fn find_similar(
+    reference: &LogEntry,
+    candidates: &[SourcedEntry],
+    top: usize,
+) -> Vec<SimilarMatch> {
+    let reference_tokens = template_tokens(&reference.message);
+    let reference_shape = shape_paths(reference);
+
+    let mut matches: Vec<SimilarMatch> = candidates
+        .iter()
+        .filter(|candidate| !std::ptr::eq(candidate.entry, reference))
+        .map(|candidate| {
+            let entry = candidate.entry;
+            let template_score = jaccard(&reference_tokens, &template_tokens(&entry.message));
+            let shape_score = match (&reference_shape, shape_paths(entry)) {
+                (Some(a), Some(b)) => jaccard(a, &b),
+                (None, None) => 1.0,
+                _ => 0.0,
+            };
+            SimilarMatch {
+                file: candidate.file.display().to_string(),
+                line: entry.source_line_number,
+                component: entry.component.clone(),
+                level: entry.level.clone(),
+                message: entry.message.clone(),
+                score: TEMPLATE_WEIGHT * template_score + (1.0 - TEMPLATE_WEIGHT) * shape_score,
+                template_score,
+                shape_score,
+            }
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    if top > 0 {
+        matches.truncate(top);
+    }
+    matches
+}
+
+fn single_line(message: &str, max_chars: usize) -> String {
+    let flat = message.replace('\n', " ");
+    if flat.chars().count() > max_chars {
+        format!("{}...", flat.chars().take(max_chars).collect::<String>())
+    } else {
+        flat
+    }
+}
+
+pub fn format_similar_text(
+    reference_file: &Path,
+    reference: &LogEntry,
+    matches: &[SimilarMatch],
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "SIMILAR to {}:{} [{}] {}",
+        reference_file.display(),
+        reference.source_line_number,
+        reference.level,
+        single_line(&reference.message, 120)
+    );
+
+    if matches.is_empty() {
+        let _ = writeln!(out, "\nNo other entries to compare.");
+        return out;
+    }
+
+    out.push('\n');
+    for item in matches {
+        let _ = writeln!(
+            out,
+            "{:.2}  {}:{}  [{}] {}  {}",
+            item.score,
+            item.file,
+            item.line,
+            item.level,
+            item.component,
+            single_line(&item.message, 100)
+        );
+    }
+    out
+}
+
+pub fn format_similar_json(
+    reference_file: &Path,
+    reference: &LogEntry,
+    matches: &[SimilarMatch],
+) -> String {
+    serde_json::to_string_pretty(&json!({
+        "similar": {
+            "reference": {
+                "file": reference_file.display().to_string(),
+                "line": reference.source_line_number,
+                "component": reference.component,
+                "level": reference.level,
+                "message": reference.message,
+            },
+            "matches": matches
+                .iter()
+                .map(|item| json!({
+                    "file": item.file,
+                    "line": item.line,
+                    "score": item.score,
+                    "template_score": item.template_score,
+                    "shape_score": item.shape_score,
+                    "component": item.component,
+                    "level": item.level,
+                    "message": item.message,
+                }))
+                .collect::<Vec<_>>(),
+        }
+    }))
+    .unwrap_or_else(|_| "{\"similar\":{\"error\":\"failed to serialize similar output\"}}".into())
+}
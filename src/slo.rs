@@ -0,0 +1,307 @@
+use crate::comparator::{LogFilter, glob_matches};
+use crate::errors::{build_error_level_filter, normalize_message_pattern, normalized_severity};
+use crate::parser::LogEntry;
+use crate::units::format_utc_millis;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Clusters listed per violation
+const DOMINANT_CLUSTERS: usize = 3;
+
+/// Sliding-window error-rate objective
+#[derive(Debug, Clone)]
+pub struct SloOptions {
+    /// Highest acceptable share (percent) of ERROR entries within one window
+    pub max_error_rate_pct: f64,
+    pub window_ms: u64,
+    pub step_ms: u64,
+    /// Windows with fewer entries are not evaluated
+    pub min_entries: usize,
+    /// Count WARN entries as errors
+    pub include_warn: bool,
+    /// Glob patterns of normalized messages that do not count as errors
+    pub suppressed_patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SloReport {
+    pub max_error_rate_pct: f64,
+    pub window_ms: u64,
+    pub step_ms: u64,
+    pub min_entries: usize,
+    pub include_warn: bool,
+    pub total_entries: usize,
+    pub error_count: usize,
+    pub overall_error_rate_pct: f64,
+    pub windows_evaluated: usize,
+    pub violating_windows: usize,
+    pub violations: Vec<SloViolation>,
+}
+
+/// A run of overlapping windows that all exceeded the objective
+#[derive(Debug, Clone, Serialize)]
+pub struct SloViolation {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub windows: usize,
+    pub error_count: usize,
+    pub peak_window_start: DateTime<Local>,
+    pub peak_error_count: usize,
+    pub peak_entry_count: usize,
+    pub peak_error_rate_pct: f64,
+    pub dominant_clusters: Vec<SloCluster>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SloCluster {
+    pub severity: String,
+    pub pattern: String,
+    pub count: usize,
+}
+
+struct WindowStats {
+    start_ms: i64,
+    entries: usize,
+    errors: usize,
+}
+
+impl WindowStats {
+    fn rate_pct(&self) -> f64 {
+        self.errors as f64 * 100.0 / self.entries.max(1) as f64
+    }
+}
+
+pub fn evaluate_slo(logs: &[LogEntry], filter: &LogFilter, options: &SloOptions) -> SloReport {
+    let level_filter = build_error_level_filter(options.include_warn);
+    let mut clusters: Vec<(String, String)> = Vec::new();
+    let mut cluster_index: HashMap<(String, String), usize> = HashMap::new();
+
+    // (timestamp, cluster of the entry when it counts as an error)
+    let mut points: Vec<(DateTime<Local>, Option<usize>)> = logs
+        .iter()
+        .filter(|entry| filter.matches(entry))
+        .map(|entry| {
+            if !level_filter.matches(entry) {
+                return (entry.timestamp, None);
+            }
+            let pattern = normalize_message_pattern(&entry.message);
+            if options
+                .suppressed_patterns
+                .iter()
+                .any(|suppressed| glob_matches(suppressed, &pattern))
+            {
+                return (entry.timestamp, None);
+            }
+            let key = (normalized_severity(&entry.level), pattern);
+            let idx = *cluster_index.entry(key.clone()).or_insert_with(|| {
+                clusters.push(key);
+                clusters.len() - 1
+            });
+            (entry.timestamp, Some(idx))
+        })
+        .collect();
+    points.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let error_count = points
+        .iter()
+        .filter(|(_, cluster)| cluster.is_some())
+        .count();
+    let mut report = SloReport {
+        max_error_rate_pct: options.max_error_rate_pct,
+        window_ms: options.window_ms,
+        step_ms: options.step_ms,
+        min_entries: options.min_entries,
+        include_warn: options.include_warn,
+        total_entries: points.len(),
+        error_count,
+        overall_error_rate_pct: error_count as f64 * 100.0 / points.len().max(1) as f64,
+        windows_evaluated: 0,
+        violating_windows: 0,
+        violations: Vec::new(),
+    };
+
+    let millis: Vec<i64> = points
+        .iter()
+        .map(|(timestamp, _)| timestamp.timestamp_millis())
+        .collect();
+    let (Some(&first), Some(&last)) = (millis.first(), millis.last()) else {
+        return report;
+    };
+
+    let mut error_prefix = Vec::with_capacity(points.len() + 1);
+    error_prefix.push(0usize);
+    for (_, cluster) in &points {
+        error_prefix
+            .push(error_prefix.last().copied().unwrap_or(0) + usize::from(cluster.is_some()));
+    }
+
+    let window = options.window_ms.max(1) as i64;
+    let step = options.step_ms.max(1) as i64;
+    let mut runs: Vec<(i64, i64, usize, WindowStats)> = Vec::new();
+    let (mut lo, mut hi) = (0usize, 0usize);
+    let mut start = first;
+    loop {
+        let end = start + window;
+        while lo < millis.len() && millis[lo] < start {
+            lo += 1;
+        }
+        while hi < millis.len() && millis[hi] < end {
+            hi += 1;
+        }
+
+        let stats = WindowStats {
+            start_ms: start,
+            entries: hi - lo,
+            errors: error_prefix[hi] - error_prefix[lo],
+        };
+        if stats.entries >= options.min_entries.max(1) {
+            report.windows_evaluated += 1;
+            if stats.rate_pct() > options.max_error_rate_pct {
+                report.violating_windows += 1;
+                match runs.last_mut() {
+                    Some((_, run_end, windows, peak)) if start <= *run_end => {
+                        *run_end = end;
+                        *windows += 1;
+                        if stats.rate_pct() > peak.rate_pct() {
+                            *peak = stats;
+                        }
+                    }
+                    _ => runs.push((start, end, 1, stats)),
+                }
+            }
+        }
+
+        if end > last {
+            break;
+        }
+        start += step;
+    }
+
+    report.violations = runs
+        .into_iter()
+        .map(|(run_start, run_end, windows, peak)| {
+            let from = millis.partition_point(|&ms| ms < run_start);
+            let to = millis.partition_point(|&ms| ms < run_end);
+            let mut counts: HashMap<usize, usize> = HashMap::new();
+            for (_, cluster) in &points[from..to] {
+                if let Some(idx) = cluster {
+                    *counts.entry(*idx).or_default() += 1;
+                }
+            }
+            let mut dominant: Vec<(usize, usize)> = counts.into_iter().collect();
+            dominant.sort_by(|a, b| {
+                b.1.cmp(&a.1)
+                    .then_with(|| clusters[a.0].cmp(&clusters[b.0]))
+            });
+
+            SloViolation {
+                start: points[from].0,
+                end: points[to - 1].0,
+                windows,
+                error_count: error_prefix[to] - error_prefix[from],
+                peak_window_start: local_from_millis(peak.start_ms),
+                peak_error_count: peak.errors,
+                peak_entry_count: peak.entries,
+                peak_error_rate_pct: peak.rate_pct(),
+                dominant_clusters: dominant
+                    .into_iter()
+                    .take(DOMINANT_CLUSTERS)
+                    .map(|(idx, count)| SloCluster {
+                        severity: clusters[idx].0.clone(),
+                        pattern: clusters[idx].1.clone(),
+                        count,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    report
+}
+
+fn local_from_millis(millis: i64) -> DateTime<Local> {
+    DateTime::from_timestamp_millis(millis)
+        .unwrap_or_default()
+        .with_timezone(&Local)
+}
+
+/// Compact duration label (`500ms`, `5s`, `1m`, `1.5m`, `2h`)
+fn format_window(ms: u64) -> String {
+    let (value, unit) = match ms {
+        ms if ms >= 3_600_000 && ms % 60_000 == 0 => (ms as f64 / 3_600_000.0, "h"),
+        ms if ms >= 60_000 => (ms as f64 / 60_000.0, "m"),
+        ms if ms >= 1_000 => (ms as f64 / 1_000.0, "s"),
+        ms => return format!("{ms}ms"),
+    };
+    format!("{}{unit}", (value * 10.0).round() / 10.0)
+}
+
+pub fn format_slo_text(report: &SloReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "ERROR-RATE SLO: max {:.1}% {} per {} window (step {}, min {} entries)",
+        report.max_error_rate_pct,
+        if report.include_warn {
+            "ERROR/WARN"
+        } else {
+            "ERROR"
+        },
+        format_window(report.window_ms),
+        format_window(report.step_ms),
+        report.min_entries
+    );
+    let _ = writeln!(
+        out,
+        "Overall error rate: {:.1}% ({}/{} entries)",
+        report.overall_error_rate_pct, report.error_count, report.total_entries
+    );
+    let _ = writeln!(
+        out,
+        "Windows evaluated: {}, violating: {}",
+        report.windows_evaluated, report.violating_windows
+    );
+
+    if report.violations.is_empty() {
+        let _ = writeln!(out, "\nNo window exceeded the SLO.");
+        return out;
+    }
+
+    let _ = writeln!(out, "\nViolations: {}", report.violations.len());
+    for (idx, violation) in report.violations.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            " #{:<2} {} → {}  ({} window{}, {} errors)",
+            idx + 1,
+            format_utc_millis(violation.start),
+            format_utc_millis(violation.end),
+            violation.windows,
+            if violation.windows == 1 { "" } else { "s" },
+            violation.error_count
+        );
+        let _ = writeln!(
+            out,
+            "     Peak: {:.1}% ({}/{}) in window starting {}",
+            violation.peak_error_rate_pct,
+            violation.peak_error_count,
+            violation.peak_entry_count,
+            format_utc_millis(violation.peak_window_start)
+        );
+        for cluster in &violation.dominant_clusters {
+            let _ = writeln!(
+                out,
+                "     ×{} [{}] {}",
+                cluster.count, cluster.severity, cluster.pattern
+            );
+        }
+    }
+    out
+}
+
+pub fn format_slo_json(report: &SloReport) -> String {
+    serde_json::to_string_pretty(&json!({ "slo": report }))
+        .unwrap_or_else(|_| "{\"slo\":{\"error\":\"failed to serialize SLO report\"}}".into())
+}
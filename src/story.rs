@@ -0,0 +1,430 @@
+use crate::analysis_context::AnalysisContext;
+use crate::comparator::LogFilter;
+use crate::config::{AnalyzerConfig, analyze_sessions};
+use crate::errors::{build_error_level_filter, normalize_message_pattern, normalized_severity};
+use crate::parser::{LogEntry, LogEntryKind};
+use crate::perf_analyzer::analyze_performance_with_config;
+use crate::session_path::SessionPath;
+use crate::trace::TraceSelector;
+use crate::units::format_utc_millis;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Requests listed in a story; the slowest ones are kept when a session has more
+const STORY_REQUESTS: usize = 15;
+/// Settings or result fields listed per command
+const STORY_FIELDS: usize = 12;
+const FIELD_VALUE_CHARS: usize = 80;
+
+/// Chronological narrative of a single session
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionStory {
+    pub session: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    pub first_seen: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
+    pub duration_ms: i64,
+    pub entry_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<StoryCommand>,
+    pub requests: Vec<StoryRequest>,
+    /// Requests left out because the session has more than `STORY_REQUESTS`
+    pub omitted_requests: usize,
+    pub problems: Vec<StoryProblem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed: Option<StoryCommand>,
+    /// Completion commands the session level expects, for sessions that never completed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub expected_completion: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoryCommand {
+    pub command: String,
+    pub timestamp: DateTime<Local>,
+    pub line: usize,
+    /// Flattened settings (creation) or result (completion)
+    pub fields: Vec<StoryField>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoryField {
+    pub path: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoryRequest {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub start: DateTime<Local>,
+    /// `None` when no response was logged
+    pub duration_ms: Option<i64>,
+    pub status: String,
+}
+
+/// A WARN/ERROR message pattern and where it first showed up
+#[derive(Debug, Clone, Serialize)]
+pub struct StoryProblem {
+    pub severity: String,
+    pub message: String,
+    pub first_seen: DateTime<Local>,
+    pub line: usize,
+    pub count: usize,
+}
+
+/// Build the story of the session selected by `session` (a segment such as `eyes-1`
+/// or a path such as `manager-1/eyes-1`). Returns `None` when no entry belongs to it.
+pub fn build_session_story(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    config: &AnalyzerConfig,
+    session: &str,
+) -> Option<SessionStory> {
+    build_session_story_in_context(&AnalysisContext::new(logs, config), filter, session)
+}
+
+/// Like [`build_session_story`], reading entries and config from an existing context.
+/// Lifecycle comes from the selected session's own entries rather than the context's
+/// shared hierarchy, so a segment reused under another parent cannot leak into it
+pub fn build_session_story_in_context(
+    context: &AnalysisContext,
+    filter: &LogFilter,
+    session: &str,
+) -> Option<SessionStory> {
+    let (logs, config) = (context.logs(), context.config());
+    let selector = TraceSelector::Session(session.to_string());
+    let mut entries: Vec<LogEntry> = logs
+        .iter()
+        .filter(|entry| filter.matches(entry) && selector.matches(entry))
+        .cloned()
+        .collect();
+    entries.sort_by_key(|entry| (entry.timestamp, entry.source_line_number));
+    let (first, last) = (entries.first()?.timestamp, entries.last()?.timestamp);
+
+    let insights = analyze_sessions(&entries, config);
+    let session_id = SessionPath::parse(session).leaf().unwrap_or(session);
+    let level = insights
+        .levels
+        .iter()
+        .find(|level| level.sessions.contains_key(session_id));
+    let info = level.and_then(|level| level.sessions.get(session_id));
+
+    let created = info
+        .and_then(|info| info.created_via.as_deref())
+        .and_then(|command| {
+            let entry = entries
+                .iter()
+                .find(|entry| command_name(entry) == Some(command))?;
+            let fields = match info {
+                Some(info) if !info.summary_fields.is_empty() => info
+                    .summary_fields
+                    .iter()
+                    .map(|(key, value)| StoryField {
+                        path: key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+                _ => entry.payload().map(flatten_fields).unwrap_or_default(),
+            };
+            Some(story_command(command, entry, fields))
+        });
+
+    let completed = info
+        .and_then(|info| info.completed_via.as_deref())
+        .and_then(|command| {
+            let position = entries
+                .iter()
+                .rposition(|entry| command_name(entry) == Some(command))?;
+            let quoted = format!("\"{command}\"");
+            let result = entries[position + 1..]
+                .iter()
+                .rev()
+                .find(|entry| entry.message.contains(&quoted) && entry.payload().is_some())
+                .and_then(LogEntry::payload)
+                .map(flatten_fields)
+                .unwrap_or_default();
+            Some(story_command(command, &entries[position], result))
+        });
+
+    let perf =
+        analyze_performance_with_config(&entries, &LogFilter::new(), Some("Request"), config);
+    let mut requests: Vec<StoryRequest> = perf
+        .operations
+        .iter()
+        .map(|op| StoryRequest {
+            name: op.name.clone(),
+            request_id: op.correlation_id.clone(),
+            start: op.start_time,
+            duration_ms: Some(op.duration_ms),
+            status: op.status.clone().unwrap_or_else(|| "completed".to_string()),
+        })
+        .chain(perf.orphans.iter().map(|orphan| StoryRequest {
+            name: orphan.name.clone(),
+            request_id: orphan.correlation_id.clone(),
+            start: orphan.start_time,
+            duration_ms: None,
+            status: "no response".to_string(),
+        }))
+        .collect();
+    let omitted_requests = requests.len().saturating_sub(STORY_REQUESTS);
+    if omitted_requests > 0 {
+        // Pending requests first, then the slowest completed ones
+        requests.sort_by_key(|request| std::cmp::Reverse(request.duration_ms.unwrap_or(i64::MAX)));
+        requests.truncate(STORY_REQUESTS);
+    }
+    requests.sort_by_key(|request| request.start);
+
+    let mut problems: Vec<StoryProblem> = Vec::new();
+    let mut problem_index: HashMap<(String, String), usize> = HashMap::new();
+    let problem_filter = build_error_level_filter(true);
+    for entry in entries.iter().filter(|entry| problem_filter.matches(entry)) {
+        let key = (
+            normalized_severity(&entry.level),
+            normalize_message_pattern(&entry.message),
+        );
+        match problem_index.get(&key) {
+            Some(&idx) => problems[idx].count += 1,
+            None => {
+                problem_index.insert(key.clone(), problems.len());
+                problems.push(StoryProblem {
+                    severity: key.0,
+                    message: entry.message.replace('\n', " "),
+                    first_seen: entry.timestamp,
+                    line: entry.source_line_number,
+                    count: 1,
+                });
+            }
+        }
+    }
+
+    let expected_completion = match (level, &completed) {
+        (Some(level), None) => level.config.complete_commands.clone(),
+        _ => Vec::new(),
+    };
+
+    Some(SessionStory {
+        session: session.to_string(),
+        display_name: level
+            .zip(info)
+            .and_then(|(level, info)| level.config.render_display_name(info)),
+        level: level.map(|level| level.config.name.clone()),
+        first_seen: first,
+        last_seen: last,
+        duration_ms: last.signed_duration_since(first).num_milliseconds(),
+        entry_count: entries.len(),
+        created,
+        requests,
+        omitted_requests,
+        problems,
+        completed,
+        expected_completion,
+    })
+}
+
+fn command_name(entry: &LogEntry) -> Option<&str> {
+    match &entry.kind {
+        LogEntryKind::Command { command, .. } => Some(command),
+        _ => None,
+    }
+}
+
+fn story_command(command: &str, entry: &LogEntry, fields: Vec<StoryField>) -> StoryCommand {
+    StoryCommand {
+        command: command.to_string(),
+        timestamp: entry.timestamp,
+        line: entry.source_line_number,
+        fields,
+    }
+}
+
+/// Scalar leaves of a payload as `path → value`, capped at `STORY_FIELDS`.
+/// A single-element array (a common result wrapper) is unwrapped.
+fn flatten_fields(value: &Value) -> Vec<StoryField> {
+    fn walk(value: &Value, path: String, out: &mut Vec<StoryField>) {
+        if out.len() >= STORY_FIELDS {
+            return;
+        }
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let child_path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    walk(child, child_path, out);
+                }
+            }
+            Value::Array(items) => {
+                for (idx, child) in items.iter().enumerate() {
+                    walk(child, format!("{path}[{idx}]"), out);
+                }
+            }
+            scalar => out.push(StoryField {
+                path,
+                value: scalar.clone(),
+            }),
+        }
+    }
+
+    let root = match value {
+        Value::Array(items) if items.len() == 1 => &items[0],
+        other => other,
+    };
+    let mut fields = Vec::new();
+    walk(root, String::new(), &mut fields);
+    fields
+}
+
+fn format_value(value: &Value) -> String {
+    let rendered = value.to_string();
+    if rendered.chars().count() <= FIELD_VALUE_CHARS {
+        return rendered;
+    }
+    let cut: String = rendered.chars().take(FIELD_VALUE_CHARS).collect();
+    format!("{cut}…")
+}
+
+pub fn format_story_text(story: &SessionStory) -> String {
+    let mut out = String::new();
+    let offset = |ts: DateTime<Local>| {
+        ts.signed_duration_since(story.first_seen)
+            .num_milliseconds()
+    };
+
+    let _ = write!(out, "SESSION STORY: {}", story.session);
+    if let Some(level) = &story.level {
+        let _ = write!(out, " ({level})");
+    }
+    if let Some(name) = &story.display_name {
+        let _ = write!(out, " — {name}");
+    }
+    let _ = writeln!(
+        out,
+        "\n{} → {} ({} ms, {} entries)",
+        format_utc_millis(story.first_seen),
+        format_utc_millis(story.last_seen),
+        story.duration_ms,
+        story.entry_count
+    );
+
+    match &story.created {
+        Some(created) => {
+            let _ = writeln!(
+                out,
+                "\nCreated via {} at +{}ms (line {})",
+                created.command,
+                offset(created.timestamp),
+                created.line
+            );
+            for field in &created.fields {
+                let _ = writeln!(out, "  {}: {}", field.path, format_value(&field.value));
+            }
+        }
+        None => {
+            let _ = writeln!(out, "\nNo creation command seen.");
+        }
+    }
+
+    if !story.requests.is_empty() {
+        let pending = story
+            .requests
+            .iter()
+            .filter(|request| request.duration_ms.is_none())
+            .count();
+        let _ = write!(
+            out,
+            "\nRequests ({} completed, {} without response",
+            story.requests.len() - pending,
+            pending
+        );
+        if story.omitted_requests > 0 {
+            let _ = write!(out, "; {} faster ones omitted", story.omitted_requests);
+        }
+        let _ = writeln!(out, "):");
+        for request in &story.requests {
+            let duration = request
+                .duration_ms
+                .map(|ms| format!("{ms} ms"))
+                .unwrap_or_else(|| "—".to_string());
+            let _ = writeln!(
+                out,
+                "  +{}ms  {}{}  {}  {}",
+                offset(request.start),
+                request.name,
+                request
+                    .request_id
+                    .as_deref()
+                    .map(|id| format!(" [{id}]"))
+                    .unwrap_or_default(),
+                duration,
+                request.status
+            );
+        }
+    }
+
+    if story.problems.is_empty() {
+        let _ = writeln!(out, "\nNo warnings or errors.");
+    } else {
+        let _ = writeln!(out, "\nWarnings and errors:");
+        for problem in &story.problems {
+            let repeat = if problem.count > 1 {
+                format!(" ×{}", problem.count)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                out,
+                "  +{}ms  [{}] {}{} (line {})",
+                offset(problem.first_seen),
+                problem.severity,
+                problem.message,
+                repeat,
+                problem.line
+            );
+        }
+    }
+
+    match &story.completed {
+        Some(completed) => {
+            let _ = writeln!(
+                out,
+                "\nCompleted via {} at +{}ms (line {})",
+                completed.command,
+                offset(completed.timestamp),
+                completed.line
+            );
+            if !completed.fields.is_empty() {
+                let _ = writeln!(out, "  Result:");
+                for field in &completed.fields {
+                    let _ = writeln!(out, "    {}: {}", field.path, format_value(&field.value));
+                }
+            }
+        }
+        None if story.expected_completion.is_empty() => {
+            let _ = writeln!(out, "\nNo completion command seen.");
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "\nNever completed: none of {} was called.",
+                story.expected_completion.join(", ")
+            );
+        }
+    }
+    out
+}
+
+pub fn format_story_json(story: &SessionStory) -> String {
+    serde_json::to_string_pretty(&json!({ "session_story": story })).unwrap_or_else(|_| {
+        "{\"session_story\":{\"error\":\"failed to serialize session story\"}}".into()
+    })
+}
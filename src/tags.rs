@@ -0,0 +1,166 @@
+use crate::comparator::{LogFilter, create_styled_table};
+use crate::config::TagRules;
+use crate::filter::{FilterExpression, to_log_filter};
+use crate::parser::LogEntry;
+use crate::units::format_duration_ms;
+use chrono::{DateTime, Local};
+use comfy_table::{Cell, CellAlignment};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// `[tags]` rules compiled to filters, for tagging entries one at a time
+#[derive(Clone, Default)]
+pub struct Tagger {
+    compiled: Vec<(String, LogFilter)>,
+}
+
+impl Tagger {
+    /// Rules that do not parse are skipped (profiles loaded from TOML have already been validated)
+    pub fn new(rules: &TagRules) -> Self {
+        let compiled = rules
+            .rules
+            .iter()
+            .filter_map(|(tag, expression)| {
+                let expr = FilterExpression::parse(expression).ok()?;
+                Some((tag.clone(), to_log_filter(&expr)))
+            })
+            .collect();
+        Self { compiled }
+    }
+
+    /// Records on the entry the names of the rules it matches
+    pub fn tag(&self, entry: &mut LogEntry) {
+        if self.compiled.is_empty() {
+            return;
+        }
+        entry.tags = self
+            .compiled
+            .iter()
+            .filter(|(_, filter)| filter.matches(entry))
+            .map(|(tag, _)| tag.clone())
+            .collect();
+    }
+}
+
+/// Records on each entry the names of the `[tags]` rules it matches
+pub fn tag_entries(logs: &mut [LogEntry], rules: &TagRules) {
+    if rules.is_empty() {
+        return;
+    }
+    let tagger = Tagger::new(rules);
+    for entry in logs {
+        tagger.tag(entry);
+    }
+}
+
+/// Entry counts and time span of one tag
+#[derive(Debug, Clone, Serialize)]
+pub struct TagRollup {
+    pub tag: String,
+    pub entries: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub first_seen: Option<DateTime<Local>>,
+    pub last_seen: Option<DateTime<Local>>,
+}
+
+impl TagRollup {
+    /// Time between the first and last tagged entry
+    pub fn span_ms(&self) -> i64 {
+        match (self.first_seen, self.last_seen) {
+            (Some(first), Some(last)) => (last - first).num_milliseconds(),
+            _ => 0,
+        }
+    }
+}
+
+/// One rollup per declared tag (including tags no entry matched), sorted by tag name
+pub fn build_tag_rollups(logs: &[LogEntry], rules: &TagRules) -> Vec<TagRollup> {
+    rules
+        .rules
+        .keys()
+        .map(|tag| {
+            let mut rollup = TagRollup {
+                tag: tag.clone(),
+                entries: 0,
+                errors: 0,
+                warnings: 0,
+                first_seen: None,
+                last_seen: None,
+            };
+            for entry in logs.iter().filter(|entry| entry.has_tag(tag)) {
+                rollup.entries += 1;
+                if entry.level.eq_ignore_ascii_case("ERROR") {
+                    rollup.errors += 1;
+                } else if entry.level.to_ascii_uppercase().starts_with("WARN") {
+                    rollup.warnings += 1;
+                }
+                rollup.first_seen = Some(
+                    rollup
+                        .first_seen
+                        .map_or(entry.timestamp, |first| first.min(entry.timestamp)),
+                );
+                rollup.last_seen = Some(
+                    rollup
+                        .last_seen
+                        .map_or(entry.timestamp, |last| last.max(entry.timestamp)),
+                );
+            }
+            rollup
+        })
+        .collect()
+}
+
+/// Tag table for `info`
+pub fn format_tag_rollups_text(rollups: &[TagRollup]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "\nTAGS:");
+    let mut table = create_styled_table(&["Tag", "Entries", "Errors", "Warnings", "Span"]);
+    for rollup in rollups {
+        table.add_row(vec![
+            Cell::new(&rollup.tag),
+            Cell::new(rollup.entries).set_alignment(CellAlignment::Right),
+            Cell::new(rollup.errors).set_alignment(CellAlignment::Right),
+            Cell::new(rollup.warnings).set_alignment(CellAlignment::Right),
+            Cell::new(format_duration_ms(rollup.span_ms())).set_alignment(CellAlignment::Right),
+        ]);
+    }
+    let _ = writeln!(out, "{table}");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_entries_and_rolls_them_up() {
+        let rules: TagRules = toml::from_str(
+            r#"
+            network = "c:socket"
+            failures = "l:ERROR"
+            "#,
+        )
+        .expect("tag rules parse");
+        let mut logs: Vec<LogEntry> = [
+            "socket | 2026-01-01T00:00:00.000Z [INFO ] connected",
+            "socket | 2026-01-01T00:00:05.000Z [ERROR] disconnected",
+            "core | 2026-01-01T00:00:06.000Z [WARN ] retrying",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, line)| crate::parser::parse_log_entry(line, i + 1).expect("line parses"))
+        .collect();
+
+        tag_entries(&mut logs, &rules);
+        assert_eq!(logs[1].tags, ["failures", "network"]);
+        assert!(logs[2].tags.is_empty());
+
+        let rollups = build_tag_rollups(&logs, &rules);
+        let network = rollups.iter().find(|r| r.tag == "network").unwrap();
+        assert_eq!(
+            (network.entries, network.errors, network.span_ms()),
+            (2, 1, 5_000)
+        );
+    }
+}
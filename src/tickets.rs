@@ -0,0 +1,166 @@
+use crate::config::AnalyzerConfig;
+use crate::errors::{
+    ErrorAnalysisReport, ErrorClusterReport, ErrorsOptions, displayed_cluster_count,
+};
+use chrono::{SecondsFormat, Utc};
+use std::collections::BTreeMap;
+
+/// Issue template used when the profile does not set `[errors] ticket_template`
+pub const DEFAULT_TICKET_TEMPLATE: &str = "\
+## {{title}}
+
+**Occurrences:** {{count}} ({{severity}})
+**First seen:** {{first_seen}}
+**Last seen:** {{last_seen}}
+**Components:** {{components}}
+**Affected sessions:** {{session_count}}
+
+### Sample
+```
+{{sample}}
+```
+
+### Affected sessions
+{{sessions}}
+
+### Reproduce
+```
+log-analyzer errors <logs> -f '{{filter}}'
+```
+";
+
+const TICKET_SEPARATOR: &str = "\n---\n\n";
+const MAX_TITLE_CHARS: usize = 100;
+const MAX_LISTED_SESSIONS: usize = 10;
+
+/// Loads the profile's ticket template file, or the built-in default
+pub fn load_ticket_template(config: &AnalyzerConfig) -> Result<String, Box<dyn std::error::Error>> {
+    match &config.errors.ticket_template {
+        Some(path) => std::fs::read_to_string(path).map_err(|e| {
+            format!("Failed to read ticket template '{}': {}", path.display(), e).into()
+        }),
+        None => Ok(DEFAULT_TICKET_TEMPLATE.to_string()),
+    }
+}
+
+fn ticket_title(cluster: &ErrorClusterReport) -> String {
+    let pattern = if cluster.pattern.chars().count() > MAX_TITLE_CHARS {
+        format!(
+            "{}...",
+            cluster
+                .pattern
+                .chars()
+                .take(MAX_TITLE_CHARS)
+                .collect::<String>()
+        )
+    } else {
+        cluster.pattern.clone()
+    };
+    format!("[{}] {}", cluster.severity, pattern)
+}
+
+fn session_lines(cluster: &ErrorClusterReport) -> String {
+    if cluster.affected_sessions.is_empty() {
+        return "- none recorded".to_string();
+    }
+
+    let mut lines: Vec<String> = cluster
+        .affected_sessions
+        .iter()
+        .take(MAX_LISTED_SESSIONS)
+        .map(|session| {
+            format!(
+                "- {} ({} errors, {})",
+                session
+                    .display_name
+                    .as_deref()
+                    .unwrap_or(&session.session_path),
+                session.error_count,
+                session.outcome.as_label()
+            )
+        })
+        .collect();
+    let hidden = cluster
+        .affected_sessions
+        .len()
+        .saturating_sub(MAX_LISTED_SESSIONS);
+    if hidden > 0 {
+        lines.push(format!("- ... and {hidden} more"));
+    }
+    lines.join("\n")
+}
+
+fn timestamp(ts: chrono::DateTime<chrono::Local>) -> String {
+    ts.with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Replaces `{{name}}` placeholders; unknown placeholders are left as written
+pub fn render_template(template: &str, values: &BTreeMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                match values.get(name) {
+                    Some(value) => out.push_str(value),
+                    None => out.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+pub fn render_ticket(template: &str, cluster: &ErrorClusterReport, rank: usize) -> String {
+    let values = BTreeMap::from([
+        ("rank", rank.to_string()),
+        ("title", ticket_title(cluster)),
+        ("severity", cluster.severity.clone()),
+        ("pattern", cluster.pattern.clone()),
+        ("count", cluster.count.to_string()),
+        ("first_seen", timestamp(cluster.first_timestamp)),
+        ("last_seen", timestamp(cluster.last_timestamp)),
+        ("components", cluster.components.join(", ")),
+        ("sample", cluster.sample_message.clone()),
+        ("session_count", cluster.affected_sessions_count.to_string()),
+        ("sessions", session_lines(cluster)),
+        ("filter", cluster.suggested_filter.clone()),
+    ]);
+    render_template(template, &values)
+}
+
+/// Renders the displayed (top-N) clusters as ready-to-paste issue bodies
+pub fn format_errors_tickets(
+    report: &ErrorAnalysisReport,
+    options: &ErrorsOptions,
+    template: &str,
+) -> String {
+    if report.clusters.is_empty() {
+        return "No error clusters found.\n".to_string();
+    }
+
+    let tickets: Vec<String> = report
+        .clusters
+        .iter()
+        .take(displayed_cluster_count(report, options))
+        .enumerate()
+        .map(|(idx, cluster)| {
+            let mut ticket = render_ticket(template, cluster, idx + 1);
+            if !ticket.ends_with('\n') {
+                ticket.push('\n');
+            }
+            ticket
+        })
+        .collect();
+    tickets.join(TICKET_SEPARATOR)
+}
@@ -0,0 +1,206 @@
+use chrono::{DateTime, Local, TimeZone, Utc};
+
+/// Finest supported bucket width
+pub const MIN_BUCKET_MS: u64 = 100;
+/// Default cap on the number of buckets in a histogram
+pub const DEFAULT_MAX_BUCKETS: usize = 60;
+
+/// Widths tried, finest first, when picking a bucket size automatically
+const NICE_BUCKET_MS: [u64; 20] = [
+    100, 200, 500, 1_000, 2_000, 5_000, 10_000, 15_000, 30_000, 60_000, 120_000, 300_000, 600_000,
+    900_000, 1_800_000, 3_600_000, 7_200_000, 21_600_000, 43_200_000, 86_400_000,
+];
+
+/// How timestamps are grouped into time buckets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeBucketOptions {
+    /// Requested bucket width; `None` picks the finest width that fits `max_buckets`
+    pub bucket_ms: Option<u64>,
+    /// Upper bound on the number of buckets; a requested width is widened to respect it
+    pub max_buckets: usize,
+}
+
+impl Default for TimeBucketOptions {
+    fn default() -> Self {
+        Self {
+            bucket_ms: None,
+            max_buckets: DEFAULT_MAX_BUCKETS,
+        }
+    }
+}
+
+impl TimeBucketOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bucket_ms(mut self, bucket_ms: Option<u64>) -> Self {
+        self.bucket_ms = bucket_ms;
+        self
+    }
+
+    pub fn max_buckets(mut self, max_buckets: usize) -> Self {
+        self.max_buckets = max_buckets;
+        self
+    }
+}
+
+/// Entry counts per fixed-width time bucket, including empty buckets
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeHistogram {
+    /// Start of the first bucket, aligned to a multiple of `bucket_ms`
+    pub start_ms: i64,
+    pub bucket_ms: u64,
+    pub counts: Vec<usize>,
+}
+
+impl TimeHistogram {
+    pub fn bucket_start(&self, index: usize) -> DateTime<Local> {
+        let ms = self.start_ms + index as i64 * self.bucket_ms as i64;
+        Local
+            .timestamp_millis_opt(ms)
+            .single()
+            .unwrap_or_else(|| DateTime::<Utc>::UNIX_EPOCH.with_timezone(&Local))
+    }
+
+    /// Index of the bucket containing `timestamp`; may exceed the last index for
+    /// timestamps outside the histogram's range
+    pub fn index_of(&self, timestamp: &DateTime<Local>) -> usize {
+        let offset = (timestamp.timestamp_millis() - self.start_ms).max(0);
+        (offset / self.bucket_ms as i64) as usize
+    }
+
+    /// Human-readable bucket width, e.g. `100ms`, `5s`, `10m`
+    pub fn width_label(&self) -> String {
+        format_bucket_width(self.bucket_ms)
+    }
+
+    /// `strftime` pattern precise enough to tell neighbouring buckets apart
+    pub fn time_format(&self) -> &'static str {
+        let spans_days = self.counts.len() as u64 * self.bucket_ms > 86_400_000;
+        match (self.bucket_ms < 1_000, spans_days) {
+            (true, _) => "%H:%M:%S%.3f",
+            (false, true) => "%m-%d %H:%M:%S",
+            (false, false) => "%H:%M:%S",
+        }
+    }
+}
+
+/// Bucket width in the largest unit that divides it evenly
+pub fn format_bucket_width(ms: u64) -> String {
+    const UNITS: [(u64, &str); 4] = [
+        (86_400_000, "d"),
+        (3_600_000, "h"),
+        (60_000, "m"),
+        (1_000, "s"),
+    ];
+    UNITS
+        .iter()
+        .find(|(unit, _)| ms >= *unit && ms.is_multiple_of(*unit))
+        .map(|(unit, suffix)| format!("{}{suffix}", ms / unit))
+        .unwrap_or_else(|| format!("{ms}ms"))
+}
+
+fn bucket_count(first_ms: i64, last_ms: i64, bucket_ms: u64) -> usize {
+    let bucket = bucket_ms as i64;
+    let start = first_ms - first_ms.rem_euclid(bucket);
+    ((last_ms - start) / bucket) as usize + 1
+}
+
+/// Bucket width for timestamps between `first_ms` and `last_ms`: the requested width
+/// (at least `MIN_BUCKET_MS`) when it fits, otherwise the finest standard width that
+/// keeps the histogram within `max_buckets`
+pub fn choose_bucket_ms(first_ms: i64, last_ms: i64, options: &TimeBucketOptions) -> u64 {
+    let floor = options
+        .bucket_ms
+        .unwrap_or(MIN_BUCKET_MS)
+        .max(MIN_BUCKET_MS);
+    let max_buckets = options.max_buckets.max(1);
+    let fits = |bucket_ms: u64| bucket_count(first_ms, last_ms, bucket_ms) <= max_buckets;
+
+    if options.bucket_ms.is_some() && fits(floor) {
+        return floor;
+    }
+    if let Some(&nice) = NICE_BUCKET_MS
+        .iter()
+        .find(|&&nice| nice >= floor && fits(nice))
+    {
+        return nice;
+    }
+
+    let span = (last_ms - first_ms).max(0) as u64;
+    let mut bucket_ms = span
+        .div_ceil((max_buckets as u64 - 1).max(1))
+        .max(floor)
+        .next_multiple_of(MIN_BUCKET_MS);
+    while !fits(bucket_ms) {
+        bucket_ms += MIN_BUCKET_MS;
+    }
+    bucket_ms
+}
+
+/// Histogram of `timestamps`, or `None` when there are none
+pub fn build_time_histogram<I>(timestamps: I, options: &TimeBucketOptions) -> Option<TimeHistogram>
+where
+    I: IntoIterator<Item = DateTime<Local>>,
+{
+    let millis: Vec<i64> = timestamps
+        .into_iter()
+        .map(|ts| ts.timestamp_millis())
+        .collect();
+    let (&first, &last) = (millis.iter().min()?, millis.iter().max()?);
+
+    let bucket_ms = choose_bucket_ms(first, last, options);
+    let bucket = bucket_ms as i64;
+    let start_ms = first - first.rem_euclid(bucket);
+    let mut counts = vec![0; bucket_count(first, last, bucket_ms)];
+    for ms in millis {
+        counts[((ms - start_ms) / bucket) as usize] += 1;
+    }
+
+    Some(TimeHistogram {
+        start_ms,
+        bucket_ms,
+        counts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(ms: i64) -> DateTime<Local> {
+        Local.timestamp_millis_opt(1_767_225_600_000 + ms).unwrap()
+    }
+
+    #[test]
+    fn auto_width_resolves_sub_second_bursts() {
+        let burst: Vec<_> = [0, 40, 120, 950, 1_890].into_iter().map(at).collect();
+        let histogram = build_time_histogram(burst, &TimeBucketOptions::new()).unwrap();
+        assert_eq!(histogram.bucket_ms, 100);
+        assert_eq!(histogram.width_label(), "100ms");
+        assert_eq!(histogram.counts.len(), 19);
+        assert_eq!(histogram.counts[0], 2);
+        assert_eq!(histogram.counts.iter().sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn requested_width_is_widened_to_respect_max_buckets() {
+        let options = TimeBucketOptions::new()
+            .bucket_ms(Some(100))
+            .max_buckets(10);
+        assert_eq!(choose_bucket_ms(0, 1_999, &options), 200);
+        assert_eq!(choose_bucket_ms(0, 999, &options), 100);
+        let coarse = TimeBucketOptions::new().max_buckets(3);
+        assert_eq!(choose_bucket_ms(0, 1_000_000_000, &coarse), 500_000_000);
+    }
+
+    #[test]
+    fn widths_use_largest_even_unit() {
+        assert_eq!(format_bucket_width(100), "100ms");
+        assert_eq!(format_bucket_width(1_500), "1500ms");
+        assert_eq!(format_bucket_width(5_000), "5s");
+        assert_eq!(format_bucket_width(600_000), "10m");
+        assert_eq!(format_bucket_width(7_200_000), "2h");
+    }
+}
@@ -1,7 +1,12 @@
-use crate::comparator::LogFilter;
+use crate::comparator::{LogFilter, compare_json};
+use crate::config::SessionInsights;
 use crate::parser::{LogEntry, LogEntryKind};
-use chrono::{SecondsFormat, Utc};
-use serde_json::json;
+use crate::payload_view::elide_long_arrays;
+use crate::session_path::SessionPath;
+use crate::units::format_utc_millis;
+use chrono::{DateTime, Local};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
 use std::fmt::Write;
 
 #[derive(Debug, Clone)]
@@ -24,12 +29,13 @@ impl TraceSelector {
         }
     }
 
-    fn matches(&self, entry: &LogEntry) -> bool {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
         match self {
             Self::Id(needle) => matches_id(entry, needle),
-            Self::Session(needle) => {
-                !entry.component_id.is_empty() && entry.component_id.contains(needle)
-            }
+            Self::Session(needle) => entry
+                .session_paths()
+                .iter()
+                .any(|path| path.as_str().contains(needle.as_str())),
         }
     }
 }
@@ -70,7 +76,11 @@ pub fn collect_trace_entries<'a>(
     entries
 }
 
-pub fn format_trace_text(entries: &[&LogEntry], selector: &TraceSelector) -> String {
+pub fn format_trace_text(
+    entries: &[&LogEntry],
+    selector: &TraceSelector,
+    sessions: &SessionInsights,
+) -> String {
     let mut out = String::new();
     let _ = writeln!(
         out,
@@ -109,14 +119,15 @@ pub fn format_trace_text(entries: &[&LogEntry], selector: &TraceSelector) -> Str
             .num_milliseconds();
         prev_ts = Some(entry.timestamp);
 
-        let ts = entry
-            .timestamp
-            .with_timezone(&Utc)
-            .to_rfc3339_opts(SecondsFormat::Millis, true);
+        let ts = format_utc_millis(entry.timestamp);
         let component_label = if entry.component_id.is_empty() {
             entry.component.as_str().to_string()
         } else {
-            format!("{} ({})", entry.component, entry.component_id)
+            format!(
+                "{} ({})",
+                entry.component,
+                sessions.display_path(&entry.component_id)
+            )
         };
         let message = entry.message.replace('\n', "\\n");
 
@@ -130,7 +141,11 @@ pub fn format_trace_text(entries: &[&LogEntry], selector: &TraceSelector) -> Str
     out
 }
 
-pub fn format_trace_json(entries: &[&LogEntry], selector: &TraceSelector) -> String {
+pub fn format_trace_json(
+    entries: &[&LogEntry],
+    selector: &TraceSelector,
+    sessions: &SessionInsights,
+) -> String {
     let first_ts = entries.first().map(|entry| entry.timestamp);
     let last_ts = entries.last().map(|entry| entry.timestamp);
 
@@ -153,16 +168,17 @@ pub fn format_trace_json(entries: &[&LogEntry], selector: &TraceSelector) -> Str
                 } => Some(id.clone()),
                 _ => None,
             };
+            let session_display_name = (!entry.component_id.is_empty())
+                .then(|| sessions.display_path(&entry.component_id))
+                .filter(|name| *name != entry.component_id);
 
             json!({
-                "timestamp": entry
-                    .timestamp
-                    .with_timezone(&Utc)
-                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+                "timestamp": format_utc_millis(entry.timestamp),
                 "delta_ms": delta_ms,
                 "elapsed_ms": elapsed_ms,
                 "component": entry.component,
                 "component_id": entry.component_id,
+                "session_display_name": session_display_name,
                 "level": entry.level,
                 "kind": entry.entry_type(),
                 "log_key": entry.log_key(),
@@ -170,6 +186,7 @@ pub fn format_trace_json(entries: &[&LogEntry], selector: &TraceSelector) -> Str
                 "raw_logline": entry.raw_logline,
                 "module_path": entry.module_path,
                 "structured_fields": entry.structured_fields,
+                "tags": entry.tags,
                 "source_line_number": entry.source_line_number,
                 "request_id": request_id,
             })
@@ -195,3 +212,211 @@ pub fn format_trace_json(entries: &[&LogEntry], selector: &TraceSelector) -> Str
     }))
     .unwrap_or_else(|_| "{\"trace\":{\"error\":\"failed to serialize trace output\"}}".to_string())
 }
+
+/// Settings drift between two successive invocations of a command in one session
+#[derive(Debug, Clone)]
+pub struct CommandSettingsDiff {
+    pub session: String,
+    pub from_line: usize,
+    pub to_line: usize,
+    pub from_timestamp: DateTime<Local>,
+    pub to_timestamp: DateTime<Local>,
+    pub differences: Vec<(String, Value, Value)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandDiffReport {
+    pub command: String,
+    pub invocations: usize,
+    pub sessions: usize,
+    pub pairs: Vec<CommandSettingsDiff>,
+}
+
+impl CommandDiffReport {
+    pub fn changed_pairs(&self) -> impl Iterator<Item = &CommandSettingsDiff> {
+        self.pairs
+            .iter()
+            .filter(|pair| !pair.differences.is_empty())
+    }
+}
+
+fn command_settings<'a>(entry: &'a LogEntry, command: &str) -> Option<Option<&'a Value>> {
+    match &entry.kind {
+        LogEntryKind::Command {
+            command: name,
+            settings,
+        } if name == command => Some(settings.as_ref()),
+        _ => None,
+    }
+}
+
+/// Diffs the settings of successive invocations of `command`, pairing invocations
+/// within the same session so parallel sessions are not mixed. Each invocation
+/// logs under its own leaf id (`manager-1/eyes-1/check-abc`), so the session is the
+/// path without that leaf.
+pub fn diff_command_settings(
+    logs: &[LogEntry],
+    filter: &LogFilter,
+    selector: Option<&TraceSelector>,
+    command: &str,
+) -> CommandDiffReport {
+    let mut by_session: BTreeMap<String, Vec<(&LogEntry, Option<&Value>)>> = BTreeMap::new();
+    for entry in logs
+        .iter()
+        .filter(|entry| filter.matches(entry) && selector.is_none_or(|s| s.matches(entry)))
+    {
+        if let Some(settings) = command_settings(entry, command) {
+            by_session
+                .entry(invocation_session(&entry.component_id))
+                .or_default()
+                .push((entry, settings));
+        }
+    }
+
+    let mut report = CommandDiffReport {
+        command: command.to_string(),
+        sessions: by_session.len(),
+        ..CommandDiffReport::default()
+    };
+
+    for (session, mut invocations) in by_session {
+        invocations.sort_by_key(|(entry, _)| (entry.timestamp, entry.source_line_number));
+        report.invocations += invocations.len();
+
+        for window in invocations.windows(2) {
+            let (previous, previous_settings) = window[0];
+            let (current, current_settings) = window[1];
+            let differences = compare_json(
+                previous_settings.unwrap_or(&Value::Null),
+                current_settings.unwrap_or(&Value::Null),
+            );
+            report.pairs.push(CommandSettingsDiff {
+                session: session.clone(),
+                from_line: previous.source_line_number,
+                to_line: current.source_line_number,
+                from_timestamp: previous.timestamp,
+                to_timestamp: current.timestamp,
+                differences,
+            });
+        }
+    }
+
+    report
+        .pairs
+        .sort_by_key(|pair| (pair.to_timestamp, pair.to_line));
+    report
+}
+
+fn invocation_session(component_id: &str) -> String {
+    let Some(path) = SessionPath::parse_parallel(component_id).into_iter().next() else {
+        return component_id.to_string();
+    };
+    path.parent().unwrap_or(path).as_str().to_string()
+}
+
+fn diff_path_label(path: &str) -> &str {
+    if path.is_empty() { "(settings)" } else { path }
+}
+
+/// Text drift report; arrays longer than `max_array_elements` in changed values keep only
+/// their first and last elements (0 = all)
+pub fn format_command_diff_text(
+    report: &CommandDiffReport,
+    sessions: &SessionInsights,
+    max_array_elements: usize,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "COMMAND SETTINGS DRIFT \"{}\" ({} invocations across {} sessions)",
+        report.command, report.invocations, report.sessions
+    );
+
+    if report.pairs.is_empty() {
+        let _ = writeln!(
+            out,
+            "No session invokes \"{}\" more than once.",
+            report.command
+        );
+        return out;
+    }
+
+    let unchanged = report.pairs.len() - report.changed_pairs().count();
+    for pair in report.changed_pairs() {
+        let session = if pair.session.is_empty() {
+            "(no session)".to_string()
+        } else {
+            sessions.display_path(&pair.session)
+        };
+        let _ = writeln!(
+            out,
+            "\n{session}: line {} → line {} (+{}ms, {})",
+            pair.from_line,
+            pair.to_line,
+            pair.to_timestamp
+                .signed_duration_since(pair.from_timestamp)
+                .num_milliseconds(),
+            format_utc_millis(pair.to_timestamp)
+        );
+        for (path, before, after) in &pair.differences {
+            let _ = writeln!(
+                out,
+                "  {}: {} → {}",
+                diff_path_label(path),
+                elide_long_arrays(before, max_array_elements),
+                elide_long_arrays(after, max_array_elements)
+            );
+        }
+    }
+
+    if unchanged > 0 {
+        let _ = writeln!(
+            out,
+            "\n{unchanged} successive invocation(s) with identical settings"
+        );
+    }
+    out
+}
+
+pub fn format_command_diff_json(report: &CommandDiffReport, sessions: &SessionInsights) -> String {
+    let pairs: Vec<Value> = report
+        .pairs
+        .iter()
+        .map(|pair| {
+            let session_display_name = (!pair.session.is_empty())
+                .then(|| sessions.display_path(&pair.session))
+                .filter(|name| *name != pair.session);
+            json!({
+                "session": pair.session,
+                "session_display_name": session_display_name,
+                "from_line": pair.from_line,
+                "to_line": pair.to_line,
+                "from_timestamp": format_utc_millis(pair.from_timestamp),
+                "to_timestamp": format_utc_millis(pair.to_timestamp),
+                "changed": !pair.differences.is_empty(),
+                "differences": pair
+                    .differences
+                    .iter()
+                    .map(|(path, before, after)| json!({
+                        "path": path,
+                        "before": before,
+                        "after": after,
+                    }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&json!({
+        "command_diff": {
+            "command": report.command,
+            "invocations": report.invocations,
+            "sessions": report.sessions,
+            "changed_pairs": report.changed_pairs().count(),
+            "pairs": pairs,
+        }
+    }))
+    .unwrap_or_else(|_| {
+        "{\"command_diff\":{\"error\":\"failed to serialize command diff\"}}".to_string()
+    })
+}
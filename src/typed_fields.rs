@@ -0,0 +1,58 @@
+//! Typed timestamp and duration fields of serialized entries (`[serialization]`)
+
+use crate::comparator::LogFilter;
+use crate::config::{AnalyzerConfig, TimestampRepr};
+use crate::parser::LogEntry;
+use crate::perf_analyzer::analyze_performance_with_config;
+use chrono::{SecondsFormat, Utc};
+use std::collections::HashMap;
+
+/// Renders an entry's timestamp in the configured representations and looks up the
+/// duration of the paired operation it starts or ends
+#[derive(Debug, Clone, Default)]
+pub struct TypedFields {
+    timestamps: TimestampRepr,
+    /// Source line number → duration of the operation starting or ending there
+    durations: HashMap<usize, i64>,
+}
+
+impl TypedFields {
+    /// Pairs operations in `logs` (one source file) when the profile asks for durations
+    pub fn new(logs: &[LogEntry], config: &AnalyzerConfig) -> Self {
+        let rules = &config.serialization;
+        let mut durations = HashMap::new();
+        if rules.durations {
+            let perf = analyze_performance_with_config(logs, &LogFilter::new(), None, config);
+            for op in &perf.operations {
+                durations.insert(op.lines.0, op.duration_ms);
+                durations.insert(op.lines.1, op.duration_ms);
+            }
+        }
+        Self {
+            timestamps: rules.timestamps,
+            durations,
+        }
+    }
+
+    /// RFC3339 in UTC, unless only epoch milliseconds are configured
+    pub fn rfc3339(&self, entry: &LogEntry) -> Option<String> {
+        self.timestamps.rfc3339().then(|| {
+            entry
+                .timestamp
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true)
+        })
+    }
+
+    /// Milliseconds since the Unix epoch, unless only RFC3339 is configured
+    pub fn epoch_millis(&self, entry: &LogEntry) -> Option<i64> {
+        self.timestamps
+            .epoch_millis()
+            .then(|| entry.timestamp.timestamp_millis())
+    }
+
+    /// Duration of the completed operation `entry` starts or ends
+    pub fn duration_ms(&self, entry: &LogEntry) -> Option<i64> {
+        self.durations.get(&entry.source_line_number).copied()
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use serde_json::{Map, Value};
+
+/// Suffix of companion fields holding a human-readable rendering of a quantity
+pub const HUMAN_SUFFIX: &str = "_human";
+
+/// Keys whose values are copied from log entries and must not be rewritten
+/// (`value1`/`value2` and compact `v1`/`v2` hold compared payload values)
+const VERBATIM_KEYS: [&str; 8] = [
+    "structured_fields",
+    "payload",
+    "settings",
+    "body",
+    "value1",
+    "value2",
+    "v1",
+    "v2",
+];
+
+/// Shape of duration and size fields in JSON reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonUnits {
+    /// `*_ms` / `*_bytes` fields are whole milliseconds / bytes, each with a `*_human` companion
+    #[default]
+    Standard,
+    /// Fields keep the shapes of earlier releases (fractional values, no companions)
+    Legacy,
+}
+
+impl JsonUnits {
+    pub fn from_legacy_flag(legacy: bool) -> Self {
+        if legacy {
+            JsonUnits::Legacy
+        } else {
+            JsonUnits::Standard
+        }
+    }
+
+    pub fn is_standard(self) -> bool {
+        self == JsonUnits::Standard
+    }
+}
+
+/// Duration as a short label in its two largest units, e.g. `850ms`, `4.2s`, `3m 20s`, `2h 05m`
+pub fn format_duration_ms(ms: i64) -> String {
+    let sign = if ms < 0 { "-" } else { "" };
+    let ms = ms.unsigned_abs();
+    let body = if ms < 1_000 {
+        format!("{ms}ms")
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1_000.0)
+    } else if ms < 3_600_000 {
+        format!("{}m {:02}s", ms / 60_000, ms % 60_000 / 1_000)
+    } else if ms < 86_400_000 {
+        format!("{}h {:02}m", ms / 3_600_000, ms % 3_600_000 / 60_000)
+    } else {
+        format!("{}d {:02}h", ms / 86_400_000, ms % 86_400_000 / 3_600_000)
+    };
+    format!("{sign}{body}")
+}
+
+/// Timestamp as RFC3339 in UTC with millisecond precision, e.g. `2026-01-01T00:00:00.000Z`
+pub fn format_utc_millis(timestamp: DateTime<Local>) -> String {
+    timestamp
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Size in binary units, e.g. `512 B`, `1.5 KiB`, `3.0 MiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn human_companion(key: &str, value: i64) -> Option<String> {
+    if key.ends_with("_ms") {
+        Some(format_duration_ms(value))
+    } else if key.ends_with("_bytes") {
+        Some(format_bytes(value.max(0) as u64))
+    } else {
+        None
+    }
+}
+
+/// Rounds every numeric `*_ms` / `*_bytes` field in `value` to a whole number and adds a
+/// `*_human` string next to it. Log-sourced objects (payloads, structured fields) are left as-is.
+pub fn standardize_json_units(value: &mut Value) {
+    match value {
+        Value::Object(object) => standardize_object(object),
+        Value::Array(items) => items.iter_mut().for_each(standardize_json_units),
+        _ => {}
+    }
+}
+
+fn standardize_object(object: &mut Map<String, Value>) {
+    let mut companions = Vec::new();
+    for (key, value) in object.iter_mut() {
+        if VERBATIM_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        let Some(number) = value.as_f64() else {
+            standardize_json_units(value);
+            continue;
+        };
+        let rounded = number.round() as i64;
+        if let Some(human) = human_companion(key, rounded) {
+            *value = Value::from(rounded);
+            companions.push((format!("{key}{HUMAN_SUFFIX}"), Value::String(human)));
+        }
+    }
+    for (key, human) in companions {
+        object.entry(key).or_insert(human);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn humanizes_durations_and_sizes() {
+        assert_eq!(format_duration_ms(850), "850ms");
+        assert_eq!(format_duration_ms(4_250), "4.2s");
+        assert_eq!(format_duration_ms(200_000), "3m 20s");
+        assert_eq!(format_duration_ms(7_500_000), "2h 05m");
+        assert_eq!(format_duration_ms(-1_500), "-1.5s");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1_536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn rounds_unit_fields_and_adds_companions() {
+        let mut report = json!({
+            "operations": [{"avg_duration_ms": 1234.6, "count": 3, "p95_duration_ms": null}],
+            "payload_total_bytes": 2048,
+            "structured_fields": {"elapsed_ms": 1.5},
+        });
+        standardize_json_units(&mut report);
+
+        assert_eq!(report["operations"][0]["avg_duration_ms"], json!(1235));
+        assert_eq!(report["operations"][0]["avg_duration_ms_human"], "1.2s");
+        assert_eq!(report["operations"][0]["count"], json!(3));
+        assert!(
+            report["operations"][0]
+                .get("p95_duration_ms_human")
+                .is_none()
+        );
+        assert_eq!(report["payload_total_bytes_human"], "2.0 KiB");
+        assert_eq!(report["structured_fields"], json!({"elapsed_ms": 1.5}));
+    }
+}
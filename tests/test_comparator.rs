@@ -1,5 +1,7 @@
+use log_analyzer::comparator::{compare_json_limited, compare_json_with_identity};
 use log_analyzer::compare_json;
 use serde_json::json;
+use std::collections::BTreeMap;
 
 // Import existing tests
 #[test]
@@ -209,6 +211,77 @@ fn test_compare_json_with_different_array_lengths() {
     assert_eq!(diff[0].2, json!(null));
 }
 
+#[test]
+fn test_compare_json_with_identity_pairs_array_elements_by_key() {
+    let json1 = json!({
+        "renders": [
+            {"renderId": "r1", "browser": "chrome", "width": 800, "status": "done"},
+            {"renderId": "r2", "browser": "firefox", "width": 1024, "status": "done"},
+            {"renderId": "r3", "browser": "safari", "width": 390, "status": "done"}
+        ]
+    });
+    let json2 = json!({
+        "renders": [
+            {"renderId": "r2", "browser": "firefox", "width": 1280, "status": "done"},
+            {"renderId": "r1", "browser": "chrome", "width": 800, "status": "failed"},
+            {"renderId": "r4", "browser": "edge", "width": 390, "status": "done"}
+        ]
+    });
+    let identity_keys = BTreeMap::from([("renders".to_string(), "renderId".to_string())]);
+
+    let diff = compare_json_with_identity(&json1, &json2, &identity_keys);
+
+    assert_eq!(diff.len(), 4, "unexpected differences: {diff:?}");
+    assert!(
+        diff.iter()
+            .any(|(path, v1, v2)| path == "renders[renderId=r1].status"
+                && v1 == &json!("done")
+                && v2 == &json!("failed"))
+    );
+    assert!(
+        diff.iter()
+            .any(|(path, v1, v2)| path == "renders[renderId=r2].width"
+                && v1 == &json!(1024)
+                && v2 == &json!(1280))
+    );
+    assert!(
+        diff.iter()
+            .any(|(path, v1, v2)| path == "renders[renderId=r3]"
+                && v1["browser"] == json!("safari")
+                && v2.is_null())
+    );
+    assert!(
+        diff.iter()
+            .any(|(path, v1, v2)| path == "renders[renderId=r4]"
+                && v1.is_null()
+                && v2["browser"] == json!("edge"))
+    );
+}
+
+#[test]
+fn test_compare_json_limited_treats_deep_subtrees_as_opaque() {
+    let json1 = json!({
+        "name": "check",
+        "dom": {"body": {"div": {"span": {"text": "a"}}}, "title": "page"}
+    });
+    let json2 = json!({
+        "name": "check",
+        "dom": {"body": {"div": {"span": {"text": "b"}}}, "title": "page"}
+    });
+
+    let (unlimited, truncated) = compare_json_limited(&json1, &json2, &BTreeMap::new(), None);
+    assert_eq!(truncated, 0);
+    assert_eq!(unlimited.len(), 1);
+    assert_eq!(unlimited[0].0, "dom.body.div.span.text");
+
+    let (limited, truncated) = compare_json_limited(&json1, &json2, &BTreeMap::new(), Some(2));
+    assert_eq!(truncated, 1);
+    assert_eq!(limited.len(), 1, "unexpected differences: {limited:?}");
+    assert_eq!(limited[0].0, "dom.body");
+    assert_eq!(limited[0].1["div"]["span"]["text"], json!("a"));
+    assert_eq!(limited[0].2["div"]["span"]["text"], json!("b"));
+}
+
 #[test]
 fn test_compare_json_with_empty_structures() {
     let json1 = json!({
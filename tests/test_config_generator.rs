@@ -22,6 +22,7 @@ fn make_entry(component: &str, component_id: &str, kind: LogEntryKind) -> LogEnt
         module_path: None,
         kind,
         source_line_number: 1,
+        tags: Vec::new(),
     }
 }
 
@@ -309,6 +310,7 @@ fn test_preserves_template_defined_session_levels() {
                 create_command: Some("makeManager".to_string()),
                 complete_commands: vec!["getResults".to_string(), "closeBatch".to_string()],
                 summary_fields: vec!["concurrency".to_string()],
+                display_name: None,
             },
             SessionLevelConfig {
                 name: "test".to_string(),
@@ -316,8 +318,10 @@ fn test_preserves_template_defined_session_levels() {
                 create_command: Some("openEyes".to_string()),
                 complete_commands: vec!["close".to_string(), "abort".to_string()],
                 summary_fields: vec![],
+                display_name: None,
             },
         ],
+        ..SessionsRules::default()
     };
 
     let generated = generate_config(
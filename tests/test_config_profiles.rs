@@ -98,6 +98,7 @@ fn test_perf_markers_can_be_overridden_by_config() {
             settings: None,
         },
         source_line_number: 1,
+        tags: Vec::new(),
     };
 
     let end = LogEntry {
@@ -114,6 +115,7 @@ fn test_perf_markers_can_be_overridden_by_config() {
             settings: None,
         },
         source_line_number: 2,
+        tags: Vec::new(),
     };
 
     let logs = vec![start, end];
@@ -158,6 +160,7 @@ fn test_profile_insights_can_be_configured_without_external_profile_file() {
             create_command: None,
             complete_commands: Vec::new(),
             summary_fields: Vec::new(),
+            display_name: None,
         },
         SessionLevelConfig {
             name: "level-2".to_string(),
@@ -165,6 +168,7 @@ fn test_profile_insights_can_be_configured_without_external_profile_file() {
             create_command: None,
             complete_commands: Vec::new(),
             summary_fields: Vec::new(),
+            display_name: None,
         },
     ];
 
@@ -184,6 +188,7 @@ fn test_profile_insights_can_be_configured_without_external_profile_file() {
             settings: None,
         },
         source_line_number: 1,
+        tags: Vec::new(),
     };
 
     let request_entry = LogEntry {
@@ -205,6 +210,7 @@ fn test_profile_insights_can_be_configured_without_external_profile_file() {
             payload: None,
         },
         source_line_number: 2,
+        tags: Vec::new(),
     };
 
     let insights = analyze_profile(&[command_entry, request_entry], &config);
@@ -1,6 +1,6 @@
 use chrono::Local;
 use log_analyzer::comparator::LogFilter;
-use log_analyzer::parser::{LogEntry, LogEntryKind};
+use log_analyzer::parser::{Endpoint, LogEntry, LogEntryKind, RequestDirection};
 use std::collections::HashMap;
 
 fn create_test_log(component: &str, level: &str, message: &str) -> LogEntry {
@@ -15,6 +15,7 @@ fn create_test_log(component: &str, level: &str, message: &str) -> LogEntry {
         module_path: None,
         kind: LogEntryKind::Generic { payload: None },
         source_line_number: 1,
+        tags: Vec::new(),
     }
 }
 
@@ -96,3 +97,37 @@ fn test_structured_field_filter_matches_extracted_fields() {
             .matches(&log)
     );
 }
+
+#[test]
+fn test_method_and_host_filters_match_request_endpoints() {
+    let request = |address: &str| {
+        let mut log = create_test_log("core-requests", "INFO", "Request \"openEyes\" will be sent");
+        log.kind = LogEntryKind::Request {
+            request: "openEyes".to_string(),
+            request_id: None,
+            endpoint: Some(Endpoint::parse(address)),
+            direction: RequestDirection::Send,
+            payload: None,
+        };
+        log
+    };
+    let eu = request("[POST]https://eyesapi-eu.example.com/api/sessions");
+    let us = request("[GET]https://eyesapi-us.example.com/api/sessions");
+
+    let post = LogFilter::new().with_method(Some("post"));
+    assert!(post.matches(&eu) && !post.matches(&us));
+    let eyes_hosts = LogFilter::new().with_host(Some("eyesapi-*.example.com"));
+    assert!(eyes_hosts.matches(&eu) && eyes_hosts.matches(&us));
+    let not_eu = LogFilter::new().exclude_host(Some("EYESAPI-EU"));
+    assert!(!not_eu.matches(&eu) && not_eu.matches(&us));
+    assert!(
+        !LogFilter::new()
+            .with_host(Some("eyesapi"))
+            .matches(&create_test_log("core", "INFO", "no endpoint"))
+    );
+    assert!(
+        LogFilter::new()
+            .with_method(Some("PUT"))
+            .matches(&create_structured_test_log("method", "put"))
+    );
+}
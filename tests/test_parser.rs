@@ -5,8 +5,8 @@
 use chrono::{DateTime, Local};
 use log_analyzer::config::{AnalyzerConfig, load_builtin_template};
 use log_analyzer::parser::{
-    LogEntryKind, RequestDirection, parse_log_entry, parse_log_entry_with_config,
-    parse_log_file_with_config,
+    Endpoint, LogEntryKind, ReadScope, RequestDirection, ScopeRange, parse_log_entry,
+    parse_log_entry_with_config, parse_log_file_with_config,
 };
 use serde_json::json;
 use std::fs;
@@ -184,6 +184,36 @@ fn test_parse_open_eyes_request() {
     // assert_eq!(record.request_name, "openEyes");
 }
 
+#[test]
+fn test_parse_request_endpoint_into_method_host_and_path() {
+    let log_line = r#"core-requests (manager-1/eyes-1) | 2025-04-03T21:35:29.392Z [INFO ] Request "openEyes" [0--e6f5] will be sent to the address "[POST]https://eyesapi.apts.com/api/sessions/running?apiKey=x" with body {"a":1}"#;
+    let record = parse_log_entry_with_config(log_line, 1, &eyes_config()).expect("request parses");
+    let endpoint = record.endpoint().expect("endpoint is extracted");
+    assert_eq!(endpoint.method.as_deref(), Some("POST"));
+    assert_eq!(endpoint.host.as_deref(), Some("eyesapi.apts.com"));
+    assert_eq!(
+        endpoint.path.as_deref(),
+        Some("/api/sessions/running?apiKey=x")
+    );
+    assert_eq!(
+        endpoint.to_string(),
+        "POST https://eyesapi.apts.com/api/sessions/running?apiKey=x"
+    );
+
+    let bare = Endpoint::parse("get /health");
+    assert_eq!(
+        (bare.method.as_deref(), bare.host, bare.path.as_deref()),
+        (Some("GET"), None, Some("/health"))
+    );
+    let method_only = Endpoint::parse("[DELETE]");
+    assert_eq!(method_only.method.as_deref(), Some("DELETE"));
+    assert_eq!(method_only.to_string(), "DELETE");
+    assert_eq!(
+        Endpoint::parse("render.example:8443/jobs").host.as_deref(),
+        Some("render.example:8443")
+    );
+}
+
 // Test for a ufg-requests log for the "startRenders" event.
 #[test]
 fn test_parse_start_renders() {
@@ -371,6 +401,57 @@ fn test_parse_rust_tracing_file_with_multiline_entries() {
     assert_eq!(logs[1].structured_field("exit_code"), Some("251"));
 }
 
+#[test]
+fn test_read_scope_keeps_whole_file_line_numbers() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("big.log");
+    let line = |n: u32| {
+        format!("core-universal | 2026-01-01T00:00:0{n}.000Z [INFO ] entry {n}\n  detail {n}\n")
+    };
+    let text: String = (1..=5).map(line).collect();
+    fs::write(&file, &text).expect("write log file");
+    let read = |scope: ReadScope| {
+        let mut config = eyes_config();
+        config.parser.scope = scope;
+        parse_log_file_with_config(&file, &config)
+            .expect("scoped file parses")
+            .iter()
+            .map(|entry| (entry.source_line_number, entry.message.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    // Lines 4-7 start inside entry 2, whose continuation line is skipped, and end
+    // inside entry 4, which is still read whole
+    let lines = read(ReadScope {
+        lines: Some(ScopeRange {
+            start: 4,
+            end: Some(7),
+        }),
+        bytes: None,
+    });
+    assert_eq!(
+        lines,
+        vec![
+            (5, "entry 3\n  detail 3".to_string()),
+            (7, "entry 4\n  detail 4".to_string()),
+        ]
+    );
+
+    // A byte range starting mid-line begins at the next line
+    let entry_len = line(1).len() as u64;
+    let bytes = read(ReadScope {
+        lines: None,
+        bytes: Some(ScopeRange {
+            start: entry_len * 3 + 1,
+            end: None,
+        }),
+    });
+    assert_eq!(
+        bytes.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+        vec![9]
+    );
+}
+
 #[test]
 fn test_parse_syslog_line() {
     let log_line =
@@ -407,3 +488,25 @@ fn test_default_base_profile_keeps_specialized_eyes_message_generic() {
     assert!(matches!(record.kind, LogEntryKind::Generic { .. }));
     assert_eq!(record.payload(), Some(&json!({ "x": 1 })));
 }
+
+#[test]
+fn test_log_entry_typed_payload_accessors() {
+    let log_line = r#"core (manager-1/eyes-2/) | 2026-01-01T00:00:00.000Z [INFO ] Request "openEyes" [0--id1] will be sent with body {"startInfo":{"batchInfo":{"id":"batch-7"},"retries":[{"timeout":1500}],"isNew":true}}"#;
+    let record = parse_log_entry_with_config(log_line, 1, &eyes_config())
+        .expect("Failed to parse request log");
+
+    assert_eq!(record.request_name(), Some("openEyes"));
+    assert_eq!(record.command_name(), None);
+    assert_eq!(record.session_segments(), vec!["manager-1", "eyes-2"]);
+    assert_eq!(
+        record.payload_str("startInfo.batchInfo.id"),
+        Some("batch-7")
+    );
+    assert_eq!(
+        record.payload_i64("startInfo.retries.0.timeout"),
+        Some(1500)
+    );
+    assert_eq!(record.payload_bool("startInfo.isNew"), Some(true));
+    assert_eq!(record.payload_i64("startInfo.batchInfo.id"), None);
+    assert_eq!(record.payload_value("startInfo.missing"), None);
+}
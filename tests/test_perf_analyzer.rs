@@ -1,3 +1,4 @@
+use log_analyzer::config::SessionAttribution;
 use log_analyzer::perf_analyzer;
 
 #[test]
@@ -54,3 +55,366 @@ fn test_truncate_string() {
         "exactly10c"
     );
 }
+
+fn timed_op(name: &str, start_ms: i64, duration_ms: i64) -> perf_analyzer::TimedOperation {
+    use chrono::{Local, TimeZone};
+
+    let start_time = Local.timestamp_millis_opt(start_ms).unwrap();
+    perf_analyzer::TimedOperation {
+        op_type: "Request".to_string(),
+        name: name.to_string(),
+        correlation_id: None,
+        start_time,
+        end_time: start_time + chrono::Duration::milliseconds(duration_ms),
+        duration_ms,
+        start_component: "svc".to_string(),
+        end_component: "svc".to_string(),
+        endpoint: None,
+        status: None,
+        component_id: None,
+        errors_during: Vec::new(),
+        tags: Vec::new(),
+        suspect: None,
+        lines: (0, 0),
+    }
+}
+
+#[test]
+fn test_throughput_stats_rate_concurrency_and_utilization() {
+    // Two overlapping ops in [0, 1000) and one back-to-back op in [1000, 1500)
+    let ops = [
+        timed_op("check", 0, 1000),
+        timed_op("check", 500, 500),
+        timed_op("check", 1000, 500),
+    ];
+    let refs: Vec<_> = ops.iter().collect();
+
+    let stats = perf_analyzer::ThroughputStats::from_operations(&refs, 2000);
+
+    assert_eq!(stats.window_ms, 2000);
+    assert!((stats.ops_per_sec - 1.5).abs() < f64::EPSILON);
+    assert_eq!(stats.peak_concurrency, 2);
+    assert!((stats.concurrency_adjusted_ops_per_sec - 0.75).abs() < f64::EPSILON);
+    assert!((stats.avg_concurrency - 1.0).abs() < f64::EPSILON);
+    assert_eq!(stats.busy_ms, 1500);
+    assert!((stats.utilization_pct - 75.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_perf_results_include_overall_and_per_name_throughput() {
+    let mut results = perf_analyzer::PerfAnalysisResults::new();
+    results.operations = vec![timed_op("a", 0, 1000), timed_op("b", 1000, 1000)];
+    results.time_range = Some((
+        results.operations[0].start_time,
+        results.operations[1].end_time,
+    ));
+    results.calculate_stats();
+
+    assert!((results.throughput.ops_per_sec - 1.0).abs() < f64::EPSILON);
+    assert_eq!(results.throughput.peak_concurrency, 1);
+    assert!((results.throughput.utilization_pct - 100.0).abs() < f64::EPSILON);
+    for stat in &results.stats {
+        assert!((stat.throughput.ops_per_sec - 0.5).abs() < f64::EPSILON);
+        assert!((stat.throughput.utilization_pct - 50.0).abs() < f64::EPSILON);
+    }
+
+    let json = perf_analyzer::format_perf_results_json(&results);
+    assert!(json.contains("\"ops_per_sec\""));
+    assert!(json.contains("\"utilization_pct\""));
+}
+
+#[test]
+fn test_negative_durations_are_classified_and_set_aside() {
+    let config = log_analyzer::config::load_config(None, Some("eyes")).expect("eyes preset");
+    let logs: Vec<_> = [
+        // Response logged after its request with an earlier timestamp
+        r#"core | 2026-01-01T00:00:30.000Z [INFO ] Request "check" [0--a] will be sent"#,
+        r#"core | 2026-01-01T00:00:00.000Z [INFO ] Request "check" [0--a] finished successfully"#,
+        // Response written before its request
+        r#"core | 2026-01-01T00:00:10.000Z [INFO ] Request "check" [0--b] finished successfully"#,
+        r#"core | 2026-01-01T00:00:12.000Z [INFO ] Request "check" [0--b] will be sent"#,
+        r#"core | 2026-01-01T00:00:20.000Z [INFO ] Request "check" [0--c] will be sent"#,
+        r#"core | 2026-01-01T00:00:21.000Z [INFO ] Request "check" [0--c] finished successfully"#,
+        r#"core | 2026-01-01T00:00:22.000Z [INFO ] Request "check" [0--d] will be sent"#,
+        r#"core | 2026-01-01T00:00:22.000Z [INFO ] Request "check" [0--d] finished successfully"#,
+        // Response whose request is never logged
+        r#"core | 2026-01-01T00:00:25.000Z [INFO ] Request "check" [0--e] finished successfully"#,
+    ]
+    .iter()
+    .enumerate()
+    .map(|(i, line)| {
+        log_analyzer::parser::parse_log_entry_with_config(line, i + 1, &config)
+            .expect("line parses")
+    })
+    .collect();
+
+    let mut results = perf_analyzer::analyze_performance_with_config(
+        &logs,
+        &log_analyzer::comparator::LogFilter::new(),
+        None,
+        &config,
+    );
+    let suspects: Vec<_> = results
+        .suspect_operations
+        .iter()
+        .map(|op| (op.correlation_id.as_deref(), op.duration_ms, op.suspect))
+        .collect();
+    assert_eq!(
+        suspects,
+        vec![
+            (
+                Some("0--a"),
+                -30_000,
+                Some(perf_analyzer::SuspectDuration::ClockSkew)
+            ),
+            (
+                Some("0--b"),
+                -2_000,
+                Some(perf_analyzer::SuspectDuration::Reordered)
+            ),
+        ]
+    );
+    assert_eq!(
+        results.suspect_durations,
+        perf_analyzer::SuspectDurationCounts {
+            clock_skew: 1,
+            reordered: 1,
+            zero_duration: 1,
+            included: false,
+        }
+    );
+    assert_eq!(results.operations.len(), 2);
+    assert!((results.stats[0].avg_duration_ms - 500.0).abs() < f64::EPSILON);
+    let orphans: Vec<_> = results
+        .orphans
+        .iter()
+        .map(|orphan| orphan.correlation_id.as_deref())
+        .collect();
+    assert_eq!(orphans, vec![Some("0--e")]);
+
+    results.include_suspect_durations(SessionAttribution::All);
+    assert!(results.suspect_operations.is_empty());
+    assert_eq!(results.stats[0].count, 4);
+    assert_eq!(results.stats[0].min_duration_ms, -30_000);
+}
+
+#[test]
+fn test_session_stats_split_parallel_sessions() {
+    let mut results = perf_analyzer::PerfAnalysisResults::new();
+    let mut fan_out = timed_op("render", 0, 1000);
+    fan_out.component_id = Some("manager-1/eyes-a & manager-1/eyes-b".to_string());
+    let mut single = timed_op("render", 0, 200);
+    single.component_id = Some("manager-1/eyes-a".to_string());
+    results.operations = vec![fan_out, single];
+
+    let session = |results: &perf_analyzer::PerfAnalysisResults, path: &str| {
+        results
+            .sessions
+            .iter()
+            .find(|session| session.session_path == path)
+            .map(|session| (session.operation_count, session.total_duration_ms))
+    };
+
+    results.calculate_session_stats(SessionAttribution::All);
+    assert_eq!(results.sessions.len(), 2);
+    assert_eq!(session(&results, "manager-1/eyes-a"), Some((2.0, 1200.0)));
+    assert_eq!(session(&results, "manager-1/eyes-b"), Some((1.0, 1000.0)));
+
+    results.calculate_session_stats(SessionAttribution::First);
+    assert_eq!(session(&results, "manager-1/eyes-a"), Some((2.0, 1200.0)));
+    assert_eq!(session(&results, "manager-1/eyes-b"), None);
+
+    results.calculate_session_stats(SessionAttribution::Proportional);
+    assert_eq!(session(&results, "manager-1/eyes-a"), Some((1.5, 700.0)));
+    assert_eq!(session(&results, "manager-1/eyes-b"), Some((0.5, 500.0)));
+}
+
+#[test]
+fn test_fairness_flags_session_waiting_while_others_run() {
+    let mut ops = Vec::new();
+    for start in [0, 1000, 2000] {
+        let mut op = timed_op("render", start, 1000);
+        op.component_id = Some("manager-1/eyes-a".to_string());
+        ops.push(op);
+    }
+    for (start, duration) in [(0, 100), (2500, 500)] {
+        let mut op = timed_op("render", start, duration);
+        op.component_id = Some("manager-1/eyes-b".to_string());
+        ops.push(op);
+    }
+
+    let report = perf_analyzer::analyze_fairness(
+        &[],
+        &log_analyzer::comparator::LogFilter::new(),
+        &ops,
+        &log_analyzer::config::SessionInsights::default(),
+        1000,
+    );
+
+    let starved: Vec<_> = report
+        .sessions
+        .iter()
+        .map(|s| (s.session_path.as_str(), s.busy_ms, s.waiting_ms, s.starved))
+        .collect();
+    assert_eq!(
+        starved,
+        vec![
+            ("manager-1/eyes-b", 600, 2400, true),
+            ("manager-1/eyes-a", 3000, 0, false),
+        ]
+    );
+
+    let indexes: Vec<_> = report
+        .buckets
+        .iter()
+        .map(|bucket| bucket.fairness_index.map(|i| (i * 100.0).round() / 100.0))
+        .collect();
+    assert_eq!(indexes, vec![Some(0.6), Some(0.5), Some(0.9)]);
+    assert_eq!(report.min_fairness_index, Some(0.5));
+}
+
+#[test]
+fn test_fairness_widens_buckets_on_long_logs() {
+    const WEEK_MS: i64 = 7 * 24 * 3_600_000;
+    let mut ops = Vec::new();
+    for (session, start) in [
+        ("manager-1/eyes-a", 0),
+        ("manager-1/eyes-b", WEEK_MS - 1000),
+    ] {
+        let mut op = timed_op("render", start, 1000);
+        op.component_id = Some(session.to_string());
+        ops.push(op);
+    }
+
+    let report = perf_analyzer::analyze_fairness(
+        &[],
+        &log_analyzer::comparator::LogFilter::new(),
+        &ops,
+        &log_analyzer::config::SessionInsights::default(),
+        1000,
+    );
+
+    assert!(
+        report.buckets.len() <= 1_000,
+        "{} buckets",
+        report.buckets.len()
+    );
+    assert!(report.bucket_ms > 1000);
+    let running: usize = report
+        .buckets
+        .iter()
+        .map(|bucket| bucket.running_sessions)
+        .sum();
+    assert_eq!(running, 2);
+}
+
+#[test]
+fn test_phases_decompose_check_sessions_with_medians() {
+    let phase = |name: &str, operations: &[&str]| log_analyzer::config::PhaseRule {
+        name: name.to_string(),
+        operations: operations.iter().map(|op| op.to_string()).collect(),
+    };
+    let phases = vec![
+        phase("dom snapshot", &["takeDomSnapshot"]),
+        phase("upload", &["uploadResource"]),
+        phase("render", &["render"]),
+        phase("match", &["match"]),
+    ];
+
+    let check_a = "manager-1/eyes-1/check-ufg-a";
+    let check_b = "manager-1/eyes-2/check-ufg-b";
+    let env_a = format!("{check_a}/environment-oja");
+    let render_a = format!("{env_a}/render-t7j/start-render-request-cly");
+    let upload_a = format!("{check_a}/upload-1 & {check_a}/upload-2");
+    let mut ops = Vec::new();
+    for (session, name, start, duration) in [
+        (check_a, "takeDomSnapshot", 0, 300),
+        (upload_a.as_str(), "uploadResource", 300, 200),
+        (upload_a.as_str(), "uploadResource", 350, 200),
+        (render_a.as_str(), "render", 600, 1000),
+        (env_a.as_str(), "match", 1600, 200),
+        (check_a, "close", 1800, 200),
+        (check_b, "takeDomSnapshot", 0, 100),
+        (
+            format!("{check_b}/environment-x/render-1").as_str(),
+            "render",
+            100,
+            400,
+        ),
+        ("manager-1", "close", 0, 5000),
+    ] {
+        let mut op = timed_op(name, start, duration);
+        op.component_id = Some(session.to_string());
+        ops.push(op);
+    }
+
+    let breakdown = perf_analyzer::analyze_phases(&ops, &phases, "check-");
+    let times = |times: &perf_analyzer::PhaseTimes| {
+        (
+            times.total_ms,
+            times
+                .phases
+                .iter()
+                .map(|phase| phase.duration_ms)
+                .collect::<Vec<_>>(),
+            times.other_ms,
+        )
+    };
+
+    let checks: Vec<_> = breakdown
+        .checks
+        .iter()
+        .map(|check| (check.session_path.as_str(), times(&check.times)))
+        .collect();
+    assert_eq!(
+        checks,
+        vec![
+            (check_a, (2000, vec![300, 250, 1000, 200], 250)),
+            (check_b, (500, vec![100, 0, 400, 0], 0)),
+        ]
+    );
+    assert_eq!(
+        times(&breakdown.median),
+        (1250, vec![200, 125, 700, 100], 125)
+    );
+}
+
+#[test]
+fn test_health_summary_scores_latency_budgets() {
+    let mut results = perf_analyzer::PerfAnalysisResults::new();
+    results.operations = (0..20)
+        .map(|idx| timed_op("check", idx * 100, if idx < 18 { 500 } else { 3000 }))
+        .chain([timed_op("open", 0, 100)])
+        .collect();
+    results.calculate_stats();
+
+    let mut config = log_analyzer::config::AnalyzerConfig::default();
+    config.perf.latency_budgets_ms = [("check".to_string(), 2000), ("open".to_string(), 1000)]
+        .into_iter()
+        .collect();
+
+    let health = log_analyzer::health::compute_health(
+        &[],
+        &log_analyzer::comparator::LogFilter::new(),
+        &results,
+        &config,
+    );
+
+    let checks: Vec<_> = health
+        .latency_budgets
+        .iter()
+        .map(|check| (check.name.as_str(), check.p95_ms, check.over_budget))
+        .collect();
+    assert_eq!(
+        checks,
+        vec![("check", Some(3000), true), ("open", Some(100), false)]
+    );
+    // check overshoots its budget by 50%, costing half of its 15-point share
+    assert_eq!(health.score, 93);
+    assert_eq!(health.verdict, "healthy");
+    assert_eq!(health.top_issues.len(), 1);
+    assert_eq!(
+        health.top_issues[0].description,
+        "check p95 3000ms over 2000ms budget"
+    );
+}
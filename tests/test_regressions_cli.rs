@@ -61,6 +61,65 @@ fn test_json_format_written_to_output_file_is_json() {
     );
 }
 
+#[test]
+fn test_diff_reports_identical_pair_counts_per_key() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("a.log");
+    let file2 = dir.path().join("b.log");
+
+    let content = concat!(
+        "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1}\n",
+        "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"foo\" [0--id2] will be sent with body {\"x\":1}\n",
+        "svc | 2026-01-01T00:00:02.000Z [INFO ] Request \"foo\" [0--id3] will be sent with body {\"x\":1}\n",
+        "svc | 2026-01-01T00:00:03.000Z [INFO ] Request \"bar\" [0--id4] will be sent with body {\"y\":1}\n",
+    );
+    write_file(&file1, content);
+    write_file(
+        &file2,
+        &content.replace(
+            "[0--id3] will be sent with body {\"x\":1}",
+            "[0--id3] will be sent with body {\"x\":2}",
+        ),
+    );
+    let (path1, path2) = (
+        file1.to_str().expect("utf8 path"),
+        file2.to_str().expect("utf8 path"),
+    );
+
+    let output = command()
+        .args(["--color", "never", "diff", path1, path2])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("3 identical pair(s) across 2 shared key(s)"),
+        "expected identical pair summary, got:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("(1 instances, 2 identical)"),
+        "expected identical count in key header, got:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("SHARED LOGS WITH ONLY IDENTICAL PAIRS")
+            && stdout.contains("1 identical  svc|INFO|Request|Send `bar`:"),
+        "expected fully identical key to be listed, got:\n{stdout}"
+    );
+
+    let output = command()
+        .args(["-F", "json", "diff", path1, path2])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(json["summary"]["identical_count"], 3);
+    assert_eq!(json["comparisons"][0]["identical_count"], 2);
+    assert_eq!(json["identical_counts"]["svc|INFO|Request|Send `bar`:"], 1);
+}
+
 #[test]
 fn test_full_diff_prints_full_json_payloads() {
     let dir = tempdir().expect("temp dir");
@@ -202,6 +261,262 @@ fn test_process_honors_output_file_flag() {
     );
 }
 
+#[test]
+fn test_serialized_entries_carry_typed_timestamps_and_paired_durations() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("input.log");
+    let config = dir.path().join("profile.toml");
+
+    write_file(
+        &file,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1}\n",
+            "svc | 2026-01-01T00:00:01.250Z [INFO ] Request \"foo\" [0--id1] finished successfully with body {\"ok\":true}\n",
+            "svc | 2026-01-01T00:00:02.000Z [INFO ] Request \"bar\" [0--id2] will be sent with body {\"x\":2}\n",
+        ),
+    );
+
+    let process = |config: Option<&std::path::Path>| {
+        let mut command = command();
+        if let Some(config) = config {
+            command.args(["--config", config.to_str().expect("utf8 path")]);
+        }
+        let output = command
+            .args(["process", file.to_str().expect("utf8 path")])
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).expect("valid json")
+    };
+
+    let json = process(None);
+    let logs = json["logs"].as_array().expect("logs array");
+    assert_eq!(logs[0]["at"], "2026-01-01T00:00:00.000Z");
+    assert_eq!(logs[0]["ms"], 1_767_225_600_000_i64);
+    assert!(
+        logs.iter().all(|log| log.get("dur").is_none()),
+        "durations are opt-in: {json}"
+    );
+
+    write_file(
+        &config,
+        concat!(
+            "[parser]\n",
+            "request_prefix = \"Request \\\"\"\n",
+            "request_send_markers = [\"will be sent\"]\n",
+            "request_receive_markers = [\"finished successfully\"]\n",
+            "request_payload_markers = [\"with body\"]\n",
+            "\n",
+            "[serialization]\n",
+            "durations = true\n",
+        ),
+    );
+    let json = process(Some(&config));
+    let logs = json["logs"].as_array().expect("logs array");
+    assert_eq!(logs[0]["dur"], 1250);
+    assert_eq!(logs[1]["dur"], 1250);
+    assert!(
+        logs[2].get("dur").is_none(),
+        "an unanswered request has no duration: {}",
+        logs[2]
+    );
+
+    write_file(&config, "[serialization]\ntimestamps = \"epoch-millis\"\n");
+    let output = command()
+        .args([
+            "--config",
+            config.to_str().expect("utf8 path"),
+            "-F",
+            "json",
+            "search",
+            file.to_str().expect("utf8 path"),
+            "--filter",
+            "t:foo",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    let entry = &json["search"]["entries"][1];
+    assert_eq!(entry["timestamp_ms"], 1_767_225_601_250_i64);
+    assert_eq!(
+        entry["timestamp"], "2026-01-01T00:00:01.250Z",
+        "the existing timestamp key stays"
+    );
+    assert!(entry.get("duration_ms").is_none(), "{entry}");
+}
+
+#[test]
+fn test_output_file_is_compressed_by_extension_and_broken_pipe_exits_quietly() {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("input.log");
+    let lines: String = (0..2_000)
+        .map(|i| {
+            format!("svc | 2026-01-01T00:00:00.000Z [ERROR] Request \"foo\" [0--id{i}] failed\n")
+        })
+        .collect();
+    write_file(&file, &lines);
+
+    let out = dir.path().join("errors.json.gz");
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "-o",
+            out.to_str().expect("utf8 path"),
+            "errors",
+        ])
+        .arg(&file)
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let mut report = String::new();
+    flate2::read::GzDecoder::new(fs::File::open(&out).expect("report exists"))
+        .read_to_string(&mut report)
+        .expect("report is gzip");
+    assert_eq!(
+        report.trim_end(),
+        String::from_utf8_lossy(&output.stdout).trim_end()
+    );
+    let leftovers: Vec<_> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .filter(|name| name.to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty(), "staging files left: {leftovers:?}");
+
+    let mut child = command()
+        .args(["search", "--context", "2"])
+        .arg(&file)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("command should start");
+    drop(child.stdout.take());
+    let output = child.wait_with_output().expect("command should finish");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    #[cfg(unix)]
+    let ended_by_pipe = {
+        use std::os::unix::process::ExitStatusExt;
+        output.status.signal() == Some(libc::SIGPIPE)
+    };
+    #[cfg(not(unix))]
+    let ended_by_pipe = false;
+    assert!(
+        (output.status.success() || ended_by_pipe) && stderr.is_empty(),
+        "expected a quiet exit on a closed stdout, got {:?}: {stderr}",
+        output.status
+    );
+}
+
+#[test]
+fn test_warnings_carry_codes_in_json_and_explain_describes_them() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("input.log");
+    write_file(
+        &file,
+        "svc | 2026-01-01T00:00:00.000Z [ERROR] Request \"foo\" [0--id1] failed\n",
+    );
+
+    let output = command()
+        .args(["-F", "json", "-f", "c:nothing-like-this l:EROR", "errors"])
+        .arg(&file)
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Warning [LA0002]: Unknown log level 'EROR'")
+            && stderr.contains("Warning [LA0001]: the filter matched none of the 1 entries"),
+        "expected coded warnings on stderr, got:\n{stderr}"
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let codes: Vec<&str> = report["warnings"]
+        .as_array()
+        .expect("warnings array")
+        .iter()
+        .map(|warning| warning["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(codes, ["LA0002", "LA0001"]);
+
+    let output = command()
+        .args(["--explain", "la0001"])
+        .output()
+        .expect("command should run");
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stdout).starts_with("LA0001: filter matched no entries")
+    );
+
+    let output = command()
+        .args(["--explain", "LA9999"])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("LA0005"));
+
+    let output = command().output().expect("command should run");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_process_embeds_legend_and_schema_version() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("input.log");
+    write_file(
+        &file,
+        "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1}\n",
+    );
+
+    let run = |legend: bool| {
+        let mut cmd = command();
+        if legend {
+            cmd.arg("--legend");
+        }
+        let output = cmd
+            .args(["process", file.to_str().expect("utf8 path")])
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).expect("valid JSON")
+    };
+
+    let plain = run(false);
+    assert_eq!(plain["schema_version"], 2);
+    assert!(plain.get("legend").is_none());
+
+    let with_legend = run(true);
+    assert_eq!(with_legend["legend"]["logs[]"]["lvl"], "level");
+    assert_eq!(with_legend["logs"][0]["typ"], "R:Send:foo");
+    assert_eq!(
+        with_legend["legend"]["logs[].typ"]["R:<direction>:<request>"],
+        "request"
+    );
+}
+
 #[test]
 fn test_perf_text_honors_output_file_flag() {
     let dir = tempdir().expect("temp dir");
@@ -385,6 +700,64 @@ fn test_diff_json_includes_unpaired_entries_in_unique_sections() {
     );
 }
 
+#[test]
+fn test_diff_json_instances_reference_source_entries() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("a.log");
+    let file2 = dir.path().join("b.log");
+
+    write_file(
+        &file1,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"bar\" [0--id0] will be sent with body {\"y\":1}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1}\n",
+        ),
+    );
+    write_file(
+        &file2,
+        "svc | 2026-01-01T00:00:05.000Z [INFO ] Request \"foo\" [0--id9] will be sent with body {\"x\":2}\n",
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "diff",
+            file1.to_str().expect("utf8 path"),
+            file2.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let instance = &parsed["comparisons"][0]["instances"][0];
+
+    assert_eq!(instance["log1_source"]["id"], "1:2", "output:\n{stdout}");
+    assert_eq!(instance["log1_source"]["line"], 2);
+    assert_eq!(instance["log1_source"]["request_id"], "0--id1");
+    assert_eq!(instance["log2_source"]["id"], "2:1");
+    assert_eq!(instance["log2_source"]["request_id"], "0--id9");
+    assert_eq!(
+        instance["log2_source"]["timestamp"],
+        "2026-01-01T00:00:05.000Z"
+    );
+
+    let unique = &parsed["unique_to_log1"][0];
+    assert_eq!(unique["sources"][0]["id"], "1:1", "output:\n{stdout}");
+    assert_eq!(unique["sources"][0]["request_id"], "0--id0");
+    assert_eq!(
+        unique["sources"][0]["timestamp"],
+        "2026-01-01T00:00:00.000Z"
+    );
+}
+
 #[test]
 fn test_diff_text_includes_unpaired_entries_in_unique_sections() {
     let dir = tempdir().expect("temp dir");
@@ -546,6 +919,56 @@ fn test_trace_by_session_filters_using_component_id_hierarchy() {
     );
 }
 
+#[test]
+fn test_trace_diff_commands_reports_settings_drift_per_session() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("commands.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1/check-a1) | 2026-01-01T00:00:00.000Z [INFO ] Command \"check\" is called with settings {\"matchLevel\":\"Strict\",\"timeout\":100}\n",
+            "core (manager-1/eyes-2/check-b1) | 2026-01-01T00:00:00.500Z [INFO ] Command \"check\" is called with settings {\"matchLevel\":\"Layout\",\"timeout\":100}\n",
+            "core (manager-1/eyes-1/check-a2) | 2026-01-01T00:00:01.000Z [INFO ] Command \"check\" is called with settings {\"matchLevel\":\"Strict\",\"timeout\":100}\n",
+            "core (manager-1/eyes-1/check-a3) | 2026-01-01T00:00:02.000Z [INFO ] Command \"check\" is called with settings {\"matchLevel\":\"Strict\",\"timeout\":250}\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "trace",
+            file.to_str().expect("utf8 path"),
+            "--diff-commands",
+            "check",
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("command diff should be JSON");
+    let report = &json["command_diff"];
+    assert_eq!(report["invocations"], 4);
+    assert_eq!(report["sessions"], 2);
+    assert_eq!(report["changed_pairs"], 1);
+
+    let pairs = report["pairs"].as_array().expect("pairs");
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[0]["changed"], false);
+    assert_eq!(pairs[1]["session"], "manager-1/eyes-1");
+    assert_eq!(
+        pairs[1]["differences"],
+        serde_json::json!([{"path": "timeout", "before": 100, "after": 250}])
+    );
+}
+
 #[test]
 fn test_generate_config_merges_multiple_logs_for_inference() {
     let dir = tempdir().expect("temp dir");
@@ -741,6 +1164,95 @@ fn test_search_context_shows_neighbor_entries_only() {
     );
 }
 
+#[test]
+fn test_search_session_and_request_id_flags_select_like_trace() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("sessions.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--aaa] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-2) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--bbb] will be sent with body {\"x\":2}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"render\" [0--ccc] will be sent with body {\"x\":3}\n",
+        ),
+    );
+
+    let search = |args: &[&str]| {
+        let output = command()
+            .args(["-F", "json", "search", file.to_str().expect("utf8 path")])
+            .args(args)
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+        json["search"]["entries"]
+            .as_array()
+            .expect("entries array")
+            .iter()
+            .map(|entry| entry["source_line_number"].as_u64().expect("line"))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(search(&["--session", "eyes-1"]), vec![1, 3]);
+    assert_eq!(search(&["--request-id", "0--bbb"]), vec![2]);
+    assert_eq!(
+        search(&["--session", "eyes-1", "--request-id", "0--ccc"]),
+        vec![3]
+    );
+    assert_eq!(
+        search(&["--session", "manager-1", "-f", "t:render"]),
+        vec![3]
+    );
+}
+
+#[test]
+fn test_search_payloads_elide_the_middle_of_giant_arrays() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("resources.log");
+    let resources: Vec<String> = (0..300).map(|i| format!("\"r{i}\"")).collect();
+
+    write_file(
+        &file,
+        &format!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"upload\" [0--id1] will be sent with body {{\"resources\":[{}]}}\n",
+            resources.join(",")
+        ),
+    );
+
+    let search = |args: &[&str]| {
+        let output = command()
+            .args(args)
+            .args(["search", file.to_str().expect("utf8 path"), "--payloads"])
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let stdout = search(&["--max-array-elements", "4"]);
+    assert!(
+        stdout.contains(r#"["r0","r1","… 296 of 300 elements omitted …","r298","r299"]"#),
+        "expected first/last elements around an omission marker, got:\n{}",
+        stdout
+    );
+
+    let stdout = search(&["--max-array-elements", "0"]);
+    assert!(
+        stdout.contains("\"r150\"") && !stdout.contains("omitted"),
+        "expected the full array with no limit, got:\n{}",
+        stdout
+    );
+}
+
 #[test]
 fn test_search_count_by_payload_groups_duplicate_payloads() {
     let dir = tempdir().expect("temp dir");
@@ -793,192 +1305,295 @@ fn test_search_count_by_payload_groups_duplicate_payloads() {
 }
 
 #[test]
-fn test_extract_aggregates_payload_field_values() {
+fn test_search_count_by_time_uses_sub_second_buckets_for_bursts() {
     let dir = tempdir().expect("temp dir");
-    let file = dir.path().join("extract.log");
+    let file = dir.path().join("burst.log");
 
     write_file(
         &file,
         concat!(
-            "core | 2026-01-01T00:00:00.000Z [INFO ] Request \"makeManager\" [0--id1] will be sent with body {\"concurrency\":100,\"env\":\"prod\"}\n",
-            "core | 2026-01-01T00:00:01.000Z [INFO ] Request \"makeManager\" [0--id2] will be sent with body {\"concurrency\":100,\"env\":\"prod\"}\n",
-            "core | 2026-01-01T00:00:02.000Z [INFO ] Request \"makeManager\" [0--id3] will be sent with body {\"concurrency\":50,\"env\":\"staging\"}\n",
-            "core | 2026-01-01T00:00:03.000Z [INFO ] Request \"otherCall\" [0--id4] will be sent with body {\"concurrency\":999}\n",
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Render started\n",
+            "svc | 2026-01-01T00:00:00.040Z [INFO ] Render started\n",
+            "svc | 2026-01-01T00:00:00.350Z [INFO ] Render started\n",
+            "svc | 2026-01-01T00:00:01.200Z [INFO ] Render started\n",
         ),
     );
+    let path = file.to_str().expect("utf8 path");
 
     let output = command()
-        .args([
-            "extract",
-            file.to_str().expect("utf8 path"),
-            "-f",
-            "t:makeManager",
-            "--field",
-            "concurrency",
-        ])
+        .args(["search", path, "--count-by", "time"])
         .output()
         .expect("command should run");
-
     assert!(
         output.status.success(),
         "stderr: {}",
         String::from_utf8_lossy(&output.stderr)
     );
-
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        stdout.contains("concurrency=100 (2 occurrences)")
-            && stdout.contains("concurrency=50 (1 occurrence)"),
-        "expected grouped extracted values, got:\n{}",
-        stdout
+        stdout.contains("SEARCH count by time (4 entries, 100ms buckets)"),
+        "expected 100ms buckets for a 1.2s burst, got:\n{stdout}"
     );
     assert!(
-        !stdout.contains("999"),
-        "expected non-matching entry to be excluded, got:\n{}",
-        stdout
+        stdout.contains("00:00:00.000       2") && stdout.contains("00:00:00.100       0"),
+        "expected empty buckets to be listed chronologically, got:\n{stdout}"
     );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "search",
+            path,
+            "--count-by",
+            "time",
+            "--bucket",
+            "100ms",
+            "--max-buckets",
+            "3",
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(json["search"]["bucket_ms"], 500);
+    assert_eq!(json["search"]["bucket"], "500ms");
+    let counts: Vec<u64> = json["search"]["groups"]
+        .as_array()
+        .expect("groups")
+        .iter()
+        .map(|group| group["count"].as_u64().unwrap())
+        .collect();
+    assert_eq!(counts, vec![3, 0, 1]);
 }
 
 #[test]
-fn test_search_and_extract_support_rust_tracing_structured_fields() {
+fn test_count_groups_matching_entries_across_files_without_payloads() {
     let dir = tempdir().expect("temp dir");
-    let file = dir.path().join("flux.log");
+    let file1 = dir.path().join("count1.log");
+    let file2 = dir.path().join("count2.log");
 
     write_file(
-        &file,
+        &file1,
         concat!(
-            "2026-03-10T08:15:30.123Z INFO fluxomni_server::integrations::srs::callback: callback received trace_id=abc123 actor_kind=switch restream_name=stream-a\n",
-            "2026-03-10T08:15:31.123Z INFO fluxomni_server::integrations::srs::callback: callback received trace_id=abc456 actor_kind=switch restream_name=stream-a\n",
-            "2026-03-10T08:15:32.123Z INFO fluxomni_server::integrations::srs::callback: callback received trace_id=def999 actor_kind=relay restream_name=stream-b\n",
+            "svc | 2026-01-01T00:00:00.000Z [ERROR] Request \"open\" [0--id1] will be sent with body {\"limit\":2}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"open\" [0--id2] will be sent with body {\"limit\":2}\n",
+            "api | 2026-01-01T00:00:02.000Z [ERROR] Request \"close\" [0--id3] will be sent with body {\"limit\":3}\n",
         ),
     );
+    write_file(
+        &file2,
+        "svc | 2026-01-01T00:00:03.000Z [ERROR] Request \"open\" [0--id4] will be sent with body {\"limit\":4}\n",
+    );
 
-    let search_output = command()
+    let output = command()
         .args([
-            "search",
-            file.to_str().expect("utf8 path"),
+            "-F",
+            "json",
+            "count",
+            file1.to_str().expect("utf8 path"),
+            file2.to_str().expect("utf8 path"),
             "-f",
-            "actor_kind:switch",
-            "--payloads",
+            "level:ERROR",
+            "--by",
+            "component",
         ])
         .output()
-        .expect("search should run");
+        .expect("command should run");
 
     assert!(
-        search_output.status.success(),
+        output.status.success(),
         "stderr: {}",
-        String::from_utf8_lossy(&search_output.stderr)
+        String::from_utf8_lossy(&output.stderr)
     );
 
-    let search_stdout = String::from_utf8_lossy(&search_output.stdout);
-    assert!(
-        search_stdout.contains("SEARCH matched 2 entries"),
-        "expected structured-field filter to match two entries, got:\n{}",
-        search_stdout
-    );
-    assert!(
-        search_stdout.contains("\"restream_name\":\"stream-a\""),
-        "expected structured fields to be shown with --payloads, got:\n{}",
-        search_stdout
-    );
-    assert!(
-        !search_stdout.contains("stream-b"),
-        "expected non-matching structured field values to be excluded, got:\n{}",
-        search_stdout
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("count output should be JSON");
+    assert_eq!(json["count"]["total"], 3);
+    assert_eq!(json["count"]["by"], "component");
+    assert_eq!(
+        json["count"]["groups"],
+        serde_json::json!([
+            {"key": "svc", "count": 2},
+            {"key": "api", "count": 1},
+        ])
     );
 
-    let extract_output = command()
-        .args([
-            "extract",
-            file.to_str().expect("utf8 path"),
-            "-f",
-            "actor_kind:switch",
-            "--field",
-            "restream_name",
-        ])
+    let output = command()
+        .args(["count", file1.to_str().expect("utf8 path")])
         .output()
-        .expect("extract should run");
+        .expect("command should run");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "3\n");
+}
 
-    assert!(
-        extract_output.status.success(),
-        "stderr: {}",
-        String::from_utf8_lossy(&extract_output.stderr)
+#[test]
+fn test_count_by_type_matches_search_for_generic_payload_lines() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("generic.log");
+    write_file(
+        &file,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Render finished with result {\"id\":1}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Render finished with result {\"id\":2}\n",
+            "svc | 2026-01-01T00:00:02.000Z [INFO ] Render finished with result [1, 2]\n",
+            "svc | 2026-01-01T00:00:03.000Z [INFO ] Retrying [0--id1] soon\n",
+        ),
     );
+    let path = file.to_str().expect("utf8 path");
 
-    let extract_stdout = String::from_utf8_lossy(&extract_output.stdout);
-    assert!(
-        extract_stdout.contains("restream_name=\"stream-a\" (2 occurrences)"),
-        "expected structured field extraction to aggregate values, got:\n{}",
-        extract_stdout
+    let groups = |args: &[&str]| {
+        let output = command()
+            .args(["-F", "json"])
+            .args(args)
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("output should be JSON");
+        json
+    };
+
+    let count = groups(&["count", path, "--by", "type"]);
+    let search = groups(&["search", path, "--count-by", "type"]);
+    assert_eq!(count["count"]["groups"], search["search"]["groups"]);
+    assert_eq!(
+        count["count"]["groups"][0],
+        serde_json::json!({"key": "Generic|Render finished with result [JSON removed]:", "count": 3})
     );
 }
 
 #[test]
-fn test_trace_by_id_matches_rust_tracing_trace_fields() {
+fn test_count_matches_search_for_missing_field_filters() {
     let dir = tempdir().expect("temp dir");
-    let file = dir.path().join("trace.log");
+    let file = dir.path().join("requests.log");
+    write_file(
+        &file,
+        concat!(
+            "core | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"renders\":[1]}\n",
+            "core | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id2] will be sent with body {\"other\":1}\n",
+            "core | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--id3] will be sent with body {\"other\":2}\n",
+            "core | 2026-01-01T00:00:03.000Z [INFO ] Render started\n",
+        ),
+    );
+    let path = file.to_str().expect("utf8 path");
+    let run = |args: &[&str]| {
+        let output = command()
+            .args(["-F", "json"])
+            .args(args)
+            .output()
+            .expect("command should run");
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).expect("output should be JSON")
+    };
+
+    for filter in ["missing:renders", "!missing:renders"] {
+        let count = run(&["count", path, "-f", filter]);
+        let search = run(&["search", path, "-f", filter]);
+        assert_eq!(
+            count["count"]["total"], search["search"]["matches"],
+            "count and search disagree on {filter}"
+        );
+    }
+    assert_eq!(
+        run(&["count", path, "-f", "missing:renders"])["count"]["total"],
+        2
+    );
+}
 
+#[test]
+fn test_command_line_flags_override_environment_defaults() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("env.log");
     write_file(
         &file,
         concat!(
-            "2026-03-10T08:15:30.123Z INFO fluxomni_server::scheduler::ingress: stream accepted trace_id=fabb5aa4 span_id=span-1 actor_kind=switch\n",
-            "2026-03-10T08:15:30.523Z INFO fluxomni_server::scheduler::ingress: stream queued trace_id=fabb5aa4 span_id=span-1 actor_kind=switch\n",
-            "2026-03-10T08:15:31.523Z ERROR fluxomni_server::scheduler::ingress: stream failed trace_id=fabb5aa4 span_id=span-1 exit_code=251\n",
-            "2026-03-10T08:15:32.523Z INFO fluxomni_server::scheduler::ingress: other trace trace_id=zzzz9999 span_id=span-2 actor_kind=switch\n",
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Render started\n",
+            "svc | 2026-01-01T00:00:01.000Z [ERROR] Render failed\n",
         ),
     );
+    let path = file.to_str().expect("utf8 path");
+    let run = |envs: &[(&str, &str)], args: &[&str]| {
+        let output = command()
+            .envs(envs.iter().copied())
+            .args(["search", path, "--count-by", "level"])
+            .args(args)
+            .output()
+            .expect("command should run");
+        (
+            output.status.success(),
+            String::from_utf8_lossy(&output.stdout).into_owned(),
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        )
+    };
 
-    let output = command()
-        .args([
-            "trace",
-            file.to_str().expect("utf8 path"),
-            "--id",
-            "fabb5aa4",
-        ])
-        .output()
-        .expect("trace should run");
+    let (ok, stdout, stderr) = run(
+        &[
+            ("LOG_ANALYZER_FORMAT", "json"),
+            ("LOG_ANALYZER_COMPACT", "true"),
+            ("LOG_ANALYZER_FILTER", "l:ERROR"),
+        ],
+        &[],
+    );
+    assert!(ok, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid json");
+    assert_eq!(json["search"]["matches"], 1, "env filter should apply");
 
+    let (ok, stdout, stderr) = run(
+        &[
+            ("LOG_ANALYZER_JSON", "true"),
+            ("LOG_ANALYZER_FILTER", "l:ERROR"),
+        ],
+        &["-F", "text", "-f", "l:INFO"],
+    );
+    assert!(ok, "stderr: {stderr}");
     assert!(
-        output.status.success(),
-        "stderr: {}",
-        String::from_utf8_lossy(&output.stderr)
+        stdout.contains("SEARCH count by level (1 entry)") && stdout.contains("INFO"),
+        "command-line format and filter should win over the environment, got:\n{stdout}"
     );
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(
-        stdout.contains("Matched 3 entries"),
-        "expected trace_id-based matching to find three entries, got:\n{}",
-        stdout
+    // The test command sets LOG_ANALYZER_PRESET; an explicit --config replaces it
+    let config = dir.path().join("profile.toml");
+    write_file(&config, "");
+    let (ok, _, stderr) = run(
+        &[],
+        &["-v", "--config", config.to_str().expect("utf8 path")],
     );
+    assert!(ok, "stderr: {stderr}");
     assert!(
-        stdout.contains("+   400ms") && stdout.contains("+  1000ms"),
-        "expected chronological timing deltas, got:\n{}",
-        stdout
+        stderr.contains("Config file:") && !stderr.contains("Config preset:"),
+        "expected --config to replace the environment preset, got:\n{stderr}"
     );
+
+    let (ok, _, stderr) = run(&[], &["-j", "-F", "text"]);
     assert!(
-        !stdout.contains("zzzz9999"),
-        "expected unrelated trace_id entries to be excluded, got:\n{}",
-        stdout
+        !ok,
+        "conflicting command-line flags should still be rejected"
     );
+    assert!(stderr.contains("'--json' and '--format' cannot be used together"));
 }
 
 #[test]
-fn test_errors_defaults_to_error_only_and_normalizes_cluster_pattern() {
+fn test_meta_pairs_are_embedded_in_json_output() {
     let dir = tempdir().expect("temp dir");
-    let file = dir.path().join("errors.log");
-
+    let file = dir.path().join("meta.log");
     write_file(
         &file,
-        concat!(
-            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--rid-1] will be sent with body {\"x\":1}\n",
-            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"5bfcc412-1fd6-4f8d-a6d5-246f90f3e7ab\" failed due to an error - internal failure\n",
-            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--rid-1] finished successfully with body {\"statusCode\":200}\n",
-            "core (manager-2/eyes-2/check-2) | 2026-01-01T00:00:03.000Z [WARN ] Warning - Invalid keys in check settings (will be ignored)\n",
-        ),
+        "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"open\" [0--id1] will be sent with body {\"limit\":2}\n",
     );
 
     let output = command()
-        .args(["errors", file.to_str().expect("utf8 path")])
+        .args([
+            "-F",
+            "json",
+            "count",
+            file.to_str().expect("utf8 path"),
+            "--meta",
+            "run_id=123",
+            "--meta",
+            "branch=feature=x",
+        ])
         .output()
         .expect("command should run");
 
@@ -987,50 +1602,195 @@ fn test_errors_defaults_to_error_only_and_normalizes_cluster_pattern() {
         "stderr: {}",
         String::from_utf8_lossy(&output.stderr)
     );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("count output should be JSON");
+    assert_eq!(
+        json["meta"],
+        serde_json::json!({"run_id": "123", "branch": "feature=x"})
+    );
+    assert_eq!(json["count"]["total"], 1);
 
+    let output = command()
+        .args([
+            "-j",
+            "diff",
+            file.to_str().expect("utf8 path"),
+            file.to_str().expect("utf8 path"),
+            "--meta",
+            "run_id=123",
+        ])
+        .output()
+        .expect("command should run");
     let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(
-        stdout.contains("ERRORS: 1 entries (1 patterns) across 1 file"),
-        "expected error-only header, got:\n{}",
-        stdout
+    assert_eq!(
+        stdout.trim_end().lines().count(),
+        1,
+        "compact JSON stays on one line"
     );
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("diff output should be JSON");
+    assert_eq!(json["meta"]["run_id"], "123");
+
+    let output = command()
+        .args([
+            "count",
+            file.to_str().expect("utf8 path"),
+            "--meta",
+            "novalue",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_lines_and_bytes_scope_reads_a_slice_and_note_it_in_headers() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("big.log");
+    let text: String = (0..10)
+        .map(|n| format!("svc | 2026-01-01T00:00:0{n}.000Z [INFO ] Entry number {n}\n"))
+        .collect();
+    write_file(&file, &text);
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "--lines",
+            "3-5",
+            "count",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
     assert!(
-        stdout.contains("Render with id \"...\" failed due to an error - internal failure"),
-        "expected normalized error cluster pattern, got:\n{}",
-        stdout
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
     );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("count output should be JSON");
+    assert_eq!(json["count"]["total"], 3);
+    assert_eq!(json["meta"]["scope"], "lines 3-5");
+
+    let line_len = text.lines().next().map(str::len).unwrap_or_default() + 1;
+    let output = command()
+        .args([
+            "--bytes",
+            &format!("{}-", line_len * 8),
+            "count",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        !stdout.contains("Warning - Invalid keys in check settings"),
-        "expected WARN entries to be excluded by default, got:\n{}",
-        stdout
+        stdout.starts_with("Scope: bytes ") && stdout.contains("of each file"),
+        "stdout: {stdout}"
     );
+
+    let path = file.to_str().expect("utf8 path");
+    for args in [
+        vec!["compare", path, path],
+        vec!["info", path],
+        vec!["perf", path],
+    ] {
+        let output = command()
+            .args(["--lines", "1-2"])
+            .args(&args)
+            .output()
+            .expect("command should run");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("Scope: lines 1-2 of each file"),
+            "{args:?} stdout: {stdout}"
+        );
+    }
+    let output = command()
+        .args(["--lines", "1-2", "info", path, "--field-matrix", "--csv"])
+        .output()
+        .expect("command should run");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Scope:"));
+
+    let output = command()
+        .args(["--lines", "5-3", "count", file.to_str().expect("utf8 path")])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
 }
 
 #[test]
-fn test_errors_warn_and_sessions_show_completed_and_orphaned_sessions() {
+fn test_relative_since_until_filters_resolve_against_now_or_log_end() {
     let dir = tempdir().expect("temp dir");
-    let file = dir.path().join("errors_sessions.log");
+    let file = dir.path().join("minutes.log");
+    let text: String = (0..10)
+        .map(|n| format!("svc | 2026-01-01T00:0{n}:00.000Z [INFO ] Minute {n}\n"))
+        .collect();
+    write_file(&file, &text);
+
+    let count = |args: &[&str]| {
+        let output = command()
+            .args(["-F", "json"])
+            .args(args)
+            .args(["count", file.to_str().expect("utf8 path")])
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("count output should be JSON");
+        json["count"]["total"].clone()
+    };
+
+    let now = "2026-01-01T00:10:00Z";
+    assert_eq!(count(&["--now", now, "-f", "since:5m until:now-2m"]), 4);
+    assert_eq!(
+        count(&["--now", now, "-f", "since:2026-01-01T00:08:00Z"]),
+        2
+    );
+    assert_eq!(count(&["--relative-to", "log-end", "-f", "since:2m"]), 3);
+    assert_eq!(count(&["-f", "since:1h"]), 0);
+
+    let output = command()
+        .args([
+            "-f",
+            "since:lately",
+            "count",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_within_filter_excludes_entries_near_anchor_matches() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("retries.log");
 
     write_file(
         &file,
         concat!(
-            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--req-complete] will be sent with body {\"x\":1}\n",
-            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"5bfcc412-1fd6-4f8d-a6d5-246f90f3e7ab\" failed due to an error - internal failure\n",
-            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--req-complete] finished successfully with body {\"statusCode\":200}\n",
-            "core (manager-2/eyes-2/check-2) | 2026-01-01T00:00:03.000Z [INFO ] Request \"check\" [0--req-orphan] will be sent with body {\"x\":1}\n",
-            "core (manager-2/eyes-2/check-2) | 2026-01-01T00:00:04.000Z [ERROR] Render with id \"aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee\" failed due to an error - internal failure\n",
-            "core (manager-2/eyes-2/check-2) | 2026-01-01T00:00:05.000Z [WARN ] Warning - Invalid keys in check settings (will be ignored)\n",
+            "svc | 2026-01-01T00:00:00.000Z [WARN ] Upload failed, scheduling retry\n",
+            "svc | 2026-01-01T00:00:01.000Z [ERROR] Connection reset\n",
+            "svc | 2026-01-01T00:00:02.500Z [ERROR] Connection reset\n",
+            "svc | 2026-01-01T00:00:10.000Z [ERROR] Disk full\n",
         ),
     );
 
     let output = command()
         .args([
-            "errors",
+            "-F",
+            "json",
+            "count",
             file.to_str().expect("utf8 path"),
-            "--warn",
-            "--sessions",
-            "--top-n",
-            "0",
+            "-f",
+            "level:ERROR !within:2s-of:text:retry",
+            "--by",
+            "type",
         ])
         .output()
         .expect("command should run");
@@ -1041,30 +1801,3112 @@ fn test_errors_warn_and_sessions_show_completed_and_orphaned_sessions() {
         String::from_utf8_lossy(&output.stderr)
     );
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(
-        stdout.contains("ERRORS/WARNS: 3 entries (2 patterns) across 1 file"),
-        "expected combined error/warn header, got:\n{}",
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("count output should be JSON");
+    assert_eq!(json["count"]["total"], 2);
+
+    let output = command()
+        .args([
+            "count",
+            file.to_str().expect("utf8 path"),
+            "-f",
+            "within:2s-of:text:retry",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_dupes_groups_reworded_entries_with_differing_counts() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("old.log");
+    let file2 = dir.path().join("new.log");
+    write_file(
+        &file1,
+        concat!(
+            "core | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"a1\" failed due to an error - internal failure\n",
+            "core | 2026-01-01T00:00:02.000Z [ERROR] Render with id \"a2\" failed due to an error - internal failure\n",
+            "core | 2026-01-01T00:00:03.000Z [INFO ] Uploading resources to the server finished\n",
+        ),
+    );
+    write_file(
+        &file2,
+        concat!(
+            "core | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"b1\" has failed due to an error - internal failure\n",
+            "core | 2026-01-01T00:00:02.000Z [INFO ] Uploading resources to the server finished\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "dupes",
+            file1.to_str().expect("utf8 path"),
+            file2.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("2 group(s) occur in both files, 1 with differing counts")
+            && stdout.contains("core  old.log ×2 → new.log ×1  (-1)")
+            && stdout.contains("old.log:1: [ERROR] Render with id \"...\" failed due to")
+            && stdout.contains("new.log:1: [ERROR] Render with id \"...\" has failed due to"),
+        "expected the reworded render errors in one group, got:\n{}",
         stdout
     );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "dupes",
+            file1.to_str().expect("utf8 path"),
+            file2.to_str().expect("utf8 path"),
+            "--max-distance",
+            "0",
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(json["dupes"]["groups_in_both"], 1);
+    assert_eq!(json["dupes"]["groups"], serde_json::json!([]));
+}
+
+#[test]
+fn test_similar_ranks_entries_by_template_and_payload_shape() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("similar1.log");
+    let file2 = dir.path().join("similar2.log");
+
+    write_file(
+        &file1,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [ERROR] Upload of batch 17 failed with body {\"retry\":1,\"reason\":\"quota\"}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Session started for user alice\n",
+        ),
+    );
+    write_file(
+        &file2,
+        concat!(
+            "svc | 2026-01-01T00:00:02.000Z [ERROR] Upload of batch 42 failed with body {\"retry\":3,\"reason\":\"timeout\"}\n",
+            "svc | 2026-01-01T00:00:03.000Z [ERROR] Upload of batch 43 failed\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "similar",
+            file1.to_str().expect("utf8 path"),
+            file2.to_str().expect("utf8 path"),
+            "--to-line",
+            "1",
+            "--top",
+            "2",
+        ])
+        .output()
+        .expect("command should run");
+
     assert!(
-        stdout.contains("Warning - Invalid keys in check settings (will be ignored)"),
-        "expected WARN cluster with --warn, got:\n{}",
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("similar output should be JSON");
+    assert_eq!(json["similar"]["reference"]["line"], 1);
+    let matches = json["similar"]["matches"].as_array().expect("matches");
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0]["line"], 1);
+    assert!(
+        matches[0]["file"]
+            .as_str()
+            .expect("file")
+            .ends_with("similar2.log")
+    );
+    assert_eq!(matches[0]["shape_score"], 1.0);
+    assert_eq!(matches[1]["line"], 2);
+    assert_eq!(matches[1]["shape_score"], 0.0);
+
+    let output = command()
+        .args([
+            "similar",
+            file1.to_str().expect("utf8 path"),
+            "--to-line",
+            "0",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_show_diff_writes_self_contained_html() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("a.log");
+    let file2 = dir.path().join("b.log");
+    let html = dir.path().join("diff.html");
+
+    write_file(
+        &file1,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Command \"check\" is called with settings {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"settings\":{\"retries\":[1,2],\"name\":\"<old>\"}}\n",
+        ),
+    );
+    write_file(
+        &file2,
+        "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"settings\":{\"retries\":[1,3],\"name\":\"<old>\",\"extra\":true}}\n",
+    );
+
+    let output = command()
+        .args([
+            "show-diff",
+            &format!("{}:2", file1.to_str().expect("utf8 path")),
+            &format!("{}:1", file2.to_str().expect("utf8 path")),
+            "--html",
+            html.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Payload differences: 2")
+            && stdout.contains("~ settings.retries[1]: 2 → 3")
+            && stdout.contains("+ settings.extra: null → true"),
+        "expected payload differences, got:\n{}",
         stdout
     );
+
+    let page = fs::read_to_string(&html).expect("html output");
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(!page.contains("<script") && !page.contains("<link"));
+    assert!(page.contains("<li class=\"changed\"><span class=\"key\">[1]</span>: 3</li>"));
+    assert!(page.contains("<li class=\"added\"><span class=\"key\">extra</span>: true</li>"));
+    assert!(page.contains("&quot;&lt;old&gt;&quot;"));
+}
+
+#[test]
+fn test_chronology_reports_reordering_and_pairing_changes() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("chrono.log");
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.500Z [INFO ] Request \"check\" [0--id1] finished successfully with body {\"statusCode\":200}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"open\" [0--id2] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:02.300Z [INFO ] Request \"open\" [0--id2] finished successfully with body {\"statusCode\":200}\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "chronology",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+
     assert!(
-        stdout.contains("manager-1/eyes-1/check-1") && stdout.contains("completed"),
-        "expected completed session status, got:\n{}",
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("chronology output should be JSON");
+    let report = &json["chronology"];
+    assert_eq!(report["trustworthy"], false);
+    assert_eq!(report["files"][0]["out_of_order"], 1);
+    assert_eq!(report["files"][0]["max_backward_ms"], 500);
+    assert_eq!(report["files"][0]["max_backward_line"], 2);
+    assert_eq!(report["files"][0]["components"]["core"]["out_of_order"], 1);
+    // The response logged with an earlier timestamp still pairs after the re-sort
+    assert_eq!(report["pairing"]["changed"], false);
+    assert_eq!(report["pairing"]["operations_source_order"], 2);
+    assert_eq!(report["pairing"]["operations_sorted"], 2);
+    assert_eq!(report["pairing"]["orphans_sorted"], 0);
+}
+
+#[test]
+fn test_capture_fixture_trims_redacts_and_loads_back() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("source.log");
+    let config = dir.path().join("profile.toml");
+    let fixture_dir = dir.path().join("fixtures");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"apiKey\":\"secret\",\"x\":1}\n",
+            "core (manager-1/eyes-2) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id2] will be sent with body {\"apiKey\":\"other\",\"x\":2}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--id3] will be sent with body {\"apiKey\":{\"token\":\"third\",\"scopes\":[\"a\"]},\"x\":3}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:05.000Z [INFO ] Request \"check\" [0--id4] will be sent with body {\"apiKey\":\"late\",\"x\":4}\n",
+        ),
+    );
+    write_file(&config, "[fixtures]\nredact_fields = [\"apiKey\"]\n");
+
+    let output = Command::new(bin())
+        .args([
+            "--config",
+            config.to_str().expect("utf8 path"),
+            "capture-fixture",
+            file.to_str().expect("utf8 path"),
+            "--name",
+            "slice",
+            "--session",
+            "eyes-1",
+            "--until",
+            "2026-01-01T00:00:03Z",
+            "--dir",
+            fixture_dir.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let captured = fs::read_to_string(fixture_dir.join("slice.log")).expect("fixture log");
+    assert!(
+        !captured.contains("secret") && captured.contains("\"apiKey\":\"[REDACTED]\""),
+        "expected redacted payloads, got:\n{}",
+        captured
+    );
+    assert!(
+        !captured.contains("third") && captured.contains("\"apiKey\":\"[REDACTED]\",\"x\":3}"),
+        "expected the redacted object to be replaced whole, got:\n{}",
+        captured
+    );
+
+    let profile = log_analyzer::config::load_config_from_path(&config).expect("profile");
+    let (manifest, logs) = log_analyzer::fixtures::load_fixture(&fixture_dir, "slice", &profile)
+        .expect("fixture should load");
+    assert_eq!(manifest.entry_count, 2);
+    assert_eq!(manifest.source_lines, Some((1, 3)));
+    assert_eq!(manifest.until.as_deref(), Some("2026-01-01T00:00:03.000Z"));
+    assert!(
+        logs.iter()
+            .all(|log| log.component_id == "manager-1/eyes-1")
+    );
+
+    let output = Command::new(bin())
+        .args([
+            "capture-fixture",
+            file.to_str().expect("utf8 path"),
+            "--name",
+            "../escaped",
+            "--dir",
+            fixture_dir.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid fixture name"));
+    assert!(!dir.path().join("escaped.log").exists());
+}
+
+#[test]
+fn test_extract_aggregates_payload_field_values() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("extract.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core | 2026-01-01T00:00:00.000Z [INFO ] Request \"makeManager\" [0--id1] will be sent with body {\"concurrency\":100,\"env\":\"prod\"}\n",
+            "core | 2026-01-01T00:00:01.000Z [INFO ] Request \"makeManager\" [0--id2] will be sent with body {\"concurrency\":100,\"env\":\"prod\"}\n",
+            "core | 2026-01-01T00:00:02.000Z [INFO ] Request \"makeManager\" [0--id3] will be sent with body {\"concurrency\":50,\"env\":\"staging\"}\n",
+            "core | 2026-01-01T00:00:03.000Z [INFO ] Request \"otherCall\" [0--id4] will be sent with body {\"concurrency\":999}\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "extract",
+            file.to_str().expect("utf8 path"),
+            "-f",
+            "t:makeManager",
+            "--field",
+            "concurrency",
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("concurrency=100 (2 occurrences)")
+            && stdout.contains("concurrency=50 (1 occurrence)"),
+        "expected grouped extracted values, got:\n{}",
         stdout
     );
     assert!(
-        stdout.contains("manager-2/eyes-2/check-2") && stdout.contains("orphaned"),
-        "expected orphaned session status, got:\n{}",
+        !stdout.contains("999"),
+        "expected non-matching entry to be excluded, got:\n{}",
         stdout
     );
+}
+
+#[test]
+fn test_extract_against_compares_value_counts_of_two_runs() {
+    let dir = tempdir().expect("temp dir");
+    let before = dir.path().join("before.log");
+    let after = dir.path().join("after.log");
+    let request = |id: usize, concurrency: usize| {
+        format!(
+            "core | 2026-01-01T00:00:0{id}.000Z [INFO ] Request \"makeManager\" [0--id{id}] will be sent with body {{\"concurrency\":{concurrency}}}\n"
+        )
+    };
+    write_file(&before, &(request(1, 5) + &request(2, 5) + &request(3, 10)));
+    write_file(&after, &(request(1, 20) + &request(2, 10)));
+
+    let output = command()
+        .args([
+            "extract",
+            before.to_str().expect("utf8 path"),
+            "--against",
+            after.to_str().expect("utf8 path"),
+            "--field",
+            "concurrency",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        stdout.contains("Longest blocking error:"),
-        "expected impact summary blocking line, got:\n{}",
+        stdout.contains("Extract delta for 'concurrency': before.log → after.log")
+            && stdout.contains("(gone)")
+            && stdout.contains("(new)")
+            && stdout.contains("Mean: 6.67 → 15 (+8.33)"),
+        "expected side-by-side value counts, got:\n{}",
         stdout
     );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "extract",
+            before.to_str().expect("utf8 path"),
+            "--against",
+            after.to_str().expect("utf8 path"),
+            "--field",
+            "concurrency",
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    let values: Vec<(i64, i64, i64, i64)> = json["extract_delta"]["values"]
+        .as_array()
+        .expect("values")
+        .iter()
+        .map(|row| {
+            (
+                row["value"].as_i64().unwrap(),
+                row["before"].as_i64().unwrap(),
+                row["after"].as_i64().unwrap(),
+                row["delta"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(values, vec![(5, 2, 0, -2), (10, 1, 1, 0), (20, 0, 1, 1)]);
+    assert_eq!(json["extract_delta"]["after"]["extracted"], 2);
+}
+
+#[test]
+fn test_search_and_extract_support_rust_tracing_structured_fields() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("flux.log");
+
+    write_file(
+        &file,
+        concat!(
+            "2026-03-10T08:15:30.123Z INFO fluxomni_server::integrations::srs::callback: callback received trace_id=abc123 actor_kind=switch restream_name=stream-a\n",
+            "2026-03-10T08:15:31.123Z INFO fluxomni_server::integrations::srs::callback: callback received trace_id=abc456 actor_kind=switch restream_name=stream-a\n",
+            "2026-03-10T08:15:32.123Z INFO fluxomni_server::integrations::srs::callback: callback received trace_id=def999 actor_kind=relay restream_name=stream-b\n",
+        ),
+    );
+
+    let search_output = command()
+        .args([
+            "search",
+            file.to_str().expect("utf8 path"),
+            "-f",
+            "actor_kind:switch",
+            "--payloads",
+        ])
+        .output()
+        .expect("search should run");
+
+    assert!(
+        search_output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&search_output.stderr)
+    );
+
+    let search_stdout = String::from_utf8_lossy(&search_output.stdout);
+    assert!(
+        search_stdout.contains("SEARCH matched 2 entries"),
+        "expected structured-field filter to match two entries, got:\n{}",
+        search_stdout
+    );
+    assert!(
+        search_stdout.contains("\"restream_name\":\"stream-a\""),
+        "expected structured fields to be shown with --payloads, got:\n{}",
+        search_stdout
+    );
+    assert!(
+        !search_stdout.contains("stream-b"),
+        "expected non-matching structured field values to be excluded, got:\n{}",
+        search_stdout
+    );
+
+    let extract_output = command()
+        .args([
+            "extract",
+            file.to_str().expect("utf8 path"),
+            "-f",
+            "actor_kind:switch",
+            "--field",
+            "restream_name",
+        ])
+        .output()
+        .expect("extract should run");
+
+    assert!(
+        extract_output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&extract_output.stderr)
+    );
+
+    let extract_stdout = String::from_utf8_lossy(&extract_output.stdout);
+    assert!(
+        extract_stdout.contains("restream_name=\"stream-a\" (2 occurrences)"),
+        "expected structured field extraction to aggregate values, got:\n{}",
+        extract_stdout
+    );
+}
+
+#[test]
+fn test_trace_by_id_matches_rust_tracing_trace_fields() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("trace.log");
+
+    write_file(
+        &file,
+        concat!(
+            "2026-03-10T08:15:30.123Z INFO fluxomni_server::scheduler::ingress: stream accepted trace_id=fabb5aa4 span_id=span-1 actor_kind=switch\n",
+            "2026-03-10T08:15:30.523Z INFO fluxomni_server::scheduler::ingress: stream queued trace_id=fabb5aa4 span_id=span-1 actor_kind=switch\n",
+            "2026-03-10T08:15:31.523Z ERROR fluxomni_server::scheduler::ingress: stream failed trace_id=fabb5aa4 span_id=span-1 exit_code=251\n",
+            "2026-03-10T08:15:32.523Z INFO fluxomni_server::scheduler::ingress: other trace trace_id=zzzz9999 span_id=span-2 actor_kind=switch\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "trace",
+            file.to_str().expect("utf8 path"),
+            "--id",
+            "fabb5aa4",
+        ])
+        .output()
+        .expect("trace should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Matched 3 entries"),
+        "expected trace_id-based matching to find three entries, got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("+   400ms") && stdout.contains("+  1000ms"),
+        "expected chronological timing deltas, got:\n{}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("zzzz9999"),
+        "expected unrelated trace_id entries to be excluded, got:\n{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_errors_defaults_to_error_only_and_normalizes_cluster_pattern() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("errors.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--rid-1] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"5bfcc412-1fd6-4f8d-a6d5-246f90f3e7ab\" failed due to an error - internal failure\n",
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--rid-1] finished successfully with body {\"statusCode\":200}\n",
+            "core (manager-2/eyes-2/check-2) | 2026-01-01T00:00:03.000Z [WARN ] Warning - Invalid keys in check settings (will be ignored)\n",
+        ),
+    );
+
+    let output = command()
+        .args(["errors", file.to_str().expect("utf8 path")])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("ERRORS: 1 entries (1 patterns) across 1 file"),
+        "expected error-only header, got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Render with id \"...\" failed due to an error - internal failure"),
+        "expected normalized error cluster pattern, got:\n{}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("Warning - Invalid keys in check settings"),
+        "expected WARN entries to be excluded by default, got:\n{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_errors_warn_and_sessions_show_completed_and_orphaned_sessions() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("errors_sessions.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--req-complete] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"5bfcc412-1fd6-4f8d-a6d5-246f90f3e7ab\" failed due to an error - internal failure\n",
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--req-complete] finished successfully with body {\"statusCode\":200}\n",
+            "core (manager-2/eyes-2/check-2) | 2026-01-01T00:00:03.000Z [INFO ] Request \"check\" [0--req-orphan] will be sent with body {\"x\":1}\n",
+            "core (manager-2/eyes-2/check-2) | 2026-01-01T00:00:04.000Z [ERROR] Render with id \"aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee\" failed due to an error - internal failure\n",
+            "core (manager-2/eyes-2/check-2) | 2026-01-01T00:00:05.000Z [WARN ] Warning - Invalid keys in check settings (will be ignored)\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "errors",
+            file.to_str().expect("utf8 path"),
+            "--warn",
+            "--sessions",
+            "--top-n",
+            "0",
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("ERRORS/WARNS: 3 entries (2 patterns) across 1 file"),
+        "expected combined error/warn header, got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Warning - Invalid keys in check settings (will be ignored)"),
+        "expected WARN cluster with --warn, got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("manager-1/eyes-1/check-1") && stdout.contains("completed"),
+        "expected completed session status, got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("manager-2/eyes-2/check-2") && stdout.contains("orphaned"),
+        "expected orphaned session status, got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Longest blocking error:"),
+        "expected impact summary blocking line, got:\n{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_errors_json_includes_health_summary() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("health.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id1] finished successfully with body {\"statusCode\":200}\n",
+            "core (manager-1/eyes-2) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id2] will be sent with body {\"x\":2}\n",
+            "core (manager-1/eyes-2) | 2026-01-01T00:00:02.000Z [ERROR] Render failed\n",
+        ),
+    );
+
+    let output = command()
+        .args(["-F", "json", "errors", file.to_str().expect("utf8 path")])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("errors output should be JSON");
+    let health = &json["errors"]["health"];
+    assert_eq!(health["error_entries"], 1);
+    assert_eq!(health["total_entries"], 4);
+    assert_eq!(health["sessions"], 2);
+    assert_eq!(health["orphaned_sessions"], 1);
+    // 25% error rate costs the full 40 points, 1 of 2 orphaned sessions the full 30
+    assert_eq!(health["score"], 30);
+    assert_eq!(health["verdict"], "unhealthy");
+    assert_eq!(health["top_issues"].as_array().map(Vec::len), Some(2));
+}
+
+#[test]
+fn test_errors_sessions_split_parallel_session_paths() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("errors_parallel.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1 & manager-1/eyes-2) | 2026-01-01T00:00:00.000Z [ERROR] Render failed due to an error - internal failure\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [ERROR] Render failed due to an error - internal failure\n",
+        ),
+    );
+
+    let run = |attribution: &str| {
+        let output = command()
+            .args([
+                "-F",
+                "json",
+                "errors",
+                file.to_str().expect("utf8 path"),
+                "--sessions",
+                "--session-attribution",
+                attribution,
+            ])
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let parsed: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+        parsed["errors"]["clusters"][0]["affected_sessions"]
+            .as_array()
+            .expect("affected_sessions should be an array")
+            .iter()
+            .map(|session| {
+                (
+                    session["session_path"].as_str().unwrap_or("").to_string(),
+                    session["error_count"].as_u64().unwrap_or(0),
+                    session["error_share"].as_f64(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        run("all"),
+        vec![
+            ("manager-1/eyes-1".to_string(), 2, None),
+            ("manager-1/eyes-2".to_string(), 1, None),
+        ]
+    );
+    assert_eq!(
+        run("first"),
+        vec![("manager-1/eyes-1".to_string(), 2, None)]
+    );
+    assert_eq!(
+        run("proportional"),
+        vec![
+            ("manager-1/eyes-1".to_string(), 2, Some(1.5)),
+            ("manager-1/eyes-2".to_string(), 1, Some(0.5)),
+        ]
+    );
+}
+
+#[test]
+fn test_errors_ticket_format_renders_template() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("errors.log");
+    let template = dir.path().join("ticket.md");
+    let config = dir.path().join("profile.toml");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"5bfcc412-1fd6-4f8d-a6d5-246f90f3e7ab\" failed due to an error - internal failure\n",
+            "core (manager-1/eyes-2/check-2) | 2026-01-01T00:00:04.000Z [ERROR] Render with id \"0e2c7a9b-3c61-4a8e-9d0f-6b1c2d3e4f50\" failed due to an error - internal failure\n",
+        ),
+    );
+
+    let output = command()
+        .args(["errors", "--ticket", file.to_str().expect("utf8 path")])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "## [ERROR] Render with id \"...\" failed due to an error - internal failure"
+        ) && stdout.contains("**Occurrences:** 2 (ERROR)")
+            && stdout.contains("-f 'level:ERROR component:core text:internal'"),
+        "expected default ticket template, got:\n{}",
+        stdout
+    );
+
+    write_file(
+        &template,
+        "[{{rank}}] {{title}} x{{count}} sessions={{session_count}}\n",
+    );
+    // The template path resolves against the profile's directory, not the working directory
+    write_file(&config, "[errors]\nticket_template = \"ticket.md\"\n");
+    let output = Command::new(bin())
+        .args([
+            "--config",
+            config.to_str().expect("utf8 path"),
+            "errors",
+            "--ticket",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "[1] [ERROR] Render with id \"...\" failed due to an error - internal failure x2 sessions=2\n"
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "errors",
+            "--ticket",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_errors_suggested_filter_reproduces_cluster_in_search() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("errors.log");
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"5bfcc412-1fd6-4f8d-a6d5-246f90f3e7ab\" failed due to an error - internal failure\n",
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:02.000Z [ERROR] Upload of resource 4711 timed out\n",
+            "core (manager-1/eyes-1/check-1) | 2026-01-01T00:00:02.500Z [ERROR] Browser couldn't start\n",
+            "core (manager-1/eyes-2/check-2) | 2026-01-01T00:00:03.000Z [INFO ] Render with id \"1a\" started - internal queue\n",
+            "core (manager-1/eyes-2/check-2) | 2026-01-01T00:00:04.000Z [ERROR] Render with id \"0e2c7a9b-3c61-4a8e-9d0f-6b1c2d3e4f50\" failed due to an error - internal failure\n",
+        ),
+    );
+
+    let output = command()
+        .args(["errors", file.to_str().expect("utf8 path")])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("     Filter: -f 'level:ERROR component:core text:internal'")
+            && stdout.contains("     Filter: -f 'level:ERROR component:core text:Browser'"),
+        "expected a filter line per cluster, got:\n{}",
+        stdout
+    );
+
+    let output = command()
+        .args(["-F", "json", "errors", file.to_str().expect("utf8 path")])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    let clusters = json["errors"]["clusters"].as_array().expect("clusters");
+    assert_eq!(clusters.len(), 3);
+    for cluster in clusters {
+        let filter = cluster["suggested_filter"]
+            .as_str()
+            .expect("suggested_filter");
+        let output = command()
+            .args([
+                "-F",
+                "json",
+                "search",
+                file.to_str().expect("utf8 path"),
+                "--filter",
+                filter,
+            ])
+            .output()
+            .expect("command should run");
+        let search: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+        assert_eq!(
+            search["search"]["matches"], cluster["count"],
+            "filter {filter} should select the cluster's entries"
+        );
+    }
+}
+
+#[test]
+fn test_compare_learn_noise_suggests_paths_that_differ_in_every_pair() {
+    let dir = tempdir().expect("temp dir");
+    let request = |body: &str| {
+        format!(
+            "core | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {body}\n"
+        )
+    };
+    let logs = [
+        ("a1.log", r#"{"renderId":"r1","renders":[{"ts":1}],"x":1}"#),
+        ("b1.log", r#"{"renderId":"r2","renders":[{"ts":2}],"x":1}"#),
+        ("a2.log", r#"{"renderId":"r3","renders":[{"ts":3}],"x":1}"#),
+        ("b2.log", r#"{"renderId":"r4","renders":[{"ts":4}],"x":2}"#),
+    ];
+    for (name, body) in logs {
+        write_file(&dir.path().join(name), &request(body));
+    }
+
+    let output = command()
+        .current_dir(dir.path())
+        .args([
+            "-o",
+            ".loganalyzerignore",
+            "compare",
+            "a1.log",
+            "b1.log",
+            "--learn-noise",
+            "2",
+            "--pair",
+            "a2.log",
+            "b2.log",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("# confidence 1.00 (2/2 pairs, 2 differences)\npath: renderId\n")
+            && stdout.contains("\npath: renders[].ts\n")
+            && stdout.contains("# confidence 0.50 (1/2 pairs, 1 differences)\n# path: x\n"),
+        "expected a suggested ignore profile, got:\n{stdout}"
+    );
+
+    let output = command()
+        .current_dir(dir.path())
+        .args(["-F", "json", "diff", "a2.log", "b2.log"])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !stdout.contains("renderId") && !stdout.contains("renders") && stdout.contains("\"x\""),
+        "expected the learned profile to suppress the noisy paths, got:\n{stdout}"
+    );
+
+    let output = command()
+        .current_dir(dir.path())
+        .args(["compare", "a1.log", "b1.log", "--learn-noise", "3"])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("expects 3 log pairs, got 1"));
+}
+
+#[test]
+fn test_compare_split_at_compares_halves_of_one_log() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    let request = |minute: u32, body: &str| {
+        format!(
+            "core | 2026-01-01T00:{minute:02}:00.000Z [INFO ] Request \"check\" [0--id{minute}] will be sent with body {body}\n"
+        )
+    };
+    let content = [
+        request(0, r#"{"retries":1}"#),
+        request(5, r#"{"retries":1}"#),
+        "core | 2026-01-01T00:10:00.000Z [INFO ] config reloaded\n".to_string(),
+        request(11, r#"{"retries":3}"#),
+        request(15, r#"{"retries":3}"#),
+    ]
+    .concat();
+    write_file(&file, &content);
+
+    let run = |split_at: &str| {
+        let output = command()
+            .args(["-F", "json", "compare", file.to_str().expect("utf8 path")])
+            .args(["--split-at", split_at])
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).expect("valid JSON")
+    };
+
+    let by_marker = run("t:reloaded");
+    assert_eq!(by_marker["split"]["marker_line"], 3);
+    assert_eq!(by_marker["split"]["before_entries"], 2);
+    assert_eq!(by_marker["split"]["after_entries"], 3);
+    assert_eq!(by_marker["summary"]["differences_count"], 2);
+
+    let by_time = run("2026-01-01T00:10:30Z");
+    assert_eq!(by_time["split"]["before_entries"], 3);
+    assert!(
+        by_time["comparisons"].to_string().contains("retries"),
+        "expected the retries change to be reported, got:\n{by_time}"
+    );
+
+    let output = command()
+        .args(["compare", file.to_str().expect("utf8 path")])
+        .args(["--split-at", "2027-01-01T00:00:00Z"])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("leaves no entries after"));
+}
+
+#[test]
+fn test_ignore_file_is_discovered_and_applied() {
+    let dir = tempdir().expect("temp dir");
+    let nested = dir.path().join("runs");
+    fs::create_dir(&nested).expect("nested dir");
+    let file1 = nested.join("a.log");
+    let file2 = nested.join("b.log");
+
+    write_file(
+        &dir.path().join(".loganalyzerignore"),
+        concat!(
+            "# shared triage decisions\n",
+            "filter: !c:socket\n",
+            "path: ts\n",
+            "error: Render with id * failed*\n",
+        ),
+    );
+    write_file(
+        &file1,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"ts\":1,\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [ERROR] Render with id \"5bfcc412\" failed due to an error\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:02.000Z [ERROR] Connection reset by peer\n",
+            "socket | 2026-01-01T00:00:03.000Z [ERROR] Socket closed unexpectedly\n",
+        ),
+    );
+    write_file(
+        &file2,
+        "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"ts\":2,\"x\":1}\n",
+    );
+
+    let output = command()
+        .current_dir(&nested)
+        .args(["errors", "a.log"])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("ERRORS: 1 entries (1 patterns)")
+            && stdout.contains("Connection reset by peer")
+            && stdout.contains("Suppressed by ignore rules: 1")
+            && !stdout.contains("Socket closed"),
+        "expected ignore rules to apply to errors, got:\n{}",
+        stdout
+    );
+
+    let output = command()
+        .current_dir(&nested)
+        .args(["-F", "json", "diff", "a.log", "b.log"])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !stdout.contains("\"ts\""),
+        "expected ignored diff path, got:\n{}",
+        stdout
+    );
+
+    let output = command()
+        .current_dir(&nested)
+        .args(["info", "a.log"])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Total log entries: 3") && !stdout.contains("socket"),
+        "expected ignore filters to apply to info, got:\n{}",
+        stdout
+    );
+
+    let output = command()
+        .current_dir(&nested)
+        .args(["--no-ignore-file", "errors", "a.log"])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("ERRORS: 3 entries (3 patterns)"),
+        "expected --no-ignore-file to disable the rules, got:\n{}",
+        stdout
+    );
+
+    write_file(&dir.path().join(".loganalyzerignore"), "skip: everything\n");
+    let output = command()
+        .current_dir(&nested)
+        .args(["errors", "a.log"])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown rule kind 'skip'"));
+}
+
+#[test]
+fn test_perf_hang_threshold_reports_stale_requests_with_session_context() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("hang.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--slow] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.500Z [INFO ] Render started\n",
+            "core (manager-2/eyes-2) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--fast] will be sent with body {\"x\":1}\n",
+            "core (manager-2/eyes-2) | 2026-01-01T00:00:01.200Z [INFO ] Request \"check\" [0--fast] finished successfully with body {\"statusCode\":200}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:06.000Z [INFO ] Request \"check\" [0--slow] finished successfully with body {\"statusCode\":200}\n",
+            "core (manager-3/eyes-3) | 2026-01-01T00:00:07.000Z [INFO ] Request \"close\" [0--lost] will be sent with body {\"x\":1}\n",
+            "core (manager-3/eyes-3) | 2026-01-01T00:00:09.500Z [INFO ] Heartbeat\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "perf",
+            file.to_str().expect("utf8 path"),
+            "--hang-threshold",
+            "2000",
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let warnings = parsed["hang_warnings"]
+        .as_array()
+        .expect("hang_warnings should be an array");
+    assert_eq!(warnings.len(), 2, "output:\n{stdout}");
+
+    assert_eq!(warnings[0]["correlation_id"], "0--slow");
+    assert_eq!(warnings[0]["component_id"], "manager-1/eyes-1");
+    assert_eq!(warnings[0]["pending_ms"], 6000);
+    assert_eq!(warnings[0]["resolved_after_ms"], 6000);
+    let recent = warnings[0]["recent_entries"]
+        .as_array()
+        .expect("recent_entries should be an array");
+    assert_eq!(recent.len(), 2);
+    assert!(recent[1].as_str().unwrap_or("").contains("Render started"));
+
+    assert_eq!(warnings[1]["correlation_id"], "0--lost");
+    assert_eq!(warnings[1]["pending_ms"], 2500);
+    assert!(warnings[1]["resolved_after_ms"].is_null());
+}
+
+#[test]
+fn test_perf_hang_threshold_times_resent_request_from_its_last_send() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("resent.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--retry] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.500Z [INFO ] Request \"check\" [0--retry] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:03.000Z [INFO ] Render started\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:04.000Z [INFO ] Render started\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:05.000Z [INFO ] Render started\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "perf",
+            file.to_str().expect("utf8 path"),
+            "--hang-threshold",
+            "2000",
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let warnings = parsed["hang_warnings"]
+        .as_array()
+        .expect("hang_warnings should be an array");
+    assert_eq!(warnings.len(), 1, "output:\n{stdout}");
+    assert_eq!(warnings[0]["correlation_id"], "0--retry");
+    assert_eq!(warnings[0]["pending_ms"], 2500);
+}
+
+#[test]
+fn test_perf_and_errors_link_operations_to_errors_in_flight() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("linked.log");
+
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--slow] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [ERROR] Render failed with timeout\n",
+            "core (manager-2/eyes-2) | 2026-01-01T00:00:01.500Z [ERROR] Render failed with timeout\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:02.000Z [ERROR] Render failed with timeout\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:03.000Z [INFO ] Request \"check\" [0--slow] finished successfully with body {\"statusCode\":200}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:04.000Z [ERROR] Upload timed out\n",
+        ),
+    );
+    let path = file.to_str().expect("utf8 path");
+
+    let output = command()
+        .args(["-F", "json", "perf", path])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let errors_during = parsed["operations"][0]["errors_during"]
+        .as_array()
+        .expect("errors_during should be an array");
+    assert_eq!(errors_during.len(), 1, "output:\n{stdout}");
+    assert_eq!(errors_during[0]["count"], 2);
+    assert_eq!(errors_during[0]["first_line"], 2);
+
+    let output = command()
+        .args(["-F", "json", "errors", path])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let clusters = parsed["errors"]["clusters"]
+        .as_array()
+        .expect("clusters should be an array");
+    let render = clusters
+        .iter()
+        .find(|cluster| cluster["count"] == 3)
+        .expect("render cluster");
+    assert_eq!(
+        render["during_operations"],
+        serde_json::json!([{"op_type": "Request", "name": "check", "count": 2}])
+    );
+    let upload = clusters
+        .iter()
+        .find(|cluster| cluster["count"] == 1)
+        .expect("upload cluster");
+    assert!(upload.get("during_operations").is_none());
+
+    let output = command()
+        .args(["errors", path])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("During: Request check ×2"),
+        "output:\n{stdout}"
+    );
+}
+
+#[test]
+fn test_info_against_reports_summary_deltas() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("before.log");
+    let file2 = dir.path().join("after.log");
+
+    write_file(
+        &file1,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"foo\" [0--id2] will be sent with body {\"x\":2}\n",
+        ),
+    );
+    write_file(
+        &file2,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"foo\" [0--id2] will be sent with body {\"x\":2}\n",
+            "svc | 2026-01-01T00:00:02.000Z [INFO ] Request \"foo\" [0--id3] will be sent with body {\"x\":3}\n",
+            "svc | 2026-01-01T00:00:03.000Z [ERROR] Request \"bar\" [0--id4] will be sent with body {\"x\":4}\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "info",
+            file1.to_str().expect("utf8 path"),
+            "--against",
+            file2.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("↑ +2 (+100.0%)"),
+        "expected entry count delta with arrow and percentage, got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("bar") && stdout.contains("(new)"),
+        "expected new request to be reported, got:\n{}",
+        stdout
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "info",
+            file1.to_str().expect("utf8 path"),
+            "--against",
+            file2.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("info --against JSON output");
+    let levels = json["info_delta"]["levels"]
+        .as_array()
+        .expect("levels array");
+    assert!(levels.iter().any(|row| row["name"] == "ERROR"
+        && row["before"] == 0.0
+        && row["after"] == 1.0
+        && row["percent"].is_null()));
+}
+
+#[test]
+fn test_json_duration_and_size_fields_use_whole_units_with_human_companions() {
+    let dir = tempdir().expect("temp dir");
+    let before = dir.path().join("before.log");
+    let after = dir.path().join("after.log");
+    write_file(
+        &before,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--a] will be sent with body {\"x\":1}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--a] finished successfully with body {\"statusCode\":200}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--b] will be sent with body {\"x\":1}\n",
+            "svc | 2026-01-01T00:00:03.001Z [INFO ] Request \"check\" [0--b] finished successfully with body {\"statusCode\":200}\n",
+        ),
+    );
+    write_file(
+        &after,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--a] will be sent with body {\"x\":1}\n",
+            "svc | 2026-01-01T00:03:20.000Z [INFO ] Request \"check\" [0--a] finished successfully with body {\"statusCode\":200}\n",
+        ),
+    );
+    let run_json = |args: &[&str]| -> serde_json::Value {
+        let output = command()
+            .args(["-F", "json"])
+            .args(args)
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice(&output.stdout).expect("JSON output")
+    };
+    let (before, after) = (
+        before.to_str().expect("utf8 path"),
+        after.to_str().expect("utf8 path"),
+    );
+
+    let perf = run_json(&["perf", before]);
+    let stats = &perf["stats"][0];
+    assert_eq!(stats["avg_duration_ms"], 1501);
+    assert_eq!(stats["avg_duration_ms_human"], "1.5s");
+    assert_eq!(stats["max_duration_ms_human"], "2.0s");
+
+    let delta = run_json(&["info", before, "--against", after]);
+    let span = &delta["info_delta"]["totals"][2];
+    assert_eq!(span["name"], "time span");
+    assert_eq!(span["unit"], "ms");
+    assert_eq!(
+        (span["before"].clone(), span["after"].clone()),
+        (3001.into(), 200_000.into())
+    );
+    assert_eq!(span["after_human"], "3m 20s");
+
+    let summary = run_json(&["--summary-only", "info", before]);
+    assert_eq!(summary["info"]["summary"]["time_span_ms"], 3001);
+    assert_eq!(summary["info"]["summary"]["time_span_ms_human"], "3.0s");
+
+    // Compared payload values are copied from the logs and stay as logged
+    let payload1 = dir.path().join("payload1.log");
+    let payload2 = dir.path().join("payload2.log");
+    write_file(
+        &payload1,
+        "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--a] will be sent with body {\"timeout_ms\":1.5}\n",
+    );
+    write_file(
+        &payload2,
+        "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--a] will be sent with body {\"timeout_ms\":2.5}\n",
+    );
+    let compare = run_json(&[
+        "compare",
+        payload1.to_str().expect("utf8 path"),
+        payload2.to_str().expect("utf8 path"),
+    ]);
+    let values = &compare["comparisons"][0]["differences"][0]["values"][0];
+    assert_eq!(
+        (values["value1"].clone(), values["value2"].clone()),
+        (1.5.into(), 2.5.into())
+    );
+
+    let legacy = run_json(&["--legacy-json-units", "perf", before]);
+    assert_eq!(legacy["stats"][0]["avg_duration_ms"], 1500.5);
+    assert!(legacy["stats"][0].get("avg_duration_ms_human").is_none());
+    let legacy = run_json(&["--legacy-json-units", "info", before, "--against", after]);
+    assert_eq!(legacy["info_delta"]["totals"][2]["name"], "time span (s)");
+    assert_eq!(legacy["info_delta"]["totals"][2]["after"], 200.0);
+}
+
+#[test]
+fn test_search_diff_against_golden_reports_per_entry_verdicts() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("requests.log");
+    let golden = dir.path().join("golden.json");
+
+    write_file(
+        &file,
+        concat!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1,\"ts\":10,\"ratio\":0.5}\n",
+            "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"foo\" [0--id2] will be sent with body {\"x\":2,\"ts\":20,\"ratio\":0.51}\n",
+        ),
+    );
+    write_file(&golden, "{\"x\":1,\"ts\":0,\"ratio\":0.5}");
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "search",
+            file.to_str().expect("utf8 path"),
+            "--diff-against",
+            golden.to_str().expect("utf8 path"),
+            "--ignore-path",
+            "ts",
+            "--tolerance",
+            "0.05",
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("search diff JSON output");
+    let report = &json["search_diff"];
+    assert_eq!(report["identical"], 1);
+    assert_eq!(report["different"], 1);
+
+    let entries = report["entries"].as_array().expect("entries array");
+    assert_eq!(entries[0]["verdict"], "MATCH");
+    assert_eq!(entries[1]["verdict"], "DIFF");
+    let diffs = entries[1]["differences"].as_array().expect("differences");
+    assert_eq!(diffs.len(), 1, "only x should differ, got: {:?}", diffs);
+    assert_eq!(diffs[0]["path"], "x");
+    assert_eq!(diffs[0]["expected"], 1);
+    assert_eq!(diffs[0]["actual"], 2);
+}
+
+#[test]
+fn test_compare_with_tiny_max_memory_spills_and_still_reports_differences() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("a.log");
+    let file2 = dir.path().join("b.log");
+
+    write_file(
+        &file1,
+        "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1}\n",
+    );
+    write_file(
+        &file2,
+        "svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":2}\n",
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "--max-memory",
+            "1K",
+            "diff",
+            file1.to_str().expect("utf8 path"),
+            file2.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("\"x\""),
+        "expected spilled diff to report the changed field, got:\n{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_payload_parse_failures_are_reported() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("a.log");
+    let file2 = dir.path().join("b.log");
+    let mut content = String::from(
+        "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"x\":1}\n",
+    );
+    for idx in 1..=4 {
+        content.push_str(&format!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:0{idx}.000Z [INFO ] Request \"check\" [0--id{idx}] will be sent with body {{\"x\": {idx}, broken}}\n"
+        ));
+    }
+    write_file(&file1, &content);
+    write_file(&file2, &content);
+
+    let output = command()
+        .args(["info", file1.to_str().unwrap()])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("a.log: 4 payload(s) failed to parse")
+            && stdout.contains("line 2: {\"x\": 1, broken}")
+            && stdout.contains("... +1 more"),
+        "expected payload failures in info, got:\n{}",
+        stdout
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "--payload-errors",
+            "compare",
+            file1.to_str().unwrap(),
+            file2.to_str().unwrap(),
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("compare output should be JSON");
+    let counts = json["summary"]["payload_parse_failures"]
+        .as_object()
+        .expect("payload failure counts");
+    assert_eq!(counts.len(), 2);
+    assert!(counts.values().all(|count| count == 4));
+    assert_eq!(
+        json["payload_errors"][0]["samples"]
+            .as_array()
+            .unwrap()
+            .len(),
+        4
+    );
+}
+
+#[test]
+fn test_include_raw_embeds_bounded_raw_lines() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("a.log");
+    let file2 = dir.path().join("b.log");
+    write_file(
+        &file1,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [ERROR] Connection reset by peer\n",
+            "    at socket.js:42\n",
+        ),
+    );
+    write_file(
+        &file2,
+        "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"x\":2}\n",
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "--include-raw",
+            "errors",
+            file1.to_str().unwrap(),
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("errors output should be JSON");
+    let sample_raw = json["errors"]["clusters"][0]["sample_raw"]
+        .as_str()
+        .expect("sample_raw should be embedded");
+    assert!(
+        sample_raw.contains("Connection reset by peer\n    at socket.js:42"),
+        "expected continuation lines in sample_raw, got: {}",
+        sample_raw
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "--include-raw",
+            "--raw-limit",
+            "40",
+            "diff",
+            file1.to_str().unwrap(),
+            file2.to_str().unwrap(),
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("diff output should be JSON");
+    let raw = json["comparisons"][0]["instances"][0]["log1_source"]["raw"]
+        .as_str()
+        .expect("raw should be embedded");
+    assert!(
+        raw.starts_with("core (manager-1/eyes-1) | 2026") && raw.contains("[truncated, "),
+        "expected a bounded raw excerpt, got: {}",
+        raw
+    );
+
+    let output = command()
+        .args(["-F", "json", "errors", file1.to_str().unwrap()])
+        .output()
+        .expect("command should run");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("sample_raw"));
+}
+
+#[test]
+fn test_errors_slo_reports_burst_windows() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    let config = dir.path().join("slo.toml");
+    let mut content = String::new();
+    for second in 0..120 {
+        let (level, message) = if (60..64).contains(&second) {
+            ("ERROR", format!("Render with id \"{second}\" failed"))
+        } else {
+            ("INFO ", "Tick ok".to_string())
+        };
+        content.push_str(&format!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:{:02}:{:02}.000Z [{level}] {message}\n",
+            second / 60,
+            second % 60
+        ));
+    }
+    write_file(&file, &content);
+    write_file(
+        &config,
+        "[errors.slo]\nmax_error_rate_pct = 10.0\nwindow_ms = 20000\nstep_ms = 5000\n",
+    );
+
+    let output = Command::new(bin())
+        .args([
+            "--config",
+            config.to_str().expect("utf8 path"),
+            "-F",
+            "json",
+            "errors",
+            file.to_str().expect("utf8 path"),
+            "--slo",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("slo output should be JSON");
+    let slo = &json["slo"];
+    assert_eq!(slo["window_ms"], 20000);
+    assert_eq!(slo["error_count"], 4);
+    let violations = slo["violations"].as_array().expect("violations");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0]["peak_error_count"], 4);
+    assert_eq!(
+        violations[0]["dominant_clusters"][0]["pattern"],
+        "Render with id \"...\" failed"
+    );
+
+    let output = command()
+        .args([
+            "errors",
+            file.to_str().expect("utf8 path"),
+            "--slo",
+            "--slo-max-rate",
+            "50",
+        ])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("No window exceeded the SLO."),
+        "expected no violations at 50%, got:\n{}",
+        stdout
+    );
+
+    let output = command()
+        .args(["errors", file.to_str().expect("utf8 path"), "--slo"])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_info_json_schema_shows_masked_example_values() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    let config = dir.path().join("schema.toml");
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"status\":\"ok\",\"email\":\"a@b.c\"}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id2] will be sent with body {\"status\":\"ok\",\"email\":\"d@e.f\"}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--id3] will be sent with body {\"status\":\"failed\",\"email\":\"g@h.i\"}\n",
+        ),
+    );
+    write_file(
+        &config,
+        concat!(
+            "[parser]\n",
+            "request_prefix = \"Request \\\"\"\n",
+            "request_send_markers = [\"will be sent\"]\n",
+            "request_payload_markers = [\"with body\"]\n",
+            "json_indicators = [\"with body\"]\n",
+            "[schema]\n",
+            "mask_fields = [\"Email\"]\n",
+        ),
+    );
+
+    let output = Command::new(bin())
+        .args([
+            "--config",
+            config.to_str().expect("utf8 path"),
+            "--color",
+            "never",
+            "info",
+            file.to_str().expect("utf8 path"),
+            "--json-schema",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("status (string) (3/3)  e.g. \"ok\" ×2, \"failed\" ×1")
+            && stdout.contains("email (string) (3/3)  e.g. [MASKED] ×3")
+            && !stdout.contains("a@b.c"),
+        "expected example values with masking, got:\n{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_info_json_schema_masks_secret_examples_without_a_profile() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    write_file(
+        &file,
+        "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"status\":\"ok\",\"apiKey\":\"SECRETKEY123\",\"accessToken\":\"tok-999\",\"password\":\"hunter2\"}\n",
+    );
+
+    let output = command()
+        .args([
+            "--color",
+            "never",
+            "info",
+            file.to_str().expect("utf8 path"),
+            "--json-schema",
+        ])
+        .output()
+        .expect("command should run");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for secret in ["SECRETKEY123", "tok-999", "hunter2"] {
+        assert!(!stdout.contains(secret), "{secret} leaked:\n{stdout}");
+    }
+    assert!(
+        stdout.contains("apiKey (string) (1/1)  e.g. [MASKED] ×1")
+            && stdout.contains("status (string) (1/1)  e.g. \"ok\" ×1"),
+        "expected masked secrets, got:\n{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_info_field_matrix_reports_presence_per_request() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"openEyes\" [0--id1] will be sent with body {\"appName\":\"demo\",\"batchInfo\":{\"id\":\"b1\"}}\n",
+            "core (manager-1/eyes-2) | 2026-01-01T00:00:01.000Z [INFO ] Request \"openEyes\" [0--id2] will be sent with body {\"appName\":\"demo\"}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--id3] will be sent with body {\"renders\":[{\"browser\":\"chrome\"},{\"browser\":\"firefox\"}]}\n",
+        ),
+    );
+    let path = file.to_str().expect("utf8 path");
+
+    let output = command()
+        .args(["-F", "json", "info", path, "--field-matrix"])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let matrix = &parsed["field_matrix"];
+    assert_eq!(matrix["requests"]["openEyes"], 2, "output:\n{stdout}");
+    assert_eq!(matrix["requests"]["check"], 1);
+    let field = |name: &str| {
+        matrix["fields"]
+            .as_array()
+            .expect("fields should be an array")
+            .iter()
+            .find(|field| field["field"] == name)
+            .unwrap_or_else(|| panic!("missing field {name}:\n{stdout}"))
+            .clone()
+    };
+    assert_eq!(field("batchInfo")["presence"]["openEyes"]["pct"], 50.0);
+    assert_eq!(field("batchInfo")["presence"]["check"]["count"], 0);
+    assert_eq!(field("appName")["presence"]["openEyes"]["pct"], 100.0);
+    assert_eq!(field("renders[].browser")["presence"]["check"]["count"], 1);
+
+    let output = command()
+        .args(["info", path, "--field-matrix", "--csv"])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "field,openEyes,check", "output:\n{stdout}");
+    assert!(lines.contains(&"batchInfo,50.0,0.0"), "output:\n{stdout}");
+    assert!(
+        lines.contains(&"batchInfo.id,50.0,0.0"),
+        "output:\n{stdout}"
+    );
+}
+
+#[test]
+fn test_config_compare_effect_reports_pairing_and_session_changes() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    let config_a = dir.path().join("a.toml");
+    let config_b = dir.path().join("b.toml");
+    write_file(
+        &file,
+        concat!(
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--id1] will be sent with body {\"x\":1}\n",
+            "core (manager-1/eyes-1) | 2026-01-01T00:00:00.500Z [INFO ] Request \"check\" [0--id1] finished successfully with body {\"ok\":true}\n",
+            "core (manager-1/eyes-2) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id2] will be sent with body {\"x\":2}\n",
+        ),
+    );
+    let parser = "[parser]\nrequest_prefix = \"Request \\\"\"\nrequest_send_markers = [\"will be sent\"]\nrequest_payload_markers = [\"with body\"]\njson_indicators = [\"with body\"]\n";
+    write_file(&config_a, parser);
+    write_file(
+        &config_b,
+        &format!(
+            "{parser}request_receive_markers = [\"finished successfully\"]\n\n[[sessions.levels]]\nname = \"eyes\"\nsegment_prefix = \"eyes-\"\n"
+        ),
+    );
+
+    let output = Command::new(bin())
+        .args([
+            "-F",
+            "json",
+            "config",
+            "compare-effect",
+            "--config-a",
+            config_a.to_str().expect("utf8 path"),
+            "--config-b",
+            config_b.to_str().expect("utf8 path"),
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("config effect output should be JSON");
+    let effect = &json["config_effect"];
+    assert_eq!(effect["a"]["kinds"]["Request"], 3);
+    assert_eq!(effect["a"]["pairing"]["completed"], 0);
+    assert_eq!(effect["b"]["pairing"]["completed"], 1);
+    assert_eq!(effect["b"]["sessions"][0]["detected"], 2);
+    assert_eq!(effect["delta"]["orphaned"], -1);
+    assert_eq!(effect["delta"]["sessions_detected"]["eyes"], 2);
+    assert_eq!(effect["has_differences"], true);
+
+    let output = Command::new(bin())
+        .args([
+            "config",
+            "compare-effect",
+            "--config-a",
+            config_a.to_str().expect("utf8 path"),
+            "--config-b",
+            config_a.to_str().expect("utf8 path"),
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("CONFIG EFFECT"), "stdout: {stdout}");
+    assert!(stdout.contains("No differences"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_sessions_story_narrates_one_session() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    write_file(
+        &file,
+        concat!(
+            "core-universal (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Command \"openEyes\" is called with settings {\"appName\":\"Shop\",\"testName\":\"checkout\"}\n",
+            "core-universal (manager-1/eyes-2) | 2026-01-01T00:00:00.100Z [INFO ] Command \"openEyes\" is called with settings {\"appName\":\"Shop\",\"testName\":\"other\"}\n",
+            "core-universal (manager-1/eyes-1/check-1) | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--id1] will be sent to the address \"[POST]https://x/api\" with body {\"x\":1}\n",
+            "core-universal (manager-1/eyes-1/check-1) | 2026-01-01T00:00:02.500Z [INFO ] Request \"check\" [0--id1] finished successfully with body {\"ok\":true}\n",
+            "core-universal (manager-1/eyes-1) | 2026-01-01T00:00:03.000Z [WARN ] Render took too long\n",
+            "core-universal (manager-1/eyes-2) | 2026-01-01T00:00:03.500Z [ERROR] Unrelated failure\n",
+            "core-universal (manager-1/eyes-1/close-1) | 2026-01-01T00:00:04.000Z [INFO ] Command \"close\" is called with settings {\"throwErr\":false}\n",
+            "core-universal (manager-1/eyes-1/close-1) | 2026-01-01T00:00:05.000Z [INFO ] Command \"close\" finished successfully with result [{\"status\":\"Unresolved\"}]\n",
+        ),
+    );
+
+    let output = command()
+        .args([
+            "sessions",
+            "story",
+            "eyes-1",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Created via openEyes")
+            && stdout.contains("testName: \"checkout\"")
+            && stdout.contains("check [0--id1]  1500 ms")
+            && stdout.contains("[WARN] Render took too long")
+            && stdout.contains("Completed via close")
+            && stdout.contains("status: \"Unresolved\"")
+            && !stdout.contains("Unrelated failure"),
+        "unexpected story:\n{stdout}"
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "sessions",
+            "story",
+            "eyes-2",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("story output should be JSON");
+    let story = &json["session_story"];
+    assert_eq!(story["level"], "test");
+    assert_eq!(story["problems"][0]["severity"], "ERROR");
+    assert!(story["completed"].is_null());
+    assert_eq!(story["expected_completion"][0], "close");
+}
+
+#[test]
+fn test_sessions_story_reads_lifecycle_from_the_selected_session_only() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    let config = dir.path().join("sessions.toml");
+    write_file(
+        &file,
+        concat!(
+            "core-universal (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Command \"openEyes\" is called with settings {\"testName\":\"first\"}\n",
+            "core-universal (manager-2/eyes-1) | 2026-01-01T00:00:01.000Z [INFO ] Command \"openEyes\" is called with settings {\"testName\":\"second\"}\n",
+        ),
+    );
+    write_file(
+        &config,
+        concat!(
+            "[parser]\n",
+            "command_prefix = \"Command \\\"\"\n",
+            "command_start_marker = \"\\\" is called\"\n",
+            "command_payload_markers = [\"with settings\"]\n",
+            "[[sessions.levels]]\n",
+            "name = \"test\"\n",
+            "segment_prefix = \"eyes-\"\n",
+            "create_command = \"openEyes\"\n",
+            "summary_fields = [\"testName\"]\n",
+        ),
+    );
+
+    // Both sessions end in `eyes-1`; the other one's settings must not leak in
+    let output = Command::new(bin())
+        .args([
+            "--config",
+            config.to_str().expect("utf8 path"),
+            "sessions",
+            "story",
+            "manager-1/eyes-1",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("testName: \"first\"") && !stdout.contains("\"second\""),
+        "unexpected story:\n{stdout}"
+    );
+}
+
+#[test]
+fn test_sessions_impact_ranks_suspects_of_failed_sessions() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("run.log");
+    write_file(
+        &file,
+        concat!(
+            "core-universal (manager-1/eyes-1) | 2026-01-01T00:00:00.000Z [INFO ] Command \"openEyes\" is called with settings {\"testName\":\"checkout\"}\n",
+            "core-universal (manager-1/eyes-2) | 2026-01-01T00:00:00.000Z [INFO ] Command \"openEyes\" is called with settings {\"testName\":\"login\"}\n",
+            "core-universal (manager-1/eyes-3) | 2026-01-01T00:00:00.000Z [INFO ] Command \"openEyes\" is called with settings {\"testName\":\"search\"}\n",
+            "core-universal (manager-1/eyes-1) | 2026-01-01T00:00:01.000Z [ERROR] Retrying request after timeout\n",
+            "core-universal (manager-1/eyes-3) | 2026-01-01T00:00:01.000Z [ERROR] Retrying request after timeout\n",
+            "core-universal (manager-1/eyes-1/check-1) | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--id1] will be sent to the address \"[POST]https://x/api\" with body {}\n",
+            "core-universal (manager-1/eyes-1/check-1) | 2026-01-01T00:00:05.000Z [INFO ] Request \"check\" [0--id1] finished successfully with body {}\n",
+            "core-universal (manager-1/eyes-1) | 2026-01-01T00:00:09.000Z [ERROR] Render failed for page 7\n",
+            "core-universal (manager-1/eyes-1/render-1) | 2026-01-01T00:00:09.500Z [INFO ] Request \"render\" [0--id2] will be sent to the address \"[POST]https://x/render\" with body {}\n",
+            "core-universal (manager-1/eyes-1) | 2026-01-01T00:00:10.000Z [INFO ] Command \"abort\" is called with settings {}\n",
+            "core-universal (manager-1/eyes-2/close-1) | 2026-01-01T00:00:10.000Z [INFO ] Command \"close\" is called with settings {}\n",
+            "core-universal (manager-1/eyes-2/close-1) | 2026-01-01T00:00:10.500Z [INFO ] Command \"close\" finished successfully with result [{}]\n",
+            "core-universal (manager-1/eyes-3/close-1) | 2026-01-01T00:00:10.000Z [INFO ] Command \"close\" is called with settings {}\n",
+            "core-universal (manager-1/eyes-3/close-1) | 2026-01-01T00:00:10.500Z [INFO ] Command \"close\" finished successfully with result [{}]\n",
+        ),
+    );
+
+    let output = command()
+        .args(["sessions", "impact", file.to_str().expect("utf8 path")])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("FAILED SESSION IMPACT: 1 of 3 test session(s) failed")
+            && stdout.contains("failed: no response to render; aborted via abort"),
+        "unexpected impact:\n{stdout}"
+    );
+
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "sessions",
+            "impact",
+            file.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("impact output should be JSON");
+    let failed = &json["session_impact"]["failed"];
+    assert_eq!(failed.as_array().map(Vec::len), Some(1));
+    assert_eq!(failed[0]["session"], "eyes-1");
+    assert_eq!(failed[0]["first_seen"], "2026-01-01T00:00:00.000Z");
+    assert_eq!(failed[0]["last_seen"], "2026-01-01T00:00:10.000Z");
+    let suspects = failed[0]["suspects"].as_array().expect("suspects");
+    let names: Vec<&str> = suspects
+        .iter()
+        .map(|suspect| suspect["name"].as_str().unwrap_or_default())
+        .collect();
+    // Only in the failed session and right before its end, then the slow check, then
+    // the retry error that a passing session shares
+    assert!(names[0].starts_with("Render failed"), "{names:?}");
+    assert_eq!(names[1], "Request check");
+    assert_eq!(suspects[1]["kind"], "slow_operation");
+    assert!(names[2].starts_with("Retrying request"), "{names:?}");
+    assert_eq!(suspects[2]["sessions_with"], 2);
+    assert_eq!(suspects[2]["failed_sessions_with"], 1);
+}
+
+#[test]
+fn test_report_merge_rolls_up_errors_and_perf_runs() {
+    let dir = tempdir().expect("temp dir");
+    let run_json = |command_name: &str, log: &str, name: &str| {
+        let file = dir.path().join(format!("{name}.log"));
+        let out = dir.path().join(format!("{name}.json"));
+        write_file(&file, log);
+        let output = command()
+            .args([
+                "-F",
+                "json",
+                "-o",
+                out.to_str().expect("utf8 path"),
+                command_name,
+                file.to_str().expect("utf8 path"),
+            ])
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        out
+    };
+    let error_line = |second: u32| {
+        format!(
+            "core | 2026-01-0{second}T00:00:00.000Z [ERROR] Render with id \"r-{second}\" failed due to an error - internal failure\n"
+        )
+    };
+    let request_pair = |id: &str, millis: u32| {
+        format!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--{id}] will be sent with body {{\"x\":1}}\n\
+             svc | 2026-01-01T00:00:0{}.{:03}Z [INFO ] Request \"foo\" [0--{id}] finished successfully with body {{\"statusCode\":200}}\n",
+            millis / 1000,
+            millis % 1000
+        )
+    };
+
+    let errors_a = run_json("errors", &(error_line(1) + &error_line(2)), "errors_a");
+    let errors_b = run_json("errors", &error_line(3), "errors_b");
+    let rollup = dir.path().join("rollup.json");
+    let output = command()
+        .args([
+            "-o",
+            rollup.to_str().expect("utf8 path"),
+            "report",
+            "merge",
+            errors_a.to_str().expect("utf8 path"),
+            errors_b.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("REPORT MERGE: 2 errors report(s)"),
+        "expected text roll-up on stdout, got:\n{stdout}"
+    );
+    let merged: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&rollup).expect("roll-up file"))
+            .expect("roll-up file is JSON");
+    let merged = &merged["report_merge"];
+    assert_eq!(merged["kind"], "errors");
+    assert_eq!(merged["summary"]["error_count"], 3);
+    assert_eq!(merged["items"][0]["count"], 3);
+    assert_eq!(merged["items"][0]["runs"], 2);
+    assert_eq!(
+        merged["items"][0]["last_timestamp"],
+        "2026-01-03T00:00:00+00:00"
+    );
+
+    let perf_a = run_json(
+        "perf",
+        &(request_pair("a1", 100) + &request_pair("a2", 200)),
+        "perf_a",
+    );
+    let perf_b = run_json("perf", &request_pair("b1", 4000), "perf_b");
+    let output = command()
+        .args([
+            "-F",
+            "json",
+            "report",
+            "merge",
+            perf_a.to_str().expect("utf8 path"),
+            perf_b.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let merged: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    let operation = &merged["report_merge"]["items"][0];
+    assert_eq!(operation["name"], "foo");
+    assert_eq!(operation["count"], 3);
+    assert_eq!(operation["runs"], 2);
+    assert_eq!(operation["min_duration_ms"], 100);
+    assert_eq!(operation["max_duration_ms"], 4000);
+    assert_eq!(operation["p99_duration_ms"], 4000);
+
+    let output = command()
+        .args([
+            "report",
+            "merge",
+            errors_a.to_str().expect("utf8 path"),
+            perf_a.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Cannot merge perf report"),
+        "expected mixed-kind error, got:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_contracts_report_and_missing_filter_flag_payloads_without_required_fields() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("contracts.log");
+    let config = dir.path().join("contracts.toml");
+    write_file(
+        &file,
+        concat!(
+            "core | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--a] will be sent with body {\"settings\":{\"name\":\"x\"}}\n",
+            "core | 2026-01-01T00:00:01.000Z [INFO ] Request \"check\" [0--b] will be sent with body {\"settings\":{}}\n",
+            "core | 2026-01-01T00:00:02.000Z [INFO ] Request \"check\" [0--b] finished successfully with body {\"statusCode\":200}\n",
+        ),
+    );
+    write_file(
+        &config,
+        concat!(
+            "[parser]\n",
+            "request_prefix = \"Request \\\"\"\n",
+            "request_send_markers = [\"will be sent\"]\n",
+            "request_receive_markers = [\"finished successfully\"]\n",
+            "request_payload_markers = [\"with body\"]\n",
+            "\n",
+            "[contracts.requests]\n",
+            "check = [\"settings.name\"]\n",
+        ),
+    );
+    let run = |args: &[&str]| {
+        let output = command()
+            .args(["--config", config.to_str().expect("utf8 path")])
+            .args(args)
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    let path = file.to_str().expect("utf8 path");
+
+    let stdout = run(&["contracts", path]);
+    assert!(
+        stdout.contains("CONTRACTS: 1 of 2 checked entries missing required fields"),
+        "expected contract summary, got:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("     1 missing  settings.name"),
+        "expected per-field count, got:\n{stdout}"
+    );
+
+    let report: serde_json::Value =
+        serde_json::from_str(&run(&["-F", "json", "contracts", path])).expect("valid JSON");
+    assert_eq!(
+        report["contracts"]["contracts"][0]["violations"][0]["line_number"],
+        2
+    );
+    assert_eq!(
+        report["contracts"]["contracts"][0]["violations"][0]["timestamp"],
+        "2026-01-01T00:00:01.000Z"
+    );
+
+    let violations = run(&["search", "--violations", path]);
+    assert!(
+        violations.contains("[0--b] will be sent"),
+        "got:\n{violations}"
+    );
+    assert!(!violations.contains("[0--a]"), "got:\n{violations}");
+
+    let missing = run(&["-f", "missing:settings.name", "search", path]);
+    assert!(missing.contains("[0--b] will be sent"), "got:\n{missing}");
+    assert!(
+        missing.contains("finished successfully"),
+        "missing: applies to every payload, got:\n{missing}"
+    );
+    assert!(!missing.contains("[0--a]"), "got:\n{missing}");
+}
+
+#[test]
+fn test_run_selection_targets_one_run_in_a_concatenated_log() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("runs.log");
+    let config = dir.path().join("runs.toml");
+    write_file(
+        &file,
+        concat!(
+            "core | 2026-01-01T00:00:00.000Z [INFO ] Core universal initialization\n",
+            "core | 2026-01-01T00:00:01.000Z [ERROR] first run failure\n",
+            "core | 2026-01-01T00:00:02.000Z [INFO ] Core universal initialization\n",
+            "core | 2026-01-01T00:00:03.000Z [ERROR] second run failure\n",
+            "core | 2026-01-01T02:00:00.000Z [ERROR] third run failure\n",
+        ),
+    );
+    write_file(
+        &config,
+        "[runs]\nstart_markers = [\"Core universal initialization\"]\n",
+    );
+    let run = |args: &[&str]| {
+        let output = command()
+            .args(["--config", config.to_str().expect("utf8 path")])
+            .args(args)
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    let path = file.to_str().expect("utf8 path");
+
+    let info = run(&["info", path]);
+    assert!(
+        info.contains("Note: 3 runs detected"),
+        "expected mixed-run note, got:\n{info}"
+    );
+    let split = run(&["info", "--split-runs", path]);
+    assert!(split.contains("RUNS: 3 detected"), "got:\n{split}");
+    assert!(split.contains("start of log"), "got:\n{split}");
+
+    let second = run(&["--run", "2", "search", "-f", "l:ERROR", path]);
+    assert!(second.contains("second run failure"), "got:\n{second}");
+    assert!(!second.contains("first run failure"), "got:\n{second}");
+    assert!(!second.contains("third run failure"), "got:\n{second}");
+}
+
+#[test]
+fn test_tags_filter_entries_and_roll_up_in_info() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("tags.log");
+    let config = dir.path().join("tags.toml");
+    let invalid = dir.path().join("invalid.toml");
+    write_file(
+        &file,
+        concat!(
+            "socket | 2026-01-01T00:00:00.000Z [INFO ] connected\n",
+            "socket | 2026-01-01T00:00:05.000Z [ERROR] disconnected\n",
+            "core | 2026-01-01T00:00:06.000Z [ERROR] render failed\n",
+        ),
+    );
+    write_file(
+        &config,
+        "[tags]\nnetwork = \"c:socket\"\nfailures = \"l:ERROR\"\n",
+    );
+    write_file(&invalid, "[tags]\nnearby = \"within:5s-of:t:a\"\n");
+    let run = |config: &std::path::Path, args: &[&str]| {
+        command()
+            .args(["--config", config.to_str().expect("utf8 path")])
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+    let path = file.to_str().expect("utf8 path");
+
+    let output = run(
+        &config,
+        &["search", "-f", "tag:network !tag:failures", path],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("connected"), "got:\n{stdout}");
+    assert!(!stdout.contains("disconnected"), "got:\n{stdout}");
+    assert!(!stdout.contains("render failed"), "got:\n{stdout}");
+
+    let output = run(&config, &["info", path]);
+    assert!(output.status.success());
+    let info = String::from_utf8_lossy(&output.stdout);
+    assert!(info.contains("TAGS:"), "expected tag rollup, got:\n{info}");
+    assert!(info.contains("network"), "got:\n{info}");
+
+    let output = run(&invalid, &["info", path]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("nearby"), "got:\n{stderr}");
+}
+
+#[test]
+fn test_perf_sets_aside_negative_durations_unless_included() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("skew.log");
+    write_file(
+        &file,
+        concat!(
+            "core | 2026-01-01T00:00:30.000Z [INFO ] Request \"check\" [0--a] will be sent\n",
+            "core | 2026-01-01T00:00:00.000Z [INFO ] Request \"check\" [0--a] finished successfully\n",
+            "core | 2026-01-01T00:00:31.000Z [INFO ] Request \"check\" [0--b] will be sent\n",
+            "core | 2026-01-01T00:00:32.000Z [INFO ] Request \"check\" [0--b] finished successfully\n",
+        ),
+    );
+    let run = |args: &[&str]| {
+        let output = command().args(args).output().expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    let path = file.to_str().expect("utf8 path");
+
+    let text = run(&["perf", path]);
+    assert!(
+        text.contains("Negative durations:         1 (1 clock skew), excluded from statistics"),
+        "got:\n{text}"
+    );
+
+    let report: serde_json::Value =
+        serde_json::from_str(&run(&["-F", "json", "perf", path])).expect("valid JSON");
+    assert_eq!(report["stats"][0]["count"], 1);
+    assert_eq!(report["stats"][0]["min_duration_ms"], 1000);
+    assert_eq!(report["suspect_durations"]["clock_skew"], 1);
+    assert_eq!(report["suspect_operations"][0]["suspect"], "clock_skew");
+
+    let included: serde_json::Value = serde_json::from_str(&run(&[
+        "-F",
+        "json",
+        "perf",
+        "--include-suspect-durations",
+        path,
+    ]))
+    .expect("valid JSON");
+    assert_eq!(included["stats"][0]["count"], 2);
+    assert_eq!(included["suspect_durations"]["included"], true);
+}
+
+#[test]
+fn test_errors_checkpoint_resumes_and_reports_days() {
+    let dir = tempdir().expect("temp dir");
+    let current = dir.path().join("app.log");
+    let rotated_in = dir.path().join("app-next.log");
+    let checkpoint = dir.path().join("errors.checkpoint");
+    write_file(
+        &current,
+        concat!(
+            "core | 2026-01-01T12:00:00.000Z [ERROR] db timeout\n",
+            "core | 2026-01-01T12:05:00.000Z [ERROR] db timeout\n",
+            "core | 2026-01-02T12:00:00.000Z [ERROR] render failed\n",
+        ),
+    );
+    let run = |args: &[&str]| {
+        command()
+            .current_dir(dir.path())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let output = run(&[
+        "errors",
+        "--daily",
+        "--checkpoint",
+        "errors.checkpoint",
+        "app.log",
+    ]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("DAY 2026-01-01: 2 errors, 0 warnings, 1 patterns (1 new)"),
+        "got:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("DAY 2026-01-02 (so far): 1 errors"),
+        "got:\n{stdout}"
+    );
+    assert!(checkpoint.exists());
+
+    // The log grows and a new file starts; only the new entries are read
+    fs::write(
+        &current,
+        concat!(
+            "core | 2026-01-01T12:00:00.000Z [ERROR] db timeout\n",
+            "core | 2026-01-01T12:05:00.000Z [ERROR] db timeout\n",
+            "core | 2026-01-02T12:00:00.000Z [ERROR] render failed\n",
+            "core | 2026-01-02T13:00:00.000Z [ERROR] render failed\n",
+        ),
+    )
+    .expect("append log");
+    write_file(
+        &rotated_in,
+        "core | 2026-01-03T12:00:00.000Z [ERROR] db timeout\n",
+    );
+    let output = run(&[
+        "-F",
+        "json",
+        "errors",
+        "--daily",
+        "--checkpoint",
+        "errors.checkpoint",
+        "app.log",
+        "app-next.log",
+    ]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Resumed from checkpoint: 3 entries already analyzed")
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(report["errors"]["summary"]["error_count"], 5);
+    let days = report["errors"]["days"].as_array().expect("days");
+    let counts: Vec<(&str, u64, bool)> = days
+        .iter()
+        .map(|day| {
+            (
+                day["date"].as_str().unwrap(),
+                day["error_count"].as_u64().unwrap(),
+                day["complete"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        counts,
+        [
+            ("2026-01-01", 2, true),
+            ("2026-01-02", 2, true),
+            ("2026-01-03", 1, false)
+        ]
+    );
+
+    let output = run(&[
+        "errors",
+        "--warn",
+        "--checkpoint",
+        "errors.checkpoint",
+        "app.log",
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("written with other options"));
+}
+
+#[test]
+fn test_summary_only_keeps_headers_and_drops_details() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("a.log");
+    let file2 = dir.path().join("b.log");
+    write_file(
+        &file1,
+        "svc | 2026-01-01T00:00:00.000Z [ERROR] Request \"foo\" [0--id1] failed\n\
+         svc | 2026-01-01T00:00:01.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":1}\n",
+    );
+    write_file(
+        &file2,
+        "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {\"x\":2}\n",
+    );
+
+    let output = command()
+        .args(["--summary-only", "errors"])
+        .arg(&file1)
+        .output()
+        .expect("command should run");
+    assert!(output.status.success());
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.starts_with("HEALTH:") && text.contains("Impact summary"));
+    assert!(!text.contains(" #1 "), "clusters should be hidden:\n{text}");
+
+    let output = command()
+        .args(["--summary-only", "-F", "json", "errors"])
+        .arg(&file1)
+        .output()
+        .expect("command should run");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(report["errors"]["summary"]["error_count"], 1);
+    assert_eq!(report["errors"]["clusters_total"], 1);
+    assert!(report["errors"].get("clusters").is_none());
+
+    let output = command()
+        .args(["--summary-only", "-F", "json", "compare"])
+        .arg(&file1)
+        .arg(&file2)
+        .output()
+        .expect("command should run");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let keys: Vec<&String> = report.as_object().expect("object").keys().collect();
+    assert_eq!(keys, ["summary"]);
+    assert_eq!(report["summary"]["shared_count"], 1);
+
+    let output = command()
+        .args(["--summary-only", "-v", "compare"])
+        .arg(&file1)
+        .arg(&file2)
+        .output()
+        .expect("command should run");
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        text.contains(
+            "1 unique log types in file 1 (source), 0 unique in file 2 (target), 1 shared"
+        )
+    );
+    assert!(!text.contains("LOGS UNIQUE TO FILE 1"), "{text}");
+
+    let output = command()
+        .args(["--summary-only", "-F", "json", "perf"])
+        .arg(&file1)
+        .output()
+        .expect("command should run");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(report["total_entries"], 2);
+    assert!(report.get("operations").is_none());
+
+    let output = command()
+        .args(["--summary-only", "-F", "json", "info"])
+        .arg(&file1)
+        .output()
+        .expect("command should run");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(report["info"]["summary"]["total_entries"], 2);
+    assert_eq!(report["info"]["summary"]["levels"]["ERROR"], 1);
+
+    let output = command()
+        .args(["--summary-only", "info"])
+        .arg(&file1)
+        .output()
+        .expect("command should run");
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("Total log entries: 2 across 1 component"));
+    assert!(!text.contains("LOG COMPONENTS"));
+}
+
+#[test]
+fn test_matrix_summarizes_run_directories_with_trends() {
+    let dir = tempdir().expect("temp dir");
+    for (run, finished_at, error) in [
+        ("2026-01-02", "00:00:03.000", true),
+        ("2026-01-01", "00:00:01.000", false),
+        ("2026-01-03", "00:00:02.000", false),
+    ] {
+        let run_dir = dir.path().join(run);
+        std::fs::create_dir(&run_dir).expect("run dir");
+        let mut log = format!(
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Request \"foo\" [0--id1] will be sent with body {{\"x\":1}}\n\
+             svc | 2026-01-01T{finished_at}Z [INFO ] Request \"foo\" [0--id1] finished successfully with body {{\"statusCode\":200}}\n"
+        );
+        if error {
+            log.push_str("svc | 2026-01-01T00:00:03.500Z [ERROR] upload failed\n");
+        }
+        write_file(&run_dir.join("run.log"), &log);
+    }
+    std::fs::create_dir(dir.path().join("empty")).expect("empty dir");
+
+    let output = command()
+        .args(["-F", "json", "matrix", "--request", "foo", "--last", "3"])
+        .arg(dir.path())
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let matrix = &report["matrix"];
+    let runs: Vec<&str> = matrix["runs"]
+        .as_array()
+        .expect("runs")
+        .iter()
+        .map(|run| run["run"].as_str().unwrap())
+        .collect();
+    assert_eq!(runs, ["2026-01-01", "2026-01-02", "2026-01-03"]);
+    assert_eq!(matrix["runs"][1]["errors"], 1);
+    assert_eq!(matrix["runs"][1]["latencies"][0]["p95_ms"], 3000);
+    assert_eq!(matrix["runs"][1]["duration_ms"], 3500);
+    assert_eq!(matrix["trends"]["errors"], "▁█▁");
+    assert_eq!(matrix["trends"]["p95 foo"], "▁█▅");
+
+    let output = command()
+        .args(["matrix", "--request", "foo", "--last", "2", "--csv"])
+        .arg(dir.path())
+        .output()
+        .expect("command should run");
+    let csv = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "run,files,entries,errors,sessions,orphaned_sessions,p95_ms foo,duration_ms",
+            "2026-01-02,1,3,1,0,0,3000,3500",
+            "2026-01-03,1,2,0,0,0,2000,2000",
+        ]
+    );
+
+    let output = command()
+        .args(["matrix", "--request", "foo"])
+        .arg(dir.path())
+        .output()
+        .expect("command should run");
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.starts_with("RUN MATRIX: 3 run(s)"));
+    assert!(text.contains("Trends (oldest → newest):"));
+}
+
+#[test]
+fn test_info_schema_cache_only_parses_new_and_changed_files() {
+    let dir = tempdir().expect("temp dir");
+    let cache = dir.path().join("schema-cache");
+    let request = |second: u32, id: u32, body: &str| {
+        format!(
+            "svc | 2026-01-01T00:00:{second:02}.000Z [INFO ] Request \"foo\" [0--id{id}] will be sent with body {body}\n"
+        )
+    };
+    let files: Vec<_> = (1..=3)
+        .map(|index| dir.path().join(format!("part{index}.log")))
+        .collect();
+    write_file(&files[0], &request(0, 1, "{\"x\":1}"));
+    write_file(&files[1], &request(1, 2, "{\"x\":2}"));
+
+    let run = |files: &[std::path::PathBuf]| {
+        let output = command()
+            .args(["info", "--json-schema", "--schema-cache"])
+            .arg(&cache)
+            .args(files)
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let first = run(&files[..2]);
+    assert!(
+        first.contains("foo (2 occurrences):") && first.contains("0 file(s) reused, 2 parsed"),
+        "expected both files parsed on the first run, got:\n{first}"
+    );
+
+    write_file(&files[2], &request(2, 3, "{\"x\":3,\"y\":true}"));
+    let second = run(&files);
+    assert!(
+        second.contains("foo (3 occurrences):")
+            && second.contains("y (boolean) (1/3)")
+            && second.contains("2 file(s) reused, 1 parsed"),
+        "expected only the new file parsed and its fields merged, got:\n{second}"
+    );
+    assert_eq!(
+        second.replace("2 file(s) reused, 1 parsed", ""),
+        run(&files).replace("3 file(s) reused, 0 parsed", ""),
+        "cached reports should render exactly like freshly parsed ones"
+    );
+
+    write_file(&files[0], &request(0, 1, "{\"z\":1}"));
+    let changed = run(&files);
+    assert!(
+        changed.contains("z (number) (1/3)") && changed.contains("2 file(s) reused, 1 parsed"),
+        "expected the edited file to invalidate its cache entry, got:\n{changed}"
+    );
+}
+
+#[test]
+fn test_multi_file_run_reports_per_file_status_on_stderr() {
+    let dir = tempdir().expect("temp dir");
+    let good = dir.path().join("good.log");
+    let malformed = dir.path().join("malformed.log");
+    let empty = dir.path().join("empty.log");
+    write_file(
+        &good,
+        concat!(
+            "not a log line\n",
+            "svc | 2026-01-01T00:00:00.000Z [INFO ] Started\n",
+            "svc | 2026-01-01T00:00:01.000Z [ERROR] Render failed\n",
+        ),
+    );
+    write_file(&malformed, "garbage\n\nmore garbage\n");
+    write_file(&empty, "");
+
+    let output = command()
+        .args(["-v", "errors"])
+        .args([&good, &malformed, &empty])
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("FILES (3)"), "stderr:\n{stderr}");
+    let row = |name: &str| {
+        stderr
+            .lines()
+            .find(|line| line.contains(name) && line.starts_with('|'))
+            .map(|line| {
+                line.split('|')
+                    .map(str::trim)
+                    .filter(|cell| !cell.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| panic!("no status row for {name} in:\n{stderr}"))
+    };
+    let good_row = row("good.log");
+    assert_eq!(
+        (good_row[1], good_row[2], good_row[4], good_row[5]),
+        ("2", "1", "1", "check")
+    );
+    let malformed_row = row("malformed.log");
+    assert_eq!((malformed_row[1], malformed_row[2]), ("0", "2"));
+    assert!(stderr.contains("malformed.log: no entries, 2 line(s) skipped"));
+    assert!(stderr.contains("empty.log: empty"));
+
+    let quiet = command()
+        .args(["errors"])
+        .args([&good, &malformed])
+        .output()
+        .expect("command should run");
+    assert!(
+        !String::from_utf8_lossy(&quiet.stderr).contains("FILES ("),
+        "the table is only printed on a terminal or with --verbose"
+    );
+}
+
+#[test]
+fn test_endpoint_filters_and_perf_host_rollup() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("requests.log");
+    let request = |second: u32, id: u32, method: &str, host: &str| {
+        format!(
+            "core-requests (manager-1/eyes-1) | 2026-01-01T00:00:{second:02}.000Z [INFO ] Request \"check\" [0--id{id}] will be sent to the address \"[{method}]https://{host}/api/check\" with body {{\"x\":{id}}}\n\
+             core-requests (manager-1/eyes-1) | 2026-01-01T00:00:{second:02}.500Z [INFO ] Request \"check\" [0--id{id}] finished successfully with body {{\"statusCode\":{}}}\n",
+            if id == 3 { 503 } else { 200 }
+        )
+    };
+    write_file(
+        &file,
+        &[
+            request(0, 1, "POST", "eyesapi-eu.example.com"),
+            request(1, 2, "GET", "eyesapi-eu.example.com"),
+            request(2, 3, "POST", "eyesapi-us.example.com"),
+            request(3, 4, "POST", "render.example.com"),
+        ]
+        .concat(),
+    );
+
+    let output = command()
+        .args(["-F", "json", "-f", "method:POST host:eyesapi-*", "search"])
+        .arg(&file)
+        .output()
+        .expect("command should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json output");
+    let entries = json["search"]["entries"].as_array().expect("entries array");
+    let lines: Vec<u64> = entries
+        .iter()
+        .map(|entry| entry["source_line_number"].as_u64().expect("line number"))
+        .collect();
+    assert_eq!(lines, vec![1, 5], "unexpected matches: {json}");
+    assert_eq!(entries[1]["endpoint"]["host"], "eyesapi-us.example.com");
+    assert_eq!(entries[1]["endpoint"]["path"], "/api/check");
+
+    let output = command()
+        .args(["perf"])
+        .arg(&file)
+        .output()
+        .expect("command should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("HOST ROLLUP"), "stdout:\n{stdout}");
+    let row = |host: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(host))
+            .map(|line| {
+                line.split('|')
+                    .map(str::trim)
+                    .filter(|cell| !cell.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| panic!("no row for {host}:\n{stdout}"))
+    };
+    assert_eq!(row("eyesapi-eu.example.com")[1], "2");
+    assert_eq!(row("eyesapi-us.example.com")[5], "1");
+    assert!(
+        stdout.contains("Endpoint: POST https://"),
+        "stdout:\n{stdout}"
+    );
+}
+
+#[test]
+fn test_heartbeat_rules_collapse_keepalives_to_one_per_window() {
+    let dir = tempdir().expect("temp dir");
+    let file = dir.path().join("keepalive.log");
+    let config = dir.path().join("heartbeats.toml");
+    let invalid = dir.path().join("invalid.toml");
+    let mut text: String = (0..12)
+        .map(|n| {
+            format!(
+                "socket | 2026-01-01T00:00:{:02}.000Z [WARN ] Heartbeat {n} missed, still waiting\n",
+                n * 5
+            )
+        })
+        .collect();
+    text.push_str("core | 2026-01-01T00:00:30.000Z [ERROR] render failed\n");
+    write_file(&file, &text);
+    write_file(
+        &config,
+        "[[heartbeats]]\nname = \"keepalive\"\nfilter = \"t:heartbeat\"\nwindow_ms = 30000\n",
+    );
+    write_file(
+        &invalid,
+        "[[heartbeats]]\nname = \"nearby\"\nfilter = \"!within:5s-of:t:a\"\n",
+    );
+    let run = |config: &std::path::Path, args: &[&str]| {
+        command()
+            .args(["--config", config.to_str().expect("utf8 path")])
+            .args(args)
+            .arg(file.to_str().expect("utf8 path"))
+            .output()
+            .expect("command should run")
+    };
+
+    let output = run(&config, &["-F", "json", "count"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("count output should be JSON");
+    // Heartbeats at 0s and 30s open a window each; the error is untouched
+    assert_eq!(json["count"]["total"], 3);
+    let warnings = json["warnings"].as_array().expect("warnings");
+    assert!(
+        warnings.iter().any(|warning| warning["code"] == "LA0008"
+            && warning["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("keepalive ×10"))),
+        "warnings: {warnings:?}"
+    );
+
+    let output = run(&config, &["errors"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Warning [LA0008]: suppressed 10 repeated heartbeat entries"),
+        "stderr: {stderr}"
+    );
+
+    let output = run(&invalid, &["count"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("nearby"), "got:\n{stderr}");
+}
+
+#[test]
+fn test_saved_comparison_renders_without_the_original_logs() {
+    let dir = tempdir().expect("temp dir");
+    let file1 = dir.path().join("before.log");
+    let file2 = dir.path().join("after.log");
+    let saved = dir.path().join("results.cmp");
+    write_file(
+        &file1,
+        concat!(
+            "core | 2026-01-01T00:00:00.000Z [INFO ] Render settings: {\"width\": 100, \"name\": \"<a>\"}\n",
+            "core | 2026-01-01T00:00:01.000Z [INFO ] Retired entry\n",
+        ),
+    );
+    write_file(
+        &file2,
+        "core | 2026-01-01T00:00:00.000Z [INFO ] Render settings: {\"width\": 120, \"name\": \"<a>\"}\n",
+    );
+    let path = |file: &Path| file.to_str().expect("utf8 path").to_string();
+    let run = |args: &[String]| {
+        let output = command().args(args).output().expect("command should run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let compared = run(&[
+        "-F".into(),
+        "json".into(),
+        "compare".into(),
+        path(&file1),
+        path(&file2),
+        "--save".into(),
+        path(&saved),
+    ]);
+    fs::remove_file(&file1).expect("remove log");
+    fs::remove_file(&file2).expect("remove log");
+
+    let rendered = run(&["-F".into(), "json".into(), "render".into(), path(&saved)]);
+    let compared: serde_json::Value = serde_json::from_str(&compared).expect("compare JSON");
+    let rendered: serde_json::Value = serde_json::from_str(&rendered).expect("render JSON");
+    assert_eq!(rendered["comparisons"], compared["comparisons"]);
+    assert_eq!(rendered["summary"], compared["summary"]);
+
+    let markdown = run(&[
+        "render".into(),
+        path(&saved),
+        "--to".into(),
+        "markdown".into(),
+    ]);
+    assert!(
+        markdown.starts_with("# Log comparison: ") && markdown.contains("## SHARED LOGS"),
+        "markdown:\n{markdown}"
+    );
+    assert!(markdown.contains("width"), "markdown:\n{markdown}");
+
+    let html = run(&["render".into(), path(&saved), "--to".into(), "html".into()]);
+    assert!(html.starts_with("<!DOCTYPE html>"), "html:\n{html}");
+    assert!(
+        html.contains("width") && !html.contains("<a>"),
+        "html:\n{html}"
+    );
+
+    let text = run(&["render".into(), path(&saved)]);
+    let json = run(&["render".into(), path(&saved), "--to".into(), "json".into()]);
+    assert!(json.trim_start().starts_with('{'), "json:\n{json}");
+    assert!(
+        text.contains("SHARED LOGS WITH DIFFERENCES"),
+        "text:\n{text}"
+    );
+
+    let output = command()
+        .args(["-F", "html", "info", &path(&saved)])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
+
+    let output = command()
+        .args(["render", dir.path().to_str().expect("utf8 path")])
+        .output()
+        .expect("command should run");
+    assert!(!output.status.success());
 }
@@ -1,6 +1,9 @@
 use chrono::{DateTime, Local};
 use log_analyzer::SortOrder;
-use log_analyzer::comparator::{ComparisonOptions, LogFilter, compare_logs};
+use log_analyzer::comparator::{
+    ComparisonOptions, LogFilter, SchemaChange, compare_logs, compare_spilled_groups,
+    generate_json_output, most_interesting_differences, spill_log_groups,
+};
 use log_analyzer::parser::{LogEntry, LogEntryKind, RequestDirection};
 use serde_json::json;
 use std::collections::HashMap;
@@ -35,6 +38,7 @@ fn request_log(
             payload: Some(payload),
         },
         source_line_number: line,
+        tags: Vec::new(),
     }
 }
 
@@ -184,3 +188,648 @@ fn test_compare_reports_unpaired_entries_as_unique() {
     );
     assert_eq!(results.unique_to_log2.len(), 0);
 }
+
+#[test]
+fn test_spilled_comparison_matches_in_memory_comparison() {
+    let logs1 = vec![
+        request_log("z", "2026-01-01T00:00:00.000Z", 1, "0--z1", json!({"x": 1})),
+        request_log("a", "2026-01-01T00:00:10.000Z", 2, "0--a1", json!({"x": 1})),
+        request_log("a", "2026-01-01T00:00:11.000Z", 3, "0--a2", json!({"x": 5})),
+        request_log("only1", "2026-01-01T00:00:12.000Z", 4, "0--o", json!({})),
+    ];
+    let logs2 = vec![
+        request_log("z", "2026-01-01T00:00:00.100Z", 1, "0--z1", json!({"x": 2})),
+        request_log("a", "2026-01-01T00:00:10.100Z", 2, "0--a1", json!({"x": 1})),
+        request_log("only2", "2026-01-01T00:00:12.000Z", 3, "0--o", json!({})),
+    ];
+    let options = ComparisonOptions::new().sort_by(SortOrder::Time);
+    let filter = LogFilter::new();
+
+    let in_memory = compare_logs(&logs1, &logs2, &filter, &options).expect("comparison");
+
+    // A chunk budget below one entry writes a sorted run per entry, so the runs are merged
+    let groups1 = spill_log_groups(logs1, &filter, 1).expect("spill log 1");
+    let groups2 = spill_log_groups(logs2, &filter, 1).expect("spill log 2");
+    assert_eq!(groups1.entry_count(), 4);
+    let spilled = compare_spilled_groups(&groups1, &groups2, &options).expect("spilled compare");
+
+    let summarize = |results: &log_analyzer::comparator::ComparisonResults| {
+        results
+            .shared_comparisons
+            .iter()
+            .map(|c| {
+                (
+                    c.key.clone(),
+                    c.log1_line_number,
+                    c.log2_line_number,
+                    c.json_differences.len(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(summarize(&spilled), summarize(&in_memory));
+
+    let mut unique1_mem = in_memory.unique_to_log1.clone();
+    let mut unique1_spill = spilled.unique_to_log1.clone();
+    unique1_mem.sort();
+    unique1_spill.sort();
+    assert_eq!(unique1_spill, unique1_mem);
+    assert_eq!(spilled.unique_to_log2.len(), in_memory.unique_to_log2.len());
+}
+
+#[test]
+fn test_spilled_time_order_uses_first_entry_per_key() {
+    // Key `a` starts later than `b` but has an out-of-order earlier entry after that
+    let logs1 = vec![
+        request_log("a", "2026-01-01T00:00:20.000Z", 1, "0--a1", json!({"x": 1})),
+        request_log("b", "2026-01-01T00:00:10.000Z", 2, "0--b1", json!({"x": 1})),
+        request_log("a", "2026-01-01T00:00:05.000Z", 3, "0--a2", json!({"x": 1})),
+    ];
+    let logs2 = vec![
+        request_log("a", "2026-01-01T00:00:20.000Z", 1, "0--a1", json!({"x": 2})),
+        request_log("b", "2026-01-01T00:00:10.000Z", 2, "0--b1", json!({"x": 2})),
+        request_log("a", "2026-01-01T00:00:05.000Z", 3, "0--a2", json!({"x": 2})),
+    ];
+    let options = ComparisonOptions::new().sort_by(SortOrder::Time);
+    let filter = LogFilter::new();
+    let keys = |results: &log_analyzer::comparator::ComparisonResults| {
+        results
+            .shared_comparisons
+            .iter()
+            .map(|c| c.key.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let in_memory = compare_logs(&logs1, &logs2, &filter, &options).expect("comparison");
+    let groups1 = spill_log_groups(logs1, &filter, 1).expect("spill log 1");
+    let groups2 = spill_log_groups(logs2, &filter, 1).expect("spill log 2");
+    let spilled = compare_spilled_groups(&groups1, &groups2, &options).expect("spilled compare");
+    assert_eq!(keys(&spilled), keys(&in_memory));
+    assert!(keys(&in_memory)[0].starts_with("b|"));
+}
+
+#[test]
+fn test_only_key_and_skip_key_scope_grouped_keys() {
+    let logs1 = vec![
+        request_log(
+            "svc",
+            "2026-01-01T00:00:00.000Z",
+            1,
+            "0--s1",
+            json!({"x": 1}),
+        ),
+        request_log(
+            "db",
+            "2026-01-01T00:00:01.000Z",
+            2,
+            "0--d1",
+            json!({"x": 1}),
+        ),
+        request_log("only1", "2026-01-01T00:00:02.000Z", 3, "0--o", json!({})),
+    ];
+    let logs2 = vec![
+        request_log(
+            "svc",
+            "2026-01-01T00:00:00.000Z",
+            1,
+            "0--s1",
+            json!({"x": 2}),
+        ),
+        request_log(
+            "db",
+            "2026-01-01T00:00:01.000Z",
+            2,
+            "0--d1",
+            json!({"x": 2}),
+        ),
+    ];
+    let filter = LogFilter::new();
+    let shared_keys = |results: &log_analyzer::comparator::ComparisonResults| {
+        results
+            .shared_comparisons
+            .iter()
+            .map(|c| c.key.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let only = ComparisonOptions::new().only_keys(vec!["svc|*|Request|*foo*".to_string()]);
+    let results = compare_logs(&logs1, &logs2, &filter, &only).expect("comparison");
+    assert_eq!(shared_keys(&results), vec!["svc|INFO|Request|Send `foo`:"]);
+    assert!(results.unique_to_log1.is_empty());
+
+    let skip = ComparisonOptions::new().skip_keys(vec!["svc|*".to_string(), "only?|*".to_string()]);
+    let results = compare_logs(&logs1, &logs2, &filter, &skip).expect("comparison");
+    assert_eq!(shared_keys(&results), vec!["db|INFO|Request|Send `foo`:"]);
+    assert!(results.unique_to_log1.is_empty());
+
+    let groups1 = spill_log_groups(logs1, &filter, u64::MAX).expect("spill log 1");
+    let groups2 = spill_log_groups(logs2, &filter, u64::MAX).expect("spill log 2");
+    let spilled = compare_spilled_groups(&groups1, &groups2, &skip).expect("spilled compare");
+    assert_eq!(shared_keys(&spilled), vec!["db|INFO|Request|Send `foo`:"]);
+    assert!(spilled.unique_to_log1.is_empty());
+}
+
+#[test]
+fn test_numeric_differences_report_absolute_and_percent_delta() {
+    let logs1 = vec![request_log(
+        "svc",
+        "2026-01-01T00:00:00.000Z",
+        1,
+        "0--a",
+        json!({"width": 400, "ratio": 0, "name": "a"}),
+    )];
+    let logs2 = vec![request_log(
+        "svc",
+        "2026-01-01T00:00:00.000Z",
+        1,
+        "0--b",
+        json!({"width": 1000, "ratio": 0.5, "name": "b"}),
+    )];
+
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &ComparisonOptions::new())
+        .expect("comparison");
+    let diffs = &results.shared_comparisons[0].json_differences;
+    let label = |path: &str| {
+        diffs
+            .iter()
+            .find(|diff| diff.path == path)
+            .and_then(|diff| diff.numeric_delta())
+            .map(|delta| delta.label())
+    };
+
+    assert_eq!(label("width").as_deref(), Some("+600 (+150%)"));
+    assert_eq!(label("ratio").as_deref(), Some("+0.5"));
+    assert_eq!(label("name"), None);
+}
+
+#[test]
+fn test_most_interesting_differences_ranks_rare_paths_first() {
+    let ts = "2026-01-01T00:00:00.000Z";
+    let logs1: Vec<LogEntry> = (0..4)
+        .map(|i| request_log("svc", ts, i + 1, "0--a", json!({"x": i, "status": "ok"})))
+        .collect();
+    let logs2: Vec<LogEntry> = (0..4)
+        .map(|i| {
+            let status = if i == 2 { "failed" } else { "ok" };
+            request_log(
+                "svc",
+                ts,
+                i + 1,
+                "0--b",
+                json!({"x": i + 10, "status": status}),
+            )
+        })
+        .collect();
+
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &ComparisonOptions::new())
+        .expect("comparison");
+    let interesting = most_interesting_differences(&results, 10);
+
+    assert_eq!(
+        interesting.len(),
+        1,
+        "systematic `x` changes are not interesting"
+    );
+    assert_eq!(interesting[0].path, "status");
+    assert_eq!(interesting[0].path_frequency, 1);
+    assert_eq!(interesting[0].log1_line_number, 3);
+    assert!(interesting[0].rarity > 0.0);
+    assert!(most_interesting_differences(&results, 0).is_empty());
+}
+
+#[test]
+fn test_cluster_payloads_compares_shape_representatives() {
+    let logs1 = vec![
+        request_log(
+            "svc",
+            "2026-01-01T00:00:00.000Z",
+            1,
+            "0--a1",
+            json!({"x": 1}),
+        ),
+        request_log(
+            "svc",
+            "2026-01-01T00:00:01.000Z",
+            2,
+            "0--a2",
+            json!({"x": 2}),
+        ),
+        request_log(
+            "svc",
+            "2026-01-01T00:00:02.000Z",
+            3,
+            "0--a3",
+            json!({"x": 3, "y": "extra"}),
+        ),
+    ];
+    let logs2 = vec![request_log(
+        "svc",
+        "2026-01-01T00:00:00.000Z",
+        1,
+        "0--b1",
+        json!({"x": 5}),
+    )];
+    let filter = LogFilter::new();
+
+    let options = ComparisonOptions::new().cluster_payloads(true);
+    let results = compare_logs(&logs1, &logs2, &filter, &options).expect("comparison");
+    assert_eq!(results.shared_comparisons.len(), 1);
+    let comparison = &results.shared_comparisons[0];
+    assert_eq!(comparison.cluster_sizes, Some((2, 1)));
+    assert_eq!(comparison.log1_line_number, 1);
+    assert_eq!(
+        results.unique_to_log1,
+        vec!["svc|INFO|Request|Send `foo`: [payload cluster of 1 at line 3]"]
+    );
+    assert!(results.unique_to_log2.is_empty());
+
+    let results =
+        compare_logs(&logs1, &logs2, &filter, &ComparisonOptions::new()).expect("comparison");
+    assert_eq!(results.shared_comparisons[0].cluster_sizes, None);
+    assert_eq!(results.unique_to_log1.len(), 2);
+}
+
+#[test]
+fn test_cluster_payloads_groups_similar_shapes() {
+    let payload = json!({"a": 1, "b": "s", "c": true, "d": null});
+    let mut with_optional = payload.clone();
+    with_optional["e"] = json!(2);
+    let logs1 = vec![
+        request_log(
+            "svc",
+            "2026-01-01T00:00:00.000Z",
+            1,
+            "0--a1",
+            payload.clone(),
+        ),
+        request_log("svc", "2026-01-01T00:00:01.000Z", 2, "0--a2", with_optional),
+    ];
+    let logs2 = vec![request_log(
+        "svc",
+        "2026-01-01T00:00:00.000Z",
+        1,
+        "0--b1",
+        payload,
+    )];
+
+    // An optional field leaves the shapes 5/6 similar, above the clustering threshold
+    let options = ComparisonOptions::new().cluster_payloads(true);
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &options).expect("comparison");
+    assert_eq!(results.shared_comparisons.len(), 1);
+    assert_eq!(results.shared_comparisons[0].cluster_sizes, Some((2, 1)));
+    assert!(results.unique_to_log1.is_empty());
+    assert!(results.unique_to_log2.is_empty());
+}
+
+#[test]
+fn test_level_drift_flags_component_with_shifted_level_mix() {
+    let log_at = |component: &str, level: &str, line: usize| {
+        let mut log = request_log(
+            component,
+            "2026-01-01T00:00:00.000Z",
+            line,
+            &format!("0--id-{line}"),
+            json!({"x": 1}),
+        );
+        log.level = level.to_string();
+        log
+    };
+
+    let mut logs1: Vec<LogEntry> = (0..10).map(|i| log_at("svc", "INFO", i)).collect();
+    logs1.extend((10..20).map(|i| log_at("api", "INFO", i)));
+    logs1.push(log_at("svc", "WARN", 20));
+
+    let mut logs2: Vec<LogEntry> = (0..10).map(|i| log_at("svc", "INFO", i)).collect();
+    logs2.extend((10..20).map(|i| log_at("api", "INFO", i)));
+    logs2.extend((20..30).map(|i| log_at("svc", "WARN", i)));
+
+    let options = ComparisonOptions::new().level_drift_threshold(10.0);
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &options).expect("compare");
+
+    assert_eq!(results.level_drift.len(), 1);
+    let drift = &results.level_drift[0];
+    assert_eq!(drift.component, "svc");
+    assert_eq!((drift.total1, drift.total2), (11, 20));
+    let warn = drift.largest_shift().expect("shifted level");
+    assert_eq!((warn.count1, warn.count2), (1, 10));
+    assert!((warn.shift().abs() - (50.0 - 100.0 / 11.0)).abs() < 1e-9);
+
+    let disabled = ComparisonOptions::new();
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &disabled).expect("compare");
+    assert!(results.level_drift.is_empty());
+}
+
+#[test]
+fn test_ignore_case_and_whitespace_suppress_trivial_text_differences() {
+    let logs1 = vec![request_log(
+        "svc",
+        "2026-01-01T00:00:00.000Z",
+        1,
+        "0--id-a",
+        json!({"title": "Hello  World", "status": "OK", "count": 1}),
+    )];
+    let mut logs2 = vec![request_log(
+        "svc",
+        "2026-01-01T00:00:00.000Z",
+        1,
+        "0--id-a",
+        json!({"title": "hello world", "status": "ok", "count": 2}),
+    )];
+    logs2[0].message = logs1[0].message.to_uppercase();
+
+    let strict = compare_logs(&logs1, &logs2, &LogFilter::new(), &ComparisonOptions::new())
+        .expect("comparison succeeds");
+    assert_eq!(strict.shared_comparisons[0].json_differences.len(), 3);
+    assert_eq!(strict.trivial_differences, 0);
+
+    let case_only = ComparisonOptions::new().ignore_case(true);
+    let results =
+        compare_logs(&logs1, &logs2, &LogFilter::new(), &case_only).expect("comparison succeeds");
+    let paths: Vec<&str> = results.shared_comparisons[0]
+        .json_differences
+        .iter()
+        .map(|diff| diff.path.as_str())
+        .collect();
+    assert_eq!(paths, ["count", "title"]);
+    assert!(results.shared_comparisons[0].text1.is_none());
+    // `status` plus the upper-cased message
+    assert_eq!(results.trivial_differences, 2);
+
+    let both = case_only.ignore_whitespace(true);
+    let results =
+        compare_logs(&logs1, &logs2, &LogFilter::new(), &both).expect("comparison succeeds");
+    assert_eq!(results.shared_comparisons[0].json_differences.len(), 1);
+    assert_eq!(results.trivial_differences, 3);
+}
+
+#[test]
+fn test_compact_json_legend_names_every_abbreviated_key() {
+    fn check(value: &serde_json::Value, scope: &str, legend: &serde_json::Value) {
+        match value {
+            serde_json::Value::Object(object) => {
+                let names = legend.as_object().and_then(|scopes| {
+                    scopes
+                        .iter()
+                        .find(|(listed, _)| listed.split(", ").any(|listed| listed == scope))
+                        .map(|(_, names)| names)
+                });
+                for (key, child) in object {
+                    if let Some(names) = names {
+                        assert!(
+                            names.get(key).is_some(),
+                            "legend scope '{scope}' is missing '{key}'"
+                        );
+                    }
+                    if scope.is_empty() && key == "legend" || matches!(key.as_str(), "v1" | "v2") {
+                        continue;
+                    }
+                    let child_scope = if scope.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{scope}.{key}")
+                    };
+                    check(child, &child_scope, legend);
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    check(item, &format!("{scope}[]"), legend);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let logs1 = vec![request_log(
+        "svc",
+        "2026-01-01T00:00:00.000Z",
+        1,
+        "0--id-a1",
+        json!({"x": 1, "y": "a"}),
+    )];
+    let logs2 = vec![request_log(
+        "svc",
+        "2026-01-01T00:00:02.000Z",
+        1,
+        "0--id-b1",
+        json!({"x": 3, "y": "b"}),
+    )];
+    let options = ComparisonOptions::new()
+        .compact_mode(true)
+        .include_legend(true);
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &options)
+        .expect("comparison should succeed");
+
+    let output: serde_json::Value =
+        serde_json::from_str(&generate_json_output(&results, &options)).expect("valid JSON");
+    assert_eq!(output["schema_version"], 2);
+    assert_eq!(output["legend"][""]["dc"], serde_json::Value::Null);
+    assert_eq!(output["legend"]["s"]["dc"], "differences_count");
+    check(&output, "", &output["legend"]);
+
+    let without = ComparisonOptions::new().compact_mode(true);
+    let output: serde_json::Value =
+        serde_json::from_str(&generate_json_output(&results, &without)).expect("valid JSON");
+    assert!(output.get("legend").is_none());
+    assert_eq!(output["schema_version"], 2);
+}
+
+#[test]
+fn test_path_grouped_values_carry_their_instance_provenance() {
+    let logs1 = vec![
+        request_log(
+            "svc",
+            "2026-01-01T00:00:00.000Z",
+            10,
+            "0--id-a1",
+            json!({"x": 1, "y": "same"}),
+        ),
+        request_log(
+            "svc",
+            "2026-01-01T00:00:01.000Z",
+            20,
+            "0--id-a2",
+            json!({"x": 2, "y": "a"}),
+        ),
+    ];
+    let logs2 = vec![
+        request_log(
+            "svc",
+            "2026-01-01T00:00:05.000Z",
+            30,
+            "0--id-b1",
+            json!({"x": 1, "y": "same"}),
+        ),
+        request_log(
+            "svc",
+            "2026-01-01T00:00:06.000Z",
+            40,
+            "0--id-b2",
+            json!({"x": 5, "y": "b"}),
+        ),
+    ];
+    let readable = ComparisonOptions::new().readable_mode(true);
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &readable)
+        .expect("comparison should succeed");
+
+    let output: serde_json::Value =
+        serde_json::from_str(&generate_json_output(&results, &readable)).expect("valid JSON");
+    let group = &output["comparisons"][0];
+    let x = &group["differences"][0];
+    assert_eq!(x["path"], "x");
+    // Only the second instance differs at `x`; its values name that instance
+    assert_eq!(x["values"].as_array().map(Vec::len), Some(1));
+    let value = &x["values"][0];
+    assert_eq!(value["instance"], 1);
+    assert_eq!(group["instances"][1]["log1_line"], 20);
+    assert_eq!(value["value1"], 2);
+    assert_eq!(value["value2"], 5);
+    assert_eq!(value["log1_line"], 20);
+    assert_eq!(value["log2_line"], 40);
+    assert_eq!(value["log1_timestamp"], "2026-01-01T00:00:01.000Z");
+    assert_eq!(value["log2_timestamp"], "2026-01-01T00:00:06.000Z");
+    assert_eq!(value["delta"]["absolute"], 3.0);
+    assert!(x.get("value1").is_none() && x.get("indexes").is_none());
+
+    let compact = ComparisonOptions::new().compact_mode(true);
+    let output: serde_json::Value =
+        serde_json::from_str(&generate_json_output(&results, &compact)).expect("valid JSON");
+    let value = &output["c"][0]["d"][0]["vs"][0];
+    assert_eq!(value["n"], 1);
+    assert_eq!(value["ln"], json!([20, 40]));
+    assert_eq!(value["v2"], 5);
+}
+
+#[test]
+fn test_fields_absent_from_one_files_schema_are_schema_changes() {
+    let logs1 = vec![
+        request_log(
+            "svc",
+            "2026-01-01T00:00:00.000Z",
+            1,
+            "0--a1",
+            json!({"x": 1, "legacy": true}),
+        ),
+        request_log(
+            "svc",
+            "2026-01-01T00:00:01.000Z",
+            2,
+            "0--a2",
+            json!({"x": 2, "opt": "a"}),
+        ),
+    ];
+    let logs2 = vec![
+        request_log(
+            "svc",
+            "2026-01-01T00:00:00.000Z",
+            1,
+            "0--b1",
+            json!({"x": 1, "sdk": {"v": 2}, "opt": "z"}),
+        ),
+        request_log(
+            "svc",
+            "2026-01-01T00:00:01.000Z",
+            2,
+            "0--b2",
+            json!({"x": 3}),
+        ),
+    ];
+
+    let options = ComparisonOptions::new().readable_mode(true);
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &options).expect("comparison");
+    let mut annotations: Vec<(&str, Option<SchemaChange>)> = results
+        .shared_comparisons
+        .iter()
+        .flat_map(|comparison| &comparison.json_differences)
+        .map(|diff| (diff.path.as_str(), diff.schema_change))
+        .collect();
+    annotations.sort_by_key(|(path, _)| *path);
+    assert_eq!(
+        annotations,
+        [
+            ("legacy", Some(SchemaChange::FieldRemoved)),
+            ("opt", None),
+            ("opt", None),
+            ("sdk", Some(SchemaChange::FieldAdded)),
+            ("x", None),
+        ]
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&generate_json_output(&results, &options)).expect("valid JSON");
+    assert_eq!(json["summary"]["schema_changes_count"], 2);
+    let differences = json["comparisons"][0]["differences"]
+        .as_array()
+        .expect("differences");
+    let sdk = differences
+        .iter()
+        .find(|diff| diff["path"] == "sdk")
+        .expect("sdk difference");
+    assert_eq!(sdk["schema_change"], "field_added");
+}
+
+#[test]
+fn test_readable_json_orders_keys_paths_and_instances_deterministically() {
+    let logs1: Vec<LogEntry> = ["svc-d", "svc-c", "svc-b", "svc-a", "only-2", "only-1"]
+        .iter()
+        .enumerate()
+        .map(|(idx, component)| {
+            request_log(
+                component,
+                "2026-01-01T00:00:00.000Z",
+                idx + 1,
+                &format!("0--a{idx}"),
+                json!({"z": 1, "m": 1, "a": 1}),
+            )
+        })
+        .collect();
+    let logs2: Vec<LogEntry> = ["svc-b", "svc-d", "svc-a", "svc-c", "only-4", "only-3"]
+        .iter()
+        .enumerate()
+        .map(|(idx, component)| {
+            request_log(
+                component,
+                "2026-01-01T00:00:00.000Z",
+                idx + 1,
+                &format!("0--b{idx}"),
+                json!({"z": 2, "m": 2, "a": 2}),
+            )
+        })
+        .collect();
+
+    let options = ComparisonOptions::new()
+        .readable_mode(true)
+        .sort_by(SortOrder::DiffCount);
+    let results = compare_logs(&logs1, &logs2, &LogFilter::new(), &options).expect("comparison");
+    let json: serde_json::Value =
+        serde_json::from_str(&generate_json_output(&results, &options)).expect("valid JSON");
+
+    let strings = |values: &serde_json::Value, field: &str| -> Vec<String> {
+        values
+            .as_array()
+            .expect("array")
+            .iter()
+            .map(|value| value[field].as_str().expect("string").to_string())
+            .collect()
+    };
+    let sorted = |mut values: Vec<String>| {
+        values.sort();
+        values
+    };
+
+    let unique1 = strings(&json["unique_to_log1"], "raw_key");
+    assert_eq!(unique1, sorted(unique1.clone()));
+    assert!(unique1[0].starts_with("only-1"), "{unique1:?}");
+    let unique2 = strings(&json["unique_to_log2"], "raw_key");
+    assert_eq!(unique2, sorted(unique2.clone()));
+
+    let comparisons = json["comparisons"].as_array().expect("comparisons");
+    let components: Vec<&str> = comparisons
+        .iter()
+        .map(|group| group["key"]["component"].as_str().expect("component"))
+        .collect();
+    assert_eq!(components, ["svc-a", "svc-b", "svc-c", "svc-d"]);
+    for group in comparisons {
+        assert_eq!(strings(&group["differences"], "path"), ["a", "m", "z"]);
+    }
+}
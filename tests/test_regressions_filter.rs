@@ -21,6 +21,7 @@ fn generic_log(component: &str) -> LogEntry {
         module_path: None,
         kind: LogEntryKind::Generic { payload: None },
         source_line_number: 1,
+        tags: Vec::new(),
     }
 }
 
@@ -42,6 +43,7 @@ fn request_log(direction: RequestDirection) -> LogEntry {
             payload: Some(serde_json::json!({"x": 1})),
         },
         source_line_number: 1,
+        tags: Vec::new(),
     }
 }
 